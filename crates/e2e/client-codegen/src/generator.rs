@@ -0,0 +1,530 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    Config,
+    Error,
+};
+use ink_metadata::{
+    InkProject,
+    MessageParamSpec,
+    ReturnTypeSpec,
+    Selector,
+};
+use scale_info::{
+    form::PortableForm,
+    PortableRegistry,
+    Type,
+    TypeDef,
+    TypeDefPrimitive,
+};
+use std::fmt::Write as _;
+
+/// The paths of the types whose path in the metadata is not their public path.
+const BUILTIN_SUBSTITUTES: &[(&str, &str)] = &[
+    ("Option", "::core::option::Option"),
+    ("Result", "::core::result::Result"),
+    ("BTreeMap", "::std::collections::BTreeMap"),
+    ("BTreeSet", "::std::collections::BTreeSet"),
+    (
+        "ink_primitives::types::AccountId",
+        "::ink::primitives::AccountId",
+    ),
+    ("ink_primitives::types::Hash", "::ink::primitives::Hash"),
+    ("ink_primitives::LangError", "::ink::LangError"),
+];
+
+/// The type of a message or constructor argument.
+struct Arg {
+    ident: String,
+    ty: String,
+}
+
+/// The items generated for a message or constructor.
+struct Callable<'a> {
+    /// The identifier of the method of the wrapper.
+    method: String,
+    /// The identifier of the type of the encoded call.
+    ty: String,
+    args: Vec<Arg>,
+    return_type: String,
+    selector: &'a Selector,
+    docs: &'a [String],
+}
+
+/// Generates the Rust client for the types of a single registry.
+pub struct RustGenerator<'a> {
+    registry: &'a PortableRegistry,
+    config: &'a Config,
+}
+
+impl<'a> RustGenerator<'a> {
+    /// Creates a new generator.
+    pub fn new(registry: &'a PortableRegistry, config: &'a Config) -> Self {
+        Self { registry, config }
+    }
+
+    /// Generates the client for the whole contract.
+    pub fn generate(
+        &self,
+        project: &'a InkProject,
+        contract_name: &str,
+    ) -> Result<String, Error> {
+        let spec = project.spec();
+        let name = pascal_case(contract_name);
+        let bundle_path = self
+            .config
+            .bundle_path
+            .clone()
+            .unwrap_or_else(|| format!("target/ink/{}.contract", contract_name));
+        let constructors = spec
+            .constructors()
+            .iter()
+            .map(|constructor| {
+                self.callable(
+                    constructor.label(),
+                    constructor.args(),
+                    constructor.return_type(),
+                    constructor.selector(),
+                    constructor.docs(),
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let messages = spec
+            .messages()
+            .iter()
+            .map(|message| {
+                self.callable(
+                    message.label(),
+                    message.args(),
+                    message.return_type(),
+                    message.selector(),
+                    message.docs(),
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut out = String::new();
+        writeln!(
+            out,
+            "// This file was generated from the metadata of the `{}` ink! smart contract.",
+            contract_name
+        )
+        .unwrap();
+        out.push_str("// Do not edit it manually.\n\n");
+        out.push_str("/// The configuration of the chain the contract is deployed on.\n");
+        writeln!(
+            out,
+            "pub type ChainConfig = {};\n",
+            self.config.chain_config
+        )
+        .unwrap();
+        out.push_str("/// The environment of the contract.\n");
+        writeln!(out, "pub type Environment = {};\n", self.config.environment).unwrap();
+        out.push_str("/// The client submitting the calls to the chain.\n");
+        out.push_str(
+            "pub type Client = ::ink_e2e::Client<ChainConfig, Environment>;\n\n",
+        );
+        out.push_str("/// The account ID of the chain.\n");
+        out.push_str(
+            "pub type AccountId = <ChainConfig as ::ink_e2e::subxt::Config>::AccountId;\n\n",
+        );
+
+        out.push_str("/// The encoded calls of the constructors of the contract.\n");
+        out.push_str("pub mod constructors {\n");
+        for constructor in &constructors {
+            write_call_type(&mut out, constructor, "InkConstructor", &bundle_path);
+        }
+        out.push_str("}\n\n");
+
+        out.push_str("/// The encoded calls of the messages of the contract.\n");
+        out.push_str("pub mod messages {\n");
+        for message in &messages {
+            write_call_type(&mut out, message, "InkMessage", &bundle_path);
+        }
+        out.push_str("}\n\n");
+
+        write_docs(&mut out, "", spec.docs());
+        writeln!(out, "pub struct {}<'a> {{", name).unwrap();
+        out.push_str("    client: &'a mut Client,\n");
+        out.push_str("    account_id: AccountId,\n");
+        out.push_str("}\n\n");
+        writeln!(out, "impl<'a> {}<'a> {{", name).unwrap();
+        out.push_str(
+            "    /// Returns the client for the contract instantiated at `account_id`.\n",
+        );
+        out.push_str(
+            "    pub fn at(client: &'a mut Client, account_id: AccountId) -> Self {\n",
+        );
+        out.push_str("        Self { client, account_id }\n");
+        out.push_str("    }\n\n");
+        out.push_str("    /// Returns the account ID of the contract.\n");
+        out.push_str("    pub fn account_id(&self) -> &AccountId {\n");
+        out.push_str("        &self.account_id\n");
+        out.push_str("    }\n");
+        for constructor in &constructors {
+            out.push('\n');
+            write_docs(&mut out, "    ", constructor.docs);
+            writeln!(
+                out,
+                "    pub fn {}(client: &mut Client{}) -> ::ink_e2e::ConstructorCall<'_, ChainConfig, Environment, constructors::{}> {{",
+                constructor.method,
+                params(&constructor.args),
+                constructor.ty,
+            )
+            .unwrap();
+            writeln!(
+                out,
+                "        ::ink_e2e::ConstructorCall::new(client, constructors::{}{})",
+                constructor.ty,
+                fields_init(&constructor.args),
+            )
+            .unwrap();
+            out.push_str("    }\n");
+        }
+        for message in &messages {
+            out.push('\n');
+            write_docs(&mut out, "    ", message.docs);
+            writeln!(
+                out,
+                "    pub fn {}(&mut self{}) -> ::ink_e2e::MessageCall<'_, ChainConfig, Environment, messages::{}> {{",
+                message.method,
+                params(&message.args),
+                message.ty,
+            )
+            .unwrap();
+            out.push_str("        ::ink_e2e::MessageCall::new(\n");
+            out.push_str("            &mut *self.client,\n");
+            out.push_str("            ::core::clone::Clone::clone(&self.account_id),\n");
+            writeln!(
+                out,
+                "            messages::{}{},",
+                message.ty,
+                fields_init(&message.args),
+            )
+            .unwrap();
+            out.push_str("        )\n");
+            out.push_str("    }\n");
+        }
+        out.push_str("}\n");
+        Ok(out)
+    }
+
+    /// Returns the items to generate for a message or constructor.
+    fn callable(
+        &self,
+        label: &str,
+        args: &[MessageParamSpec<PortableForm>],
+        return_type: &ReturnTypeSpec<PortableForm>,
+        selector: &'a Selector,
+        docs: &'a [String],
+    ) -> Result<Callable<'a>, Error> {
+        let args = args
+            .iter()
+            .map(|arg| {
+                Ok(Arg {
+                    ident: escape_keyword(snake_case(arg.label())),
+                    ty: self.type_expr(arg.ty().ty().id())?,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        let return_type = match return_type.opt_type() {
+            Some(ty) => self.type_expr(ty.ty().id())?,
+            None => "()".to_string(),
+        };
+        Ok(Callable {
+            method: escape_keyword(snake_case(label)),
+            ty: pascal_case(label),
+            args,
+            return_type,
+            selector,
+            docs,
+        })
+    }
+
+    /// Returns the Rust type referring to the type with the given `id`.
+    fn type_expr(&self, id: u32) -> Result<String, Error> {
+        let ty = self.resolve(id);
+        match ty.type_def() {
+            TypeDef::Composite(_) | TypeDef::Variant(_) => self.path_expr(ty),
+            TypeDef::Sequence(sequence) => {
+                Ok(format!(
+                    "::std::vec::Vec<{}>",
+                    self.type_expr(sequence.type_param().id())?
+                ))
+            }
+            TypeDef::Array(array) => {
+                Ok(format!(
+                    "[{}; {}]",
+                    self.type_expr(array.type_param().id())?,
+                    array.len()
+                ))
+            }
+            TypeDef::Tuple(tuple) => {
+                let fields = tuple
+                    .fields()
+                    .iter()
+                    .map(|field| self.type_expr(field.id()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                match fields.as_slice() {
+                    [field] => Ok(format!("({},)", field)),
+                    fields => Ok(format!("({})", fields.join(", "))),
+                }
+            }
+            TypeDef::Primitive(primitive) => primitive_expr(primitive),
+            TypeDef::Compact(compact) => {
+                Ok(format!(
+                    "::ink_e2e::scale::Compact<{}>",
+                    self.type_expr(compact.type_param().id())?
+                ))
+            }
+            TypeDef::BitSequence(_) => {
+                Err(Error::UnsupportedType("bit sequences".to_string()))
+            }
+        }
+    }
+
+    /// Returns the Rust path of the given struct or enum with its generic parameters.
+    fn path_expr(&self, ty: &Type<PortableForm>) -> Result<String, Error> {
+        let segments = ty.path().segments();
+        let path = segments.join("::");
+        let substitute = self.config.substitutes.get(&path).cloned().or_else(|| {
+            BUILTIN_SUBSTITUTES
+                .iter()
+                .find(|(builtin, _)| *builtin == path)
+                .map(|(_, substitute)| substitute.to_string())
+        });
+        let path = match substitute {
+            Some(substitute) => substitute,
+            None if segments.len() > 1 => format!("::{}", path),
+            // Types without a module, e.g. anonymous types or Rust prelude types
+            // other than the built-in ones, have no public path.
+            None => {
+                return Err(Error::UnsupportedType(format!(
+                    "type `{}` without a path, add a substitute via `Config::substitute_type`",
+                    path
+                )))
+            }
+        };
+        if ty.type_params().is_empty() {
+            return Ok(path)
+        }
+        let params = ty
+            .type_params()
+            .iter()
+            .map(|param| {
+                match param.ty() {
+                    Some(param) => self.type_expr(param.id()),
+                    None => {
+                        Err(Error::UnsupportedType(format!(
+                            "type `{}` with an unknown generic parameter",
+                            path
+                        )))
+                    }
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(format!("{}<{}>", path, params.join(", ")))
+    }
+
+    /// Returns the type with the given `id` from the registry.
+    fn resolve(&self, id: u32) -> &Type<PortableForm> {
+        self.registry
+            .resolve(id)
+            .unwrap_or_else(|| panic!("encountered unknown type with id {}", id))
+    }
+}
+
+/// Writes the type of the encoded call of a message or constructor and its
+/// implementation of the `ink_e2e` trait `call_trait`.
+fn write_call_type(
+    out: &mut String,
+    callable: &Callable,
+    call_trait: &str,
+    bundle_path: &str,
+) {
+    write_docs(out, "    ", callable.docs);
+    if callable.args.is_empty() {
+        writeln!(out, "    pub struct {};\n", callable.ty).unwrap();
+    } else {
+        writeln!(out, "    pub struct {} {{", callable.ty).unwrap();
+        for arg in &callable.args {
+            writeln!(out, "        pub {}: {},", arg.ident, arg.ty).unwrap();
+        }
+        out.push_str("    }\n\n");
+    }
+    writeln!(
+        out,
+        "    impl ::ink_e2e::scale::Encode for {} {{",
+        callable.ty
+    )
+    .unwrap();
+    out.push_str(
+        "        fn encode_to<O: ::ink_e2e::scale::Output + ?::core::marker::Sized>(&self, _dest: &mut O) {\n",
+    );
+    for arg in &callable.args {
+        writeln!(
+            out,
+            "            ::ink_e2e::scale::Encode::encode_to(&self.{}, _dest);",
+            arg.ident
+        )
+        .unwrap();
+    }
+    out.push_str("        }\n");
+    out.push_str("    }\n\n");
+    writeln!(
+        out,
+        "    impl ::ink_e2e::{} for {} {{",
+        call_trait, callable.ty
+    )
+    .unwrap();
+    writeln!(out, "        type ReturnType = {};", callable.return_type).unwrap();
+    let selector = callable
+        .selector
+        .to_bytes()
+        .iter()
+        .map(|byte| format!("0x{:02X}", byte))
+        .collect::<Vec<_>>();
+    writeln!(
+        out,
+        "        const SELECTOR: [u8; 4] = [{}];",
+        selector.join(", ")
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "        const CONTRACT_PATH: &'static str = {:?};",
+        bundle_path
+    )
+    .unwrap();
+    out.push_str("    }\n\n");
+}
+
+/// Returns the parameter list for the given arguments, prefixed with `, ` unless empty.
+fn params(args: &[Arg]) -> String {
+    args.iter()
+        .map(|arg| format!(", {}: {}", arg.ident, arg.ty))
+        .collect()
+}
+
+/// Returns the initializer of the type of an encoded call with the given arguments.
+fn fields_init(args: &[Arg]) -> String {
+    if args.is_empty() {
+        return String::new()
+    }
+    let fields = args
+        .iter()
+        .map(|arg| arg.ident.as_str())
+        .collect::<Vec<_>>();
+    format!(" {{ {} }}", fields.join(", "))
+}
+
+/// Returns the Rust type of a primitive type.
+fn primitive_expr(primitive: &TypeDefPrimitive) -> Result<String, Error> {
+    let ty = match primitive {
+        TypeDefPrimitive::Bool => "bool",
+        TypeDefPrimitive::Char => "char",
+        TypeDefPrimitive::Str => "::std::string::String",
+        TypeDefPrimitive::U8 => "u8",
+        TypeDefPrimitive::U16 => "u16",
+        TypeDefPrimitive::U32 => "u32",
+        TypeDefPrimitive::U64 => "u64",
+        TypeDefPrimitive::U128 => "u128",
+        TypeDefPrimitive::I8 => "i8",
+        TypeDefPrimitive::I16 => "i16",
+        TypeDefPrimitive::I32 => "i32",
+        TypeDefPrimitive::I64 => "i64",
+        TypeDefPrimitive::I128 => "i128",
+        TypeDefPrimitive::U256 | TypeDefPrimitive::I256 => {
+            return Err(Error::UnsupportedType(format!("{:?}", primitive)))
+        }
+    };
+    Ok(ty.to_string())
+}
+
+/// Writes the given documentation lines as doc comments with the given indentation.
+fn write_docs(out: &mut String, indent: &str, docs: &[String]) {
+    for line in docs {
+        let line = line.trim_end();
+        if line.is_empty() {
+            writeln!(out, "{}///", indent).unwrap();
+        } else {
+            writeln!(
+                out,
+                "{}/// {}",
+                indent,
+                line.strip_prefix(' ').unwrap_or(line)
+            )
+            .unwrap();
+        }
+    }
+}
+
+/// Escapes identifiers which are Rust keywords.
+fn escape_keyword(ident: String) -> String {
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const",
+        "continue", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for",
+        "if", "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut",
+        "override", "priv", "pub", "ref", "return", "static", "struct", "trait", "true",
+        "try", "type", "typeof", "unsafe", "unsized", "use", "virtual", "where", "while",
+        "yield",
+    ];
+    match ident.as_str() {
+        // These cannot be raw identifiers.
+        "self" | "super" | "crate" | "_" => format!("{}_", ident),
+        ident if KEYWORDS.contains(&ident) => format!("r#{}", ident),
+        _ => ident,
+    }
+}
+
+/// Converts the given identifier into `snake_case`.
+///
+/// Every non-alphanumeric character is treated as a word boundary, so that
+/// e.g. `BaseErc20::total_supply` becomes `base_erc20_total_supply`.
+fn snake_case(ident: &str) -> String {
+    let mut snake = String::new();
+    for word in ident
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+    {
+        if !snake.is_empty() {
+            snake.push('_');
+        }
+        let mut previous_is_lowercase = false;
+        for c in word.chars() {
+            if c.is_uppercase() && previous_is_lowercase {
+                snake.push('_');
+            }
+            previous_is_lowercase = c.is_lowercase() || c.is_numeric();
+            snake.extend(c.to_lowercase());
+        }
+    }
+    snake
+}
+
+/// Converts the given identifier into `PascalCase`.
+///
+/// Every non-alphanumeric character is treated as a word boundary.
+fn pascal_case(ident: &str) -> String {
+    ident
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            let first = chars.next().expect("words are never empty");
+            first.to_uppercase().chain(chars).collect::<String>()
+        })
+        .collect()
+}
@@ -0,0 +1,207 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generation of Rust clients from the metadata of an ink! smart contract.
+//!
+//! The generated client is meant for off-chain code like backends and bots
+//! interacting with a deployed contract. It consists of
+//!
+//! - a type for every message and constructor implementing
+//!   [`InkMessage`](https://docs.rs/ink_e2e/latest/ink_e2e/trait.InkMessage.html)
+//!   and `InkConstructor` respectively,
+//! - a wrapper around an `ink_e2e::Client` for the contract with a typed method
+//!   for every message and constructor.
+//!
+//! The calls are submitted by the `subxt` based `ink_e2e::Client`, hence the
+//! crate using the generated client has to depend on `ink_e2e` as well as `ink`.
+//!
+//! # Types
+//!
+//! No types are generated for the arguments and return values of the messages.
+//! Instead the types are referred to by their path in the metadata, which is the
+//! path of their definition, e.g. `::adder::adder::Error`. This way the client
+//! reuses the type definitions of the contract crate, which it has to depend on
+//! with the `ink-as-dependency` feature.
+//!
+//! Types not reachable under their path, e.g. since they are defined in a private
+//! module, can be mapped to another path with [`Config::substitute_type`].
+//!
+//! # Example
+//!
+//! Generating the client from a `build.rs` script:
+//!
+//! ```no_run
+//! let metadata = std::fs::read_to_string("adder/target/ink/metadata.json").unwrap();
+//! let client = ink_client_codegen::generate(&metadata, &ink_client_codegen::Config::new())
+//!     .unwrap_or_else(|error| panic!("generating the client failed: {}", error));
+//! let out_dir = std::env::var("OUT_DIR").unwrap();
+//! std::fs::write(format!("{}/adder.rs", out_dir), client).unwrap();
+//! ```
+//!
+//! Using the generated client:
+//!
+//! ```ignore
+//! mod adder {
+//!     include!(concat!(env!("OUT_DIR"), "/adder.rs"));
+//! }
+//!
+//! let mut client = adder::Client::new("ws://127.0.0.1:9944").await;
+//! let mut adder = adder::Adder::at(&mut client, account_id);
+//! adder.inc(by).signed(&pair).submit().await?;
+//! let value = adder.get().signed(&pair).dry_run().await?.value;
+//! ```
+
+#![doc(
+    html_logo_url = "https://use.ink/img/crate-docs/logo.png",
+    html_favicon_url = "https://use.ink/crate-docs/favicon.png"
+)]
+
+mod generator;
+#[cfg(test)]
+mod tests;
+
+use self::generator::RustGenerator;
+use ink_metadata::InkProject;
+use std::collections::BTreeMap;
+
+/// The configuration of the generated client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    contract_name: Option<String>,
+    bundle_path: Option<String>,
+    chain_config: String,
+    environment: String,
+    substitutes: BTreeMap<String, String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            contract_name: None,
+            bundle_path: None,
+            chain_config: "::ink_e2e::PolkadotConfig".to_string(),
+            environment: "::ink::env::DefaultEnvironment".to_string(),
+            substitutes: BTreeMap::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Creates the default configuration.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets the name of the contract, which defaults to the name in the metadata.
+    ///
+    /// The name is used to name the generated wrapper, e.g. `adder` yields `Adder`.
+    pub fn contract_name(mut self, contract_name: &str) -> Self {
+        self.contract_name = Some(contract_name.to_string());
+        self
+    }
+
+    /// Sets the path of the contract bundle uploaded by the constructors.
+    ///
+    /// Defaults to `target/ink/$contract_name.contract`.
+    pub fn bundle_path(mut self, bundle_path: &str) -> Self {
+        self.bundle_path = Some(bundle_path.to_string());
+        self
+    }
+
+    /// Sets the path of the `subxt::Config` of the chain.
+    ///
+    /// Defaults to `::ink_e2e::PolkadotConfig`.
+    pub fn chain_config(mut self, path: &str) -> Self {
+        self.chain_config = path.to_string();
+        self
+    }
+
+    /// Sets the path of the `ink::env::Environment` of the contract.
+    ///
+    /// Defaults to `::ink::env::DefaultEnvironment`.
+    pub fn environment(mut self, path: &str) -> Self {
+        self.environment = path.to_string();
+        self
+    }
+
+    /// Refers to the type at `path` in the metadata by `substitute` instead.
+    ///
+    /// The path in the metadata is given with `::` separators and without
+    /// generic parameters, e.g. `adder::adder::Error`.
+    pub fn substitute_type(mut self, path: &str, substitute: &str) -> Self {
+        self.substitutes
+            .insert(path.to_string(), substitute.to_string());
+        self
+    }
+}
+
+/// The errors of the client generation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The metadata could not be deserialized.
+    InvalidMetadata(String),
+    /// The name of the contract is neither in the metadata nor configured.
+    MissingContractName,
+    /// A type of the contract has no Rust representation.
+    UnsupportedType(String),
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidMetadata(error) => write!(f, "invalid metadata: {}", error),
+            Self::MissingContractName => write!(f, "missing contract name"),
+            Self::UnsupportedType(ty) => write!(f, "unsupported type: {}", ty),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Generates a Rust client for the contract described by the `metadata` JSON.
+///
+/// The metadata is either the `metadata.json` or the `.contract` bundle
+/// generated by `cargo-contract`.
+pub fn generate(metadata: &str, config: &Config) -> Result<String, Error> {
+    let metadata: serde_json::Value = serde_json::from_str(metadata)
+        .map_err(|error| Error::InvalidMetadata(error.to_string()))?;
+    let contract_name = config
+        .contract_name
+        .clone()
+        .or_else(|| {
+            metadata["contract"]["name"]
+                .as_str()
+                .map(ToString::to_string)
+        })
+        .ok_or(Error::MissingContractName)?;
+    let project: InkProject = serde_json::from_value(metadata)
+        .map_err(|error| Error::InvalidMetadata(error.to_string()))?;
+    generate_from_project(&project, &config.clone().contract_name(&contract_name))
+}
+
+/// Generates a Rust client for the contract described by `project`.
+///
+/// # Errors
+///
+/// If the name of the contract is not configured, see [`Config::contract_name`].
+pub fn generate_from_project(
+    project: &InkProject,
+    config: &Config,
+) -> Result<String, Error> {
+    let contract_name = config
+        .contract_name
+        .as_deref()
+        .ok_or(Error::MissingContractName)?;
+    RustGenerator::new(project.registry(), config).generate(project, contract_name)
+}
@@ -0,0 +1,178 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use ink_metadata::{
+    layout,
+    ConstructorSpec,
+    ContractSpec,
+    MessageParamSpec,
+    MessageSpec,
+    ReturnTypeSpec,
+    TypeSpec,
+};
+
+#[derive(scale_info::TypeInfo)]
+#[allow(dead_code)]
+enum Error {
+    InsufficientBalance,
+}
+
+fn project() -> InkProject {
+    let spec: ContractSpec = ContractSpec::new()
+        .constructors(vec![ConstructorSpec::from_label("new")
+            .selector([94u8, 189u8, 136u8, 214u8])
+            .payable(false)
+            .args(vec![MessageParamSpec::new("init_value")
+                .of_type(TypeSpec::of_type::<u64>())
+                .done()])
+            .returns(ReturnTypeSpec::new(None))
+            .docs(vec![" Creates a new contract."])
+            .done()])
+        .messages(vec![
+            MessageSpec::from_label("get_value")
+                .selector([1u8, 2u8, 3u8, 4u8])
+                .mutates(false)
+                .payable(false)
+                .args(Vec::new())
+                .returns(ReturnTypeSpec::new(TypeSpec::of_type::<Option<bool>>()))
+                .docs(Vec::new())
+                .done(),
+            MessageSpec::from_label("BaseErc20::transfer")
+                .selector([5u8, 6u8, 7u8, 8u8])
+                .mutates(true)
+                .payable(false)
+                .args(vec![
+                    MessageParamSpec::new("to")
+                        .of_type(TypeSpec::of_type::<ink_primitives::AccountId>())
+                        .done(),
+                    MessageParamSpec::new("type")
+                        .of_type(TypeSpec::of_type::<(Vec<u8>, [u16; 2])>())
+                        .done(),
+                ])
+                .returns(ReturnTypeSpec::new(TypeSpec::of_type::<Result<(), Error>>()))
+                .docs(Vec::new())
+                .done(),
+        ])
+        .events(Vec::new())
+        .docs(vec![" My token."])
+        .done();
+    let layout = layout::Layout::Leaf(layout::LeafLayout::from_key::<u64>(
+        layout::LayoutKey::new(0u32),
+    ));
+    InkProject::new(layout, spec)
+}
+
+#[test]
+fn generate_works() {
+    let config = Config::new()
+        .contract_name("my_token")
+        .substitute_type("ink_client_codegen::tests::Error", "::my_token::Error");
+    let client = generate_from_project(&project(), &config).unwrap();
+
+    syn::parse_file(&client).expect("the generated client must be valid Rust");
+    assert!(
+        client.contains("pub type Client = ::ink_e2e::Client<ChainConfig, Environment>;")
+    );
+    assert!(client.contains("/// My token.\npub struct MyToken<'a> {"));
+    assert!(client.contains(
+        "    /// Creates a new contract.\n    \
+         pub struct New {\n        pub init_value: u64,\n    }"
+    ));
+    assert!(client.contains(
+        "    impl ::ink_e2e::InkConstructor for New {\n        \
+         type ReturnType = ();\n        \
+         const SELECTOR: [u8; 4] = [0x5E, 0xBD, 0x88, 0xD6];\n        \
+         const CONTRACT_PATH: &'static str = \"target/ink/my_token.contract\";\n    }"
+    ));
+    assert!(client.contains("    pub struct GetValue;"));
+    assert!(client.contains("        type ReturnType = ::core::option::Option<bool>;"));
+    assert!(client.contains(
+        "    pub struct BaseErc20Transfer {\n        \
+         pub to: ::ink::primitives::AccountId,\n        \
+         pub r#type: (::std::vec::Vec<u8>, [u16; 2]),\n    }"
+    ));
+    assert!(client.contains(
+        "        type ReturnType = ::core::result::Result<(), ::my_token::Error>;"
+    ));
+    assert!(client.contains(
+        "    pub fn new(client: &mut Client, init_value: u64) -> \
+         ::ink_e2e::ConstructorCall<'_, ChainConfig, Environment, constructors::New> {"
+    ));
+    assert!(client.contains(
+        "    pub fn base_erc20_transfer(&mut self, to: ::ink::primitives::AccountId, \
+         r#type: (::std::vec::Vec<u8>, [u16; 2])) -> \
+         ::ink_e2e::MessageCall<'_, ChainConfig, Environment, messages::BaseErc20Transfer> {"
+    ));
+    assert!(client.contains("            messages::BaseErc20Transfer { to, r#type },"));
+}
+
+#[test]
+fn generate_from_metadata_json_works() {
+    let mut metadata = serde_json::to_value(project()).unwrap();
+    metadata["contract"] = serde_json::json!({ "name": "my_token", "version": "0.1.0" });
+    let config = Config::new()
+        .bundle_path("my_token.contract")
+        .environment("::my_token::CustomEnvironment")
+        .substitute_type("ink_client_codegen::tests::Error", "::my_token::Error");
+
+    let client = generate(&metadata.to_string(), &config).unwrap();
+
+    assert!(client.contains("pub struct MyToken<'a> {"));
+    assert!(client.contains("pub type Environment = ::my_token::CustomEnvironment;"));
+    assert!(client.contains("const CONTRACT_PATH: &'static str = \"my_token.contract\";"));
+}
+
+#[test]
+fn generate_fails_for_invalid_input() {
+    let config = Config::new();
+    assert_eq!(
+        generate_from_project(&project(), &config),
+        Err(crate::Error::MissingContractName)
+    );
+    assert!(matches!(
+        generate("{", &config),
+        Err(crate::Error::InvalidMetadata(_))
+    ));
+
+    let spec: ContractSpec = ContractSpec::new()
+        .constructors(vec![ConstructorSpec::from_label("new")
+            .selector([94u8, 189u8, 136u8, 214u8])
+            .payable(false)
+            .args(Vec::new())
+            .returns(ReturnTypeSpec::new(None))
+            .docs(Vec::new())
+            .done()])
+        .messages(vec![MessageSpec::from_label("set_name")
+            .selector([1u8, 2u8, 3u8, 4u8])
+            .mutates(true)
+            .payable(false)
+            .args(vec![MessageParamSpec::new("name")
+                .of_type(TypeSpec::of_type::<std::borrow::Cow<'static, str>>())
+                .done()])
+            .returns(ReturnTypeSpec::new(None))
+            .docs(Vec::new())
+            .done()])
+        .events(Vec::new())
+        .docs(Vec::new())
+        .done();
+    let layout = layout::Layout::Leaf(layout::LeafLayout::from_key::<u64>(
+        layout::LayoutKey::new(0u32),
+    ));
+    let project = InkProject::new(layout, spec);
+    assert!(matches!(
+        generate_from_project(&project, &config.contract_name("my_token")),
+        Err(crate::Error::UnsupportedType(_))
+    ));
+}
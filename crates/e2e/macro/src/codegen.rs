@@ -73,6 +73,45 @@ impl InkE2ETest {
         };
 
         let ws_url = &self.test.config.ws_url();
+        let environment = self.test.config.environment();
+        let runtime_config = self.test.config.runtime_config();
+        let node_lease = if self.test.config.spawns_custom_node() {
+            let binary = match self.test.config.node_release() {
+                Some((version, sha256)) => {
+                    quote! {
+                        ::core::option::Option::Some(
+                            ::ink_e2e::node_release::NodeRelease::new(#version, #sha256)
+                                .unwrap_or_else(|err| panic!("invalid node release: {}", err))
+                                .binary(),
+                        )
+                    }
+                }
+                None => quote! { ::core::option::Option::None },
+            };
+            let node_args = self.test.config.node_args();
+            // The chain specification is resolved relative to the tested contract.
+            let chainspec_arg = self.test.config.chainspec().map(|chainspec| {
+                quote! {
+                    ::std::format!(
+                        "--chain={}",
+                        ::std::path::Path::new(::core::env!("CARGO_MANIFEST_DIR"))
+                            .join(#chainspec)
+                            .display(),
+                    ),
+                }
+            });
+            quote! {
+                ::core::option::Option::Some(::ink_e2e::node_pool::lease_custom(
+                    #binary,
+                    &[
+                        #( ::std::string::String::from(#node_args), )*
+                        #chainspec_arg
+                    ],
+                ))
+            }
+        } else {
+            quote! { ::ink_e2e::node_pool::lease() }
+        };
 
         let mut additional_contracts: Vec<String> =
             self.test.config.additional_contracts();
@@ -130,12 +169,19 @@ impl InkE2ETest {
                 });
                 log_info("setting up e2e test");
 
+                // Kills the node spawned for this test, if any, once the test finishes.
+                let __ink_node_lease = #node_lease;
+                let ws_url = match &__ink_node_lease {
+                    ::core::option::Option::Some(lease) => lease.url().to_string(),
+                    ::core::option::Option::None => ::ink_e2e::node_url(#ws_url),
+                };
+
                 ::ink_e2e::INIT.call_once(|| {
                     ::ink_e2e::env_logger::init();
                     let check_async = ::ink_e2e::Client::<
-                        ::ink_e2e::PolkadotConfig,
-                        ink::env::DefaultEnvironment
-                    >::new(&#ws_url);
+                        #runtime_config,
+                        #environment
+                    >::new(&ws_url);
 
                     ::ink_e2e::tokio::runtime::Builder::new_current_thread()
                         .enable_all()
@@ -150,11 +196,10 @@ impl InkE2ETest {
                 log_info("creating new client");
 
                 let run = async {
-                    // TODO(#xxx) Make those two generic environments customizable.
                     let mut client = ::ink_e2e::Client::<
-                        ::ink_e2e::PolkadotConfig,
-                        ink::env::DefaultEnvironment
-                    >::new(&#ws_url).await;
+                        #runtime_config,
+                        #environment
+                    >::new(&ws_url).await;
 
                     let __ret = {
                         #block
@@ -220,3 +265,39 @@ fn build_contract(manifest_path: &str) -> String {
     let dest_metadata = metadata["metadata_result"]["dest_bundle"].to_string();
     dest_metadata.trim_matches('"').to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn client_uses_custom_environment() {
+        // Pretends the contract has been built, which requires `cargo-contract`.
+        set_already_built_contracts(HashMap::from([(
+            String::from("Cargo.toml"),
+            String::from("target/ink/contract.contract"),
+        )]));
+        let test = ir::InkE2ETest::new(
+            quote! {
+                environment = crate::EthEnvironment,
+                runtime_config = crate::EthConfig,
+            },
+            quote! {
+                async fn it_works(mut client: ::ink_e2e::Client<C, E>) -> E2EResult<()> {
+                    Ok(())
+                }
+            },
+        )
+        .unwrap();
+
+        let code = InkE2ETest::from(test).generate_code().to_string();
+
+        let client = quote! {
+            ::ink_e2e::Client::<crate::EthConfig, crate::EthEnvironment>::new(&ws_url)
+        }
+        .to_string();
+        assert_eq!(code.matches(&client).count(), 2);
+        assert!(!code.contains("DefaultEnvironment"));
+        assert!(!code.contains("PolkadotConfig"));
+    }
+}
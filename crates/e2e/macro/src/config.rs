@@ -30,6 +30,18 @@ pub struct E2EConfig {
     whitelisted_attributes: WhitelistedAttributes,
     /// Additional contracts that have to be built before executing the test.
     additional_contracts: Vec<String>,
+    /// The release of `substrate-contracts-node` to run the test against.
+    node_version: Option<syn::LitStr>,
+    /// The SHA-256 checksum of the release archive of `node_version`.
+    node_sha256: Option<syn::LitStr>,
+    /// The environmental types of the tested contract.
+    environment: Option<Environment>,
+    /// The `subxt` configuration of the runtime of the node.
+    runtime_config: Option<syn::Path>,
+    /// Additional command line arguments of the node spawned for the test.
+    node_args: Vec<syn::LitStr>,
+    /// The chain specification of the node spawned for the test.
+    chainspec: Option<syn::LitStr>,
 }
 
 impl TryFrom<ast::AttributeArgs> for E2EConfig {
@@ -39,6 +51,12 @@ impl TryFrom<ast::AttributeArgs> for E2EConfig {
         let mut ws_url: Option<(syn::LitStr, ast::MetaNameValue)> = None;
         let mut whitelisted_attributes = WhitelistedAttributes::default();
         let mut additional_contracts: Option<(syn::LitStr, ast::MetaNameValue)> = None;
+        let mut node_version: Option<(syn::LitStr, ast::MetaNameValue)> = None;
+        let mut node_sha256: Option<(syn::LitStr, ast::MetaNameValue)> = None;
+        let mut environment: Option<(Environment, ast::MetaNameValue)> = None;
+        let mut runtime_config: Option<(syn::Path, ast::MetaNameValue)> = None;
+        let mut node_args: Option<(Vec<syn::LitStr>, ast::MetaNameValue)> = None;
+        let mut chainspec: Option<(syn::LitStr, ast::MetaNameValue)> = None;
 
         for arg in args.into_iter() {
             if arg.name.is_ident("ws_url") {
@@ -72,6 +90,117 @@ impl TryFrom<ast::AttributeArgs> for E2EConfig {
                         "expected a bool literal for `additional_contracts` ink! e2e test configuration argument",
                     ))
                 }
+            } else if arg.name.is_ident("node_version") {
+                if let Some((_, ast)) = node_version {
+                    return Err(duplicate_config_err(ast, arg, "node_version", "e2e test"))
+                }
+                match &arg.value {
+                    ast::PathOrLit::Lit(syn::Lit::Str(lit_str))
+                        if !lit_str.value().trim().is_empty() =>
+                    {
+                        node_version = Some((lit_str.clone(), arg))
+                    }
+                    _ => {
+                        return Err(format_err_spanned!(
+                            arg,
+                            "expected a release tag string literal for `node_version` ink! e2e test configuration argument",
+                        ))
+                    }
+                }
+            } else if arg.name.is_ident("node_sha256") {
+                if let Some((_, ast)) = node_sha256 {
+                    return Err(duplicate_config_err(ast, arg, "node_sha256", "e2e test"))
+                }
+                match &arg.value {
+                    ast::PathOrLit::Lit(syn::Lit::Str(lit_str))
+                        if is_sha256_hex(&lit_str.value()) =>
+                    {
+                        node_sha256 = Some((lit_str.clone(), arg))
+                    }
+                    _ => {
+                        return Err(format_err_spanned!(
+                            arg,
+                            "expected a hex encoded SHA-256 checksum string literal for `node_sha256` ink! e2e test configuration argument",
+                        ))
+                    }
+                }
+            } else if arg.name.is_ident("environment") {
+                if let Some((_, ast)) = environment {
+                    return Err(duplicate_config_err(ast, arg, "environment", "e2e test"))
+                }
+                if let ast::PathOrLit::Path(path) = &arg.value {
+                    environment = Some((Environment { path: path.clone() }, arg))
+                } else {
+                    return Err(format_err_spanned!(
+                        arg,
+                        "expected a path for `environment` ink! e2e test configuration argument",
+                    ))
+                }
+            } else if arg.name.is_ident("runtime_config") {
+                if let Some((_, ast)) = runtime_config {
+                    return Err(duplicate_config_err(
+                        ast,
+                        arg,
+                        "runtime_config",
+                        "e2e test",
+                    ))
+                }
+                if let ast::PathOrLit::Path(path) = &arg.value {
+                    runtime_config = Some((path.clone(), arg))
+                } else {
+                    return Err(format_err_spanned!(
+                        arg,
+                        "expected a path for `runtime_config` ink! e2e test configuration argument",
+                    ))
+                }
+            } else if arg.name.is_ident("node_args") {
+                if let Some((_, ast)) = node_args {
+                    return Err(duplicate_config_err(ast, arg, "node_args", "e2e test"))
+                }
+                let args = match &arg.value {
+                    ast::PathOrLit::Array(array) => {
+                        array
+                            .elems
+                            .iter()
+                            .map(|elem| {
+                                match elem {
+                                    syn::Expr::Lit(syn::ExprLit {
+                                        lit: syn::Lit::Str(lit_str),
+                                        ..
+                                    }) => Some(lit_str.clone()),
+                                    _ => None,
+                                }
+                            })
+                            .collect::<Option<Vec<_>>>()
+                    }
+                    _ => None,
+                };
+                match args {
+                    Some(args) => node_args = Some((args, arg)),
+                    None => {
+                        return Err(format_err_spanned!(
+                            arg,
+                            "expected an array of string literals for `node_args` ink! e2e test configuration argument",
+                        ))
+                    }
+                }
+            } else if arg.name.is_ident("chainspec") {
+                if let Some((_, ast)) = chainspec {
+                    return Err(duplicate_config_err(ast, arg, "chainspec", "e2e test"))
+                }
+                match &arg.value {
+                    ast::PathOrLit::Lit(syn::Lit::Str(lit_str))
+                        if !lit_str.value().trim().is_empty() =>
+                    {
+                        chainspec = Some((lit_str.clone(), arg))
+                    }
+                    _ => {
+                        return Err(format_err_spanned!(
+                            arg,
+                            "expected a file path string literal for `chainspec` ink! e2e test configuration argument",
+                        ))
+                    }
+                }
             } else {
                 return Err(format_err_spanned!(
                     arg,
@@ -82,10 +211,31 @@ impl TryFrom<ast::AttributeArgs> for E2EConfig {
         let additional_contracts = additional_contracts
             .map(|(value, _)| value.value().split(' ').map(String::from).collect())
             .unwrap_or_else(Vec::new);
+        match (&node_version, &node_sha256) {
+            (Some((_, ast)), None) => {
+                return Err(format_err_spanned!(
+                    ast,
+                    "`node_version` requires the `node_sha256` checksum of the release archive",
+                ))
+            }
+            (None, Some((_, ast))) => {
+                return Err(format_err_spanned!(
+                    ast,
+                    "`node_sha256` requires the `node_version` of the release",
+                ))
+            }
+            _ => (),
+        }
         Ok(E2EConfig {
             ws_url: ws_url.map(|(value, _)| value),
             additional_contracts,
             whitelisted_attributes,
+            node_version: node_version.map(|(value, _)| value),
+            node_sha256: node_sha256.map(|(value, _)| value),
+            environment: environment.map(|(value, _)| value),
+            runtime_config: runtime_config.map(|(value, _)| value),
+            node_args: node_args.map(|(value, _)| value).unwrap_or_default(),
+            chainspec: chainspec.map(|(value, _)| value),
         })
     }
 }
@@ -105,6 +255,55 @@ impl E2EConfig {
     pub fn additional_contracts(&self) -> Vec<String> {
         self.additional_contracts.clone()
     }
+
+    /// Returns the release of `substrate-contracts-node` and the checksum of its
+    /// archive if the test runs against a pinned release.
+    pub fn node_release(&self) -> Option<(syn::LitStr, syn::LitStr)> {
+        self.node_version.clone().zip(self.node_sha256.clone())
+    }
+
+    /// Returns the environmental types of the tested contract, if specified.
+    /// Otherwise returns `ink::env::DefaultEnvironment`.
+    pub fn environment(&self) -> syn::Path {
+        self.environment
+            .as_ref()
+            .map(|environment| &environment.path)
+            .cloned()
+            .unwrap_or(Environment::default().path)
+    }
+
+    /// Returns the `subxt` configuration of the runtime of the node, if specified.
+    /// Otherwise returns `ink_e2e::PolkadotConfig`.
+    pub fn runtime_config(&self) -> syn::Path {
+        self.runtime_config
+            .clone()
+            .unwrap_or_else(|| syn::parse_quote! { ::ink_e2e::PolkadotConfig })
+    }
+
+    /// Returns the additional command line arguments of the node spawned for the
+    /// test.
+    pub fn node_args(&self) -> &[syn::LitStr] {
+        &self.node_args
+    }
+
+    /// Returns the path to the chain specification of the node spawned for the test,
+    /// relative to the manifest of the tested contract, if specified.
+    pub fn chainspec(&self) -> Option<&syn::LitStr> {
+        self.chainspec.as_ref()
+    }
+
+    /// Returns `true` if the test has to spawn its own node, since it is customized
+    /// by the configuration.
+    pub fn spawns_custom_node(&self) -> bool {
+        self.node_release().is_some()
+            || !self.node_args.is_empty()
+            || self.chainspec.is_some()
+    }
+}
+
+/// Returns `true` if `value` is a hex encoded SHA-256 checksum.
+fn is_sha256_hex(value: &str) -> bool {
+    value.len() == 64 && value.chars().all(|c| c.is_ascii_hexdigit())
 }
 
 /// The environmental types definition.
@@ -117,7 +316,7 @@ pub struct Environment {
 impl Default for Environment {
     fn default() -> Self {
         Self {
-            path: syn::parse_quote! { ::ink_env::DefaultEnvironment },
+            path: syn::parse_quote! { ::ink::env::DefaultEnvironment },
         }
     }
 }
@@ -178,6 +377,12 @@ mod tests {
                 ws_url: None,
                 whitelisted_attributes: attrs,
                 additional_contracts: Vec::new(),
+                node_version: None,
+                node_sha256: None,
+                environment: None,
+                runtime_config: None,
+                node_args: Vec::new(),
+                chainspec: None,
             }),
         )
     }
@@ -189,4 +394,119 @@ mod tests {
             Err("expected a string with attributes separated by `,`"),
         );
     }
+
+    #[test]
+    fn node_release_works() {
+        let sha256 = "ab".repeat(32);
+        let config =
+            <E2EConfig as TryFrom<ast::AttributeArgs>>::try_from(syn::parse_quote! {
+                node_version = "v0.23.0",
+                node_sha256 = #sha256,
+            })
+            .unwrap();
+        let (version, checksum) = config.node_release().unwrap();
+        assert_eq!(version.value(), "v0.23.0");
+        assert_eq!(checksum.value(), sha256);
+    }
+
+    #[test]
+    fn node_release_requires_both_args() {
+        assert_try_from(
+            syn::parse_quote! { node_version = "v0.23.0" },
+            Err("`node_version` requires the `node_sha256` checksum of the release archive"),
+        );
+        let sha256 = "ab".repeat(32);
+        assert_try_from(
+            syn::parse_quote! { node_sha256 = #sha256 },
+            Err("`node_sha256` requires the `node_version` of the release"),
+        );
+    }
+
+    #[test]
+    fn node_sha256_invalid_value_fails() {
+        assert_try_from(
+            syn::parse_quote! { node_version = "v0.23.0", node_sha256 = "abab" },
+            Err("expected a hex encoded SHA-256 checksum string literal for `node_sha256` ink! e2e test configuration argument"),
+        );
+    }
+
+    #[test]
+    fn environment_and_runtime_config_work() {
+        let config =
+            <E2EConfig as TryFrom<ast::AttributeArgs>>::try_from(syn::parse_quote! {
+                environment = crate::CustomEnvironment,
+                runtime_config = crate::CustomConfig,
+            })
+            .unwrap();
+        assert_eq!(
+            config.environment(),
+            syn::parse_quote! { crate::CustomEnvironment }
+        );
+        assert_eq!(
+            config.runtime_config(),
+            syn::parse_quote! { crate::CustomConfig }
+        );
+    }
+
+    #[test]
+    fn environment_and_runtime_config_default_works() {
+        let config = E2EConfig::default();
+        assert_eq!(
+            config.environment(),
+            syn::parse_quote! { ::ink::env::DefaultEnvironment }
+        );
+        assert_eq!(
+            config.runtime_config(),
+            syn::parse_quote! { ::ink_e2e::PolkadotConfig }
+        );
+    }
+
+    #[test]
+    fn environment_invalid_value_fails() {
+        assert_try_from(
+            syn::parse_quote! { environment = "crate::CustomEnvironment" },
+            Err("expected a path for `environment` ink! e2e test configuration argument"),
+        );
+        assert_try_from(
+            syn::parse_quote! { runtime_config = "crate::CustomConfig" },
+            Err("expected a path for `runtime_config` ink! e2e test configuration argument"),
+        );
+    }
+
+    #[test]
+    fn node_args_and_chainspec_work() {
+        let config =
+            <E2EConfig as TryFrom<ast::AttributeArgs>>::try_from(syn::parse_quote! {
+                node_args = ["--some-flag", "--other-flag=1"],
+                chainspec = "dev-heavy.json",
+            })
+            .unwrap();
+        assert_eq!(
+            config
+                .node_args()
+                .iter()
+                .map(syn::LitStr::value)
+                .collect::<Vec<_>>(),
+            vec!["--some-flag", "--other-flag=1"]
+        );
+        assert_eq!(config.chainspec().unwrap().value(), "dev-heavy.json");
+        assert!(config.spawns_custom_node());
+        assert!(!E2EConfig::default().spawns_custom_node());
+    }
+
+    #[test]
+    fn node_args_and_chainspec_invalid_value_fails() {
+        assert_try_from(
+            syn::parse_quote! { node_args = "--some-flag" },
+            Err("expected an array of string literals for `node_args` ink! e2e test configuration argument"),
+        );
+        assert_try_from(
+            syn::parse_quote! { node_args = ["--some-flag", 1] },
+            Err("expected an array of string literals for `node_args` ink! e2e test configuration argument"),
+        );
+        assert_try_from(
+            syn::parse_quote! { chainspec = ["dev-heavy.json"] },
+            Err("expected a file path string literal for `chainspec` ink! e2e test configuration argument"),
+        );
+    }
 }
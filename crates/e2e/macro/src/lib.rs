@@ -63,6 +63,91 @@ use syn::Result;
 ///
 ///     **Default value:** `"ws://localhost:9944"`.
 ///
+///     The `CONTRACTS_NODE_URL` environment variable overrides this argument at test
+///     run time, e.g. to run the same tests against a persistent testnet. Set
+///     `CONTRACTS_NODE_SEED_FILE` to provide pre-funded accounts for such a node, see
+///     the `ink_e2e::node` module documentation.
+///
+///     Set `CONTRACTS_NODE_POOL_SIZE` to run every test against a fresh node spawned
+///     for it instead, so that the tests can run in parallel, see the
+///     `ink_e2e::node_pool` module documentation.
+///
+/// - `node_version: String` and `node_sha256: String`
+///
+///     Runs the test against a fresh node spawned from the given release of
+///     `substrate-contracts-node`, which is downloaded once and verified against the
+///     SHA-256 checksum of its release archive. Both arguments have to be provided
+///     together. This allows testing against several releases of the node.
+///
+///     **Usage Example:**
+///     ```no_compile
+///     # // TODO(#xxx) Remove the `no_compile`.
+///     type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+///     #[ink::e2e_test(
+///         node_version = "v0.23.0",
+///         node_sha256 = "<checksum of substrate-contracts-node-linux.tar.gz>",
+///     )]
+///     async fn e2e_works_with_v0_23(
+///         mut client: ::ink_e2e::Client<C, E>,
+///     ) -> E2EResult<()> {
+///         Ok(())
+///     }
+///     ```
+///
+///     Set `CONTRACTS_NODE_VERSION` and `CONTRACTS_NODE_SHA256` to pin the release
+///     for all tests instead, see the `ink_e2e::node_release` module documentation.
+///
+/// - `environment: impl Environment` and `runtime_config: impl subxt::Config`
+///
+///     Tests contracts written for a custom `#[ink::contract(env = ...)]` against a
+///     node whose runtime uses other types than the default Substrate runtime, e.g. a
+///     20 byte `AccountId` or a 256 bit `Balance`. The client of the test is then a
+///     `ink_e2e::Client<MyConfig, MyEnvironment>`, so that account ids, balances and
+///     hashes are decoded as the types of the runtime. The default accounts, e.g.
+///     `ink_e2e::alice`, use `sr25519` keys, so runtimes with other accounts pass their
+///     own `ink_e2e::TxSigner` to the client instead, e.g. a `PairSigner` of `ecdsa`
+///     keys.
+///
+///     **Usage Example:**
+///     ```no_compile
+///     # // TODO(#xxx) Remove the `no_compile`.
+///     type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+///     #[ink::e2e_test(environment = crate::MyEnvironment, runtime_config = crate::MyConfig)]
+///     async fn e2e_works_with_custom_types(
+///         mut client: ::ink_e2e::Client<C, E>,
+///     ) -> E2EResult<()> {
+///         let mut signer = crate::EthKeypair::from_seed(&[1; 32]);
+///         let constructor = contract_ref::new();
+///         let contract = client
+///             .instantiate(&mut signer, constructor, 0, None)
+///             .await?;
+///         Ok(())
+///     }
+///     ```
+///
+///     **Default value:** `ink::env::DefaultEnvironment` and `ink_e2e::PolkadotConfig`.
+///
+/// - `node_args: [String]` and `chainspec: String`
+///
+///     Runs the test against a fresh node spawned with the additional command line
+///     arguments `node_args` and the chain specification file `chainspec`, relative
+///     to the manifest of the tested contract. This allows testing against e.g.
+///     other existential deposits, block times or limits of the contracts pallet,
+///     like the maximum code size. The node binary is the one pinned by
+///     `node_version` if provided, otherwise the one of the `ink_e2e::node_pool`.
+///
+///     **Usage Example:**
+///     ```no_compile
+///     # // TODO(#xxx) Remove the `no_compile`.
+///     type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+///     #[ink::e2e_test(node_args = ["--some-flag"], chainspec = "dev-heavy.json")]
+///     async fn e2e_works_with_heavy_chain(
+///         mut client: ::ink_e2e::Client<C, E>,
+///     ) -> E2EResult<()> {
+///         Ok(())
+///     }
+///     ```
+///
 /// # Example
 ///
 /// ```no_compile
@@ -117,6 +202,61 @@ pub fn test(attr: TokenStream, item: TokenStream) -> TokenStream {
     generate(attr.into(), item.into()).into()
 }
 
+/// Defines an End-to-End benchmark.
+///
+/// A benchmark is an End-to-End test which is ignored by default, since executing
+/// messages many times against the node is slow. Run benchmarks with
+/// `cargo test -- --ignored`. Within the benchmark use `Client::bench_call` to
+/// measure the median cost of a message and `BenchReport::assert_within` to fail
+/// the benchmark if the message gets more expensive.
+///
+/// Accepts the same header arguments as [`macro@test`].
+///
+/// The attribute is `#[ink_e2e::bench]` rather than `#[ink::bench]`, since the
+/// benchmark drives an `ink_e2e::Client` like any other End-to-End test, and `ink`
+/// does not depend on the std-only `ink_e2e` crate.
+///
+/// # Example
+///
+/// ```no_compile
+/// #[ink_e2e::bench]
+/// async fn bench_flip(mut client: ::ink_e2e::Client<C, E>) -> E2EResult<()> {
+///     let contract_acc_id = client
+///         .instantiate(&mut ::ink_e2e::alice(), flipper::constructors::new(false), 0, None)
+///         .await
+///         .expect("instantiating contract failed")
+///         .account_id;
+///
+///     client
+///         .bench_call("flip", &mut ::ink_e2e::alice(), contract_acc_id, 0, 10, || {
+///             flipper::messages::flip()
+///         })
+///         .await
+///         .expect("benchmark failed")
+///         .assert_within(&::ink_e2e::Thresholds::new().max_gas_consumed(1_000_000_000));
+///     Ok(())
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn bench(attr: TokenStream, item: TokenStream) -> TokenStream {
+    match generate_bench_or_err(attr.into(), item.into()) {
+        Ok(tokens) => tokens,
+        Err(err) => err.to_compile_error(),
+    }
+    .into()
+}
+
+fn generate_bench_or_err(
+    attr: TokenStream2,
+    input: TokenStream2,
+) -> Result<TokenStream2> {
+    let mut item_fn = syn::parse2::<syn::ItemFn>(input)?;
+    item_fn.attrs.push(syn::parse_quote! {
+        #[ignore = "benchmark, run with `cargo test -- --ignored`"]
+    });
+    generate_or_err(attr, quote::quote! { #item_fn })
+}
+
 fn generate(attr: TokenStream2, input: TokenStream2) -> TokenStream2 {
     match generate_or_err(attr, input) {
         Ok(tokens) => tokens,
@@ -0,0 +1,280 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarking the cost of contract messages against the E2E node.
+//!
+//! [`Client::bench_call`](crate::Client::bench_call) executes a message a given
+//! number of times and returns a [`BenchReport`] with the median of the consumed
+//! gas, the proof size and the storage deposit of all runs. Asserting the report
+//! against [`Thresholds`] fails the benchmark if a message gets more expensive.
+//!
+//! # Example
+//!
+//! ```no_compile
+//! #[ink_e2e::bench]
+//! async fn bench_change(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+//!     let delegator = client
+//!         .instantiate(&mut ink_e2e::alice(), delegator::constructors::new(), 0, None)
+//!         .await
+//!         .expect("instantiate failed")
+//!         .account_id;
+//!
+//!     let report = client
+//!         .bench_call("change", &mut ink_e2e::alice(), delegator, 0, 10, || {
+//!             delegator::messages::change(1)
+//!         })
+//!         .await
+//!         .expect("benchmark failed");
+//!
+//!     report.assert_within(
+//!         &Thresholds::new()
+//!             .max_gas_consumed(2_000_000_000)
+//!             .max_proof_size(10_000),
+//!     );
+//!     Ok(())
+//! }
+//! ```
+
+use pallet_contracts_primitives::{
+    ContractExecResult,
+    StorageDeposit,
+};
+
+/// The cost of a single execution of a message.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Measurement {
+    /// The consumed `ref_time` weight.
+    pub gas_consumed: u64,
+    /// The consumed `proof_size` weight.
+    pub proof_size: u64,
+    /// The charged storage deposit, refunds are negative.
+    pub storage_deposit: i128,
+}
+
+impl Measurement {
+    /// Returns the measurement of the given execution result.
+    pub fn from_exec_result<Balance>(exec_result: &ContractExecResult<Balance>) -> Self
+    where
+        Balance: Copy + Into<u128>,
+    {
+        let storage_deposit = match exec_result.storage_deposit {
+            StorageDeposit::Charge(amount) => saturating_i128(amount.into()),
+            StorageDeposit::Refund(amount) => -saturating_i128(amount.into()),
+        };
+        Self {
+            gas_consumed: exec_result.gas_consumed.ref_time(),
+            proof_size: exec_result.gas_consumed.proof_size(),
+            storage_deposit,
+        }
+    }
+}
+
+fn saturating_i128(value: u128) -> i128 {
+    i128::try_from(value).unwrap_or(i128::MAX)
+}
+
+/// The median cost of all runs of a benchmarked message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchReport {
+    /// The name of the benchmark.
+    pub name: String,
+    /// The number of runs the medians are taken of.
+    pub runs: usize,
+    /// The median consumed `ref_time` weight.
+    pub gas_consumed: u64,
+    /// The median consumed `proof_size` weight.
+    pub proof_size: u64,
+    /// The median charged storage deposit, refunds are negative.
+    pub storage_deposit: i128,
+}
+
+impl BenchReport {
+    /// Returns the report of the given measurements.
+    ///
+    /// # Panics
+    ///
+    /// If `measurements` is empty.
+    pub fn new(name: &str, measurements: &[Measurement]) -> Self {
+        assert!(
+            !measurements.is_empty(),
+            "benchmark {} requires at least one run",
+            name
+        );
+        Self {
+            name: name.to_string(),
+            runs: measurements.len(),
+            gas_consumed: median(measurements.iter().map(|m| m.gas_consumed)),
+            proof_size: median(measurements.iter().map(|m| m.proof_size)),
+            storage_deposit: median(measurements.iter().map(|m| m.storage_deposit)),
+        }
+    }
+
+    /// Asserts that none of the medians exceed the given thresholds.
+    ///
+    /// # Panics
+    ///
+    /// If any median exceeds its threshold. The panic message lists all
+    /// exceeded thresholds.
+    pub fn assert_within(&self, thresholds: &Thresholds) {
+        let mut exceeded = Vec::new();
+        let mut check = |what: &str, value: i128, max: Option<i128>| {
+            if let Some(max) = max.filter(|max| value > *max) {
+                exceeded.push(format!("{} {} > {}", what, value, max));
+            }
+        };
+        check(
+            "gas consumed",
+            self.gas_consumed.into(),
+            thresholds.max_gas_consumed.map(Into::into),
+        );
+        check(
+            "proof size",
+            self.proof_size.into(),
+            thresholds.max_proof_size.map(Into::into),
+        );
+        check(
+            "storage deposit",
+            self.storage_deposit,
+            thresholds.max_storage_deposit,
+        );
+        assert!(
+            exceeded.is_empty(),
+            "benchmark {} exceeded its thresholds: {}",
+            self.name,
+            exceeded.join(", ")
+        );
+    }
+}
+
+impl core::fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "bench {}: median of {} runs: gas consumed {}, proof size {}, storage deposit {}",
+            self.name, self.runs, self.gas_consumed, self.proof_size, self.storage_deposit
+        )
+    }
+}
+
+/// Upper bounds for the medians of a [`BenchReport`].
+///
+/// Thresholds which are not set are not checked.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Thresholds {
+    max_gas_consumed: Option<u64>,
+    max_proof_size: Option<u64>,
+    max_storage_deposit: Option<i128>,
+}
+
+impl Thresholds {
+    /// Creates thresholds which do not check anything yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum median consumed `ref_time` weight.
+    pub fn max_gas_consumed(self, max: u64) -> Self {
+        Self {
+            max_gas_consumed: Some(max),
+            ..self
+        }
+    }
+
+    /// Sets the maximum median consumed `proof_size` weight.
+    pub fn max_proof_size(self, max: u64) -> Self {
+        Self {
+            max_proof_size: Some(max),
+            ..self
+        }
+    }
+
+    /// Sets the maximum median charged storage deposit.
+    pub fn max_storage_deposit(self, max: i128) -> Self {
+        Self {
+            max_storage_deposit: Some(max),
+            ..self
+        }
+    }
+}
+
+/// Returns the median of `values`, the lower one for an even number of values.
+fn median<T, I>(values: I) -> T
+where
+    T: Ord + Copy,
+    I: Iterator<Item = T>,
+{
+    let mut values = values.collect::<Vec<_>>();
+    values.sort_unstable();
+    values[(values.len() - 1) / 2]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(
+        gas_consumed: u64,
+        proof_size: u64,
+        storage_deposit: i128,
+    ) -> Measurement {
+        Measurement {
+            gas_consumed,
+            proof_size,
+            storage_deposit,
+        }
+    }
+
+    #[test]
+    fn median_works() {
+        assert_eq!(median([3, 1, 2].into_iter()), 2);
+        assert_eq!(median([4, 1, 3, 2].into_iter()), 2);
+        assert_eq!(median([7].into_iter()), 7);
+    }
+
+    #[test]
+    fn report_takes_medians() {
+        let report = BenchReport::new(
+            "change",
+            &[
+                measurement(30, 3, -1),
+                measurement(10, 5, 2),
+                measurement(20, 4, 0),
+            ],
+        );
+        assert_eq!(
+            report,
+            BenchReport {
+                name: "change".to_string(),
+                runs: 3,
+                gas_consumed: 20,
+                proof_size: 4,
+                storage_deposit: 0,
+            }
+        );
+        report.assert_within(&Thresholds::new().max_gas_consumed(20).max_proof_size(4));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "benchmark change exceeded its thresholds: gas consumed 20 > 19, storage deposit 0 > -1"
+    )]
+    fn exceeded_thresholds_fail() {
+        BenchReport::new("change", &[measurement(20, 4, 0)]).assert_within(
+            &Thresholds::new()
+                .max_gas_consumed(19)
+                .max_proof_size(4)
+                .max_storage_deposit(-1),
+        );
+    }
+}
@@ -0,0 +1,325 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Builders for calls of contract messages and constructors.
+//!
+//! These are returned by the clients generated with `ink_client_codegen`, e.g.
+//! `adder.inc(by).signed(&pair).submit().await`, but can be used with any
+//! [`InkMessage`] and [`InkConstructor`] as well.
+
+use super::{
+    client::{
+        CallDryRunResult,
+        CallResult,
+        Client,
+        Error,
+        InstantiationResult,
+    },
+    sr25519,
+    xts::{
+        Call,
+        Instantiate,
+        InstantiateWithCode,
+    },
+    InkConstructor,
+    InkMessage,
+    Signer,
+    TxSigner,
+};
+use ink_env::Environment;
+use sp_runtime::traits::{
+    IdentifyAccount,
+    Verify,
+};
+use subxt::tx::{
+    ExtrinsicParams,
+    PairSigner,
+};
+
+/// A call of the message `M` of the contract at `account_id`.
+///
+/// The call is submitted once it is [`signed`](MessageCall::signed).
+pub struct MessageCall<'a, C, E, M>
+where
+    C: subxt::Config,
+    E: Environment,
+{
+    client: &'a mut Client<C, E>,
+    account_id: C::AccountId,
+    message: M,
+    value: E::Balance,
+    storage_deposit_limit: Option<E::Balance>,
+}
+
+impl<'a, C, E, M> MessageCall<'a, C, E, M>
+where
+    C: subxt::Config,
+    C::AccountId: Into<C::Address> + serde::de::DeserializeOwned,
+    C::Address: From<C::AccountId>,
+    <C::ExtrinsicParams as ExtrinsicParams<C::Index, C::Hash>>::OtherParams: Default,
+
+    E: Environment,
+    E::Balance: core::fmt::Debug + scale::Encode + serde::Serialize,
+
+    Call<C, E::Balance>: scale::Encode,
+    InstantiateWithCode<E::Balance>: scale::Encode,
+    Instantiate<C, E::Balance>: scale::Encode,
+
+    M: InkMessage,
+    <M as InkMessage>::ReturnType: scale::Decode,
+{
+    /// Creates a call of `message` for the contract at `account_id`.
+    pub fn new(
+        client: &'a mut Client<C, E>,
+        account_id: C::AccountId,
+        message: M,
+    ) -> Self {
+        Self {
+            client,
+            account_id,
+            message,
+            value: E::Balance::from(0u8),
+            storage_deposit_limit: None,
+        }
+    }
+
+    /// Sets the value transferred to the contract, only allowed for payable messages.
+    pub fn value(mut self, value: E::Balance) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// Sets the maximum storage deposit charged for the call.
+    pub fn storage_deposit_limit(mut self, storage_deposit_limit: E::Balance) -> Self {
+        self.storage_deposit_limit = Some(storage_deposit_limit);
+        self
+    }
+
+    /// Signs the call with the `sr25519` key `pair`.
+    pub fn signed(self, pair: &sr25519::Pair) -> SignedMessageCall<'a, C, E, M>
+    where
+        C::Signature: From<sr25519::Signature>,
+        <C::Signature as Verify>::Signer:
+            From<sr25519::Public> + IdentifyAccount<AccountId = C::AccountId>,
+    {
+        self.signed_by(PairSigner::new(pair.clone()))
+    }
+
+    /// Signs the call with `signer`, e.g. for runtimes whose accounts are
+    /// not derived from `sr25519` keys.
+    pub fn signed_by<S: TxSigner<C>>(
+        self,
+        signer: S,
+    ) -> SignedMessageCall<'a, C, E, M, S> {
+        SignedMessageCall { call: self, signer }
+    }
+}
+
+/// A [`MessageCall`] signed by an account.
+pub struct SignedMessageCall<'a, C, E, M, S = Signer<C>>
+where
+    C: subxt::Config,
+    E: Environment,
+{
+    call: MessageCall<'a, C, E, M>,
+    signer: S,
+}
+
+impl<'a, C, E, M, S> SignedMessageCall<'a, C, E, M, S>
+where
+    C: subxt::Config,
+    C::AccountId: Into<C::Address> + serde::de::DeserializeOwned,
+    C::Address: From<C::AccountId>,
+    <C::ExtrinsicParams as ExtrinsicParams<C::Index, C::Hash>>::OtherParams: Default,
+
+    E: Environment,
+    E::Balance: core::fmt::Debug + scale::Encode + serde::Serialize,
+
+    Call<C, E::Balance>: scale::Encode,
+    InstantiateWithCode<E::Balance>: scale::Encode,
+    Instantiate<C, E::Balance>: scale::Encode,
+
+    M: InkMessage,
+    <M as InkMessage>::ReturnType: scale::Decode,
+
+    S: TxSigner<C>,
+{
+    /// Submits the call, see [`Client::call`].
+    pub async fn submit(
+        mut self,
+    ) -> Result<CallResult<C, E, <M as InkMessage>::ReturnType>, Error<C, E>> {
+        let call = self.call;
+        call.client
+            .call(
+                &mut self.signer,
+                call.account_id,
+                call.message,
+                call.value,
+                call.storage_deposit_limit,
+            )
+            .await
+    }
+
+    /// Dry runs the call without submitting it, see [`Client::call_dry_run`].
+    pub async fn dry_run(
+        self,
+    ) -> Result<CallDryRunResult<E, <M as InkMessage>::ReturnType>, Error<C, E>> {
+        let call = self.call;
+        call.client
+            .call_dry_run(&self.signer, call.account_id, call.message, call.value)
+            .await
+    }
+}
+
+/// An instantiation of a contract using the constructor `CO`.
+///
+/// The instantiation is submitted once it is [`signed`](ConstructorCall::signed).
+pub struct ConstructorCall<'a, C, E, CO>
+where
+    C: subxt::Config,
+    E: Environment,
+{
+    client: &'a mut Client<C, E>,
+    constructor: CO,
+    value: E::Balance,
+    storage_deposit_limit: Option<E::Balance>,
+    code_hash: Option<C::Hash>,
+}
+
+impl<'a, C, E, CO> ConstructorCall<'a, C, E, CO>
+where
+    C: subxt::Config,
+    C::AccountId: Into<C::Address> + serde::de::DeserializeOwned,
+    C::Address: From<C::AccountId>,
+    <C::ExtrinsicParams as ExtrinsicParams<C::Index, C::Hash>>::OtherParams: Default,
+
+    E: Environment,
+    E::Balance: core::fmt::Debug + scale::Encode + serde::Serialize,
+
+    Call<C, E::Balance>: scale::Encode,
+    InstantiateWithCode<E::Balance>: scale::Encode,
+    Instantiate<C, E::Balance>: scale::Encode,
+
+    CO: InkConstructor,
+{
+    /// Creates an instantiation using `constructor`.
+    ///
+    /// By default the code of the contract bundle at `CO::CONTRACT_PATH` is
+    /// uploaded along with the instantiation.
+    pub fn new(client: &'a mut Client<C, E>, constructor: CO) -> Self {
+        Self {
+            client,
+            constructor,
+            value: E::Balance::from(0u8),
+            storage_deposit_limit: None,
+            code_hash: None,
+        }
+    }
+
+    /// Sets the value transferred to the contract, only allowed for payable constructors.
+    pub fn value(mut self, value: E::Balance) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// Sets the maximum storage deposit charged for the instantiation.
+    pub fn storage_deposit_limit(mut self, storage_deposit_limit: E::Balance) -> Self {
+        self.storage_deposit_limit = Some(storage_deposit_limit);
+        self
+    }
+
+    /// Instantiates the code already uploaded with `code_hash` instead of
+    /// uploading the code of the contract bundle.
+    pub fn code_hash(mut self, code_hash: C::Hash) -> Self {
+        self.code_hash = Some(code_hash);
+        self
+    }
+
+    /// Signs the instantiation with the `sr25519` key `pair`.
+    pub fn signed(self, pair: &sr25519::Pair) -> SignedConstructorCall<'a, C, E, CO>
+    where
+        C::Signature: From<sr25519::Signature>,
+        <C::Signature as Verify>::Signer:
+            From<sr25519::Public> + IdentifyAccount<AccountId = C::AccountId>,
+    {
+        self.signed_by(PairSigner::new(pair.clone()))
+    }
+
+    /// Signs the instantiation with `signer`, e.g. for runtimes whose accounts are
+    /// not derived from `sr25519` keys.
+    pub fn signed_by<S: TxSigner<C>>(
+        self,
+        signer: S,
+    ) -> SignedConstructorCall<'a, C, E, CO, S> {
+        SignedConstructorCall { call: self, signer }
+    }
+}
+
+/// A [`ConstructorCall`] signed by an account.
+pub struct SignedConstructorCall<'a, C, E, CO, S = Signer<C>>
+where
+    C: subxt::Config,
+    E: Environment,
+{
+    call: ConstructorCall<'a, C, E, CO>,
+    signer: S,
+}
+
+impl<'a, C, E, CO, S> SignedConstructorCall<'a, C, E, CO, S>
+where
+    C: subxt::Config,
+    C::AccountId: Into<C::Address> + serde::de::DeserializeOwned,
+    C::Address: From<C::AccountId>,
+    <C::ExtrinsicParams as ExtrinsicParams<C::Index, C::Hash>>::OtherParams: Default,
+
+    E: Environment,
+    E::Balance: core::fmt::Debug + scale::Encode + serde::Serialize,
+
+    Call<C, E::Balance>: scale::Encode,
+    InstantiateWithCode<E::Balance>: scale::Encode,
+    Instantiate<C, E::Balance>: scale::Encode,
+
+    CO: InkConstructor,
+
+    S: TxSigner<C>,
+{
+    /// Submits the instantiation, see [`Client::instantiate`] and
+    /// [`Client::instantiate_with_code_hash`].
+    pub async fn submit(mut self) -> Result<InstantiationResult<C, E>, Error<C, E>> {
+        let call = self.call;
+        match call.code_hash {
+            Some(code_hash) => {
+                call.client
+                    .instantiate_with_code_hash(
+                        &mut self.signer,
+                        code_hash,
+                        call.constructor,
+                        call.value,
+                        call.storage_deposit_limit,
+                    )
+                    .await
+            }
+            None => {
+                call.client
+                    .instantiate(
+                        &mut self.signer,
+                        call.constructor,
+                        call.value,
+                        call.storage_deposit_limit,
+                    )
+                    .await
+            }
+        }
+    }
+}
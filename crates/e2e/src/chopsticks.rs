@@ -0,0 +1,380 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Controlling the chain of a [chopsticks](https://github.com/AcalaNetwork/chopsticks)
+//! development node.
+//!
+//! The `substrate-contracts-node` spawned for `#[ink_e2e::test]` seals a block for
+//! every extrinsic and offers no RPC methods to alter its chain. The features of this
+//! module hence require running the tests against a chopsticks node, e.g. by pointing
+//! `CONTRACTS_NODE_URL` to it:
+//!
+//! ```text
+//! npx @acala-network/chopsticks --endpoint=wss://rpc.shibuya.astar.network
+//! CONTRACTS_NODE_URL=ws://127.0.0.1:8000 cargo test --features e2e-tests
+//! ```
+
+use super::log_info;
+use core::time::Duration;
+use jsonrpsee::{
+    core::client::ClientT,
+    rpc_params,
+    ws_client::{
+        WsClient,
+        WsClientBuilder,
+    },
+};
+use sp_core::{
+    hashing::twox_128,
+    Bytes,
+};
+
+/// A snapshot of the chain state, created by [`Chopsticks::snapshot`].
+///
+/// The chain can be reverted to this state by using [`Chopsticks::revert`].
+pub struct Snapshot<C: subxt::Config> {
+    /// The hash of the best block at the time the snapshot was taken.
+    block_hash: C::Hash,
+}
+
+impl<C> Snapshot<C>
+where
+    C: subxt::Config,
+{
+    /// Returns the hash of the block the snapshot refers to.
+    pub fn block_hash(&self) -> C::Hash {
+        self.block_hash
+    }
+}
+
+// We implement a custom `Debug` here, as to avoid requiring the trait
+// bound `Debug` for `C`.
+impl<C> core::fmt::Debug for Snapshot<C>
+where
+    C: subxt::Config,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Snapshot")
+            .field("block_hash", &self.block_hash)
+            .finish()
+    }
+}
+
+/// The response of the `rpc_methods` RPC method.
+#[derive(serde::Deserialize)]
+struct RpcMethods {
+    methods: Vec<String>,
+}
+
+/// A handle to the RPC methods of a chopsticks node, created by
+/// [`Client::chopsticks`](crate::Client::chopsticks).
+pub struct Chopsticks<C: subxt::Config> {
+    ws_client: WsClient,
+    methods: Vec<String>,
+    _phantom: core::marker::PhantomData<fn() -> C>,
+}
+
+impl<C> Chopsticks<C>
+where
+    C: subxt::Config,
+{
+    /// Connects to the chopsticks node at `url`.
+    ///
+    /// # Panics
+    ///
+    /// If the node can't be reached.
+    pub async fn connect(url: &str) -> Self {
+        let ws_client =
+            WsClientBuilder::default()
+                .build(url)
+                .await
+                .unwrap_or_else(|err| {
+                    panic!("error on ws request: {:?}", err);
+                });
+        let RpcMethods { methods } = ws_client
+            .request("rpc_methods", rpc_params![])
+            .await
+            .unwrap_or_else(|err| {
+                panic!("error on ws request `rpc_methods`: {:?}", err);
+            });
+        Self {
+            ws_client,
+            methods,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Panics if the node does not offer the RPC `method`, i.e. is not a chopsticks
+    /// node.
+    fn ensure_offered(&self, method: &str) {
+        if !self.methods.iter().any(|offered| offered == method) {
+            panic!(
+                "the node does not offer the RPC method `{}`, make sure it is a chopsticks node",
+                method
+            )
+        }
+    }
+
+    /// Calls the RPC `method` of the node with the given `params`.
+    ///
+    /// # Panics
+    ///
+    /// If the node does not offer `method`, i.e. is not a chopsticks node.
+    async fn request<R>(
+        &self,
+        method: &str,
+        params: jsonrpsee::core::params::ArrayParams,
+    ) -> R
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        self.ensure_offered(method);
+        self.ws_client
+            .request(method, params)
+            .await
+            .unwrap_or_else(|err| {
+                panic!("error on ws request `{}`: {:?}", method, err);
+            })
+    }
+
+    /// Takes a snapshot of the current chain state.
+    ///
+    /// Use [`Chopsticks::revert`] to discard all blocks produced after the snapshot,
+    /// e.g. to reuse uploaded code or instantiated contracts across tests
+    /// instead of uploading and instantiating them for every test again.
+    pub async fn snapshot(&self) -> Snapshot<C> {
+        let block_hash: Option<C::Hash> =
+            self.request("chain_getBlockHash", rpc_params![]).await;
+        let block_hash =
+            block_hash.unwrap_or_else(|| panic!("node did not return a best block hash"));
+        log_info(&format!("took snapshot at block {:?}", block_hash));
+        Snapshot { block_hash }
+    }
+
+    /// Reverts the chain state to the given `snapshot`.
+    ///
+    /// All blocks after the one of the `snapshot` are discarded.
+    pub async fn revert(&self, snapshot: &Snapshot<C>) {
+        let _: serde_json::Value = self
+            .request("dev_setHead", rpc_params![snapshot.block_hash])
+            .await;
+        log_info(&format!(
+            "reverted to snapshot at block {:?}",
+            snapshot.block_hash
+        ));
+    }
+
+    /// Returns the timestamp of the best block in milliseconds.
+    async fn timestamp(&self) -> u64 {
+        let key = [twox_128(b"Timestamp"), twox_128(b"Now")].concat();
+        let now: Option<Bytes> = self
+            .request("state_getStorage", rpc_params![Bytes(key)])
+            .await;
+        now.map(|now| {
+            <u64 as scale::Decode>::decode(&mut &now[..])
+                .expect("unable to decode timestamp")
+        })
+        .unwrap_or_default()
+    }
+
+    /// Seals a new block with the given `timestamp` in milliseconds.
+    ///
+    /// Subsequent blocks continue from this timestamp, so that e.g. auction or
+    /// vesting contracts can be tested at a specific point in time without
+    /// waiting for or producing thousands of blocks.
+    ///
+    /// # Note
+    ///
+    /// The `Timestamp` pallet rejects blocks whose timestamp is not past the one of
+    /// their parent, hence the `timestamp` must lie in the future of the best block.
+    pub async fn set_timestamp(&self, timestamp: u64) {
+        let _: serde_json::Value =
+            self.request("dev_timeTravel", rpc_params![timestamp]).await;
+        let _: serde_json::Value = self.request("dev_newBlock", rpc_params![]).await;
+        log_info(&format!("sealed block at timestamp {}", timestamp));
+    }
+
+    /// Seals a new block whose timestamp is `duration` past the one of the best
+    /// block.
+    pub async fn skip_time(&self, duration: Duration) {
+        let millis = u64::try_from(duration.as_millis())
+            .expect("duration in milliseconds must fit into u64");
+        let timestamp = self.timestamp().await.saturating_add(millis);
+        self.set_timestamp(timestamp).await;
+    }
+
+    /// Clones the state of `contract` in the block `block_hash` of the live network
+    /// at `url`, e.g. `wss://rpc.shibuya.astar.network`, into the chain.
+    ///
+    /// This pulls the contract info, the code, the account and the storage of the
+    /// contract, so that tests can call the contract, e.g. an existing DEX pool, with
+    /// its realistic state instead of redeploying it. Contracts called by it are not
+    /// cloned and must be forked separately.
+    ///
+    /// # Note
+    ///
+    /// The storage keys and the contract info are derived from the metadata of the
+    /// live network. The live network must offer the `state` and `childstate` RPC
+    /// methods for the given block, i.e. be an archive node for older blocks.
+    pub async fn fork_from(&self, url: &str, block_hash: C::Hash, contract: C::AccountId)
+    where
+        C::AccountId: scale::Encode,
+    {
+        // Fail before fetching the state if it can't be set.
+        self.ensure_offered("dev_setStorage");
+        let entries = crate::fork::fetch_contract_state::<C>(
+            url,
+            block_hash,
+            &scale::Encode::encode(&contract),
+        )
+        .await;
+        let _: serde_json::Value =
+            self.request("dev_setStorage", rpc_params![entries]).await;
+        log_info(&format!(
+            "forked contract {:?} from block {:?} of {}",
+            contract, block_hash, url
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::{
+        server::ServerBuilder,
+        RpcModule,
+    };
+    use serde_json::{
+        json,
+        Value,
+    };
+    use std::sync::{
+        Arc,
+        Mutex,
+    };
+    use subxt::PolkadotConfig;
+
+    /// The requests received by a [`mock_node`].
+    type Requests = Arc<Mutex<Vec<(&'static str, Value)>>>;
+
+    /// The timestamp of the best block of a [`mock_node`].
+    const NOW: u64 = 1_000;
+
+    /// Starts a node offering the RPC `methods`, which records the requests and
+    /// answers them with `null`, except for `chain_getBlockHash` and
+    /// `state_getStorage`, which returns [`NOW`] for any key.
+    ///
+    /// Returns the URL of the node and its recorded requests.
+    async fn mock_node(methods: &[&'static str]) -> (String, Requests) {
+        let server = ServerBuilder::default()
+            .build("127.0.0.1:0")
+            .await
+            .expect("server must start");
+        let url = format!("ws://{}", server.local_addr().expect("server is bound"));
+        let requests = Requests::default();
+        let mut module = RpcModule::new(requests.clone());
+        let offered = methods.to_vec();
+        module
+            .register_method("rpc_methods", move |_, _| {
+                Ok(json!({ "version": 1, "methods": offered }))
+            })
+            .expect("method is registered once");
+        for &method in methods {
+            module
+                .register_method(method, move |params, requests| {
+                    let params: Value = params.parse().unwrap_or(Value::Null);
+                    requests.lock().unwrap().push((method, params));
+                    Ok(match method {
+                        "chain_getBlockHash" => json!(format!("0x{}", "01".repeat(32))),
+                        "state_getStorage" => json!(Bytes(scale::Encode::encode(&NOW))),
+                        _ => Value::Null,
+                    })
+                })
+                .expect("method is registered once");
+        }
+        let handle = server.start(module).expect("server must start");
+        // The server is stopped once the test process exits.
+        std::mem::forget(handle);
+        (url, requests)
+    }
+
+    #[tokio::test]
+    async fn snapshot_and_revert_work() {
+        let (url, requests) = mock_node(&["chain_getBlockHash", "dev_setHead"]).await;
+        let chopsticks = Chopsticks::<PolkadotConfig>::connect(&url).await;
+
+        let snapshot = chopsticks.snapshot().await;
+        assert_eq!(snapshot.block_hash(), sp_core::H256([0x01; 32]));
+        chopsticks.revert(&snapshot).await;
+
+        assert_eq!(
+            requests.lock().unwrap().last(),
+            Some(&("dev_setHead", json!([format!("0x{}", "01".repeat(32))])))
+        );
+    }
+
+    #[tokio::test]
+    async fn time_travel_works() {
+        let (url, requests) =
+            mock_node(&["state_getStorage", "dev_timeTravel", "dev_newBlock"]).await;
+        let chopsticks = Chopsticks::<PolkadotConfig>::connect(&url).await;
+
+        chopsticks.set_timestamp(5_000).await;
+        chopsticks.skip_time(Duration::from_secs(2)).await;
+
+        let requests = requests.lock().unwrap();
+        let methods = requests
+            .iter()
+            .map(|(method, _)| *method)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            methods,
+            [
+                "dev_timeTravel",
+                "dev_newBlock",
+                "state_getStorage",
+                "dev_timeTravel",
+                "dev_newBlock"
+            ]
+        );
+        assert_eq!(requests[0].1, json!([5_000]));
+        assert_eq!(requests[3].1, json!([NOW + 2_000]));
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "the node does not offer the RPC method `dev_setStorage`")]
+    async fn fork_fails_on_nodes_other_than_chopsticks() {
+        let (url, _) = mock_node(&["chain_getBlockHash"]).await;
+        let chopsticks = Chopsticks::<PolkadotConfig>::connect(&url).await;
+
+        // The state is not fetched, hence the network needs not be reachable.
+        chopsticks
+            .fork_from(
+                "ws://127.0.0.1:1",
+                sp_core::H256([0x01; 32]),
+                [0x01; 32].into(),
+            )
+            .await;
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "the node does not offer the RPC method `dev_setHead`")]
+    async fn revert_fails_on_nodes_other_than_chopsticks() {
+        let (url, _) = mock_node(&["chain_getBlockHash"]).await;
+        let chopsticks = Chopsticks::<PolkadotConfig>::connect(&url).await;
+
+        let snapshot = chopsticks.snapshot().await;
+        chopsticks.revert(&snapshot).await;
+    }
+}
@@ -13,33 +13,48 @@
 // limitations under the License.
 
 use super::{
+    bench::{
+        BenchReport,
+        Measurement,
+    },
+    chopsticks::Chopsticks,
     client::api::runtime_types::{
         frame_system::AccountInfo,
         pallet_balances::AccountData,
+        pallet_contracts::storage::ContractInfo,
+    },
+    contract_log::{
+        decode_log_records,
+        forward_log_records,
+        LogRecord,
     },
     log_error,
     log_info,
-    sr25519,
     xts::{
         self,
         api,
         Call,
+        Determinism,
+        Instantiate,
         InstantiateWithCode,
     },
     CodeUploadResult,
     ContractExecResult,
     ContractInstantiateResult,
     ContractsApi,
+    ImpersonatedSigner,
     InkConstructor,
     InkMessage,
-    Signer,
+    TxSigner,
 };
 use ink_env::Environment;
-
-use sp_runtime::traits::{
-    IdentifyAccount,
-    Verify,
+use ink_storage_traits::{
+    Storable,
+    StorageKey,
 };
+
+use pallet_contracts_primitives::StorageDeposit;
+use sp_weights::Weight;
 use subxt::{
     blocks::ExtrinsicEvents,
     ext::bitvec::macros::internal::funty::Fundamental,
@@ -73,6 +88,47 @@ where
     }
 }
 
+/// Determines the gas limit of the extrinsics submitted by the [`Client`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GasPolicy {
+    /// Dry runs the extrinsic first and uses the required weight of the dry run
+    /// multiplied by `margin` as gas limit.
+    ///
+    /// A `margin` above `1.0` leaves some head room for state changes happening
+    /// in between the dry run and the execution of the extrinsic.
+    Estimated {
+        /// The factor applied to the estimated weight.
+        margin: f64,
+    },
+    /// Uses the given weight as gas limit.
+    ///
+    /// The extrinsic is still dry run in order to catch errors early.
+    Fixed(Weight),
+}
+
+impl GasPolicy {
+    /// Returns the gas limit for an extrinsic with the given estimated weight.
+    pub fn gas_limit(&self, estimated: Weight) -> Weight {
+        match self {
+            Self::Estimated { margin } => {
+                let apply_margin = |value: u64| (value as f64 * margin) as u64;
+                Weight::from_parts(
+                    apply_margin(estimated.ref_time()),
+                    apply_margin(estimated.proof_size()),
+                )
+            }
+            Self::Fixed(gas_limit) => *gas_limit,
+        }
+    }
+}
+
+/// The default gas policy estimates the gas limit with a safety margin of 20%.
+impl Default for GasPolicy {
+    fn default() -> Self {
+        Self::Estimated { margin: 1.2 }
+    }
+}
+
 /// Result of a contract instantiation.
 pub struct InstantiationResult<C: subxt::Config, E: Environment> {
     /// The account id at which the contract was instantiated.
@@ -131,6 +187,8 @@ where
 
 /// Result of a contract call.
 pub struct CallResult<C: subxt::Config, E: Environment, V> {
+    /// The account id of the called contract.
+    pub account_id: C::AccountId,
     /// The result of the dry run, contains debug messages
     /// if there were any.
     pub dry_run: ContractExecResult<E::Balance>,
@@ -143,6 +201,72 @@ pub struct CallResult<C: subxt::Config, E: Environment, V> {
     pub value: V,
 }
 
+impl<C, E, V> CallResult<C, E, V>
+where
+    C: subxt::Config,
+    E: Environment,
+{
+    /// Returns the decoded ink! events of the given kind emitted by the called
+    /// contract during the call.
+    ///
+    /// Only `Contracts::ContractEmitted` events of the called contract are taken
+    /// into account. Events of other kinds or emitted by other contracts, e.g. the
+    /// callees of cross-contract calls, are skipped, so this works for any event
+    /// defined with `#[ink(event)]` by contracts of the same crate or its
+    /// dependencies.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let transfers = call_res.contract_events::<erc20::Transfer>();
+    /// assert_eq!(transfers.len(), 1);
+    /// assert_eq!(transfers[0].value, 100);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the events of the extrinsic can not be decoded.
+    pub fn contract_events<Event>(&self) -> Vec<Event>
+    where
+        Event: ink_env::test::DecodeEvent,
+    {
+        let emitted = self.events.iter().filter_map(|evt| {
+            let evt = evt.unwrap_or_else(|err| {
+                panic!("unable to unwrap event: {:?}", err);
+            });
+            evt.as_event::<ContractEmittedEvent<C>>()
+                .unwrap_or_else(|err| {
+                    panic!("event conversion to `ContractEmitted` failed: {:?}", err);
+                })
+        });
+        decode_contract_events(&self.account_id, emitted)
+    }
+}
+
+/// Returns the decoded ink! events of the given kind among the `emitted` events of
+/// the `contract`.
+fn decode_contract_events<C, Event>(
+    contract: &C::AccountId,
+    emitted: impl IntoIterator<Item = ContractEmittedEvent<C>>,
+) -> Vec<Event>
+where
+    C: subxt::Config,
+    Event: ink_env::test::DecodeEvent,
+{
+    emitted
+        .into_iter()
+        .filter(|emitted| &emitted.contract == contract)
+        .filter_map(|emitted| {
+            // The topics are not part of the `ContractEmitted` event and
+            // not required to decode the event data.
+            Event::decode_event(&ink_env::test::EmittedEvent {
+                topics: Vec::new(),
+                data: emitted.data,
+            })
+        })
+        .collect()
+}
+
 /// We implement a custom `Debug` here, as to avoid requiring the trait
 /// bound `Debug` for `E`.
 // TODO(#xxx) Improve the `Debug` implementation.
@@ -154,12 +278,120 @@ where
 {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         f.debug_struct("CallResult")
+            .field("account_id", &self.account_id)
             .field("dry_run", &self.dry_run)
             .field("events", &self.events)
             .finish()
     }
 }
 
+/// The change of a balance.
+///
+/// An unchanged balance is an increase by zero.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BalanceDelta<Balance> {
+    /// The balance increased by the given amount.
+    Increase(Balance),
+    /// The balance decreased by the given amount.
+    Decrease(Balance),
+}
+
+impl<Balance> BalanceDelta<Balance>
+where
+    Balance: Ord + core::ops::Sub<Output = Balance>,
+{
+    /// Returns the change from the balance `before` to the balance `after`.
+    pub fn between(before: Balance, after: Balance) -> Self {
+        if after >= before {
+            Self::Increase(after - before)
+        } else {
+            Self::Decrease(before - after)
+        }
+    }
+}
+
+/// The balance changes caused by a contract call, see [`Client::call_with_accounting`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallAccounting<Balance> {
+    /// The change of the free balance of the caller.
+    ///
+    /// Includes the transferred value, the fee and the storage deposit.
+    pub caller_balance: BalanceDelta<Balance>,
+    /// The change of the free balance of the contract.
+    pub contract_balance: BalanceDelta<Balance>,
+    /// The storage deposit charged from or refunded to the caller.
+    ///
+    /// The deposit is held as reserved balance of the contract, hence it is not
+    /// part of the change of its free balance.
+    pub storage_deposit: StorageDeposit<Balance>,
+    /// The transaction fee paid by the caller, including the tip.
+    pub fee: Balance,
+}
+
+/// Result of a dry run of a contract call.
+///
+/// No extrinsic is submitted for a dry run, hence there are no events.
+pub struct CallDryRunResult<E: Environment, V> {
+    /// The result of the dry run, contains debug messages
+    /// if there were any.
+    pub exec_result: ContractExecResult<E::Balance>,
+    /// Contains the return value of the called function.
+    ///
+    /// This field contains the decoded `data` from the dry-run,
+    /// the raw data is available under `exec_result.result.data`.
+    pub value: V,
+}
+
+impl<E, V> CallDryRunResult<E, V>
+where
+    E: Environment,
+{
+    /// Returns the weight consumed by the dry run.
+    pub fn gas_consumed(&self) -> Weight {
+        self.exec_result.gas_consumed
+    }
+
+    /// Returns the weight required to execute the call.
+    ///
+    /// This can be higher than [`CallDryRunResult::gas_consumed`] due to
+    /// refunds happening during the execution.
+    pub fn gas_required(&self) -> Weight {
+        self.exec_result.gas_required
+    }
+
+    /// Returns the estimated storage deposit charged or refunded by the call.
+    pub fn storage_deposit(&self) -> &StorageDeposit<E::Balance> {
+        &self.exec_result.storage_deposit
+    }
+
+    /// Returns the debug message emitted by the contract during the dry run.
+    pub fn debug_message(&self) -> String {
+        String::from_utf8_lossy(&self.exec_result.debug_message).into()
+    }
+
+    /// Returns the records written by the `ink::log` macros of the contract during
+    /// the dry run.
+    pub fn log_records(&self) -> Vec<LogRecord> {
+        decode_log_records(&self.debug_message())
+    }
+}
+
+/// We implement a custom `Debug` here, as to avoid requiring the trait
+/// bound `Debug` for `E`.
+impl<E, V> core::fmt::Debug for CallDryRunResult<E, V>
+where
+    E: Environment,
+    <E as Environment>::Balance: core::fmt::Debug,
+    V: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("CallDryRunResult")
+            .field("exec_result", &self.exec_result)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
 /// An error occurred while interacting with the Substrate node.
 ///
 /// We only convey errors here that are caused by the contract's
@@ -184,6 +416,8 @@ where
     CallDryRun(ContractExecResult<E::Balance>),
     /// The `call` extrinsic failed.
     CallExtrinsic(subxt::error::DispatchError),
+    /// The `set_code` extrinsic of a runtime upgrade failed.
+    RuntimeUpgradeExtrinsic(subxt::error::DispatchError),
 }
 
 // We implement a custom `Debug` here, as to avoid requiring the trait
@@ -208,6 +442,7 @@ where
             Error::UploadExtrinsic(_) => f.write_str("UploadExtrinsic"),
             Error::CallDryRun(_) => f.write_str("CallDryRun"),
             Error::CallExtrinsic(_) => f.write_str("CallExtrinsic"),
+            Error::RuntimeUpgradeExtrinsic(_) => f.write_str("RuntimeUpgradeExtrinsic"),
         }
     }
 }
@@ -244,6 +479,43 @@ where
     const EVENT: &'static str = "CodeStored";
 }
 
+/// A custom event emitted by a contract.
+#[derive(Debug, scale::Decode, scale::Encode)]
+struct ContractEmittedEvent<C: subxt::Config> {
+    /// Account id of the contract that emitted the event.
+    pub contract: C::AccountId,
+    /// The encoded data of the ink! event.
+    pub data: Vec<u8>,
+}
+
+impl<C> subxt::events::StaticEvent for ContractEmittedEvent<C>
+where
+    C: subxt::Config,
+{
+    const PALLET: &'static str = "Contracts";
+    const EVENT: &'static str = "ContractEmitted";
+}
+
+/// A transaction fee was paid by a transactor.
+#[derive(Debug, scale::Decode, scale::Encode)]
+struct TransactionFeePaidEvent<C: subxt::Config, E: Environment> {
+    /// Account id of the transactor.
+    pub who: C::AccountId,
+    /// The fee paid, including the tip.
+    pub actual_fee: E::Balance,
+    /// The tip paid.
+    pub tip: E::Balance,
+}
+
+impl<C, E> subxt::events::StaticEvent for TransactionFeePaidEvent<C, E>
+where
+    C: subxt::Config,
+    E: Environment,
+{
+    const PALLET: &'static str = "TransactionPayment";
+    const EVENT: &'static str = "TransactionFeePaid";
+}
+
 /// The `Client` takes care of communicating with the node.
 ///
 /// This node's RPC interface will be used for instantiating the contract
@@ -254,6 +526,8 @@ where
     E: Environment,
 {
     api: ContractsApi<C, E>,
+    gas_policy: GasPolicy,
+    url: String,
 }
 
 impl<C, E> Client<C, E>
@@ -261,21 +535,26 @@ where
     C: subxt::Config,
     C::AccountId: Into<C::Address> + serde::de::DeserializeOwned,
     C::Address: From<C::AccountId>,
-    C::Signature: From<sr25519::Signature>,
-    <C::Signature as Verify>::Signer: From<sr25519::Public>,
     <C::ExtrinsicParams as ExtrinsicParams<C::Index, C::Hash>>::OtherParams: Default,
-    <C::Signature as Verify>::Signer:
-        From<sr25519::Public> + IdentifyAccount<AccountId = C::AccountId>,
-    sr25519::Signature: Into<C::Signature>,
 
     E: Environment,
     E::Balance: core::fmt::Debug + scale::Encode + serde::Serialize,
 
     Call<C, E::Balance>: scale::Encode,
     InstantiateWithCode<E::Balance>: scale::Encode,
+    Instantiate<C, E::Balance>: scale::Encode,
 {
     /// Creates a new [`Client`] instance.
     pub async fn new(url: &str) -> Self {
+        Self {
+            api: Self::connect(url).await,
+            gas_policy: GasPolicy::default(),
+            url: url.to_string(),
+        }
+    }
+
+    /// Connects to the node at `url`.
+    async fn connect(url: &str) -> ContractsApi<C, E> {
         let client = subxt::OnlineClient::from_url(url)
             .await
             .unwrap_or_else(|err| {
@@ -289,10 +568,118 @@ where
                 );
                 panic!("Unable to create client: {:?}", err);
             });
+        ContractsApi::new(client, url).await
+    }
 
-        Self {
-            api: ContractsApi::new(client, url).await,
+    /// Returns the [`GasPolicy`] used for submitted extrinsics.
+    pub fn gas_policy(&self) -> GasPolicy {
+        self.gas_policy
+    }
+
+    /// Sets the [`GasPolicy`] used for submitted extrinsics.
+    pub fn set_gas_policy(&mut self, gas_policy: GasPolicy) {
+        self.gas_policy = gas_policy;
+    }
+
+    /// Returns a handle to the RPC methods of a chopsticks node, e.g. to take
+    /// snapshots of the chain state.
+    ///
+    /// See the [`chopsticks`](crate::chopsticks) module for how to run the tests
+    /// against a chopsticks node.
+    ///
+    /// # Panics
+    ///
+    /// If the node can't be reached. Calling the methods of the handle panics if
+    /// the node is not a chopsticks node.
+    pub async fn chopsticks(&self) -> Chopsticks<C> {
+        Chopsticks::connect(&self.url).await
+    }
+
+    /// Returns the timestamp of the best block in milliseconds.
+    pub async fn timestamp(&self) -> u64 {
+        let now_addr = subxt::storage::StaticStorageAddress::<
+            DecodeStaticType<u64>,
+            Yes,
+            Yes,
+            (),
+        >::new("Timestamp", "Now", vec![], Default::default())
+        .unvalidated();
+
+        self.api
+            .client
+            .storage()
+            .fetch_or_default(&now_addr, None)
+            .await
+            .unwrap_or_else(|err| {
+                panic!("unable to fetch timestamp: {:?}", err);
+            })
+    }
+
+    /// Upgrades the runtime of the chain to the Wasm blob `code`.
+    ///
+    /// The upgrade is dispatched as `System::set_code` via `Sudo`, hence the
+    /// `signer` must be the sudo key of the chain, e.g. [`alice`](crate::alice) on
+    /// development nodes. Afterwards the client reconnects to the node, so that
+    /// subsequent extrinsics use the metadata of the upgraded runtime.
+    ///
+    /// This allows to test that deployed contracts keep working across runtime
+    /// upgrades, which may change weights or the host functions available to
+    /// contracts.
+    pub async fn runtime_upgrade<S: TxSigner<C>>(
+        &mut self,
+        signer: &mut S,
+        code: Vec<u8>,
+    ) -> Result<(), Error<C, E>> {
+        let tx_events = self.api.sudo_set_code(code, signer).await;
+
+        for evt in tx_events.iter() {
+            let evt = evt.unwrap_or_else(|err| {
+                panic!("unable to unwrap event: {:?}", err);
+            });
+
+            let metadata = self.api.client.metadata();
+            let dispatch_error = if let Some(sudid) = evt
+                .as_event::<xts::api::sudo::events::Sudid>()
+                .unwrap_or_else(|err| {
+                    panic!("event conversion to `Sudid` failed: {:?}", err)
+                }) {
+                // The field bytes are the encoded `Result`, the error follows
+                // the leading variant index.
+                sudid.sudo_result.err().map(|_| {
+                    subxt::error::DispatchError::decode_from(
+                        &evt.field_bytes()[1..],
+                        &metadata,
+                    )
+                })
+            } else if evt
+                .as_event::<xts::api::system::events::ExtrinsicFailed>()
+                .unwrap_or_else(|err| {
+                    panic!("event conversion to `ExtrinsicFailed` failed: {:?}", err)
+                })
+                .is_some()
+            {
+                Some(subxt::error::DispatchError::decode_from(
+                    evt.field_bytes(),
+                    &metadata,
+                ))
+            } else {
+                None
+            };
+            if let Some(dispatch_error) = dispatch_error {
+                log_error(&format!(
+                    "extrinsic for runtime upgrade failed: {:?}",
+                    dispatch_error
+                ));
+                return Err(Error::RuntimeUpgradeExtrinsic(dispatch_error))
+            }
         }
+
+        self.api = Self::connect(&self.url).await;
+        log_info(&format!(
+            "upgraded runtime to spec version {}",
+            self.api.client.runtime_version().spec_version
+        ));
+        Ok(())
     }
 
     /// This function extracts the metadata of the contract at the file path
@@ -303,9 +690,9 @@ where
     /// Calling this function multiple times is idempotent, the contract is
     /// newly instantiated each time using a unique salt. No existing contract
     /// instance is reused!
-    pub async fn instantiate<CO>(
+    pub async fn instantiate<CO, S: TxSigner<C>>(
         &mut self,
-        signer: &mut Signer<C>,
+        signer: &mut S,
         constructor: CO,
         value: E::Balance,
         storage_deposit_limit: Option<E::Balance>,
@@ -322,9 +709,9 @@ where
     }
 
     /// Dry run contract instantiation using the given constructor.
-    pub async fn instantiate_dry_run<CO: InkConstructor>(
+    pub async fn instantiate_dry_run<CO: InkConstructor, S: TxSigner<C>>(
         &mut self,
-        signer: &Signer<C>,
+        signer: &S,
         constructor: &CO,
         value: E::Balance,
         storage_deposit_limit: Option<E::Balance>,
@@ -350,9 +737,9 @@ where
     }
 
     /// Executes an `instantiate_with_code` call and captures the resulting events.
-    async fn exec_instantiate<CO: InkConstructor>(
+    async fn exec_instantiate<CO: InkConstructor, S: TxSigner<C>>(
         &mut self,
-        signer: &mut Signer<C>,
+        signer: &mut S,
         value: E::Balance,
         storage_deposit_limit: Option<E::Balance>,
         code: Vec<u8>,
@@ -383,6 +770,7 @@ where
             "instantiate dry run debug message: {:?}",
             String::from_utf8_lossy(&dry_run.debug_message)
         ));
+        forward_log_records(&dry_run.debug_message);
         log_info(&format!("instantiate dry run result: {:?}", dry_run.result));
         if dry_run.result.is_err() {
             return Err(Error::InstantiateDryRun(dry_run))
@@ -392,7 +780,7 @@ where
             .api
             .instantiate_with_code(
                 value,
-                dry_run.gas_required,
+                self.gas_policy.gas_limit(dry_run.gas_required),
                 storage_deposit_limit,
                 code,
                 data.clone(),
@@ -401,6 +789,70 @@ where
             )
             .await;
 
+        self.instantiation_result(dry_run, tx_events)
+    }
+
+    /// Instantiates the code uploaded with `code_hash` using the given constructor.
+    ///
+    /// In contrast to [`Client::instantiate`], the code is not uploaded by the
+    /// `signer`. This allows testing chains which restrict the origins allowed to
+    /// upload code, by uploading the code with a privileged account first.
+    pub async fn instantiate_with_code_hash<CO, S: TxSigner<C>>(
+        &mut self,
+        signer: &mut S,
+        code_hash: C::Hash,
+        constructor: CO,
+        value: E::Balance,
+        storage_deposit_limit: Option<E::Balance>,
+    ) -> Result<InstantiationResult<C, E>, Error<C, E>>
+    where
+        CO: InkConstructor,
+    {
+        let mut data = CO::SELECTOR.to_vec();
+        <CO as scale::Encode>::encode_to(&constructor, &mut data);
+        let salt = Self::salt();
+
+        let dry_run = self
+            .api
+            .instantiate_dry_run(
+                value,
+                storage_deposit_limit,
+                code_hash,
+                data.clone(),
+                salt.clone(),
+                signer,
+            )
+            .await;
+        log_info(&format!("instantiate dry run result: {:?}", dry_run.result));
+        if dry_run.result.is_err() {
+            return Err(Error::InstantiateDryRun(dry_run))
+        }
+
+        let tx_events = self
+            .api
+            .instantiate(
+                value,
+                self.gas_policy.gas_limit(dry_run.gas_required),
+                storage_deposit_limit,
+                code_hash,
+                data,
+                salt,
+                signer,
+            )
+            .await;
+
+        let ret = self.instantiation_result(dry_run, tx_events)?;
+        log_info(&format!("instantiated contract at {:?}", ret.account_id));
+        Ok(ret)
+    }
+
+    /// Returns the result of a submitted instantiation with the given `dry_run`, or
+    /// an error if the extrinsic failed.
+    fn instantiation_result(
+        &self,
+        dry_run: ContractInstantiateResult<C::AccountId, E::Balance>,
+        tx_events: ExtrinsicEvents<C>,
+    ) -> Result<InstantiationResult<C, E>, Error<C, E>> {
         let mut account_id = None;
         for evt in tx_events.iter() {
             let evt = evt.unwrap_or_else(|err| {
@@ -470,38 +922,69 @@ where
     /// Calling this function multiple times is idempotent, the contract is
     /// newly instantiated each time using a unique salt. No existing contract
     /// instance is reused!
-    pub async fn upload(
+    pub async fn upload<S: TxSigner<C>>(
         &mut self,
-        signer: &mut Signer<C>,
+        signer: &mut S,
         contract_path: &str,
         storage_deposit_limit: Option<E::Balance>,
     ) -> Result<UploadResult<C, E>, Error<C, E>> {
         let code = crate::utils::extract_wasm(contract_path);
         let ret = self
-            .exec_upload(signer, code, storage_deposit_limit)
+            .exec_upload(signer, code, storage_deposit_limit, None)
             .await?;
         log_info(&format!("contract stored with hash {:?}", ret.code_hash));
         Ok(ret)
     }
 
-    /// Executes an `instantiate_with_code` call and captures the resulting events.
-    async fn exec_upload(
+    /// Uploads the contract at `contract_path` like [`Client::upload`], but with the
+    /// given `determinism`.
+    ///
+    /// Uploading code with [`Determinism::Relaxed`] allows it to use indeterministic
+    /// instructions, but such code can only be executed by dry runs.
+    ///
+    /// # Note
+    ///
+    /// This requires a node whose `pallet-contracts` supports indeterministic code.
+    pub async fn upload_with_determinism<S: TxSigner<C>>(
         &mut self,
-        signer: &mut Signer<C>,
+        signer: &mut S,
+        contract_path: &str,
+        storage_deposit_limit: Option<E::Balance>,
+        determinism: Determinism,
+    ) -> Result<UploadResult<C, E>, Error<C, E>> {
+        let code = crate::utils::extract_wasm(contract_path);
+        let ret = self
+            .exec_upload(signer, code, storage_deposit_limit, Some(determinism))
+            .await?;
+        log_info(&format!(
+            "contract stored with hash {:?} and determinism {:?}",
+            ret.code_hash, determinism
+        ));
+        Ok(ret)
+    }
+
+    /// Executes an `upload_code` call and captures the resulting events.
+    async fn exec_upload<S: TxSigner<C>>(
+        &mut self,
+        signer: &mut S,
         code: Vec<u8>,
         storage_deposit_limit: Option<E::Balance>,
+        determinism: Option<Determinism>,
     ) -> Result<UploadResult<C, E>, Error<C, E>> {
         // dry run the instantiate to calculate the gas limit
         let dry_run = self
             .api
-            .upload_dry_run(signer, code.clone(), storage_deposit_limit)
+            .upload_dry_run(signer, code.clone(), storage_deposit_limit, determinism)
             .await;
         log_info(&format!("upload dry run: {:?}", dry_run));
         if dry_run.is_err() {
             return Err(Error::UploadDryRun(dry_run))
         }
 
-        let tx_events = self.api.upload(signer, code, storage_deposit_limit).await;
+        let tx_events = self
+            .api
+            .upload(signer, code, storage_deposit_limit, determinism)
+            .await;
 
         let mut hash = None;
         for evt in tx_events.iter() {
@@ -563,11 +1046,13 @@ where
 
     /// Executes a `call` for the contract at `account_id`.
     ///
+    /// The gas limit of the call is determined by the client's [`GasPolicy`].
+    ///
     /// Returns when the transaction is included in a block. The return value
     /// contains all events that are associated with this transaction.
-    pub async fn call<M>(
+    pub async fn call<M, S: TxSigner<C>>(
         &mut self,
-        signer: &mut Signer<C>,
+        signer: &mut S,
         account_id: C::AccountId,
         contract_call: M,
         value: E::Balance,
@@ -595,6 +1080,7 @@ where
             "call dry run debug message: {}",
             String::from_utf8_lossy(&dry_run.debug_message)
         ));
+        forward_log_records(&dry_run.debug_message);
         if dry_run.result.is_err() {
             return Err(Error::CallDryRun(dry_run))
         }
@@ -602,15 +1088,300 @@ where
         let tx_events = self
             .api
             .call(
-                sp_runtime::MultiAddress::Id(account_id),
+                sp_runtime::MultiAddress::Id(account_id.clone()),
                 value,
-                dry_run.gas_required,
+                self.gas_policy.gas_limit(dry_run.gas_required),
                 storage_deposit_limit,
                 contract_call.0.clone(),
                 signer,
             )
             .await;
 
+        self.call_result(account_id, dry_run, tx_events)
+    }
+
+    /// Executes a `call` for the contract at `account_id` on behalf of the account
+    /// impersonated by `signer`, see [`signer_from_account_id`](crate::signer_from_account_id).
+    ///
+    /// The `Contracts::call` extrinsic is dispatched via `Sudo::sudo_as`, hence the
+    /// transaction fee is paid by the sudo key instead of the impersonated account.
+    ///
+    /// Returns when the transaction is included in a block. The return value
+    /// contains all events that are associated with this transaction.
+    pub async fn call_as<M, S: TxSigner<C>>(
+        &mut self,
+        signer: &ImpersonatedSigner<C, S>,
+        account_id: C::AccountId,
+        contract_call: M,
+        value: E::Balance,
+        storage_deposit_limit: Option<E::Balance>,
+    ) -> Result<CallResult<C, E, <M as InkMessage>::ReturnType>, Error<C, E>>
+    where
+        M: InkMessage,
+        <M as InkMessage>::ReturnType: scale::Decode,
+    {
+        let contract_call: EncodedMessage = contract_call.into();
+        log_info(&format!(
+            "call as {:?}: {:02X?}",
+            signer.account_id(),
+            contract_call.0
+        ));
+
+        let dry_run = self
+            .api
+            .call_dry_run(
+                signer.account_id().clone(),
+                account_id.clone(),
+                value,
+                None,
+                contract_call.0.clone(),
+            )
+            .await;
+        log_info(&format!("call dry run: {:?}", &dry_run.result));
+        log_info(&format!(
+            "call dry run debug message: {}",
+            String::from_utf8_lossy(&dry_run.debug_message)
+        ));
+        forward_log_records(&dry_run.debug_message);
+        if dry_run.result.is_err() {
+            return Err(Error::CallDryRun(dry_run))
+        }
+
+        let tx_events = self
+            .api
+            .sudo_call_as(
+                signer.account_id().clone(),
+                sp_runtime::MultiAddress::Id(account_id.clone()),
+                value,
+                self.gas_policy.gas_limit(dry_run.gas_required),
+                storage_deposit_limit,
+                contract_call.0.clone(),
+                signer.sudo(),
+            )
+            .await;
+
+        for evt in tx_events.iter() {
+            let evt = evt.unwrap_or_else(|err| {
+                panic!("unable to unwrap event: {:?}", err);
+            });
+            let sudo_as_done = evt
+                .as_event::<xts::api::sudo::events::SudoAsDone>()
+                .unwrap_or_else(|err| {
+                    panic!("event conversion to `SudoAsDone` failed: {:?}", err)
+                });
+            if let Some(sudo_as_done) = sudo_as_done {
+                if sudo_as_done.sudo_result.is_err() {
+                    // The field bytes are the encoded `Result`, the error follows
+                    // the leading variant index.
+                    let metadata = self.api.client.metadata();
+                    let dispatch_error = subxt::error::DispatchError::decode_from(
+                        &evt.field_bytes()[1..],
+                        &metadata,
+                    );
+                    log_error(&format!(
+                        "extrinsic for call as {:?} failed: {:?}",
+                        signer.account_id(),
+                        dispatch_error
+                    ));
+                    return Err(Error::CallExtrinsic(dispatch_error))
+                }
+            }
+        }
+
+        self.call_result(account_id, dry_run, tx_events)
+    }
+
+    /// Executes a `call` for the contract at `account_id`, like [`Client::call`],
+    /// and reports the balance changes it caused.
+    ///
+    /// The balances are queried before and after the call, so other transactions
+    /// included in the same blocks affect the report.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let (_, accounting) = client
+    ///     .call_with_accounting(&mut ink_e2e::bob(), contract_acc_id, terminate_me(), 0, None)
+    ///     .await
+    ///     .expect("terminate failed");
+    /// assert!(matches!(accounting.storage_deposit, StorageDeposit::Refund(_)));
+    /// ```
+    pub async fn call_with_accounting<M, S: TxSigner<C>>(
+        &mut self,
+        signer: &mut S,
+        account_id: C::AccountId,
+        contract_call: M,
+        value: E::Balance,
+        storage_deposit_limit: Option<E::Balance>,
+    ) -> Result<
+        (
+            CallResult<C, E, <M as InkMessage>::ReturnType>,
+            CallAccounting<E::Balance>,
+        ),
+        Error<C, E>,
+    >
+    where
+        M: InkMessage,
+        <M as InkMessage>::ReturnType: scale::Decode,
+    {
+        let caller = signer.account_id().clone();
+        let caller_before = self.account_data(caller.clone()).await;
+        let contract_before = self.account_data(account_id.clone()).await;
+
+        let call_result = self
+            .call(
+                signer,
+                account_id.clone(),
+                contract_call,
+                value,
+                storage_deposit_limit,
+            )
+            .await?;
+
+        let caller_after = self.account_data(caller.clone()).await;
+        let contract_after = self.account_data(account_id).await;
+        let storage_deposit = match BalanceDelta::between(
+            contract_before.reserved,
+            contract_after.reserved,
+        ) {
+            BalanceDelta::Increase(charged) => StorageDeposit::Charge(charged),
+            BalanceDelta::Decrease(refunded) => StorageDeposit::Refund(refunded),
+        };
+        let accounting = CallAccounting {
+            caller_balance: BalanceDelta::between(caller_before.free, caller_after.free),
+            contract_balance: BalanceDelta::between(
+                contract_before.free,
+                contract_after.free,
+            ),
+            storage_deposit,
+            fee: Self::transaction_fee(&call_result.events, &caller),
+        };
+        log_info(&format!("call accounting: {:?}", accounting));
+        Ok((call_result, accounting))
+    }
+
+    /// Returns the transaction fee paid by `payer` for the extrinsic of `tx_events`.
+    fn transaction_fee(
+        tx_events: &ExtrinsicEvents<C>,
+        payer: &C::AccountId,
+    ) -> E::Balance {
+        let mut fee = E::Balance::from(0u8);
+        for evt in tx_events.iter() {
+            let evt = evt.unwrap_or_else(|err| {
+                panic!("unable to unwrap event: {:?}", err);
+            });
+            let fee_paid = evt
+                .as_event::<TransactionFeePaidEvent<C, E>>()
+                .unwrap_or_else(|err| {
+                    panic!("event conversion to `TransactionFeePaid` failed: {:?}", err)
+                });
+            if let Some(fee_paid) = fee_paid {
+                if &fee_paid.who == payer {
+                    fee += fee_paid.actual_fee;
+                }
+            }
+        }
+        fee
+    }
+
+    /// Executes a batch of `call`s, each given as a tuple of the signer, the
+    /// account id of the called contract, the message and the transferred value.
+    ///
+    /// All extrinsics are submitted before waiting for any of them to be included,
+    /// so that a node which does not seal a block per transaction includes them in
+    /// the same block, in the order of the batch. This allows simulating concurrent
+    /// users, e.g. a transaction front-running another one.
+    ///
+    /// Returns the result of every call in the order of the batch. Calls whose dry
+    /// run fails are not submitted. Since all dry runs are executed before any
+    /// call is submitted, the returned values are those of the dry runs against
+    /// the state before the batch.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let results = client
+    ///     .call_batch(vec![
+    ///         (&ink_e2e::alice(), auction_id.clone(), bid(10), 10),
+    ///         (&ink_e2e::bob(), auction_id.clone(), bid(11), 11),
+    ///     ])
+    ///     .await;
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub async fn call_batch<M, S: TxSigner<C>>(
+        &mut self,
+        calls: Vec<(&S, C::AccountId, M, E::Balance)>,
+    ) -> Vec<Result<CallResult<C, E, <M as InkMessage>::ReturnType>, Error<C, E>>>
+    where
+        M: InkMessage,
+        <M as InkMessage>::ReturnType: scale::Decode,
+    {
+        let mut dry_runs = Vec::with_capacity(calls.len());
+        for (signer, account_id, contract_call, value) in calls {
+            let contract_call: EncodedMessage = contract_call.into();
+            log_info(&format!("batched call: {:02X?}", contract_call.0));
+
+            let dry_run = self
+                .api
+                .call_dry_run(
+                    signer.account_id().clone(),
+                    account_id.clone(),
+                    value,
+                    None,
+                    contract_call.0.clone(),
+                )
+                .await;
+            log_info(&format!("batched call dry run: {:?}", &dry_run.result));
+            dry_runs.push((signer, account_id, contract_call, value, dry_run));
+        }
+
+        let mut submitted = Vec::with_capacity(dry_runs.len());
+        for (signer, account_id, contract_call, value, dry_run) in dry_runs {
+            if dry_run.result.is_err() {
+                submitted.push(Err(Error::CallDryRun(dry_run)));
+                continue
+            }
+            let tx_progress = self
+                .api
+                .submit_call(
+                    sp_runtime::MultiAddress::Id(account_id.clone()),
+                    value,
+                    self.gas_policy.gas_limit(dry_run.gas_required),
+                    None,
+                    contract_call.0,
+                    signer,
+                )
+                .await;
+            submitted.push(Ok((account_id, dry_run, tx_progress)));
+        }
+
+        let mut results = Vec::with_capacity(submitted.len());
+        for submitted in submitted {
+            let result = match submitted {
+                Ok((account_id, dry_run, tx_progress)) => {
+                    let tx_events =
+                        ContractsApi::<C, E>::wait_for_events(tx_progress).await;
+                    self.call_result(account_id, dry_run, tx_events)
+                }
+                Err(err) => Err(err),
+            };
+            results.push(result);
+        }
+        results
+    }
+
+    /// Returns the result of a submitted call of the contract at `account_id` with
+    /// the given `dry_run`, or an error
+    /// if the extrinsic failed.
+    fn call_result<V>(
+        &self,
+        account_id: C::AccountId,
+        dry_run: ContractExecResult<E::Balance>,
+        tx_events: ExtrinsicEvents<C>,
+    ) -> Result<CallResult<C, E, V>, Error<C, E>>
+    where
+        V: scale::Decode,
+    {
         for evt in tx_events.iter() {
             let evt = evt.unwrap_or_else(|err| {
                 panic!("unable to unwrap event: {:?}", err);
@@ -634,6 +1405,64 @@ where
         }
 
         let bytes = &dry_run.result.as_ref().unwrap().data;
+        let value: V = scale::Decode::decode(&mut bytes.as_ref()).unwrap_or_else(|err| {
+            panic!(
+                "decoding dry run result to ink! message return type failed: {}",
+                err
+            )
+        });
+
+        Ok(CallResult {
+            account_id,
+            value,
+            dry_run,
+            events: tx_events,
+        })
+    }
+
+    /// Dry runs a `call` for the contract at `account_id`.
+    ///
+    /// This uses the contracts RPC to simulate the call, no extrinsic is
+    /// submitted. Hence the call neither costs any balance nor waits for a
+    /// block, which makes it a good fit for read-only interactions.
+    ///
+    /// The returned [`CallDryRunResult`] contains the decoded return value
+    /// as well as the consumed gas and the estimated storage deposit.
+    pub async fn call_dry_run<M, S: TxSigner<C>>(
+        &self,
+        signer: &S,
+        account_id: C::AccountId,
+        contract_call: M,
+        value: E::Balance,
+    ) -> Result<CallDryRunResult<E, <M as InkMessage>::ReturnType>, Error<C, E>>
+    where
+        M: InkMessage,
+        <M as InkMessage>::ReturnType: scale::Decode,
+    {
+        let contract_call: EncodedMessage = contract_call.into();
+        log_info(&format!("call dry run: {:02X?}", contract_call.0));
+
+        let exec_result = self
+            .api
+            .call_dry_run(
+                signer.account_id().clone(),
+                account_id,
+                value,
+                None,
+                contract_call.0,
+            )
+            .await;
+        log_info(&format!("call dry run result: {:?}", &exec_result.result));
+        log_info(&format!(
+            "call dry run debug message: {}",
+            String::from_utf8_lossy(&exec_result.debug_message)
+        ));
+        forward_log_records(&exec_result.debug_message);
+
+        let bytes = match &exec_result.result {
+            Ok(exec_return_value) => exec_return_value.data.clone(),
+            Err(_) => return Err(Error::CallDryRun(exec_result)),
+        };
         let value: <M as InkMessage>::ReturnType =
             scale::Decode::decode(&mut bytes.as_ref()).unwrap_or_else(|err| {
                 panic!(
@@ -642,11 +1471,41 @@ where
                 )
             });
 
-        Ok(CallResult {
-            value,
-            dry_run,
-            events: tx_events,
-        })
+        Ok(CallDryRunResult { exec_result, value })
+    }
+
+    /// Executes the message returned by `message` `runs` times for the contract
+    /// at `account_id` and returns a report of the median cost of all runs.
+    ///
+    /// The report is logged at the `info` level as well. Each run is a regular [`Client::call`], so messages that mutate storage
+    /// are measured against the state left behind by the previous run.
+    ///
+    /// See the [`bench`](crate::bench) module for how to fail on regressions.
+    pub async fn bench_call<M, F, S: TxSigner<C>>(
+        &mut self,
+        name: &str,
+        signer: &mut S,
+        account_id: C::AccountId,
+        value: E::Balance,
+        runs: usize,
+        mut message: F,
+    ) -> Result<BenchReport, Error<C, E>>
+    where
+        M: InkMessage,
+        <M as InkMessage>::ReturnType: scale::Decode,
+        F: FnMut() -> M,
+        E::Balance: Into<u128>,
+    {
+        let mut measurements = Vec::with_capacity(runs);
+        for _ in 0..runs {
+            let result = self
+                .call(signer, account_id.clone(), message(), value, None)
+                .await?;
+            measurements.push(Measurement::from_exec_result(&result.dry_run));
+        }
+        let report = BenchReport::new(name, &measurements);
+        log_info(&report.to_string());
+        Ok(report)
     }
 
     /// Returns the balance of `account_id`.
@@ -654,6 +1513,84 @@ where
         &self,
         account_id: C::AccountId,
     ) -> Result<E::Balance, Error<C, E>> {
+        Ok(self.account_data(account_id).await.free)
+    }
+
+    /// Returns the storage of the contract at `contract` decoded as `T`.
+    ///
+    /// `T` is the `#[ink(storage)]` struct of the contract. Its packed fields are
+    /// read from the contract's child trie under the root key of its storage layout,
+    /// hence e2e tests can assert the internal state of a contract without getter
+    /// messages only added for the tests:
+    ///
+    /// ```ignore
+    /// let delegator = client.read_storage::<Delegator>(contract_acc_id).await;
+    /// assert_eq!(delegator.which, Which::Adder);
+    /// ```
+    ///
+    /// # Note
+    ///
+    /// Lazy fields, such as `Mapping` or `Lazy`, are stored under their own keys and
+    /// are not fetched.
+    ///
+    /// # Panics
+    ///
+    /// If there is no contract at `contract` or its storage can't be decoded as `T`.
+    pub async fn read_storage<T>(&self, contract: C::AccountId) -> T
+    where
+        T: Storable + StorageKey,
+    {
+        let contract_info_addr = subxt::storage::StaticStorageAddress::<
+            DecodeStaticType<ContractInfo>,
+            Yes,
+            (),
+            (),
+        >::new(
+            "Contracts",
+            "ContractInfoOf",
+            vec![StorageMapKey::new(
+                contract.clone(),
+                StorageHasher::Twox64Concat,
+            )],
+            Default::default(),
+        )
+        .unvalidated();
+
+        let contract_info = self
+            .api
+            .client
+            .storage()
+            .fetch(&contract_info_addr, None)
+            .await
+            .unwrap_or_else(|err| {
+                panic!("unable to fetch contract info: {:?}", err);
+            })
+            .unwrap_or_else(|| panic!("no contract at {:?}", contract));
+
+        // `pallet-contracts` stores variable sized keys hashed with `Blake2_128Concat`.
+        let root_key = scale::Encode::encode(&T::KEY);
+        let hashed_key =
+            [&sp_core::hashing::blake2_128(&root_key)[..], &root_key].concat();
+        let value = self
+            .api
+            .child_storage(&contract_info.trie_id.0, &hashed_key)
+            .await
+            .unwrap_or_else(|| panic!("no storage for contract {:?}", contract));
+        log_info(&format!(
+            "read {} bytes of storage of contract {:?}",
+            value.len(),
+            contract
+        ));
+        <T as Storable>::decode(&mut &value[..]).unwrap_or_else(|err| {
+            panic!(
+                "decoding the storage of contract {:?} failed: {}",
+                contract, err
+            )
+        })
+    }
+
+    /// Returns the balances of `account_id`.
+    async fn account_data(&self, account_id: C::AccountId) -> AccountData<E::Balance> {
         let account_addr = subxt::storage::StaticStorageAddress::<
             DecodeStaticType<AccountInfo<C::Index, AccountData<E::Balance>>>,
             Yes,
@@ -683,6 +1620,46 @@ where
             "balance of contract {:?} is {:?}",
             account_id, alice_pre
         ));
-        Ok(alice_pre.data.free)
+        alice_pre.data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PolkadotConfig;
+    use sp_runtime::AccountId32;
+
+    /// An ink! event whose data is a single byte.
+    #[derive(Debug, PartialEq)]
+    struct Event(u8);
+
+    impl ink_env::test::DecodeEvent for Event {
+        fn decode_event(event: &ink_env::test::EmittedEvent) -> Option<Self> {
+            scale::Decode::decode(&mut &event.data[..]).ok().map(Self)
+        }
+    }
+
+    #[test]
+    fn contract_events_skips_events_of_other_contracts() {
+        let caller = AccountId32::new([1; 32]);
+        let callee = AccountId32::new([2; 32]);
+        // Both contracts emit events during a cross-contract call of `caller`.
+        let emitted = || {
+            [(&caller, 1), (&callee, 2), (&caller, 3)].map(|(contract, data)| {
+                ContractEmittedEvent::<PolkadotConfig> {
+                    contract: contract.clone(),
+                    data: vec![data],
+                }
+            })
+        };
+        assert_eq!(
+            decode_contract_events::<PolkadotConfig, Event>(&caller, emitted()),
+            vec![Event(1), Event(3)]
+        );
+        assert_eq!(
+            decode_contract_events::<PolkadotConfig, Event>(&callee, emitted()),
+            vec![Event(2)]
+        );
     }
 }
@@ -0,0 +1,110 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Decoding of the records written by the `ink::log` macros to the debug buffer.
+
+use super::log_prefix;
+use ink_env::log::Level;
+
+/// A record written by one of the `ink::log` macros of a contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogRecord {
+    /// The level of the record.
+    pub level: Level,
+    /// The target of the record, the module path of the contract by default.
+    pub target: String,
+    /// The formatted message of the record.
+    pub message: String,
+}
+
+impl LogRecord {
+    /// Decodes the record from a `line` of the form `[LEVEL target] message`.
+    fn decode(line: &str) -> Option<Self> {
+        let (header, message) = line.strip_prefix('[')?.split_once("] ")?;
+        let (level, target) = header.split_once(' ')?;
+        Some(Self {
+            level: Level::from_name(level)?,
+            target: target.to_string(),
+            message: message.replace("\\n", "\n"),
+        })
+    }
+}
+
+/// Decodes the log records of the `debug_message` buffer of a contract execution.
+///
+/// Lines not written by the `ink::log` macros, e.g. by `ink::env::debug_println!`,
+/// are skipped.
+pub fn decode_log_records(debug_message: &str) -> Vec<LogRecord> {
+    debug_message
+        .lines()
+        .filter_map(LogRecord::decode)
+        .collect()
+}
+
+/// Forwards the log records of the `debug_message` buffer to the `log` crate.
+///
+/// The records keep their level and target, so that `RUST_LOG` filters them like
+/// the log output of any other crate, e.g. `RUST_LOG=auction=debug`.
+pub(crate) fn forward_log_records(debug_message: &[u8]) {
+    for record in decode_log_records(&String::from_utf8_lossy(debug_message)) {
+        let level = match record.level {
+            Level::Error => log::Level::Error,
+            Level::Warn => log::Level::Warn,
+            Level::Info => log::Level::Info,
+            Level::Debug => log::Level::Debug,
+            Level::Trace => log::Level::Trace,
+        };
+        log::log!(
+            target: &record.target,
+            level,
+            "[{}] {}",
+            log_prefix(),
+            record.message
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_log_records_works() {
+        let debug_message = "[INFO auction] bid 100 from [1, 1, 1, 1]\n\
+            plain debug_println output\n\
+            [ERROR auction::bids] multi\\nline\n\
+            [NOTICE auction] unknown level\n\
+            [WARN auction]missing separator\n";
+        assert_eq!(
+            decode_log_records(debug_message),
+            vec![
+                LogRecord {
+                    level: Level::Info,
+                    target: "auction".to_string(),
+                    message: "bid 100 from [1, 1, 1, 1]".to_string(),
+                },
+                LogRecord {
+                    level: Level::Error,
+                    target: "auction::bids".to_string(),
+                    message: "multi\nline".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn decode_log_records_of_empty_buffer() {
+        assert!(decode_log_records("").is_empty());
+    }
+}
@@ -13,8 +13,12 @@
 // limitations under the License.
 
 //! Default accounts provided by [`sp_keyring::sr25519::Keyring`].
+//!
+//! If `CONTRACTS_NODE_SEED_FILE` is set the accounts are read from the seed file
+//! instead, see [`crate::node`].
 
 use super::{
+    node::seed_file_account,
     AccountKeyring,
     IdentifyAccount,
     PairSigner,
@@ -28,7 +32,9 @@ macro_rules! default_account {
         #[doc = concat!(
             "Returns the default [`sp_keyring::sr25519::Keyring`] for `//",
             stringify!($keyring_fn_name),
-            "`."
+            "`, or the `",
+            stringify!($fn_name),
+            "` account of the seed file if `CONTRACTS_NODE_SEED_FILE` is set."
         )]
         pub fn $fn_name<C>() -> PairSigner<C, sr25519::Pair>
         where
@@ -37,7 +43,9 @@ macro_rules! default_account {
             C::Signature: From<sr25519::Signature>,
             <C::Signature as Verify>::Signer: IdentifyAccount<AccountId = C::AccountId>,
         {
-            PairSigner::new(AccountKeyring::$keyring_fn_name.pair())
+            let pair = seed_file_account(stringify!($fn_name))
+                .unwrap_or_else(|| AccountKeyring::$keyring_fn_name.pair());
+            PairSigner::new(pair)
         }
     };
 }
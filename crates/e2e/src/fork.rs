@@ -0,0 +1,348 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Fetches the state of a contract from a live network, see
+//! [`Chopsticks::fork_from`](crate::chopsticks::Chopsticks::fork_from).
+//!
+//! The storage keys and the contract info are derived from the metadata of the live
+//! network, so that forking does not depend on its runtime matching the one of the
+//! `substrate-contracts-node` the types of this crate are generated from.
+
+use super::log_info;
+use jsonrpsee::{
+    core::client::ClientT,
+    rpc_params,
+    ws_client::{
+        WsClient,
+        WsClientBuilder,
+    },
+};
+use sp_core::Bytes;
+use subxt::{
+    dynamic::Value,
+    ext::scale_value::{
+        At,
+        Primitive,
+        ValueDef,
+    },
+    metadata::{
+        EncodeWithMetadata,
+        Metadata,
+    },
+    storage::utils::storage_address_bytes,
+    OnlineClient,
+};
+
+/// The prefix of the storage keys of the default child tries.
+const CHILD_STORAGE_PREFIX: &[u8] = b":child_storage:default:";
+
+/// The number of child trie keys fetched per request.
+const KEYS_PAGE_SIZE: u32 = 512;
+
+/// The raw storage entries of a contract.
+///
+/// The entries of the child trie of the contract are keyed by the storage key of the
+/// child trie followed by their key in it, as expected by the `dev_setStorage` RPC
+/// method of chopsticks.
+pub type StorageEntries = Vec<(Bytes, Bytes)>;
+
+/// A storage map key which is SCALE encoded already.
+struct EncodedKey<'a>(&'a [u8]);
+
+impl EncodeWithMetadata for EncodedKey<'_> {
+    fn encode_with_metadata(
+        &self,
+        _type_id: u32,
+        _metadata: &Metadata,
+        bytes: &mut Vec<u8>,
+    ) -> Result<(), subxt::Error> {
+        bytes.extend_from_slice(self.0);
+        Ok(())
+    }
+}
+
+/// Returns the key of the entry of the storage map `pallet::map` under the SCALE
+/// `encoded_key`, hashed as declared in the `metadata`.
+///
+/// Returns `None` if the runtime of the `metadata` has no such storage map.
+fn storage_map_key(
+    metadata: &Metadata,
+    pallet: &str,
+    map: &str,
+    encoded_key: &[u8],
+) -> Option<Vec<u8>> {
+    let address = subxt::dynamic::storage(pallet, map, vec![EncodedKey(encoded_key)]);
+    storage_address_bytes(&address, metadata).ok()
+}
+
+/// Returns the storage key of the child trie `trie_id` of a contract.
+fn child_storage_key(trie_id: &[u8]) -> Vec<u8> {
+    [CHILD_STORAGE_PREFIX, trie_id].concat()
+}
+
+/// Returns the bytes of a dynamically decoded byte sequence, such as a
+/// `BoundedVec<u8>` or a `H256`, which are nested into composites.
+fn value_to_bytes<T>(value: &Value<T>) -> Option<Vec<u8>> {
+    match &value.value {
+        ValueDef::Primitive(Primitive::U128(byte)) => {
+            u8::try_from(*byte).ok().map(|byte| vec![byte])
+        }
+        ValueDef::Composite(composite) => {
+            composite
+                .values()
+                .map(value_to_bytes)
+                .collect::<Option<Vec<_>>>()
+                .map(|bytes| bytes.concat())
+        }
+        _ => None,
+    }
+}
+
+/// Fetches the storage entries of the contract with the SCALE encoded `account_id`
+/// in the block `block_hash` of the live network at `url`.
+///
+/// These are the contract info, the code, the account with the balance and the
+/// child trie of the contract. Contracts called by the contract are not fetched.
+///
+/// # Panics
+///
+/// If the network can't be reached or there is no contract at `account_id`.
+pub async fn fetch_contract_state<C>(
+    url: &str,
+    block_hash: C::Hash,
+    account_id: &[u8],
+) -> StorageEntries
+where
+    C: subxt::Config,
+{
+    let api = OnlineClient::<C>::from_url(url)
+        .await
+        .unwrap_or_else(|err| {
+            panic!(
+                "error connecting to the network at {} to fork: {:?}",
+                url, err
+            )
+        });
+    let metadata = api.metadata();
+    let fetch = |key: Vec<u8>| {
+        let api = &api;
+        async move {
+            let value = api
+                .storage()
+                .fetch_raw(&key, Some(block_hash))
+                .await
+                .unwrap_or_else(|err| {
+                    panic!("error fetching storage of the network to fork: {:?}", err)
+                });
+            value.map(|value| (Bytes(key), Bytes(value)))
+        }
+    };
+
+    let info_address = subxt::dynamic::storage(
+        "Contracts",
+        "ContractInfoOf",
+        vec![EncodedKey(account_id)],
+    );
+    let info = api
+        .storage()
+        .fetch(&info_address, Some(block_hash))
+        .await
+        .unwrap_or_else(|err| {
+            panic!("error fetching the contract info to fork: {:?}", err)
+        })
+        .unwrap_or_else(|| {
+            panic!(
+                "no contract at {:?} in block {:?} of the network at {}",
+                Bytes(account_id.to_vec()),
+                block_hash,
+                url
+            )
+        });
+    let info_key = storage_address_bytes(&info_address, &metadata)
+        .expect("the address was fetched successfully");
+    let info_value = info
+        .to_value()
+        .unwrap_or_else(|err| panic!("decoding the contract info failed: {:?}", err));
+    let field = |name: &str| {
+        info_value
+            .at(name)
+            .and_then(value_to_bytes)
+            .unwrap_or_else(|| {
+                panic!("the contract info of the network has no `{}` bytes", name)
+            })
+    };
+    let (trie_id, code_hash) = (field("trie_id"), field("code_hash"));
+    let mut entries = vec![(Bytes(info_key), Bytes(info.into_encoded()))];
+
+    // The maps storing the code differ between versions of `pallet-contracts`, hence
+    // those not declared by the runtime of the network are skipped. The code hash is
+    // a fixed size array, so its bytes are its SCALE encoding.
+    for map in ["PristineCode", "CodeStorage", "OwnerInfoOf", "CodeInfoOf"] {
+        if let Some(key) = storage_map_key(&metadata, "Contracts", map, &code_hash) {
+            entries.extend(fetch(key).await);
+        }
+    }
+    let account_key = storage_map_key(&metadata, "System", "Account", account_id)
+        .expect("the network has no `System::Account` storage map");
+    entries.extend(fetch(account_key).await);
+
+    let ws_client = WsClientBuilder::default()
+        .build(url)
+        .await
+        .unwrap_or_else(|err| {
+            panic!(
+                "error connecting to the network at {} to fork: {:?}",
+                url, err
+            )
+        });
+    let child_entries = fetch_child_storage(&ws_client, block_hash, &trie_id).await;
+    log_info(&format!(
+        "fetched {} storage entries of contract {:?} from {}",
+        child_entries.len(),
+        Bytes(account_id.to_vec()),
+        url
+    ));
+    entries.extend(child_entries);
+    entries
+}
+
+/// Fetches all entries of the child trie `trie_id` page by page.
+async fn fetch_child_storage<Hash>(
+    ws_client: &WsClient,
+    block_hash: Hash,
+    trie_id: &[u8],
+) -> StorageEntries
+where
+    Hash: serde::Serialize + Copy + Send + Sync,
+{
+    let child_key = child_storage_key(trie_id);
+    let mut entries = Vec::new();
+    let mut start_key: Option<Bytes> = None;
+    loop {
+        let keys: Vec<Bytes> = ws_client
+            .request(
+                "childstate_getKeysPaged",
+                rpc_params![
+                    Bytes(child_key.clone()),
+                    Bytes(Vec::new()),
+                    KEYS_PAGE_SIZE,
+                    start_key.clone(),
+                    block_hash
+                ],
+            )
+            .await
+            .unwrap_or_else(|err| {
+                panic!("error on ws request `childstate_getKeysPaged`: {:?}", err);
+            });
+        let values: Vec<Option<Bytes>> = ws_client
+            .request(
+                "childstate_getStorageEntries",
+                rpc_params![Bytes(child_key.clone()), keys.clone(), block_hash],
+            )
+            .await
+            .unwrap_or_else(|err| {
+                panic!(
+                    "error on ws request `childstate_getStorageEntries`: {:?}",
+                    err
+                );
+            });
+        let is_last_page = keys.len() < KEYS_PAGE_SIZE as usize;
+        start_key = keys.last().cloned();
+        entries.extend(keys.into_iter().zip(values).filter_map(|(key, value)| {
+            value.map(|value| (Bytes([&child_key[..], &key.0].concat()), value))
+        }));
+        if is_last_page {
+            return entries
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sp_core::hashing::{
+        blake2_128,
+        twox_128,
+        twox_64,
+    };
+    use subxt::ext::frame_metadata::RuntimeMetadataPrefixed;
+
+    /// Returns the metadata of the `substrate-contracts-node`.
+    fn metadata() -> Metadata {
+        let bytes = include_bytes!("../metadata/contracts-node.scale");
+        let metadata =
+            <RuntimeMetadataPrefixed as scale::Decode>::decode(&mut &bytes[..])
+                .expect("metadata must decode");
+        Metadata::try_from(metadata).expect("metadata must be valid")
+    }
+
+    #[test]
+    fn storage_keys_are_hashed_as_declared_by_the_metadata() {
+        let metadata = metadata();
+        let account_id = [0x01; 32];
+        let prefix = |pallet: &str, map: &str| {
+            [twox_128(pallet.as_bytes()), twox_128(map.as_bytes())].concat()
+        };
+        assert_eq!(
+            storage_map_key(&metadata, "Contracts", "ContractInfoOf", &account_id),
+            Some(
+                [
+                    &prefix("Contracts", "ContractInfoOf")[..],
+                    &twox_64(&account_id)[..],
+                    &account_id[..],
+                ]
+                .concat()
+            )
+        );
+        assert_eq!(
+            storage_map_key(&metadata, "Contracts", "PristineCode", &[0x02; 32]),
+            Some([&prefix("Contracts", "PristineCode")[..], &[0x02; 32][..]].concat())
+        );
+        assert_eq!(
+            storage_map_key(&metadata, "System", "Account", &account_id),
+            Some(
+                [
+                    &prefix("System", "Account")[..],
+                    &blake2_128(&account_id)[..],
+                    &account_id[..],
+                ]
+                .concat()
+            )
+        );
+    }
+
+    #[test]
+    fn storage_keys_of_undeclared_maps_are_skipped() {
+        assert_eq!(
+            storage_map_key(&metadata(), "Contracts", "NoSuchMap", &[0x01; 32]),
+            None
+        );
+    }
+
+    #[test]
+    fn child_storage_key_works() {
+        assert_eq!(
+            child_storage_key(&[0x02, 0x03]),
+            b":child_storage:default:\x02\x03".to_vec()
+        );
+    }
+
+    #[test]
+    fn nested_bytes_are_flattened() {
+        let hash = Value::unnamed_composite(vec![Value::from_bytes([0x01; 32])]);
+        assert_eq!(value_to_bytes(&hash), Some(vec![0x01; 32]));
+        assert_eq!(value_to_bytes(&Value::u128(256)), None);
+        assert_eq!(value_to_bytes(&Value::string("trie")), None);
+    }
+}
@@ -0,0 +1,86 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Impersonation of accounts without a known key pair.
+//!
+//! Messages restricted to chain-level accounts, e.g. a treasury or the sovereign
+//! account of a parachain, can't be called by the keyring accounts. On development
+//! nodes with `pallet-sudo` these accounts can be impersonated instead: their calls
+//! are dispatched via `Sudo::sudo_as`, signed by the sudo key of the chain.
+
+use super::{
+    alice,
+    IdentifyAccount,
+    Signer,
+    Verify,
+};
+use sp_core::sr25519;
+
+/// An account impersonated via `Sudo::sudo_as`, see [`signer_from_account_id`].
+///
+/// The extrinsics of the impersonated account are signed by the sudo key of the
+/// chain, which is [`alice`] unless set via [`ImpersonatedSigner::new`] or
+/// [`ImpersonatedSigner::with_sudo`].
+pub struct ImpersonatedSigner<C: subxt::Config, S = Signer<C>> {
+    account_id: C::AccountId,
+    sudo: S,
+}
+
+impl<C: subxt::Config, S> ImpersonatedSigner<C, S> {
+    /// Returns a signer impersonating `account_id` via the `sudo` key of the chain.
+    pub fn new(account_id: C::AccountId, sudo: S) -> Self {
+        Self { account_id, sudo }
+    }
+
+    /// Returns the account id of the impersonated account.
+    pub fn account_id(&self) -> &C::AccountId {
+        &self.account_id
+    }
+
+    /// Returns the sudo key signing the extrinsics of the impersonated account.
+    pub fn sudo(&self) -> &S {
+        &self.sudo
+    }
+
+    /// Sets the sudo key of the chain.
+    pub fn with_sudo(mut self, sudo: S) -> Self {
+        self.sudo = sudo;
+        self
+    }
+}
+
+/// Returns a signer impersonating the account `account_id`.
+///
+/// Calls made with [`Client::call_as`](crate::Client::call_as) using the signer
+/// are dispatched with `account_id` as their origin. This requires the node to
+/// include `pallet-sudo`, like the `substrate-contracts-node` does.
+///
+/// # Example
+///
+/// ```ignore
+/// let treasury = ink_e2e::signer_from_account_id(treasury_account_id);
+/// client
+///     .call_as(&treasury, contract_acc_id, set_fee(10), 0, None)
+///     .await
+///     .expect("calling `set_fee` as the treasury failed");
+/// ```
+pub fn signer_from_account_id<C>(account_id: C::AccountId) -> ImpersonatedSigner<C>
+where
+    C: subxt::Config,
+    <C::Signature as Verify>::Signer: From<sr25519::Public>,
+    C::Signature: From<sr25519::Signature>,
+    <C::Signature as Verify>::Signer: IdentifyAccount<AccountId = C::AccountId>,
+{
+    ImpersonatedSigner::new(account_id, alice())
+}
@@ -19,20 +19,60 @@
     html_favicon_url = "https://use.ink/crate-docs/favicon.png"
 )]
 
+pub mod bench;
+mod calls;
+pub mod chopsticks;
 mod client;
+mod contract_log;
 mod default_accounts;
+mod fork;
+mod impersonation;
+pub mod node;
+pub mod node_pool;
+pub mod node_release;
 #[cfg(test)]
 mod tests;
 pub mod utils;
 mod xts;
 
+pub use bench::Thresholds;
+pub use calls::{
+    ConstructorCall,
+    MessageCall,
+    SignedConstructorCall,
+    SignedMessageCall,
+};
+pub use chopsticks::{
+    Chopsticks,
+    Snapshot,
+};
 pub use client::{
+    BalanceDelta,
+    CallAccounting,
+    CallDryRunResult,
+    CallResult,
     Client,
     Error,
+    GasPolicy,
+    InstantiationResult,
+};
+pub use contract_log::{
+    decode_log_records,
+    LogRecord,
 };
 pub use default_accounts::*;
 pub use env_logger;
-pub use ink_e2e_macro::test;
+pub use impersonation::{
+    signer_from_account_id,
+    ImpersonatedSigner,
+};
+pub use ink_e2e_macro::{
+    bench,
+    test,
+};
+pub use node::node_url;
+pub use scale;
+pub use xts::Determinism;
 // TODO(#1421) `smart-bench_macro` needs to be forked.
 pub use smart_bench_macro;
 pub use sp_core::H256;
@@ -84,12 +124,25 @@ pub type PolkadotConfig = subxt::config::WithExtrinsicParams<
     subxt::tx::PolkadotExtrinsicParams<SubstrateConfig>,
 >;
 
-/// Signer that is used throughout the E2E testing.
+/// Signer of the default accounts, e.g. [`alice`].
 ///
-/// The E2E testing can only be used with nodes that support `sr25519`
-/// cryptography.
+/// The default accounts are derived from `sr25519` keys. Tests of runtimes with
+/// other accounts sign with their own [`TxSigner`] instead.
 pub type Signer<C> = PairSigner<C, sr25519::Pair>;
 
+/// Signs the extrinsics submitted by the [`Client`].
+///
+/// Implemented for every `subxt` signer, e.g. a [`PairSigner`] of `ecdsa` keys for
+/// runtimes with 20 byte account ids, or a signer delegating to a hardware wallet.
+pub trait TxSigner<C: subxt::Config>: subxt::tx::Signer<C> + Send + Sync {}
+
+impl<C, S> TxSigner<C> for S
+where
+    C: subxt::Config,
+    S: subxt::tx::Signer<C> + Send + Sync,
+{
+}
+
 /// Trait for contract constructors.
 // TODO(#1421) Merge this with `InkMessage` to be just `InkSelector`. Requires forking `smart-bench-macro`.
 pub trait InkConstructor: scale::Encode {
@@ -0,0 +1,148 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Running End-to-End tests against an external node, e.g. a persistent testnet.
+//!
+//! - `CONTRACTS_NODE_URL` overrides the `ws_url` of every `#[ink_e2e::test]`.
+//! - `CONTRACTS_NODE_SEED_FILE` points to a file with the secret URIs of pre-funded
+//!   accounts. The default accounts (`alice()`, `bob()`, ...) then sign with the key
+//!   of the same name from this file instead of the well-known development keys,
+//!   which have no funds on a live chain.
+//!
+//! The seed file contains one `name = secret-uri` entry per line. Empty lines and
+//! lines starting with `#` are ignored:
+//!
+//! ```text
+//! # Pre-funded accounts on the testnet.
+//! alice = bottom drive obey lake curtain smoke basket hold race lonely fit walk//ci
+//! bob = 0x398f0c28f98885e046333d4a41c19cee4c37368a9832c6502f6cfd182e2aef89
+//! ```
+
+use super::log_info;
+use sp_core::{
+    sr25519,
+    Pair as _,
+};
+use std::collections::BTreeMap;
+
+/// The environment variable holding the WebSocket URL of an external node.
+pub const NODE_URL_ENV: &str = "CONTRACTS_NODE_URL";
+
+/// The environment variable holding the path to the seed file of pre-funded accounts.
+pub const SEED_FILE_ENV: &str = "CONTRACTS_NODE_SEED_FILE";
+
+/// Returns the WebSocket URL of the node to run the tests against.
+///
+/// This is the value of `CONTRACTS_NODE_URL` if set, otherwise `default_url`.
+pub fn node_url(default_url: &str) -> String {
+    match std::env::var(NODE_URL_ENV) {
+        Ok(url) => {
+            log_info(&format!("using external node at {}", url));
+            url
+        }
+        Err(_) => default_url.to_string(),
+    }
+}
+
+/// Returns the key pair of the account `name` from the seed file.
+///
+/// Returns `None` if `CONTRACTS_NODE_SEED_FILE` is not set.
+///
+/// # Panics
+///
+/// If the seed file can not be read, is malformed, has no entry for `name`,
+/// or the entry is not a valid secret URI.
+pub fn seed_file_account(name: &str) -> Option<sr25519::Pair> {
+    let path = std::env::var(SEED_FILE_ENV).ok()?;
+    let contents = std::fs::read_to_string(&path).unwrap_or_else(|err| {
+        panic!("seed file {} cannot be read: {:?}", path, err);
+    });
+    let accounts = parse_seed_file(&contents).unwrap_or_else(|err| {
+        panic!("seed file {} is malformed: {}", path, err);
+    });
+    let secret_uri = accounts.get(name).unwrap_or_else(|| {
+        panic!("seed file {} has no entry for account `{}`", path, name);
+    });
+    let pair = sr25519::Pair::from_string(secret_uri, None).unwrap_or_else(|err| {
+        panic!(
+            "seed file {} has an invalid secret URI for account `{}`: {:?}",
+            path, name, err
+        );
+    });
+    Some(pair)
+}
+
+/// Parses the `name = secret-uri` entries of a seed file.
+fn parse_seed_file(contents: &str) -> Result<BTreeMap<String, String>, String> {
+    let mut accounts = BTreeMap::new();
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue
+        }
+        let (name, secret_uri) = line
+            .split_once('=')
+            .map(|(name, secret_uri)| (name.trim(), secret_uri.trim()))
+            .filter(|(name, secret_uri)| !name.is_empty() && !secret_uri.is_empty())
+            .ok_or_else(|| {
+                format!("expected `name = secret-uri` in line {}", index + 1)
+            })?;
+        if accounts
+            .insert(name.to_string(), secret_uri.to_string())
+            .is_some()
+        {
+            return Err(format!(
+                "duplicate entry for account `{}` in line {}",
+                name,
+                index + 1
+            ))
+        }
+    }
+    Ok(accounts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_seed_file_works() {
+        let accounts = parse_seed_file(
+            "# comment\n\nalice = //Alice\n  bob=0x398f0c28f98885e046333d4a41c19cee4c37368a9832c6502f6cfd182e2aef89  \n",
+        )
+        .unwrap();
+        assert_eq!(accounts.len(), 2);
+        assert_eq!(accounts["alice"], "//Alice");
+        assert_eq!(
+            accounts["bob"],
+            "0x398f0c28f98885e046333d4a41c19cee4c37368a9832c6502f6cfd182e2aef89"
+        );
+    }
+
+    #[test]
+    fn parse_seed_file_fails() {
+        assert_eq!(
+            parse_seed_file("alice //Alice"),
+            Err("expected `name = secret-uri` in line 1".to_string())
+        );
+        assert_eq!(
+            parse_seed_file("alice = //Alice\nbob =\n"),
+            Err("expected `name = secret-uri` in line 2".to_string())
+        );
+        assert_eq!(
+            parse_seed_file("alice = //Alice\nalice = //Bob\n"),
+            Err("duplicate entry for account `alice` in line 2".to_string())
+        );
+    }
+}
@@ -0,0 +1,337 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Running End-to-End tests in parallel, each against its own node.
+//!
+//! All `#[ink_e2e::test]`s connect to the same node by default, so that they have
+//! to be run with `--test-threads=1` in order not to interfere with each other,
+//! e.g. via the nonces of the default accounts.
+//!
+//! If `CONTRACTS_NODE_POOL_SIZE` is set to `N > 0`, every test instead spawns a
+//! fresh development node on free ports and kills it once the test has finished.
+//! At most `N` nodes run at the same time, tests beyond that wait for a running
+//! test to finish. The tests are thus isolated from each other and can run in
+//! parallel:
+//!
+//! ```text
+//! CONTRACTS_NODE_POOL_SIZE=8 cargo test --features e2e-tests
+//! ```
+//!
+//! The node binary is taken from `CONTRACTS_NODE`. Otherwise the release pinned by
+//! `CONTRACTS_NODE_VERSION` is downloaded, see [`node_release`](crate::node_release),
+//! or `substrate-contracts-node` is taken from the `PATH`. The pool takes precedence
+//! over `CONTRACTS_NODE_URL`.
+
+use super::{
+    log_info,
+    node_release::NodeRelease,
+};
+use std::{
+    ffi::{
+        OsStr,
+        OsString,
+    },
+    net::{
+        TcpListener,
+        TcpStream,
+    },
+    path::{
+        Path,
+        PathBuf,
+    },
+    process::{
+        Child,
+        Command,
+        Stdio,
+    },
+    sync::{
+        Condvar,
+        Mutex,
+    },
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+/// The environment variable holding the maximum number of nodes running at once.
+pub const NODE_POOL_SIZE_ENV: &str = "CONTRACTS_NODE_POOL_SIZE";
+
+/// The environment variable holding the path to the node binary.
+pub const NODE_BINARY_ENV: &str = "CONTRACTS_NODE";
+
+/// The node binary used if neither `CONTRACTS_NODE` nor `CONTRACTS_NODE_VERSION` is set.
+const DEFAULT_NODE_BINARY: &str = "substrate-contracts-node";
+
+/// The time to wait for a spawned node to accept connections.
+const NODE_STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The number of nodes currently running, shared by all tests of the process.
+static RUNNING: Slots = Slots::new();
+
+/// A node spawned for a single test.
+///
+/// The node is killed once the lease is dropped at the end of the test.
+#[derive(Debug)]
+pub struct NodeLease {
+    child: Child,
+    url: String,
+}
+
+impl NodeLease {
+    /// Returns the WebSocket URL of the node.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+impl Drop for NodeLease {
+    fn drop(&mut self) {
+        // The node may have exited already, in which case there is nothing to kill.
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        log_info(&format!("stopped node at {}", self.url));
+        RUNNING.release();
+    }
+}
+
+/// Spawns a fresh node for the calling test if `CONTRACTS_NODE_POOL_SIZE` is set.
+///
+/// Blocks until less than `CONTRACTS_NODE_POOL_SIZE` nodes are running. Returns
+/// `None` if the pool is disabled.
+///
+/// # Panics
+///
+/// If `CONTRACTS_NODE_POOL_SIZE` is not a number, if the pinned release can not be
+/// downloaded, or if the node can not be spawned or does not accept connections in
+/// time.
+pub fn lease() -> Option<NodeLease> {
+    let size = pool_size(std::env::var(NODE_POOL_SIZE_ENV).ok().as_deref())
+        .unwrap_or_else(|err| panic!("invalid `{}`: {}", NODE_POOL_SIZE_ENV, err))?;
+    let binary = default_binary();
+    RUNNING.acquire(size);
+    Some(spawn(&binary, &[]))
+}
+
+/// Spawns a fresh node running `binary` for the calling test.
+///
+/// This is used by tests selecting their own node binary, e.g. a pinned release
+/// with `#[ink_e2e::test(node_version = "..", node_sha256 = "..")]`. It spawns a
+/// node even if the pool is disabled, but still waits for a free slot otherwise.
+///
+/// # Panics
+///
+/// If `CONTRACTS_NODE_POOL_SIZE` is not a number, or if the node can not be spawned
+/// or does not accept connections in time.
+pub fn lease_with<P: AsRef<Path>>(binary: P) -> NodeLease {
+    lease_custom(Some(binary.as_ref().to_path_buf()), &[])
+}
+
+/// Spawns a fresh node for the calling test with the additional command line
+/// `args`, e.g. `--chain=path/to/chainspec.json`.
+///
+/// This is used by tests customizing their node with
+/// `#[ink_e2e::test(node_args = [..], chainspec = "..")]`. The node runs `binary`,
+/// or the binary the pool would run if `None`. Like [`lease_with`] it spawns a node
+/// even if the pool is disabled, but still waits for a free slot otherwise.
+///
+/// # Note
+///
+/// The `args` are passed after `--dev` and the ports chosen by the pool, so they
+/// must not set the ports again.
+///
+/// # Panics
+///
+/// If `CONTRACTS_NODE_POOL_SIZE` is not a number, if the pinned release can not be
+/// downloaded, or if the node can not be spawned or does not accept connections in
+/// time.
+pub fn lease_custom(binary: Option<PathBuf>, args: &[String]) -> NodeLease {
+    let size = pool_size(std::env::var(NODE_POOL_SIZE_ENV).ok().as_deref())
+        .unwrap_or_else(|err| panic!("invalid `{}`: {}", NODE_POOL_SIZE_ENV, err));
+    let binary = binary
+        .map(PathBuf::into_os_string)
+        .unwrap_or_else(default_binary);
+    RUNNING.acquire(size.unwrap_or(usize::MAX));
+    spawn(&binary, args)
+}
+
+/// Returns the node binary given by `CONTRACTS_NODE`, the release pinned by
+/// `CONTRACTS_NODE_VERSION` or `substrate-contracts-node` from the `PATH`.
+fn default_binary() -> OsString {
+    match std::env::var_os(NODE_BINARY_ENV) {
+        Some(binary) => binary,
+        None => {
+            NodeRelease::from_env()
+                .map(|release| release.binary().into_os_string())
+                .unwrap_or_else(|| DEFAULT_NODE_BINARY.into())
+        }
+    }
+}
+
+/// Spawns a node running `binary` with the additional `args` on free ports, its
+/// slot already being taken.
+fn spawn(binary: &OsStr, args: &[String]) -> NodeLease {
+    let ws_port = free_port();
+    let child = Command::new(binary)
+        .args(["--dev", "--tmp", "--no-prometheus", "--no-telemetry"])
+        .arg(format!("--ws-port={}", ws_port))
+        .arg(format!("--rpc-port={}", free_port()))
+        .arg(format!("--port={}", free_port()))
+        .args(args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap_or_else(|err| {
+            RUNNING.release();
+            panic!(
+                "failed to spawn node `{}`, set `{}` to the path of the node binary: {:?}",
+                Path::new(binary).display(),
+                NODE_BINARY_ENV,
+                err
+            )
+        });
+    // Kills the node and releases its slot in case it does not start.
+    let lease = NodeLease {
+        child,
+        url: format!("ws://127.0.0.1:{}", ws_port),
+    };
+    wait_for_port(ws_port);
+    log_info(&format!("started node at {}", lease.url));
+    lease
+}
+
+/// Parses the value of `CONTRACTS_NODE_POOL_SIZE`.
+///
+/// Returns `Ok(None)` if the pool is disabled, i.e. the value is unset or `0`.
+fn pool_size(value: Option<&str>) -> Result<Option<usize>, String> {
+    match value.map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(value) => {
+            let size = value.parse::<usize>().map_err(|err| {
+                format!("expected a number, found {:?}: {}", value, err)
+            })?;
+            Ok(Some(size).filter(|size| *size > 0))
+        }
+    }
+}
+
+/// Returns a TCP port which is currently not in use.
+fn free_port() -> u16 {
+    TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .map(|addr| addr.port())
+        .unwrap_or_else(|err| panic!("unable to find a free port: {:?}", err))
+}
+
+/// Waits until the node accepts connections at `port`.
+fn wait_for_port(port: u16) {
+    let started = Instant::now();
+    while TcpStream::connect(("127.0.0.1", port)).is_err() {
+        if started.elapsed() > NODE_STARTUP_TIMEOUT {
+            panic!(
+                "node did not accept connections at port {} within {:?}",
+                port, NODE_STARTUP_TIMEOUT
+            )
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
+/// A counting semaphore limiting the number of nodes running at once.
+struct Slots {
+    used: Mutex<usize>,
+    released: Condvar,
+}
+
+impl Slots {
+    /// Creates a new semaphore with no slot in use.
+    const fn new() -> Self {
+        Self {
+            used: Mutex::new(0),
+            released: Condvar::new(),
+        }
+    }
+
+    /// Takes a slot, blocking until less than `limit` slots are in use.
+    fn acquire(&self, limit: usize) {
+        let mut used = self.used.lock().unwrap_or_else(|err| err.into_inner());
+        while *used >= limit {
+            used = self
+                .released
+                .wait(used)
+                .unwrap_or_else(|err| err.into_inner());
+        }
+        *used += 1;
+    }
+
+    /// Releases a slot taken by [`Slots::acquire`].
+    fn release(&self) {
+        let mut used = self.used.lock().unwrap_or_else(|err| err.into_inner());
+        *used = used.saturating_sub(1);
+        self.released.notify_one();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{
+        atomic::{
+            AtomicUsize,
+            Ordering,
+        },
+        Arc,
+    };
+
+    #[test]
+    fn pool_size_works() {
+        assert_eq!(pool_size(None), Ok(None));
+        assert_eq!(pool_size(Some("")), Ok(None));
+        assert_eq!(pool_size(Some("0")), Ok(None));
+        assert_eq!(pool_size(Some(" 8 ")), Ok(Some(8)));
+        assert!(pool_size(Some("eight")).is_err());
+    }
+
+    #[test]
+    fn free_port_works() {
+        let port = free_port();
+        assert_ne!(port, 0);
+        TcpListener::bind(("127.0.0.1", port)).expect("port must be free");
+    }
+
+    #[test]
+    fn slots_limit_concurrency() {
+        static SLOTS: Slots = Slots::new();
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+        let threads = (0..8)
+            .map(|_| {
+                let running = running.clone();
+                let max_running = max_running.clone();
+                std::thread::spawn(move || {
+                    SLOTS.acquire(2);
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_running.fetch_max(now, Ordering::SeqCst);
+                    std::thread::sleep(Duration::from_millis(10));
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    SLOTS.release();
+                })
+            })
+            .collect::<Vec<_>>();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        assert!(max_running.load(Ordering::SeqCst) <= 2);
+    }
+}
@@ -0,0 +1,386 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Running End-to-End tests against pinned releases of `substrate-contracts-node`.
+//!
+//! Instead of requiring `substrate-contracts-node` on the `PATH`, the nodes spawned
+//! by the [node pool](crate::node_pool) can run a release downloaded from GitHub:
+//!
+//! - `CONTRACTS_NODE_VERSION` holds the tag of the release, e.g. `v0.23.0`.
+//! - `CONTRACTS_NODE_SHA256` holds the hex encoded SHA-256 checksum of the release
+//!   archive for the current platform. The download is rejected if it does not
+//!   match, so the checksum pins the exact release.
+//! - `CONTRACTS_NODE_CACHE` overrides the directory the releases are cached in,
+//!   `$XDG_CACHE_HOME/ink_e2e` or `$HOME/.cache/ink_e2e` by default. Releases are
+//!   cached by their version and checksum, so pinning another checksum for the
+//!   same version downloads and verifies the release again.
+//!
+//! ```text
+//! CONTRACTS_NODE_VERSION=v0.23.0 CONTRACTS_NODE_SHA256=… CONTRACTS_NODE_POOL_SIZE=4 \
+//!     cargo test --features e2e-tests
+//! ```
+//!
+//! Single tests select their own release with the `node_version` and `node_sha256`
+//! arguments of `#[ink_e2e::test]`, e.g. in order to test against several releases.
+//! Such tests always run against a fresh node spawned for them.
+//!
+//! Downloading requires `curl` and `tar` on the `PATH`.
+
+use super::log_info;
+use std::{
+    path::{
+        Path,
+        PathBuf,
+    },
+    process::Command,
+    sync::Mutex,
+};
+
+/// The environment variable holding the tag of the release to download.
+pub const NODE_VERSION_ENV: &str = "CONTRACTS_NODE_VERSION";
+
+/// The environment variable holding the SHA-256 checksum of the release archive.
+pub const NODE_SHA256_ENV: &str = "CONTRACTS_NODE_SHA256";
+
+/// The environment variable holding the directory the releases are cached in.
+pub const NODE_CACHE_ENV: &str = "CONTRACTS_NODE_CACHE";
+
+/// The URL the release archives are downloaded from.
+const RELEASES_URL: &str =
+    "https://github.com/paritytech/substrate-contracts-node/releases/download";
+
+/// The name of the node binary in the release archives.
+const BINARY_NAME: &str = "substrate-contracts-node";
+
+/// Serializes the downloads of all tests of the process.
+static DOWNLOAD: Mutex<()> = Mutex::new(());
+
+/// A release of `substrate-contracts-node` pinned by the checksum of its archive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeRelease {
+    version: String,
+    sha256: [u8; 32],
+    asset: Option<String>,
+}
+
+impl NodeRelease {
+    /// Creates the release with the tag `version` whose archive has the hex encoded
+    /// SHA-256 checksum `sha256`.
+    ///
+    /// # Errors
+    ///
+    /// If `version` is empty or `sha256` is not a hex encoded 32 bytes checksum.
+    pub fn new(version: &str, sha256: &str) -> Result<Self, String> {
+        let version = version.trim();
+        if version.is_empty() {
+            return Err("expected a release tag, e.g. `v0.23.0`".to_string())
+        }
+        Ok(Self {
+            version: version.to_string(),
+            sha256: parse_sha256(sha256)?,
+            asset: None,
+        })
+    }
+
+    /// Returns the release given by `CONTRACTS_NODE_VERSION` and `CONTRACTS_NODE_SHA256`.
+    ///
+    /// Returns `None` if `CONTRACTS_NODE_VERSION` is not set.
+    ///
+    /// # Panics
+    ///
+    /// If `CONTRACTS_NODE_SHA256` is not set or the release is invalid.
+    pub fn from_env() -> Option<Self> {
+        let version = std::env::var(NODE_VERSION_ENV).ok()?;
+        let sha256 = std::env::var(NODE_SHA256_ENV).unwrap_or_else(|_| {
+            panic!(
+                "`{}` must be set to the checksum of the release archive if `{}` is set",
+                NODE_SHA256_ENV, NODE_VERSION_ENV
+            )
+        });
+        let release = Self::new(&version, &sha256).unwrap_or_else(|err| {
+            panic!(
+                "invalid `{}` or `{}`: {}",
+                NODE_VERSION_ENV, NODE_SHA256_ENV, err
+            )
+        });
+        Some(release)
+    }
+
+    /// Sets the name of the release archive to download.
+    ///
+    /// By default this is the archive of the current platform, e.g.
+    /// `substrate-contracts-node-linux.tar.gz`.
+    pub fn with_asset(self, asset: &str) -> Self {
+        Self {
+            asset: Some(asset.to_string()),
+            ..self
+        }
+    }
+
+    /// Returns the tag of the release.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Returns the URL of the release archive.
+    ///
+    /// # Errors
+    ///
+    /// If no archive name is set and there is no release archive for the current
+    /// platform.
+    pub fn archive_url(&self) -> Result<String, String> {
+        let asset = match &self.asset {
+            Some(asset) => asset.as_str(),
+            None => platform_asset()?,
+        };
+        Ok(format!("{}/{}/{}", RELEASES_URL, self.version, asset))
+    }
+
+    /// Returns the path to the node binary of the release.
+    ///
+    /// The release is downloaded and verified unless it is cached already. The
+    /// cache is keyed by the checksum of the release, so a cached binary has been
+    /// verified against the same checksum.
+    ///
+    /// # Panics
+    ///
+    /// If the release can not be downloaded or does not match its checksum.
+    pub fn binary(&self) -> PathBuf {
+        let binary = self.cache_dir().join(BINARY_NAME);
+        let _guard = DOWNLOAD.lock().unwrap_or_else(|err| err.into_inner());
+        if !binary.is_file() {
+            self.download(&binary).unwrap_or_else(|err| {
+                panic!(
+                    "failed to download `{}` {}: {}",
+                    BINARY_NAME, self.version, err
+                )
+            });
+        }
+        binary
+    }
+
+    /// Returns the directory the release is cached in.
+    fn cache_dir(&self) -> PathBuf {
+        cache_root().join(BINARY_NAME).join(&self.version).join(
+            self.sha256
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect::<String>(),
+        )
+    }
+
+    /// Downloads and verifies the release archive, then extracts its node binary
+    /// to `binary`.
+    fn download(&self, binary: &Path) -> Result<(), String> {
+        let url = self.archive_url()?;
+        log_info(&format!("downloading {}", url));
+        let staging = self.cache_dir().with_extension("download");
+        // Leftovers of an interrupted download are discarded.
+        let _ = std::fs::remove_dir_all(&staging);
+        std::fs::create_dir_all(&staging)
+            .map_err(|err| format!("cannot create {}: {:?}", staging.display(), err))?;
+        let archive = staging.join("archive.tar.gz");
+        run(Command::new("curl")
+            .args([
+                "--fail",
+                "--location",
+                "--silent",
+                "--show-error",
+                "--output",
+            ])
+            .arg(&archive)
+            .arg(&url))?;
+        let contents = std::fs::read(&archive)
+            .map_err(|err| format!("cannot read {}: {:?}", archive.display(), err))?;
+        verify_checksum(&contents, &self.sha256)?;
+        run(Command::new("tar")
+            .arg("-xzf")
+            .arg(&archive)
+            .arg("-C")
+            .arg(&staging))?;
+        let extracted = find_binary(&staging).ok_or_else(|| {
+            format!("the release archive at {} has no `{}`", url, BINARY_NAME)
+        })?;
+        make_executable(&extracted)?;
+        let dir = binary
+            .parent()
+            .expect("the binary is in the cache directory");
+        std::fs::create_dir_all(dir)
+            .map_err(|err| format!("cannot create {}: {:?}", dir.display(), err))?;
+        std::fs::rename(&extracted, binary)
+            .map_err(|err| format!("cannot move the node binary: {:?}", err))?;
+        let _ = std::fs::remove_dir_all(&staging);
+        log_info(&format!("cached node binary at {}", binary.display()));
+        Ok(())
+    }
+}
+
+/// Returns the name of the release archive for the current platform.
+fn platform_asset() -> Result<&'static str, String> {
+    match std::env::consts::OS {
+        "linux" => Ok("substrate-contracts-node-linux.tar.gz"),
+        "macos" => Ok("substrate-contracts-node-mac-universal.tar.gz"),
+        os => {
+            Err(format!(
+                "no release archive for `{}`, set the archive name explicitly",
+                os
+            ))
+        }
+    }
+}
+
+/// Returns the directory all releases are cached in.
+fn cache_root() -> PathBuf {
+    if let Some(dir) = std::env::var_os(NODE_CACHE_ENV) {
+        return PathBuf::from(dir)
+    }
+    let cache = std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))
+        .unwrap_or_else(std::env::temp_dir);
+    cache.join("ink_e2e")
+}
+
+/// Parses the hex encoded SHA-256 checksum `hex`.
+fn parse_sha256(hex: &str) -> Result<[u8; 32], String> {
+    let hex = hex.trim();
+    let invalid = || format!("expected a hex encoded SHA-256 checksum, found {:?}", hex);
+    if hex.len() != 64 || !hex.is_ascii() {
+        return Err(invalid())
+    }
+    let mut sha256 = [0x00; 32];
+    for (n, byte) in sha256.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * n..2 * n + 2], 16).map_err(|_| invalid())?;
+    }
+    Ok(sha256)
+}
+
+/// Ensures that the SHA-256 checksum of `contents` is `expected`.
+fn verify_checksum(contents: &[u8], expected: &[u8; 32]) -> Result<(), String> {
+    let actual = sp_core::hashing::sha2_256(contents);
+    if &actual != expected {
+        return Err(format!(
+            "checksum mismatch, expected {} but the download has {}",
+            impl_serde::serialize::to_hex(expected, false),
+            impl_serde::serialize::to_hex(&actual, false),
+        ))
+    }
+    Ok(())
+}
+
+/// Returns the path to the node binary somewhere below `dir`.
+fn find_binary(dir: &Path) -> Option<PathBuf> {
+    let entries = std::fs::read_dir(dir).ok()?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if let Some(binary) = find_binary(&path) {
+                return Some(binary)
+            }
+        } else if path.file_name().and_then(|name| name.to_str()) == Some(BINARY_NAME) {
+            return Some(path)
+        }
+    }
+    None
+}
+
+/// Marks the file at `path` as executable.
+fn make_executable(path: &Path) -> Result<(), String> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt as _;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755)).map_err(
+            |err| format!("cannot make the node binary executable: {:?}", err),
+        )?;
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
+
+/// Runs `command`, failing if it does not exit successfully.
+fn run(command: &mut Command) -> Result<(), String> {
+    let output = command
+        .output()
+        .map_err(|err| format!("cannot run {:?}: {:?}", command, err))?;
+    if !output.status.success() {
+        return Err(format!(
+            "{:?} failed: {}",
+            command,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_works() {
+        let release = NodeRelease::new(" v0.23.0 ", &"ab".repeat(32)).unwrap();
+        assert_eq!(release.version(), "v0.23.0");
+        assert_eq!(release.sha256, [0xAB; 32]);
+        assert!(NodeRelease::new("", &"ab".repeat(32)).is_err());
+        assert!(NodeRelease::new("v0.23.0", "abab").is_err());
+        assert!(NodeRelease::new("v0.23.0", &"zz".repeat(32)).is_err());
+    }
+
+    #[test]
+    fn archive_url_works() {
+        let release = NodeRelease::new("v0.23.0", &"ab".repeat(32))
+            .unwrap()
+            .with_asset("node.tar.gz");
+        assert_eq!(
+            release.archive_url(),
+            Ok(
+                "https://github.com/paritytech/substrate-contracts-node/releases/download/v0.23.0/node.tar.gz"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn cache_dir_is_keyed_by_checksum() {
+        let release = NodeRelease::new("v0.23.0", &"ab".repeat(32)).unwrap();
+        let repinned = NodeRelease::new("v0.23.0", &"cd".repeat(32)).unwrap();
+        assert_ne!(release.cache_dir(), repinned.cache_dir());
+        assert!(release
+            .cache_dir()
+            .ends_with(format!("v0.23.0/{}", "ab".repeat(32))));
+    }
+
+    #[test]
+    fn verify_checksum_works() {
+        let sha256 = sp_core::hashing::sha2_256(b"ink!");
+        assert_eq!(verify_checksum(b"ink!", &sha256), Ok(()));
+        assert!(verify_checksum(b"ink", &sha256)
+            .unwrap_err()
+            .starts_with("checksum mismatch"));
+    }
+
+    #[test]
+    fn find_binary_works() {
+        let dir = std::env::temp_dir()
+            .join(format!("ink_e2e_find_binary_{}", std::process::id()));
+        let nested = dir.join("artifacts").join("linux");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(nested.join("README"), b"").unwrap();
+        assert_eq!(find_binary(&dir), None);
+        std::fs::write(nested.join(BINARY_NAME), b"").unwrap();
+        assert_eq!(find_binary(&dir), Some(nested.join(BINARY_NAME)));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -8,3 +8,207 @@ async fn fail_initialization_with_no_node() {
     )
     .await;
 }
+
+#[test]
+fn balance_delta_between_works() {
+    use crate::BalanceDelta;
+    assert_eq!(
+        BalanceDelta::between(100u128, 150),
+        BalanceDelta::Increase(50)
+    );
+    assert_eq!(
+        BalanceDelta::between(150u128, 100),
+        BalanceDelta::Decrease(50)
+    );
+    assert_eq!(
+        BalanceDelta::between(100u128, 100),
+        BalanceDelta::Increase(0)
+    );
+}
+
+#[test]
+fn signer_from_account_id_works() {
+    use crate::{
+        signer_from_account_id,
+        PolkadotConfig,
+    };
+
+    let account_id = sp_runtime::AccountId32::new([7u8; 32]);
+    let signer = signer_from_account_id::<PolkadotConfig>(account_id.clone());
+    assert_eq!(signer.account_id(), &account_id);
+    assert_eq!(
+        signer.sudo().account_id(),
+        crate::alice::<PolkadotConfig>().account_id()
+    );
+
+    let signer = signer.with_sudo(crate::bob());
+    assert_eq!(
+        signer.sudo().account_id(),
+        crate::bob::<PolkadotConfig>().account_id()
+    );
+}
+
+#[test]
+fn client_supports_custom_environment() {
+    use ink_env::{
+        DefaultEnvironment,
+        Environment,
+    };
+
+    /// An environment whose balances are 64 bit wide.
+    enum CustomEnvironment {}
+
+    impl Environment for CustomEnvironment {
+        const MAX_EVENT_TOPICS: usize = 4;
+
+        type AccountId = <DefaultEnvironment as Environment>::AccountId;
+        type Balance = u64;
+        type Hash = <DefaultEnvironment as Environment>::Hash;
+        type Timestamp = <DefaultEnvironment as Environment>::Timestamp;
+        type BlockNumber = <DefaultEnvironment as Environment>::BlockNumber;
+        type ChainExtension = <DefaultEnvironment as Environment>::ChainExtension;
+    }
+
+    // The client, which `#[ink_e2e::test(environment = ...)]` creates, accepts the
+    // custom balance type.
+    let _ = crate::Client::<crate::PolkadotConfig, CustomEnvironment>::new;
+    assert_eq!(
+        crate::BalanceDelta::between(<CustomEnvironment as Environment>::Balance::MAX, 0),
+        crate::BalanceDelta::Decrease(u64::MAX)
+    );
+}
+
+/// A runtime whose 20 byte accounts are derived from `ecdsa` keys.
+mod eth {
+    use sp_core::{
+        ecdsa,
+        Pair as _,
+    };
+    use sp_runtime::traits::{
+        IdentifyAccount,
+        Lazy,
+        Verify,
+    };
+
+    pub type AccountId = [u8; 20];
+
+    pub enum EthConfig {}
+
+    impl subxt::Config for EthConfig {
+        type Index = u32;
+        type BlockNumber = u32;
+        type Hash = sp_core::H256;
+        type Hashing = sp_runtime::traits::BlakeTwo256;
+        type AccountId = AccountId;
+        type Address = AccountId;
+        type Header = sp_runtime::generic::Header<
+            Self::BlockNumber,
+            sp_runtime::traits::BlakeTwo256,
+        >;
+        type Signature = EthSignature;
+        type ExtrinsicParams = subxt::tx::SubstrateExtrinsicParams<Self>;
+    }
+
+    pub enum EthEnvironment {}
+
+    impl ink_env::Environment for EthEnvironment {
+        const MAX_EVENT_TOPICS: usize = 4;
+
+        type AccountId = AccountId;
+        type Balance = u128;
+        type Hash = [u8; 32];
+        type Timestamp = u64;
+        type BlockNumber = u32;
+        type ChainExtension = ink_env::NoChainExtension;
+    }
+
+    fn account_id(public: &ecdsa::Public) -> AccountId {
+        let hash = sp_core::keccak_256(public.as_ref());
+        let mut account_id = [0; 20];
+        account_id.copy_from_slice(&hash[12..]);
+        account_id
+    }
+
+    #[derive(Debug, scale::Encode, scale::Decode)]
+    pub struct EthSignature(ecdsa::Signature);
+
+    pub struct EthSigner(ecdsa::Public);
+
+    impl IdentifyAccount for EthSigner {
+        type AccountId = AccountId;
+
+        fn into_account(self) -> AccountId {
+            account_id(&self.0)
+        }
+    }
+
+    impl Verify for EthSignature {
+        type Signer = EthSigner;
+
+        fn verify<L: Lazy<[u8]>>(&self, mut msg: L, signer: &AccountId) -> bool {
+            matches!(
+                self.0.recover(msg.get()),
+                Some(public) if &account_id(&public) == signer
+            )
+        }
+    }
+
+    /// Signs with an `ecdsa` key, which the default accounts don't have.
+    pub struct EthKeypair {
+        pair: ecdsa::Pair,
+        account_id: AccountId,
+    }
+
+    impl EthKeypair {
+        pub fn from_seed(seed: &[u8; 32]) -> Self {
+            let pair = ecdsa::Pair::from_seed(seed);
+            Self {
+                account_id: account_id(&pair.public()),
+                pair,
+            }
+        }
+    }
+
+    impl subxt::tx::Signer<EthConfig> for EthKeypair {
+        fn account_id(&self) -> &AccountId {
+            &self.account_id
+        }
+
+        fn address(&self) -> AccountId {
+            self.account_id
+        }
+
+        fn sign(&self, signer_payload: &[u8]) -> EthSignature {
+            EthSignature(self.pair.sign(signer_payload))
+        }
+    }
+}
+
+#[test]
+fn client_accepts_custom_signer() {
+    use eth::{
+        EthConfig,
+        EthEnvironment,
+        EthKeypair,
+    };
+    use sp_runtime::traits::Verify;
+    use subxt::tx::Signer as _;
+
+    // The client signs with any `TxSigner`, so tests of runtimes with 20 byte
+    // accounts can submit extrinsics.
+    #[allow(dead_code)]
+    async fn upload(client: &mut crate::Client<EthConfig, EthEnvironment>) {
+        let mut signer = EthKeypair::from_seed(&[1; 32]);
+        let _ = client.upload(&mut signer, "contract.contract", None).await;
+        let _ = client
+            .balance(*<EthKeypair as subxt::tx::Signer<EthConfig>>::account_id(
+                &signer,
+            ))
+            .await;
+    }
+
+    let signer = EthKeypair::from_seed(&[1; 32]);
+    let signature = signer.sign(b"payload");
+    assert!(signature.verify(&b"payload"[..], signer.account_id()));
+    assert!(!signature.verify(&b"other payload"[..], signer.account_id()));
+}
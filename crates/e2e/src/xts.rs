@@ -14,12 +14,9 @@
 
 use super::{
     log_info,
-    sr25519,
     ContractExecResult,
     ContractInstantiateResult,
-    IdentifyAccount,
-    Signer,
-    Verify,
+    TxSigner,
 };
 use ink_env::Environment;
 
@@ -33,14 +30,14 @@ use jsonrpsee::{
     },
 };
 use pallet_contracts_primitives::CodeUploadResult;
-use sp_core::{
-    Bytes,
-    H256,
-};
+use sp_core::Bytes;
 use sp_weights::Weight;
 use subxt::{
     blocks::ExtrinsicEvents,
-    tx::ExtrinsicParams,
+    tx::{
+        ExtrinsicParams,
+        TxProgress,
+    },
     OnlineClient,
 };
 
@@ -63,6 +60,18 @@ pub struct InstantiateWithCode<B> {
     salt: Vec<u8>,
 }
 
+/// A raw call to `pallet-contracts`'s `instantiate`.
+#[derive(Debug, scale::Encode, scale::Decode)]
+pub struct Instantiate<C: subxt::Config, B> {
+    #[codec(compact)]
+    value: B,
+    gas_limit: Weight,
+    storage_deposit_limit: Option<B>,
+    code_hash: C::Hash,
+    data: Vec<u8>,
+    salt: Vec<u8>,
+}
+
 /// A raw call to `pallet-contracts`'s `call`.
 #[derive(Debug, scale::Encode, scale::Decode)]
 pub struct Call<C: subxt::Config, B> {
@@ -74,11 +83,55 @@ pub struct Call<C: subxt::Config, B> {
     data: Vec<u8>,
 }
 
+/// A raw call to `pallet-sudo`'s `sudo_as`.
+#[derive(Debug)]
+pub struct SudoAs<C: subxt::Config> {
+    who: sp_runtime::MultiAddress<C::AccountId, ()>,
+    /// The encoded call data of the dispatched call.
+    call: Vec<u8>,
+}
+
+impl<C: subxt::Config> scale::Encode for SudoAs<C> {
+    fn encode_to<T: scale::Output + ?Sized>(&self, dest: &mut T) {
+        self.who.encode_to(dest);
+        // The call is a boxed `RuntimeCall`, which is encoded as is.
+        dest.write(&self.call);
+    }
+}
+
 /// A raw call to `pallet-contracts`'s `upload`.
-#[derive(Debug, scale::Encode, scale::Decode)]
+#[derive(Debug)]
 pub struct UploadCode<B> {
     code: Vec<u8>,
     storage_deposit_limit: Option<B>,
+    determinism: Option<Determinism>,
+}
+
+impl<B: scale::Encode> scale::Encode for UploadCode<B> {
+    fn encode_to<T: scale::Output + ?Sized>(&self, dest: &mut T) {
+        self.code.encode_to(dest);
+        self.storage_deposit_limit.encode_to(dest);
+        // Only nodes supporting indeterministic code take the determinism.
+        if let Some(determinism) = &self.determinism {
+            determinism.encode_to(dest);
+        }
+    }
+}
+
+/// The determinism required of uploaded code.
+///
+/// # Note
+///
+/// The determinism is only passed to the node if it is given explicitly, since
+/// older versions of `pallet-contracts` don't take it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Serialize, scale::Encode)]
+pub enum Determinism {
+    /// The code must execute deterministically, as required for all code that
+    /// can be instantiated or called by extrinsics.
+    Enforced,
+    /// The code may use indeterministic instructions such as floating point
+    /// arithmetic. Such code can only be executed by off-chain RPC calls.
+    Relaxed,
 }
 
 /// A struct that encodes RPC parameters required to instantiate a new smart contract.
@@ -89,7 +142,7 @@ struct RpcInstantiateRequest<C: subxt::Config, E: Environment> {
     value: E::Balance,
     gas_limit: Option<Weight>,
     storage_deposit_limit: Option<E::Balance>,
-    code: Code,
+    code: Code<C::Hash>,
     data: Vec<u8>,
     salt: Vec<u8>,
 }
@@ -104,6 +157,9 @@ where
     origin: C::AccountId,
     code: Vec<u8>,
     storage_deposit_limit: Option<E::Balance>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[codec(skip)]
+    determinism: Option<Determinism>,
 }
 
 /// A struct that encodes RPC parameters required for a call to a smart contract.
@@ -123,12 +179,11 @@ struct RpcCallRequest<C: subxt::Config, E: Environment> {
 /// Reference to an existing code hash or a new Wasm module.
 #[derive(serde::Serialize, scale::Encode)]
 #[serde(rename_all = "camelCase")]
-enum Code {
+enum Code<Hash> {
     /// A Wasm module as raw bytes.
     Upload(Vec<u8>),
-    #[allow(unused)]
     /// The code hash of an on-chain Wasm blob.
-    Existing(H256),
+    Existing(Hash),
 }
 
 /// Provides functions for interacting with the `pallet-contracts` API.
@@ -144,17 +199,12 @@ where
     C::AccountId: Into<C::Address> + serde::de::DeserializeOwned,
     <C::ExtrinsicParams as ExtrinsicParams<C::Index, C::Hash>>::OtherParams: Default,
 
-    C::Signature: From<sr25519::Signature>,
-    <C::Signature as Verify>::Signer: From<sr25519::Public>,
-    <C::Signature as Verify>::Signer:
-        From<sr25519::Public> + IdentifyAccount<AccountId = C::AccountId>,
-    sr25519::Signature: Into<C::Signature>,
-
     E: Environment,
     E::Balance: scale::Encode + serde::Serialize,
 
     Call<C, E::Balance>: scale::Encode,
     InstantiateWithCode<E::Balance>: scale::Encode,
+    Instantiate<C, E::Balance>: scale::Encode,
 {
     /// Creates a new [`ContractsApi`] instance.
     pub async fn new(client: OnlineClient<C>, url: &str) -> Self {
@@ -174,16 +224,56 @@ where
     }
 
     /// Dry runs the instantiation of the given `code`.
-    pub async fn instantiate_with_code_dry_run(
+    pub async fn instantiate_with_code_dry_run<S: TxSigner<C>>(
         &self,
         value: E::Balance,
         storage_deposit_limit: Option<E::Balance>,
         code: Vec<u8>,
         data: Vec<u8>,
         salt: Vec<u8>,
-        signer: &Signer<C>,
+        signer: &S,
+    ) -> ContractInstantiateResult<C::AccountId, E::Balance> {
+        self.exec_instantiate_dry_run(
+            value,
+            storage_deposit_limit,
+            Code::Upload(code),
+            data,
+            salt,
+            signer,
+        )
+        .await
+    }
+
+    /// Dry runs the instantiation of the code uploaded with `code_hash`.
+    pub async fn instantiate_dry_run<S: TxSigner<C>>(
+        &self,
+        value: E::Balance,
+        storage_deposit_limit: Option<E::Balance>,
+        code_hash: C::Hash,
+        data: Vec<u8>,
+        salt: Vec<u8>,
+        signer: &S,
+    ) -> ContractInstantiateResult<C::AccountId, E::Balance> {
+        self.exec_instantiate_dry_run(
+            value,
+            storage_deposit_limit,
+            Code::Existing(code_hash),
+            data,
+            salt,
+            signer,
+        )
+        .await
+    }
+
+    async fn exec_instantiate_dry_run<S: TxSigner<C>>(
+        &self,
+        value: E::Balance,
+        storage_deposit_limit: Option<E::Balance>,
+        code: Code<C::Hash>,
+        data: Vec<u8>,
+        salt: Vec<u8>,
+        signer: &S,
     ) -> ContractInstantiateResult<C::AccountId, E::Balance> {
-        let code = Code::Upload(code);
         let call_request = RpcInstantiateRequest::<C, E> {
             origin: signer.account_id().clone(),
             value,
@@ -212,7 +302,7 @@ where
     /// Returns when the transaction is included in a block. The return value
     /// contains all events that are associated with this transaction.
     #[allow(clippy::too_many_arguments)]
-    pub async fn instantiate_with_code(
+    pub async fn instantiate_with_code<S: TxSigner<C>>(
         &self,
         value: E::Balance,
         gas_limit: Weight,
@@ -220,7 +310,7 @@ where
         code: Vec<u8>,
         data: Vec<u8>,
         salt: Vec<u8>,
-        signer: &Signer<C>,
+        signer: &S,
     ) -> ExtrinsicEvents<C> {
         let call = subxt::tx::StaticTxPayload::new(
             "Contracts",
@@ -237,9 +327,87 @@ where
         )
         .unvalidated();
 
+        self.submit_and_wait(&call, signer).await
+    }
+
+    /// Submits an extrinsic to instantiate the code uploaded with `code_hash`.
+    ///
+    /// Returns when the transaction is included in a block. The return value
+    /// contains all events that are associated with this transaction.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn instantiate<S: TxSigner<C>>(
+        &self,
+        value: E::Balance,
+        gas_limit: Weight,
+        storage_deposit_limit: Option<E::Balance>,
+        code_hash: C::Hash,
+        data: Vec<u8>,
+        salt: Vec<u8>,
+        signer: &S,
+    ) -> ExtrinsicEvents<C> {
+        let call = subxt::tx::StaticTxPayload::new(
+            "Contracts",
+            "instantiate",
+            Instantiate::<C, E::Balance> {
+                value,
+                gas_limit,
+                storage_deposit_limit,
+                code_hash,
+                data,
+                salt,
+            },
+            Default::default(),
+        )
+        .unvalidated();
+
+        self.submit_and_wait(&call, signer).await
+    }
+
+    /// Submits an extrinsic to replace the runtime of the chain with `code`.
+    ///
+    /// The `System::set_code` call is dispatched via `Sudo::sudo_unchecked_weight`,
+    /// hence the `signer` must be the sudo key of the chain.
+    ///
+    /// Returns when the transaction is included in a block. The return value
+    /// contains all events that are associated with this transaction.
+    pub async fn sudo_set_code<S: TxSigner<C>>(
+        &self,
+        code: Vec<u8>,
+        signer: &S,
+    ) -> ExtrinsicEvents<C> {
+        use api::runtime_types::{
+            contracts_node_runtime::RuntimeCall,
+            frame_system::pallet::Call as SystemCall,
+            sp_weights::weight_v2::Weight as RuntimeWeight,
+        };
+        let call = api::tx()
+            .sudo()
+            .sudo_unchecked_weight(
+                RuntimeCall::System(SystemCall::set_code { code }),
+                // The weight of `set_code` is the maximum block weight, which
+                // `sudo_unchecked_weight` allows to override.
+                RuntimeWeight {
+                    ref_time: 0,
+                    proof_size: 0,
+                },
+            )
+            .unvalidated();
+
+        self.submit_and_wait(&call, signer).await
+    }
+
+    /// Signs and submits the extrinsic `call` and waits for its inclusion in a block.
+    async fn submit_and_wait<Call, S: TxSigner<C>>(
+        &self,
+        call: &Call,
+        signer: &S,
+    ) -> ExtrinsicEvents<C>
+    where
+        Call: subxt::tx::TxPayload,
+    {
         self.client
             .tx()
-            .sign_and_submit_then_watch_default(&call, signer)
+            .sign_and_submit_then_watch_default(call, signer)
             .await
             .map(|tx_progress| {
                 log_info(&format!(
@@ -267,19 +435,26 @@ where
     }
 
     /// Dry runs the upload of the given `code`.
-    pub async fn upload_dry_run(
+    pub async fn upload_dry_run<S: TxSigner<C>>(
         &self,
-        signer: &Signer<C>,
+        signer: &S,
         code: Vec<u8>,
         storage_deposit_limit: Option<E::Balance>,
+        determinism: Option<Determinism>,
     ) -> CodeUploadResult<C::Hash, E::Balance> {
         let call_request = RpcCodeUploadRequest::<C, E> {
             origin: signer.account_id().clone(),
             code,
             storage_deposit_limit,
+            determinism,
         };
         let func = "ContractsApi_upload_code";
-        let params = rpc_params![func, Bytes(scale::Encode::encode(&call_request))];
+        let mut encoded = scale::Encode::encode(&call_request);
+        // Only nodes supporting indeterministic code take the determinism.
+        if let Some(determinism) = determinism {
+            scale::Encode::encode_to(&determinism, &mut encoded);
+        }
+        let params = rpc_params![func, Bytes(encoded)];
         let bytes: Bytes = self
             .ws_client
             .request("state_call", params)
@@ -295,11 +470,12 @@ where
     ///
     /// Returns when the transaction is included in a block. The return value
     /// contains all events that are associated with this transaction.
-    pub async fn upload(
+    pub async fn upload<S: TxSigner<C>>(
         &self,
-        signer: &Signer<C>,
+        signer: &S,
         code: Vec<u8>,
         storage_deposit_limit: Option<E::Balance>,
+        determinism: Option<Determinism>,
     ) -> ExtrinsicEvents<C> {
         let call = subxt::tx::StaticTxPayload::new(
             "Contracts",
@@ -307,38 +483,13 @@ where
             UploadCode::<E::Balance> {
                 code,
                 storage_deposit_limit,
+                determinism,
             },
             Default::default(),
         )
         .unvalidated();
 
-        self.client
-            .tx()
-            .sign_and_submit_then_watch_default(&call, signer)
-            .await
-            .map(|tx_progress| {
-                log_info(&format!(
-                    "signed and submitted tx with hash {:?}",
-                    tx_progress.extrinsic_hash()
-                ));
-                tx_progress
-            })
-            .unwrap_or_else(|err| {
-                panic!(
-                    "error on call `sign_and_submit_then_watch_default`: {:?}",
-                    err
-                );
-            })
-            .wait_for_in_block()
-            .await
-            .unwrap_or_else(|err| {
-                panic!("error on call `wait_for_in_block`: {:?}", err);
-            })
-            .fetch_events()
-            .await
-            .unwrap_or_else(|err| {
-                panic!("error on call `fetch_events`: {:?}", err);
-            })
+        self.submit_and_wait(&call, signer).await
     }
 
     /// Dry runs a call of the contract at `contract` with the given parameters.
@@ -371,19 +522,60 @@ where
             .unwrap_or_else(|err| panic!("decoding ContractExecResult failed: {}", err))
     }
 
+    /// Returns the value stored under the hashed `key` in the child trie `trie_id`
+    /// of a contract.
+    pub async fn child_storage(&self, trie_id: &[u8], key: &[u8]) -> Option<Vec<u8>> {
+        let child_storage_key = [b":child_storage:default:", trie_id].concat();
+        let params = rpc_params![Bytes(child_storage_key), Bytes(key.to_vec())];
+        let value: Option<Bytes> = self
+            .ws_client
+            .request("childstate_getStorage", params)
+            .await
+            .unwrap_or_else(|err| {
+                panic!("error on ws request `childstate_getStorage`: {:?}", err);
+            });
+        value.map(|bytes| bytes.0)
+    }
+
     /// Submits an extrinsic to call a contract with the given parameters.
     ///
     /// Returns when the transaction is included in a block. The return value
     /// contains all events that are associated with this transaction.
-    pub async fn call(
+    pub async fn call<S: TxSigner<C>>(
         &self,
         contract: sp_runtime::MultiAddress<C::AccountId, ()>,
         value: E::Balance,
         gas_limit: Weight,
         storage_deposit_limit: Option<E::Balance>,
         data: Vec<u8>,
-        signer: &Signer<C>,
+        signer: &S,
     ) -> ExtrinsicEvents<C> {
+        let tx_progress = self
+            .submit_call(
+                contract,
+                value,
+                gas_limit,
+                storage_deposit_limit,
+                data,
+                signer,
+            )
+            .await;
+        Self::wait_for_events(tx_progress).await
+    }
+
+    /// Submits an extrinsic to call a contract with the given parameters.
+    ///
+    /// Returns as soon as the transaction is submitted, use
+    /// [`ContractsApi::wait_for_events`] to wait for its inclusion in a block.
+    pub async fn submit_call<S: TxSigner<C>>(
+        &self,
+        contract: sp_runtime::MultiAddress<C::AccountId, ()>,
+        value: E::Balance,
+        gas_limit: Weight,
+        storage_deposit_limit: Option<E::Balance>,
+        data: Vec<u8>,
+        signer: &S,
+    ) -> TxProgress<C, OnlineClient<C>> {
         let call = subxt::tx::StaticTxPayload::new(
             "Contracts",
             "call",
@@ -415,6 +607,64 @@ where
                     err
                 );
             })
+    }
+
+    /// Submits an extrinsic to call a contract on behalf of `who`.
+    ///
+    /// The `Contracts::call` is dispatched via `Sudo::sudo_as`, hence the
+    /// `signer` must be the sudo key of the chain.
+    ///
+    /// Returns when the transaction is included in a block. The return value
+    /// contains all events that are associated with this transaction.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn sudo_call_as<S: TxSigner<C>>(
+        &self,
+        who: C::AccountId,
+        contract: sp_runtime::MultiAddress<C::AccountId, ()>,
+        value: E::Balance,
+        gas_limit: Weight,
+        storage_deposit_limit: Option<E::Balance>,
+        data: Vec<u8>,
+        signer: &S,
+    ) -> ExtrinsicEvents<C> {
+        let call = subxt::tx::StaticTxPayload::new(
+            "Contracts",
+            "call",
+            Call::<C, E::Balance> {
+                dest: contract,
+                value,
+                gas_limit,
+                storage_deposit_limit,
+                data,
+            },
+            Default::default(),
+        )
+        .unvalidated();
+        let call = self.client.tx().call_data(&call).unwrap_or_else(|err| {
+            panic!("error on encoding the call data: {:?}", err);
+        });
+        let sudo_as = subxt::tx::StaticTxPayload::new(
+            "Sudo",
+            "sudo_as",
+            SudoAs::<C> {
+                who: sp_runtime::MultiAddress::Id(who),
+                call,
+            },
+            Default::default(),
+        )
+        .unvalidated();
+
+        self.submit_and_wait(&sudo_as, signer).await
+    }
+
+    /// Waits for the submitted transaction to be included in a block.
+    ///
+    /// The return value contains all events that are associated with the
+    /// transaction.
+    pub async fn wait_for_events(
+        tx_progress: TxProgress<C, OnlineClient<C>>,
+    ) -> ExtrinsicEvents<C> {
+        tx_progress
             .wait_for_in_block()
             .await
             .unwrap_or_else(|err| {
@@ -27,6 +27,8 @@ pub struct ChainExtensionHandler {
     registered: HashMap<ExtensionId, Box<dyn ChainExtension>>,
     /// The output buffer used and reused for chain extension method call results.
     output: Vec<u8>,
+    /// The inputs of all calls to the chain extension methods in order.
+    recorded_calls: HashMap<ExtensionId, Vec<Vec<u8>>>,
 }
 
 /// The unique ID of the registered chain extension method.
@@ -67,6 +69,7 @@ impl ChainExtensionHandler {
         Self {
             registered: HashMap::new(),
             output: Vec::new(),
+            recorded_calls: HashMap::new(),
         }
     }
 
@@ -74,6 +77,31 @@ impl ChainExtensionHandler {
     pub fn reset(&mut self) {
         self.registered.clear();
         self.output.clear();
+        self.recorded_calls.clear();
+    }
+
+    /// Clears the recorded calls to the chain extension methods.
+    ///
+    /// The registered chain extensions are kept.
+    pub fn clear_recorded_calls(&mut self) {
+        self.recorded_calls.clear();
+    }
+
+    /// Records a call to the chain extension method registered at `func_id`.
+    pub fn record_call(&mut self, func_id: u32, input: &[u8]) {
+        self.recorded_calls
+            .entry(ExtensionId::from(func_id))
+            .or_default()
+            .push(input.to_vec());
+    }
+
+    /// Returns the inputs of all recorded calls to the chain extension method
+    /// registered at `func_id` in order.
+    pub fn recorded_calls(&self, func_id: u32) -> &[Vec<u8>] {
+        self.recorded_calls
+            .get(&ExtensionId::from(func_id))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
     }
 
     /// Register a new chain extension.
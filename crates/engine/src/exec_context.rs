@@ -18,6 +18,7 @@ use super::types::{
     BlockNumber,
     BlockTimestamp,
 };
+use std::collections::HashSet;
 
 /// The context of a contract execution.
 #[cfg_attr(test, derive(Debug, PartialEq, Eq))]
@@ -43,6 +44,12 @@ pub struct ExecContext {
     pub block_number: BlockNumber,
     /// The current block timestamp.
     pub block_timestamp: BlockTimestamp,
+    /// The gas limit of the execution, `None` if unlimited.
+    pub gas_limit: Option<u64>,
+    /// The gas consumed by the execution so far.
+    pub gas_consumed: u64,
+    /// The accounts to which every transfer fails.
+    pub failing_transfers: HashSet<AccountId>,
 }
 
 impl ExecContext {
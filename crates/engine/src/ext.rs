@@ -95,6 +95,8 @@ define_error_codes! {
     /// The call to `debug_message` had no effect because debug message
     /// recording was disabled.
     LoggingDisabled = 9,
+    /// The call dispatched by `call_runtime` was executed but returned an error.
+    CallRuntimeFailed = 10,
     /// ECDSA public key recovery failed. Most probably wrong recovery id or signature.
     EcdsaRecoveryFailed = 11,
 }
@@ -135,6 +137,8 @@ pub struct ChainSpec {
     pub minimum_balance: Balance,
     /// The targeted block time.
     pub block_time: BlockTimestamp,
+    /// The gas charged for host functions.
+    pub gas_schedule: GasSchedule,
 }
 
 /// The default values for the chain specification are:
@@ -150,10 +154,62 @@ impl Default for ChainSpec {
             gas_price: 100,
             minimum_balance: 1000000,
             block_time: 6,
+            gas_schedule: GasSchedule::default(),
         }
     }
 }
 
+/// The gas charged by the off-chain engine for host functions.
+///
+/// Only storage accesses and emitted events are charged, so that tests can
+/// catch unbounded iteration over contract storage.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct GasSchedule {
+    /// Charged for every read of a storage cell.
+    pub storage_read: u64,
+    /// Charged for every write or removal of a storage cell.
+    pub storage_write: u64,
+    /// Charged for every byte read from or written to storage.
+    pub storage_per_byte: u64,
+    /// Charged for every emitted event.
+    pub deposit_event: u64,
+    /// Charged for every byte of the data and topics of an emitted event.
+    pub deposit_event_per_byte: u64,
+}
+
+/// The default values for the gas schedule are:
+///
+///   * `storage_read`: 1_000
+///   * `storage_write`: 2_000
+///   * `storage_per_byte`: 10
+///   * `deposit_event`: 1_000
+///   * `deposit_event_per_byte`: 10
+///
+/// They only roughly reflect the relative costs on a real chain.
+impl Default for GasSchedule {
+    fn default() -> Self {
+        Self {
+            storage_read: 1_000,
+            storage_write: 2_000,
+            storage_per_byte: 10,
+            deposit_event: 1_000,
+            deposit_event_per_byte: 10,
+        }
+    }
+}
+
+/// The panic payload of an execution which exceeded its gas limit.
+///
+/// The engine panics with this payload via [`std::panic::panic_any`] so that
+/// an out-of-gas execution can be told apart from other panics.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct OutOfGas {
+    /// The gas limit of the execution.
+    pub gas_limit: u64,
+    /// The gas the execution required when it ran out of gas.
+    pub gas_required: u64,
+}
+
 impl Engine {
     // Creates a new `Engine instance.
     pub fn new() -> Self {
@@ -181,6 +237,13 @@ impl Engine {
             .map_err(|_| Error::TransferFailed)?;
 
         let dest = account_id.to_vec();
+        if self
+            .exec_context
+            .failing_transfers
+            .contains(&AccountId::from_bytes(&dest))
+        {
+            return Err(Error::TransferFailed)
+        }
         // Note that the destination account does not have to exist
         let dest_old_balance = self.get_balance(dest.clone()).unwrap_or_default();
 
@@ -198,6 +261,13 @@ impl Engine {
 
     /// Deposits an event identified by the supplied topics and data.
     pub fn deposit_event(&mut self, topics: &[u8], data: &[u8]) {
+        let schedule = self.chain_spec.gas_schedule;
+        self.charge_gas(per_byte(
+            schedule.deposit_event,
+            schedule.deposit_event_per_byte,
+            topics.len() + data.len(),
+        ));
+
         // The first byte contains the number of topics in the slice
         let topics_count: scale::Compact<u32> = scale::Decode::decode(&mut &topics[0..1])
             .unwrap_or_else(|err| panic!("decoding number of topics failed: {}", err));
@@ -226,6 +296,7 @@ impl Engine {
     /// Writes the encoded value into the storage at the given key.
     /// Returns the size of the previously stored value at the key if any.
     pub fn set_storage(&mut self, key: &[u8], encoded_value: &[u8]) -> Option<u32> {
+        self.charge_storage_write(encoded_value.len());
         let callee = self.get_callee();
         let account_id = AccountId::from_bytes(&callee[..]);
 
@@ -242,6 +313,7 @@ impl Engine {
     pub fn get_storage(&mut self, key: &[u8], output: &mut &mut [u8]) -> Result {
         let callee = self.get_callee();
         let account_id = AccountId::from_bytes(&callee[..]);
+        self.charge_storage_read(self.stored_len(&callee, key));
 
         self.debug_info.inc_reads(account_id);
         match self.database.get_from_contract_storage(&callee, key) {
@@ -258,6 +330,7 @@ impl Engine {
     pub fn take_storage(&mut self, key: &[u8], output: &mut &mut [u8]) -> Result {
         let callee = self.get_callee();
         let account_id = AccountId::from_bytes(&callee[..]);
+        self.charge_storage_write(self.stored_len(&callee, key));
 
         self.debug_info.inc_writes(account_id);
         match self.database.remove_contract_storage(&callee, key) {
@@ -273,6 +346,7 @@ impl Engine {
     pub fn contains_storage(&mut self, key: &[u8]) -> Option<u32> {
         let callee = self.get_callee();
         let account_id = AccountId::from_bytes(&callee[..]);
+        self.charge_storage_read(self.stored_len(&callee, key));
 
         self.debug_info.inc_reads(account_id);
         self.database
@@ -285,6 +359,7 @@ impl Engine {
     pub fn clear_storage(&mut self, key: &[u8]) -> Option<u32> {
         let callee = self.get_callee();
         let account_id = AccountId::from_bytes(&callee[..]);
+        self.charge_storage_write(self.stored_len(&callee, key));
         self.debug_info.inc_writes(account_id.clone());
         let _ = self
             .debug_info
@@ -401,8 +476,62 @@ impl Engine {
         set_output(output, &block_timestamp[..])
     }
 
-    pub fn gas_left(&self, _output: &mut &mut [u8]) {
-        unimplemented!("off-chain environment does not yet support `gas_left`");
+    /// Returns the gas left until the gas limit of the execution is reached.
+    ///
+    /// Returns `u64::MAX` if no gas limit has been set.
+    pub fn gas_left(&self, output: &mut &mut [u8]) {
+        let gas_left = self
+            .exec_context
+            .gas_limit
+            .map(|gas_limit| gas_limit.saturating_sub(self.exec_context.gas_consumed))
+            .unwrap_or(u64::MAX);
+        set_output(output, &scale::Encode::encode(&gas_left)[..])
+    }
+
+    /// Charges `amount` of gas to the current execution.
+    ///
+    /// # Panics
+    ///
+    /// With an [`OutOfGas`] payload if the gas limit of the execution is exceeded.
+    pub fn charge_gas(&mut self, amount: u64) {
+        let gas_required = self.exec_context.gas_consumed.saturating_add(amount);
+        self.exec_context.gas_consumed = gas_required;
+        if let Some(gas_limit) = self.exec_context.gas_limit {
+            if gas_required > gas_limit {
+                panic_any(OutOfGas {
+                    gas_limit,
+                    gas_required,
+                })
+            }
+        }
+    }
+
+    /// Charges the gas for reading a storage cell of `len` bytes.
+    fn charge_storage_read(&mut self, len: usize) {
+        let schedule = self.chain_spec.gas_schedule;
+        self.charge_gas(per_byte(
+            schedule.storage_read,
+            schedule.storage_per_byte,
+            len,
+        ));
+    }
+
+    /// Charges the gas for writing or removing a storage cell of `len` bytes.
+    fn charge_storage_write(&mut self, len: usize) {
+        let schedule = self.chain_spec.gas_schedule;
+        self.charge_gas(per_byte(
+            schedule.storage_write,
+            schedule.storage_per_byte,
+            len,
+        ));
+    }
+
+    /// Returns the length of the value stored under `key` by `callee`, if any.
+    fn stored_len(&self, callee: &[u8], key: &[u8]) -> usize {
+        self.database
+            .get_from_contract_storage(callee, key)
+            .map(|value| value.len())
+            .unwrap_or(0)
     }
 
     /// Returns the minimum balance that is required for creating an account
@@ -452,6 +581,7 @@ impl Engine {
         input: &[u8],
         output: &mut &mut [u8],
     ) {
+        self.chain_extension_handler.record_call(func_id, input);
         let encoded_input = input.encode();
         let (status_code, out) = self
             .chain_extension_handler
@@ -467,6 +597,14 @@ impl Engine {
         set_output(output, &decoded[..])
     }
 
+    /// Records the SCALE encoded runtime `call` dispatched by the contract.
+    ///
+    /// The call is not executed since there is no runtime in the off-chain environment.
+    pub fn call_runtime(&mut self, call: &[u8]) -> Result {
+        self.debug_info.record_runtime_call(call.to_vec());
+        Ok(())
+    }
+
     /// Recovers the compressed ECDSA public key for given `signature` and `message_hash`,
     /// and stores the result in `output`.
     pub fn ecdsa_recover(
@@ -515,7 +653,17 @@ impl Engine {
     }
 }
 
-/// Copies the `slice` into `output`.
+/// Returns the gas of a host function with a `base` cost and a cost `per_byte` of `len` bytes.
+fn per_byte(base: u64, per_byte: u64, len: usize) -> u64 {
+    base.saturating_add(per_byte.saturating_mul(len as u64))
+}
+
+/// Copies the `slice` into `output` and shrinks `output` to the copied bytes.
+///
+/// The host functions of `pallet-contracts` report the length of their output via
+/// `out_len`, and the on-chain environment decodes only that many bytes of its
+/// buffer. Shrinking `output` lets the off-chain environment do the same, instead
+/// of decoding the zero-padded rest of the buffer.
 ///
 /// Panics if the slice is too large and does not fit.
 fn set_output(output: &mut &mut [u8], slice: &[u8]) {
@@ -527,4 +675,6 @@ fn set_output(output: &mut &mut [u8], slice: &[u8]) {
         output.len(),
     );
     output[..slice.len()].copy_from_slice(slice);
+    let output_buffer = core::mem::take(output);
+    *output = &mut output_buffer[..slice.len()];
 }
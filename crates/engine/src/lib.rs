@@ -30,6 +30,7 @@ mod types;
 mod tests;
 
 pub use chain_extension::ChainExtension;
+pub use database::Database;
 pub use types::AccountError;
 
 use derive_more::From;
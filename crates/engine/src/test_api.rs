@@ -13,10 +13,15 @@
 // limitations under the License.
 
 use crate::{
-    ext::Engine,
+    ext::{
+        Engine,
+        GasSchedule,
+    },
     types::{
         AccountId,
         Balance,
+        BlockNumber,
+        BlockTimestamp,
     },
     AccountError,
     Error,
@@ -83,6 +88,8 @@ pub struct DebugInfo {
     count_writes: HashMap<AccountId, usize>,
     /// The number of storage cells used by each account id.
     cells_per_account: HashMap<AccountId, HashMap<Vec<u8>, bool>>,
+    /// Dispatched runtime calls recorder.
+    runtime_calls: Vec<Vec<u8>>,
 }
 
 impl Default for DebugInfo {
@@ -100,6 +107,7 @@ impl DebugInfo {
             count_reads: HashMap::new(),
             count_writes: HashMap::new(),
             cells_per_account: HashMap::new(),
+            runtime_calls: Vec::new(),
         }
     }
 
@@ -110,6 +118,7 @@ impl DebugInfo {
         self.emitted_events.clear();
         self.emitted_debug_messages.clear();
         self.cells_per_account.clear();
+        self.runtime_calls.clear();
     }
 
     /// Increases the number of storage writes for the supplied account by one.
@@ -168,6 +177,11 @@ impl DebugInfo {
     pub fn record_event(&mut self, event: EmittedEvent) {
         self.emitted_events.push(event);
     }
+
+    /// Records a dispatched runtime call.
+    pub fn record_runtime_call(&mut self, call: Vec<u8>) {
+        self.runtime_calls.push(call);
+    }
 }
 
 impl Engine {
@@ -176,6 +190,7 @@ impl Engine {
         self.exec_context.reset();
         self.database.clear();
         self.debug_info.reset();
+        self.chain_extension_handler.clear_recorded_calls();
     }
 
     /// Returns the total number of reads and writes of the contract's storage.
@@ -220,10 +235,44 @@ impl Engine {
         Ok(cells.len())
     }
 
+    /// Returns the value stored at `key` in the storage of the callee if any.
+    ///
+    /// Unlike [`Engine::get_storage`] the read is neither charged nor recorded.
+    pub fn get_storage_raw(&self, key: &[u8]) -> Option<&[u8]> {
+        self.database
+            .get_from_contract_storage(&self.get_callee(), key)
+            .map(Vec::as_slice)
+    }
+
+    /// Writes the encoded `value` into the storage of the callee at `key`.
+    ///
+    /// Unlike [`Engine::set_storage`] the write is neither charged nor recorded,
+    /// only the storage cell is counted for [`Engine::count_used_storage_cells`].
+    pub fn set_storage_raw(&mut self, key: &[u8], value: Vec<u8>) {
+        let callee = self.get_callee();
+        self.debug_info
+            .record_cell_for_account(AccountId::from_bytes(&callee[..]), key.to_vec());
+        self.database
+            .insert_into_contract_storage(&callee, key, value);
+    }
+
     /// Advances the chain by a single block.
     pub fn advance_block(&mut self) {
-        self.exec_context.block_number += 1;
-        self.exec_context.block_timestamp += self.chain_spec.block_time;
+        self.advance_blocks(1, self.chain_spec.block_time);
+    }
+
+    /// Advances the chain by `n` blocks, each taking `block_time` to produce.
+    ///
+    /// The block number is increased by `n` and the block timestamp by
+    /// `n * block_time`.
+    pub fn advance_blocks(&mut self, n: BlockNumber, block_time: BlockTimestamp) {
+        self.exec_context.block_number += n;
+        self.exec_context.block_timestamp += BlockTimestamp::from(n) * block_time;
+    }
+
+    /// Sets the block time used by [`Engine::advance_block`].
+    pub fn set_block_time(&mut self, block_time: BlockTimestamp) {
+        self.chain_spec.block_time = block_time;
     }
 
     /// Returns the callee, i.e. the currently executing contract.
@@ -241,6 +290,16 @@ impl Engine {
         self.debug_info.emitted_events.clone().into_iter()
     }
 
+    /// Returns the number of emitted events recorded so far.
+    pub fn count_emitted_events(&self) -> usize {
+        self.debug_info.emitted_events.len()
+    }
+
+    /// Returns the SCALE encoded runtime calls dispatched so far in order.
+    pub fn get_runtime_calls(&self) -> impl Iterator<Item = Vec<u8>> {
+        self.debug_info.runtime_calls.clone().into_iter()
+    }
+
     /// Returns the current balance of `account_id`.
     pub fn get_balance(&self, account_id: Vec<u8>) -> Result<Balance, Error> {
         self.database
@@ -253,10 +312,38 @@ impl Engine {
         self.database.set_balance(&account_id, new_balance);
     }
 
+    /// Sets whether every transfer to `account_id` fails.
+    pub fn set_transfer_fails(&mut self, account_id: Vec<u8>, fails: bool) {
+        let account_id = AccountId::from(account_id);
+        if fails {
+            self.exec_context.failing_transfers.insert(account_id);
+        } else {
+            self.exec_context.failing_transfers.remove(&account_id);
+        }
+    }
+
     /// Sets the value transferred from the caller to the callee as part of the call.
     pub fn set_value_transferred(&mut self, value: Balance) {
         self.exec_context.value_transferred = value;
     }
+
+    /// Sets the gas limit of the execution and resets the consumed gas.
+    ///
+    /// A gas limit of `None` lets the execution consume unlimited gas.
+    pub fn set_gas_limit(&mut self, gas_limit: Option<u64>) {
+        self.exec_context.gas_limit = gas_limit;
+        self.exec_context.gas_consumed = 0;
+    }
+
+    /// Returns the gas consumed by the execution so far.
+    pub fn gas_consumed(&self) -> u64 {
+        self.exec_context.gas_consumed
+    }
+
+    /// Sets the gas charged for host functions.
+    pub fn set_gas_schedule(&mut self, gas_schedule: GasSchedule) {
+        self.chain_spec.gas_schedule = gas_schedule;
+    }
 }
 
 #[cfg(test)]
@@ -307,6 +394,63 @@ mod tests {
         assert_eq!(engine.count_used_storage_cells(&account_id), Ok(0));
     }
 
+    #[test]
+    fn storage_accesses_are_charged() {
+        let mut engine = Engine::new();
+        engine.set_callee(vec![1; 32]);
+        engine.set_gas_schedule(GasSchedule {
+            storage_read: 10,
+            storage_write: 20,
+            storage_per_byte: 1,
+            deposit_event: 0,
+            deposit_event_per_byte: 0,
+        });
+        let key: &[u8; 32] = &[0x42; 32];
+        let mut buf = [0_u8; 32];
+
+        engine.set_storage(key, &[0x05_u8; 5]);
+        assert_eq!(engine.gas_consumed(), 25);
+        engine.get_storage(key, &mut &mut buf[..]).unwrap();
+        assert_eq!(engine.gas_consumed(), 40);
+        engine.contains_storage(key);
+        assert_eq!(engine.gas_consumed(), 55);
+        engine.clear_storage(key);
+        assert_eq!(engine.gas_consumed(), 80);
+        // The removed entry is no longer charged by its length.
+        engine.contains_storage(key);
+        assert_eq!(engine.gas_consumed(), 90);
+        engine.clear_storage(key);
+        assert_eq!(engine.gas_consumed(), 110);
+
+        engine.set_gas_limit(None);
+        assert_eq!(engine.gas_consumed(), 0);
+    }
+
+    #[test]
+    fn exceeding_gas_limit_panics_with_out_of_gas() {
+        let mut engine = Engine::new();
+        engine.set_callee(vec![1; 32]);
+        engine.set_gas_limit(Some(2_500));
+        let key: &[u8; 32] = &[0x42; 32];
+
+        engine.set_storage(key, &[0x05_u8; 5]);
+        let mut gas_left = [0_u8; 8];
+        engine.gas_left(&mut &mut gas_left[..]);
+        assert_eq!(u64::from_le_bytes(gas_left), 450);
+
+        let payload = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            engine.set_storage(key, &[0x05_u8; 5]);
+        }))
+        .expect_err("second write must run out of gas");
+        assert_eq!(
+            payload.downcast_ref::<crate::ext::OutOfGas>(),
+            Some(&crate::ext::OutOfGas {
+                gas_limit: 2_500,
+                gas_required: 4_100,
+            })
+        );
+    }
+
     #[test]
     fn count_total_writes() {
         // given
@@ -54,6 +54,24 @@ fn store_load_clear() {
     assert_eq!(res, Err(Error::KeyNotFound));
 }
 
+#[test]
+fn output_is_shrunk_to_the_stored_value() {
+    let mut engine = Engine::new();
+    engine.set_callee(vec![1; 32]);
+    let key: &[u8; 32] = &[0x42; 32];
+    engine.set_storage(key, &[0x05_u8; 5]);
+
+    let mut buffer = get_buffer();
+    let output = &mut &mut buffer[..];
+    assert_eq!(engine.get_storage(key, output), Ok(()));
+    assert_eq!(output, &[0x05; 5]);
+
+    let mut buffer = get_buffer();
+    let output = &mut &mut buffer[..];
+    assert_eq!(engine.take_storage(key, output), Ok(()));
+    assert_eq!(output, &[0x05; 5]);
+}
+
 #[test]
 fn setting_getting_balance() {
     // given
@@ -121,6 +139,32 @@ fn transfer() {
     assert_eq!(engine.get_balance(bob), Ok(337));
 }
 
+#[test]
+fn failing_transfer() {
+    // given
+    let mut engine = Engine::new();
+    let alice = vec![1; 32];
+    let bob = vec![2; 32];
+    engine.set_callee(alice.clone());
+    engine.set_balance(alice.clone(), 1337);
+    engine.set_transfer_fails(bob.clone(), true);
+
+    // when
+    let val = scale::Encode::encode(&337u128);
+    assert_eq!(engine.transfer(&bob, &val), Err(Error::TransferFailed));
+
+    // then
+    assert_eq!(engine.get_balance(alice.clone()), Ok(1337));
+    assert!(engine.get_balance(bob.clone()).is_err());
+
+    // when
+    engine.set_transfer_fails(bob.clone(), false);
+
+    // then
+    assert_eq!(engine.transfer(&bob, &val), Ok(()));
+    assert_eq!(engine.get_balance(alice), Ok(1000));
+}
+
 #[test]
 fn debug_messages() {
     let mut engine = Engine::new();
@@ -272,3 +316,29 @@ fn ecdsa_recovery_with_secp256k1_crate() {
     // then
     assert_eq!(output, pubkey.serialize());
 }
+
+#[test]
+fn advancing_blocks_works() {
+    // given
+    let mut engine = Engine::new();
+    let get_block_number = |engine: &Engine| {
+        let mut output = get_buffer();
+        engine.block_number(&mut &mut output[..]);
+        <u32 as scale::Decode>::decode(&mut &output[..4]).expect("decoding failed")
+    };
+    let get_block_timestamp = |engine: &Engine| {
+        let mut output = get_buffer();
+        engine.block_timestamp(&mut &mut output[..]);
+        <u64 as scale::Decode>::decode(&mut &output[..8]).expect("decoding failed")
+    };
+
+    // when
+    engine.advance_block();
+    engine.set_block_time(12);
+    engine.advance_block();
+    engine.advance_blocks(10, 1_000);
+
+    // then
+    assert_eq!(get_block_number(&engine), 12);
+    assert_eq!(get_block_timestamp(&engine), 6 + 12 + 10_000);
+}
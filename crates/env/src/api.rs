@@ -15,6 +15,7 @@
 //! The public raw interface towards the host Wasm engine.
 
 use crate::{
+    arithmetic::Saturating,
     backend::{
         EnvBackend,
         ReturnFlags,
@@ -35,9 +36,14 @@ use crate::{
         HashOutput,
     },
     topics::Topics,
-    types::Gas,
+    types::{
+        Gas,
+        Weight,
+    },
     Environment,
+    Error,
     Result,
+    StorageDepositInfo,
 };
 use ink_storage_traits::Storable;
 
@@ -97,6 +103,24 @@ where
     })
 }
 
+/// Returns the weight left for the contract execution.
+///
+/// Unlike [`gas_left`] this includes the storage proof size left, which allows
+/// contracts doing batched work to stop before either of the limits is reached.
+///
+/// # Note
+///
+/// The off-chain environment does not meter the proof size, so its `proof_size`
+/// is always `u64::MAX`.
+pub fn weight_left<E>() -> Weight
+where
+    E: Environment,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        TypedEnvBackend::weight_left::<E>(instance)
+    })
+}
+
 /// Returns the current block timestamp.
 ///
 /// # Errors
@@ -253,6 +277,20 @@ where
     })
 }
 
+/// Returns the storage added and removed by the current call so far.
+///
+/// # Note
+///
+/// - Only the storage written, taken or cleared via the functions of this module is
+///   taken into account, not the storage of nested calls into other contracts.
+/// - The contract storage of an ink! message is written back after the message
+///   returned and thus not included.
+pub fn storage_deposit_info() -> StorageDepositInfo {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        EnvBackend::storage_deposit_info(instance)
+    })
+}
+
 /// Invokes a contract message and returns its result.
 ///
 /// # Note
@@ -274,9 +312,7 @@ where
     Args: scale::Encode,
     R: scale::Decode,
 {
-    <EnvInstance as OnInstance>::on_instance(|instance| {
-        TypedEnvBackend::invoke_contract::<E, Args, R>(instance, params)
-    })
+    <EnvInstance as TypedEnvBackend>::invoke_contract::<E, Args, R>(params)
 }
 
 /// Invokes a contract message via delegate call and returns its result.
@@ -332,6 +368,35 @@ where
     })
 }
 
+/// Instantiates another contract with a fallible constructor.
+///
+/// # Note
+///
+/// In contrast to [`instantiate_contract`] the error returned by a constructor of
+/// type `Result<Self, ContractError>` is decoded from the output of the reverted
+/// instantiation and handed back to the caller.
+///
+/// # Errors
+///
+/// - If the instantiation fails for any of the reasons of [`instantiate_contract`]
+///   other than the constructor returning an error.
+/// - If the returned constructor error failed to decode properly.
+pub fn try_instantiate_contract<E, Args, Salt, C, ContractError>(
+    params: &CreateParams<E, Args, Salt, C>,
+) -> Result<core::result::Result<E::AccountId, ContractError>>
+where
+    E: Environment,
+    Args: scale::Encode,
+    Salt: AsRef<[u8]>,
+    ContractError: scale::Decode,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        TypedEnvBackend::try_instantiate_contract::<E, Args, Salt, C, ContractError>(
+            instance, params,
+        )
+    })
+}
+
 /// Terminates the existence of the currently executed smart contract.
 ///
 /// This removes the calling account and transfers all remaining balance
@@ -374,6 +439,59 @@ where
     })
 }
 
+/// Transfers value from the contract to the destination account ID while keeping
+/// the contract account alive.
+///
+/// # Note
+///
+/// Unlike [`transfer`] this checks up front that the balance of the contract stays
+/// at or above the [`minimum_balance`] instead of leaving it to the runtime, so
+/// that the failure can be told apart from other transfer failures.
+///
+/// # Errors
+///
+/// - [`Error::BelowMinimumBalance`](crate::Error::BelowMinimumBalance) if the
+///   transfer would bring the balance of the contract below the minimum balance.
+/// - Any error of [`transfer`].
+pub fn transfer_keep_alive<E>(destination: E::AccountId, value: E::Balance) -> Result<()>
+where
+    E: Environment,
+{
+    let reducible = balance::<E>().saturating_sub(minimum_balance::<E>());
+    if value > reducible {
+        return Err(Error::BelowMinimumBalance)
+    }
+    transfer::<E>(destination, value)
+}
+
+/// Transfers the whole balance of the contract to the destination account ID and
+/// returns the transferred value.
+///
+/// If `keep_alive` is set the [`minimum_balance`] is kept, so that the contract
+/// account stays alive.
+///
+/// # Note
+///
+/// The contracts pallet never lets a transfer remove a contract account, so
+/// without `keep_alive` the transfer fails on-chain unless the balance of the
+/// contract is zero. Use [`terminate_contract`] to transfer the whole balance
+/// while removing the contract.
+///
+/// # Errors
+///
+/// Any error of [`transfer`].
+pub fn transfer_all<E>(destination: E::AccountId, keep_alive: bool) -> Result<E::Balance>
+where
+    E: Environment,
+{
+    let mut value = balance::<E>();
+    if keep_alive {
+        value = value.saturating_sub(minimum_balance::<E>());
+    }
+    transfer::<E>(destination, value)?;
+    Ok(value)
+}
+
 /// Returns the execution input to the executed contract and decodes it as `T`.
 ///
 /// # Note
@@ -406,6 +524,18 @@ where
     })
 }
 
+/// Calls `f` with the undecoded input of the executed contract.
+///
+/// Unlike [`decode_input`] this allows borrowed values like `&[u8]` or `&str` to be
+/// decoded from the input within `f` instead of copying them. The input can be read
+/// any number of times per execution, also mixed with [`decode_input`].
+pub fn with_input_bytes<F, R>(f: F) -> R
+where
+    F: FnOnce(&[u8]) -> R,
+{
+    <EnvInstance as EnvBackend>::with_input_bytes(f)
+}
+
 /// Returns the value back to the caller of the executed contract.
 ///
 /// # Note
@@ -548,10 +678,20 @@ where
 
 /// Retrieves the code hash of the contract at the specified account id.
 ///
+/// Factory or registry contracts can compare it with the code hash of an expected
+/// implementation before trusting a given contract.
+///
 /// # Errors
 ///
-/// - If no code hash was found for the specified account id.
+/// - [`Error::KeyNotFound`](crate::Error::KeyNotFound) if there is no contract at the
+///   specified account id, e.g. because it is a plain account or the contract was
+///   terminated.
 /// - If the returned value cannot be properly decoded.
+///
+/// # Note
+///
+/// In the off-chain environment the code hashes are set via
+/// [`test::set_code_hash`](crate::test::set_code_hash).
 pub fn code_hash<E>(account: &E::AccountId) -> Result<E::Hash>
 where
     E: Environment,
@@ -566,6 +706,12 @@ where
 /// # Errors
 ///
 /// If the returned value cannot be properly decoded.
+///
+/// # Note
+///
+/// In the off-chain environment the code hash of the callee is set via
+/// [`test::set_code_hash`](crate::test::set_code_hash), otherwise
+/// [`Error::KeyNotFound`](crate::Error::KeyNotFound) is returned.
 pub fn own_code_hash<E>() -> Result<E::Hash>
 where
     E: Environment,
@@ -575,6 +721,32 @@ where
     })
 }
 
+/// Dispatches the runtime `call` with the executed contract as origin.
+///
+/// The `call` must encode as the `RuntimeCall` of the runtime, see
+/// [`RuntimeCall`](crate::runtime_call::RuntimeCall).
+///
+/// # Note
+///
+/// This is only available with the `call-runtime` crate feature. The runtime decides
+/// which calls contracts may dispatch, all other calls fail. The off-chain environment
+/// records the calls, see [`test::recorded_runtime_calls`](crate::test::recorded_runtime_calls).
+///
+/// # Errors
+///
+/// - If the runtime does not allow contracts to dispatch the `call`.
+/// - If the dispatched `call` returned an error.
+#[cfg(feature = "call-runtime")]
+pub fn call_runtime<E, Call>(call: &Call) -> Result<()>
+where
+    E: Environment,
+    Call: crate::runtime_call::RuntimeCall,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        TypedEnvBackend::call_runtime::<E, _>(instance, call)
+    })
+}
+
 /// Checks whether the caller of the current contract is the origin of the whole call stack.
 ///
 /// Prefer this over [`is_contract`] when checking whether your contract is being called by
@@ -584,6 +756,11 @@ where
 /// A return value of `true` indicates that this contract is being called by a plain account.
 /// and `false` indicates that the caller is another contract.
 ///
+/// # Note
+///
+/// Unlike `origin`, this is part of the stable interface of `pallet-contracts`, hence
+/// it does not require the `unstable-interface` crate feature.
+///
 /// # Errors
 ///
 /// If the returned value cannot be properly decoded.
@@ -596,6 +773,32 @@ where
     })
 }
 
+/// Returns the account which signed the extrinsic executing the call stack.
+///
+/// Unlike the [`caller`], which is the contract calling the current contract in case
+/// of cross-contract calls, the origin stays the same for all contracts on the call
+/// stack.
+///
+/// # Note
+///
+/// This is only available with the `unstable-interface` crate feature, since it
+/// depends on the `__unstable__` interface of `pallet-contracts`. Contracts using it
+/// can only be instantiated on chains whose runtime enables the
+/// `"pallet-contracts/unstable-interface"` feature.
+///
+/// # Errors
+///
+/// If the returned value cannot be properly decoded.
+#[cfg(feature = "unstable-interface")]
+pub fn origin<E>() -> E::AccountId
+where
+    E: Environment,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        TypedEnvBackend::origin::<E>(instance)
+    })
+}
+
 /// Replace the contract code at the specified address with new code.
 ///
 /// # Note
@@ -614,6 +817,9 @@ where
 /// would revert the changes made by `set_code_hash` and the next caller would use
 /// the old code.
 ///
+/// Use [`upgrade::set_code_hash_with_migration`](crate::upgrade::set_code_hash_with_migration)
+/// in order to migrate the storage on the new code as part of the upgrade.
+///
 /// # Errors
 ///
 /// `ReturnCode::CodeNotFound` in case the supplied `code_hash` cannot be found on-chain.
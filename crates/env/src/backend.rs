@@ -24,8 +24,10 @@ use crate::{
         HashOutput,
     },
     topics::Topics,
+    types::Weight,
     Environment,
     Result,
+    StorageDepositInfo,
 };
 use ink_storage_traits::Storable;
 
@@ -51,6 +53,12 @@ impl ReturnFlags {
         self
     }
 
+    /// Returns `true` if the execution is going to be reverted.
+    #[cfg(feature = "std")]
+    pub(crate) fn is_reverted(&self) -> bool {
+        self.value & 1 != 0
+    }
+
     /// Returns the underlying `u32` representation.
     #[cfg(not(feature = "std"))]
     pub(crate) fn into_u32(self) -> u32 {
@@ -208,6 +216,9 @@ pub trait EnvBackend {
     where
         K: scale::Encode;
 
+    /// Returns the storage added and removed by the current call so far.
+    fn storage_deposit_info(&mut self) -> StorageDepositInfo;
+
     /// Returns the execution input to the executed contract and decodes it as `T`.
     ///
     /// # Note
@@ -235,6 +246,16 @@ pub trait EnvBackend {
     where
         T: scale::Decode;
 
+    /// Calls `f` with the undecoded input of the executed contract.
+    ///
+    /// # Note
+    ///
+    /// The environment is not in use while `f` is executed, so that `f` can access it
+    /// like any other code.
+    fn with_input_bytes<F, R>(f: F) -> R
+    where
+        F: FnOnce(&[u8]) -> R;
+
     /// Returns the value back to the caller of the executed contract.
     ///
     /// # Note
@@ -360,6 +381,13 @@ pub trait TypedEnvBackend: EnvBackend {
     /// For more details visit: [`gas_left`][`crate::gas_left`]
     fn gas_left<E: Environment>(&mut self) -> u64;
 
+    /// Returns the weight left for the contract execution.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`weight_left`][`crate::weight_left`]
+    fn weight_left<E: Environment>(&mut self) -> Weight;
+
     /// Returns the timestamp of the current block.
     ///
     /// # Note
@@ -410,9 +438,11 @@ pub trait TypedEnvBackend: EnvBackend {
     ///
     /// # Note
     ///
+    /// This takes no `self`, since the off-chain environment must not be held while
+    /// the called contract executes, so that the contract can access it.
+    ///
     /// For more details visit: [`invoke_contract`][`crate::invoke_contract`]
     fn invoke_contract<E, Args, R>(
-        &mut self,
         call_data: &CallParams<E, Call<E>, Args, R>,
     ) -> Result<R>
     where
@@ -448,6 +478,21 @@ pub trait TypedEnvBackend: EnvBackend {
         Args: scale::Encode,
         Salt: AsRef<[u8]>;
 
+    /// Instantiates another contract with a fallible constructor.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`try_instantiate_contract`][`crate::try_instantiate_contract`]
+    fn try_instantiate_contract<E, Args, Salt, C, ContractError>(
+        &mut self,
+        params: &CreateParams<E, Args, Salt, C>,
+    ) -> Result<core::result::Result<E::AccountId, ContractError>>
+    where
+        E: Environment,
+        Args: scale::Encode,
+        Salt: AsRef<[u8]>,
+        ContractError: scale::Decode;
+
     /// Terminates a smart contract.
     ///
     /// # Note
@@ -485,6 +530,16 @@ pub trait TypedEnvBackend: EnvBackend {
     where
         E: Environment;
 
+    /// Returns the account which signed the extrinsic executing the call stack.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`origin`][`crate::origin`]
+    #[cfg(feature = "unstable-interface")]
+    fn origin<E>(&mut self) -> E::AccountId
+    where
+        E: Environment;
+
     /// Retrieves the code hash of the contract at the given `account` id.
     ///
     /// # Note
@@ -502,4 +557,15 @@ pub trait TypedEnvBackend: EnvBackend {
     fn own_code_hash<E>(&mut self) -> Result<E::Hash>
     where
         E: Environment;
+
+    /// Dispatches the runtime `call` with the contract as origin.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`call_runtime`][`crate::call_runtime`]
+    #[cfg(feature = "call-runtime")]
+    fn call_runtime<E, Call>(&mut self, call: &Call) -> Result<()>
+    where
+        E: Environment,
+        Call: scale::Encode;
 }
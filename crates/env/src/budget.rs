@@ -0,0 +1,124 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Processing of unbounded work in batches spread across several calls.
+//!
+//! Iterating over all entries of a growing collection in one message eventually
+//! exceeds the weight limit of a block, which makes the message impossible to call.
+//! With [`for_each_bounded`] the message processes only as many items as the weight
+//! left for the execution allows, stores the returned [`Progress::cursor`] and
+//! resumes from it in the next call.
+
+use crate::{
+    Environment,
+    Weight,
+};
+
+/// The progress of a [`for_each_bounded`] loop.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Progress {
+    processed: u32,
+    completed: bool,
+}
+
+impl Progress {
+    /// Returns the number of processed items.
+    pub fn processed(&self) -> u32 {
+        self.processed
+    }
+
+    /// Returns `true` if all items have been processed.
+    pub fn is_completed(&self) -> bool {
+        self.completed
+    }
+
+    /// Returns the position of the first unprocessed item, from which the loop is
+    /// resumed by the next call, given the position `start` the loop began at.
+    ///
+    /// The position is absolute, i.e. it counts the items skipped by previous calls
+    /// as well, so that it can be stored as is.
+    ///
+    /// Returns `None` if all items have been processed.
+    pub fn cursor(&self, start: u32) -> Option<u32> {
+        (!self.completed).then_some(start.saturating_add(self.processed))
+    }
+}
+
+/// Calls `f` with the items of `iter` for as long as the weight left for the
+/// contract execution allows.
+///
+/// Before every item the loop checks that at least `per_item_weight` is left,
+/// both for the computation time and the storage proof size, and stops otherwise.
+/// The next call resumes the loop by skipping the items up to the returned
+/// [`Progress::cursor`].
+///
+/// # Note
+///
+/// The weight needed after the loop, e.g. for writing back the contract storage,
+/// is not reserved. Add it to `per_item_weight` to make sure the message can
+/// always finish.
+///
+/// # Example
+///
+/// ```
+/// use ink_env::{
+///     budget::for_each_bounded,
+///     DefaultEnvironment,
+///     Weight,
+/// };
+///
+/// # ink_env::test::run_test::<DefaultEnvironment, _>(|_| {
+/// let accounts = [1u32, 2, 3, 4, 5];
+/// // The cursor stored by the previous call.
+/// let cursor = 2;
+/// let mut total = 0;
+/// let progress = for_each_bounded::<DefaultEnvironment, _, _>(
+///     accounts.iter().skip(cursor as usize),
+///     Weight {
+///         ref_time: 1_000_000,
+///         proof_size: 1_024,
+///     },
+///     |account| total += account,
+/// );
+/// assert_eq!(progress.cursor(cursor), None);
+/// assert_eq!(total, 12);
+/// # Ok(())
+/// # }).unwrap();
+/// ```
+pub fn for_each_bounded<E, I, F>(iter: I, per_item_weight: Weight, mut f: F) -> Progress
+where
+    E: Environment,
+    I: IntoIterator,
+    F: FnMut(I::Item),
+{
+    let mut processed = 0;
+    for item in iter {
+        let weight_left = crate::weight_left::<E>();
+        if weight_left.ref_time < per_item_weight.ref_time
+            || weight_left.proof_size < per_item_weight.proof_size
+        {
+            return Progress {
+                processed,
+                completed: false,
+            }
+        }
+        f(item);
+        processed += 1;
+    }
+    Progress {
+        processed,
+        completed: true,
+    }
+}
@@ -28,6 +28,7 @@ use crate::{
     Error,
 };
 use core::marker::PhantomData;
+use ink_prelude::vec::Vec;
 use ink_primitives::Clear;
 use num_traits::Zero;
 
@@ -112,6 +113,38 @@ where
     pub fn invoke(&self) -> Result<R, crate::Error> {
         crate::invoke_contract(self)
     }
+
+    /// Invokes the contract and applies the `fallback` if the callee traps.
+    ///
+    /// The alternative callees of the `fallback` are called with the same
+    /// parameters in order until one of them does not trap. If all of them trap
+    /// the fallback value is returned, or [`Error::CalleeTrapped`] if it is unset.
+    /// Any other error is returned as is.
+    ///
+    /// # Note
+    ///
+    /// This is meant for read-only messages, e.g. reading several price feeds, since
+    /// state changes of the callees which did not trap are kept.
+    ///
+    /// A trapped callee consumes all of its gas. Set a gas limit via
+    /// [`Call::gas_limit`], so that there is gas left for the fallback.
+    pub fn try_invoke_with_fallback(
+        mut self,
+        fallback: Fallback<E, R>,
+    ) -> Result<R, crate::Error> {
+        let mut callees = fallback.callees.into_iter();
+        loop {
+            match self.invoke() {
+                Err(Error::CalleeTrapped) => {
+                    match callees.next() {
+                        Some(callee) => self.call_type.callee = callee,
+                        None => return fallback.value.ok_or(Error::CalleeTrapped),
+                    }
+                }
+                result => return result,
+            }
+        }
+    }
 }
 
 impl<E, Args, R> CallParams<E, DelegateCall<E>, Args, R>
@@ -133,9 +166,10 @@ where
 ///
 /// # Example
 ///
-/// **Note:** The shown examples panic because there is currently no cross-calling
-///           support in the off-chain testing environment. However, this code
-///           should work fine in on-chain environments.
+/// **Note:** The shown examples panic because the called accounts are not registered
+///           as contracts in the off-chain testing environment, see
+///           [`test::register_contract`](crate::test::register_contract). However,
+///           this code should work fine in on-chain environments.
 ///
 /// ## Example 1: No Return Value
 ///
@@ -258,6 +292,49 @@ where
     }
 }
 
+/// A [`CallBuilder`] for delegate calls, see [`build_delegate_call`].
+pub type DelegateCallBuilder<E, Args, RetType> =
+    CallBuilder<E, Set<DelegateCall<E>>, Args, RetType>;
+
+/// Returns a new [`DelegateCallBuilder`] that delegate calls into the code at `code_hash`.
+///
+/// This is a shorthand for
+/// `build_call::<E>().call_type(DelegateCall::new().code_hash(code_hash))`.
+///
+/// # Example
+///
+/// The below example forwards the input of the current call to the code at
+/// `code_hash`, as e.g. done by proxy contracts in their fallback message.
+///
+/// ```should_panic
+/// # use ::ink_env::{
+/// #     Environment,
+/// #     DefaultEnvironment,
+/// #     CallFlags,
+/// #     call::build_delegate_call,
+/// # };
+/// # use ink_primitives::Clear;
+/// # type Hash = <DefaultEnvironment as Environment>::Hash;
+/// build_delegate_call::<DefaultEnvironment>(Hash::clear())
+///     .call_flags(CallFlags::default().set_forward_input(true).set_tail_call(true))
+///     .fire()
+///     .unwrap();
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn build_delegate_call<E>(
+    code_hash: E::Hash,
+) -> DelegateCallBuilder<E, Unset<ExecutionInput<EmptyArgumentList>>, Unset<ReturnType<()>>>
+where
+    E: Environment,
+{
+    build_call::<E>().call_type(DelegateCall::new().code_hash(code_hash))
+}
+
+/// Returns the gas left in the current execution minus `reserve_weight`, but at least `1`.
+fn remaining_gas_minus<E: Environment>(reserve_weight: Gas) -> Gas {
+    crate::gas_left::<E>().saturating_sub(reserve_weight).max(1)
+}
+
 /// The default call type for cross-contract calls. Performs a cross-contract call to `callee`
 /// with gas limit `gas_limit`, transferring `transferred_value` of currency.
 pub struct Call<E: Environment> {
@@ -305,6 +382,21 @@ where
         }
     }
 
+    /// Sets the `gas_limit` for the current cross-contract call to all of the gas
+    /// that is left in the current execution except for `reserve_weight`.
+    ///
+    /// # Note
+    ///
+    /// The remaining gas is queried when this method is called. Use this instead of
+    /// hard-coded gas limits to forward as much gas as possible to the callee while
+    /// keeping `reserve_weight` to process the result of the call.
+    /// If no more than `reserve_weight` gas is left the gas limit is set to `1` instead
+    /// of zero, since a zero gas limit means "no limit" to the contracts pallet. The call
+    /// then fails with an out of gas error instead of consuming the reserve.
+    pub fn gas_limit_remaining_minus(self, reserve_weight: Gas) -> Self {
+        self.gas_limit(remaining_gas_minus::<E>(reserve_weight))
+    }
+
     /// Sets the `transferred_value` for the current cross-contract call.
     pub fn transferred_value(self, transferred_value: E::Balance) -> Self {
         Call {
@@ -315,6 +407,58 @@ where
     }
 }
 
+/// The fallback of a cross-contract call whose callee traps.
+///
+/// See [`CallBuilder::try_invoke_with_fallback`] for more details.
+///
+/// # Example
+///
+/// ```
+/// # use ink_env::{
+/// #     DefaultEnvironment,
+/// #     call::Fallback,
+/// # };
+/// # type AccountId = <DefaultEnvironment as ink_env::Environment>::AccountId;
+/// let fallback = Fallback::<DefaultEnvironment, u128>::new()
+///     .callee(AccountId::from([0x02; 32]))
+///     .callee(AccountId::from([0x03; 32]))
+///     .value(0);
+/// ```
+pub struct Fallback<E: Environment, R> {
+    callees: Vec<E::AccountId>,
+    value: Option<R>,
+}
+
+impl<E: Environment, R> Default for Fallback<E, R> {
+    fn default() -> Self {
+        Fallback {
+            callees: Vec::new(),
+            value: None,
+        }
+    }
+}
+
+impl<E: Environment, R> Fallback<E, R> {
+    /// Returns a clean builder for [`Fallback`].
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Appends an alternative `callee` which is called if the previous callees trap.
+    pub fn callee(mut self, callee: E::AccountId) -> Self {
+        self.callees.push(callee);
+        self
+    }
+
+    /// Sets the `value` which is returned if all callees trap.
+    pub fn value(self, value: R) -> Self {
+        Fallback {
+            callees: self.callees,
+            value: Some(value),
+        }
+    }
+}
+
 /// The `delegatecall` call type. Performs a call with the given code hash.
 pub struct DelegateCall<E: Environment> {
     code_hash: E::Hash,
@@ -475,6 +619,14 @@ where
         }
     }
 
+    /// Sets the `gas_limit` for the current cross-contract call to all of the gas
+    /// that is left in the current execution except for `reserve_weight`.
+    ///
+    /// See [`Call::gas_limit_remaining_minus`] for details.
+    pub fn gas_limit_remaining_minus(self, reserve_weight: Gas) -> Self {
+        self.gas_limit(remaining_gas_minus::<E>(reserve_weight))
+    }
+
     /// Sets the `transferred_value` for the current cross-contract call.
     pub fn transferred_value(self, transferred_value: E::Balance) -> Self {
         let call_type = self.call_type.value();
@@ -629,6 +781,43 @@ where
     pub fn fire(self) -> Result<R, Error> {
         self.params().invoke()
     }
+
+    /// Invokes the cross-chain function call and applies the `fallback` if the
+    /// callee traps.
+    ///
+    /// # Example
+    ///
+    /// Reads a price from the first of several feeds which does not trap, or `0`
+    /// if all of them trap:
+    ///
+    /// ```should_panic
+    /// # use ink_env::{
+    /// #     DefaultEnvironment,
+    /// #     call::{build_call, Call, ExecutionInput, Fallback, Selector},
+    /// # };
+    /// # type AccountId = <DefaultEnvironment as ink_env::Environment>::AccountId;
+    /// let price = build_call::<DefaultEnvironment>()
+    ///     .call_type(
+    ///         Call::new()
+    ///             .callee(AccountId::from([0x01; 32]))
+    ///             .gas_limit(100_000_000),
+    ///     )
+    ///     .exec_input(ExecutionInput::new(Selector::new([0xCA, 0xFE, 0xBA, 0xBE])))
+    ///     .returns::<u128>()
+    ///     .try_invoke_with_fallback(
+    ///         Fallback::new()
+    ///             .callee(AccountId::from([0x02; 32]))
+    ///             .value(0),
+    ///     )
+    ///     .unwrap();
+    /// ```
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`CallParams::try_invoke_with_fallback`]
+    pub fn try_invoke_with_fallback(self, fallback: Fallback<E, R>) -> Result<R, Error> {
+        self.params().try_invoke_with_fallback(fallback)
+    }
 }
 
 impl<E, Args, R>
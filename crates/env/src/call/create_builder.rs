@@ -34,6 +34,14 @@ pub mod state {
 
     /// Type state for the salt used for contract instantiation.
     pub enum Salt {}
+
+    /// Type state for the not yet set endowment of an instantiation of a payable
+    /// constructor.
+    ///
+    /// Only builders in this state provide
+    /// [`transferred_value`](super::CreateBuilder::transferred_value).
+    #[derive(Debug, Default, Copy, Clone)]
+    pub struct Payable;
 }
 
 /// Contracts that can be constructed from an `AccountId`.
@@ -60,6 +68,8 @@ where
     code_hash: E::Hash,
     /// The maximum gas costs allowed for the instantiation.
     gas_limit: u64,
+    /// The maximum storage deposit allowed to be charged for the instantiation.
+    storage_deposit_limit: Option<E::Balance>,
     /// The endowment for the instantiated contract.
     endowment: E::Balance,
     /// The input data for the instantiation.
@@ -86,6 +96,12 @@ where
         self.gas_limit
     }
 
+    /// The storage deposit limit for the contract instantiation, if any.
+    #[inline]
+    pub(crate) fn storage_deposit_limit(&self) -> Option<&E::Balance> {
+        self.storage_deposit_limit.as_ref()
+    }
+
     /// The endowment for the instantiated contract.
     #[inline]
     pub(crate) fn endowment(&self) -> &E::Balance {
@@ -123,6 +139,21 @@ where
     pub fn instantiate(&self) -> Result<R, crate::Error> {
         crate::instantiate_contract(self).map(FromAccountId::from_account_id)
     }
+
+    /// Instantiates the contract using a fallible constructor.
+    ///
+    /// Returns the error of a constructor of type `Result<Self, ContractError>`
+    /// back to the caller instead of the generic [`Error::CalleeReverted`].
+    #[inline]
+    pub fn try_instantiate<ContractError>(
+        &self,
+    ) -> Result<core::result::Result<R, ContractError>, crate::Error>
+    where
+        ContractError: scale::Decode,
+    {
+        crate::try_instantiate_contract(self)
+            .map(|result| result.map(FromAccountId::from_account_id))
+    }
 }
 
 /// Builds up contract instantiations.
@@ -132,6 +163,7 @@ where
 {
     code_hash: CodeHash,
     gas_limit: GasLimit,
+    storage_deposit_limit: Option<E::Balance>,
     endowment: Endowment,
     exec_input: Args,
     salt: Salt,
@@ -149,6 +181,7 @@ where
 ///
 /// - has a selector equal to `0xDEADBEEF`
 /// - is provided with 4000 units of gas for its execution
+/// - may be charged at most 1000 units of storage deposit
 /// - is provided with 25 units of transferred value for the new contract instance
 /// - receives the following arguments in order
 ///    1. an `i32` with value `42`
@@ -171,6 +204,7 @@ where
 /// let my_contract: MyContract = build_create::<DefaultEnvironment, MyContract>()
 ///     .code_hash(Hash::from([0x42; 32]))
 ///     .gas_limit(4000)
+///     .storage_deposit_limit(1000)
 ///     .endowment(25)
 ///     .exec_input(
 ///         ExecutionInput::new(Selector::new([0xDE, 0xAD, 0xBE, 0xEF]))
@@ -204,6 +238,7 @@ where
     CreateBuilder {
         code_hash: Default::default(),
         gas_limit: Default::default(),
+        storage_deposit_limit: None,
         endowment: Default::default(),
         exec_input: Default::default(),
         salt: Default::default(),
@@ -212,6 +247,32 @@ where
     }
 }
 
+impl<E, CodeHash, GasLimit, Endowment, Args, Salt, R>
+    CreateBuilder<E, CodeHash, GasLimit, Endowment, Args, Salt, R>
+where
+    E: Environment,
+{
+    /// Sets the maximum storage deposit charged for the contract instantiation.
+    ///
+    /// The instantiation fails if storing the new contract requires a larger
+    /// deposit. Without a limit, any deposit the origin of the transaction can
+    /// pay is charged.
+    ///
+    /// # Note
+    ///
+    /// The deposit is charged from the origin of the transaction by the contracts
+    /// pallet, which does not report the charged amount back to the contract. The
+    /// limit is therefore the way for factory contracts to enforce a budget for
+    /// the contracts they instantiate.
+    #[inline]
+    pub fn storage_deposit_limit(self, storage_deposit_limit: E::Balance) -> Self {
+        Self {
+            storage_deposit_limit: Some(storage_deposit_limit),
+            ..self
+        }
+    }
+}
+
 impl<E, GasLimit, Endowment, Args, Salt, R>
     CreateBuilder<E, Unset<E::Hash>, GasLimit, Endowment, Args, Salt, R>
 where
@@ -226,6 +287,7 @@ where
         CreateBuilder {
             code_hash: Set(code_hash),
             gas_limit: self.gas_limit,
+            storage_deposit_limit: self.storage_deposit_limit,
             endowment: self.endowment,
             exec_input: self.exec_input,
             salt: self.salt,
@@ -249,6 +311,7 @@ where
         CreateBuilder {
             code_hash: self.code_hash,
             gas_limit: Set(gas_limit),
+            storage_deposit_limit: self.storage_deposit_limit,
             endowment: self.endowment,
             exec_input: self.exec_input,
             salt: self.salt,
@@ -272,6 +335,7 @@ where
         CreateBuilder {
             code_hash: self.code_hash,
             gas_limit: self.gas_limit,
+            storage_deposit_limit: self.storage_deposit_limit,
             endowment: Set(endowment),
             exec_input: self.exec_input,
             salt: self.salt,
@@ -279,6 +343,63 @@ where
             _phantom: Default::default(),
         }
     }
+
+    /// Marks the instantiated constructor as payable.
+    ///
+    /// This enables setting the value transferred to the constructor via
+    /// [`CreateBuilder::transferred_value`]. The contract references generated
+    /// by ink! call this for all constructors annotated with `#[ink(payable)]`, so
+    /// that transferring value to a non-payable constructor fails to compile.
+    #[inline]
+    pub fn payable(
+        self,
+    ) -> CreateBuilder<E, CodeHash, GasLimit, state::Payable, Args, Salt, R> {
+        CreateBuilder {
+            code_hash: self.code_hash,
+            gas_limit: self.gas_limit,
+            storage_deposit_limit: self.storage_deposit_limit,
+            endowment: Default::default(),
+            exec_input: self.exec_input,
+            salt: self.salt,
+            return_type: self.return_type,
+            _phantom: Default::default(),
+        }
+    }
+}
+
+impl<E, CodeHash, GasLimit, Args, Salt, R>
+    CreateBuilder<E, CodeHash, GasLimit, state::Payable, Args, Salt, R>
+where
+    E: Environment,
+{
+    /// Sets the value transferred to the payable constructor.
+    #[inline]
+    pub fn transferred_value(
+        self,
+        transferred_value: E::Balance,
+    ) -> CreateBuilder<E, CodeHash, GasLimit, Set<E::Balance>, Args, Salt, R> {
+        CreateBuilder {
+            code_hash: self.code_hash,
+            gas_limit: self.gas_limit,
+            storage_deposit_limit: self.storage_deposit_limit,
+            endowment: Set(transferred_value),
+            exec_input: self.exec_input,
+            salt: self.salt,
+            return_type: self.return_type,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Sets the value transferred upon the execution of the call.
+    ///
+    /// Same as [`CreateBuilder::transferred_value`] for payable constructors.
+    #[inline]
+    pub fn endowment(
+        self,
+        endowment: E::Balance,
+    ) -> CreateBuilder<E, CodeHash, GasLimit, Set<E::Balance>, Args, Salt, R> {
+        self.transferred_value(endowment)
+    }
 }
 
 impl<E, CodeHash, GasLimit, Endowment, Salt, R>
@@ -304,6 +425,7 @@ where
         CreateBuilder {
             code_hash: self.code_hash,
             gas_limit: self.gas_limit,
+            storage_deposit_limit: self.storage_deposit_limit,
             endowment: self.endowment,
             exec_input: Set(exec_input),
             salt: self.salt,
@@ -330,6 +452,7 @@ where
         CreateBuilder {
             code_hash: self.code_hash,
             gas_limit: self.gas_limit,
+            storage_deposit_limit: self.storage_deposit_limit,
             endowment: self.endowment,
             exec_input: self.exec_input,
             salt: Set(salt),
@@ -359,6 +482,7 @@ where
         CreateParams {
             code_hash: self.code_hash.value(),
             gas_limit: self.gas_limit.unwrap_or_else(|| 0),
+            storage_deposit_limit: self.storage_deposit_limit,
             endowment: self.endowment.value(),
             exec_input: self.exec_input.value(),
             salt_bytes: self.salt.value(),
@@ -389,4 +513,19 @@ where
     pub fn instantiate(self) -> Result<R, Error> {
         self.params().instantiate()
     }
+
+    /// Instantiates the contract using the given instantiation parameters
+    /// and a fallible constructor.
+    ///
+    /// Returns the error of a constructor of type `Result<Self, ContractError>`
+    /// back to the caller instead of the generic [`Error::CalleeReverted`].
+    #[inline]
+    pub fn try_instantiate<ContractError>(
+        self,
+    ) -> Result<core::result::Result<R, ContractError>, Error>
+    where
+        ContractError: scale::Decode,
+    {
+        self.params().try_instantiate()
+    }
 }
@@ -42,10 +42,13 @@ pub mod utils {
 pub use self::{
     call_builder::{
         build_call,
+        build_delegate_call,
         Call,
         CallBuilder,
         CallParams,
         DelegateCall,
+        DelegateCallBuilder,
+        Fallback,
     },
     create_builder::{
         build_create,
@@ -0,0 +1,105 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Consensus information of the current block provided by the runtime.
+//!
+//! These are [`RuntimeFunction`]s, which return [`NotSupported`] unless the runtime
+//! of the environment provides them.
+
+use crate::{
+    runtime_function::{
+        self,
+        NotSupported,
+        RuntimeFunction,
+    },
+    Environment,
+};
+
+/// Returns the account ID of the author of the current block.
+///
+/// # Errors
+///
+/// If the runtime does not provide [`RuntimeFunction::BlockAuthor`].
+pub fn block_author<E>() -> Result<E::AccountId, NotSupported>
+where
+    E: Environment,
+{
+    runtime_function::call::<E, _, _, _>(RuntimeFunction::BlockAuthor, &())
+}
+
+/// Returns the hash of the parent of the current block.
+///
+/// # Errors
+///
+/// If the runtime does not provide [`RuntimeFunction::ParentHash`].
+pub fn parent_hash<E>() -> Result<E::Hash, NotSupported>
+where
+    E: Environment,
+{
+    runtime_function::call::<E, _, _, _>(RuntimeFunction::ParentHash, &())
+}
+
+/// Returns the identifier of the chain.
+///
+/// # Errors
+///
+/// If the runtime does not provide [`RuntimeFunction::ChainId`].
+pub fn chain_id<E>() -> Result<u64, NotSupported>
+where
+    E: Environment,
+{
+    runtime_function::call::<E, _, _, _>(RuntimeFunction::ChainId, &())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        runtime_function::TestEnvironment,
+        test,
+        DefaultEnvironment,
+    };
+
+    #[test]
+    fn consensus_functions_work() {
+        test::run_test::<DefaultEnvironment, _>(|accounts| {
+            TestEnvironment::register_fn(RuntimeFunction::BlockAuthor, move |_| {
+                scale::Encode::encode(&accounts.bob)
+            });
+            TestEnvironment::register_fn(RuntimeFunction::ParentHash, |_| {
+                scale::Encode::encode(&[0x01u8; 32])
+            });
+            TestEnvironment::register_fn(RuntimeFunction::ChainId, |_| {
+                scale::Encode::encode(&42u64)
+            });
+
+            assert_eq!(block_author::<TestEnvironment>(), Ok(accounts.bob));
+            assert_eq!(parent_hash::<TestEnvironment>(), Ok([0x01; 32].into()));
+            assert_eq!(chain_id::<TestEnvironment>(), Ok(42));
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn unsupported_consensus_functions_fail() {
+        test::run_test::<DefaultEnvironment, _>(|_| {
+            assert_eq!(block_author::<DefaultEnvironment>(), Err(NotSupported));
+            assert_eq!(parent_hash::<DefaultEnvironment>(), Err(NotSupported));
+            assert_eq!(chain_id::<DefaultEnvironment>(), Err(NotSupported));
+            Ok(())
+        })
+        .unwrap()
+    }
+}
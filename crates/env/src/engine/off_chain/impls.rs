@@ -20,6 +20,7 @@ use crate::{
         CreateParams,
         DelegateCall,
     },
+    engine::OnInstance,
     hash::{
         Blake2x128,
         Blake2x256,
@@ -38,13 +39,55 @@ use crate::{
     Error,
     Result,
     ReturnFlags,
+    StorageDepositInfo,
     TypedEnvBackend,
+    Weight,
 };
 use ink_engine::{
     ext,
     ext::Engine,
+    Database,
 };
 use ink_storage_traits::Storable;
+use std::collections::HashMap;
+
+/// The value returned via [`EnvBackend::return_value`].
+///
+/// It is passed to the code calling the contract via the environment, see
+/// [`Returned`].
+pub(super) struct ReturnValue {
+    /// `true` if the execution reverted.
+    pub(super) reverted: bool,
+    /// The SCALE encoded return value.
+    pub(super) data: Vec<u8>,
+}
+
+/// The payload with which [`EnvBackend::return_value`] unwinds to the code calling
+/// the contract, e.g. [`TypedEnvBackend::invoke_contract`].
+struct Returned;
+
+/// The state of the caller of a cross-contract call, which is restored once the
+/// called contract ended.
+struct CallFrame {
+    /// The entry point of the called contract.
+    entry: fn(&[u8]),
+    /// The account ID of the called contract.
+    callee: Vec<u8>,
+    /// The caller of the calling contract, if any.
+    caller: Option<Vec<u8>>,
+    /// The account ID of the calling contract.
+    previous_callee: Vec<u8>,
+    /// The value transferred to the calling contract.
+    value_transferred: u128,
+    /// The input of the calling contract.
+    input: Vec<u8>,
+    /// The storage added and removed by the calling contract so far.
+    storage_deposit: StorageDepositInfo,
+    /// The database before the call, restored if the called contract reverts.
+    database: Database,
+    /// The storage deposits before the call, restored if the called contract reverts.
+    storage_deposits: HashMap<Vec<u8>, u128>,
+}
 
 /// The capacity of the static buffer.
 /// This is the same size as the ink! on-chain environment. We chose to use the same size
@@ -112,6 +155,7 @@ impl From<ext::Error> for crate::Error {
             ext::Error::CodeNotFound => Self::CodeNotFound,
             ext::Error::NotCallable => Self::NotCallable,
             ext::Error::LoggingDisabled => Self::LoggingDisabled,
+            ext::Error::CallRuntimeFailed => Self::CallRuntimeFailed,
             ext::Error::EcdsaRecoveryFailed => Self::EcdsaRecoveryFailed,
         }
     }
@@ -168,6 +212,40 @@ where
 }
 
 impl EnvInstance {
+    /// Records that a value of `new_len` bytes replaced the value of `old_len` bytes
+    /// in the storage of the executed contract, where `None` denotes a missing value.
+    ///
+    /// If storage deposits are accounted, the deposit of the change is charged from
+    /// or refunded to the origin of the call stack.
+    fn record_storage(&mut self, old_len: Option<u32>, new_len: Option<u32>) {
+        self.storage_deposit.record(old_len, new_len);
+        let (per_byte, per_item) = match self.storage_deposit_prices {
+            Some(prices) => prices,
+            None => return,
+        };
+        let mut change = StorageDepositInfo::new();
+        change.record(old_len, new_len);
+        let origin = match self.call_depth {
+            0 => {
+                self.engine
+                    .exec_context
+                    .caller
+                    .as_ref()
+                    .map(|caller| caller.as_bytes().to_vec())
+            }
+            _ => self.origin.clone(),
+        };
+        let deposit = self
+            .storage_deposits
+            .entry(origin.expect("no origin has been set"))
+            .or_default();
+        // The deposit does not drop below zero if the origin is refunded the
+        // deposit paid by other accounts.
+        *deposit = deposit
+            .saturating_add(change.charge(per_byte, per_item))
+            .saturating_sub(change.refund(per_byte, per_item));
+    }
+
     /// Returns the contract property value.
     fn get_property<T>(
         &mut self,
@@ -191,7 +269,9 @@ impl EnvBackend for EnvInstance {
     {
         let mut v = vec![];
         Storable::encode(value, &mut v);
-        self.engine.set_storage(&key.encode(), &v[..])
+        let old_len = self.engine.set_storage(&key.encode(), &v[..]);
+        self.record_storage(old_len, Some(v.len() as u32));
+        old_len
     }
 
     fn get_contract_storage<K, R>(&mut self, key: &K) -> Result<Option<R>>
@@ -200,7 +280,8 @@ impl EnvBackend for EnvInstance {
         R: Storable,
     {
         let mut output: [u8; 9600] = [0; 9600];
-        match self.engine.get_storage(&key.encode(), &mut &mut output[..]) {
+        let output = &mut &mut output[..];
+        match self.engine.get_storage(&key.encode(), output) {
             Ok(_) => (),
             Err(ext::Error::KeyNotFound) => return Ok(None),
             Err(_) => panic!("encountered unexpected error"),
@@ -215,14 +296,13 @@ impl EnvBackend for EnvInstance {
         R: Storable,
     {
         let mut output: [u8; 9600] = [0; 9600];
-        match self
-            .engine
-            .take_storage(&key.encode(), &mut &mut output[..])
-        {
+        let output = &mut &mut output[..];
+        match self.engine.take_storage(&key.encode(), output) {
             Ok(_) => (),
             Err(ext::Error::KeyNotFound) => return Ok(None),
             Err(_) => panic!("encountered unexpected error"),
         }
+        self.record_storage(Some(output.len() as u32), None);
         let decoded = Storable::decode(&mut &output[..])?;
         Ok(Some(decoded))
     }
@@ -238,21 +318,56 @@ impl EnvBackend for EnvInstance {
     where
         K: scale::Encode,
     {
-        self.engine.clear_storage(&key.encode())
+        let old_len = self.engine.clear_storage(&key.encode());
+        self.record_storage(old_len, None);
+        old_len
+    }
+
+    fn storage_deposit_info(&mut self) -> StorageDepositInfo {
+        self.storage_deposit
     }
 
     fn decode_input<T>(&mut self) -> Result<T>
     where
         T: scale::Decode,
     {
-        unimplemented!("the off-chain env does not implement `input`")
+        scale::Decode::decode(&mut &self.input[..]).map_err(Into::into)
     }
 
-    fn return_value<R>(&mut self, _flags: ReturnFlags, _return_value: &R) -> !
+    fn with_input_bytes<F, R>(f: F) -> R
+    where
+        F: FnOnce(&[u8]) -> R,
+    {
+        let input = Self::on_instance(|instance| instance.input.clone());
+        f(&input)
+    }
+
+    fn return_value<R>(&mut self, flags: ReturnFlags, return_value: &R) -> !
     where
         R: scale::Encode,
     {
-        unimplemented!("the off-chain env does not implement `return_value`")
+        let data = return_value.encode();
+        if self.call_depth == 0 {
+            // Returning outside of a contract call ends the unit test, unless a revert
+            // is caught by `test::assert_reverts_with`, which takes the returned value.
+            let reverted = flags.is_reverted();
+            self.returned = Some(ReturnValue {
+                reverted,
+                data: data.clone(),
+            });
+            panic!(
+                "the ink! message {} with the SCALE encoded output {:?}",
+                if reverted { "reverted" } else { "returned" },
+                data
+            )
+        }
+        self.returned = Some(ReturnValue {
+            reverted: flags.is_reverted(),
+            data,
+        });
+        // Unlike a panic, this does not invoke the panic hook, so that returning
+        // prints no panic message.
+        std::panic::resume_unwind(Box::new(Returned))
     }
 
     fn debug_message(&mut self, message: &str) {
@@ -364,8 +479,120 @@ impl EnvBackend for EnvInstance {
         Ok(decoded)
     }
 
-    fn set_code_hash(&mut self, _code_hash: &[u8]) -> Result<()> {
-        unimplemented!("off-chain environment does not support `set_code_hash`")
+    fn set_code_hash(&mut self, code_hash: &[u8]) -> Result<()> {
+        // The entry point of the contract is not replaced, only its code hash.
+        let callee = self.engine.get_callee();
+        self.code_hashes.insert(callee, code_hash.to_vec());
+        Ok(())
+    }
+}
+
+impl EnvInstance {
+    /// Prepares the call of the registered contract `callee` with the `input`
+    /// transferring the encoded `transferred_value` to it.
+    ///
+    /// Returns the state of the caller to restore via [`EnvInstance::leave_call`].
+    fn enter_call(
+        &mut self,
+        callee: Vec<u8>,
+        input: Vec<u8>,
+        transferred_value: &[u8],
+    ) -> Result<CallFrame> {
+        let entry = *self.contracts.get(&callee).ok_or(Error::NotCallable)?;
+        let value = <u128 as scale::Decode>::decode(&mut &transferred_value[..])?;
+        let database = self.engine.database.clone();
+        let storage_deposits = self.storage_deposits.clone();
+        self.engine.transfer(&callee, transferred_value)?;
+        let caller = self
+            .engine
+            .exec_context
+            .caller
+            .take()
+            .map(|caller| caller.as_bytes().to_vec());
+        if self.call_depth == 0 {
+            self.origin = caller.clone();
+        }
+        let previous_callee = self.engine.get_callee();
+        let value_transferred = self.engine.exec_context.value_transferred;
+        self.engine.set_caller(previous_callee.clone());
+        self.engine.set_callee(callee.clone());
+        self.engine.set_value_transferred(value);
+        self.call_depth += 1;
+        Ok(CallFrame {
+            entry,
+            callee,
+            caller,
+            previous_callee,
+            value_transferred,
+            input: core::mem::replace(&mut self.input, input),
+            storage_deposit: core::mem::take(&mut self.storage_deposit),
+            database,
+            storage_deposits,
+        })
+    }
+
+    /// Restores the state of the caller saved in the `frame` once the called contract
+    /// ended with the `outcome`.
+    ///
+    /// Returns the output of the called contract. Reverts the storage and balances if
+    /// the called contract reverted or trapped.
+    fn leave_call(
+        &mut self,
+        frame: CallFrame,
+        outcome: std::thread::Result<()>,
+    ) -> Result<Vec<u8>> {
+        self.call_depth -= 1;
+        self.storage_deposit = frame.storage_deposit;
+        self.engine.exec_context.caller = None;
+        if let Some(caller) = frame.caller {
+            self.engine.set_caller(caller);
+        }
+        self.engine.set_callee(frame.previous_callee);
+        self.engine.set_value_transferred(frame.value_transferred);
+        self.input = frame.input;
+        let returned = self.returned.take();
+        match outcome {
+            // The contract returned without calling `return_value`.
+            Ok(()) => Ok(Vec::new()),
+            Err(payload) if payload.is::<Returned>() => {
+                let returned =
+                    returned.expect("the returned value is passed via the environment");
+                if returned.reverted {
+                    self.engine.database = frame.database;
+                    self.storage_deposits = frame.storage_deposits;
+                }
+                Ok(returned.data)
+            }
+            // The contract terminated itself, see `Engine::terminate`.
+            Err(payload) if payload.is::<Vec<u8>>() => {
+                self.contracts.remove(&frame.callee);
+                self.code_hashes.remove(&frame.callee);
+                Ok(Vec::new())
+            }
+            Err(payload) if payload.is::<ink_engine::ext::OutOfGas>() => {
+                std::panic::resume_unwind(payload)
+            }
+            Err(_) => {
+                self.engine.database = frame.database;
+                self.storage_deposits = frame.storage_deposits;
+                Err(Error::CalleeTrapped)
+            }
+        }
+    }
+
+    /// Returns the code hash of the contract at the encoded `account`.
+    fn code_hash_of<H>(&self, account: &[u8]) -> Result<H>
+    where
+        H: scale::Decode,
+    {
+        if let Some(code_hash) = self.code_hashes.get(account) {
+            return scale::Decode::decode(&mut &code_hash[..]).map_err(Into::into)
+        }
+        // Contracts registered with the same entry point share their code hash.
+        let call = self.contracts.get(account).ok_or(Error::KeyNotFound)?;
+        let mut hash = <Blake2x256 as HashOutput>::Type::default();
+        <Blake2x256 as CryptoHash>::hash(&(*call as usize).to_le_bytes(), &mut hash);
+        scale::Decode::decode(&mut &hash[..]).map_err(Into::into)
     }
 }
 
@@ -391,6 +618,14 @@ impl TypedEnvBackend for EnvInstance {
             })
     }
 
+    fn weight_left<E: Environment>(&mut self) -> Weight {
+        // The off-chain engine only meters the computation time.
+        Weight {
+            ref_time: self.gas_left::<E>(),
+            proof_size: u64::MAX,
+        }
+    }
+
     fn block_timestamp<E: Environment>(&mut self) -> E::Timestamp {
         self.get_property::<E::Timestamp>(Engine::block_timestamp)
             .unwrap_or_else(|error| {
@@ -434,24 +669,42 @@ impl TypedEnvBackend for EnvInstance {
         let builder = TopicsBuilder::default();
         let enc_topics = event.topics::<E, _>(builder.into());
         let enc_data = &scale::Encode::encode(&event)[..];
+        if self.constructor_depth > 0 {
+            self.constructor_events
+                .push(self.engine.count_emitted_events());
+        }
         self.engine.deposit_event(&enc_topics[..], enc_data);
     }
 
-    fn invoke_contract<E, Args, R>(
-        &mut self,
-        params: &CallParams<E, Call<E>, Args, R>,
-    ) -> Result<R>
+    fn invoke_contract<E, Args, R>(params: &CallParams<E, Call<E>, Args, R>) -> Result<R>
     where
         E: Environment,
         Args: scale::Encode,
         R: scale::Decode,
     {
-        let _gas_limit = params.gas_limit();
-        let _callee = params.callee();
-        let _call_flags = params.call_flags().into_u32();
-        let _transferred_value = params.transferred_value();
-        let _input = params.exec_input();
-        unimplemented!("off-chain environment does not support contract invocation")
+        let call_flags = params.call_flags();
+        let callee = scale::Encode::encode(params.callee());
+        let input = Self::on_instance(|instance| {
+            if call_flags.clone_input() {
+                instance.input.clone()
+            } else if call_flags.forward_input() {
+                // The forwarded input is consumed, so that the caller can no longer
+                // read it, just like on-chain.
+                core::mem::take(&mut instance.input)
+            } else {
+                scale::Encode::encode(params.exec_input())
+            }
+        });
+        let transferred_value = scale::Encode::encode(params.transferred_value());
+        let frame = Self::on_instance(|instance| {
+            instance.enter_call(callee, input.clone(), &transferred_value)
+        })?;
+        // The called contract is executed without holding the environment, so that it
+        // can access it like any other code.
+        let entry = frame.entry;
+        let outcome = std::panic::catch_unwind(|| entry(&input));
+        let output = Self::on_instance(|instance| instance.leave_call(frame, outcome))?;
+        scale::Decode::decode(&mut &output[..]).map_err(Into::into)
     }
 
     fn invoke_contract_delegate<E, Args, R>(
@@ -471,19 +724,31 @@ impl TypedEnvBackend for EnvInstance {
 
     fn instantiate_contract<E, Args, Salt, C>(
         &mut self,
-        params: &CreateParams<E, Args, Salt, C>,
+        _params: &CreateParams<E, Args, Salt, C>,
     ) -> Result<E::AccountId>
     where
         E: Environment,
         Args: scale::Encode,
         Salt: AsRef<[u8]>,
     {
-        let _code_hash = params.code_hash();
-        let _gas_limit = params.gas_limit();
-        let _endowment = params.endowment();
-        let _input = params.exec_input();
-        let _salt_bytes = params.salt_bytes();
-        unimplemented!("off-chain environment does not support contract instantiation")
+        // No code is ever uploaded to the off-chain environment, contracts are
+        // registered by their account ID instead, see `test::register_contract`.
+        Err(Error::CodeNotFound)
+    }
+
+    fn try_instantiate_contract<E, Args, Salt, C, ContractError>(
+        &mut self,
+        _params: &CreateParams<E, Args, Salt, C>,
+    ) -> Result<core::result::Result<E::AccountId, ContractError>>
+    where
+        E: Environment,
+        Args: scale::Encode,
+        Salt: AsRef<[u8]>,
+        ContractError: scale::Decode,
+    {
+        // No code is ever uploaded to the off-chain environment, contracts are
+        // registered by their account ID instead, see `test::register_contract`.
+        Err(Error::CodeNotFound)
     }
 
     fn terminate_contract<E>(&mut self, beneficiary: E::AccountId) -> !
@@ -513,31 +778,59 @@ impl TypedEnvBackend for EnvInstance {
         })
     }
 
-    fn is_contract<E>(&mut self, _account: &E::AccountId) -> bool
+    fn is_contract<E>(&mut self, account: &E::AccountId) -> bool
     where
         E: Environment,
     {
-        unimplemented!("off-chain environment does not support contract instantiation")
+        self.contracts.contains_key(&scale::Encode::encode(account))
     }
 
     fn caller_is_origin<E>(&mut self) -> bool
     where
         E: Environment,
     {
-        unimplemented!("off-chain environment does not support cross-contract calls")
+        // Only calls between contracts registered via `test::register_contract`
+        // have a contract as caller.
+        self.call_depth == 0
+    }
+
+    #[cfg(feature = "unstable-interface")]
+    fn origin<E>(&mut self) -> E::AccountId
+    where
+        E: Environment,
+    {
+        if self.call_depth == 0 {
+            return self.caller::<E>()
+        }
+        let origin = self.origin.as_deref().expect("no origin has been set");
+        scale::Decode::decode(&mut &origin[..]).unwrap_or_else(|error| {
+            panic!("could not read `origin` property: {:?}", error)
+        })
     }
 
-    fn code_hash<E>(&mut self, _account: &E::AccountId) -> Result<E::Hash>
+    fn code_hash<E>(&mut self, account: &E::AccountId) -> Result<E::Hash>
     where
         E: Environment,
     {
-        unimplemented!("off-chain environment does not support `code_hash`")
+        self.code_hash_of(&scale::Encode::encode(account))
     }
 
     fn own_code_hash<E>(&mut self) -> Result<E::Hash>
     where
         E: Environment,
     {
-        unimplemented!("off-chain environment does not support `own_code_hash`")
+        let callee = self.engine.get_callee();
+        self.code_hash_of(&callee)
+    }
+
+    #[cfg(feature = "call-runtime")]
+    fn call_runtime<E, Call>(&mut self, call: &Call) -> Result<()>
+    where
+        E: Environment,
+        Call: scale::Encode,
+    {
+        self.engine
+            .call_runtime(&scale::Encode::encode(call))
+            .map_err(Into::into)
     }
 }
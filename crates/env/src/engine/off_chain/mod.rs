@@ -23,14 +23,84 @@ mod tests;
 pub use call_data::CallData;
 
 use super::OnInstance;
-use crate::Error;
+use crate::{
+    Error,
+    StorageDepositInfo,
+};
 
+use core::cell::RefCell;
 use derive_more::From;
 use ink_engine::ext::Engine;
+use std::collections::HashMap;
 
 /// The off-chain environment.
 pub struct EnvInstance {
     engine: Engine,
+    /// The entry points of the contracts registered via
+    /// [`test::register_contract`](test_api::register_contract) by their account ID.
+    contracts: HashMap<Vec<u8>, fn(&[u8])>,
+    /// The code hashes set via [`test::set_code_hash`](test_api::set_code_hash) by
+    /// the account ID.
+    code_hashes: HashMap<Vec<u8>, Vec<u8>>,
+    /// The number of cross-contract calls currently being executed.
+    call_depth: usize,
+    /// The input of the registered contract currently being called, see
+    /// [`test::register_contract`](test_api::register_contract).
+    input: Vec<u8>,
+    /// The value returned via [`return_value`](crate::return_value) by the contract
+    /// executing, until it is taken by the code calling the contract.
+    returned: Option<impls::ReturnValue>,
+    /// The caller of the outermost cross-contract call currently being executed.
+    origin: Option<Vec<u8>>,
+    /// The number of ink! constructors currently being executed.
+    constructor_depth: usize,
+    /// The indices of the recorded events emitted by ink! constructors.
+    constructor_events: Vec<usize>,
+    /// The storage added and removed by the current call so far.
+    storage_deposit: StorageDepositInfo,
+    /// The prices of the storage deposit per byte and per item if storage deposits
+    /// are accounted, see
+    /// [`test::set_storage_deposit_prices`](test_api::set_storage_deposit_prices).
+    storage_deposit_prices: Option<(u128, u128)>,
+    /// The storage deposits paid by the accounts by their account ID.
+    storage_deposits: HashMap<Vec<u8>, u128>,
+}
+
+thread_local!(
+    /// The off-chain environment of the current thread.
+    ///
+    /// It is `None` while it is in use, see [`OnInstance::on_instance`].
+    static INSTANCE: RefCell<Option<EnvInstance>> = RefCell::new(Some(EnvInstance::new()))
+);
+
+impl EnvInstance {
+    /// Creates a new off-chain environment.
+    fn new() -> Self {
+        Self {
+            engine: Engine::new(),
+            contracts: HashMap::new(),
+            code_hashes: HashMap::new(),
+            call_depth: 0,
+            input: Vec::new(),
+            returned: None,
+            origin: None,
+            constructor_depth: 0,
+            constructor_events: Vec::new(),
+            storage_deposit: StorageDepositInfo::new(),
+            storage_deposit_prices: None,
+            storage_deposits: HashMap::new(),
+        }
+    }
+}
+
+/// Gives the taken off-chain environment back to the current thread when dropped,
+/// which happens even if the code using it panics.
+struct InstanceGuard(Option<EnvInstance>);
+
+impl Drop for InstanceGuard {
+    fn drop(&mut self) {
+        INSTANCE.with(|instance| *instance.borrow_mut() = self.0.take());
+    }
 }
 
 impl OnInstance for EnvInstance {
@@ -38,15 +108,14 @@ impl OnInstance for EnvInstance {
     where
         F: FnOnce(&mut Self) -> R,
     {
-        use core::cell::RefCell;
-        thread_local!(
-            static INSTANCE: RefCell<EnvInstance> = RefCell::new(
-                EnvInstance {
-                    engine: Engine::new()
-                }
-            )
-        );
-        INSTANCE.with(|instance| f(&mut instance.borrow_mut()))
+        let instance = INSTANCE
+            .with(|instance| instance.borrow_mut().take())
+            .expect("encountered reentrant access to the off-chain environment");
+        let mut guard = InstanceGuard(Some(instance));
+        f(guard
+            .0
+            .as_mut()
+            .expect("the guard holds the off-chain environment"))
     }
 }
 
@@ -24,10 +24,19 @@ use crate::{
 };
 use core::fmt::Debug;
 use ink_engine::test_api::RecordedDebugMessages;
-use std::panic::UnwindSafe;
+use std::panic::{
+    AssertUnwindSafe,
+    UnwindSafe,
+};
 
 pub use super::call_data::CallData;
-pub use ink_engine::ChainExtension;
+pub use ink_engine::{
+    ext::{
+        GasSchedule,
+        OutOfGas,
+    },
+    ChainExtension,
+};
 
 /// Record for an emitted event.
 #[derive(Clone)]
@@ -61,6 +70,23 @@ where
     })
 }
 
+/// Sets whether every transfer to the account fails.
+///
+/// Allows to test how a contract handles failing transfers, e.g. to accounts which
+/// would fall below the existential deposit. A failing transfer returns
+/// [`Error::TransferFailed`](crate::Error::TransferFailed) and leaves the balances
+/// unchanged.
+pub fn set_transfer_fails<T>(account_id: T::AccountId, fails: bool)
+where
+    T: Environment,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .engine
+            .set_transfer_fails(scale::Encode::encode(&account_id), fails);
+    })
+}
+
 /// Returns the balance of the account.
 ///
 /// # Note
@@ -98,6 +124,132 @@ where
     })
 }
 
+/// A chain extension method mocked by a closure.
+///
+/// Used by [`register_chain_extension_fn`].
+struct ChainExtensionFn<F> {
+    /// The function ID of the mocked chain extension method.
+    func_id: u32,
+    /// Maps the SCALE encoded input to the SCALE encoded output.
+    handler: F,
+}
+
+impl<F> ChainExtension for ChainExtensionFn<F>
+where
+    F: FnMut(&[u8]) -> Vec<u8>,
+{
+    fn func_id(&self) -> u32 {
+        self.func_id
+    }
+
+    fn call(&mut self, input: &[u8], output: &mut Vec<u8>) -> u32 {
+        // The engine forwards the input as an encoded `Vec<u8>`.
+        let input =
+            <Vec<u8> as scale::Decode>::decode(&mut &input[..]).unwrap_or_else(|error| {
+                panic!("could not decode chain extension input: {:?}", error)
+            });
+        output.extend((self.handler)(&input));
+        0
+    }
+}
+
+/// Registers a closure mocking the chain extension method with the given `func_id`.
+///
+/// The closure is given the SCALE encoded input of the chain extension method
+/// and returns its SCALE encoded output. The returned status code is always `0`.
+///
+/// # Example
+///
+/// ```
+/// ink_env::test::register_chain_extension_fn(1101, |input| {
+///     let subject = <[u8; 32] as scale::Decode>::decode(&mut &input[..]).unwrap();
+///     scale::Encode::encode(&subject)
+/// });
+/// ```
+pub fn register_chain_extension_fn<F>(func_id: u32, handler: F)
+where
+    F: FnMut(&[u8]) -> Vec<u8> + 'static,
+{
+    register_chain_extension(ChainExtensionFn { func_id, handler })
+}
+
+/// Registers the `call` entry point of a contract under `account_id`.
+///
+/// Calls to the account via [`invoke_contract`](crate::invoke_contract) are routed
+/// to `call`, which is given the SCALE encoded input of the call. It is executed
+/// with the caller of the call as caller and `account_id` as callee:
+///
+/// - If `call` ends with [`return_value`](crate::return_value), the returned value is
+///   decoded as the output of the call.
+/// - If `call` returns with the reverted flag set or panics, the changes to the
+///   storage and balances done by the call are reverted. A panic is returned to the
+///   caller as [`Error::CalleeTrapped`](crate::Error::CalleeTrapped).
+/// - If `call` ends with [`terminate_contract`](crate::terminate_contract), the
+///   contract is removed, so that subsequent calls to the account fail with
+///   [`Error::NotCallable`](crate::Error::NotCallable).
+///
+/// Unless set via [`set_code_hash`], the [`code_hash`](crate::code_hash) of the
+/// account is derived from `call`.
+///
+/// # Note
+///
+/// ink! smart contracts are registered via `ink::off_chain::register_contract`.
+pub fn register_contract<T>(account_id: T::AccountId, call: fn(&[u8]))
+where
+    T: Environment,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .contracts
+            .insert(scale::Encode::encode(&account_id), call);
+    })
+}
+
+/// Sets the code hash of the contract at `account_id`.
+///
+/// [`code_hash`](crate::code_hash) returns `code_hash` for the account afterwards,
+/// and [`own_code_hash`](crate::own_code_hash) if the account is the callee. This
+/// allows to test e.g. factory or registry contracts which validate the code hash
+/// of the contracts they are given.
+///
+/// Both return [`Error::KeyNotFound`](crate::Error::KeyNotFound) for accounts with
+/// neither a code hash set nor a contract registered via [`register_contract`].
+pub fn set_code_hash<T>(account_id: T::AccountId, code_hash: T::Hash)
+where
+    T: Environment,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.code_hashes.insert(
+            scale::Encode::encode(&account_id),
+            scale::Encode::encode(&code_hash),
+        );
+    })
+}
+
+/// Returns the SCALE encoded inputs of all past calls to the chain extension
+/// method with the given `func_id` in order.
+pub fn recorded_chain_extension_calls(func_id: u32) -> Vec<Vec<u8>> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .engine
+            .chain_extension_handler
+            .recorded_calls(func_id)
+            .to_vec()
+    })
+}
+
+/// Returns the number of past calls to the chain extension method with the
+/// given `func_id`.
+pub fn count_chain_extension_calls(func_id: u32) -> usize {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .engine
+            .chain_extension_handler
+            .recorded_calls(func_id)
+            .len()
+    })
+}
+
 /// Returns the contents of the past performed environmental debug messages in order.
 pub fn recorded_debug_messages() -> RecordedDebugMessages {
     <EnvInstance as OnInstance>::on_instance(|instance| {
@@ -127,6 +279,200 @@ where
     })
 }
 
+/// Advances the chain by `n` blocks, each taking `block_time` to produce.
+///
+/// Both the block number and the block timestamp are advanced, i.e. the block
+/// number is increased by `n` and the block timestamp by `n * block_time`.
+/// This is useful to test contracts relying on `block_timestamp` for e.g.
+/// vesting schedules or auction deadlines.
+pub fn advance_blocks<T>(n: T::BlockNumber, block_time: T::Timestamp)
+where
+    T: Environment<BlockNumber = u32, Timestamp = u64>,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.advance_blocks(n, block_time);
+    })
+}
+
+/// Sets the block time used when advancing the chain by a single block
+/// via [`advance_block`].
+pub fn set_block_time<T>(block_time: T::Timestamp)
+where
+    T: Environment<Timestamp = u64>,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.set_block_time(block_time);
+    })
+}
+
+/// Sets the gas limit of the execution and resets the consumed gas.
+///
+/// Storage accesses and emitted events are charged according to the [`GasSchedule`].
+/// Exceeding the gas limit panics with an [`OutOfGas`] payload. By default the gas
+/// is unlimited, which a gas limit of `None` restores.
+pub fn set_gas_limit(gas_limit: Option<u64>) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.set_gas_limit(gas_limit);
+    })
+}
+
+/// Charges `amount` of gas to the execution.
+///
+/// Useful to account for the cost of computations which are not charged by
+/// the off-chain environment, e.g. the iterations of a loop.
+///
+/// # Panics
+///
+/// With an [`OutOfGas`] payload if the gas limit is exceeded.
+pub fn charge_gas(amount: u64) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.charge_gas(amount);
+    })
+}
+
+/// Returns the gas consumed since the gas limit was last set.
+pub fn gas_consumed() -> u64 {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.engine.gas_consumed())
+}
+
+/// Sets the gas charged for storage accesses and emitted events.
+pub fn set_gas_schedule(gas_schedule: GasSchedule) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.set_gas_schedule(gas_schedule);
+    })
+}
+
+/// Asserts that `f` runs out of gas when executed with the given `gas_limit`.
+///
+/// The gas is unlimited again afterwards.
+///
+/// # Panics
+///
+/// If `f` completes within the `gas_limit` or panics for another reason than
+/// running out of gas.
+///
+/// # Usage
+///
+/// ```no_compile
+/// ink_env::test::assert_out_of_gas(100_000, || {
+///     contract.sum_all_entries();
+/// });
+/// ```
+pub fn assert_out_of_gas<F>(gas_limit: u64, f: F)
+where
+    F: FnOnce() + UnwindSafe,
+{
+    set_gas_limit(Some(gas_limit));
+    let result = std::panic::catch_unwind(f);
+    let consumed = gas_consumed();
+    set_gas_limit(None);
+    match result {
+        Ok(()) => {
+            panic!(
+                "expected execution to run out of gas, but it consumed {} of {} gas",
+                consumed, gas_limit
+            )
+        }
+        Err(payload) if payload.is::<OutOfGas>() => (),
+        Err(payload) => std::panic::resume_unwind(payload),
+    }
+}
+
+/// A call of an ink! message in a unit test, reverting with the error it returns.
+///
+/// Implemented by closures returning a `Result`, see [`assert_reverts_with`].
+pub trait RevertibleCall {
+    /// The error the call reverts with.
+    type Error;
+
+    /// Calls the ink! message, returning the error it reverts with if any.
+    fn call(self) -> core::result::Result<(), Self::Error>;
+}
+
+impl<F, R, Err> RevertibleCall for F
+where
+    F: FnOnce() -> core::result::Result<R, Err>,
+{
+    type Error = Err;
+
+    fn call(self) -> core::result::Result<(), Err> {
+        self().map(|_| ())
+    }
+}
+
+/// Asserts that the ink! message called by `f` reverts with the `expected` error.
+///
+/// The message either returns the error, with which ink! reverts the call, or
+/// reverts explicitly via [`return_value`](crate::return_value) with the data of
+/// a reverted ink! message, i.e. an encoded `MessageResult<Result<T, Error>>`.
+///
+/// # Panics
+///
+/// - If `f` does not revert or reverts with another error.
+/// - If `f` reverts with data not decodable into the error.
+/// - If `f` panics for another reason than reverting.
+///
+/// # Usage
+///
+/// ```
+/// #[derive(Debug, PartialEq, scale::Decode)]
+/// enum Error {
+///     InsufficientBalance,
+/// }
+///
+/// fn transfer(balance: u128, value: u128) -> Result<(), Error> {
+///     if balance < value {
+///         return Err(Error::InsufficientBalance)
+///     }
+///     Ok(())
+/// }
+///
+/// ink_env::test::assert_reverts_with(|| transfer(5, 10), Error::InsufficientBalance);
+/// ```
+pub fn assert_reverts_with<F>(f: F, expected: F::Error)
+where
+    F: RevertibleCall,
+    F::Error: scale::Decode + PartialEq + Debug,
+{
+    let outcome = std::panic::catch_unwind(AssertUnwindSafe(move || f.call()));
+    let returned =
+        <EnvInstance as OnInstance>::on_instance(|instance| instance.returned.take());
+    let error = match (outcome, returned) {
+        (Ok(Ok(())), _) => {
+            panic!(
+                "expected the call to revert with {:?}, but it succeeded",
+                expected
+            )
+        }
+        (Ok(Err(error)), _) => error,
+        (Err(_), Some(return_value)) if return_value.reverted => {
+            let output = <ink_primitives::MessageResult<
+                core::result::Result<(), F::Error>,
+            > as scale::Decode>::decode(
+                &mut &return_value.data[..]
+            )
+            .unwrap_or_else(|err| panic!("revert data can not be decoded: {}", err));
+            match output {
+                Ok(Err(error)) => error,
+                Ok(Ok(())) => {
+                    panic!(
+                        "expected the call to revert with {:?}, but it reverted without an error",
+                        expected
+                    )
+                }
+                Err(lang_error) => {
+                    panic!(
+                        "expected the call to revert with {:?}, but it reverted with {:?}",
+                        expected, lang_error
+                    )
+                }
+            }
+        }
+        (Err(payload), _) => std::panic::resume_unwind(payload),
+    };
+    assert_eq!(error, expected, "the call reverted with another error");
+}
+
 /// Sets a caller for the next call.
 pub fn set_caller<T>(caller: T::AccountId)
 where
@@ -142,7 +488,6 @@ where
 pub fn set_callee<T>(callee: T::AccountId)
 where
     T: Environment,
-    <T as Environment>::AccountId: From<[u8; 32]>,
 {
     <EnvInstance as OnInstance>::on_instance(|instance| {
         instance.engine.set_callee(scale::Encode::encode(&callee));
@@ -175,6 +520,51 @@ where
     })
 }
 
+/// Returns the raw encoded value stored at `key` in the storage of the callee.
+///
+/// Returns `None` if no value is stored at `key`. The read is neither charged nor
+/// counted by [`get_contract_storage_rw`].
+pub fn get_contract_storage_raw<K>(key: &K) -> Option<Vec<u8>>
+where
+    K: scale::Encode,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .engine
+            .get_storage_raw(&scale::Encode::encode(key))
+            .map(ToOwned::to_owned)
+    })
+}
+
+/// Stores the raw encoded `value` at `key` in the storage of the callee.
+///
+/// This allows tests to simulate storage written by a legacy layout or corrupted
+/// storage, in order to verify migrations and defensive decoding. The write is
+/// neither charged nor counted by [`get_contract_storage_rw`].
+///
+/// # Example
+///
+/// ```
+/// # use ink_env::test;
+/// # test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+/// // A `u8` where the contract expects a `u32`.
+/// test::set_contract_storage_raw(&42u32, &[1u8]);
+/// assert_eq!(test::get_contract_storage_raw(&42u32), Some(vec![1u8]));
+/// assert!(ink_env::get_contract_storage::<u32, u32>(&42u32).is_err());
+/// # Ok(())
+/// # }).unwrap();
+/// ```
+pub fn set_contract_storage_raw<K>(key: &K, value: &[u8])
+where
+    K: scale::Encode,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .engine
+            .set_storage_raw(&scale::Encode::encode(key), value.to_vec());
+    })
+}
+
 /// Sets the balance of `account_id` to `new_balance`.
 pub fn set_balance<T>(account_id: T::AccountId, new_balance: T::Balance)
 where
@@ -253,6 +643,62 @@ where
     })
 }
 
+/// Enables accounting the storage deposits with the deposit prices of the chain
+/// `per_byte` and `per_item`.
+///
+/// Every value written to or removed from the contract storage afterwards charges
+/// the origin of the call stack the deposit of the added storage or refunds it the
+/// deposit of the removed storage, like `pallet-contracts` does. The balances of
+/// the accounts are not changed. Storage deposits are not accounted by default.
+///
+/// # Example
+///
+/// ```
+/// use ink_env::{
+///     test,
+///     DefaultEnvironment,
+/// };
+///
+/// # test::run_test::<DefaultEnvironment, _>(|accounts| {
+/// test::set_storage_deposit_prices::<DefaultEnvironment>(10, 100);
+/// test::set_caller::<DefaultEnvironment>(accounts.bob);
+///
+/// // Staking stores the stake of the caller ...
+/// ink_env::set_contract_storage(&accounts.bob, &1_000u128);
+/// assert_eq!(test::storage_deposit::<DefaultEnvironment>(accounts.bob), 260);
+///
+/// // ... which is refunded when unstaking.
+/// ink_env::clear_contract_storage(&accounts.bob);
+/// assert_eq!(test::storage_deposit::<DefaultEnvironment>(accounts.bob), 0);
+/// # Ok(())
+/// # }).unwrap();
+/// ```
+pub fn set_storage_deposit_prices<T>(per_byte: T::Balance, per_item: T::Balance)
+where
+    T: Environment<Balance = u128>,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.storage_deposit_prices = Some((per_byte, per_item));
+    })
+}
+
+/// Returns the storage deposit paid by the account `account_id`.
+///
+/// Storage deposits are only accounted after enabling them via
+/// [`set_storage_deposit_prices`].
+pub fn storage_deposit<T>(account_id: T::AccountId) -> T::Balance
+where
+    T: Environment<Balance = u128>,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .storage_deposits
+            .get(&scale::Encode::encode(&account_id))
+            .copied()
+            .unwrap_or_default()
+    })
+}
+
 /// Runs the given closure test function with the default configuration
 /// for the off-chain environment.
 pub fn run_test<T, F>(f: F) -> Result<()>
@@ -264,6 +710,13 @@ where
     let default_accounts = default_accounts::<T>();
     <EnvInstance as OnInstance>::on_instance(|instance| {
         instance.engine.initialize_or_reset();
+        instance.contracts.clear();
+        instance.code_hashes.clear();
+        instance.constructor_depth = 0;
+        instance.constructor_events.clear();
+        instance.storage_deposit = Default::default();
+        instance.storage_deposit_prices = None;
+        instance.storage_deposits.clear();
 
         let encoded_alice = scale::Encode::encode(&default_accounts.alice);
         instance.engine.set_caller(encoded_alice.clone());
@@ -339,6 +792,117 @@ pub fn recorded_events() -> impl Iterator<Item = EmittedEvent> {
     })
 }
 
+/// Returns the recorded events emitted by ink! constructors in order.
+///
+/// This includes the events emitted by constructors called directly in unit tests
+/// as well as by the constructors of contracts registered via
+/// `ink::off_chain::register_contract`.
+pub fn recorded_constructor_events() -> impl Iterator<Item = EmittedEvent> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        let events = instance.engine.get_emitted_events().collect::<Vec<_>>();
+        instance
+            .constructor_events
+            .iter()
+            .map(|index| events[*index].clone().into())
+            .collect::<Vec<EmittedEvent>>()
+            .into_iter()
+    })
+}
+
+/// Marks the start of the execution of an ink! constructor.
+///
+/// # Note
+///
+/// This is called by the code generated for ink! constructors.
+#[doc(hidden)]
+pub fn enter_constructor() {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.constructor_depth += 1;
+    })
+}
+
+/// Marks the end of the execution of an ink! constructor.
+///
+/// # Note
+///
+/// This is called by the code generated for ink! constructors.
+#[doc(hidden)]
+pub fn leave_constructor() {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.constructor_depth = instance.constructor_depth.saturating_sub(1);
+    })
+}
+
+/// Returns the SCALE encoded runtime calls dispatched via
+/// [`call_runtime`](crate::call_runtime) in order.
+#[cfg(feature = "call-runtime")]
+pub fn recorded_runtime_calls() -> impl Iterator<Item = Vec<u8>> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.engine.get_runtime_calls()
+    })
+}
+
+/// Implemented by ink! events to decode them from recorded events.
+///
+/// # Note
+///
+/// This is implemented automatically for all events defined with `#[ink(event)]`.
+pub trait DecodeEvent: Sized {
+    /// Decodes the event from the data of the recorded `event`.
+    ///
+    /// Returns `None` if the recorded event is of another kind.
+    fn decode_event(event: &EmittedEvent) -> Option<Self>;
+}
+
+/// Returns all recorded events of the given kind in the order they were emitted.
+///
+/// # Example
+///
+/// ```ignore
+/// let transfers = ink::env::test::recorded_events_decoded::<Transfer>();
+/// assert_eq!(transfers.len(), 1);
+/// assert_eq!(transfers[0].value, 100);
+/// ```
+pub fn recorded_events_decoded<Event>() -> Vec<Event>
+where
+    Event: DecodeEvent,
+{
+    recorded_events()
+        .filter_map(|event| Event::decode_event(&event))
+        .collect()
+}
+
+/// Asserts that an ink! constructor emitted an event of the given kind and returns
+/// the most recently emitted one.
+///
+/// # Example
+///
+/// ```ignore
+/// let contract = MyContract::new();
+/// let instantiated =
+///     ink::env::test::assert_constructor_event::<DefaultEnvironment, Instantiated>();
+/// assert_eq!(instantiated.admin, accounts.alice);
+/// ```
+///
+/// # Panics
+///
+/// If no constructor emitted an event of the given kind.
+pub fn assert_constructor_event<T, Event>() -> Event
+where
+    T: Environment,
+    Event: DecodeEvent,
+{
+    recorded_constructor_events()
+        .filter_map(|event| Event::decode_event(&event))
+        .last()
+        .unwrap_or_else(|| {
+            panic!(
+                "no `{}` event was emitted by a constructor",
+                core::any::type_name::<Event>()
+            )
+        })
+}
+
 /// Tests if a contract terminates successfully after `self.env().terminate()`
 /// has been called.
 ///
@@ -394,3 +958,27 @@ macro_rules! pay_with_call {
         $contract.$message($ ($params) ,*)
     }}
 }
+
+/// Asserts that an event matching the given pattern has been emitted.
+///
+/// Only recorded events of the kind named by the pattern are taken into account.
+/// Fields that are not of interest can be omitted with `..`.
+///
+/// # Example
+///
+/// ```ignore
+/// ink::env::assert_emitted!(Transfer { value: 100, .. });
+/// ```
+#[macro_export]
+macro_rules! assert_emitted {
+    ( $event:path { $( $fields:tt )* } ) => {{
+        let events = $crate::test::recorded_events_decoded::<$event>();
+        ::core::assert!(
+            events.iter().any(|event| ::core::matches!(event, $event { $( $fields )* })),
+            "expected `{}` to have been emitted, but found {} `{}` event(s) not matching",
+            ::core::stringify!($event { $( $fields )* }),
+            events.len(),
+            ::core::stringify!($event),
+        );
+    }};
+}
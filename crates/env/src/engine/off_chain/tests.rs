@@ -41,3 +41,1002 @@ fn topics_builder() -> Result<()> {
         Ok(())
     })
 }
+
+#[test]
+fn advance_blocks_advances_block_number_and_timestamp() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        // given
+        let block_number = crate::block_number::<crate::DefaultEnvironment>();
+        let block_timestamp = crate::block_timestamp::<crate::DefaultEnvironment>();
+
+        // when
+        crate::test::advance_blocks::<crate::DefaultEnvironment>(5, 12_000);
+
+        // then
+        assert_eq!(
+            crate::block_number::<crate::DefaultEnvironment>(),
+            block_number + 5
+        );
+        assert_eq!(
+            crate::block_timestamp::<crate::DefaultEnvironment>(),
+            block_timestamp + 60_000
+        );
+
+        Ok(())
+    })
+}
+
+#[test]
+fn chain_extension_fn_mock_records_calls() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        // given
+        crate::test::register_chain_extension_fn(42, |input| {
+            let value = <u32 as scale::Decode>::decode(&mut &input[..]).unwrap();
+            scale::Encode::encode(&(value * 2))
+        });
+        let call = |value: u32| {
+            crate::chain_extension::ChainExtensionMethod::build(42)
+                .input::<u32>()
+                .output::<u32>()
+                .ignore_error_code()
+                .call(&value)
+        };
+
+        // when
+        let first = call(3);
+        let second = call(7);
+
+        // then
+        assert_eq!((first, second), (6, 14));
+        assert_eq!(crate::test::count_chain_extension_calls(42), 2);
+        assert_eq!(
+            crate::test::recorded_chain_extension_calls(42),
+            vec![scale::Encode::encode(&3_u32), scale::Encode::encode(&7_u32)]
+        );
+        assert_eq!(crate::test::count_chain_extension_calls(1), 0);
+
+        Ok(())
+    })
+}
+
+#[test]
+fn gas_limit_is_enforced() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        // given
+        crate::test::set_gas_limit(Some(10_000));
+        crate::test::charge_gas(4_000);
+
+        // then
+        assert_eq!(crate::test::gas_consumed(), 4_000);
+        assert_eq!(crate::gas_left::<crate::DefaultEnvironment>(), 6_000);
+
+        // when
+        crate::set_contract_storage(&1u32, &[0u8; 100]);
+
+        // then
+        assert!(crate::test::gas_consumed() > 4_000);
+        crate::test::assert_out_of_gas(10_000, || {
+            for key in 0u32.. {
+                crate::set_contract_storage(&key, &key);
+            }
+        });
+        assert_eq!(crate::gas_left::<crate::DefaultEnvironment>(), u64::MAX);
+        Ok(())
+    })
+}
+
+#[test]
+fn transfers_to_marked_accounts_fail() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|accounts| {
+        // given
+        crate::test::set_transfer_fails::<crate::DefaultEnvironment>(accounts.bob, true);
+        let balance_of = |account| {
+            crate::test::get_account_balance::<crate::DefaultEnvironment>(account)
+        };
+        let contract_balance = balance_of(accounts.alice)?;
+        let bob_balance = balance_of(accounts.bob)?;
+
+        // when
+        let failed = crate::transfer::<crate::DefaultEnvironment>(accounts.bob, 10);
+        let succeeded =
+            crate::transfer::<crate::DefaultEnvironment>(accounts.charlie, 10);
+
+        // then
+        assert_eq!(failed, Err(crate::Error::TransferFailed));
+        assert_eq!(succeeded, Ok(()));
+        assert_eq!(balance_of(accounts.alice)?, contract_balance - 10);
+        assert_eq!(balance_of(accounts.bob)?, bob_balance);
+        Ok(())
+    })
+}
+
+#[test]
+fn transfer_keep_alive_keeps_minimum_balance() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|accounts| {
+        // given
+        let minimum_balance = crate::minimum_balance::<crate::DefaultEnvironment>();
+        crate::test::set_account_balance::<crate::DefaultEnvironment>(
+            accounts.alice,
+            minimum_balance + 100,
+        );
+        let balance_of = |account| {
+            crate::test::get_account_balance::<crate::DefaultEnvironment>(account)
+        };
+        let bob_balance = balance_of(accounts.bob)?;
+
+        // when
+        let failed =
+            crate::transfer_keep_alive::<crate::DefaultEnvironment>(accounts.bob, 101);
+        let succeeded =
+            crate::transfer_keep_alive::<crate::DefaultEnvironment>(accounts.bob, 100);
+
+        // then
+        assert_eq!(failed, Err(crate::Error::BelowMinimumBalance));
+        assert_eq!(succeeded, Ok(()));
+        assert_eq!(balance_of(accounts.alice)?, minimum_balance);
+        assert_eq!(balance_of(accounts.bob)?, bob_balance + 100);
+        Ok(())
+    })
+}
+
+#[test]
+fn transfer_all_works() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|accounts| {
+        // given
+        let minimum_balance = crate::minimum_balance::<crate::DefaultEnvironment>();
+        crate::test::set_account_balance::<crate::DefaultEnvironment>(
+            accounts.alice,
+            minimum_balance + 100,
+        );
+        let balance_of = |account| {
+            crate::test::get_account_balance::<crate::DefaultEnvironment>(account)
+        };
+
+        // when
+        let kept_alive =
+            crate::transfer_all::<crate::DefaultEnvironment>(accounts.bob, true);
+
+        // then
+        assert_eq!(kept_alive, Ok(100));
+        assert_eq!(balance_of(accounts.alice)?, minimum_balance);
+
+        // when
+        let all = crate::transfer_all::<crate::DefaultEnvironment>(accounts.bob, false);
+
+        // then
+        assert_eq!(all, Ok(minimum_balance));
+        assert_eq!(balance_of(accounts.alice)?, 0);
+        Ok(())
+    })
+}
+
+#[test]
+fn weight_left_tracks_gas_limit() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        // given
+        crate::test::set_gas_limit(Some(10_000));
+
+        // when
+        crate::test::charge_gas(4_000);
+
+        // then
+        let weight_left = crate::weight_left::<crate::DefaultEnvironment>();
+        assert_eq!(weight_left.ref_time, 6_000);
+        assert_eq!(weight_left.proof_size, u64::MAX);
+        Ok(())
+    })
+}
+
+#[test]
+fn for_each_bounded_stops_at_weight_limit() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        // given
+        let per_item_weight = crate::Weight {
+            ref_time: 3_000,
+            proof_size: 0,
+        };
+        let mut items = Vec::new();
+        let mut cursor = Some(0);
+        let mut batches = Vec::new();
+
+        // when
+        while let Some(start) = cursor {
+            crate::test::set_gas_limit(Some(10_000));
+            let progress =
+                crate::budget::for_each_bounded::<crate::DefaultEnvironment, _, _>(
+                    (0..8).skip(start as usize),
+                    per_item_weight,
+                    |item| {
+                        crate::test::charge_gas(3_000);
+                        items.push(item);
+                    },
+                );
+            batches.push(progress.processed());
+            cursor = progress.cursor(start);
+        }
+
+        // then
+        assert_eq!(items, [0, 1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(batches, [3, 3, 2]);
+        Ok(())
+    })
+}
+
+#[test]
+fn progress_cursor_is_absolute() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        // given
+        crate::test::set_gas_limit(Some(10_000));
+
+        // when
+        let progress = crate::budget::for_each_bounded::<crate::DefaultEnvironment, _, _>(
+            (0..10).skip(6),
+            crate::Weight {
+                ref_time: 3_000,
+                proof_size: 0,
+            },
+            |_| crate::test::charge_gas(3_000),
+        );
+
+        // then
+        assert_eq!(progress.processed(), 3);
+        assert!(!progress.is_completed());
+        assert_eq!(progress.cursor(6), Some(9));
+        Ok(())
+    })
+}
+
+#[test]
+#[should_panic(expected = "expected execution to run out of gas, but it consumed")]
+fn assert_out_of_gas_fails_within_gas_limit() {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        crate::test::assert_out_of_gas(10_000, || crate::test::charge_gas(10_000));
+        Ok(())
+    })
+    .unwrap()
+}
+
+#[test]
+fn assert_reverts_with_works() {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        // The error returned by the message.
+        crate::test::assert_reverts_with(|| Err::<u32, _>(42u8), 42u8);
+        // The error of the explicitly reverted message.
+        crate::test::assert_reverts_with(
+            || -> core::result::Result<(), u8> {
+                let output: ink_primitives::MessageResult<core::result::Result<u32, u8>> =
+                    Ok(Err(42));
+                crate::return_value(crate::ReturnFlags::new_with_reverted(true), &output)
+            },
+            42u8,
+        );
+        Ok(())
+    })
+    .unwrap()
+}
+
+#[test]
+#[should_panic(expected = "expected the call to revert with 42, but it succeeded")]
+fn assert_reverts_with_fails_without_revert() {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        crate::test::assert_reverts_with(|| Ok::<_, u8>(()), 42u8);
+        Ok(())
+    })
+    .unwrap()
+}
+
+#[test]
+#[should_panic(expected = "the call reverted with another error")]
+fn assert_reverts_with_fails_for_other_error() {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        crate::test::assert_reverts_with(|| Err::<(), _>(7u8), 42u8);
+        Ok(())
+    })
+    .unwrap()
+}
+
+#[test]
+#[should_panic(
+    expected = "the ink! message reverted with the SCALE encoded output [0, 1, 42]"
+)]
+fn revert_outside_of_contract_call_panics_with_output() {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        let output: ink_primitives::MessageResult<core::result::Result<u32, u8>> =
+            Ok(Err(42));
+        crate::return_value(crate::ReturnFlags::new_with_reverted(true), &output)
+    })
+    .unwrap()
+}
+
+#[test]
+#[should_panic(
+    expected = "the ink! message returned with the SCALE encoded output [0, 42]"
+)]
+fn return_outside_of_contract_call_panics_with_output() {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        let output: ink_primitives::MessageResult<u8> = Ok(42);
+        crate::return_value(crate::ReturnFlags::default(), &output)
+    })
+    .unwrap()
+}
+
+#[test]
+fn instantiate_contract_fails_without_code() -> Result<()> {
+    use crate::call::{
+        build_create,
+        ExecutionInput,
+        FromAccountId,
+        Selector,
+    };
+    /// A reference to an instantiated contract.
+    struct ContractRef;
+    impl FromAccountId<crate::DefaultEnvironment> for ContractRef {
+        fn from_account_id(_account_id: ink_primitives::AccountId) -> Self {
+            Self
+        }
+    }
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        let create = || {
+            build_create::<crate::DefaultEnvironment, ContractRef>()
+                .code_hash(Default::default())
+                .endowment(0)
+                .exec_input(ExecutionInput::new(Selector::new([0x00; 4])))
+                .salt_bytes(&[0x00])
+                .params()
+        };
+        assert_eq!(
+            crate::instantiate_contract(&create()).err(),
+            Some(crate::Error::CodeNotFound)
+        );
+        assert_eq!(
+            crate::try_instantiate_contract::<_, _, _, _, u8>(&create()).err(),
+            Some(crate::Error::CodeNotFound)
+        );
+        Ok(())
+    })
+}
+
+/// A contract adding the `u32` argument of every call to its stored counter.
+///
+/// Reverts if the argument is `0` and traps if it is `u32::MAX`.
+fn counter(mut input: &[u8]) {
+    let (_selector, by) = <([u8; 4], u32) as scale::Decode>::decode(&mut input).unwrap();
+    assert_ne!(by, u32::MAX, "encountered invalid argument");
+    let counter = crate::get_contract_storage::<u32, u32>(&0)
+        .unwrap()
+        .unwrap_or_default()
+        + by;
+    crate::set_contract_storage(&0u32, &counter);
+    crate::return_value(crate::ReturnFlags::new_with_reverted(by == 0), &counter)
+}
+
+/// A contract which traps on every call.
+fn trapping(_input: &[u8]) {
+    panic!("trapped")
+}
+
+#[test]
+fn fallback_applies_when_callee_traps() -> Result<()> {
+    use crate::call::{
+        build_call,
+        Call,
+        ExecutionInput,
+        Fallback,
+        Selector,
+    };
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|accounts| {
+        // given
+        crate::test::register_contract::<crate::DefaultEnvironment>(
+            accounts.bob,
+            trapping,
+        );
+        crate::test::register_contract::<crate::DefaultEnvironment>(
+            accounts.charlie,
+            counter,
+        );
+        let call = |by: u32, fallback| {
+            build_call::<crate::DefaultEnvironment>()
+                .call_type(Call::new().callee(accounts.bob))
+                .exec_input(ExecutionInput::new(Selector::new([0x00; 4])).push_arg(by))
+                .returns::<u32>()
+                .try_invoke_with_fallback(fallback)
+        };
+
+        // then
+        assert_eq!(
+            call(5, Fallback::new().callee(accounts.charlie).value(0)),
+            Ok(5)
+        );
+        assert_eq!(
+            call(5, Fallback::new().callee(accounts.bob).value(0)),
+            Ok(0)
+        );
+        assert_eq!(call(5, Fallback::new()), Err(crate::Error::CalleeTrapped));
+        assert_eq!(
+            call(5, Fallback::new().callee(accounts.django).value(0)),
+            Err(crate::Error::NotCallable),
+            "errors other than traps must be returned"
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn invoke_contract_routes_to_registered_contracts() -> Result<()> {
+    use crate::call::{
+        build_call,
+        Call,
+        ExecutionInput,
+        Selector,
+    };
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|accounts| {
+        // given
+        crate::test::register_contract::<crate::DefaultEnvironment>(
+            accounts.bob,
+            counter,
+        );
+        let call = |callee, by: u32| {
+            build_call::<crate::DefaultEnvironment>()
+                .call_type(Call::new().callee(callee))
+                .exec_input(ExecutionInput::new(Selector::new([0x00; 4])).push_arg(by))
+                .returns::<u32>()
+                .fire()
+        };
+
+        // when
+        let first = call(accounts.bob, 3);
+        let second = call(accounts.bob, 4);
+        let reverted = call(accounts.bob, 0);
+        let trapped = call(accounts.bob, u32::MAX);
+
+        // then
+        assert_eq!((first, second, reverted), (Ok(3), Ok(7), Ok(7)));
+        assert_eq!(trapped, Err(crate::Error::CalleeTrapped));
+        assert_eq!(call(accounts.charlie, 1), Err(crate::Error::NotCallable));
+        assert_eq!(
+            crate::test::callee::<crate::DefaultEnvironment>(),
+            accounts.alice
+        );
+        assert_eq!(
+            crate::get_contract_storage::<u32, u32>(&0),
+            Ok(None),
+            "the storage of the caller must not be changed"
+        );
+        assert!(crate::is_contract::<crate::DefaultEnvironment>(
+            &accounts.bob
+        ));
+        assert!(!crate::is_contract::<crate::DefaultEnvironment>(
+            &accounts.charlie
+        ));
+
+        Ok(())
+    })
+}
+
+/// A contract returning the `u32` argument of the call and whether its input is
+/// readable via both [`crate::decode_input`] and [`crate::with_input_bytes`].
+fn echoing(input: &[u8]) {
+    let (_selector, arg) = crate::decode_input::<([u8; 4], u32)>().unwrap();
+    let matches = crate::with_input_bytes(|bytes| bytes == input);
+    crate::return_value(crate::ReturnFlags::default(), &(arg, matches))
+}
+
+#[test]
+fn registered_contracts_read_their_input() -> Result<()> {
+    use crate::call::{
+        build_call,
+        Call,
+        ExecutionInput,
+        Selector,
+    };
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|accounts| {
+        crate::test::register_contract::<crate::DefaultEnvironment>(
+            accounts.bob,
+            echoing,
+        );
+        let output = build_call::<crate::DefaultEnvironment>()
+            .call_type(Call::new().callee(accounts.bob))
+            .exec_input(ExecutionInput::new(Selector::new([0x00; 4])).push_arg(42u32))
+            .returns::<(u32, bool)>()
+            .fire();
+        assert_eq!(output, Ok((42, true)));
+        assert_eq!(
+            crate::with_input_bytes(<[u8]>::to_vec),
+            Vec::<u8>::new(),
+            "the input of the caller must be restored"
+        );
+        Ok(())
+    })
+}
+
+/// A contract relaying its input to the [`echoing`] contract of `charlie`.
+///
+/// Returns the output of `echoing` and the length of its own input afterwards.
+fn relaying<const CLONE_INPUT: bool>(_input: &[u8]) {
+    use crate::{
+        call::{
+            build_call,
+            Call,
+            ExecutionInput,
+            Selector,
+        },
+        CallFlags,
+    };
+    let accounts = crate::test::default_accounts::<crate::DefaultEnvironment>();
+    let output = build_call::<crate::DefaultEnvironment>()
+        .call_type(Call::new().callee(accounts.charlie))
+        .call_flags(
+            CallFlags::default()
+                .set_forward_input(!CLONE_INPUT)
+                .set_clone_input(CLONE_INPUT),
+        )
+        .exec_input(ExecutionInput::new(Selector::new([0xFF; 4])))
+        .returns::<(u32, bool)>()
+        .fire()
+        .unwrap();
+    let input_len = crate::with_input_bytes(<[u8]>::len);
+    crate::return_value(crate::ReturnFlags::default(), &(output, input_len as u32))
+}
+
+#[test]
+fn forwarded_and_cloned_inputs_are_passed_to_callee() -> Result<()> {
+    use crate::call::{
+        build_call,
+        Call,
+        ExecutionInput,
+        Selector,
+    };
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|accounts| {
+        crate::test::register_contract::<crate::DefaultEnvironment>(
+            accounts.bob,
+            relaying::<false>,
+        );
+        crate::test::register_contract::<crate::DefaultEnvironment>(
+            accounts.charlie,
+            echoing,
+        );
+        crate::test::register_contract::<crate::DefaultEnvironment>(
+            accounts.django,
+            relaying::<true>,
+        );
+        let call = |callee| {
+            build_call::<crate::DefaultEnvironment>()
+                .call_type(Call::new().callee(callee))
+                .exec_input(ExecutionInput::new(Selector::new([0x00; 4])).push_arg(42u32))
+                .returns::<((u32, bool), u32)>()
+                .fire()
+        };
+        assert_eq!(
+            call(accounts.bob),
+            Ok(((42, true), 0)),
+            "the forwarded input must be consumed"
+        );
+        assert_eq!(
+            call(accounts.django),
+            Ok(((42, true), 8)),
+            "the cloned input must be preserved"
+        );
+        Ok(())
+    })
+}
+
+/// The output of the [`introspecting`] contract.
+#[cfg(feature = "unstable-interface")]
+type Introspection = (
+    <crate::DefaultEnvironment as crate::Environment>::AccountId,
+    bool,
+);
+
+/// A contract returning the origin and whether its caller is the origin.
+#[cfg(feature = "unstable-interface")]
+fn introspecting(_input: &[u8]) {
+    let output: Introspection = (
+        crate::origin::<crate::DefaultEnvironment>(),
+        crate::caller_is_origin::<crate::DefaultEnvironment>(),
+    );
+    crate::return_value(crate::ReturnFlags::default(), &output)
+}
+
+/// A contract forwarding every call to the [`introspecting`] contract of `bob`.
+#[cfg(feature = "unstable-interface")]
+fn forwarding(_input: &[u8]) {
+    use crate::call::{
+        build_call,
+        Call,
+        ExecutionInput,
+        Selector,
+    };
+    let accounts = crate::test::default_accounts::<crate::DefaultEnvironment>();
+    let output = build_call::<crate::DefaultEnvironment>()
+        .call_type(Call::new().callee(accounts.bob))
+        .exec_input(ExecutionInput::new(Selector::new([0x00; 4])))
+        .returns::<Introspection>()
+        .fire()
+        .unwrap();
+    crate::return_value(crate::ReturnFlags::default(), &output)
+}
+
+#[test]
+#[cfg(feature = "unstable-interface")]
+fn call_stack_introspection_works() -> Result<()> {
+    use crate::call::{
+        build_call,
+        Call,
+        ExecutionInput,
+        Selector,
+    };
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|accounts| {
+        // given
+        crate::test::register_contract::<crate::DefaultEnvironment>(
+            accounts.bob,
+            introspecting,
+        );
+        crate::test::register_contract::<crate::DefaultEnvironment>(
+            accounts.charlie,
+            forwarding,
+        );
+        let call = |callee| {
+            build_call::<crate::DefaultEnvironment>()
+                .call_type(Call::new().callee(callee))
+                .exec_input(ExecutionInput::new(Selector::new([0x00; 4])))
+                .returns::<Introspection>()
+                .fire()
+        };
+
+        // then
+        assert_eq!(crate::origin::<crate::DefaultEnvironment>(), accounts.alice);
+        assert!(crate::caller_is_origin::<crate::DefaultEnvironment>());
+        assert_eq!(call(accounts.bob), Ok((accounts.alice, false)));
+        assert_eq!(call(accounts.charlie), Ok((accounts.alice, false)));
+        Ok(())
+    })
+}
+
+/// A contract which terminates itself on every call.
+fn terminating(_input: &[u8]) {
+    let accounts = crate::test::default_accounts::<crate::DefaultEnvironment>();
+    crate::terminate_contract::<crate::DefaultEnvironment>(accounts.alice)
+}
+
+#[test]
+fn terminated_contracts_are_removed() -> Result<()> {
+    use crate::call::{
+        build_call,
+        Call,
+        ExecutionInput,
+        Selector,
+    };
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|accounts| {
+        // given
+        crate::test::register_contract::<crate::DefaultEnvironment>(
+            accounts.bob,
+            terminating,
+        );
+        crate::test::register_contract::<crate::DefaultEnvironment>(
+            accounts.charlie,
+            terminating,
+        );
+        crate::test::register_contract::<crate::DefaultEnvironment>(
+            accounts.django,
+            counter,
+        );
+        let code_hash = |account| crate::code_hash::<crate::DefaultEnvironment>(&account);
+        let call = || {
+            build_call::<crate::DefaultEnvironment>()
+                .call_type(Call::new().callee(accounts.bob))
+                .exec_input(ExecutionInput::new(Selector::new([0x00; 4])))
+                .returns::<()>()
+                .fire()
+        };
+        assert_eq!(code_hash(accounts.bob), code_hash(accounts.charlie));
+        assert_ne!(code_hash(accounts.bob), code_hash(accounts.django));
+        assert_eq!(code_hash(accounts.eve), Err(crate::Error::KeyNotFound));
+
+        // when
+        let terminated = call();
+
+        // then
+        assert_eq!(terminated, Ok(()));
+        assert_eq!(call(), Err(crate::Error::NotCallable));
+        assert_eq!(code_hash(accounts.bob), Err(crate::Error::KeyNotFound));
+        assert!(code_hash(accounts.charlie).is_ok());
+        Ok(())
+    })
+}
+
+/// The new code of an upgraded contract whose migration stores `true` at key `0`.
+///
+/// Traps if the migration is not pending.
+fn migrating(_input: &[u8]) {
+    crate::upgrade::ensure_pending_migration::<crate::DefaultEnvironment>()
+        .expect("migration must be pending");
+    crate::set_contract_storage(&0u32, &true);
+    let output: ink_primitives::MessageResult<u32> = Ok(7);
+    crate::return_value(crate::ReturnFlags::default(), &output)
+}
+
+/// A migration of the contract at `alice` which reverts with an error.
+fn failing_migration(_input: &[u8]) {
+    crate::upgrade::ensure_pending_migration::<crate::DefaultEnvironment>()
+        .expect("migration must be pending");
+    crate::set_contract_storage(&0u32, &true);
+    let output: ink_primitives::MessageResult<core::result::Result<u32, u8>> = Ok(Err(1));
+    crate::return_value(crate::ReturnFlags::new_with_reverted(true), &output)
+}
+
+#[test]
+fn set_code_hash_with_migration_restores_code_hash_if_migration_reverts() -> Result<()> {
+    use crate::call::{
+        ExecutionInput,
+        Selector,
+    };
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|accounts| {
+        // given
+        crate::test::register_contract::<crate::DefaultEnvironment>(
+            accounts.alice,
+            failing_migration,
+        );
+        let old_code_hash = crate::own_code_hash::<crate::DefaultEnvironment>()?;
+
+        // when
+        let reverted =
+            crate::upgrade::set_code_hash_with_migration::<
+                crate::DefaultEnvironment,
+                _,
+                core::result::Result<u32, u8>,
+            >(&[0x42; 32], ExecutionInput::new(Selector::new([0x00; 4])));
+
+        // then
+        assert_eq!(reverted, Err(crate::Error::CalleeReverted));
+        assert_eq!(
+            crate::own_code_hash::<crate::DefaultEnvironment>(),
+            Ok(old_code_hash)
+        );
+        assert_eq!(crate::get_contract_storage::<u32, bool>(&0), Ok(None));
+        assert_eq!(
+            crate::upgrade::ensure_pending_migration::<crate::DefaultEnvironment>(),
+            Err(crate::Error::MigrationNotPending)
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn set_code_hash_with_migration_runs_migration_once() -> Result<()> {
+    use crate::call::{
+        build_call,
+        Call,
+        ExecutionInput,
+        Selector,
+    };
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|accounts| {
+        // given
+        crate::test::register_contract::<crate::DefaultEnvironment>(
+            accounts.alice,
+            trapping,
+        );
+        let old_code_hash = crate::own_code_hash::<crate::DefaultEnvironment>()?;
+        let migrate = |code_hash| {
+            crate::upgrade::set_code_hash_with_migration::<
+                crate::DefaultEnvironment,
+                _,
+                u32,
+            >(&code_hash, ExecutionInput::new(Selector::new([0x00; 4])))
+        };
+
+        // when
+        let trapped = migrate([0x13; 32]);
+        let trapped_code_hash = crate::own_code_hash::<crate::DefaultEnvironment>()?;
+        crate::test::register_contract::<crate::DefaultEnvironment>(
+            accounts.alice,
+            migrating,
+        );
+        let migrated = migrate([0x42; 32]);
+
+        // then
+        assert_eq!(trapped, Err(crate::Error::CalleeTrapped));
+        assert_eq!(trapped_code_hash, old_code_hash);
+        assert_eq!(migrated, Ok(7));
+        assert_eq!(
+            crate::own_code_hash::<crate::DefaultEnvironment>(),
+            Ok(ink_primitives::Hash::from([0x42; 32]))
+        );
+        assert_eq!(crate::get_contract_storage::<u32, bool>(&0), Ok(Some(true)));
+        assert_eq!(
+            crate::upgrade::ensure_pending_migration::<crate::DefaultEnvironment>(),
+            Err(crate::Error::MigrationNotPending)
+        );
+        let again = build_call::<crate::DefaultEnvironment>()
+            .call_type(Call::new().callee(accounts.alice))
+            .exec_input(ExecutionInput::new(Selector::new([0x00; 4])))
+            .returns::<ink_primitives::MessageResult<u32>>()
+            .fire();
+        assert_eq!(again, Err(crate::Error::CalleeTrapped));
+        Ok(())
+    })
+}
+
+#[test]
+fn set_code_hash_works() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|accounts| {
+        // given
+        let implementation = ink_primitives::Hash::from([0x42; 32]);
+        assert_eq!(
+            crate::code_hash::<crate::DefaultEnvironment>(&accounts.bob),
+            Err(crate::Error::KeyNotFound)
+        );
+        assert_eq!(
+            crate::own_code_hash::<crate::DefaultEnvironment>(),
+            Err(crate::Error::KeyNotFound)
+        );
+
+        // when
+        crate::test::set_code_hash::<crate::DefaultEnvironment>(
+            accounts.bob,
+            implementation,
+        );
+        crate::test::set_callee::<crate::DefaultEnvironment>(accounts.bob);
+
+        // then
+        assert_eq!(
+            crate::code_hash::<crate::DefaultEnvironment>(&accounts.bob),
+            Ok(implementation)
+        );
+        assert_eq!(
+            crate::own_code_hash::<crate::DefaultEnvironment>(),
+            Ok(implementation)
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn storage_deposit_info_tracks_storage() -> Result<()> {
+    use crate::StorageDepositInfo;
+
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        // given
+        assert_eq!(crate::storage_deposit_info(), StorageDepositInfo::default());
+
+        // when
+        crate::set_contract_storage(&1u32, &[0u8; 10]);
+        crate::set_contract_storage(&2u32, &[0u8; 4]);
+        crate::set_contract_storage(&1u32, &[0u8; 6]);
+        crate::take_contract_storage::<u32, [u8; 4]>(&2u32)?;
+        crate::clear_contract_storage(&3u32);
+
+        // then
+        let info = crate::storage_deposit_info();
+        assert_eq!(
+            info,
+            StorageDepositInfo {
+                bytes_added: 14,
+                bytes_removed: 8,
+                items_added: 2,
+                items_removed: 1,
+            }
+        );
+        assert_eq!(info.charge(10u128, 100u128), 160);
+        assert_eq!(info.refund(10u128, 100u128), 0);
+        crate::clear_contract_storage(&1u32);
+        let info = crate::storage_deposit_info();
+        assert_eq!(info.charge(10u128, 100u128), 0);
+        assert_eq!(info.refund(10u128, 100u128), 0);
+        Ok(())
+    })
+}
+
+#[test]
+fn storage_deposits_are_charged_from_origin() -> Result<()> {
+    use crate::{
+        call::{
+            build_call,
+            Call,
+            ExecutionInput,
+            Selector,
+        },
+        test,
+        DefaultEnvironment,
+    };
+
+    test::run_test::<DefaultEnvironment, _>(|accounts| {
+        // given
+        test::register_contract::<DefaultEnvironment>(accounts.bob, counter);
+        let call = |by: u32| {
+            build_call::<DefaultEnvironment>()
+                .call_type(Call::new().callee(accounts.bob))
+                .exec_input(ExecutionInput::new(Selector::new([0x00; 4])).push_arg(by))
+                .returns::<u32>()
+                .fire()
+        };
+        crate::set_contract_storage(&1u32, &[0u8; 10]);
+        assert_eq!(
+            test::storage_deposit::<DefaultEnvironment>(accounts.alice),
+            0
+        );
+
+        // when
+        test::set_storage_deposit_prices::<DefaultEnvironment>(10, 100);
+        call(0)?;
+        let reverted = test::storage_deposit::<DefaultEnvironment>(accounts.alice);
+        call(3)?;
+        crate::set_contract_storage(&2u32, &[0u8; 10]);
+
+        // then
+        assert_eq!(reverted, 0, "reverted calls must not charge deposits");
+        assert_eq!(
+            test::storage_deposit::<DefaultEnvironment>(accounts.alice),
+            340
+        );
+        assert_eq!(test::storage_deposit::<DefaultEnvironment>(accounts.bob), 0);
+
+        test::set_caller::<DefaultEnvironment>(accounts.charlie);
+        crate::clear_contract_storage(&1u32);
+        crate::set_contract_storage(&2u32, &[0u8; 5]);
+        assert_eq!(
+            test::storage_deposit::<DefaultEnvironment>(accounts.charlie),
+            0
+        );
+        assert_eq!(
+            test::storage_deposit::<DefaultEnvironment>(accounts.alice),
+            340
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn raw_contract_storage_works() -> Result<()> {
+    use crate::{
+        test,
+        DefaultEnvironment,
+    };
+
+    test::run_test::<DefaultEnvironment, _>(|_| {
+        // given
+        let contract = test::callee::<DefaultEnvironment>();
+        crate::set_contract_storage(&(1u32, 7u8), &0x0102_0304u32);
+        let rw_before = test::get_contract_storage_rw::<DefaultEnvironment>(&contract);
+
+        // when
+        let stored = test::get_contract_storage_raw(&(1u32, 7u8));
+        test::set_contract_storage_raw(&2u32, &[0xFF]);
+
+        // then
+        assert_eq!(stored, Some(vec![0x04, 0x03, 0x02, 0x01]));
+        assert_eq!(test::get_contract_storage_raw(&3u32), None);
+        assert_eq!(
+            test::get_contract_storage_rw::<DefaultEnvironment>(&contract),
+            rw_before
+        );
+        assert!(crate::get_contract_storage::<u32, u32>(&2u32).is_err());
+        assert_eq!(
+            crate::get_contract_storage::<u32, u8>(&2u32),
+            Ok(Some(0xFF))
+        );
+        assert_eq!(
+            test::count_used_storage_cells::<DefaultEnvironment>(&contract),
+            Ok(2)
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn contract_storage_decodes_only_the_stored_bytes() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        // given
+        crate::set_contract_storage(&1u32, &0x07u8);
+
+        // then
+        assert!(crate::get_contract_storage::<u32, u16>(&1u32).is_err());
+        assert!(crate::take_contract_storage::<u32, u16>(&1u32).is_err());
+        assert_eq!(crate::get_contract_storage::<u32, u8>(&1u32), Ok(None));
+        assert_eq!(
+            crate::storage_deposit_info().bytes_removed,
+            1,
+            "the length of the taken value is recorded"
+        );
+        Ok(())
+    })
+}
@@ -28,6 +28,11 @@ impl StaticBuffer {
             buffer: [0; Self::CAPACITY],
         }
     }
+
+    /// Returns the entire static buffer as a slice.
+    pub const fn as_mut_slice(&mut self) -> &mut [u8] {
+        &mut self.buffer
+    }
 }
 
 impl core::ops::Index<core::ops::RangeFull> for StaticBuffer {
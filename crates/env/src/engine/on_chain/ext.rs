@@ -76,6 +76,8 @@ define_error_codes! {
      /// The call to `debug_message` had no effect because debug message
     /// recording was disabled.
     LoggingDisabled = 9,
+    /// The call dispatched by `call_runtime` was executed but returned an error.
+    CallRuntimeFailed = 10,
     /// ECDSA public key recovery failed. Most probably wrong recovery id or signature.
     EcdsaRecoveryFailed = 11,
 }
@@ -338,6 +340,11 @@ mod sys {
 
         pub fn terminate(beneficiary_ptr: Ptr32<[u8]>) -> !;
 
+        // Like `seal0::gas_left` but writes the SCALE encoded `Weight` left,
+        // including the `proof_size`.
+        #[link_name = "gas_left"]
+        pub fn weight_left(output_ptr: Ptr32Mut<[u8]>, output_len_ptr: Ptr32Mut<u32>);
+
         pub fn call(
             flags: u32,
             callee_ptr: Ptr32<[u8]>,
@@ -411,6 +418,30 @@ mod sys {
             out_ptr: Ptr32Mut<[u8]>,
             out_len_ptr: Ptr32Mut<u32>,
         ) -> ReturnCode;
+
+        /// Dispatches the SCALE encoded runtime call with the contract as origin.
+        ///
+        /// # Parameters
+        ///
+        /// - `call_ptr`: pointer into the linear memory where the encoded call is placed.
+        /// - `call_len`: the length of the encoded call in bytes.
+        ///
+        /// # Errors
+        ///
+        /// `ReturnCode::CallRuntimeFailed`
+        #[cfg(feature = "call-runtime")]
+        pub fn call_runtime(call_ptr: Ptr32<[u8]>, call_len: u32) -> ReturnCode;
+
+        /// Writes the SCALE encoded account which signed the extrinsic executing the
+        /// call stack.
+        ///
+        /// # Parameters
+        ///
+        /// - `output_ptr`: pointer to the linear memory where the account is written to.
+        /// - `output_len_ptr`: in-out pointer into linear memory where the buffer length
+        ///   is read from and the account length is written to.
+        #[cfg(feature = "unstable-interface")]
+        pub fn origin(output_ptr: Ptr32Mut<[u8]>, output_len_ptr: Ptr32Mut<u32>);
     }
 
     #[link(wasm_import_module = "seal2")]
@@ -432,6 +463,30 @@ mod sys {
             value_ptr: Ptr32<[u8]>,
             value_len: u32,
         ) -> ReturnCode;
+
+        /// Like `seal0::instantiate` but with separate `ref_time` and `proof_size`
+        /// weight limits and a limit of the storage deposit charged for the instantiation.
+        ///
+        /// # Parameters
+        ///
+        /// - `deposit_limit_ptr`: pointer into the linear memory where the encoded
+        ///   storage deposit limit is placed. `SENTINEL` means no limit.
+        #[link_name = "instantiate"]
+        pub fn instantiate_v2(
+            code_hash_ptr: Ptr32<[u8]>,
+            ref_time_limit: u64,
+            proof_size_limit: u64,
+            deposit_limit_ptr: Ptr32<[u8]>,
+            value_ptr: Ptr32<[u8]>,
+            input_ptr: Ptr32<[u8]>,
+            input_len: u32,
+            address_ptr: Ptr32Mut<[u8]>,
+            address_len_ptr: Ptr32Mut<u32>,
+            output_ptr: Ptr32Mut<[u8]>,
+            output_len_ptr: Ptr32Mut<u32>,
+            salt_ptr: Ptr32<[u8]>,
+            salt_len: u32,
+        ) -> ReturnCode;
     }
 }
 
@@ -443,9 +498,11 @@ fn extract_from_slice(output: &mut &mut [u8], new_len: usize) {
 }
 
 #[inline(always)]
+#[allow(clippy::too_many_arguments)]
 pub fn instantiate(
     code_hash: &[u8],
     gas_limit: u64,
+    storage_deposit_limit: Option<&[u8]>,
     endowment: &[u8],
     input: &[u8],
     out_address: &mut &mut [u8],
@@ -454,8 +511,8 @@ pub fn instantiate(
 ) -> Result {
     let mut address_len = out_address.len() as u32;
     let mut return_value_len = out_return_value.len() as u32;
-    let ret_code = {
-        unsafe {
+    let ret_code = match storage_deposit_limit {
+        None => unsafe {
             sys::instantiate(
                 Ptr32::from_slice(code_hash),
                 gas_limit,
@@ -469,6 +526,27 @@ pub fn instantiate(
                 Ptr32::from_slice(salt),
                 salt.len() as u32,
             )
+        },
+        Some(storage_deposit_limit) => {
+            unsafe {
+                sys::instantiate_v2(
+                    Ptr32::from_slice(code_hash),
+                    gas_limit,
+                    // The `proof_size` limit is only relevant for parachains,
+                    // `0` means that it is not limited.
+                    0,
+                    Ptr32::from_slice(storage_deposit_limit),
+                    Ptr32::from_slice(endowment),
+                    Ptr32::from_slice(input),
+                    input.len() as u32,
+                    Ptr32Mut::from_slice(out_address),
+                    Ptr32Mut::from_ref(&mut address_len),
+                    Ptr32Mut::from_slice(out_return_value),
+                    Ptr32Mut::from_ref(&mut return_value_len),
+                    Ptr32::from_slice(salt),
+                    salt.len() as u32,
+                )
+            }
         }
     };
     extract_from_slice(out_address, address_len as usize);
@@ -609,6 +687,13 @@ pub fn storage_contains(key: &[u8]) -> Option<u32> {
     ret_code.into()
 }
 
+#[cfg(feature = "call-runtime")]
+pub fn call_runtime(call: &[u8]) -> Result {
+    let ret_code =
+        unsafe { sys::call_runtime(Ptr32::from_slice(call), call.len() as u32) };
+    ret_code.into()
+}
+
 pub fn terminate(beneficiary: &[u8]) -> ! {
     unsafe { sys::terminate(Ptr32::from_slice(beneficiary)) }
 }
@@ -679,10 +764,15 @@ impl_wrapper_for! {
     address,
     balance,
     gas_left,
+    weight_left,
     value_transferred,
     now,
     minimum_balance,
 }
+#[cfg(feature = "unstable-interface")]
+impl_wrapper_for! {
+    origin,
+}
 
 #[inline(always)]
 pub fn weight_to_fee(gas: u64, output: &mut &mut [u8]) {
@@ -25,6 +25,7 @@ use crate::{
         CreateParams,
         DelegateCall,
     },
+    engine::OnInstance,
     hash::{
         Blake2x128,
         Blake2x256,
@@ -44,7 +45,9 @@ use crate::{
     FromLittleEndian,
     Result,
     ReturnFlags,
+    StorageDepositInfo,
     TypedEnvBackend,
+    Weight,
 };
 use ink_storage_traits::Storable;
 
@@ -109,6 +112,7 @@ impl From<ext::Error> for Error {
             ext::Error::CodeNotFound => Self::CodeNotFound,
             ext::Error::NotCallable => Self::NotCallable,
             ext::Error::LoggingDisabled => Self::LoggingDisabled,
+            ext::Error::CallRuntimeFailed => Self::CallRuntimeFailed,
             ext::Error::EcdsaRecoveryFailed => Self::EcdsaRecoveryFailed,
         }
     }
@@ -180,6 +184,33 @@ impl EnvInstance {
         ScopedBuffer::from(&mut self.buffer[..])
     }
 
+    /// Splits the input of the call off the start of the static buffer.
+    ///
+    /// Returns whether this call split it off, in which case the space has to be
+    /// given back via `rejoin_input`. Nested calls reuse the already lent input.
+    fn split_input(&mut self) -> (&'static [u8], bool) {
+        if let Some(input) = self.input {
+            return (input, false)
+        }
+        let buffer = core::mem::take(&mut self.buffer);
+        let mut output = &mut buffer[..];
+        ext::input(&mut output);
+        let len = output.len();
+        let (input, rest) = buffer.split_at_mut(len);
+        self.buffer = rest;
+        self.input = Some(input);
+        (input, true)
+    }
+
+    /// Gives the space of the lent input back to the encode & decode buffer.
+    fn rejoin_input(&mut self) {
+        self.input = None;
+        // SAFETY: The input is no longer borrowed and `BUFFER` is only ever
+        //         accessed through the instance.
+        self.buffer =
+            unsafe { (&mut *core::ptr::addr_of_mut!(super::BUFFER)).as_mut_slice() };
+    }
+
     /// Returns the contract property value into the given result buffer.
     ///
     /// # Note
@@ -231,7 +262,10 @@ impl EnvBackend for EnvInstance {
         let mut buffer = self.scoped_buffer();
         let key = buffer.take_encoded(key);
         let value = buffer.take_storable_encoded(value);
-        ext::set_storage(key, value)
+        let new_len = value.len() as u32;
+        let old_len = ext::set_storage(key, value);
+        self.storage_deposit.record(old_len, Some(new_len));
+        old_len
     }
 
     fn get_contract_storage<K, R>(&mut self, key: &K) -> Result<Option<R>>
@@ -264,8 +298,10 @@ impl EnvBackend for EnvInstance {
             Err(ExtError::KeyNotFound) => return Ok(None),
             Err(_) => panic!("encountered unexpected error"),
         }
-        let decoded = Storable::decode(&mut &output[..])?;
-        Ok(Some(decoded))
+        let old_len = output.len() as u32;
+        let decoded = Storable::decode(&mut &output[..]);
+        self.storage_deposit.record(Some(old_len), None);
+        Ok(Some(decoded?))
     }
 
     fn contains_contract_storage<K>(&mut self, key: &K) -> Option<u32>
@@ -283,7 +319,13 @@ impl EnvBackend for EnvInstance {
     {
         let mut buffer = self.scoped_buffer();
         let key = buffer.take_encoded(key);
-        ext::clear_storage(key)
+        let old_len = ext::clear_storage(key);
+        self.storage_deposit.record(old_len, None);
+        old_len
+    }
+
+    fn storage_deposit_info(&mut self) -> StorageDepositInfo {
+        self.storage_deposit
     }
 
     fn decode_input<T>(&mut self) -> Result<T>
@@ -293,6 +335,18 @@ impl EnvBackend for EnvInstance {
         self.get_property::<T>(ext::input)
     }
 
+    fn with_input_bytes<F, R>(f: F) -> R
+    where
+        F: FnOnce(&[u8]) -> R,
+    {
+        let (input, split) = Self::on_instance(Self::split_input);
+        let result = f(input);
+        if split {
+            Self::on_instance(Self::rejoin_input);
+        }
+        result
+    }
+
     fn return_value<R>(&mut self, flags: ReturnFlags, return_value: &R) -> !
     where
         R: scale::Encode,
@@ -381,6 +435,11 @@ impl TypedEnvBackend for EnvInstance {
         self.get_property_little_endian::<u64>(ext::gas_left)
     }
 
+    fn weight_left<E: Environment>(&mut self) -> Weight {
+        self.get_property::<Weight>(ext::weight_left)
+            .expect("encountered invalid weight left")
+    }
+
     fn block_timestamp<E: Environment>(&mut self) -> E::Timestamp {
         self.get_property_little_endian::<E::Timestamp>(ext::now)
     }
@@ -412,42 +471,41 @@ impl TypedEnvBackend for EnvInstance {
         ext::deposit_event(enc_topics, enc_data);
     }
 
-    fn invoke_contract<E, Args, R>(
-        &mut self,
-        params: &CallParams<E, Call<E>, Args, R>,
-    ) -> Result<R>
+    fn invoke_contract<E, Args, R>(params: &CallParams<E, Call<E>, Args, R>) -> Result<R>
     where
         E: Environment,
         Args: scale::Encode,
         R: scale::Decode,
     {
-        let mut scope = self.scoped_buffer();
-        let gas_limit = params.gas_limit();
-        let enc_callee = scope.take_encoded(params.callee());
-        let enc_transferred_value = scope.take_encoded(params.transferred_value());
-        let call_flags = params.call_flags();
-        let enc_input = if !call_flags.forward_input() && !call_flags.clone_input() {
-            scope.take_encoded(params.exec_input())
-        } else {
-            &mut []
-        };
-        let output = &mut scope.take_rest();
-        let flags = params.call_flags().into_u32();
-        let call_result = ext::call(
-            flags,
-            enc_callee,
-            gas_limit,
-            enc_transferred_value,
-            enc_input,
-            output,
-        );
-        match call_result {
-            Ok(()) | Err(ext::Error::CalleeReverted) => {
-                let decoded = scale::Decode::decode(&mut &output[..])?;
-                Ok(decoded)
+        <Self as OnInstance>::on_instance(|instance| {
+            let mut scope = instance.scoped_buffer();
+            let gas_limit = params.gas_limit();
+            let enc_callee = scope.take_encoded(params.callee());
+            let enc_transferred_value = scope.take_encoded(params.transferred_value());
+            let call_flags = params.call_flags();
+            let enc_input = if !call_flags.forward_input() && !call_flags.clone_input() {
+                scope.take_encoded(params.exec_input())
+            } else {
+                &mut []
+            };
+            let output = &mut scope.take_rest();
+            let flags = params.call_flags().into_u32();
+            let call_result = ext::call(
+                flags,
+                enc_callee,
+                gas_limit,
+                enc_transferred_value,
+                enc_input,
+                output,
+            );
+            match call_result {
+                Ok(()) | Err(ext::Error::CalleeReverted) => {
+                    let decoded = scale::Decode::decode(&mut &output[..])?;
+                    Ok(decoded)
+                }
+                Err(actual_error) => Err(actual_error.into()),
             }
-            Err(actual_error) => Err(actual_error.into()),
-        }
+        })
     }
 
     fn invoke_contract_delegate<E, Args, R>(
@@ -491,6 +549,9 @@ impl TypedEnvBackend for EnvInstance {
         let mut scoped = self.scoped_buffer();
         let gas_limit = params.gas_limit();
         let enc_code_hash = scoped.take_encoded(params.code_hash());
+        let enc_storage_deposit_limit = params
+            .storage_deposit_limit()
+            .map(|limit| &*scoped.take_encoded(limit));
         let enc_endowment = scoped.take_encoded(params.endowment());
         let enc_input = scoped.take_encoded(params.exec_input());
         // We support `AccountId` types with an encoding that requires up to
@@ -499,13 +560,12 @@ impl TypedEnvBackend for EnvInstance {
         let out_address = &mut scoped.take(1024);
         let salt = params.salt_bytes().as_ref();
         let out_return_value = &mut scoped.take_rest();
-        // We currently do nothing with the `out_return_value` buffer.
-        // This should change in the future but for that we need to add support
-        // for constructors that may return values.
-        // This is useful to support fallible constructors for example.
+        // We do nothing with the `out_return_value` buffer here, use
+        // `try_instantiate_contract` to receive the errors of fallible constructors.
         ext::instantiate(
             enc_code_hash,
             gas_limit,
+            enc_storage_deposit_limit,
             enc_endowment,
             enc_input,
             out_address,
@@ -516,6 +576,58 @@ impl TypedEnvBackend for EnvInstance {
         Ok(account_id)
     }
 
+    fn try_instantiate_contract<E, Args, Salt, C, ContractError>(
+        &mut self,
+        params: &CreateParams<E, Args, Salt, C>,
+    ) -> Result<core::result::Result<E::AccountId, ContractError>>
+    where
+        E: Environment,
+        Args: scale::Encode,
+        Salt: AsRef<[u8]>,
+        ContractError: scale::Decode,
+    {
+        let mut scoped = self.scoped_buffer();
+        let gas_limit = params.gas_limit();
+        let enc_code_hash = scoped.take_encoded(params.code_hash());
+        let enc_storage_deposit_limit = params
+            .storage_deposit_limit()
+            .map(|limit| &*scoped.take_encoded(limit));
+        let enc_endowment = scoped.take_encoded(params.endowment());
+        let enc_input = scoped.take_encoded(params.exec_input());
+        let out_address = &mut scoped.take(1024);
+        let salt = params.salt_bytes().as_ref();
+        let out_return_value = &mut scoped.take_rest();
+        let instantiated = ext::instantiate(
+            enc_code_hash,
+            gas_limit,
+            enc_storage_deposit_limit,
+            enc_endowment,
+            enc_input,
+            out_address,
+            out_return_value,
+            salt,
+        );
+        match instantiated {
+            Ok(()) => {
+                let account_id = scale::Decode::decode(&mut &out_address[..])?;
+                Ok(Ok(account_id))
+            }
+            Err(ext::Error::CalleeReverted) => {
+                // A fallible constructor reverts with its encoded error as output.
+                let output = <ink_primitives::ConstructorResult<
+                    core::result::Result<(), ContractError>,
+                > as scale::Decode>::decode(
+                    &mut &out_return_value[..]
+                )?;
+                match output {
+                    Ok(Err(contract_error)) => Ok(Err(contract_error)),
+                    Ok(Ok(())) | Err(_) => Err(Error::CalleeReverted),
+                }
+            }
+            Err(actual_error) => Err(actual_error.into()),
+        }
+    }
+
     fn terminate_contract<E>(&mut self, beneficiary: E::AccountId) -> !
     where
         E: Environment,
@@ -556,6 +668,14 @@ impl TypedEnvBackend for EnvInstance {
         ext::caller_is_origin()
     }
 
+    #[cfg(feature = "unstable-interface")]
+    fn origin<E>(&mut self) -> E::AccountId
+    where
+        E: Environment,
+    {
+        self.get_property_inplace::<E::AccountId>(ext::origin)
+    }
+
     fn code_hash<E>(&mut self, account_id: &E::AccountId) -> Result<E::Hash>
     where
         E: Environment,
@@ -579,4 +699,15 @@ impl TypedEnvBackend for EnvInstance {
         let hash = scale::Decode::decode(&mut &output[..])?;
         Ok(hash)
     }
+
+    #[cfg(feature = "call-runtime")]
+    fn call_runtime<E, Call>(&mut self, call: &Call) -> Result<()>
+    where
+        E: Environment,
+        Call: scale::Encode,
+    {
+        let mut scope = self.scoped_buffer();
+        let enc_call = scope.take_encoded(call);
+        ext::call_runtime(enc_call).map_err(Into::into)
+    }
 }
@@ -25,6 +25,10 @@ use self::{
     ext::Error,
 };
 use super::OnInstance;
+use crate::StorageDepositInfo;
+
+/// The static buffer backing the encode & decode buffer of the [`EnvInstance`].
+static mut BUFFER: StaticBuffer = StaticBuffer::new();
 
 /// The on-chain environment.
 pub struct EnvInstance {
@@ -37,7 +41,16 @@ pub struct EnvInstance {
     /// Please note that this is still an implementation detail and
     /// might change. Users should generally avoid storing too big values
     /// into single storage entries.
-    buffer: StaticBuffer,
+    ///
+    /// The input of the call is split off its start while it is lent, see `input`.
+    buffer: &'static mut [u8],
+    /// The input of the call while it is lent via `with_input_bytes`.
+    ///
+    /// It is read into the start of the static buffer and not overwritten until it
+    /// is given back, so that borrowed values can point into it.
+    input: Option<&'static [u8]>,
+    /// The storage added and removed by the current call so far.
+    storage_deposit: StorageDepositInfo,
 }
 
 impl OnInstance for EnvInstance {
@@ -46,7 +59,10 @@ impl OnInstance for EnvInstance {
         F: FnOnce(&mut Self) -> R,
     {
         static mut INSTANCE: EnvInstance = EnvInstance {
-            buffer: StaticBuffer::new(),
+            // SAFETY: `BUFFER` is only ever accessed through `INSTANCE`.
+            buffer: unsafe { (&mut *core::ptr::addr_of_mut!(BUFFER)).as_mut_slice() },
+            input: None,
+            storage_deposit: StorageDepositInfo::new(),
         };
         f(unsafe { &mut INSTANCE })
     }
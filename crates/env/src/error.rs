@@ -47,8 +47,17 @@ pub enum Error {
     /// The call to `debug_message` had no effect because debug message
     /// recording was disabled.
     LoggingDisabled,
+    /// The call dispatched by `call_runtime` was executed but returned an error.
+    CallRuntimeFailed,
     /// ECDSA pubkey recovery failed. Most probably wrong recovery id or signature.
     EcdsaRecoveryFailed,
+    /// The migration was not started by
+    /// [`set_code_hash_with_migration`](crate::upgrade::set_code_hash_with_migration)
+    /// or has already run.
+    MigrationNotPending,
+    /// The transfer would have brought the balance of the contract below the
+    /// minimum balance, see [`transfer_keep_alive`](crate::transfer_keep_alive).
+    BelowMinimumBalance,
 }
 
 /// A result of environmental operations.
@@ -72,14 +72,24 @@ extern crate ink_allocator;
 mod api;
 mod arithmetic;
 mod backend;
+pub mod budget;
 pub mod call;
 pub mod chain_extension;
+pub mod consensus;
 mod engine;
 mod error;
 pub mod hash;
+pub mod log;
+pub mod random;
+pub mod runtime_call;
+pub mod runtime_function;
+pub mod sig;
 #[doc(hidden)]
 pub mod topics;
 mod types;
+pub mod upgrade;
+#[cfg(feature = "xcm")]
+pub mod xcm;
 
 #[cfg(test)]
 mod tests;
@@ -98,17 +108,29 @@ pub use self::{
         CallFlags,
         ReturnFlags,
     },
+    consensus::{
+        block_author,
+        chain_id,
+        parent_hash,
+    },
     error::{
         Error,
         Result,
     },
+    random::{
+        random_insecure,
+        RandomnessError,
+    },
     topics::Topics,
     types::{
+        format_balance,
         DefaultEnvironment,
         Environment,
         FromLittleEndian,
         Gas,
         NoChainExtension,
+        StorageDepositInfo,
+        Weight,
     },
 };
 use ink_primitives::Clear;
@@ -0,0 +1,201 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Structured logging to the `debug_message` buffer.
+//!
+//! The macros of this module append a record with a [`Level`], a target and a
+//! formatted message to the `debug_message` buffer, e.g.
+//!
+//! ```
+//! # let (amount, caller) = (100u128, [0x01u8; 4]);
+//! ink_env::log::info!(target: "auction", "bid {} from {:?}", amount, caller);
+//! // Without a target, the path of the module is used as target.
+//! ink_env::log::warn!("auction ends soon");
+//! ```
+//!
+//! Each record is written as a single line of the form `[LEVEL target] message`,
+//! with line breaks of the message escaped as `\n`. `ink_e2e` decodes the records
+//! of dry runs and forwards them to the `log` crate, so that they can be filtered
+//! by level and target like any other log output of the tests.
+//!
+//! # Note
+//!
+//! Like [`debug_println!`](crate::debug_println) the macros only emit records if
+//! the `ink-debug` or `std` feature is enabled, and the contracts pallet only
+//! records them for calls performed via RPC.
+
+/// The level of a log record, ordered by decreasing severity.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    /// Errors the contract can not recover from.
+    Error,
+    /// Unexpected but recoverable situations.
+    Warn,
+    /// Information about the progress of the contract.
+    Info,
+    /// Information helpful when debugging the contract.
+    Debug,
+    /// Very detailed information about the execution of the contract.
+    Trace,
+}
+
+impl Level {
+    /// Returns the name of the level as written to the records.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Error => "ERROR",
+            Self::Warn => "WARN",
+            Self::Info => "INFO",
+            Self::Debug => "DEBUG",
+            Self::Trace => "TRACE",
+        }
+    }
+
+    /// Returns the level with the given `name` as written to the records.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "ERROR" => Some(Self::Error),
+            "WARN" => Some(Self::Warn),
+            "INFO" => Some(Self::Info),
+            "DEBUG" => Some(Self::Debug),
+            "TRACE" => Some(Self::Trace),
+            _ => None,
+        }
+    }
+}
+
+impl core::fmt::Display for Level {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Appends the record of the `message` with the given `level` and `target` to the
+/// `debug_message` buffer.
+///
+/// Use the macros of this module instead, which compile to nothing if debug
+/// messages are disabled.
+pub fn log(level: Level, target: &str, message: &str) {
+    crate::debug_message(&ink_prelude::format!(
+        "[{} {}] {}\n",
+        level,
+        target,
+        message.replace('\n', "\\n"),
+    ))
+}
+
+#[cfg(any(feature = "ink-debug", feature = "std"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ink_log {
+    ($level:expr, target: $target:expr, $($arg:tt)+) => (
+        $crate::log::log($level, $target, &$crate::format!($($arg)+))
+    );
+    ($level:expr, $($arg:tt)+) => (
+        $crate::__ink_log!($level, target: ::core::module_path!(), $($arg)+)
+    );
+}
+
+#[cfg(not(any(feature = "ink-debug", feature = "std")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ink_log {
+    ($($arg:tt)*) => {};
+}
+
+/// Logs a message at the [`Level::Error`], see the [module documentation](self).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ink_log_error {
+    ($($arg:tt)+) => ($crate::__ink_log!($crate::log::Level::Error, $($arg)+));
+}
+
+/// Logs a message at the [`Level::Warn`], see the [module documentation](self).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ink_log_warn {
+    ($($arg:tt)+) => ($crate::__ink_log!($crate::log::Level::Warn, $($arg)+));
+}
+
+/// Logs a message at the [`Level::Info`], see the [module documentation](self).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ink_log_info {
+    ($($arg:tt)+) => ($crate::__ink_log!($crate::log::Level::Info, $($arg)+));
+}
+
+/// Logs a message at the [`Level::Debug`], see the [module documentation](self).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ink_log_debug {
+    ($($arg:tt)+) => ($crate::__ink_log!($crate::log::Level::Debug, $($arg)+));
+}
+
+/// Logs a message at the [`Level::Trace`], see the [module documentation](self).
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ink_log_trace {
+    ($($arg:tt)+) => ($crate::__ink_log!($crate::log::Level::Trace, $($arg)+));
+}
+
+#[doc(inline)]
+pub use crate::{
+    __ink_log_debug as debug,
+    __ink_log_error as error,
+    __ink_log_info as info,
+    __ink_log_trace as trace,
+    __ink_log_warn as warn,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_roundtrip() {
+        for level in [
+            Level::Error,
+            Level::Warn,
+            Level::Info,
+            Level::Debug,
+            Level::Trace,
+        ] {
+            assert_eq!(Level::from_name(level.as_str()), Some(level));
+        }
+        assert_eq!(Level::from_name("info"), None);
+        assert!(Level::Error < Level::Trace);
+    }
+
+    #[test]
+    fn macros_record_structured_messages() {
+        crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+            info!(target: "auction", "bid {} from {:?}", 100, [0x01u8; 4]);
+            warn!("auction ends soon");
+            error!(target: "auction", "multi\nline");
+            let messages = crate::test::recorded_debug_messages()
+                .into_iter()
+                .collect::<Vec<_>>();
+            assert_eq!(
+                messages,
+                [
+                    "[INFO auction] bid 100 from [1, 1, 1, 1]\n",
+                    "[WARN ink_env::log::tests] auction ends soon\n",
+                    "[ERROR auction] multi\\nline\n",
+                ]
+            );
+            Ok(())
+        })
+        .unwrap()
+    }
+}
@@ -0,0 +1,189 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Randomness provided by the runtime, tiered by its security.
+//!
+//! The contracts pallet does not expose randomness to contracts. Runtimes provide
+//! it instead as the [`RuntimeFunction`]s [`RandomInsecure`] and [`RandomVrf`],
+//! which return [`RandomnessError::NotSupported`] unless the runtime of the
+//! environment provides them.
+//!
+//! The randomness comes in two tiers:
+//!
+//! - [`random_insecure`] returns [`InsecureRandomness`], e.g. of the
+//!   `pallet-insecure-randomness-collective-flip`. Block producers can predict and
+//!   influence it, so it must not decide about anything of value. It is still
+//!   preferable over randomness derived from the block timestamp, which the block
+//!   producer chooses freely.
+//! - [`SecureRandomness`] is implemented by sources which block producers can
+//!   neither predict nor influence, such as VRF outputs of the block production.
+//!   [`VrfRandomness`] implements it via [`RandomVrf`].
+//!
+//! [`RandomInsecure`]: RuntimeFunction::RandomInsecure
+//! [`RandomVrf`]: RuntimeFunction::RandomVrf
+
+use crate::{
+    chain_extension::FromStatusCode,
+    runtime_function::{
+        self,
+        RuntimeFunction,
+        RuntimeFunctionError,
+    },
+    Environment,
+};
+
+pub use crate::runtime_function::errors::RandomnessError;
+
+impl FromStatusCode for RandomnessError {
+    fn from_status_code(status_code: u32) -> Result<(), Self> {
+        match status_code {
+            0 => Ok(()),
+            _ => Err(Self::NotSupported),
+        }
+    }
+}
+
+impl RuntimeFunctionError for RandomnessError {
+    const NOT_SUPPORTED: Self = Self::NotSupported;
+}
+
+/// Randomness which block producers can predict and influence.
+///
+/// See the [module documentation](self) for when it is fine to use.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+pub struct InsecureRandomness<Hash, BlockNumber> {
+    output: Hash,
+    known_since: BlockNumber,
+}
+
+impl<Hash, BlockNumber> InsecureRandomness<Hash, BlockNumber> {
+    /// Returns the random output.
+    pub fn output(&self) -> &Hash {
+        &self.output
+    }
+
+    /// Returns the earliest block number at which the output was known.
+    ///
+    /// The output must not be used to decide about anything that was committed
+    /// to before this block.
+    pub fn known_since(&self) -> &BlockNumber {
+        &self.known_since
+    }
+}
+
+/// Returns randomness for the given `subject` which block producers can predict
+/// and influence.
+///
+/// # Errors
+///
+/// If the runtime does not provide [`RuntimeFunction::RandomInsecure`].
+pub fn random_insecure<E>(
+    subject: &[u8],
+) -> Result<InsecureRandomness<E::Hash, E::BlockNumber>, RandomnessError>
+where
+    E: Environment,
+{
+    runtime_function::call::<E, _, _, _>(RuntimeFunction::RandomInsecure, &subject)
+}
+
+/// A source of randomness which block producers can neither predict nor influence.
+///
+/// Chains offering such randomness via their own chain extension can implement
+/// the trait for it, so that contracts stay generic over the source.
+pub trait SecureRandomness<E>
+where
+    E: Environment,
+{
+    /// The error returned if no randomness is available.
+    type Error;
+
+    /// Returns the randomness for the given `subject`.
+    fn random(subject: &[u8]) -> Result<E::Hash, Self::Error>;
+}
+
+/// The [`SecureRandomness`] of runtimes providing [`RuntimeFunction::RandomVrf`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum VrfRandomness {}
+
+impl<E> SecureRandomness<E> for VrfRandomness
+where
+    E: Environment,
+{
+    type Error = RandomnessError;
+
+    fn random(subject: &[u8]) -> Result<E::Hash, Self::Error> {
+        runtime_function::call::<E, _, _, _>(RuntimeFunction::RandomVrf, &subject)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        runtime_function::TestEnvironment,
+        test,
+        DefaultEnvironment,
+    };
+
+    #[test]
+    fn randomness_functions_work() {
+        test::run_test::<DefaultEnvironment, _>(|_| {
+            TestEnvironment::register_fn(RuntimeFunction::RandomInsecure, |input| {
+                assert_eq!(input, &scale::Encode::encode(&b"dice"[..])[..]);
+                scale::Encode::encode(&([0x01u8; 32], 42u32))
+            });
+            TestEnvironment::register_fn(RuntimeFunction::RandomVrf, |_| {
+                scale::Encode::encode(&[0x02u8; 32])
+            });
+
+            let insecure = random_insecure::<TestEnvironment>(b"dice").unwrap();
+            assert_eq!(insecure.output(), &[0x01; 32].into());
+            assert_eq!(insecure.known_since(), &42);
+            assert_eq!(
+                <VrfRandomness as SecureRandomness<TestEnvironment>>::random(b"dice"),
+                Ok([0x02; 32].into())
+            );
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn unsupported_randomness_functions_fail() {
+        test::run_test::<DefaultEnvironment, _>(|_| {
+            // The default environment declares no function IDs.
+            assert_eq!(
+                random_insecure::<DefaultEnvironment>(b"dice"),
+                Err(RandomnessError::NotSupported)
+            );
+            assert_eq!(
+                <VrfRandomness as SecureRandomness<DefaultEnvironment>>::random(b"dice"),
+                Err(RandomnessError::NotSupported)
+            );
+
+            TestEnvironment::register_failing(RuntimeFunction::RandomInsecure, 1);
+            TestEnvironment::register_failing(RuntimeFunction::RandomVrf, 1);
+            assert_eq!(
+                random_insecure::<TestEnvironment>(b"dice"),
+                Err(RandomnessError::NotSupported)
+            );
+            assert_eq!(
+                <VrfRandomness as SecureRandomness<TestEnvironment>>::random(b"dice"),
+                Err(RandomnessError::NotSupported)
+            );
+            Ok(())
+        })
+        .unwrap()
+    }
+}
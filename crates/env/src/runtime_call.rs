@@ -0,0 +1,138 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Typed encodings of runtime calls dispatched via [`call_runtime`](crate::call_runtime).
+//!
+//! A `RuntimeCall` is encoded as the index of the pallet in the runtime, followed by
+//! the index of the call in the pallet and the encoded arguments of the call. The
+//! indices can be looked up in the metadata of the runtime.
+//!
+//! Only [`RuntimeCall`]s can be dispatched. Contracts mirror the calls of the pallets
+//! they dispatch by deriving `ink::runtime_call::RuntimeCall` for an enum per pallet,
+//! or build single calls with [`PalletCall`].
+//!
+//! # Note
+//!
+//! The encodings are available without the `call-runtime` crate feature, so that
+//! they can be tested off-chain. Dispatching them via
+//! [`call_runtime`](crate::call_runtime) requires the feature.
+
+/// Implemented by types encoding as the `RuntimeCall` of the runtime.
+///
+/// # Note
+///
+/// Implement this via `#[derive(ink::runtime_call::RuntimeCall)]`, which encodes the
+/// variants of an enum as the calls of a pallet with the indices declared for the
+/// pallet and each call.
+pub trait RuntimeCall: scale::Encode {}
+
+/// A call of the pallet at `pallet_index` with the call index `call_index`.
+///
+/// Encodes the same as the corresponding variant of the `RuntimeCall` of the runtime.
+///
+/// # Example
+///
+/// ```
+/// # use ink_env::runtime_call::PalletCall;
+/// # type AccountId = [u8; 32];
+/// // `Balances::transfer_keep_alive(dest, value)` of a runtime which has the
+/// // `Balances` pallet at index 4.
+/// let dest: (u8, AccountId) = (0, [0x01; 32]);
+/// let value = scale::Compact(100u128);
+/// let call = PalletCall::new(4, 3, (dest, value));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PalletCall<Args> {
+    pallet_index: u8,
+    call_index: u8,
+    args: Args,
+}
+
+impl<Args> PalletCall<Args> {
+    /// Creates the call with the given indices and `args`.
+    pub fn new(pallet_index: u8, call_index: u8, args: Args) -> Self {
+        Self {
+            pallet_index,
+            call_index,
+            args,
+        }
+    }
+
+    /// Returns the index of the pallet in the runtime.
+    pub fn pallet_index(&self) -> u8 {
+        self.pallet_index
+    }
+
+    /// Returns the index of the call in the pallet.
+    pub fn call_index(&self) -> u8 {
+        self.call_index
+    }
+
+    /// Returns the arguments of the call.
+    pub fn args(&self) -> &Args {
+        &self.args
+    }
+}
+
+impl<Args> scale::Encode for PalletCall<Args>
+where
+    Args: scale::Encode,
+{
+    fn size_hint(&self) -> usize {
+        2 + self.args.size_hint()
+    }
+
+    fn encode_to<O: scale::Output + ?Sized>(&self, dest: &mut O) {
+        dest.push_byte(self.pallet_index);
+        dest.push_byte(self.call_index);
+        self.args.encode_to(dest);
+    }
+}
+
+impl<Args> RuntimeCall for PalletCall<Args> where Args: scale::Encode {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scale::Encode as _;
+
+    #[test]
+    fn pallet_call_encoding_works() {
+        let call = PalletCall::new(4, 3, (0u8, scale::Compact(100u128)));
+        assert_eq!(
+            call.encode(),
+            (4u8, 3u8, 0u8, scale::Compact(100u128)).encode()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "call-runtime")]
+    fn call_runtime_records_calls() {
+        use crate::{
+            test,
+            DefaultEnvironment,
+        };
+
+        test::run_test::<DefaultEnvironment, _>(|_| {
+            let call = PalletCall::new(4, 3, 42u32);
+            assert_eq!(crate::call_runtime::<DefaultEnvironment, _>(&call), Ok(()));
+            assert_eq!(
+                test::recorded_runtime_calls().collect::<Vec<_>>(),
+                vec![call.encode()]
+            );
+            Ok(())
+        })
+        .unwrap()
+    }
+}
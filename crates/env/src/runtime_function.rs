@@ -0,0 +1,279 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Optional functions which runtimes provide via their chain extension.
+//!
+//! The contracts pallet has no host functions for e.g. the consensus information
+//! of [`block_author`](crate::block_author). Runtimes provide such functions via
+//! their chain extension instead, under function IDs of their own choosing. An
+//! [`Environment`] declares the IDs of the functions its runtime provides via
+//! [`Environment::runtime_function_id`].
+//!
+//! Calling a [`RuntimeFunction`] the environment declares no ID for returns
+//! [`RuntimeFunctionError::NOT_SUPPORTED`] without calling the chain extension, so
+//! that contracts degrade gracefully on runtimes which do not provide the function
+//! or have no chain extension at all. Otherwise the chain extension function is
+//! called with the SCALE encoded input of the runtime function, and returns:
+//!
+//! - status code `0` and the SCALE encoded output on success.
+//! - any other status code on failure, which is mapped to the error of the runtime
+//!   function via [`FromStatusCode`].
+
+use crate::{
+    chain_extension::{
+        ChainExtensionMethod,
+        FromStatusCode,
+    },
+    Environment,
+};
+
+/// The optional functions of `ink_env` which runtimes provide via their chain
+/// extension.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RuntimeFunction {
+    /// Takes no input and returns the SCALE encoded `AccountId` of the author of the
+    /// current block.
+    BlockAuthor,
+    /// Takes no input and returns the SCALE encoded `Hash` of the parent of the
+    /// current block.
+    ParentHash,
+    /// Takes no input and returns the SCALE encoded `u64` identifier of the chain.
+    ChainId,
+    /// Takes the SCALE encoded `VersionedMultiLocation` destination and
+    /// `VersionedXcm<()>` message, sends the message via `pallet-xcm` and returns
+    /// the SCALE encoded `XcmHash` of the sent message.
+    ///
+    /// Returns status code `1` if the message was not sent.
+    XcmSend,
+    /// Takes the SCALE encoded `VersionedXcm<()>` message and maximum `Weight`,
+    /// executes the message via `pallet-xcm` with the contract as origin and
+    /// returns nothing.
+    ///
+    /// Returns status code `1` if the message was not executed completely.
+    XcmExecute,
+    /// Takes the SCALE encoded subject and returns the SCALE encoded
+    /// `(Hash, BlockNumber)` of the
+    /// [`InsecureRandomness`](crate::random::InsecureRandomness) for it.
+    RandomInsecure,
+    /// Takes the SCALE encoded subject and returns the SCALE encoded `Hash` derived
+    /// from the VRF output of the block production.
+    RandomVrf,
+    /// Takes the SCALE encoded `(message, signature, public_key)` with a 64 bytes
+    /// Sr25519 signature and a 32 bytes public key and returns nothing.
+    ///
+    /// Returns status code `1` if the signature is invalid.
+    Sr25519Verify,
+    /// Takes the SCALE encoded `(message, signature, public_key)` with a 64 bytes
+    /// Ed25519 signature and a 32 bytes public key and returns nothing.
+    ///
+    /// Returns status code `1` if the signature is invalid.
+    Ed25519Verify,
+}
+
+/// The error types of the runtime functions, re-exported by the modules providing
+/// the functions.
+///
+/// The `scale` derives emit trivial numeric casts for the indices of the variants
+/// of fieldless enums.
+#[allow(trivial_numeric_casts)]
+pub(crate) mod errors {
+    /// The error returned if the runtime does not provide randomness.
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum RandomnessError {
+        /// The runtime does not support the requested function.
+        NotSupported,
+    }
+
+    /// The errors of verifying a signature.
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum SignatureError {
+        /// The signature does not match the message and public key.
+        InvalidSignature,
+        /// The signature or public key has an invalid length for the scheme.
+        InvalidLength,
+        /// The runtime does not support verifying signatures of the scheme.
+        NotSupported,
+    }
+
+    /// The errors of the XCM functions.
+    #[cfg(feature = "xcm")]
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum XcmError {
+        /// The runtime rejected the message, e.g. since it could not be routed or
+        /// its execution failed.
+        Rejected,
+        /// The runtime does not provide the requested function.
+        NotSupported,
+    }
+}
+
+/// The errors of runtime functions.
+pub trait RuntimeFunctionError: FromStatusCode {
+    /// The error returned if the runtime does not provide the function.
+    const NOT_SUPPORTED: Self;
+}
+
+/// The error of runtime functions which fail only if the runtime does not provide
+/// them.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct NotSupported;
+
+impl FromStatusCode for NotSupported {
+    fn from_status_code(status_code: u32) -> Result<(), Self> {
+        match status_code {
+            0 => Ok(()),
+            _ => Err(Self),
+        }
+    }
+}
+
+impl RuntimeFunctionError for NotSupported {
+    const NOT_SUPPORTED: Self = Self;
+}
+
+/// Calls the runtime `function` of the runtime of `E` with the given `input`.
+///
+/// # Errors
+///
+/// - If `E` declares no function ID for `function`.
+/// - If the chain extension function returns a status code other than `0`.
+pub fn call<E, I, O, Error>(function: RuntimeFunction, input: &I) -> Result<O, Error>
+where
+    E: Environment,
+    I: scale::Encode,
+    O: scale::Decode,
+    Error: RuntimeFunctionError,
+{
+    let func_id = E::runtime_function_id(function).ok_or(Error::NOT_SUPPORTED)?;
+    ChainExtensionMethod::build(func_id)
+        .input::<I>()
+        .output::<O>()
+        .handle_error_code::<Error>()
+        .call(input)
+}
+
+/// An environment declaring the function ID `0x0C00_0000` plus the index of the
+/// variant for every [`RuntimeFunction`].
+#[cfg(test)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TestEnvironment {}
+
+#[cfg(test)]
+impl Environment for TestEnvironment {
+    const MAX_EVENT_TOPICS: usize =
+        <crate::DefaultEnvironment as Environment>::MAX_EVENT_TOPICS;
+
+    type AccountId = <crate::DefaultEnvironment as Environment>::AccountId;
+    type Balance = <crate::DefaultEnvironment as Environment>::Balance;
+    type Hash = <crate::DefaultEnvironment as Environment>::Hash;
+    type Timestamp = <crate::DefaultEnvironment as Environment>::Timestamp;
+    type BlockNumber = <crate::DefaultEnvironment as Environment>::BlockNumber;
+    type ChainExtension = crate::NoChainExtension;
+
+    fn runtime_function_id(function: RuntimeFunction) -> Option<u32> {
+        Some(0x0C00_0000 + function as u32)
+    }
+}
+
+#[cfg(test)]
+impl TestEnvironment {
+    /// Registers `handler` as the chain extension function of `function`.
+    ///
+    /// The `handler` is given the SCALE encoded input of the runtime function and
+    /// returns its SCALE encoded output.
+    pub(crate) fn register_fn<F>(function: RuntimeFunction, handler: F)
+    where
+        F: FnMut(&[u8]) -> ink_prelude::vec::Vec<u8> + 'static,
+    {
+        let func_id = Self::runtime_function_id(function).unwrap();
+        crate::test::register_chain_extension_fn(func_id, handler)
+    }
+
+    /// Registers a chain extension function of `function` which fails with the
+    /// given `status_code`.
+    pub(crate) fn register_failing(function: RuntimeFunction, status_code: u32) {
+        /// A chain extension function failing with a fixed status code.
+        struct Failing {
+            func_id: u32,
+            status_code: u32,
+        }
+
+        impl crate::test::ChainExtension for Failing {
+            fn func_id(&self) -> u32 {
+                self.func_id
+            }
+
+            fn call(
+                &mut self,
+                _input: &[u8],
+                _output: &mut ink_prelude::vec::Vec<u8>,
+            ) -> u32 {
+                self.status_code
+            }
+        }
+
+        crate::test::register_chain_extension(Failing {
+            func_id: Self::runtime_function_id(function).unwrap(),
+            status_code,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test,
+        DefaultEnvironment,
+    };
+
+    #[test]
+    fn undeclared_functions_are_not_called() {
+        test::run_test::<DefaultEnvironment, _>(|_| {
+            // Calling an unregistered chain extension function would panic.
+            assert_eq!(
+                call::<DefaultEnvironment, (), u64, NotSupported>(
+                    RuntimeFunction::ChainId,
+                    &()
+                ),
+                Err(NotSupported)
+            );
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn declared_functions_are_called() {
+        test::run_test::<DefaultEnvironment, _>(|_| {
+            TestEnvironment::register_fn(RuntimeFunction::ChainId, |_| {
+                scale::Encode::encode(&42u64)
+            });
+            assert_eq!(
+                call::<TestEnvironment, (), u64, NotSupported>(
+                    RuntimeFunction::ChainId,
+                    &()
+                ),
+                Ok(42)
+            );
+            Ok(())
+        })
+        .unwrap()
+    }
+}
@@ -0,0 +1,367 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Verification of signatures of the common Substrate signature schemes.
+//!
+//! [`verify`] checks signatures of any [`Scheme`] with the same error type, e.g. to
+//! implement permits or meta-transactions:
+//!
+//! - ECDSA signatures are verified via the `ecdsa_recover` host function of the
+//!   contracts pallet. The message is hashed with Blake2x256 before signing, as
+//!   done by Substrate.
+//! - The contracts pallet does not expose Sr25519 and Ed25519 verification to
+//!   contracts. Runtimes provide it instead as the [`RuntimeFunction`]s
+//!   [`Sr25519Verify`] and [`Ed25519Verify`], which return
+//!   [`SignatureError::NotSupported`] unless the runtime of the environment
+//!   provides them.
+//!
+//! Wallets usually prefix messages before signing them, so that users can not be
+//! tricked into signing transactions. [`wrap_bytes`] and [`eth_message_hash`] apply
+//! the prefixes of the Polkadot{.js} extension and Ethereum wallets respectively.
+//!
+//! [`Sr25519Verify`]: RuntimeFunction::Sr25519Verify
+//! [`Ed25519Verify`]: RuntimeFunction::Ed25519Verify
+
+use crate::{
+    chain_extension::FromStatusCode,
+    hash::{
+        Blake2x256,
+        Keccak256,
+    },
+    runtime_function::{
+        self,
+        RuntimeFunction,
+        RuntimeFunctionError,
+    },
+    Environment,
+};
+use ink_prelude::vec::Vec;
+
+pub use crate::runtime_function::errors::SignatureError;
+
+/// The prefix applied by the Polkadot{.js} extension to signed raw bytes.
+const WRAP_BYTES_PREFIX: &[u8] = b"<Bytes>";
+
+/// The postfix applied by the Polkadot{.js} extension to signed raw bytes.
+const WRAP_BYTES_POSTFIX: &[u8] = b"</Bytes>";
+
+/// The prefix applied by Ethereum wallets to signed messages, followed by the
+/// decimal length of the message.
+const ETH_MESSAGE_PREFIX: &str = "\x19Ethereum Signed Message:\n";
+
+/// The signature schemes supported by [`verify`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Scheme {
+    /// ECDSA signatures over the secp256k1 curve.
+    ///
+    /// Takes 65 bytes signatures and 33 bytes compressed public keys.
+    Ecdsa,
+    /// Schnorr signatures over the Ristretto group.
+    ///
+    /// Takes 64 bytes signatures and 32 bytes public keys.
+    Sr25519,
+    /// EdDSA signatures over the Edwards25519 curve.
+    ///
+    /// Takes 64 bytes signatures and 32 bytes public keys.
+    Ed25519,
+}
+
+impl FromStatusCode for SignatureError {
+    fn from_status_code(status_code: u32) -> Result<(), Self> {
+        match status_code {
+            0 => Ok(()),
+            1 => Err(Self::InvalidSignature),
+            _ => Err(Self::NotSupported),
+        }
+    }
+}
+
+impl RuntimeFunctionError for SignatureError {
+    const NOT_SUPPORTED: Self = Self::NotSupported;
+}
+
+/// Verifies the `signature` of the `message` by the owner of `public_key`.
+///
+/// # Errors
+///
+/// - If the `signature` is invalid.
+/// - If the `signature` or `public_key` have an invalid length for the `scheme`.
+/// - If the runtime does not provide [`RuntimeFunction::Sr25519Verify`] or
+///   [`RuntimeFunction::Ed25519Verify`] respectively.
+pub fn verify<E>(
+    scheme: Scheme,
+    message: &[u8],
+    signature: &[u8],
+    public_key: &[u8],
+) -> Result<(), SignatureError>
+where
+    E: Environment,
+{
+    match scheme {
+        Scheme::Ecdsa => {
+            let mut message_hash = [0x00; 32];
+            crate::hash_bytes::<Blake2x256>(message, &mut message_hash);
+            verify_ecdsa_prehashed(
+                &message_hash,
+                signature
+                    .try_into()
+                    .map_err(|_| SignatureError::InvalidLength)?,
+                public_key
+                    .try_into()
+                    .map_err(|_| SignatureError::InvalidLength)?,
+            )
+        }
+        Scheme::Sr25519 => {
+            call::<E>(
+                RuntimeFunction::Sr25519Verify,
+                message,
+                signature,
+                public_key,
+            )
+        }
+        Scheme::Ed25519 => {
+            call::<E>(
+                RuntimeFunction::Ed25519Verify,
+                message,
+                signature,
+                public_key,
+            )
+        }
+    }
+}
+
+/// Verifies the ECDSA `signature` of the `message_hash` by the owner of `public_key`.
+///
+/// Use this for messages which are not hashed with Blake2x256, e.g. with the
+/// [`eth_message_hash`] of messages signed by Ethereum wallets.
+///
+/// # Errors
+///
+/// If the `signature` is invalid.
+pub fn verify_ecdsa_prehashed(
+    message_hash: &[u8; 32],
+    signature: &[u8; 65],
+    public_key: &[u8; 33],
+) -> Result<(), SignatureError> {
+    let mut recovered = [0x00; 33];
+    crate::ecdsa_recover(signature, message_hash, &mut recovered)
+        .map_err(|_| SignatureError::InvalidSignature)?;
+    if &recovered != public_key {
+        return Err(SignatureError::InvalidSignature)
+    }
+    Ok(())
+}
+
+/// Calls the verification runtime `function`.
+fn call<E>(
+    function: RuntimeFunction,
+    message: &[u8],
+    signature: &[u8],
+    public_key: &[u8],
+) -> Result<(), SignatureError>
+where
+    E: Environment,
+{
+    let signature: &[u8; 64] = signature
+        .try_into()
+        .map_err(|_| SignatureError::InvalidLength)?;
+    let public_key: &[u8; 32] = public_key
+        .try_into()
+        .map_err(|_| SignatureError::InvalidLength)?;
+    runtime_function::call::<E, _, _, _>(function, &(message, signature, public_key))
+}
+
+/// Returns the `message` as signed by the Polkadot{.js} extension, which wraps
+/// raw bytes in `<Bytes>` and `</Bytes>`.
+pub fn wrap_bytes(message: &[u8]) -> Vec<u8> {
+    [WRAP_BYTES_PREFIX, message, WRAP_BYTES_POSTFIX].concat()
+}
+
+/// Returns the Keccak256 hash of the `message` as signed by Ethereum wallets,
+/// which prefix it with `"\x19Ethereum Signed Message:\n"` and its length.
+pub fn eth_message_hash(message: &[u8]) -> [u8; 32] {
+    let prefix = ink_prelude::format!("{}{}", ETH_MESSAGE_PREFIX, message.len());
+    let mut message_hash = [0x00; 32];
+    crate::hash_bytes::<Keccak256>(
+        &[prefix.as_bytes(), message].concat(),
+        &mut message_hash,
+    );
+    message_hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        runtime_function::TestEnvironment,
+        test,
+        DefaultEnvironment,
+    };
+    use secp256k1::{
+        Message,
+        PublicKey,
+        SecretKey,
+        SECP256K1,
+    };
+
+    /// Returns the ECDSA signature of the `message_hash` and the compressed public key.
+    fn sign_ecdsa(message_hash: &[u8; 32]) -> ([u8; 65], [u8; 33]) {
+        let secret_key = SecretKey::from_slice(&[0x01; 32]).unwrap();
+        let message = Message::from_slice(message_hash).unwrap();
+        let (recovery_id, compact) = SECP256K1
+            .sign_ecdsa_recoverable(&message, &secret_key)
+            .serialize_compact();
+        let mut signature = [0x00; 65];
+        signature[..64].copy_from_slice(&compact);
+        signature[64] = recovery_id.to_i32() as u8;
+        let public_key = PublicKey::from_secret_key(SECP256K1, &secret_key).serialize();
+        (signature, public_key)
+    }
+
+    #[test]
+    fn verify_ecdsa_works() {
+        test::run_test::<DefaultEnvironment, _>(|_| {
+            let mut message_hash = [0x00; 32];
+            crate::hash_bytes::<Blake2x256>(b"permit", &mut message_hash);
+            let (signature, public_key) = sign_ecdsa(&message_hash);
+
+            assert_eq!(
+                verify::<TestEnvironment>(
+                    Scheme::Ecdsa,
+                    b"permit",
+                    &signature,
+                    &public_key
+                ),
+                Ok(())
+            );
+            assert_eq!(
+                verify::<TestEnvironment>(
+                    Scheme::Ecdsa,
+                    b"forged",
+                    &signature,
+                    &public_key
+                ),
+                Err(SignatureError::InvalidSignature)
+            );
+            assert_eq!(
+                verify::<TestEnvironment>(
+                    Scheme::Ecdsa,
+                    b"permit",
+                    &signature[..64],
+                    &public_key
+                ),
+                Err(SignatureError::InvalidLength)
+            );
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn verify_eth_signed_message_works() {
+        test::run_test::<DefaultEnvironment, _>(|_| {
+            let message_hash = eth_message_hash(b"permit");
+            let (mut signature, public_key) = sign_ecdsa(&message_hash);
+            // Ethereum wallets add 27 to the recovery ID.
+            signature[64] += 27;
+
+            let mut expected = [0x00; 32];
+            crate::hash_bytes::<Keccak256>(
+                b"\x19Ethereum Signed Message:\n6permit",
+                &mut expected,
+            );
+            assert_eq!(message_hash, expected);
+            assert_eq!(
+                verify_ecdsa_prehashed(&message_hash, &signature, &public_key),
+                Ok(())
+            );
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn verify_via_chain_extension_works() {
+        test::run_test::<DefaultEnvironment, _>(|_| {
+            let message = wrap_bytes(b"permit");
+            assert_eq!(message, b"<Bytes>permit</Bytes>".to_vec());
+            let expected_input =
+                scale::Encode::encode(&(&message[..], [0x01u8; 64], [0x02u8; 32]));
+            TestEnvironment::register_fn(RuntimeFunction::Sr25519Verify, move |input| {
+                assert_eq!(input, &expected_input[..]);
+                Vec::new()
+            });
+
+            assert_eq!(
+                verify::<TestEnvironment>(
+                    Scheme::Sr25519,
+                    &message,
+                    &[0x01; 64],
+                    &[0x02; 32]
+                ),
+                Ok(())
+            );
+            assert_eq!(
+                verify::<TestEnvironment>(
+                    Scheme::Ed25519,
+                    &message,
+                    &[0x01; 64],
+                    &[0x02; 31]
+                ),
+                Err(SignatureError::InvalidLength)
+            );
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn invalid_and_unsupported_signatures_fail() {
+        test::run_test::<DefaultEnvironment, _>(|_| {
+            TestEnvironment::register_failing(RuntimeFunction::Sr25519Verify, 1);
+            TestEnvironment::register_failing(RuntimeFunction::Ed25519Verify, 2);
+
+            assert_eq!(
+                verify::<TestEnvironment>(
+                    Scheme::Sr25519,
+                    b"permit",
+                    &[0x01; 64],
+                    &[0x02; 32]
+                ),
+                Err(SignatureError::InvalidSignature)
+            );
+            assert_eq!(
+                verify::<TestEnvironment>(
+                    Scheme::Ed25519,
+                    b"permit",
+                    &[0x01; 64],
+                    &[0x02; 32]
+                ),
+                Err(SignatureError::NotSupported)
+            );
+            // The default environment declares no function IDs.
+            assert_eq!(
+                verify::<DefaultEnvironment>(
+                    Scheme::Sr25519,
+                    b"permit",
+                    &[0x01; 64],
+                    &[0x02; 32]
+                ),
+                Err(SignatureError::NotSupported)
+            );
+            Ok(())
+        })
+        .unwrap()
+    }
+}
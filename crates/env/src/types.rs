@@ -31,7 +31,10 @@
 //! there is no knowledge of the concrete types, the functionality is restricted to
 //! the trait bounds on the `Environment` trait types.
 
-use super::arithmetic::AtLeast32BitUnsigned;
+use super::{
+    arithmetic::AtLeast32BitUnsigned,
+    runtime_function::RuntimeFunction,
+};
 use ink_primitives::{
     AccountId,
     Clear,
@@ -161,6 +164,15 @@ pub trait Environment {
     ///
     /// [chain_extension]: https://paritytech.github.io/ink/ink/attr.chain_extension.html
     type ChainExtension;
+
+    /// Returns the ID of the chain extension function under which the runtime
+    /// provides the given [`RuntimeFunction`], or `None` if it does not provide it.
+    ///
+    /// By default the runtime provides none of them, so that calling them returns an
+    /// error instead of calling the chain extension.
+    fn runtime_function_id(_function: RuntimeFunction) -> Option<u32> {
+        None
+    }
 }
 
 /// Placeholder for chains that have no defined chain extension.
@@ -185,6 +197,28 @@ impl Environment for DefaultEnvironment {
 /// The default balance type.
 pub type Balance = u128;
 
+/// Returns the decimal representation of the balance `value` of a currency with
+/// `decimals` decimal places, e.g. `1.5` for `1_500_000_000_000` with `12` decimals.
+///
+/// Trailing zeros of the fraction are omitted. Formatting the returned value does
+/// not allocate, so that it can be used in debug messages of contracts.
+///
+/// # Example
+///
+/// ```
+/// use ink_env::format_balance;
+///
+/// let balance: u128 = 1_500_000_000_000;
+/// ink_env::debug_println!("transferred {} UNIT", format_balance(balance, 12));
+/// assert_eq!(format!("{} UNIT", format_balance(balance, 12)), "1.5 UNIT");
+/// ```
+pub fn format_balance<B>(value: B, decimals: u8) -> ink_prelude::fixed::DisplayDecimal
+where
+    B: Into<u128>,
+{
+    ink_prelude::fixed::format_decimal(value.into(), decimals)
+}
+
 /// The default timestamp type.
 pub type Timestamp = u64;
 
@@ -193,3 +227,120 @@ pub type Gas = u64;
 
 /// The default block number type.
 pub type BlockNumber = u32;
+
+/// The weight of a contract execution.
+///
+/// In addition to the computation time measured by the [`Gas`] the weight tracks
+/// the size of the storage proof, which is limited on parachains.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(TypeInfo))]
+pub struct Weight {
+    /// The computation time in picoseconds, equivalent to the [`Gas`].
+    #[codec(compact)]
+    pub ref_time: u64,
+    /// The size of the storage proof in bytes.
+    #[codec(compact)]
+    pub proof_size: u64,
+}
+
+/// The storage a contract execution has added and removed so far.
+///
+/// Only the values written via the storage functions of this crate, e.g.
+/// [`set_contract_storage`](crate::set_contract_storage), are taken into account.
+/// This includes the writes of `Lazy` and `Mapping`, which write through. The
+/// fields of the contract storage struct itself are written back at the end of an
+/// ink! message and are not included until then.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(TypeInfo))]
+pub struct StorageDepositInfo {
+    /// The number of bytes the written values have grown by.
+    pub bytes_added: u32,
+    /// The number of bytes the written or removed values have shrunk by.
+    pub bytes_removed: u32,
+    /// The number of storage items created.
+    pub items_added: u32,
+    /// The number of storage items removed.
+    pub items_removed: u32,
+}
+
+impl StorageDepositInfo {
+    /// No storage added or removed.
+    pub const fn new() -> Self {
+        Self {
+            bytes_added: 0,
+            bytes_removed: 0,
+            items_added: 0,
+            items_removed: 0,
+        }
+    }
+
+    /// Records that a value of `new_len` bytes replaced the value of `old_len` bytes,
+    /// where `None` denotes a missing value.
+    pub(crate) fn record(&mut self, old_len: Option<u32>, new_len: Option<u32>) {
+        match (old_len, new_len) {
+            (Some(old_len), Some(new_len)) if new_len > old_len => {
+                self.bytes_added = self.bytes_added.saturating_add(new_len - old_len)
+            }
+            (Some(old_len), Some(new_len)) => {
+                self.bytes_removed = self.bytes_removed.saturating_add(old_len - new_len)
+            }
+            (None, Some(new_len)) => {
+                self.bytes_added = self.bytes_added.saturating_add(new_len);
+                self.items_added = self.items_added.saturating_add(1);
+            }
+            (Some(old_len), None) => {
+                self.bytes_removed = self.bytes_removed.saturating_add(old_len);
+                self.items_removed = self.items_removed.saturating_add(1);
+            }
+            (None, None) => (),
+        }
+    }
+
+    /// Returns the storage deposit charged from the caller, given the deposit prices
+    /// of the chain `per_byte` and `per_item`.
+    ///
+    /// Returns zero if the removed storage outweighs the added storage, see
+    /// [`StorageDepositInfo::refund`].
+    ///
+    /// # Note
+    ///
+    /// The prices are part of the configuration of the chain, e.g. the
+    /// `DepositPerByte` and `DepositPerItem` of `pallet-contracts`, and are not
+    /// accessible to contracts.
+    pub fn charge<Balance>(&self, per_byte: Balance, per_item: Balance) -> Balance
+    where
+        Balance: AtLeast32BitUnsigned + From<u32> + Copy,
+    {
+        let (charge, refund) = self.prices(per_byte, per_item);
+        charge.saturating_sub(refund)
+    }
+
+    /// Returns the storage deposit refunded to the caller, given the deposit prices
+    /// of the chain `per_byte` and `per_item`.
+    ///
+    /// Returns zero if the added storage outweighs the removed storage, see
+    /// [`StorageDepositInfo::charge`].
+    pub fn refund<Balance>(&self, per_byte: Balance, per_item: Balance) -> Balance
+    where
+        Balance: AtLeast32BitUnsigned + From<u32> + Copy,
+    {
+        let (charge, refund) = self.prices(per_byte, per_item);
+        refund.saturating_sub(charge)
+    }
+
+    /// Returns the price of the added and of the removed storage.
+    fn prices<Balance>(&self, per_byte: Balance, per_item: Balance) -> (Balance, Balance)
+    where
+        Balance: AtLeast32BitUnsigned + From<u32> + Copy,
+    {
+        let price = |bytes: u32, items: u32| {
+            per_byte
+                .saturating_mul(Balance::from(bytes))
+                .saturating_add(per_item.saturating_mul(Balance::from(items)))
+        };
+        (
+            price(self.bytes_added, self.items_added),
+            price(self.bytes_removed, self.items_removed),
+        )
+    }
+}
@@ -0,0 +1,164 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Utilities to upgrade the code of the executed contract.
+
+use crate::{
+    backend::EnvBackend,
+    call::{
+        build_call,
+        Call,
+        ExecutionInput,
+    },
+    engine::{
+        EnvInstance,
+        OnInstance,
+    },
+    CallFlags,
+    Environment,
+    Error,
+    Result,
+};
+use ink_primitives::MessageResult;
+
+/// The storage key of the code hash whose migration is pending.
+///
+/// Unlike the `u32` root keys of ink! storage, the key is a SCALE encoded byte
+/// string, so that it does not collide with the storage of the contract.
+const PENDING_MIGRATION_KEY: &[u8] = b"ink:pending_migration";
+
+/// Ensures that the executed message is the migration run by
+/// [`set_code_hash_with_migration`] and marks the migration as done.
+///
+/// Migration messages call this first, so that they can't be called by anyone
+/// else and do not run again after the upgrade.
+///
+/// # Errors
+///
+/// If the migration was not started by [`set_code_hash_with_migration`] of the
+/// contract itself or has already run.
+///
+/// # Example
+///
+/// ```no_run
+/// use ink_env::DefaultEnvironment;
+///
+/// // The migration message of the new code.
+/// fn migrate() -> u32 {
+///     ink_env::upgrade::ensure_pending_migration::<DefaultEnvironment>()
+///         .expect("only the upgrade runs the migration");
+///     // Migrate the storage ...
+///     0
+/// }
+/// ```
+pub fn ensure_pending_migration<E>() -> Result<()>
+where
+    E: Environment,
+{
+    if crate::caller::<E>() != crate::account_id::<E>() {
+        return Err(Error::MigrationNotPending)
+    }
+    let pending = crate::take_contract_storage::<_, [u8; 32]>(&PENDING_MIGRATION_KEY)
+        .ok()
+        .flatten();
+    let own_code_hash = crate::own_code_hash::<E>()?;
+    match pending {
+        Some(code_hash) if code_hash[..] == *own_code_hash.as_ref() => Ok(()),
+        _ => Err(Error::MigrationNotPending),
+    }
+}
+
+/// Replaces the contract code at the specified address with new code and runs
+/// the given migration entry point on the new code.
+///
+/// The migration is invoked as a call of the executed contract to itself, so
+/// `migration` must contain the selector and arguments of an ink! message of the
+/// new code. It is executed before control is returned to the caller which makes
+/// sure that the storage has been migrated before any other message runs on the
+/// new code.
+///
+/// If the migration traps, reverts or cannot be dispatched, the code hash of the
+/// contract is reset to its previous value and an error is returned, so that the
+/// contract remains usable with its old code.
+///
+/// The migration message must call [`ensure_pending_migration`] first, so that it
+/// only runs once and only as part of the upgrade.
+///
+/// # Note
+///
+/// The message calling this function should not write back its own storage
+/// afterwards, e.g. by being a `&self` message, since the storage has been
+/// migrated to the layout of the new code in the meantime.
+///
+/// # Errors
+///
+/// - If the supplied `code_hash` cannot be found on-chain.
+/// - If the migration trapped or could not be dispatched.
+/// - If the migration reverted, e.g. by returning an `Err` value of a `Result`
+///   typed `R`, or did not call [`ensure_pending_migration`].
+/// - If the migration returned a value that cannot be decoded as `R`.
+///
+/// # Example
+///
+/// ```no_run
+/// use ink_env::{
+///     call::{
+///         ExecutionInput,
+///         Selector,
+///     },
+///     DefaultEnvironment,
+/// };
+///
+/// let new_code_hash = [0x42; 32];
+/// let migrated_entries: u32 =
+///     ink_env::upgrade::set_code_hash_with_migration::<DefaultEnvironment, _, _>(
+///         &new_code_hash,
+///         ExecutionInput::new(Selector::new([0xCA, 0xFE, 0xBA, 0xBE])).push_arg(true),
+///     )
+///     .expect("migration must succeed");
+/// ```
+pub fn set_code_hash_with_migration<E, Args, R>(
+    code_hash: &[u8; 32],
+    migration: ExecutionInput<Args>,
+) -> Result<R>
+where
+    E: Environment,
+    Args: scale::Encode,
+    R: scale::Decode,
+{
+    let old_code_hash = crate::own_code_hash::<E>()?;
+    crate::set_code_hash(code_hash)?;
+    crate::set_contract_storage(&PENDING_MIGRATION_KEY, code_hash);
+    let result = build_call::<E>()
+        .call_type(Call::new().callee(crate::account_id::<E>()))
+        .call_flags(CallFlags::default().set_allow_reentry(true))
+        .exec_input(migration)
+        .returns::<MessageResult<R>>()
+        .fire();
+    // Still pending if the migration did not call `ensure_pending_migration` or
+    // reverted, which ink! messages returning an `Err` value do.
+    let pending = crate::take_contract_storage::<_, [u8; 32]>(&PENDING_MIGRATION_KEY)
+        .ok()
+        .flatten()
+        .is_some();
+    let error = match result {
+        Ok(Ok(value)) if !pending => return Ok(value),
+        Ok(_) => Error::CalleeReverted,
+        Err(error) => error,
+    };
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.set_code_hash(old_code_hash.as_ref())
+    })?;
+    Err(error)
+}
@@ -0,0 +1,211 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Sending and executing XCM messages from contracts.
+//!
+//! The contracts pallet does not expose XCM to contracts. Runtimes of parachains
+//! provide it instead via the [`RuntimeFunction::XcmSend`] and
+//! [`RuntimeFunction::XcmExecute`] functions of their chain extension, which forward
+//! to `pallet-xcm`.
+//!
+//! Messages are built with the types of the `xcm` crate, which are re-exported by
+//! [`prelude`], e.g. via the `Xcm::builder()`. The [`transfer`] helper builds the
+//! common message transferring assets to a beneficiary.
+//!
+//! # Example
+//!
+//! ```
+//! use ink_env::xcm::{
+//!     prelude::*,
+//!     transfer,
+//!     XcmError,
+//! };
+//!
+//! /// Transfers `amount` of the native asset of the relay chain to `beneficiary` on
+//! /// the parachain `2000`.
+//! fn transfer_to_parachain(
+//!     beneficiary: [u8; 32],
+//!     amount: u128,
+//! ) -> Result<XcmHash, XcmError> {
+//!     let dest = MultiLocation::new(1, X1(Parachain(2000)));
+//!     let beneficiary = MultiLocation::new(
+//!         0,
+//!         X1(AccountId32 {
+//!             network: None,
+//!             id: beneficiary,
+//!         }),
+//!     );
+//!     let message = transfer((Parent, amount).into(), beneficiary);
+//!     ink_env::xcm::send::<ink_env::DefaultEnvironment>(&dest.into(), &message)
+//! }
+//! ```
+//!
+//! # Note
+//!
+//! This module is only available with the `xcm` crate feature.
+
+use crate::{
+    chain_extension::FromStatusCode,
+    runtime_function::{
+        self,
+        RuntimeFunction,
+        RuntimeFunctionError,
+    },
+    Environment,
+};
+use prelude::*;
+
+pub use crate::runtime_function::errors::XcmError;
+
+/// The types of the `xcm` crate to build XCM messages with.
+pub mod prelude {
+    pub use ::xcm::prelude::*;
+}
+
+impl FromStatusCode for XcmError {
+    fn from_status_code(status_code: u32) -> Result<(), Self> {
+        match status_code {
+            0 => Ok(()),
+            1 => Err(Self::Rejected),
+            _ => Err(Self::NotSupported),
+        }
+    }
+}
+
+impl RuntimeFunctionError for XcmError {
+    const NOT_SUPPORTED: Self = Self::NotSupported;
+}
+
+/// Returns the message transferring the `asset` to the `beneficiary`, as seen from
+/// the destination the message is sent to.
+///
+/// The asset is withdrawn from the sovereign account of the contract on the
+/// destination, which pays the fees of the execution of the message with it.
+pub fn transfer(asset: MultiAsset, beneficiary: MultiLocation) -> VersionedXcm<()> {
+    let message = Xcm::builder()
+        .withdraw_asset(asset.clone().into())
+        .buy_execution(asset, Unlimited)
+        .deposit_asset(All.into(), beneficiary)
+        .build();
+    VersionedXcm::V3(message)
+}
+
+/// Sends the XCM `message` to the `dest` location.
+///
+/// # Errors
+///
+/// - If the runtime rejected the message.
+/// - If the runtime of `E` does not provide [`RuntimeFunction::XcmSend`].
+pub fn send<E>(
+    dest: &VersionedMultiLocation,
+    message: &VersionedXcm<()>,
+) -> Result<XcmHash, XcmError>
+where
+    E: Environment,
+{
+    runtime_function::call::<E, _, _, _>(RuntimeFunction::XcmSend, &(dest, message))
+}
+
+/// Executes the XCM `message` with the contract as origin.
+///
+/// The execution of the message may consume at most `max_weight`.
+///
+/// # Errors
+///
+/// - If the runtime rejected the message or its execution failed.
+/// - If the runtime of `E` does not provide [`RuntimeFunction::XcmExecute`].
+pub fn execute<E>(message: &VersionedXcm<()>, max_weight: Weight) -> Result<(), XcmError>
+where
+    E: Environment,
+{
+    runtime_function::call::<E, _, _, _>(
+        RuntimeFunction::XcmExecute,
+        &(message, max_weight),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        runtime_function::TestEnvironment,
+        test,
+        DefaultEnvironment,
+    };
+    use scale::Encode as _;
+
+    fn dest() -> VersionedMultiLocation {
+        MultiLocation::new(1, X1(Parachain(2000))).into()
+    }
+
+    fn message() -> VersionedXcm<()> {
+        transfer((Parent, 1_000u128).into(), MultiLocation::here())
+    }
+
+    #[test]
+    fn transfer_works() {
+        let asset: MultiAsset = (Parent, 1_000u128).into();
+        let expected = Xcm(vec![
+            WithdrawAsset(asset.clone().into()),
+            BuyExecution {
+                fees: asset,
+                weight_limit: Unlimited,
+            },
+            DepositAsset {
+                assets: All.into(),
+                beneficiary: MultiLocation::here(),
+            },
+        ]);
+        assert_eq!(message(), VersionedXcm::V3(expected));
+    }
+
+    #[test]
+    fn send_and_execute_work() {
+        test::run_test::<DefaultEnvironment, _>(|_| {
+            let expected_input = (dest(), message()).encode();
+            TestEnvironment::register_fn(RuntimeFunction::XcmSend, move |input| {
+                assert_eq!(input, &expected_input[..]);
+                [0x07u8; 32].encode()
+            });
+            TestEnvironment::register_fn(RuntimeFunction::XcmExecute, |_| Vec::new());
+
+            assert_eq!(send::<TestEnvironment>(&dest(), &message()), Ok([0x07; 32]));
+            assert_eq!(
+                execute::<TestEnvironment>(&message(), Weight::from_parts(1_000, 0)),
+                Ok(())
+            );
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn rejected_and_unsupported_messages_fail() {
+        test::run_test::<DefaultEnvironment, _>(|_| {
+            TestEnvironment::register_failing(RuntimeFunction::XcmSend, 1);
+
+            assert_eq!(
+                send::<TestEnvironment>(&dest(), &message()),
+                Err(XcmError::Rejected)
+            );
+            // The default environment declares no XCM functions.
+            assert_eq!(
+                execute::<DefaultEnvironment>(&message(), Weight::zero()),
+                Err(XcmError::NotSupported)
+            );
+            Ok(())
+        })
+        .unwrap()
+    }
+}
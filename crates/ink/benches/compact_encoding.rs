@@ -0,0 +1,98 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compares the size and the encoding and decoding cost of a storage item stored
+//! with `#[ink::storage_item(encoding = compact)]` to the default encoding.
+//!
+//! Run with `cargo bench -p ink --bench compact_encoding`.
+
+use ink::storage::traits::Storable;
+use std::{
+    hint::black_box,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+/// The number of encodings and decodings timed per measurement.
+const ITERATIONS: u32 = 1_000_000;
+
+#[ink::storage_item]
+#[derive(Clone)]
+struct Order {
+    maker: [u8; 32],
+    price: u128,
+    amount: u128,
+    expiry: u32,
+}
+
+#[ink::storage_item(encoding = compact)]
+#[derive(Clone)]
+struct CompactOrder {
+    maker: [u8; 32],
+    price: u128,
+    amount: u128,
+    expiry: u32,
+}
+
+/// Returns the average time taken by `f` over [`ITERATIONS`] runs.
+fn time<R>(mut f: impl FnMut() -> R) -> Duration {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(f());
+    }
+    start.elapsed() / ITERATIONS
+}
+
+/// Prints the encoded size and the average encoding and decoding time of `value`.
+fn bench<T: Storable>(name: &str, value: &T) {
+    let mut encoded = Vec::new();
+    value.encode(&mut encoded);
+    let encode = time(|| {
+        let mut buffer = Vec::with_capacity(encoded.len());
+        black_box(value).encode(&mut buffer);
+        buffer
+    });
+    let decode = time(|| T::decode(&mut black_box(&encoded[..])));
+    println!(
+        "{:<30} {:>4} bytes, encode {:>8?}, decode {:>8?}",
+        name,
+        encoded.len(),
+        encode,
+        decode
+    );
+}
+
+fn main() {
+    for (name, price, amount, expiry) in [
+        ("small values", 1_000, 50_000_000, 1_000_000),
+        ("maximum values", u128::MAX, u128::MAX, u32::MAX),
+    ] {
+        let order = Order {
+            maker: [0x01; 32],
+            price,
+            amount,
+            expiry,
+        };
+        let compact = CompactOrder {
+            maker: order.maker,
+            price: order.price,
+            amount: order.amount,
+            expiry: order.expiry,
+        };
+        bench(&format!("scale, {}", name), &order);
+        bench(&format!("compact, {}", name), &compact);
+    }
+}
@@ -46,9 +46,36 @@ pub fn input_types(inputs: ir::InputsIter) -> Vec<&syn::Type> {
     inputs.map(|pat_type| &*pat_type.ty).collect::<Vec<_>>()
 }
 
+/// Returns the input type with the given `lifetime` if it is a borrowed input.
+///
+/// # Note
+///
+/// ink! messages only support `&[u8]` and `&str` as borrowed inputs whose
+/// lifetime is elided.
+pub fn input_type_with_lifetime(ty: &syn::Type, lifetime: syn::Lifetime) -> syn::Type {
+    match ty {
+        syn::Type::Reference(reference) => {
+            syn::Type::Reference(syn::TypeReference {
+                lifetime: Some(lifetime),
+                ..reference.clone()
+            })
+        }
+        ty => ty.clone(),
+    }
+}
+
 /// Returns a tuple type representing the types yielded by the input types.
+///
+/// # Note
+///
+/// Borrowed inputs are given a `'static` lifetime since the type must not depend
+/// on the lifetime of the input. The dispatch thus decodes them and calls their
+/// message directly.
 pub fn input_types_tuple(inputs: ir::InputsIter) -> TokenStream2 {
-    let input_types = input_types(inputs);
+    let input_types = input_types(inputs)
+        .into_iter()
+        .map(|ty| input_type_with_lifetime(ty, syn::parse_quote! { 'static }))
+        .collect::<Vec<_>>();
     if input_types.len() != 1 {
         // Pack all types into a tuple if they are not exactly 1.
         // This results in `()` for zero input types.
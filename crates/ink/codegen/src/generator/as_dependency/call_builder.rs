@@ -366,10 +366,45 @@ impl CallBuilder<'_> {
         let selector = message.composed_selector();
         let selector_bytes = selector.hex_lits();
         let input_bindings = generator::input_bindings(callable.inputs());
-        let input_types = generator::input_types(message.inputs());
-        let arg_list = generator::generate_argument_list(input_types.iter().cloned());
+        // Borrowed inputs are stored in the returned call builder, so they share
+        // a lifetime with it.
+        let input_lifetime = callable
+            .has_borrowed_inputs()
+            .then(|| quote! { <'__ink_input> });
+        let input_types = generator::input_types(message.inputs())
+            .into_iter()
+            .map(|ty| {
+                generator::input_type_with_lifetime(
+                    ty,
+                    syn::parse_quote! { '__ink_input },
+                )
+            })
+            .collect::<Vec<_>>();
         let mut_tok = callable.receiver().is_ref_mut().then(|| quote! { mut });
-        let return_type = message.wrapped_output();
+        let (arg_list, push_args, return_type) = if self.contract.config().abi()
+            == ir::Abi::Solidity
+        {
+            // The inputs and output are ABI encoded as the parameters and
+            // return values of the message.
+            let output = message
+                .output()
+                .map(quote::ToTokens::to_token_stream)
+                .unwrap_or_else(|| quote! { () });
+            let sol_input: syn::Type = syn::parse_quote! {
+                ::ink::sol::SolEncoded<( #( #input_types, )* )>
+            };
+            (
+                generator::generate_argument_list([&sol_input]),
+                quote! { .push_arg(::ink::sol::SolEncoded(( #( #input_bindings, )* ))) },
+                syn::parse_quote! { ::ink::sol::SolEncoded<(#output,)> },
+            )
+        } else {
+            (
+                generator::generate_argument_list(input_types.iter()),
+                quote! { #( .push_arg(#input_bindings) )* },
+                message.wrapped_output(),
+            )
+        };
         let output_span = return_type.span();
         let output_type = quote_spanned!(output_span=>
             ::ink::env::call::CallBuilder<
@@ -383,7 +418,7 @@ impl CallBuilder<'_> {
             #( #attrs )*
             #[allow(clippy::type_complexity)]
             #[inline]
-            pub fn #message_ident(
+            pub fn #message_ident #input_lifetime (
                 & #mut_tok self
                 #( , #input_bindings : #input_types )*
             ) -> #output_type {
@@ -393,9 +428,7 @@ impl CallBuilder<'_> {
                         ::ink::env::call::ExecutionInput::new(
                             ::ink::env::call::Selector::new([ #( #selector_bytes ),* ])
                         )
-                        #(
-                            .push_arg(#input_bindings)
-                        )*
+                        #push_args
                     )
                     .returns::<#return_type>()
             }
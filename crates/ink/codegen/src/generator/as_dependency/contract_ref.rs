@@ -142,6 +142,65 @@ impl ContractRef<'_> {
                         as ::ink::ToAccountId<Environment>>::to_account_id(&self.inner)
                 }
             }
+
+            impl #ref_ident {
+                /// Returns `true` if the referenced contract still exists.
+                ///
+                /// Calls to a contract which no longer exists, e.g. because it
+                /// terminated itself, trap. Use `ink::WeakContractRef` in order
+                /// to check this before every call.
+                #[inline]
+                pub fn exists(&self) -> bool {
+                    ::ink::env::code_hash::<Environment>(
+                        &<Self as ::ink::ToAccountId<Environment>>::to_account_id(self),
+                    )
+                    .is_ok()
+                }
+
+                /// Creates a reference to the already instantiated contract at
+                /// `account_id`.
+                ///
+                /// The contract is not checked, see `at_code_hash` for a checked
+                /// alternative.
+                #[inline]
+                pub fn from_account_id(account_id: AccountId) -> Self {
+                    <Self as ::ink::env::call::FromAccountId<Environment>>::from_account_id(
+                        account_id,
+                    )
+                }
+
+                /// Creates a reference to the already instantiated contract at
+                /// `account_id` after checking that it runs the code with `code_hash`.
+                ///
+                /// # Errors
+                ///
+                /// - If no contract exists at `account_id`.
+                /// - If the contract at `account_id` runs another code.
+                #[inline]
+                pub fn at_code_hash(
+                    account_id: AccountId,
+                    code_hash: Hash,
+                ) -> ::core::result::Result<Self, ::ink::RebindError> {
+                    let actual = ::ink::env::code_hash::<Environment>(&account_id)
+                        .map_err(|_| ::ink::RebindError::CalleeGone)?;
+                    if actual != code_hash {
+                        return ::core::result::Result::Err(
+                            ::ink::RebindError::CodeHashMismatch,
+                        )
+                    }
+                    ::core::result::Result::Ok(Self::from_account_id(account_id))
+                }
+
+                /// Points the reference to the already instantiated contract at
+                /// `account_id`, e.g. after the referenced contract was replaced.
+                ///
+                /// The contract is not checked, see `at_code_hash` for a checked
+                /// alternative.
+                #[inline]
+                pub fn rebind(&mut self, account_id: AccountId) {
+                    *self = Self::from_account_id(account_id);
+                }
+            }
         )
     }
 
@@ -149,11 +208,28 @@ impl ContractRef<'_> {
     ///
     /// This creates the bridge between the ink! smart contract type and the
     /// associated call builder.
+    ///
+    /// Also generates the inherent `call_builder` method giving access to the
+    /// call builder without importing the `TraitCallBuilder` trait.
     fn generate_call_builder_trait_impl(&self) -> TokenStream2 {
         let span = self.contract.module().storage().span();
         let ref_ident = self.generate_contract_ref_ident();
         let storage_ident = self.contract.module().storage().ident();
         quote_spanned!(span=>
+            impl #ref_ident {
+                /// Returns the call builder of the contract reference.
+                ///
+                /// Its methods return the underlying call builder of each message
+                /// of the contract, which allows to e.g. set the gas limit or the
+                /// call flags of the call before firing it.
+                #[inline]
+                pub fn call_builder(
+                    &self,
+                ) -> <#storage_ident as ::ink::codegen::ContractCallBuilder>::Type {
+                    ::core::clone::Clone::clone(&self.inner)
+                }
+            }
+
             const _: () = {
                 impl ::ink::codegen::TraitCallBuilder for #ref_ident {
                     type Builder = <#storage_ident as ::ink::codegen::ContractCallBuilder>::Type;
@@ -323,6 +399,10 @@ impl ContractRef<'_> {
     ///
     /// This produces the short-hand calling notation for the inherent contract message.
     /// The generated code simply forwards its calling logic to the associated call builder.
+    ///
+    /// For messages returning a `Result` an additional `try_` prefixed method is generated
+    /// which returns the error of the callee as [`ink::CalleeError::Callee`] instead of
+    /// panicking on failed cross-contract calls.
     fn generate_contract_inherent_impl_for_message(
         &self,
         message: ir::CallableWithSelector<ir::Message>,
@@ -344,8 +424,54 @@ impl ContractRef<'_> {
         let mut_token = message.receiver().is_ref_mut().then(|| quote! { mut });
         let input_bindings = message.inputs().map(|input| &input.pat).collect::<Vec<_>>();
         let input_types = message.inputs().map(|input| &input.ty).collect::<Vec<_>>();
-        let output_type = message.output().map(|ty| quote! { -> #ty });
+        // Calls to `timelocked` messages return the `OperationId` of the queued call.
+        let output_type = match message.timelock_delay() {
+            Some(_) => Some(quote! { -> ::ink::contrib::OperationId }),
+            None => message.output().map(|ty| quote! { -> #ty }),
+        };
         let wrapped_output_type = message.wrapped_output();
+        // The call builder of contracts using the Solidity ABI decodes the output
+        // as `ink::sol::SolEncoded` which is converted into the wrapped output.
+        let convert_output =
+            (self.contract.config().abi() == ir::Abi::Solidity).then(|| {
+                quote! {
+                    .map(<#wrapped_output_type as ::core::convert::From<_>>::from)
+                }
+            });
+        let try_message = message.returns_result().then(|| {
+            let try_message_ident = message.try_ident();
+            let output = message.output();
+            quote_spanned!(span=>
+                #( #attrs )*
+                #[inline]
+                pub fn #try_message_ident(
+                    & #mut_token self
+                    #( , #input_bindings : #input_types )*
+                ) -> ::core::result::Result<
+                    <#output as ::ink::IsResultType>::Ok,
+                    ::ink::CalleeError<<#output as ::ink::IsResultType>::Err>,
+                > {
+                    match <Self as ::ink::codegen::TraitCallBuilder>::#call_operator(self)
+                        .#message_ident( #( #input_bindings ),* )
+                        .fire()
+                        #convert_output
+                    {
+                        ::core::result::Result::Ok(::core::result::Result::Ok(
+                            ::core::result::Result::Ok(value),
+                        )) => ::core::result::Result::Ok(value),
+                        ::core::result::Result::Ok(::core::result::Result::Ok(
+                            ::core::result::Result::Err(error),
+                        )) => ::core::result::Result::Err(::ink::CalleeError::Callee(error)),
+                        ::core::result::Result::Ok(::core::result::Result::Err(error)) => {
+                            ::core::result::Result::Err(::ink::CalleeError::Lang(error))
+                        }
+                        ::core::result::Result::Err(error) => {
+                            ::core::result::Result::Err(::ink::CalleeError::Env(error))
+                        }
+                    }
+                }
+            )
+        });
         quote_spanned!(span=>
             #( #attrs )*
             #[inline]
@@ -371,6 +497,7 @@ impl ContractRef<'_> {
                 <Self as ::ink::codegen::TraitCallBuilder>::#call_operator(self)
                     .#message_ident( #( #input_bindings ),* )
                     .fire()
+                    #convert_output
                     .unwrap_or_else(|error| ::core::panic!(
                         "encountered error while calling {}::{}: {:?}",
                         ::core::stringify!(#storage_ident),
@@ -378,6 +505,8 @@ impl ContractRef<'_> {
                         error,
                     ))
             }
+
+            #try_message
         )
     }
 
@@ -403,6 +532,18 @@ impl ContractRef<'_> {
         let input_bindings = generator::input_bindings(constructor.inputs());
         let input_types = generator::input_types(constructor.inputs());
         let arg_list = generator::generate_argument_list(input_types.iter().cloned());
+        // Only builders of payable constructors allow to set a transferred value.
+        let (endowment_state, mark_payable) = if constructor.is_payable() {
+            (
+                quote! { ::ink::env::call::state::Payable },
+                quote! { .payable() },
+            )
+        } else {
+            (
+                quote! { ::ink::env::call::utils::Unset<Balance> },
+                quote! {},
+            )
+        };
         quote_spanned!(span =>
             #( #attrs )*
             #[inline]
@@ -413,7 +554,7 @@ impl ContractRef<'_> {
                 Environment,
                 ::ink::env::call::utils::Unset<Hash>,
                 ::ink::env::call::utils::Unset<u64>,
-                ::ink::env::call::utils::Unset<Balance>,
+                #endowment_state,
                 ::ink::env::call::utils::Set<::ink::env::call::ExecutionInput<#arg_list>>,
                 ::ink::env::call::utils::Unset<::ink::env::call::state::Salt>,
                 Self,
@@ -427,6 +568,7 @@ impl ContractRef<'_> {
                             .push_arg(#input_bindings)
                         )*
                     )
+                    #mark_payable
             }
         )
     }
@@ -39,6 +39,7 @@ impl GenerateCode for Contract<'_> {
         let env = self.generate_code_using::<generator::Env>();
         let storage = self.generate_code_using::<generator::Storage>();
         let events = self.generate_code_using::<generator::Events>();
+        let errors = self.generate_code_using::<generator::Errors>();
         let dispatch2 = self.generate_code_using::<generator::Dispatch>();
         let item_impls = self.generate_code_using::<generator::ItemImpls>();
         let metadata = self.generate_code_using::<generator::Metadata>();
@@ -56,6 +57,7 @@ impl GenerateCode for Contract<'_> {
                 #env
                 #storage
                 #events
+                #errors
                 #dispatch2
                 #item_impls
                 #contract_reference
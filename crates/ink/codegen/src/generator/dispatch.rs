@@ -67,21 +67,31 @@ impl GenerateCode for Dispatch<'_> {
             self.generate_dispatchable_constructor_infos();
         let contract_dispatchable_messages_infos =
             self.generate_dispatchable_message_infos();
+        let selector_collision_checks = self.generate_selector_collision_checks();
         let constructor_decoder_type =
             self.generate_constructor_decoder_type(&constructor_spans);
         let message_decoder_type = self.generate_message_decoder_type(&message_spans);
+        let timelock_execution = self.generate_timelock_execution(&message_spans);
         let entry_points = self.generate_entry_points(&constructor_spans, &message_spans);
+        let entry_cfg = self
+            .contract
+            .config()
+            .entry()
+            .map(|entry| quote! { #[cfg(feature = #entry)] });
         quote! {
             #amount_dispatchables
             #contract_dispatchable_messages
             #contract_dispatchable_constructors
             #contract_dispatchable_constructor_infos
             #contract_dispatchable_messages_infos
+            #selector_collision_checks
             #constructor_decoder_type
             #message_decoder_type
+            #timelock_execution
 
             #[cfg(not(test))]
             #[cfg(not(feature = "ink-as-dependency"))]
+            #entry_cfg
             const _: () = {
                 #entry_points
             };
@@ -119,6 +129,15 @@ impl Dispatch<'_> {
             .position(|item| item.has_wildcard_selector())
     }
 
+    /// Returns the index of the ink! message flagged with `#[ink(receive)]`, if existent.
+    fn query_receive_message(&self) -> Option<usize> {
+        self.contract
+            .module()
+            .impls()
+            .flat_map(|item_impl| item_impl.iter_messages())
+            .position(|item| item.is_receive())
+    }
+
     /// Returns the index of the ink! constructor which has a wildcard selector, if existent.
     fn query_wildcard_constructor(&self) -> Option<usize> {
         self.contract
@@ -410,6 +429,31 @@ impl Dispatch<'_> {
             self.any_constructor_accepts_payment_expr(constructor_spans);
         let any_message_accept_payment =
             self.any_message_accepts_payment_expr(message_spans);
+        let max_message_size = self.contract.config().max_message_size();
+        let check_constructor_input = max_message_size.map(|max_message_size| {
+            quote_spanned!(span=>
+                if ::ink::env::with_input_bytes(|input| input.len())
+                    > #max_message_size as ::core::primitive::usize
+                {
+                    ::ink::env::return_value::<::ink::ConstructorResult<()>>(
+                        ::ink::env::ReturnFlags::new_with_reverted(true),
+                        &::ink::ConstructorResult::Err(::ink::LangError::InputTooLarge),
+                    );
+                }
+            )
+        });
+        let check_message_input = max_message_size.map(|max_message_size| {
+            quote_spanned!(span=>
+                if ::ink::env::with_input_bytes(|input| input.len())
+                    > #max_message_size as ::core::primitive::usize
+                {
+                    ::ink::env::return_value::<::ink::MessageResult<()>>(
+                        ::ink::env::ReturnFlags::new_with_reverted(true),
+                        &::ink::MessageResult::Err(::ink::LangError::InputTooLarge),
+                    );
+                }
+            )
+        });
         quote_spanned!(span=>
             #[cfg(not(test))]
             #[no_mangle]
@@ -420,6 +464,8 @@ impl Dispatch<'_> {
                         .unwrap_or_else(|error| ::core::panic!("{}", error))
                 }
 
+                #check_constructor_input
+
                 let dispatchable = match ::ink::env::decode_input::<
                     <#storage_ident as ::ink::reflect::ContractConstructorDecoder>::Type,
                 >() {
@@ -458,6 +504,8 @@ impl Dispatch<'_> {
                         .unwrap_or_else(|error| ::core::panic!("{}", error))
                 }
 
+                #check_message_input
+
                 let dispatchable = match ::ink::env::decode_input::<
                     <#storage_ident as ::ink::reflect::ContractMessageDecoder>::Type,
                 >() {
@@ -489,6 +537,54 @@ impl Dispatch<'_> {
         )
     }
 
+    /// Generates a compile time check that no two ink! messages share a selector.
+    ///
+    /// # Note
+    ///
+    /// The selectors of ink! trait messages are defined by the ink! trait definition
+    /// and are therefore only known to the compiler, which evaluates the check over
+    /// the selectors of all ink! messages. The check names both messages instead of
+    /// only reporting the resulting conflicting trait implementations.
+    fn generate_selector_collision_checks(&self) -> TokenStream2 {
+        let storage_ident = self.contract.module().storage().ident();
+        let (labels, selectors): (Vec<_>, Vec<_>) = self
+            .contract
+            .module()
+            .impls()
+            .flat_map(|item_impl| iter::repeat(item_impl).zip(item_impl.iter_messages()))
+            .filter(|(_, message)| !message.has_wildcard_selector())
+            .map(|(item_impl, message)| {
+                let span = message.span();
+                let message_ident = message.ident();
+                match item_impl.trait_path() {
+                    Some(trait_path) => {
+                        let trait_ident = item_impl.trait_ident().expect(
+                            "must have an ink! trait identifier if it is an ink! trait implementation"
+                        );
+                        let local_id = message.local_id().hex_padded_suffixed();
+                        let selector = quote_spanned!(span=>
+                            <<::ink::reflect::TraitDefinitionRegistry<<#storage_ident as ::ink::reflect::ContractEnv>::Env>
+                                as #trait_path>::__ink_TraitInfo
+                                as ::ink::reflect::TraitMessageInfo<#local_id>>::SELECTOR
+                        );
+                        (format!("{}::{}", trait_ident, message_ident), selector)
+                    }
+                    None => {
+                        let selector_bytes = message.composed_selector().hex_lits();
+                        let selector = quote_spanned!(span=> [ #( #selector_bytes ),* ]);
+                        (message_ident.to_string(), selector)
+                    }
+                }
+            })
+            .unzip();
+        quote! {
+            const _: () = ::ink::codegen::utils::assert_unique_selectors(
+                &[ #( ::core::primitive::u32::from_be_bytes(#selectors) ),* ],
+                &[ #( #labels ),* ],
+            );
+        }
+    }
+
     /// Generates code for the ink! constructor decoder type of the ink! smart contract.
     ///
     /// This type can be used in order to decode the input bytes received by a call to `deploy`
@@ -522,6 +618,13 @@ impl Dispatch<'_> {
 
         let span = self.contract.module().storage().span();
         let storage_ident = self.contract.module().storage().ident();
+        let set_storage_version = self.contract.module().storage().storage_version().map(|_| {
+            quote_spanned!(span=>
+                ::ink::storage::migrate::set_stored_version(
+                    <#storage_ident as ::ink::storage::migrate::StorageVersion>::VERSION,
+                );
+            )
+        });
         let count_constructors = self.query_amount_constructors();
         let constructors_variants = (0..count_constructors).map(|index| {
             let constructor_span = constructor_spans[index];
@@ -584,6 +687,13 @@ impl Dispatch<'_> {
         };
         let any_constructor_accept_payment =
             self.any_constructor_accepts_payment_expr(constructor_spans);
+        let constructor_refunds = self
+            .contract
+            .module()
+            .impls()
+            .flat_map(|item_impl| item_impl.iter_constructors())
+            .map(|constructor| constructor.refund_excess())
+            .collect::<Vec<_>>();
 
         let constructor_execute = (0..count_constructors).map(|index| {
             let constructor_span = constructor_spans[index];
@@ -614,12 +724,21 @@ impl Dispatch<'_> {
                     as ::ink::reflect::ConstructorOutput::<#storage_ident>>
             );
 
+            let refund_excess = constructor_refunds[index].map(|minimum| {
+                let minimum = proc_macro2::Literal::u128_unsuffixed(minimum);
+                quote_spanned!(constructor_span=>
+                    ::ink::codegen::refund_excess_endowment::<
+                        <#storage_ident as ::ink::reflect::ContractEnv>::Env>(#minimum)?;
+                )
+            });
+
             quote_spanned!(constructor_span=>
                 Self::#constructor_ident(input) => {
                     if #any_constructor_accept_payment && #deny_payment {
                         ::ink::codegen::deny_payment::<
                             <#storage_ident as ::ink::reflect::ContractEnv>::Env>()?;
                     }
+                    #refund_excess
 
                     let result: #constructor_output = #constructor_callable(input);
                     let output_value = ::ink::reflect::ConstructorOutputValue::new(result);
@@ -630,6 +749,7 @@ impl Dispatch<'_> {
                             &<#storage_ident as ::ink::storage::traits::StorageKey>::KEY,
                             contract,
                         );
+                        #set_storage_version
                     }
 
                     ::ink::env::return_value::<
@@ -729,11 +849,33 @@ impl Dispatch<'_> {
 
         let span = self.contract.module().storage().span();
         let storage_ident = self.contract.module().storage().ident();
+        let migrate_storage =
+            self.contract.module().storage().storage_version().map(|_| {
+                quote_spanned!(span=>
+                    ::ink::storage::migrate::migrate::<#storage_ident>();
+                )
+            });
         let count_messages = self.query_amount_messages();
+        let messages = self
+            .contract
+            .module()
+            .impls()
+            .flat_map(|item_impl| item_impl.iter_messages())
+            .collect::<Vec<_>>();
+        // The inputs of messages with deferred or borrowed inputs are only decoded when
+        // executing them, see `DeferredInput`.
+        let is_deferred = |message: &ir::CallableWithSelector<ir::Message>| {
+            message.has_deferred_inputs() || message.has_borrowed_inputs()
+        };
         let message_variants = (0..count_messages).map(|index| {
             let message_span = message_spans[index];
             let message_ident = message_variant_ident(index);
             let message_input = expand_message_input(message_span, storage_ident, index);
+            if is_deferred(&messages[index]) {
+                return quote_spanned!(message_span=>
+                    #message_ident(::ink::codegen::DeferredInput)
+                )
+            }
             quote_spanned!(message_span=>
                 #message_ident(#message_input)
             )
@@ -750,32 +892,126 @@ impl Dispatch<'_> {
             )
         });
 
-        let message_match = (0..count_messages).map(|index| {
+        let is_sol_abi = self.contract.config().abi() == ir::Abi::Solidity;
+        // Decodes the input of the message at the given index from `input`,
+        // `wildcard` is set when dispatching to it as the wildcard message.
+        //
+        // Messages with borrowed inputs decode their inputs one by one from the
+        // input of the call, which the borrowed inputs point into.
+        let decode_input = |index: usize, wildcard: bool| {
+            let message_span = message_spans[index];
+            let message = &messages[index];
+            if is_sol_abi {
+                // The inputs are ABI encoded as the parameters of the message.
+                let input_bindings = generator::input_bindings(message.inputs());
+                let input_types = generator::input_types(message.inputs());
+                let input_tuple = match input_bindings.len() {
+                    1 => quote! { #( #input_bindings )* },
+                    _ => quote! { ( #( #input_bindings ),* ) },
+                };
+                return quote_spanned!(message_span=>
+                    let ( #( #input_bindings, )* ) =
+                        <::ink::sol::SolEncoded<( #( #input_types, )* )> as ::scale::Decode>::decode(input)
+                            .map_err(|_| ::ink::reflect::DispatchError::InvalidParameters)?
+                            .0;
+                    #input_tuple
+                )
+            }
+            if !message.has_borrowed_inputs() {
+                let message_input =
+                    expand_message_input(message_span, storage_ident, index);
+                // The selector has already been consumed, so it is prepended again
+                // for messages taking the raw input of the call.
+                let input = if wildcard && message.takes_raw_input() {
+                    quote! { &mut ::ink::codegen::SelectorPrefixedInput::new(_invalid, input) }
+                } else {
+                    quote! { input }
+                };
+                return quote_spanned!(message_span=>
+                    <#message_input as ::scale::Decode>::decode(#input)
+                        .map_err(|_| ::ink::reflect::DispatchError::InvalidParameters)?
+                )
+            }
+            let input_bindings = generator::input_bindings(message.inputs());
+            let input_tuple_bindings = match input_bindings.len() {
+                1 => quote! { #( #input_bindings )* },
+                _ => quote! { ( #( #input_bindings ),* ) },
+            };
+            let decode_inputs = message.inputs().map(|input| {
+                let span = input.span();
+                let input_type = generator::input_type_with_lifetime(
+                    &input.ty,
+                    syn::parse_quote! { 'a },
+                );
+                match &*input.ty {
+                    syn::Type::Reference(_) => quote_spanned!(span=>
+                        <#input_type as ::ink::codegen::DecodeBorrowed<'a>>::decode_borrowed(input)
+                    ),
+                    _ => quote_spanned!(span=>
+                        <#input_type as ::scale::Decode>::decode(input)
+                    ),
+                }
+            });
+            quote_spanned!(message_span=>
+                #(
+                    let #input_bindings = #decode_inputs
+                        .map_err(|_| ::ink::reflect::DispatchError::InvalidParameters)?;
+                )*
+                #input_tuple_bindings
+            )
+        };
+        // Decodes the message at the given index, `offset` is the position of its
+        // inputs in the input of the call.
+        //
+        // Messages with deferred or borrowed inputs only record the position of their
+        // inputs, which are decoded when executing them.
+        let decode_message = |index: usize, wildcard: bool, offset: usize| {
             let message_span = message_spans[index];
             let message_ident = message_variant_ident(index);
+            if is_deferred(&messages[index]) {
+                return quote_spanned!(message_span=>
+                    ::core::result::Result::Ok(Self::#message_ident(
+                        ::ink::codegen::DeferredInput::new(#offset)
+                    ))
+                )
+            }
+            let decode_input = decode_input(index, wildcard);
+            quote_spanned!(message_span=>
+                ::core::result::Result::Ok(Self::#message_ident({ #decode_input }))
+            )
+        };
+        let message_match = (0..count_messages).map(|index| {
+            let message_span = message_spans[index];
             let const_ident = format_ident!("MESSAGE_{}", index);
-            let message_input = expand_message_input(message_span, storage_ident, index);
+            let decode_message = decode_message(index, false, 4);
             quote_spanned!(message_span=>
                 #const_ident => {
-                    ::core::result::Result::Ok(Self::#message_ident(
-                        <#message_input as ::scale::Decode>::decode(input)
-                            .map_err(|_| ::ink::reflect::DispatchError::InvalidParameters)?
-                    ))
+                    #decode_message
                 }
             )
         });
+        // Plain transfers come without a selector and are dispatched to the
+        // receive message before decoding one.
+        let possibly_receive_message =
+            self.query_receive_message().map(|receive_index| {
+                let decode_message = decode_message(receive_index, false, 0);
+                quote_spanned!(message_spans[receive_index]=>
+                    if ::ink::codegen::is_plain_transfer::<
+                        <#storage_ident as ::ink::reflect::ContractEnv>::Env,
+                        _,
+                    >(input) {
+                        return { #decode_message }
+                    }
+                )
+            });
         let possibly_wildcard_selector_message = match self.query_wildcard_message() {
             Some(wildcard_index) => {
-                let message_span = message_spans[wildcard_index];
-                let message_ident = message_variant_ident(wildcard_index);
-                let message_input =
-                    expand_message_input(message_span, storage_ident, wildcard_index);
-                quote! {
-                    ::core::result::Result::Ok(Self::#message_ident(
-                        <#message_input as ::scale::Decode>::decode(input)
-                            .map_err(|_| ::ink::reflect::DispatchError::InvalidParameters)?
-                    ))
-                }
+                let offset = if messages[wildcard_index].takes_raw_input() {
+                    0
+                } else {
+                    4
+                };
+                decode_message(wildcard_index, true, offset)
             }
             None => {
                 quote! {
@@ -785,6 +1021,34 @@ impl Dispatch<'_> {
         };
         let any_message_accept_payment =
             self.any_message_accepts_payment_expr(message_spans);
+        let message_non_reentrant = self
+            .contract
+            .module()
+            .impls()
+            .flat_map(|item_impl| item_impl.iter_messages())
+            .map(|message| message.is_non_reentrant())
+            .collect::<Vec<_>>();
+        let message_min_transferred_values = self
+            .contract
+            .module()
+            .impls()
+            .flat_map(|item_impl| item_impl.iter_messages())
+            .map(|message| message.min_transferred_value())
+            .collect::<Vec<_>>();
+        let message_internal_code_hashes = self
+            .contract
+            .module()
+            .impls()
+            .flat_map(|item_impl| item_impl.iter_messages())
+            .map(|message| message.internal_code_hashes().map(<[_]>::to_vec))
+            .collect::<Vec<_>>();
+        let message_required_roles = self
+            .contract
+            .module()
+            .impls()
+            .flat_map(|item_impl| item_impl.iter_messages())
+            .map(|message| message.required_roles().map(<[_]>::to_vec))
+            .collect::<Vec<_>>();
 
         let message_execute = (0..count_messages).map(|index| {
             let message_span = message_spans[index];
@@ -818,28 +1082,228 @@ impl Dispatch<'_> {
                 }>>::MUTATES
             );
 
-            quote_spanned!(message_span=>
-                Self::#message_ident(input) => {
-                    if #any_message_accept_payment && #deny_payment {
-                        ::ink::codegen::deny_payment::<
-                            <#storage_ident as ::ink::reflect::ContractEnv>::Env>()?;
+            let (acquire_lock, release_lock) = if message_non_reentrant[index] {
+                (
+                    Some(quote_spanned!(message_span=>
+                        ::ink::codegen::acquire_reentrancy_lock()?;
+                    )),
+                    Some(quote_spanned!(message_span=>
+                        ::ink::codegen::release_reentrancy_lock();
+                    )),
+                )
+            } else {
+                (None, None)
+            };
+            let ensure_min_transferred_value = message_min_transferred_values[index].map(|minimum| {
+                let minimum = proc_macro2::Literal::u128_unsuffixed(minimum);
+                quote_spanned!(message_span=>
+                    if let ::core::result::Result::Err(error) =
+                        ::ink::codegen::ensure_min_transferred_value::<
+                            <#storage_ident as ::ink::reflect::ContractEnv>::Env>(#minimum)
+                    {
+                        ::ink::env::return_value::<::ink::MessageResult::<#message_output>>(
+                            ::ink::env::ReturnFlags::new_with_reverted(true),
+                            &::ink::MessageResult::Err(error),
+                        )
+                    }
+                )
+            });
+
+            // The borrowed inputs do not live for `'static`, so the message is
+            // called directly instead of via its `CALLABLE`.
+            let call_message = if messages[index].has_borrowed_inputs() {
+                let message_ident = messages[index].ident();
+                let input_bindings = generator::input_bindings(messages[index].inputs());
+                let input_tuple_bindings = match input_bindings.len() {
+                    1 => quote! { #( #input_bindings )* },
+                    _ => quote! { ( #( #input_bindings ),* ) },
+                };
+                quote_spanned!(message_span=>
+                    {
+                        let #input_tuple_bindings = input;
+                        #storage_ident::#message_ident(&mut contract #( , #input_bindings )* )
                     }
+                )
+            } else {
+                quote_spanned!(message_span=>
+                    #message_callable(&mut contract, input)
+                )
+            };
 
-                    let result: #message_output = #message_callable(&mut contract, input);
-                    let is_reverted = ::ink::is_result_type!(#message_output)
-                        && ::ink::is_result_err!(result);
+            let ensure_internal_caller = message_internal_code_hashes[index].as_ref().map(|code_hashes| {
+                let code_hashes = code_hashes.iter().map(|code_hash| {
+                    quote! { [ #( #code_hash ),* ] }
+                });
+                quote_spanned!(message_span=>
+                    if let ::core::result::Result::Err(error) =
+                        ::ink::codegen::ensure_internal_caller::<
+                            <#storage_ident as ::ink::reflect::ContractEnv>::Env>(&[ #( #code_hashes ),* ])
+                    {
+                        ::ink::env::return_value::<::ink::MessageResult::<#message_output>>(
+                            ::ink::env::ReturnFlags::new_with_reverted(true),
+                            &::ink::MessageResult::Err(error),
+                        )
+                    }
+                )
+            });
 
-                    // no need to push back results: transaction gets reverted anyways
-                    if !is_reverted {
-                        push_contract(contract, #mutates_storage);
+            let ensure_any_role = message_required_roles[index].as_ref().map(|roles| {
+                let roles = roles.iter().map(|role| {
+                    let mut output = [0x00_u8; 32];
+                    ir::blake2b_256(role.as_bytes(), &mut output);
+                    quote! { [ #( #output ),* ] }
+                });
+                quote_spanned!(message_span=>
+                    if let ::core::result::Result::Err(error) = ::ink::codegen::ensure_any_role(
+                        <#storage_ident as ::ink::contrib::AccessControlled<
+                            <#storage_ident as ::ink::reflect::ContractEnv>::Env>>::access_control(&mut contract),
+                        &[ #( #roles ),* ],
+                    ) {
+                        ::ink::env::return_value::<::ink::MessageResult::<#message_output>>(
+                            ::ink::env::ReturnFlags::new_with_reverted(true),
+                            &::ink::MessageResult::Err(error),
+                        )
                     }
+                )
+            });
 
+            let return_value = if is_sol_abi {
+                quote_spanned!(message_span=>
+                    ::ink::env::return_value::<::ink::sol::SolEncoded<(&#message_output,)>>(
+                        ::ink::env::ReturnFlags::new_with_reverted(is_reverted),
+                        &::ink::sol::SolEncoded((&result,)),
+                    )
+                )
+            } else {
+                quote_spanned!(message_span=>
                     ::ink::env::return_value::<::ink::MessageResult::<#message_output>>(
                         ::ink::env::ReturnFlags::new_with_reverted(is_reverted),
                         // Currently no `LangError`s are raised at this level of the
                         // dispatch logic so `Ok` is always returned to the caller.
                         &::ink::MessageResult::Ok(result),
                     )
+                )
+            };
+
+            let checks = quote_spanned!(message_span=>
+                #ensure_internal_caller
+                #ensure_any_role
+                if #any_message_accept_payment && #deny_payment {
+                    ::ink::codegen::deny_payment::<
+                        <#storage_ident as ::ink::reflect::ContractEnv>::Env>()?;
+                }
+                #ensure_min_transferred_value
+            );
+            // Calls to `timelocked` messages are queued instead of executed, and
+            // return the `OperationId` of the queued call.
+            if let Some(delay) = messages[index].timelock_delay() {
+                let message_selector = quote_spanned!(message_span=>
+                    <#storage_ident as ::ink::reflect::DispatchableMessageInfo<{
+                        <#storage_ident as ::ink::reflect::ContractDispatchableMessages<{
+                            <#storage_ident as ::ink::reflect::ContractAmountDispatchables>::MESSAGES
+                        }>>::IDS[#index]
+                    }>>::SELECTOR
+                );
+                return quote_spanned!(message_span=>
+                    Self::#message_ident(input) => {
+                        #checks
+                        #acquire_lock
+
+                        let id = <#storage_ident as ::ink::contrib::Timelocked>::timelock(&mut contract)
+                            .schedule(
+                                #message_selector,
+                                ::scale::Encode::encode(&input),
+                                #delay,
+                            );
+                        push_contract(contract, true);
+                        #release_lock
+
+                        ::ink::env::return_value::<::ink::MessageResult::<::ink::contrib::OperationId>>(
+                            ::ink::env::ReturnFlags::new_with_reverted(false),
+                            &::ink::MessageResult::Ok(id),
+                        )
+                    }
+                )
+            }
+
+            let execute = quote_spanned!(message_span=>
+                #acquire_lock
+
+                let result: #message_output = #call_message;
+                let is_reverted = ::ink::is_result_type!(#message_output)
+                    && ::ink::is_result_err!(result);
+
+                // no need to push back results: transaction gets reverted anyways
+                if !is_reverted {
+                    push_contract(contract, #mutates_storage);
+                }
+                #release_lock
+
+                #return_value
+            );
+            if !is_deferred(&messages[index]) {
+                return quote_spanned!(message_span=>
+                    Self::#message_ident(input) => {
+                        #checks
+                        #execute
+                    }
+                )
+            }
+
+            // The inputs are decoded once the checks have passed, see `DeferredInput`.
+            let has_borrowed_inputs = messages[index].has_borrowed_inputs();
+            let decoded_input = if has_borrowed_inputs {
+                let input_types = messages[index].inputs().map(|input| {
+                    generator::input_type_with_lifetime(&input.ty, syn::parse_quote! { 'a })
+                });
+                quote! { ( #( #input_types ),* ) }
+            } else {
+                expand_message_input(message_span, storage_ident, index)
+            };
+            let decode_input = decode_input(index, false);
+            let decode_input_fn = quote_spanned!(message_span=>
+                fn decode_input<'a>(
+                    input: &mut &'a [::core::primitive::u8],
+                ) -> ::core::result::Result<#decoded_input, ::ink::reflect::DispatchError> {
+                    ::core::result::Result::Ok({ #decode_input })
+                }
+            );
+            let return_decoding_error = quote_spanned!(message_span=>
+                ::ink::env::return_value::<::ink::MessageResult::<#message_output>>(
+                    ::ink::env::ReturnFlags::new_with_reverted(true),
+                    &::ink::MessageResult::Err(::ink::LangError::CouldNotReadInput),
+                )
+            );
+            if !has_borrowed_inputs {
+                // The owned inputs are decoded before executing the message, so that
+                // the input of the call is no longer lent while it executes.
+                return quote_spanned!(message_span=>
+                    Self::#message_ident(deferred_input) => {
+                        #checks
+                        #decode_input_fn
+                        let input = match deferred_input.with_input(decode_input) {
+                            ::core::result::Result::Ok(input) => input,
+                            ::core::result::Result::Err(_decoding_error) => {
+                                #return_decoding_error
+                            }
+                        };
+                        #execute
+                    }
+                )
+            }
+            quote_spanned!(message_span=>
+                Self::#message_ident(deferred_input) => {
+                    #checks
+                    return deferred_input.with_input(|input| -> ::core::result::Result<(), ::ink::reflect::DispatchError> {
+                        #decode_input_fn
+                        let input = match decode_input(input) {
+                            ::core::result::Result::Ok(input) => input,
+                            ::core::result::Result::Err(_decoding_error) => {
+                                #return_decoding_error
+                            }
+                        };
+                        #execute
+                    })
                 }
             )
         });
@@ -860,11 +1324,12 @@ impl Dispatch<'_> {
                         #(
                             #message_selector
                         )*
+                        #possibly_receive_message
                         match <[::core::primitive::u8; 4usize] as ::scale::Decode>::decode(input)
                             .map_err(|_| ::ink::reflect::DispatchError::InvalidSelector)?
                         {
                             #( #message_match , )*
-                            _invalid => #possibly_wildcard_selector_message
+                            _invalid => { #possibly_wildcard_selector_message }
                         }
                     }
                 }
@@ -893,6 +1358,7 @@ impl Dispatch<'_> {
                     fn execute_dispatchable(
                         self
                     ) -> ::core::result::Result<(), ::ink::reflect::DispatchError> {
+                        #migrate_storage
                         let key = <#storage_ident as ::ink::storage::traits::StorageKey>::KEY;
                         let mut contract: ::core::mem::ManuallyDrop<#storage_ident> =
                             ::core::mem::ManuallyDrop::new(
@@ -920,6 +1386,146 @@ impl Dispatch<'_> {
         )
     }
 
+    /// Generates the `execute_timelocked` and `cancel_timelocked` methods for the
+    /// queued calls to the `timelocked` ink! messages, if any.
+    ///
+    /// # Note
+    ///
+    /// Executing a queued call applies the `internal`, `only(roles)` and
+    /// `non_reentrant` guards of the message to the executing caller, just like
+    /// a direct call of the message.
+    fn generate_timelock_execution(
+        &self,
+        message_spans: &[proc_macro2::Span],
+    ) -> Option<TokenStream2> {
+        let span = self.contract.module().storage().span();
+        let storage_ident = self.contract.module().storage().ident();
+        let messages = self
+            .contract
+            .module()
+            .impls()
+            .flat_map(|item_impl| item_impl.iter_messages())
+            .collect::<Vec<_>>();
+        if messages
+            .iter()
+            .all(|message| message.timelock_delay().is_none())
+        {
+            return None
+        }
+        let arms = messages
+            .iter()
+            .enumerate()
+            .filter(|(_, message)| message.timelock_delay().is_some())
+            .map(|(index, message)| {
+                let message_span = message_spans[index];
+                let message_info = quote_spanned!(message_span=>
+                    <#storage_ident as ::ink::reflect::DispatchableMessageInfo<{
+                        <#storage_ident as ::ink::reflect::ContractDispatchableMessages<{
+                            <#storage_ident as ::ink::reflect::ContractAmountDispatchables>::MESSAGES
+                        }>>::IDS[#index]
+                    }>>
+                );
+                let ensure_internal_caller = message.internal_code_hashes().map(|code_hashes| {
+                    let code_hashes = code_hashes.iter().map(|code_hash| {
+                        quote! { [ #( #code_hash ),* ] }
+                    });
+                    quote_spanned!(message_span=>
+                        ::ink::codegen::ensure_internal_caller::<
+                            <#storage_ident as ::ink::reflect::ContractEnv>::Env>(&[ #( #code_hashes ),* ])
+                            .map_err(|_| ::ink::contrib::TimelockError::CallerNotAllowed)?;
+                    )
+                });
+                let ensure_any_role = message.required_roles().map(|roles| {
+                    let roles = roles.iter().map(|role| {
+                        let mut output = [0x00_u8; 32];
+                        ir::blake2b_256(role.as_bytes(), &mut output);
+                        quote! { [ #( #output ),* ] }
+                    });
+                    quote_spanned!(message_span=>
+                        ::ink::codegen::ensure_any_role(
+                            <Self as ::ink::contrib::AccessControlled<
+                                <Self as ::ink::reflect::ContractEnv>::Env>>::access_control(self),
+                            &[ #( #roles ),* ],
+                        )
+                        .map_err(|_| ::ink::contrib::TimelockError::CallerNotAllowed)?;
+                    )
+                });
+                let (acquire_lock, release_lock) = if message.is_non_reentrant() {
+                    (
+                        Some(quote_spanned!(message_span=>
+                            ::ink::codegen::acquire_reentrancy_lock()
+                                .map_err(|_| ::ink::contrib::TimelockError::ReentrantCall)?;
+                        )),
+                        Some(quote_spanned!(message_span=>
+                            ::ink::codegen::release_reentrancy_lock();
+                        )),
+                    )
+                } else {
+                    (None, None)
+                };
+                quote_spanned!(message_span=>
+                    selector if selector == #message_info::SELECTOR => {
+                        #ensure_internal_caller
+                        #ensure_any_role
+                        let input = <#message_info::Input as ::scale::Decode>::decode(
+                            &mut operation.input(),
+                        )
+                        .map_err(|_| ::ink::contrib::TimelockError::InvalidInput)?;
+                        <Self as ::ink::contrib::Timelocked>::timelock(self).take_ready(id)?;
+                        #acquire_lock
+                        #message_info::CALLABLE(self, input);
+                        #release_lock
+                        ::core::result::Result::Ok(())
+                    }
+                )
+            });
+        Some(quote_spanned!(span=>
+            impl #storage_ident {
+                /// Executes the queued call to a `timelocked` ink! message with the
+                /// given `id` once its delay has passed.
+                ///
+                /// The guards of the message, e.g. `internal` or `only(roles)`, are
+                /// applied to the caller of the contract.
+                ///
+                /// # Errors
+                ///
+                /// - If no call with the given `id` is queued.
+                /// - If the delay of the call has not passed yet.
+                /// - If the caller does not pass the guards of the message.
+                /// - If the input of the call does not match its message anymore.
+                pub fn execute_timelocked(
+                    &mut self,
+                    id: ::ink::contrib::OperationId,
+                ) -> ::core::result::Result<(), ::ink::contrib::TimelockError> {
+                    let operation =
+                        <Self as ::ink::contrib::Timelocked>::timelock(self).ready(id)?;
+                    match operation.selector() {
+                        #( #arms )*
+                        _ => ::core::result::Result::Err(
+                            ::ink::contrib::TimelockError::InvalidInput
+                        ),
+                    }
+                }
+
+                /// Cancels the queued call to a `timelocked` ink! message with the
+                /// given `id`.
+                ///
+                /// # Errors
+                ///
+                /// - If no call with the given `id` is queued.
+                /// - If the caller is not the account which queued the call.
+                pub fn cancel_timelocked(
+                    &mut self,
+                    id: ::ink::contrib::OperationId,
+                ) -> ::core::result::Result<(), ::ink::contrib::TimelockError> {
+                    <Self as ::ink::contrib::Timelocked>::timelock(self)
+                        .cancel_scheduled(id)
+                        .map(|_| ())
+                }
+            }
+        ))
+    }
+
     /// Generates code to express if any dispatchable ink! message accepts payment.
     ///
     /// This information can be used to speed-up dispatch since denying of payment
@@ -0,0 +1,275 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::GenerateCode;
+use derive_more::From;
+use ir::IsDocAttribute as _;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{
+    format_ident,
+    quote,
+    quote_spanned,
+};
+use syn::spanned::Spanned as _;
+
+/// Generates code for the ink! error enums of the contract.
+///
+/// The variants of ink! errors are encoded with their stable 4-byte error codes,
+/// see `ink::reflect::ContractError` for the layout.
+#[derive(From)]
+pub struct Errors<'a> {
+    contract: &'a ir::Contract,
+}
+impl_as_ref_for_generator!(Errors);
+
+impl GenerateCode for Errors<'_> {
+    fn generate_code(&self) -> TokenStream2 {
+        let errors = self.contract.module().errors().map(|error| {
+            let item = error.item();
+            let contract_error_impl = Self::generate_contract_error_impl(error);
+            let encode_impl = Self::generate_encode_impl(error);
+            let decode_impl = Self::generate_decode_impl(error);
+            let type_info_impl = Self::generate_type_info_impl(error);
+            quote! {
+                #item
+
+                const _: () = {
+                    #contract_error_impl
+                    #encode_impl
+                    #decode_impl
+                    #type_info_impl
+                };
+            }
+        });
+        quote! {
+            #( #errors )*
+        }
+    }
+}
+
+/// The variant of an ink! error with its index, error code and field bindings.
+///
+/// The index is only used to describe the variant in the metadata, it is not part
+/// of the encoding.
+struct Variant<'a> {
+    variant: &'a syn::Variant,
+    index: u8,
+    code: [u8; 4],
+    bindings: Vec<syn::Ident>,
+}
+
+impl<'a> Variant<'a> {
+    /// Returns the variants of the ink! error in declaration order.
+    fn all(error: &'a ir::Error) -> impl Iterator<Item = Variant<'a>> + 'a {
+        error
+            .variants()
+            .enumerate()
+            .map(|(index, (variant, code))| {
+                let bindings = variant
+                    .fields
+                    .iter()
+                    .enumerate()
+                    .map(|(n, field)| {
+                        field
+                            .ident
+                            .clone()
+                            .unwrap_or_else(|| format_ident!("__ink_field_{}", n))
+                    })
+                    .collect();
+                Variant {
+                    variant,
+                    index: u8::try_from(index).expect(
+                        "encountered ink! error enum with more than 256 variants",
+                    ),
+                    code,
+                    bindings,
+                }
+            })
+    }
+
+    /// Returns the pattern matching the variant and binding its fields.
+    fn pattern(&self) -> TokenStream2 {
+        let ident = &self.variant.ident;
+        let bindings = &self.bindings;
+        match &self.variant.fields {
+            syn::Fields::Named(_) => quote! { Self::#ident { #( #bindings ),* } },
+            syn::Fields::Unnamed(_) => quote! { Self::#ident ( #( #bindings ),* ) },
+            syn::Fields::Unit => quote! { Self::#ident },
+        }
+    }
+}
+
+impl Errors<'_> {
+    /// Generates the `ContractError` implementation of the ink! error.
+    fn generate_contract_error_impl(error: &ir::Error) -> TokenStream2 {
+        let ident = error.ident();
+        let names = error
+            .variants()
+            .map(|(variant, code)| {
+                let name = variant.ident.to_string();
+                quote! { (#name, [ #( #code ),* ]) }
+            })
+            .collect::<Vec<_>>();
+        let arms = Variant::all(error).map(|variant| {
+            let ident = &variant.variant.ident;
+            let code = variant.code;
+            quote! { Self::#ident { .. } => [ #( #code ),* ], }
+        });
+        quote! {
+            impl ::ink::reflect::ContractError for #ident {
+                const ERROR_CODES: &'static [(&'static ::core::primitive::str, [::core::primitive::u8; 4])] = &[
+                    #( #names ),*
+                ];
+
+                fn error_code(&self) -> [::core::primitive::u8; 4] {
+                    match self {
+                        #( #arms )*
+                    }
+                }
+            }
+        }
+    }
+
+    /// Generates the `scale::Encode` implementation of the ink! error.
+    fn generate_encode_impl(error: &ir::Error) -> TokenStream2 {
+        let ident = error.ident();
+        let arms = Variant::all(error).map(|variant| {
+            let span = variant.variant.span();
+            let pattern = variant.pattern();
+            let code = variant.code;
+            let bindings = &variant.bindings;
+            quote_spanned!(span=>
+                #pattern => {
+                    ::scale::Encode::encode_to(&[ #( #code ),* ], __ink_dest);
+                    #( ::scale::Encode::encode_to(#bindings, __ink_dest); )*
+                }
+            )
+        });
+        quote! {
+            impl ::scale::Encode for #ident {
+                fn encode_to<__ink_O>(&self, __ink_dest: &mut __ink_O)
+                where
+                    __ink_O: ::scale::Output + ?::core::marker::Sized,
+                {
+                    match self {
+                        #( #arms )*
+                    }
+                }
+            }
+
+            impl ::scale::EncodeLike for #ident {}
+        }
+    }
+
+    /// Generates the `scale::Decode` implementation of the ink! error.
+    ///
+    /// Decoding fails if the error code does not match any of the variants.
+    fn generate_decode_impl(error: &ir::Error) -> TokenStream2 {
+        let ident = error.ident();
+        let arms = Variant::all(error).map(|variant| {
+            let span = variant.variant.span();
+            let pattern = variant.pattern();
+            let code = variant.code;
+            let bindings = &variant.bindings;
+            quote_spanned!(span=>
+                [ #( #code ),* ] => {
+                    #( let #bindings = ::scale::Decode::decode(__ink_input)?; )*
+                    ::core::result::Result::Ok(#pattern)
+                }
+            )
+        });
+        quote! {
+            impl ::scale::Decode for #ident {
+                fn decode<__ink_I>(
+                    __ink_input: &mut __ink_I,
+                ) -> ::core::result::Result<Self, ::scale::Error>
+                where
+                    __ink_I: ::scale::Input,
+                {
+                    let code = <[::core::primitive::u8; 4] as ::scale::Decode>::decode(__ink_input)?;
+                    match code {
+                        #( #arms )*
+                        _ => ::core::result::Result::Err(
+                            ::core::convert::Into::into("unknown ink! error code")
+                        ),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Generates the `scale_info::TypeInfo` implementation of the ink! error.
+    ///
+    /// The error code is described as the leading `[u8; 4]` field of every variant.
+    /// The variant indices follow the declaration order but are not encoded.
+    fn generate_type_info_impl(error: &ir::Error) -> TokenStream2 {
+        let ident = error.ident();
+        let ident_str = ident.to_string();
+        let docs = error.attrs().iter().filter_map(|attr| attr.extract_docs());
+        let error_code_field = ir::Error::error_code_field();
+        let variants = Variant::all(error).map(|variant| {
+            let span = variant.variant.span();
+            let name = variant.variant.ident.to_string();
+            let index = variant.index;
+            let docs = variant
+                .variant
+                .attrs
+                .iter()
+                .filter_map(|attr| attr.extract_docs());
+            let fields = variant.variant.fields.iter().map(|field| {
+                let ty = &field.ty;
+                let type_name = quote!(#ty).to_string().replace(' ', "");
+                let name = field.ident.as_ref().map(|ident| {
+                    let name = ident.to_string();
+                    quote! { .name(#name) }
+                });
+                quote_spanned!(field.span()=>
+                    .field(|f| f.ty::<#ty>()#name.type_name(#type_name))
+                )
+            });
+            let (fields_builder, code_name) = match &variant.variant.fields {
+                syn::Fields::Named(_) => {
+                    (quote! { named() }, Some(quote! { .name(#error_code_field) }))
+                }
+                syn::Fields::Unnamed(_) | syn::Fields::Unit => (quote! { unnamed() }, None),
+            };
+            quote_spanned!(span=>
+                .variant(#name, |v| v
+                    .index(#index)
+                    .fields(::scale_info::build::Fields::#fields_builder
+                        .field(|f| f.ty::<[::core::primitive::u8; 4]>()#code_name.type_name("[u8; 4]"))
+                        #( #fields )*
+                    )
+                    .docs(&[ #( #docs ),* ])
+                )
+            )
+        });
+        quote! {
+            #[cfg(feature = "std")]
+            impl ::scale_info::TypeInfo for #ident {
+                type Identity = Self;
+
+                fn type_info() -> ::scale_info::Type {
+                    ::scale_info::Type::builder()
+                        .path(::scale_info::Path::new(#ident_str, ::core::module_path!()))
+                        .docs(&[ #( #docs ),* ])
+                        .variant(
+                            ::scale_info::build::Variants::new()
+                                #( #variants )*
+                        )
+                }
+            }
+        }
+    }
+}
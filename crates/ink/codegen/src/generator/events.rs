@@ -116,6 +116,24 @@ impl<'a> Events<'a> {
                 };
             )*
 
+            #(
+                #[cfg(feature = "std")]
+                const _: () = {
+                    impl ::ink::env::test::DecodeEvent for #event_idents {
+                        fn decode_event(
+                            event: &::ink::env::test::EmittedEvent,
+                        ) -> ::core::option::Option<Self> {
+                            match <#base_event_ident as ::scale::Decode>::decode(&mut &event.data[..]) {
+                                ::core::result::Result::Ok(#base_event_ident::#event_idents(event)) => {
+                                    ::core::option::Option::Some(event)
+                                }
+                                _ => ::core::option::Option::None,
+                            }
+                        }
+                    }
+                };
+            )*
+
             const _: () = {
                 pub enum __ink_UndefinedAmountOfTopics {}
                 impl ::ink::env::topics::EventTopicsAmount for __ink_UndefinedAmountOfTopics {
@@ -207,6 +225,26 @@ impl<'a> Events<'a> {
                         format!("{}::{}::{}", contract_ident, event_ident,
                             field_ident
                         ).as_bytes(), span);
+                    if topic_field.is_hashed_topic {
+                        // Hashed topics are pushed as the `Blake2x256` hash of their
+                        // SCALE encoded value in order to keep large fields fixed-size.
+                        // The hash fits into a topic, so it is emitted as it is. It is
+                        // computed without `ink::env::hash_encoded`, which must not be
+                        // called while the event is being emitted.
+                        return quote_spanned!(span =>
+                            .push_topic::<[::core::primitive::u8; 32]>(&{
+                                let mut output = <::ink::env::hash::Blake2x256 as ::ink::env::hash::HashOutput>::Type::default();
+                                <#field_type as ::scale::Encode>::using_encoded(
+                                    &self.#field_ident,
+                                    |encoded| <::ink::env::hash::Blake2x256 as ::ink::env::hash::CryptoHash>::hash(
+                                        encoded,
+                                        &mut output,
+                                    ),
+                                );
+                                output
+                            })
+                        )
+                    }
                     quote_spanned!(span =>
                         .push_topic::<::ink::env::topics::PrefixedValue<#field_type>>(
                             &::ink::env::topics::PrefixedValue { value: &self.#field_ident, prefix: #signature }
@@ -214,9 +252,14 @@ impl<'a> Events<'a> {
                     )
                 });
             // Only include topic for event signature in case of non-anonymous event.
-            let event_signature_topic = match event.anonymous {
-                true => None,
-                false => Some(quote_spanned!(span=>
+            let event_signature_topic = match (event.anonymous, event.signature_topic()) {
+                (true, _) => None,
+                // User provided signature topics are pushed as they are in order to
+                // match the topics of EVM events.
+                (false, Some(signature_topic)) => Some(quote_spanned!(span=>
+                    .push_topic::<[::core::primitive::u8; 32]>(&[ #( #signature_topic ),* ])
+                )),
+                (false, None) => Some(quote_spanned!(span=>
                     .push_topic::<::ink::env::topics::PrefixedValue<[u8; #len_event_signature]>>(
                         &::ink::env::topics::PrefixedValue { value: #event_signature, prefix: b"" }
                     )
@@ -35,6 +35,7 @@ impl GenerateCode for InkTest<'_> {
         let fn_block = &item_fn.block;
         let vis = &item_fn.vis;
         let fn_args = &sig.inputs;
+        let setup = self.generate_setup();
         let expect_msg = format!(
             "{}: the off-chain testing environment returned an error",
             stringify!(#fn_name)
@@ -46,6 +47,7 @@ impl GenerateCode for InkTest<'_> {
                     #[test]
                     #vis fn #fn_name( #fn_args ) {
                         ::ink::env::test::run_test::<::ink::env::DefaultEnvironment, _>(|_| {
+                            #setup
                             {
                                 {
                                     #fn_block
@@ -63,6 +65,7 @@ impl GenerateCode for InkTest<'_> {
                     #[test]
                     #vis fn #fn_name( #fn_args ) #rarrow #ret_type {
                         ::ink::env::test::run_test::<::ink::env::DefaultEnvironment, _>(|_| {
+                            #setup
                             #fn_block
                         })
                     }
@@ -72,6 +75,43 @@ impl GenerateCode for InkTest<'_> {
     }
 }
 
+impl InkTest<'_> {
+    /// Generates the code setting up the off-chain environment as configured via
+    /// `#[ink::test(caller = "alice", balance = 1_000, callee_balance = 500)]`.
+    fn generate_setup(&self) -> TokenStream2 {
+        let config = &self.test.config;
+        let caller = config.caller().map(|caller| {
+            quote! {
+                ::ink::env::test::set_caller::<::ink::env::DefaultEnvironment>(
+                    ::ink::env::test::default_accounts::<::ink::env::DefaultEnvironment>()
+                        .#caller,
+                );
+            }
+        });
+        let balance = config.balance().map(|balance| {
+            quote! {
+                ::ink::env::test::set_account_balance::<::ink::env::DefaultEnvironment>(
+                    ::ink::env::caller::<::ink::env::DefaultEnvironment>(),
+                    #balance,
+                );
+            }
+        });
+        let callee_balance = config.callee_balance().map(|callee_balance| {
+            quote! {
+                ::ink::env::test::set_account_balance::<::ink::env::DefaultEnvironment>(
+                    ::ink::env::test::callee::<::ink::env::DefaultEnvironment>(),
+                    #callee_balance,
+                );
+            }
+        });
+        quote! {
+            #caller
+            #balance
+            #callee_balance
+        }
+    }
+}
+
 impl GenerateCode for ir::InkTest {
     fn generate_code(&self) -> TokenStream2 {
         InkTest::from(self).generate_code()
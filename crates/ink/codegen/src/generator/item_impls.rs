@@ -14,7 +14,10 @@
 
 use core::iter;
 
-use crate::GenerateCode;
+use crate::{
+    generator,
+    GenerateCode,
+};
 use derive_more::From;
 use heck::ToLowerCamelCase as _;
 use ir::{
@@ -149,15 +152,19 @@ impl ItemImpls<'_> {
             .flat_map(|item_impl| item_impl.iter_messages())
             .map(|message| {
                 let message_span = message.span();
-                let message_inputs = message.inputs().map(|input| {
-                    let span = input.span();
-                    let input_type = &*input.ty;
-                    quote_spanned!(span=>
-                        ::ink::codegen::utils::consume_type::<
-                            ::ink::codegen::DispatchInput<#input_type>
-                        >();
-                    )
-                });
+                // Borrowed inputs are decoded via `DecodeBorrowed` instead.
+                let message_inputs = message
+                    .inputs()
+                    .filter(|input| !matches!(&*input.ty, syn::Type::Reference(_)))
+                    .map(|input| {
+                        let span = input.span();
+                        let input_type = &*input.ty;
+                        quote_spanned!(span=>
+                            ::ink::codegen::utils::consume_type::<
+                                ::ink::codegen::DispatchInput<#input_type>
+                            >();
+                        )
+                    });
                 let message_output = message.output().map(|output_type| {
                     let span = output_type.span();
                     quote_spanned!(span=>
@@ -166,9 +173,21 @@ impl ItemImpls<'_> {
                         >();
                     )
                 });
+                let paginated_output = message
+                    .output()
+                    .filter(|_| message.is_paginated())
+                    .map(|output_type| {
+                        let span = output_type.span();
+                        quote_spanned!(span=>
+                            ::ink::codegen::utils::consume_type::<
+                                ::ink::codegen::PaginatedOutput<#output_type>
+                            >();
+                        )
+                    });
                 quote_spanned!(message_span=>
                     #( #message_inputs )*
                     #message_output
+                    #paginated_output
                 )
             });
         quote_spanned!(storage_span=>
@@ -180,7 +199,13 @@ impl ItemImpls<'_> {
     }
 
     /// Generates the code for the given ink! message within a trait implementation block.
-    fn generate_trait_message(message: &ir::Message) -> TokenStream2 {
+    ///
+    /// Messages without a body inline the default implementation of the ink! trait
+    /// definition, which is provided by the macro re-exported under the trait path.
+    fn generate_trait_message(
+        message: &ir::Message,
+        trait_path: &syn::Path,
+    ) -> TokenStream2 {
         let span = message.span();
         let attrs = message.attrs();
         let vis = message.visibility();
@@ -193,6 +218,20 @@ impl ItemImpls<'_> {
             .output()
             .cloned()
             .unwrap_or_else(|| syn::parse_quote! { () });
+        if message.uses_trait_default() {
+            let input_bindings = generator::input_bindings(message.inputs());
+            let input_types = generator::input_types(message.inputs());
+            return quote_spanned!(span =>
+                type #output_ident = #output;
+
+                #( #attrs )*
+                #vis fn #ident(
+                    #receiver #( , #input_bindings : #input_types )*
+                ) -> Self::#output_ident {
+                    #trait_path!(#ident, self #( , #input_bindings )* )
+                }
+            )
+        }
         let statements = message.statements();
         quote_spanned!(span =>
             type #output_ident = #output;
@@ -208,16 +247,17 @@ impl ItemImpls<'_> {
         assert!(item_impl.trait_path().is_some());
         let span = item_impl.span();
         let attrs = item_impl.attrs();
-        let messages = item_impl
-            .iter_messages()
-            .map(|cws| Self::generate_trait_message(cws.callable()));
         let trait_path = item_impl
             .trait_path()
             .expect("encountered missing trait path for trait impl block");
+        let messages = item_impl
+            .iter_messages()
+            .map(|cws| Self::generate_trait_message(cws.callable(), trait_path));
         let self_type = item_impl.self_type();
+        let (impl_generics, _, where_clause) = item_impl.generics().split_for_impl();
         quote_spanned!(span =>
             #( #attrs )*
-            impl #trait_path for #self_type {
+            impl #impl_generics #trait_path for #self_type #where_clause {
                 type __ink_TraitInfo = <::ink::reflect::TraitDefinitionRegistry<Environment>
                     as #trait_path>::__ink_TraitInfo;
 
@@ -244,6 +284,9 @@ impl ItemImpls<'_> {
             #( #attrs )*
             #[cfg(not(feature = "__ink_dylint_Constructor"))]
             #vis fn #ident( #( #inputs ),* ) -> #output {
+                // Attributes the events emitted by the constructor to it in the
+                // off-chain environment.
+                let __ink_constructor_scope = ::ink::codegen::ConstructorScope::enter();
                 #( #statements )*
             }
         )
@@ -284,9 +327,10 @@ impl ItemImpls<'_> {
             .filter_map(ir::ImplItem::filter_map_other_item)
             .map(ToTokens::to_token_stream);
         let self_type = item_impl.self_type();
+        let (impl_generics, _, where_clause) = item_impl.generics().split_for_impl();
         quote_spanned!(span =>
             #( #attrs )*
-            impl #self_type {
+            impl #impl_generics #self_type #where_clause {
                 #( #constructors )*
                 #( #messages )*
                 #( #other_items )*
@@ -296,13 +340,28 @@ impl ItemImpls<'_> {
 
     /// Generates code to guard against ink! implementations that have not been implemented
     /// for the ink! storage struct.
+    ///
+    /// Generic implementation blocks must be implemented for the generic ink! storage
+    /// struct with any type parameter.
     fn generate_item_impl_self_ty_guard(&self, item_impl: &ir::ItemImpl) -> TokenStream2 {
         let self_ty = item_impl.self_type();
         let span = self_ty.span();
         let storage_ident = self.contract.module().storage().ident();
+        if item_impl.generics().params.is_empty() {
+            return quote_spanned!(span =>
+                const _: ::ink::codegen::utils::IsSameType<#storage_ident> =
+                    ::ink::codegen::utils::IsSameType::<#self_ty>::new();
+            )
+        }
+        let (impl_generics, _, where_clause) = item_impl.generics().split_for_impl();
         quote_spanned!(span =>
-            const _: ::ink::codegen::utils::IsSameType<#storage_ident> =
-                ::ink::codegen::utils::IsSameType::<#self_ty>::new();
+            const _: () = {
+                #[allow(dead_code)]
+                fn __ink_self_ty_guard #impl_generics () #where_clause {
+                    let _: ::ink::codegen::utils::IsSameType<#storage_ident<_>> =
+                        ::ink::codegen::utils::IsSameType::<#self_ty>::new();
+                }
+            };
         )
     }
 
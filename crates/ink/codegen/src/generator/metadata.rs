@@ -12,7 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::GenerateCode;
+use crate::{
+    generator,
+    GenerateCode,
+};
 use ::core::iter;
 use derive_more::From;
 use ir::{
@@ -42,10 +45,34 @@ impl GenerateCode for Metadata<'_> {
     fn generate_code(&self) -> TokenStream2 {
         let contract = self.generate_contract();
         let layout = self.generate_layout();
+        let entry_cfg = self
+            .contract
+            .config()
+            .entry()
+            .map(|entry| quote! { #[cfg(feature = #entry)] });
+        let embedded_metadata_hash =
+            self.contract.config().embed_metadata_hash().then(|| {
+                quote! {
+                    #[cfg(not(feature = "std"))]
+                    #[cfg(not(feature = "ink-as-dependency"))]
+                    #entry_cfg
+                    const _: () = {
+                        const METADATA_HASH: ::core::option::Option<[::core::primitive::u8; 32]> =
+                            ::ink::metadata_hash!();
+
+                        #[link_section = "ink_metadata_hash"]
+                        static __INK_METADATA_HASH: [
+                            ::core::primitive::u8;
+                            ::ink::codegen::metadata_hash_section_len(METADATA_HASH)
+                        ] = ::ink::codegen::metadata_hash_section(METADATA_HASH);
+                    };
+                }
+            });
 
         quote! {
             #[cfg(feature = "std")]
             #[cfg(not(feature = "ink-as-dependency"))]
+            #entry_cfg
             const _: () = {
                 #[no_mangle]
                 pub fn __ink_generate_metadata() -> ::ink::metadata::InkProject  {
@@ -56,6 +83,8 @@ impl GenerateCode for Metadata<'_> {
                     ::ink::metadata::InkProject::new(layout, #contract)
                 }
             };
+
+            #embedded_metadata_hash
         }
     }
 }
@@ -168,7 +197,10 @@ impl Metadata<'_> {
             syn::Pat::Ident(ident) => &ident.ident,
             _ => unreachable!("encountered ink! dispatch input with missing identifier"),
         };
-        let type_spec = Self::generate_type_spec(&pat_type.ty);
+        let type_spec = Self::generate_type_spec(&generator::input_type_with_lifetime(
+            &pat_type.ty,
+            syn::parse_quote! { 'static },
+        ));
         quote! {
             ::ink::metadata::MessageParamSpec::new(::core::stringify!(#ident))
                 .of_type(#type_spec)
@@ -224,15 +256,27 @@ impl Metadata<'_> {
             .module()
             .impls()
             .filter(|item_impl| item_impl.trait_path().is_none())
-            .flat_map(|item_impl| item_impl.iter_messages())
-            .map(|message| {
+            .flat_map(|item_impl| {
+                iter::repeat(item_impl.namespace()).zip(item_impl.iter_messages())
+            })
+            .map(|(namespace, message)| {
                 let span = message.span();
+                let namespace = namespace.map(|namespace| {
+                    let namespace = String::from_utf8_lossy(namespace.as_bytes());
+                    quote_spanned!(span=> .namespace(#namespace))
+                });
                 let docs = message
                     .attrs()
                     .iter()
                     .filter_map(|attr| attr.extract_docs());
                 let selector_bytes = message.composed_selector().hex_lits();
                 let is_payable = message.is_payable();
+                let abi_version = message
+                    .abi_version()
+                    .map(|version| quote_spanned!(span=> .abi_version(#version)));
+                let paginated = message
+                    .is_paginated()
+                    .then(|| quote_spanned!(span=> .paginated(true)));
                 let message = message.callable();
                 let mutates = message.receiver().is_ref_mut();
                 let ident = message.ident();
@@ -252,6 +296,9 @@ impl Metadata<'_> {
                         .docs([
                             #( #docs ),*
                         ])
+                        #namespace
+                        #abi_version
+                        #paginated
                         .done()
                 )
             })
@@ -286,6 +333,9 @@ impl Metadata<'_> {
                     .inputs()
                     .map(Self::generate_dispatch_argument);
                 let mutates = message.receiver().is_ref_mut();
+                let abi_version = message
+                    .abi_version()
+                    .map(|version| quote_spanned!(message_span=> .abi_version(#version)));
                 let local_id = message.local_id().hex_padded_suffixed();
                 let is_payable = quote! {{
                     <<::ink::reflect::TraitDefinitionRegistry<<#storage_ident as ::ink::reflect::ContractEnv>::Env>
@@ -311,6 +361,7 @@ impl Metadata<'_> {
                         .docs([
                             #( #message_docs ),*
                         ])
+                        #abi_version
                         .done()
                 )
             })
@@ -364,6 +415,11 @@ impl Metadata<'_> {
             let ident = event.ident();
             let docs = event.attrs().iter().filter_map(|attr| attr.extract_docs());
             let args = Self::generate_event_args(event);
+            let signature_topic = event.signature_topic().map(|signature_topic| {
+                quote_spanned!(span =>
+                    .signature_topic([ #( #signature_topic ),* ])
+                )
+            });
             quote_spanned!(span =>
                 ::ink::metadata::EventSpec::new(::core::stringify!(#ident))
                     .args([
@@ -372,6 +428,7 @@ impl Metadata<'_> {
                     .docs([
                         #( #docs ),*
                     ])
+                    #signature_topic
                     .done()
             )
         })
@@ -383,6 +440,10 @@ impl Metadata<'_> {
             let span = event_field.span();
             let ident = event_field.ident();
             let is_topic = event_field.is_topic;
+            let is_hashed_topic = event_field.is_hashed_topic;
+            let decimals = event_field
+                .decimals
+                .map(|decimals| quote_spanned!(span => .decimals(#decimals)));
             let docs = event_field
                 .attrs()
                 .into_iter()
@@ -392,6 +453,8 @@ impl Metadata<'_> {
                 ::ink::metadata::EventParamSpec::new(::core::stringify!(#ident))
                     .of_type(#ty)
                     .indexed(#is_topic)
+                    .hashed(#is_hashed_topic)
+                    #decimals
                     .docs([
                         #( #docs ),*
                     ])
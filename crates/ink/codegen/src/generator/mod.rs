@@ -33,6 +33,7 @@ mod chain_extension;
 mod contract;
 mod dispatch;
 mod env;
+mod errors;
 mod events;
 mod ink_test;
 mod item_impls;
@@ -48,6 +49,7 @@ pub use self::{
         generate_reference_to_trait_info,
         input_bindings,
         input_bindings_tuple,
+        input_type_with_lifetime,
         input_types,
         input_types_tuple,
         output_ident,
@@ -58,6 +60,7 @@ pub use self::{
     contract::Contract,
     dispatch::Dispatch,
     env::Env,
+    errors::Errors,
     events::Events,
     ink_test::InkTest,
     item_impls::ItemImpls,
@@ -33,6 +33,7 @@ impl GenerateCode for Storage<'_> {
         let storage_span = self.contract.module().storage().span();
         let access_env_impls = self.generate_access_env_trait_impls();
         let storage_struct = self.generate_storage_struct();
+        let storage_version = self.generate_storage_version();
         let use_emit_event =
             self.contract.module().events().next().is_some().then(|| {
                 // Required to allow for `self.env().emit_event(...)` in messages and constructors.
@@ -41,6 +42,7 @@ impl GenerateCode for Storage<'_> {
         quote_spanned!(storage_span =>
             #storage_struct
             #access_env_impls
+            #storage_version
 
             const _: () = {
                 // Used to make `self.env()` and `Self::env()` available in message code.
@@ -83,6 +85,21 @@ impl Storage<'_> {
         }
     }
 
+    /// Generates the storage layout version if set via `#[ink(storage_version = N)]`.
+    fn generate_storage_version(&self) -> Option<TokenStream2> {
+        let storage = self.contract.module().storage();
+        let version = storage.storage_version()?;
+        let span = storage.span();
+        let ident = storage.ident();
+        Some(quote_spanned!(span=>
+            const _: () = {
+                impl ::ink::storage::migrate::StorageVersion for #ident {
+                    const VERSION: ::core::primitive::u32 = #version;
+                }
+            };
+        ))
+    }
+
     /// Generates the storage struct definition.
     ///
     /// # Developer Note
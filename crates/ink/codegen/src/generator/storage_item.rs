@@ -15,7 +15,6 @@
 use crate::GenerateCode;
 use derive_more::From;
 use proc_macro2::{
-    Ident,
     TokenStream as TokenStream2,
     TokenStream,
 };
@@ -54,7 +53,17 @@ impl GenerateCode for StorageItem<'_> {
         };
 
         let mut derive = quote! {};
-        if self.item.config().derive() {
+        if self.item.config().compact_encoding() {
+            // The item is packed, so that the blanket implementations of the storage
+            // traits for packed types apply to it.
+            derive = quote! {
+                #[cfg_attr(feature = "std", derive(
+                    ::scale_info::TypeInfo,
+                    ::ink::storage::traits::StorageLayout,
+                ))]
+                #[derive(::scale::Encode, ::scale::Decode)]
+            };
+        } else if self.item.config().derive() {
             derive = quote! {
                 #[cfg_attr(feature = "std", derive(
                     ::scale_info::TypeInfo,
@@ -69,9 +78,11 @@ impl GenerateCode for StorageItem<'_> {
         }
 
         let type_check = self.generate_type_check();
+        let packed_collection_warnings = self.generate_packed_collection_warnings();
 
         quote! {
             #type_check
+            #( #packed_collection_warnings )*
 
             #(#attrs)*
             #derive
@@ -87,9 +98,14 @@ impl<'a> StorageItem<'a> {
         let vis = item.vis();
         let generics = item.generics();
         let salt = item.salt();
+        let key_name = self.key_name();
 
         let fields = struct_item.fields.iter().enumerate().map(|(i, field)| {
-            convert_into_storage_field(struct_ident, None, &salt, i, field)
+            if item.config().compact_encoding() {
+                return convert_into_compact_field(field)
+            }
+            let key = item.storage_key(None, i);
+            convert_into_storage_field(&key_name, None, &salt, key, i, field)
         });
 
         match struct_item.fields {
@@ -116,6 +132,7 @@ impl<'a> StorageItem<'a> {
         let vis = item.vis();
         let generics = item.generics();
         let salt = item.salt();
+        let key_name = self.key_name();
 
         let variants = enum_item.variants.into_iter().map(|variant| {
             let attrs = variant.attrs;
@@ -131,10 +148,15 @@ impl<'a> StorageItem<'a> {
                 .iter()
                 .enumerate()
                 .map(|(i, field)| {
+                    if item.config().compact_encoding() {
+                        return convert_into_compact_field(field)
+                    }
+                    let key = item.storage_key(Some(variant_ident), i);
                     convert_into_storage_field(
-                        enum_ident,
+                        &key_name,
                         Some(variant_ident),
                         &salt,
+                        key,
                         i,
                         field,
                     )
@@ -166,6 +188,7 @@ impl<'a> StorageItem<'a> {
         let vis = item.vis();
         let generics = item.generics();
         let salt = item.salt();
+        let key_name = self.key_name();
 
         let fields = union_item
             .fields
@@ -173,7 +196,8 @@ impl<'a> StorageItem<'a> {
             .iter()
             .enumerate()
             .map(|(i, field)| {
-                convert_into_storage_field(union_ident, None, &salt, i, field)
+                let key = item.storage_key(None, i);
+                convert_into_storage_field(&key_name, None, &salt, key, i, field)
             });
 
         quote! {
@@ -183,6 +207,55 @@ impl<'a> StorageItem<'a> {
         }
     }
 
+    /// Returns the name from which the storage keys of the fields are computed.
+    ///
+    /// This is the namespace of the storage item if specified and the name of the
+    /// type otherwise.
+    fn key_name(&self) -> String {
+        self.item
+            .config()
+            .namespace()
+            .map(ToString::to_string)
+            .unwrap_or_else(|| self.item.ident().to_string())
+    }
+
+    /// Generates a warning for every field which packs an unbounded collection.
+    ///
+    /// # Developer Note
+    ///
+    /// Procedural macros can not emit warnings on stable Rust, so the warning is
+    /// raised by using a deprecated item at the span of the field type. Fields whose
+    /// collection is bounded by the contract logic opt out via `#[ink(bounded)]`.
+    fn generate_packed_collection_warnings(&self) -> Vec<TokenStream2> {
+        let item_ident = self.item.ident();
+        self.item
+            .packed_collections()
+            .into_iter()
+            .map(|packed| {
+                let note = format!(
+                    "`{}` packs the unbounded `{}` into the storage cell of `{}`, which \
+                     fails to load once its encoded size exceeds the static buffer of \
+                     16 KiB: consider storing the elements in an \
+                     `ink::storage::Mapping` or the collection in an \
+                     `ink::storage::Lazy`, or flag the field with `#[ink(bounded)]` \
+                     if its size is bounded",
+                    packed.field_name, packed.collection, item_ident,
+                );
+                let span = packed.field.ty.span();
+                let use_warning = quote_spanned!(span=>
+                    let _ = PackedUnboundedCollection;
+                );
+                quote! {
+                    const _: () = {
+                        #[deprecated(note = #note)]
+                        struct PackedUnboundedCollection;
+                        #use_warning
+                    };
+                }
+            })
+            .collect()
+    }
+
     fn generate_type_check(&self) -> TokenStream2 {
         let fields = self
             .item
@@ -210,10 +283,51 @@ impl<'a> StorageItem<'a> {
     }
 }
 
+/// Returns `true` if the type `ty` is an unsigned primitive integer of at least
+/// 32 bits, the compact encoding of which is expected to be smaller than its
+/// default encoding.
+///
+/// Type aliases are not resolved by the macro, so fields with an alias such as
+/// `Balance` are only compacted if flagged with `#[codec(compact)]` explicitly.
+fn is_compact_integer(ty: &Type) -> bool {
+    const COMPACT_INTEGERS: [&str; 3] = ["u32", "u64", "u128"];
+    matches!(
+        ty,
+        Type::Path(type_path) if type_path.qself.is_none()
+            && COMPACT_INTEGERS.iter().any(|ident| type_path.path.is_ident(ident))
+    )
+}
+
+/// Returns `true` if the `field` is flagged with a `#[codec(..)]` attribute.
+fn has_codec_attr(field: &Field) -> bool {
+    field.attrs.iter().any(|attr| attr.path.is_ident("codec"))
+}
+
+/// Converts the `field` of an item stored with the compact encoding.
+///
+/// The item is packed, so the type of the field is kept and primitive integer
+/// fields are flagged to be encoded compactly, unless they configure their
+/// encoding via `#[codec(..)]` themselves.
+fn convert_into_compact_field(field: &Field) -> Field {
+    let mut new_field = field.clone();
+    if is_compact_integer(&field.ty) && !has_codec_attr(field) {
+        new_field
+            .attrs
+            .push(syn::parse_quote! { #[codec(compact)] });
+    }
+    new_field
+}
+
+/// Converts the `field` into its storage type.
+///
+/// The storage key of the field is the manual `key` if set via
+/// `#[ink(storage_key = N)]` and computed from the `key_name` of the type, the name
+/// of the variant and the field otherwise.
 fn convert_into_storage_field(
-    struct_ident: &Ident,
+    key_name: &str,
     variant_ident: Option<&syn::Ident>,
     salt: &TokenStream,
+    key: Option<u32>,
     index: usize,
     field: &Field,
 ) -> Field {
@@ -229,12 +343,14 @@ fn convert_into_storage_field(
         "".to_string()
     };
 
-    let key = ink_primitives::KeyComposer::compute_key(
-        struct_ident.to_string().as_str(),
-        variant_name.as_str(),
-        field_name.as_str(),
-    )
-    .expect("unable to compute the storage key for the field");
+    let key = key.unwrap_or_else(|| {
+        ink_primitives::KeyComposer::compute_key(
+            key_name,
+            variant_name.as_str(),
+            field_name.as_str(),
+        )
+        .expect("unable to compute the storage key for the field")
+    });
 
     let mut new_field = field.clone();
     let ty = field.ty.clone().to_token_stream();
@@ -0,0 +1,133 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generates the macro holding the default implementations of the ink! trait messages.
+//!
+//! The implementers of an ink! trait definition are expanded by `#[ink::contract]`,
+//! which only sees the trait path. Therefore the default implementations are put
+//! into a macro that is re-exported under the name of the trait, so that the
+//! implementers can inline them via `TraitPath!(message, self, inputs..)`.
+
+use super::TraitDefinition;
+use proc_macro2::{
+    Group,
+    TokenStream as TokenStream2,
+    TokenTree,
+};
+use quote::{
+    format_ident,
+    quote_spanned,
+    ToTokens,
+};
+
+impl TraitDefinition<'_> {
+    /// Generates the macro with the default implementations of the ink! trait messages.
+    ///
+    /// # Note
+    ///
+    /// The macro has one rule per ink! trait message with a default implementation.
+    /// It takes the identifier of the message, the `self` receiver and the
+    /// identifiers of the inputs and expands to the body of the default
+    /// implementation with its `self` replaced by the given receiver.
+    pub(super) fn generate_trait_defaults(&self) -> TokenStream2 {
+        let item = self.trait_def.item();
+        let span = item.span();
+        let trait_ident = item.ident();
+        let macro_ident = self.trait_defaults_ident();
+        let rules = item
+            .iter_items()
+            .map(|(item, _)| item)
+            .flat_map(ir::InkTraitItem::filter_map_message)
+            .filter_map(|message| {
+                let block = message.default_impl()?;
+                let span = message.span();
+                let ident = message.ident();
+                let (input_patterns, input_bindings): (Vec<_>, Vec<_>) = message
+                    .inputs()
+                    .enumerate()
+                    .map(|(n, pat_type)| {
+                        (&pat_type.pat, format_ident!("__ink_binding_{}", n))
+                    })
+                    .unzip();
+                let block = replace_self(block.to_token_stream());
+                Some(quote_spanned!(span =>
+                    (#ident, $__ink_self:ident #( , $#input_bindings:ident )* ) => {{
+                        #( let #input_patterns = $#input_bindings; )*
+                        #block
+                    }};
+                ))
+            });
+        quote_spanned!(span =>
+            #[doc(hidden)]
+            #[macro_export]
+            macro_rules! #macro_ident {
+                #( #rules )*
+                ($message:ident $( , $__ink_input:ident )* ) => {
+                    ::core::compile_error!(::core::concat!(
+                        "ink! trait message `",
+                        ::core::stringify!($message),
+                        "` has no default implementation",
+                    ))
+                };
+            }
+
+            #[doc(hidden)]
+            pub use #macro_ident as #trait_ident;
+        )
+    }
+
+    /// Returns the identifier of the macro with the default implementations.
+    ///
+    /// Exported macros live in the crate root, so the identifier is made unique by
+    /// the selectors of the ink! trait messages, which include the namespace.
+    fn trait_defaults_ident(&self) -> syn::Ident {
+        let selectors = self
+            .trait_def
+            .item()
+            .iter_items()
+            .flat_map(|(_, selector)| selector.to_bytes())
+            .collect::<Vec<_>>();
+        let mut hash = [0x00; 32];
+        ir::blake2b_256(&selectors, &mut hash);
+        format_ident!(
+            "__ink_trait_defaults_{}_{:08x}",
+            self.trait_def.item().ident(),
+            u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]]),
+        )
+    }
+}
+
+/// Replaces the `self` receivers in the `tokens` by the `$__ink_self` macro input.
+///
+/// Required since `self` is hygienic in declarative macros and would otherwise not
+/// refer to the receiver of the implementing ink! message.
+fn replace_self(tokens: TokenStream2) -> TokenStream2 {
+    tokens
+        .into_iter()
+        .map(|token| {
+            match token {
+                TokenTree::Ident(ident) if ident == "self" => {
+                    quote_spanned!(ident.span()=> $__ink_self)
+                }
+                TokenTree::Group(group) => {
+                    let mut replaced =
+                        Group::new(group.delimiter(), replace_self(group.stream()));
+                    replaced.set_span(group.span());
+                    TokenTree::Group(replaced).into_token_stream()
+                }
+                token => token.into_token_stream(),
+            }
+        })
+        .collect()
+}
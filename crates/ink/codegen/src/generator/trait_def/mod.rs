@@ -14,6 +14,7 @@
 
 mod call_builder;
 mod call_forwarder;
+mod defaults;
 mod definition;
 mod trait_registry;
 
@@ -57,8 +58,10 @@ impl GenerateCode for TraitDefinition<'_> {
         let trait_registry = self.generate_trait_registry_impl();
         let trait_call_builder = self.generate_call_builder();
         let trait_call_forwarder = self.generate_call_forwarder();
+        let trait_defaults = self.generate_trait_defaults();
         quote_spanned!(span =>
             #trait_definition
+            #trait_defaults
             const _: () = {
                 #trait_registry
                 #trait_call_builder
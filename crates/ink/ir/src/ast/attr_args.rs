@@ -48,11 +48,12 @@ pub struct MetaNameValue {
     pub value: PathOrLit,
 }
 
-/// Either a path or a literal.
+/// Either a path, a literal or an array, like `["--flag", "--other-flag"]`.
 #[derive(Debug, PartialEq, Eq)]
 pub enum PathOrLit {
     Path(syn::Path),
     Lit(syn::Lit),
+    Array(syn::ExprArray),
 }
 
 impl IntoIterator for AttributeArgs {
@@ -84,6 +85,7 @@ impl ToTokens for PathOrLit {
         match self {
             Self::Lit(lit) => lit.to_tokens(tokens),
             Self::Path(path) => path.to_tokens(tokens),
+            Self::Array(array) => array.to_tokens(tokens),
         }
     }
 }
@@ -153,7 +155,10 @@ impl Parse for PathOrLit {
         if input.fork().peek(Ident::peek_any) || input.fork().peek(Token![::]) {
             return input.parse::<syn::Path>().map(PathOrLit::Path)
         }
-        Err(input.error("cannot parse into either literal or path"))
+        if input.fork().peek(syn::token::Bracket) {
+            return input.parse::<syn::ExprArray>().map(PathOrLit::Array)
+        }
+        Err(input.error("cannot parse into either literal, path or array"))
     }
 }
 
@@ -245,6 +250,18 @@ mod tests {
         )
     }
 
+    #[test]
+    fn array_value_works() {
+        assert_eq!(
+            syn::parse2::<AttributeArgs>(quote! { name = ["first", "second"] }).unwrap(),
+            AttributeArgs::new(vec![MetaNameValue {
+                name: syn::parse_quote! { name },
+                eq_token: syn::parse_quote! { = },
+                value: PathOrLit::Array(syn::parse_quote! { ["first", "second"] }),
+            }])
+        )
+    }
+
     #[test]
     fn trailing_comma_works() {
         let mut expected_args = Punctuated::new();
@@ -22,6 +22,7 @@ use crate::{
 };
 use core::result::Result;
 use proc_macro2::{
+    Delimiter as Delimiter2,
     Group as Group2,
     Ident,
     Span,
@@ -274,9 +275,76 @@ impl InkAttribute {
     }
 
     /// Returns `true` if the ink! attribute contains the `payable` argument.
+    ///
+    /// # Note
+    ///
+    /// This also holds for the `payable(refund_excess = N)` and `payable(min = N)`
+    /// arguments.
     pub fn is_payable(&self) -> bool {
+        self.args().any(|arg| {
+            matches!(
+                arg.kind(),
+                AttributeArg::Payable
+                    | AttributeArg::PayableRefundExcess(_)
+                    | AttributeArg::PayableMin(_)
+            )
+        })
+    }
+
+    /// Returns the minimum endowment of the `payable(refund_excess = N)` argument if any.
+    pub fn refund_excess(&self) -> Option<u128> {
+        self.args().find_map(|arg| {
+            if let ir::AttributeArg::PayableRefundExcess(minimum) = arg.kind() {
+                return Some(*minimum)
+            }
+            None
+        })
+    }
+
+    /// Returns the minimum transferred value of the `payable(min = N)` argument if any.
+    pub fn payable_min(&self) -> Option<u128> {
+        self.args().find_map(|arg| {
+            if let ir::AttributeArg::PayableMin(minimum) = arg.kind() {
+                return Some(*minimum)
+            }
+            None
+        })
+    }
+
+    /// Returns the storage version of the `storage_version = N` argument if any.
+    pub fn storage_version(&self) -> Option<u32> {
+        self.args().find_map(|arg| {
+            if let ir::AttributeArg::StorageVersion(version) = arg.kind() {
+                return Some(*version)
+            }
+            None
+        })
+    }
+
+    /// Returns the number of decimal places of the `decimals = N` argument if any.
+    pub fn decimals(&self) -> Option<u8> {
+        self.args().find_map(|arg| {
+            if let ir::AttributeArg::Decimals(decimals) = arg.kind() {
+                return Some(*decimals)
+            }
+            None
+        })
+    }
+
+    /// Returns the storage key of the `storage_key = N` argument if any.
+    pub fn storage_key(&self) -> Option<u32> {
+        self.args().find_map(|arg| {
+            if let ir::AttributeArg::StorageKey(key) = arg.kind() {
+                return Some(*key)
+            }
+            None
+        })
+    }
+
+    /// Returns `true` if the ink! attribute contains the `bounded` argument.
+    pub fn is_bounded(&self) -> bool {
         self.args()
-            .any(|arg| matches!(arg.kind(), AttributeArg::Payable))
+            .any(|arg| matches!(arg.kind(), AttributeArg::Bounded))
     }
 
     /// Returns `true` if the ink! attribute contains the wildcard selector.
@@ -289,10 +357,90 @@ impl InkAttribute {
         })
     }
 
-    /// Returns `true` if the ink! attribute contains the `anonymous` argument.
+    /// Returns `true` if the ink! attribute contains the `non_reentrant` argument.
+    pub fn is_non_reentrant(&self) -> bool {
+        self.args()
+            .any(|arg| matches!(arg.kind(), AttributeArg::NonReentrant))
+    }
+
+    /// Returns the allowed code hashes of the callers if the ink! attribute contains
+    /// the `internal` argument.
+    ///
+    /// The code hashes are empty if any contract is allowed to call.
+    pub fn internal_code_hashes(&self) -> Option<Vec<[u8; 32]>> {
+        self.args().find_map(|arg| {
+            if let AttributeArg::Internal(code_hashes) = arg.kind() {
+                return Some(code_hashes.clone())
+            }
+            None
+        })
+    }
+
+    /// Returns `true` if the ink! attribute contains the `paginated` argument.
+    pub fn is_paginated(&self) -> bool {
+        self.args()
+            .any(|arg| matches!(arg.kind(), AttributeArg::Paginated))
+    }
+
+    /// Returns the delay in milliseconds if the ink! attribute contains the
+    /// `timelocked` argument.
+    pub fn timelock_delay(&self) -> Option<u64> {
+        self.args().find_map(|arg| {
+            if let AttributeArg::Timelocked(delay) = arg.kind() {
+                return Some(*delay)
+            }
+            None
+        })
+    }
+
+    /// Returns the frozen ABI version of the `abi_version = N` argument if any.
+    pub fn abi_version(&self) -> Option<u32> {
+        self.args().find_map(|arg| {
+            if let ir::AttributeArg::AbiVersion(version) = arg.kind() {
+                return Some(*version)
+            }
+            None
+        })
+    }
+
+    /// Returns `true` if the ink! attribute contains the `receive` argument.
+    pub fn is_receive(&self) -> bool {
+        self.args()
+            .any(|arg| matches!(arg.kind(), AttributeArg::Receive))
+    }
+
+    /// Returns `true` if the ink! attribute contains the `deferred_inputs` argument.
+    pub fn has_deferred_inputs(&self) -> bool {
+        self.args()
+            .any(|arg| matches!(arg.kind(), AttributeArg::DeferredInputs))
+    }
+
+    /// Returns the names of the roles allowed to call if the ink! attribute contains
+    /// the `only` argument.
+    pub fn required_roles(&self) -> Option<Vec<String>> {
+        self.args().find_map(|arg| {
+            if let AttributeArg::Only(roles) = arg.kind() {
+                return Some(roles.clone())
+            }
+            None
+        })
+    }
+
+    /// Returns `true` if the ink! attribute contains the `anonymous` or
+    /// `anonymous = true` argument.
     pub fn is_anonymous(&self) -> bool {
         self.args()
-            .any(|arg| matches!(arg.kind(), AttributeArg::Anonymous))
+            .any(|arg| matches!(arg.kind(), AttributeArg::Anonymous(true)))
+    }
+
+    /// Returns the signature of the `signature_topic = "S"` argument if any.
+    pub fn signature_topic(&self) -> Option<String> {
+        self.args().find_map(|arg| {
+            if let ir::AttributeArg::SignatureTopic(signature) = arg.kind() {
+                return Some(signature.clone())
+            }
+            None
+        })
     }
 
     /// Returns `false` if the ink! attribute contains the `handle_status = false` argument.
@@ -339,18 +487,46 @@ impl Spanned for AttributeFrag {
 pub enum AttributeArgKind {
     /// `#[ink(storage)]`
     Storage,
+    /// `#[ink(storage_version = N: u32)]`
+    StorageVersion,
+    /// `#[ink(storage_key = N: u32)]`
+    StorageKey,
+    /// `#[ink(bounded)]`
+    Bounded,
     /// `#[ink(event)]`
     Event,
+    /// `#[ink(error)]`
+    Error,
     /// `#[ink(anonymous)]`
     Anonymous,
+    /// `#[ink(signature_topic = "S")]`
+    SignatureTopic,
     /// `#[ink(topic)]`
     Topic,
+    /// `#[ink(decimals = N: u8)]`
+    Decimals,
     /// `#[ink(message)]`
     Message,
     /// `#[ink(constructor)]`
     Constructor,
     /// `#[ink(payable)]`
     Payable,
+    /// `#[ink(non_reentrant)]`
+    NonReentrant,
+    /// `#[ink(internal)]`
+    Internal,
+    /// `#[ink(paginated)]`
+    Paginated,
+    /// `#[ink(deferred_inputs)]`
+    DeferredInputs,
+    /// `#[ink(receive)]`
+    Receive,
+    /// `#[ink(abi_version = N: u32)]`
+    AbiVersion,
+    /// `#[ink(timelocked(delay = "2 days"))]`
+    Timelocked,
+    /// `#[ink(only(roles = ["admin", "operator"]))]`
+    Only,
     /// `#[ink(selector = _)]`
     /// `#[ink(selector = 0xDEADBEEF)]`
     Selector,
@@ -374,22 +550,67 @@ pub enum AttributeArg {
     /// Applied on `struct` types in order to flag them for being the
     /// contract's storage definition.
     Storage,
+    /// `#[ink(storage_version = N: u32)]`
+    ///
+    /// Applied on the ink! storage struct in order to version its layout.
+    /// Messages of a contract whose stored version is older than `N` run the
+    /// registered storage migrations before the storage is loaded.
+    ///
+    /// Default value: `1`
+    StorageVersion(u32),
+    /// `#[ink(storage_key = N: u32)]`
+    ///
+    /// Applied on fields of storage structs and storage items in order to pin the
+    /// storage key of the field instead of deriving it from the names of the type
+    /// and the field.
+    StorageKey(u32),
+    /// `#[ink(bounded)]`
+    ///
+    /// Applied on fields of storage structs and storage items which pack a
+    /// collection such as a `Vec` in order to state that its size is bounded by the
+    /// contract logic. This silences the warning about packed unbounded collections.
+    Bounded,
     /// `#[ink(event)]`
     ///
     /// Applied on `struct` types in order to flag them for being an ink! event.
     Event,
-    /// `#[ink(anonymous)]`
+    /// `#[ink(error)]`
+    ///
+    /// Applied on `enum` types in order to flag them for being an ink! error
+    /// whose variants are encoded with stable 4-byte error codes.
+    Error,
+    /// `#[ink(anonymous)]` or `#[ink(anonymous = flag: bool)]`
     ///
     /// Applied on `struct` event types in order to flag them as anonymous.
     /// Anonymous events have similar semantics as in Solidity in that their
     /// event signature won't be included in their event topics serialization
     /// to reduce event emitting overhead. This is especially useful for user
     /// defined events.
-    Anonymous,
+    Anonymous(bool),
+    /// `#[ink(signature_topic = "S")]`
+    ///
+    /// Applied on non-anonymous `struct` event types in order to use the Keccak-256
+    /// hash of the Solidity style event signature `S` as their signature topic, e.g.
+    /// `#[ink(signature_topic = "Transfer(address,address,uint128)")]`.
+    ///
+    /// This allows indexers built for the topics of EVM events to consume the event.
+    SignatureTopic(String),
     /// `#[ink(topic)]`
     ///
     /// Applied on fields of ink! event types to indicate that they are topics.
     Topic,
+    /// `#[ink(topic(hash))]`
+    ///
+    /// Applied on fields of ink! event types to indicate that they are topics
+    /// whose value is hashed into a fixed-size topic. This is useful for large
+    /// fields such as `Vec<u8>` or `String`.
+    HashedTopic,
+    /// `#[ink(decimals = N: u8)]`
+    ///
+    /// Applied on fields of ink! event types to indicate that they are amounts of a
+    /// currency with `N` decimal places, e.g. balances. Tools then render them like
+    /// `1.5 UNIT` instead of the integer amount.
+    Decimals(u8),
     /// `#[ink(message)]`
     ///
     /// Applied on `&self` or `&mut self` methods to flag them for being an ink!
@@ -405,6 +626,70 @@ pub enum AttributeArg {
     /// Applied on ink! constructors or messages in order to specify that they
     /// can receive funds from callers.
     Payable,
+    /// `#[ink(payable(refund_excess = N: u128))]`
+    ///
+    /// Applied on ink! constructors in order to specify that they can receive
+    /// funds from callers while keeping at most `N` as the contract's endowment.
+    /// Any transferred value above `N` is refunded to the caller before the
+    /// constructor executes, transferring less than `N` is an error.
+    PayableRefundExcess(u128),
+    /// `#[ink(payable(min = N: u128))]`
+    ///
+    /// Applied on ink! messages in order to specify that they can receive funds
+    /// from callers, but require a transferred value of at least `N`. Calls
+    /// transferring less are reverted with `LangError::InsufficientTransferredValue`
+    /// before the message executes.
+    PayableMin(u128),
+    /// `#[ink(non_reentrant)]`
+    ///
+    /// Applied on ink! messages in order to guard them against reentrancy.
+    /// A storage-backed lock is taken for the duration of the message so that
+    /// any reentrant call into a `non_reentrant` message fails.
+    NonReentrant,
+    /// `#[ink(internal)]` or `#[ink(internal(code_hash = "0x…", …))]`
+    ///
+    /// Applied on ink! messages in order to make them callable only by other
+    /// contracts. If code hashes are given, the calling contract must also run one
+    /// of them. Other calls are reverted with `LangError::CallerNotAllowed` before
+    /// the message executes.
+    Internal(Vec<[u8; 32]>),
+    /// `#[ink(paginated)]`
+    ///
+    /// Applied on ink! messages in order to flag them as getters returning their
+    /// items in pages of `(Vec<T>, Option<Cursor>)`, so that callers can read large
+    /// collections page by page.
+    Paginated,
+    /// `#[ink(deferred_inputs)]`
+    ///
+    /// Applied on ink! messages in order to defer decoding their inputs until the
+    /// checks of the message, e.g. denying payment, have passed. Calls failing them
+    /// then do not pay for decoding large inputs. The inputs are still decoded as a
+    /// whole, not argument by argument.
+    DeferredInputs,
+    /// `#[ink(receive)]`
+    ///
+    /// Applied on an ink! message without inputs in order to execute it when the
+    /// contract is called with empty input data and a nonzero transferred value,
+    /// so that the contract can account plain transfers. Implies `payable`.
+    Receive,
+    /// `#[ink(abi_version = N: u32)]`
+    ///
+    /// Applied on ink! messages in order to freeze the encoding of their inputs as
+    /// version `N` of their ABI. The stability snapshot of the contract then
+    /// rejects changes to the types or the order of the arguments unless `N` is
+    /// bumped.
+    AbiVersion(u32),
+    /// `#[ink(timelocked(delay = "2 days"))]`
+    ///
+    /// Applied on ink! messages in order to queue the calls dispatched to them in
+    /// the `ink::contrib::Timelock` of the contract. The queued calls can be
+    /// executed once the delay, here given in milliseconds, has passed.
+    Timelocked(u64),
+    /// `#[ink(only(roles = ["admin", "operator"]))]`
+    ///
+    /// Applied on ink! messages in order to only allow callers having any of the
+    /// given roles in the `ink::contrib::AccessControl` of the contract.
+    Only(Vec<String>),
     /// Can be either one of:
     ///
     /// - `#[ink(selector = 0xDEADBEEF)]`
@@ -416,8 +701,9 @@ pub enum AttributeArg {
     Selector(SelectorOrWildcard),
     /// `#[ink(namespace = "my_namespace")]`
     ///
-    /// Applied on ink! trait implementation blocks to disambiguate other trait
-    /// implementation blocks with equal names.
+    /// Applied on ink! inherent implementation blocks to disambiguate their
+    /// messages and constructors from ones with equal names. The namespace
+    /// may also be a path of identifiers, e.g. `my_std::v2`.
     Namespace(Namespace),
     /// `#[ink(impl)]`
     ///
@@ -456,12 +742,26 @@ impl core::fmt::Display for AttributeArgKind {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
         match self {
             Self::Storage => write!(f, "storage"),
+            Self::StorageVersion => write!(f, "storage_version = N:u32"),
+            Self::StorageKey => write!(f, "storage_key = N:u32"),
+            Self::Bounded => write!(f, "bounded"),
             Self::Event => write!(f, "event"),
+            Self::Error => write!(f, "error"),
             Self::Anonymous => write!(f, "anonymous"),
+            Self::SignatureTopic => write!(f, "signature_topic = S:string"),
             Self::Topic => write!(f, "topic"),
+            Self::Decimals => write!(f, "decimals = N:u8"),
             Self::Message => write!(f, "message"),
             Self::Constructor => write!(f, "constructor"),
             Self::Payable => write!(f, "payable"),
+            Self::NonReentrant => write!(f, "non_reentrant"),
+            Self::Internal => write!(f, "internal"),
+            Self::Paginated => write!(f, "paginated"),
+            Self::DeferredInputs => write!(f, "deferred_inputs"),
+            Self::Receive => write!(f, "receive"),
+            Self::AbiVersion => write!(f, "abi_version = N:u32"),
+            Self::Timelocked => write!(f, "timelocked(delay = D:string)"),
+            Self::Only => write!(f, "only(roles = [R:string, ...])"),
             Self::Selector => {
                 write!(f, "selector = S:[u8; 4] || _")
             }
@@ -483,12 +783,28 @@ impl AttributeArg {
     pub fn kind(&self) -> AttributeArgKind {
         match self {
             Self::Storage => AttributeArgKind::Storage,
+            Self::StorageVersion(_) => AttributeArgKind::StorageVersion,
+            Self::StorageKey(_) => AttributeArgKind::StorageKey,
+            Self::Bounded => AttributeArgKind::Bounded,
             Self::Event => AttributeArgKind::Event,
-            Self::Anonymous => AttributeArgKind::Anonymous,
-            Self::Topic => AttributeArgKind::Topic,
+            Self::Error => AttributeArgKind::Error,
+            Self::Anonymous(_) => AttributeArgKind::Anonymous,
+            Self::SignatureTopic(_) => AttributeArgKind::SignatureTopic,
+            Self::Topic | Self::HashedTopic => AttributeArgKind::Topic,
+            Self::Decimals(_) => AttributeArgKind::Decimals,
             Self::Message => AttributeArgKind::Message,
             Self::Constructor => AttributeArgKind::Constructor,
-            Self::Payable => AttributeArgKind::Payable,
+            Self::Payable | Self::PayableRefundExcess(_) | Self::PayableMin(_) => {
+                AttributeArgKind::Payable
+            }
+            Self::NonReentrant => AttributeArgKind::NonReentrant,
+            Self::Internal(_) => AttributeArgKind::Internal,
+            Self::Paginated => AttributeArgKind::Paginated,
+            Self::DeferredInputs => AttributeArgKind::DeferredInputs,
+            Self::Receive => AttributeArgKind::Receive,
+            Self::AbiVersion(_) => AttributeArgKind::AbiVersion,
+            Self::Timelocked(_) => AttributeArgKind::Timelocked,
+            Self::Only(_) => AttributeArgKind::Only,
             Self::Selector(_) => AttributeArgKind::Selector,
             Self::Extension(_) => AttributeArgKind::Extension,
             Self::Namespace(_) => AttributeArgKind::Namespace,
@@ -503,12 +819,54 @@ impl core::fmt::Display for AttributeArg {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> Result<(), core::fmt::Error> {
         match self {
             Self::Storage => write!(f, "storage"),
+            Self::StorageVersion(version) => {
+                write!(f, "storage_version = {:?}", version)
+            }
+            Self::StorageKey(key) => write!(f, "storage_key = 0x{:08X}", key),
+            Self::Bounded => write!(f, "bounded"),
             Self::Event => write!(f, "event"),
-            Self::Anonymous => write!(f, "anonymous"),
+            Self::Error => write!(f, "error"),
+            Self::Anonymous(true) => write!(f, "anonymous"),
+            Self::Anonymous(false) => write!(f, "anonymous = false"),
+            Self::SignatureTopic(signature) => {
+                write!(f, "signature_topic = {:?}", signature)
+            }
             Self::Topic => write!(f, "topic"),
+            Self::HashedTopic => write!(f, "topic(hash)"),
+            Self::Decimals(decimals) => write!(f, "decimals = {:?}", decimals),
             Self::Message => write!(f, "message"),
             Self::Constructor => write!(f, "constructor"),
             Self::Payable => write!(f, "payable"),
+            Self::PayableRefundExcess(minimum) => {
+                write!(f, "payable(refund_excess = {:?})", minimum)
+            }
+            Self::PayableMin(minimum) => write!(f, "payable(min = {:?})", minimum),
+            Self::NonReentrant => write!(f, "non_reentrant"),
+            Self::Internal(code_hashes) if code_hashes.is_empty() => {
+                write!(f, "internal")
+            }
+            Self::Internal(code_hashes) => {
+                write!(f, "internal(")?;
+                for (n, code_hash) in code_hashes.iter().enumerate() {
+                    if n > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "code_hash = \"0x")?;
+                    for byte in code_hash {
+                        write!(f, "{:02x}", byte)?;
+                    }
+                    write!(f, "\"")?;
+                }
+                write!(f, ")")
+            }
+            Self::Paginated => write!(f, "paginated"),
+            Self::DeferredInputs => write!(f, "deferred_inputs"),
+            Self::Receive => write!(f, "receive"),
+            Self::AbiVersion(version) => write!(f, "abi_version = {:?}", version),
+            Self::Timelocked(delay) => {
+                write!(f, "timelocked(delay = \"{} milliseconds\")", delay)
+            }
+            Self::Only(roles) => write!(f, "only(roles = {:?})", roles),
             Self::Selector(selector) => core::fmt::Display::fmt(&selector, f),
             Self::Extension(extension) => {
                 write!(f, "extension = {:?}", extension.into_u32())
@@ -761,6 +1119,40 @@ impl From<InkAttribute> for Attribute {
     }
 }
 
+/// This function replaces occurrences of the sequence `name = [ ... ]` with the
+/// sequence `name( ... )`.
+///
+/// This is done because `syn::Attribute::parse_meta` does not support parsing
+/// arrays like `roles = ["admin", "operator"]` as values.
+fn transform_bracketed_values_to_list(group: Group2) -> TokenTree2 {
+    let mut tokens: Vec<TokenTree2> = Vec::new();
+    for tt in group.stream() {
+        let tt = match tt {
+            TokenTree2::Group(grp) => transform_bracketed_values_to_list(grp),
+            _ => tt,
+        };
+        match tt {
+            TokenTree2::Group(grp)
+                if grp.delimiter() == Delimiter2::Bracket
+                    && matches!(
+                        tokens.as_slice(),
+                        [.., TokenTree2::Ident(_), TokenTree2::Punct(punct)]
+                            if punct.as_char() == '='
+                    ) =>
+            {
+                tokens.pop();
+                let mut list = Group2::new(Delimiter2::Parenthesis, grp.stream());
+                list.set_span(grp.span());
+                tokens.push(TokenTree2::Group(list));
+            }
+            _ => tokens.push(tt),
+        }
+    }
+    let mut new_group = Group2::new(group.delimiter(), tokens.into_iter().collect());
+    new_group.set_span(group.span());
+    TokenTree2::Group(new_group)
+}
+
 /// This function replaces occurrences of a `TokenTree::Ident` of the sequence
 /// `selector = _` with the sequence `selector = "_"`.
 ///
@@ -814,7 +1206,14 @@ impl TryFrom<syn::Attribute> for InkAttribute {
             .into_iter()
             .map(|tt| {
                 match tt {
-                    TokenTree2::Group(grp) => transform_wildcard_selector_to_string(grp),
+                    TokenTree2::Group(grp) => {
+                        match transform_wildcard_selector_to_string(grp) {
+                            TokenTree2::Group(grp) => {
+                                transform_bracketed_values_to_list(grp)
+                            }
+                            tt => tt,
+                        }
+                    }
                     _ => tt,
                 }
             })
@@ -891,6 +1290,99 @@ impl InkAttribute {
     }
 }
 
+/// Parses the delay of `#[ink(timelocked(delay = "2 days"))]` into milliseconds.
+fn parse_delay(lit: &syn::Lit) -> Result<u64, syn::Error> {
+    let invalid = || {
+        format_err!(
+            lit,
+            "expected a delay string for `delay`, e.g. \"2 days\", in milliseconds, \
+            seconds, minutes, hours, days or weeks"
+        )
+    };
+    let lit_str = match lit {
+        syn::Lit::Str(lit_str) => lit_str.value(),
+        _ => return Err(invalid()),
+    };
+    let (amount, unit) = match lit_str.split_whitespace().collect::<Vec<_>>().as_slice() {
+        [amount, unit] => (amount.replace('_', ""), *unit),
+        _ => return Err(invalid()),
+    };
+    let amount = amount.parse::<u64>().map_err(|_| invalid())?;
+    let factor: u64 = match unit {
+        "millisecond" | "milliseconds" => 1,
+        "second" | "seconds" => 1_000,
+        "minute" | "minutes" => 60 * 1_000,
+        "hour" | "hours" => 60 * 60 * 1_000,
+        "day" | "days" => 24 * 60 * 60 * 1_000,
+        "week" | "weeks" => 7 * 24 * 60 * 60 * 1_000,
+        _ => return Err(invalid()),
+    };
+    amount.checked_mul(factor).ok_or_else(|| {
+        format_err!(
+            lit,
+            "the delay of `timelocked` must fit into a `u64` of milliseconds"
+        )
+    })
+}
+
+/// Parses the role names of `#[ink(only(roles = ["admin", …]))]`.
+fn parse_roles(roles: &syn::MetaList) -> Result<Vec<String>, syn::Error> {
+    let mut names = Vec::new();
+    for nested in &roles.nested {
+        let name = match nested {
+            syn::NestedMeta::Lit(syn::Lit::Str(lit_str))
+                if !lit_str.value().is_empty() =>
+            {
+                lit_str.value()
+            }
+            _ => {
+                return Err(format_err_spanned!(
+                    nested,
+                    "expected a non-empty string literal role name"
+                ))
+            }
+        };
+        if names.contains(&name) {
+            return Err(format_err_spanned!(
+                nested,
+                "encountered duplicate role {:?}",
+                name
+            ))
+        }
+        names.push(name);
+    }
+    if names.is_empty() {
+        return Err(format_err_spanned!(
+            roles,
+            "expected at least one role, e.g. #[ink(only(roles = [\"admin\"]))]"
+        ))
+    }
+    Ok(names)
+}
+
+/// Parses the hex encoded 32 bytes code hash of `#[ink(internal(code_hash = "0x…"))]`.
+fn parse_code_hash(lit: &syn::Lit) -> Result<[u8; 32], syn::Error> {
+    let invalid = || {
+        format_err!(
+            lit,
+            "expected hex encoded 32 bytes string for `code_hash`, e.g. \"0x0123…\""
+        )
+    };
+    let lit_str = match lit {
+        syn::Lit::Str(lit_str) => lit_str.value(),
+        _ => return Err(invalid()),
+    };
+    let hex = lit_str.strip_prefix("0x").ok_or_else(invalid)?;
+    if hex.len() != 64 || !hex.is_ascii() {
+        return Err(invalid())
+    }
+    let mut code_hash = [0x00; 32];
+    for (n, byte) in code_hash.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * n..2 * n + 2], 16).map_err(|_| invalid())?;
+    }
+    Ok(code_hash)
+}
+
 impl TryFrom<syn::NestedMeta> for AttributeFrag {
     type Error = syn::Error;
 
@@ -937,11 +1429,14 @@ impl TryFrom<syn::NestedMeta> for AttributeFrag {
                         if name_value.path.is_ident("namespace") {
                             if let syn::Lit::Str(lit_str) = &name_value.lit {
                                 let argument = lit_str.value();
-                                syn::parse_str::<syn::Ident>(&argument)
-                                    .map_err(|_error| format_err!(
-                                        lit_str,
-                                        "encountered invalid Rust identifier for namespace argument",
-                                    ))?;
+                                // Namespaces may be paths such as `my_std::v2`.
+                                for segment in argument.split("::") {
+                                    syn::parse_str::<syn::Ident>(segment)
+                                        .map_err(|_error| format_err!(
+                                            lit_str,
+                                            "encountered invalid Rust identifier for namespace argument",
+                                        ))?;
+                                }
                                 return Ok(AttributeFrag {
                                     ast: meta,
                                     arg: AttributeArg::Namespace(
@@ -968,6 +1463,98 @@ impl TryFrom<syn::NestedMeta> for AttributeFrag {
                             }
                             return Err(format_err!(name_value, "expected `u32` integer type for `N` in #[ink(extension = N)]"))
                         }
+                        if name_value.path.is_ident("storage_version") {
+                            if let syn::Lit::Int(lit_int) = &name_value.lit {
+                                let version = lit_int.base10_parse::<u32>().map_err(|parse_err| {
+                                    format_err!(
+                                        name_value,
+                                        "could not parse `N` in `#[ink(storage_version = N)]` into a `u32` integer",
+                                    ).into_combine(parse_err)
+                                })?;
+                                if version == 0 {
+                                    return Err(format_err!(
+                                        name_value,
+                                        "storage versions start at 1, found #[ink(storage_version = 0)]"
+                                    ))
+                                }
+                                return Ok(AttributeFrag {
+                                    ast: meta,
+                                    arg: AttributeArg::StorageVersion(version),
+                                })
+                            }
+                            return Err(format_err!(name_value, "expected `u32` integer type for `N` in #[ink(storage_version = N)]"))
+                        }
+                        if name_value.path.is_ident("storage_key") {
+                            if let syn::Lit::Int(lit_int) = &name_value.lit {
+                                let key = lit_int.base10_parse::<u32>().map_err(|parse_err| {
+                                    format_err!(
+                                        name_value,
+                                        "could not parse `N` in `#[ink(storage_key = N)]` into a `u32` integer",
+                                    ).into_combine(parse_err)
+                                })?;
+                                return Ok(AttributeFrag {
+                                    ast: meta,
+                                    arg: AttributeArg::StorageKey(key),
+                                })
+                            }
+                            return Err(format_err!(name_value, "expected `u32` integer type for `N` in #[ink(storage_key = N)]"))
+                        }
+                        if name_value.path.is_ident("decimals") {
+                            if let syn::Lit::Int(lit_int) = &name_value.lit {
+                                let decimals = lit_int.base10_parse::<u8>().map_err(|parse_err| {
+                                    format_err!(
+                                        name_value,
+                                        "could not parse `N` in `#[ink(decimals = N)]` into a `u8` integer",
+                                    ).into_combine(parse_err)
+                                })?;
+                                return Ok(AttributeFrag {
+                                    ast: meta,
+                                    arg: AttributeArg::Decimals(decimals),
+                                })
+                            }
+                            return Err(format_err!(name_value, "expected `u8` integer type for `N` in #[ink(decimals = N)]"))
+                        }
+                        if name_value.path.is_ident("abi_version") {
+                            if let syn::Lit::Int(lit_int) = &name_value.lit {
+                                let version = lit_int.base10_parse::<u32>().map_err(|parse_err| {
+                                    format_err!(
+                                        name_value,
+                                        "could not parse `N` in `#[ink(abi_version = N)]` into a `u32` integer",
+                                    ).into_combine(parse_err)
+                                })?;
+                                return Ok(AttributeFrag {
+                                    ast: meta,
+                                    arg: AttributeArg::AbiVersion(version),
+                                })
+                            }
+                            return Err(format_err!(name_value, "expected `u32` integer type for `N` in #[ink(abi_version = N)]"))
+                        }
+                        if name_value.path.is_ident("anonymous") {
+                            if let syn::Lit::Bool(lit_bool) = &name_value.lit {
+                                let value = lit_bool.value;
+                                return Ok(AttributeFrag {
+                                    ast: meta,
+                                    arg: AttributeArg::Anonymous(value),
+                                })
+                            }
+                            return Err(format_err!(name_value, "expected `bool` value type for `flag` in #[ink(anonymous = flag)]"))
+                        }
+                        if name_value.path.is_ident("signature_topic") {
+                            if let syn::Lit::Str(lit_str) = &name_value.lit {
+                                let signature = lit_str.value();
+                                if signature.is_empty() {
+                                    return Err(format_err!(
+                                        lit_str,
+                                        "encountered empty event signature for signature_topic argument",
+                                    ))
+                                }
+                                return Ok(AttributeFrag {
+                                    ast: meta,
+                                    arg: AttributeArg::SignatureTopic(signature),
+                                })
+                            }
+                            return Err(format_err!(name_value, "expected string type for `signature_topic` argument, e.g. #[ink(signature_topic = \"Transfer(address,address,uint128)\")]"))
+                        }
                         if name_value.path.is_ident("handle_status") {
                             if let syn::Lit::Bool(lit_bool) = &name_value.lit {
                                 let value = lit_bool.value;
@@ -1000,12 +1587,29 @@ impl TryFrom<syn::NestedMeta> for AttributeFrag {
                             .ok_or_else(|| format_err_spanned!(meta, "unknown ink! attribute (path)"))
                             .and_then(|ident| match ident.as_str() {
                                 "storage" => Ok(AttributeArg::Storage),
+                                "bounded" => Ok(AttributeArg::Bounded),
                                 "message" => Ok(AttributeArg::Message),
                                 "constructor" => Ok(AttributeArg::Constructor),
                                 "event" => Ok(AttributeArg::Event),
-                                "anonymous" => Ok(AttributeArg::Anonymous),
+                                "error" => Ok(AttributeArg::Error),
+                                "anonymous" => Ok(AttributeArg::Anonymous(true)),
                                 "topic" => Ok(AttributeArg::Topic),
                                 "payable" => Ok(AttributeArg::Payable),
+                                "non_reentrant" => Ok(AttributeArg::NonReentrant),
+                                "internal" => Ok(AttributeArg::Internal(Vec::new())),
+                                "paginated" => Ok(AttributeArg::Paginated),
+                                "deferred_inputs" => Ok(AttributeArg::DeferredInputs),
+                                "receive" => Ok(AttributeArg::Receive),
+                                "timelocked" => Err(format_err!(
+                                    meta,
+                                    "encountered #[ink(timelocked)] that is missing its delay. \
+                                    Did you mean #[ink(timelocked(delay = \"2 days\"))] ?"
+                                )),
+                                "only" => Err(format_err!(
+                                    meta,
+                                    "encountered #[ink(only)] that is missing its roles. \
+                                    Did you mean #[ink(only(roles = [\"admin\"]))] ?"
+                                )),
                                 "impl" => Ok(AttributeArg::Implementation),
                                 "selector" => Err(format_err!(
                                     meta,
@@ -1022,6 +1626,31 @@ impl TryFrom<syn::NestedMeta> for AttributeFrag {
                                     "encountered #[ink(extension)] that is missing its `id` parameter. \
                                     Did you mean #[ink(extension = id: u32)] ?"
                                 )),
+                                "storage_version" => Err(format_err!(
+                                    meta,
+                                    "encountered #[ink(storage_version)] that is missing its `N: u32` parameter. \
+                                    Did you mean #[ink(storage_version = N: u32)] ?"
+                                )),
+                                "signature_topic" => Err(format_err!(
+                                    meta,
+                                    "encountered #[ink(signature_topic)] that is missing its string parameter. \
+                                    Did you mean #[ink(signature_topic = signature: str)] ?"
+                                )),
+                                "storage_key" => Err(format_err!(
+                                    meta,
+                                    "encountered #[ink(storage_key)] that is missing its `N: u32` parameter. \
+                                    Did you mean #[ink(storage_key = N: u32)] ?"
+                                )),
+                                "decimals" => Err(format_err!(
+                                    meta,
+                                    "encountered #[ink(decimals)] that is missing its `N: u8` parameter. \
+                                    Did you mean #[ink(decimals = N: u8)] ?"
+                                )),
+                                "abi_version" => Err(format_err!(
+                                    meta,
+                                    "encountered #[ink(abi_version)] that is missing its `N: u32` parameter. \
+                                    Did you mean #[ink(abi_version = N: u32)] ?"
+                                )),
                                 "handle_status" => Err(format_err!(
                                     meta,
                                     "encountered #[ink(handle_status)] that is missing its `flag: bool` parameter. \
@@ -1038,6 +1667,130 @@ impl TryFrom<syn::NestedMeta> for AttributeFrag {
                             })
                             .map(|kind| AttributeFrag { ast: meta, arg: kind, })
                     }
+                    syn::Meta::List(meta_list) if meta_list.path.is_ident("payable") => {
+                        let (name, name_value) = match meta_list.nested.iter().collect::<Vec<_>>().as_slice() {
+                            [syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))]
+                                if name_value.path.is_ident("refund_excess") =>
+                            {
+                                ("refund_excess", name_value)
+                            }
+                            [syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))]
+                                if name_value.path.is_ident("min") =>
+                            {
+                                ("min", name_value)
+                            }
+                            _ => {
+                                return Err(format_err_spanned!(
+                                    meta_list,
+                                    "expected #[ink(payable(refund_excess = N: u128))] or #[ink(payable(min = N: u128))]"
+                                ))
+                            }
+                        };
+                        let minimum = if let syn::Lit::Int(lit_int) = &name_value.lit {
+                            lit_int.base10_parse::<u128>().map_err(|parse_err| {
+                                format_err!(
+                                    name_value,
+                                    "could not parse `N` in `#[ink(payable({} = N))]` into a `u128` integer",
+                                    name,
+                                ).into_combine(parse_err)
+                            })?
+                        } else {
+                            return Err(format_err!(name_value, "expected `u128` integer type for `N` in #[ink(payable({} = N))]", name))
+                        };
+                        let arg = match name {
+                            "min" => AttributeArg::PayableMin(minimum),
+                            _ => AttributeArg::PayableRefundExcess(minimum),
+                        };
+                        Ok(AttributeFrag { ast: meta, arg })
+                    }
+                    syn::Meta::List(meta_list) if meta_list.path.is_ident("internal") => {
+                        let code_hashes = meta_list
+                            .nested
+                            .iter()
+                            .map(|nested| {
+                                match nested {
+                                    syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))
+                                        if name_value.path.is_ident("code_hash") =>
+                                    {
+                                        parse_code_hash(&name_value.lit)
+                                    }
+                                    _ => {
+                                        Err(format_err_spanned!(
+                                            nested,
+                                            "expected #[ink(internal(code_hash = \"0x…\", …))]"
+                                        ))
+                                    }
+                                }
+                            })
+                            .collect::<Result<Vec<_>, _>>()?;
+                        if code_hashes.is_empty() {
+                            return Err(format_err_spanned!(
+                                meta_list,
+                                "expected at least one code hash, e.g. #[ink(internal(code_hash = \"0x…\"))]"
+                            ))
+                        }
+                        Ok(AttributeFrag {
+                            ast: meta,
+                            arg: AttributeArg::Internal(code_hashes),
+                        })
+                    }
+                    syn::Meta::List(meta_list) if meta_list.path.is_ident("timelocked") => {
+                        match meta_list.nested.iter().collect::<Vec<_>>().as_slice() {
+                            [syn::NestedMeta::Meta(syn::Meta::NameValue(name_value))]
+                                if name_value.path.is_ident("delay") =>
+                            {
+                                let delay = parse_delay(&name_value.lit)?;
+                                Ok(AttributeFrag {
+                                    ast: meta,
+                                    arg: AttributeArg::Timelocked(delay),
+                                })
+                            }
+                            _ => {
+                                Err(format_err_spanned!(
+                                    meta_list,
+                                    "expected #[ink(timelocked(delay = \"2 days\"))]"
+                                ))
+                            }
+                        }
+                    }
+                    syn::Meta::List(meta_list) if meta_list.path.is_ident("only") => {
+                        let roles = match meta_list.nested.iter().collect::<Vec<_>>().as_slice() {
+                            // The `roles = [..]` list has been pre-processed to `roles(..)`.
+                            [syn::NestedMeta::Meta(syn::Meta::List(roles))]
+                                if roles.path.is_ident("roles") =>
+                            {
+                                parse_roles(roles)?
+                            }
+                            _ => {
+                                return Err(format_err_spanned!(
+                                    meta_list,
+                                    "expected #[ink(only(roles = [\"admin\", …]))]"
+                                ))
+                            }
+                        };
+                        Ok(AttributeFrag {
+                            ast: meta,
+                            arg: AttributeArg::Only(roles),
+                        })
+                    }
+                    syn::Meta::List(meta_list) if meta_list.path.is_ident("topic") => {
+                        match meta_list.nested.iter().collect::<Vec<_>>().as_slice() {
+                            [syn::NestedMeta::Meta(syn::Meta::Path(path))]
+                                if path.is_ident("hash") =>
+                            {
+                                Ok(AttributeFrag {
+                                    ast: meta,
+                                    arg: AttributeArg::HashedTopic,
+                                })
+                            }
+                            _ => {
+                                Err(format_err_spanned!(
+                                    meta_list,
+                                    "expected #[ink(topic(hash))]"
+                                ))
+                            }
+                        }
+                    }
                     syn::Meta::List(_) => {
                         Err(format_err_spanned!(
                             meta,
@@ -1279,17 +2032,35 @@ mod tests {
     }
 
     #[test]
-    fn namespace_invalid_identifier() {
+    fn namespace_path_works() {
         assert_attribute_try_from(
             syn::parse_quote! {
-                #[ink(namespace = "::invalid_identifier")]
+                #[ink(namespace = "my_std::v2")]
+            },
+            Ok(test::Attribute::Ink(vec![AttributeArg::Namespace(
+                Namespace::from("my_std::v2".to_string().into_bytes()),
+            )])),
+        );
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(namespace = "my_std::")]
             },
             Err("encountered invalid Rust identifier for namespace argument"),
         );
     }
 
     #[test]
-    fn namespace_invalid_type() {
+    fn namespace_invalid_identifier() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(namespace = "::invalid_identifier")]
+            },
+            Err("encountered invalid Rust identifier for namespace argument"),
+        );
+    }
+
+    #[test]
+    fn namespace_invalid_type() {
         assert_attribute_try_from(
             syn::parse_quote! {
                 #[ink(namespace = 42)]
@@ -1455,6 +2226,494 @@ mod tests {
         );
     }
 
+    #[test]
+    fn storage_version_works() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(storage, storage_version = 2)]
+            },
+            Ok(test::Attribute::Ink(vec![
+                AttributeArg::Storage,
+                AttributeArg::StorageVersion(2),
+            ])),
+        );
+    }
+
+    #[test]
+    fn storage_version_invalid_value_fails() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(storage_version = "two")]
+            },
+            Err("expected `u32` integer type for `N` in #[ink(storage_version = N)]"),
+        );
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(storage_version = 0)]
+            },
+            Err("storage versions start at 1, found #[ink(storage_version = 0)]"),
+        );
+    }
+
+    #[test]
+    fn storage_version_missing_parameter() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(storage_version)]
+            },
+            Err(
+                "encountered #[ink(storage_version)] that is missing its `N: u32` parameter. \
+                Did you mean #[ink(storage_version = N: u32)] ?",
+            ),
+        );
+    }
+
+    #[test]
+    fn storage_key_works() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(storage_key = 0x1234_5678)]
+            },
+            Ok(test::Attribute::Ink(vec![AttributeArg::StorageKey(
+                0x1234_5678,
+            )])),
+        );
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(storage_key = "key")]
+            },
+            Err("expected `u32` integer type for `N` in #[ink(storage_key = N)]"),
+        );
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(storage_key)]
+            },
+            Err(
+                "encountered #[ink(storage_key)] that is missing its `N: u32` parameter. \
+                Did you mean #[ink(storage_key = N: u32)] ?",
+            ),
+        );
+    }
+
+    #[test]
+    fn decimals_works() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(decimals = 12)]
+            },
+            Ok(test::Attribute::Ink(vec![AttributeArg::Decimals(12)])),
+        );
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(decimals = 256)]
+            },
+            Err("could not parse `N` in `#[ink(decimals = N)]` into a `u8` integer"),
+        );
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(decimals)]
+            },
+            Err(
+                "encountered #[ink(decimals)] that is missing its `N: u8` parameter. \
+                Did you mean #[ink(decimals = N: u8)] ?",
+            ),
+        );
+    }
+
+    #[test]
+    fn anonymous_works() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(anonymous)]
+            },
+            Ok(test::Attribute::Ink(vec![AttributeArg::Anonymous(true)])),
+        );
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(anonymous = false)]
+            },
+            Ok(test::Attribute::Ink(vec![AttributeArg::Anonymous(false)])),
+        );
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(anonymous = 1)]
+            },
+            Err("expected `bool` value type for `flag` in #[ink(anonymous = flag)]"),
+        );
+    }
+
+    #[test]
+    fn signature_topic_works() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(signature_topic = "Transfer(address,address,uint128)")]
+            },
+            Ok(test::Attribute::Ink(vec![AttributeArg::SignatureTopic(
+                "Transfer(address,address,uint128)".to_owned(),
+            )])),
+        );
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(signature_topic = "")]
+            },
+            Err("encountered empty event signature for signature_topic argument"),
+        );
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(signature_topic)]
+            },
+            Err(
+                "encountered #[ink(signature_topic)] that is missing its string parameter. \
+                Did you mean #[ink(signature_topic = signature: str)] ?",
+            ),
+        );
+    }
+
+    #[test]
+    fn payable_refund_excess_works() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(payable(refund_excess = 1_000))]
+            },
+            Ok(test::Attribute::Ink(vec![
+                AttributeArg::PayableRefundExcess(1_000),
+            ])),
+        );
+    }
+
+    #[test]
+    fn payable_refund_excess_invalid_value_type() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(payable(refund_excess = "string"))]
+            },
+            Err("expected `u128` integer type for `N` in #[ink(payable(refund_excess = N))]"),
+        );
+    }
+
+    #[test]
+    fn payable_refund_excess_missing_parameter() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(payable(refund_excess))]
+            },
+            Err("expected #[ink(payable(refund_excess = N: u128))] or #[ink(payable(min = N: u128))]"),
+        );
+    }
+
+    #[test]
+    fn payable_min_works() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(message, payable(min = 1_000))]
+            },
+            Ok(test::Attribute::Ink(vec![
+                AttributeArg::Message,
+                AttributeArg::PayableMin(1_000),
+            ])),
+        );
+    }
+
+    #[test]
+    fn payable_min_invalid_value_type() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(payable(min = "string"))]
+            },
+            Err("expected `u128` integer type for `N` in #[ink(payable(min = N))]"),
+        );
+    }
+
+    #[test]
+    fn payable_and_payable_min_fails() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(payable, payable(min = 1))]
+            },
+            Err("encountered ink! attribute arguments with equal kinds"),
+        );
+    }
+
+    #[test]
+    fn payable_and_payable_refund_excess_fails() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(payable, payable(refund_excess = 1))]
+            },
+            Err("encountered ink! attribute arguments with equal kinds"),
+        );
+    }
+
+    #[test]
+    fn non_reentrant_works() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(message, non_reentrant)]
+            },
+            Ok(test::Attribute::Ink(vec![
+                AttributeArg::Message,
+                AttributeArg::NonReentrant,
+            ])),
+        );
+    }
+
+    #[test]
+    fn internal_works() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(message, internal)]
+            },
+            Ok(test::Attribute::Ink(vec![
+                AttributeArg::Message,
+                AttributeArg::Internal(Vec::new()),
+            ])),
+        );
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(internal(
+                    code_hash = "0x0101010101010101010101010101010101010101010101010101010101010101",
+                    code_hash = "0xABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABABAB",
+                ))]
+            },
+            Ok(test::Attribute::Ink(vec![AttributeArg::Internal(vec![
+                [0x01; 32], [0xAB; 32],
+            ])])),
+        );
+    }
+
+    #[test]
+    fn internal_invalid_code_hash() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(internal(code_hash = "0x0101"))]
+            },
+            Err("expected hex encoded 32 bytes string for `code_hash`, e.g. \"0x0123…\""),
+        );
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(internal(code_hash = 1))]
+            },
+            Err("expected hex encoded 32 bytes string for `code_hash`, e.g. \"0x0123…\""),
+        );
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(internal())]
+            },
+            Err("expected at least one code hash, e.g. #[ink(internal(code_hash = \"0x…\"))]"),
+        );
+    }
+
+    #[test]
+    fn paginated_works() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(message, paginated)]
+            },
+            Ok(test::Attribute::Ink(vec![
+                AttributeArg::Message,
+                AttributeArg::Paginated,
+            ])),
+        );
+    }
+
+    #[test]
+    fn bounded_works() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(bounded)]
+            },
+            Ok(test::Attribute::Ink(vec![AttributeArg::Bounded])),
+        );
+    }
+
+    #[test]
+    fn deferred_inputs_works() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(message, deferred_inputs)]
+            },
+            Ok(test::Attribute::Ink(vec![
+                AttributeArg::Message,
+                AttributeArg::DeferredInputs,
+            ])),
+        );
+    }
+
+    #[test]
+    fn receive_works() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(message, receive)]
+            },
+            Ok(test::Attribute::Ink(vec![
+                AttributeArg::Message,
+                AttributeArg::Receive,
+            ])),
+        );
+    }
+
+    #[test]
+    fn abi_version_works() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(message, abi_version = 1)]
+            },
+            Ok(test::Attribute::Ink(vec![
+                AttributeArg::Message,
+                AttributeArg::AbiVersion(1),
+            ])),
+        );
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(abi_version = -1)]
+            },
+            Err("could not parse `N` in `#[ink(abi_version = N)]` into a `u32` integer"),
+        );
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(abi_version)]
+            },
+            Err(
+                "encountered #[ink(abi_version)] that is missing its `N: u32` parameter. \
+                Did you mean #[ink(abi_version = N: u32)] ?",
+            ),
+        );
+    }
+
+    #[test]
+    fn timelocked_works() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(message, timelocked(delay = "2 days"))]
+            },
+            Ok(test::Attribute::Ink(vec![
+                AttributeArg::Message,
+                AttributeArg::Timelocked(2 * 24 * 60 * 60 * 1_000),
+            ])),
+        );
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(timelocked(delay = "1 second"))]
+            },
+            Ok(test::Attribute::Ink(vec![AttributeArg::Timelocked(1_000)])),
+        );
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(timelocked(delay = "1_500 milliseconds"))]
+            },
+            Ok(test::Attribute::Ink(vec![AttributeArg::Timelocked(1_500)])),
+        );
+    }
+
+    #[test]
+    fn timelocked_invalid_delay_fails() {
+        let invalid = "expected a delay string for `delay`, e.g. \"2 days\", in \
+            milliseconds, seconds, minutes, hours, days or weeks";
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(timelocked(delay = "2 fortnights"))]
+            },
+            Err(invalid),
+        );
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(timelocked(delay = "days"))]
+            },
+            Err(invalid),
+        );
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(timelocked(delay = 2))]
+            },
+            Err(invalid),
+        );
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(timelocked)]
+            },
+            Err("encountered #[ink(timelocked)] that is missing its delay. \
+                Did you mean #[ink(timelocked(delay = \"2 days\"))] ?"),
+        );
+    }
+
+    #[test]
+    fn only_works() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(message, only(roles = ["admin", "operator"]))]
+            },
+            Ok(test::Attribute::Ink(vec![
+                AttributeArg::Message,
+                AttributeArg::Only(vec!["admin".to_string(), "operator".to_string()]),
+            ])),
+        );
+    }
+
+    #[test]
+    fn only_invalid_roles_fails() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(only(roles = []))]
+            },
+            Err("expected at least one role, e.g. #[ink(only(roles = [\"admin\"]))]"),
+        );
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(only(roles = ["admin", "admin"]))]
+            },
+            Err("encountered duplicate role \"admin\""),
+        );
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(only(roles = [1]))]
+            },
+            Err("expected a non-empty string literal role name"),
+        );
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(only(roles = "admin"))]
+            },
+            Err("expected #[ink(only(roles = [\"admin\", …]))]"),
+        );
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(only)]
+            },
+            Err("encountered #[ink(only)] that is missing its roles. \
+                Did you mean #[ink(only(roles = [\"admin\"]))] ?"),
+        );
+    }
+
+    #[test]
+    fn hashed_topic_works() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(topic(hash))]
+            },
+            Ok(test::Attribute::Ink(vec![AttributeArg::HashedTopic])),
+        );
+    }
+
+    #[test]
+    fn hashed_topic_invalid_parameter() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(topic(blake2))]
+            },
+            Err("expected #[ink(topic(hash))]"),
+        );
+    }
+
+    #[test]
+    fn topic_and_hashed_topic_fails() {
+        assert_attribute_try_from(
+            syn::parse_quote! {
+                #[ink(topic, topic(hash))]
+            },
+            Err("encountered ink! attribute arguments with equal kinds"),
+        );
+    }
+
     #[test]
     fn compound_mixed_works() {
         assert_attribute_try_from(
@@ -1479,6 +2738,7 @@ mod tests {
                     message,
                     constructor,
                     event,
+                    error,
                     topic,
                     payable,
                     impl,
@@ -1489,6 +2749,7 @@ mod tests {
                 AttributeArg::Message,
                 AttributeArg::Constructor,
                 AttributeArg::Event,
+                AttributeArg::Error,
                 AttributeArg::Topic,
                 AttributeArg::Payable,
                 AttributeArg::Implementation,
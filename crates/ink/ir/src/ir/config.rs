@@ -20,6 +20,13 @@ use crate::{
     },
 };
 
+/// The size in bytes of the static buffer of the on-chain environment.
+///
+/// The input of a call is read into this buffer before any ink! code sees it, so
+/// larger inputs trap in the host function instead of reaching the
+/// `max_message_size` check.
+const INPUT_BUFFER_SIZE: u32 = 1 << 14;
+
 /// The ink! configuration.
 #[derive(Debug, Default, PartialEq, Eq)]
 pub struct Config {
@@ -29,6 +36,34 @@ pub struct Config {
     /// be used to change the underlying environmental types of an ink! smart
     /// contract.
     env: Option<Environment>,
+    /// The name of the crate feature that selects this contract as the entry
+    /// point of the crate.
+    ///
+    /// This allows to define multiple ink! smart contracts within the same crate.
+    /// Only the contract whose `entry` feature is enabled exports its `deploy` and
+    /// `call` functions as well as its metadata.
+    entry: Option<String>,
+    /// The type chosen for the type parameter of a generic ink! storage struct.
+    ///
+    /// This allows to define contract templates whose storage struct is generic
+    /// over a configuration, e.g. decimals or token types, and to instantiate
+    /// them with `#[ink::contract(config = MyConfig)]`.
+    config_type: Option<syn::Path>,
+    /// The encoding of the inputs and outputs of the ink! messages.
+    ///
+    /// With `#[ink::contract(abi = "solidity")]` the messages are callable by EVM
+    /// tooling, see [`Abi::Solidity`].
+    abi: Abi,
+    /// The maximum size in bytes of the input of a call or an instantiation.
+    ///
+    /// Larger inputs are rejected by the entry points of the contract before they
+    /// are decoded.
+    max_message_size: Option<u32>,
+    /// Whether the hash of the contract metadata is embedded into the Wasm blob.
+    ///
+    /// The hash is read from the `INK_METADATA_HASH` environment variable at build
+    /// time and stored in the `ink_metadata_hash` custom section.
+    embed_metadata_hash: bool,
     /// The set of attributes that can be passed to call builder in the codegen.
     whitelisted_attributes: WhitelistedAttributes,
 }
@@ -38,6 +73,11 @@ impl TryFrom<ast::AttributeArgs> for Config {
 
     fn try_from(args: ast::AttributeArgs) -> Result<Self, Self::Error> {
         let mut env: Option<(Environment, ast::MetaNameValue)> = None;
+        let mut entry: Option<(String, ast::MetaNameValue)> = None;
+        let mut config_type: Option<(syn::Path, ast::MetaNameValue)> = None;
+        let mut abi: Option<(Abi, ast::MetaNameValue)> = None;
+        let mut max_message_size: Option<(u32, ast::MetaNameValue)> = None;
+        let mut embed_metadata_hash: Option<(bool, ast::MetaNameValue)> = None;
         let mut whitelisted_attributes = WhitelistedAttributes::default();
 
         for arg in args.into_iter() {
@@ -53,6 +93,105 @@ impl TryFrom<ast::AttributeArgs> for Config {
                         "expected a path for `env` ink! configuration argument",
                     ))
                 }
+            } else if arg.name.is_ident("entry") {
+                if let Some((_, ast)) = entry {
+                    return Err(duplicate_config_err(ast, arg, "entry", "contract"))
+                }
+                if let ast::PathOrLit::Lit(syn::Lit::Str(lit_str)) = &arg.value {
+                    entry = Some((lit_str.value(), arg))
+                } else {
+                    return Err(format_err_spanned!(
+                        arg,
+                        "expected a string literal for `entry` ink! configuration argument",
+                    ))
+                }
+            } else if arg.name.is_ident("config") {
+                if let Some((_, ast)) = config_type {
+                    return Err(duplicate_config_err(ast, arg, "config", "contract"))
+                }
+                if let ast::PathOrLit::Path(path) = &arg.value {
+                    config_type = Some((path.clone(), arg))
+                } else {
+                    return Err(format_err_spanned!(
+                        arg,
+                        "expected a path for `config` ink! configuration argument",
+                    ))
+                }
+            } else if arg.name.is_ident("abi") {
+                if let Some((_, ast)) = abi {
+                    return Err(duplicate_config_err(ast, arg, "abi", "contract"))
+                }
+                let value = match &arg.value {
+                    ast::PathOrLit::Lit(syn::Lit::Str(lit_str)) => {
+                        match lit_str.value().as_str() {
+                            "scale" => Some(Abi::Scale),
+                            "solidity" => Some(Abi::Solidity),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                };
+                match value {
+                    Some(value) => abi = Some((value, arg)),
+                    None => {
+                        return Err(format_err_spanned!(
+                            arg,
+                            "expected either \"scale\" or \"solidity\" for `abi` ink! configuration argument",
+                        ))
+                    }
+                }
+            } else if arg.name.is_ident("max_message_size") {
+                if let Some((_, ast)) = max_message_size {
+                    return Err(duplicate_config_err(
+                        ast,
+                        arg,
+                        "max_message_size",
+                        "contract",
+                    ))
+                }
+                let value = match &arg.value {
+                    ast::PathOrLit::Lit(syn::Lit::Int(lit_int)) => {
+                        lit_int
+                            .base10_parse::<u32>()
+                            .ok()
+                            .filter(|value| *value > 0)
+                    }
+                    _ => None,
+                };
+                match value {
+                    Some(value) if value >= INPUT_BUFFER_SIZE => {
+                        return Err(format_err_spanned!(
+                            arg,
+                            "`max_message_size` ink! configuration argument must be smaller than {} bytes, \
+                            the size of the buffer the input is read into",
+                            INPUT_BUFFER_SIZE,
+                        ))
+                    }
+                    Some(value) => max_message_size = Some((value, arg)),
+                    None => {
+                        return Err(format_err_spanned!(
+                            arg,
+                            "expected a positive integer for `max_message_size` ink! configuration argument",
+                        ))
+                    }
+                }
+            } else if arg.name.is_ident("embed_metadata_hash") {
+                if let Some((_, ast)) = embed_metadata_hash {
+                    return Err(duplicate_config_err(
+                        ast,
+                        arg,
+                        "embed_metadata_hash",
+                        "contract",
+                    ))
+                }
+                if let ast::PathOrLit::Lit(syn::Lit::Bool(lit_bool)) = &arg.value {
+                    embed_metadata_hash = Some((lit_bool.value, arg))
+                } else {
+                    return Err(format_err_spanned!(
+                        arg,
+                        "expected a bool literal for `embed_metadata_hash` ink! configuration argument",
+                    ))
+                }
             } else if arg.name.is_ident("keep_attr") {
                 whitelisted_attributes.parse_arg_value(&arg)?;
             } else {
@@ -64,6 +203,13 @@ impl TryFrom<ast::AttributeArgs> for Config {
         }
         Ok(Config {
             env: env.map(|(value, _)| value),
+            entry: entry.map(|(value, _)| value),
+            config_type: config_type.map(|(value, _)| value),
+            abi: abi.map(|(value, _)| value).unwrap_or_default(),
+            max_message_size: max_message_size.map(|(value, _)| value),
+            embed_metadata_hash: embed_metadata_hash
+                .map(|(value, _)| value)
+                .unwrap_or_default(),
             whitelisted_attributes,
         })
     }
@@ -81,12 +227,57 @@ impl Config {
             .unwrap_or(Environment::default().path)
     }
 
+    /// Returns the name of the crate feature that selects the contract as entry point
+    /// if specified.
+    pub fn entry(&self) -> Option<&str> {
+        self.entry.as_deref()
+    }
+
+    /// Returns the type chosen for the type parameter of a generic ink! storage
+    /// struct if specified.
+    pub fn config_type(&self) -> Option<&syn::Path> {
+        self.config_type.as_ref()
+    }
+
+    /// Returns the encoding of the inputs and outputs of the ink! messages.
+    pub fn abi(&self) -> Abi {
+        self.abi
+    }
+
+    /// Returns the maximum size in bytes of the input of a call or an instantiation
+    /// if specified.
+    pub fn max_message_size(&self) -> Option<u32> {
+        self.max_message_size
+    }
+
+    /// Returns `true` if the hash of the contract metadata is embedded into the
+    /// Wasm blob.
+    pub fn embed_metadata_hash(&self) -> bool {
+        self.embed_metadata_hash
+    }
+
     /// Return set of attributes that can be passed to call builder in the codegen.
     pub fn whitelisted_attributes(&self) -> &WhitelistedAttributes {
         &self.whitelisted_attributes
     }
 }
 
+/// The encoding of the inputs and outputs of ink! messages.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Abi {
+    /// The messages use the SCALE codec and selectors derived from their names.
+    Scale,
+    /// The messages use the Solidity ABI encoding and selectors derived from
+    /// their Solidity style signatures, e.g. `balanceOf(address)`.
+    Solidity,
+}
+
+impl Default for Abi {
+    fn default() -> Self {
+        Self::Scale
+    }
+}
+
 /// The environmental types definition.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Environment {
@@ -134,6 +325,11 @@ mod tests {
                 env: Some(Environment {
                     path: syn::parse_quote! { ::my::env::Types },
                 }),
+                entry: None,
+                config_type: None,
+                abi: Abi::Scale,
+                max_message_size: None,
+                embed_metadata_hash: false,
                 whitelisted_attributes: Default::default(),
             }),
         )
@@ -166,6 +362,189 @@ mod tests {
         );
     }
 
+    #[test]
+    fn entry_works() {
+        assert_try_from(
+            syn::parse_quote! {
+                entry = "adder"
+            },
+            Ok(Config {
+                env: None,
+                entry: Some("adder".to_string()),
+                config_type: None,
+                abi: Abi::Scale,
+                max_message_size: None,
+                embed_metadata_hash: false,
+                whitelisted_attributes: Default::default(),
+            }),
+        )
+    }
+
+    #[test]
+    fn entry_invalid_value_fails() {
+        assert_try_from(
+            syn::parse_quote! { entry = adder },
+            Err("expected a string literal for `entry` ink! configuration argument"),
+        );
+    }
+
+    #[test]
+    fn duplicate_entry_fails() {
+        assert_try_from(
+            syn::parse_quote! {
+                entry = "adder",
+                entry = "subber",
+            },
+            Err("encountered duplicate ink! contract `entry` configuration argument"),
+        );
+    }
+
+    #[test]
+    fn config_works() {
+        assert_try_from(
+            syn::parse_quote! {
+                config = crate::MyConfig
+            },
+            Ok(Config {
+                env: None,
+                entry: None,
+                config_type: Some(syn::parse_quote! { crate::MyConfig }),
+                abi: Abi::Scale,
+                max_message_size: None,
+                embed_metadata_hash: false,
+                whitelisted_attributes: Default::default(),
+            }),
+        )
+    }
+
+    #[test]
+    fn config_invalid_value_fails() {
+        assert_try_from(
+            syn::parse_quote! { config = "MyConfig" },
+            Err("expected a path for `config` ink! configuration argument"),
+        );
+    }
+
+    #[test]
+    fn abi_works() {
+        assert_try_from(
+            syn::parse_quote! {
+                abi = "solidity"
+            },
+            Ok(Config {
+                env: None,
+                entry: None,
+                config_type: None,
+                abi: Abi::Solidity,
+                max_message_size: None,
+                embed_metadata_hash: false,
+                whitelisted_attributes: Default::default(),
+            }),
+        )
+    }
+
+    #[test]
+    fn abi_invalid_value_fails() {
+        assert_try_from(
+            syn::parse_quote! { abi = "evm" },
+            Err("expected either \"scale\" or \"solidity\" for `abi` ink! configuration argument"),
+        );
+    }
+
+    #[test]
+    fn max_message_size_works() {
+        assert_try_from(
+            syn::parse_quote! {
+                max_message_size = 8_192
+            },
+            Ok(Config {
+                env: None,
+                entry: None,
+                config_type: None,
+                abi: Abi::Scale,
+                max_message_size: Some(8_192),
+                embed_metadata_hash: false,
+                whitelisted_attributes: Default::default(),
+            }),
+        )
+    }
+
+    #[test]
+    fn max_message_size_invalid_value_fails() {
+        for invalid in [
+            syn::parse_quote! { max_message_size = 0 },
+            syn::parse_quote! { max_message_size = 4_294_967_296 },
+            syn::parse_quote! { max_message_size = "16384" },
+        ] {
+            assert_try_from(
+                invalid,
+                Err("expected a positive integer for `max_message_size` ink! configuration argument"),
+            );
+        }
+    }
+
+    #[test]
+    fn max_message_size_exceeding_input_buffer_fails() {
+        for invalid in [
+            syn::parse_quote! { max_message_size = 16_384 },
+            syn::parse_quote! { max_message_size = 1_000_000 },
+        ] {
+            assert_try_from(
+                invalid,
+                Err("`max_message_size` ink! configuration argument must be smaller than 16384 bytes, \
+                    the size of the buffer the input is read into"),
+            );
+        }
+    }
+
+    #[test]
+    fn duplicate_max_message_size_fails() {
+        assert_try_from(
+            syn::parse_quote! {
+                max_message_size = 1024,
+                max_message_size = 2048,
+            },
+            Err("encountered duplicate ink! contract `max_message_size` configuration argument"),
+        );
+    }
+
+    #[test]
+    fn embed_metadata_hash_works() {
+        assert_try_from(
+            syn::parse_quote! {
+                embed_metadata_hash = true
+            },
+            Ok(Config {
+                env: None,
+                entry: None,
+                config_type: None,
+                abi: Abi::Scale,
+                max_message_size: None,
+                embed_metadata_hash: true,
+                whitelisted_attributes: Default::default(),
+            }),
+        )
+    }
+
+    #[test]
+    fn embed_metadata_hash_invalid_value_fails() {
+        assert_try_from(
+            syn::parse_quote! { embed_metadata_hash = "true" },
+            Err("expected a bool literal for `embed_metadata_hash` ink! configuration argument"),
+        );
+    }
+
+    #[test]
+    fn duplicate_embed_metadata_hash_fails() {
+        assert_try_from(
+            syn::parse_quote! {
+                embed_metadata_hash = true,
+                embed_metadata_hash = false,
+            },
+            Err("encountered duplicate ink! contract `embed_metadata_hash` configuration argument"),
+        );
+    }
+
     #[test]
     fn keep_attr_works() {
         let mut attrs = WhitelistedAttributes::default();
@@ -177,6 +556,11 @@ mod tests {
             },
             Ok(Config {
                 env: None,
+                entry: None,
+                config_type: None,
+                abi: Abi::Scale,
+                max_message_size: None,
+                embed_metadata_hash: false,
                 whitelisted_attributes: attrs,
             }),
         )
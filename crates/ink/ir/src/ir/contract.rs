@@ -65,7 +65,11 @@ impl Contract {
         let config = syn::parse2::<ast::AttributeArgs>(ink_config)?;
         let module = syn::parse2::<syn::ItemMod>(ink_module)?;
         let ink_config = ir::Config::try_from(config)?;
-        let ink_module = ir::ItemMod::try_from(module)?;
+        let mut ink_module = ir::ItemMod::try_from(module)?;
+        ink_module
+            .storage_mut()
+            .set_config_type(ink_config.config_type())?;
+        ink_module.set_abi(ink_config.abi())?;
         Ok(Self {
             item: ink_module,
             config: ink_config,
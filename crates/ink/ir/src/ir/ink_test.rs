@@ -12,13 +12,115 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::ir::idents_lint;
+use crate::{
+    ast,
+    ir::idents_lint,
+    utils::duplicate_config_err,
+};
 use proc_macro2::TokenStream as TokenStream2;
 
+/// The names of the default accounts of the off-chain environment.
+const DEFAULT_ACCOUNTS: [&str; 6] = ["alice", "bob", "charlie", "django", "eve", "frank"];
+
 /// The ink! test with all required information.
 pub struct InkTest {
     /// The function which was annotated.
     pub item_fn: syn::ItemFn,
+    /// The accounts and balances the off-chain environment is set up with.
+    pub config: InkTestConfig,
+}
+
+/// The configuration of an ink! test.
+///
+/// This replaces the `ink::env::test::set_*` calls at the start of most tests, e.g.
+/// `#[ink::test(caller = "alice", balance = 1_000, callee_balance = 500)]`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct InkTestConfig {
+    /// The default account which calls the contract, e.g. `alice`.
+    caller: Option<syn::Ident>,
+    /// The balance of the caller.
+    balance: Option<syn::LitInt>,
+    /// The balance of the contract under test.
+    callee_balance: Option<syn::LitInt>,
+}
+
+impl TryFrom<ast::AttributeArgs> for InkTestConfig {
+    type Error = syn::Error;
+
+    fn try_from(args: ast::AttributeArgs) -> Result<Self, Self::Error> {
+        let mut caller: Option<(syn::Ident, ast::MetaNameValue)> = None;
+        let mut balance: Option<(syn::LitInt, ast::MetaNameValue)> = None;
+        let mut callee_balance: Option<(syn::LitInt, ast::MetaNameValue)> = None;
+        for arg in args.into_iter() {
+            if arg.name.is_ident("caller") {
+                if let Some((_, ast)) = caller {
+                    return Err(duplicate_config_err(ast, arg, "caller", "test"))
+                }
+                match &arg.value {
+                    ast::PathOrLit::Lit(syn::Lit::Str(lit_str))
+                        if DEFAULT_ACCOUNTS.contains(&lit_str.value().as_str()) =>
+                    {
+                        caller =
+                            Some((syn::Ident::new(&lit_str.value(), lit_str.span()), arg))
+                    }
+                    _ => {
+                        return Err(format_err_spanned!(
+                            arg,
+                            "expected one of {} for `caller` ink! test configuration argument",
+                            DEFAULT_ACCOUNTS.map(|name| format!("\"{}\"", name)).join(", "),
+                        ))
+                    }
+                }
+            } else if arg.name.is_ident("balance") || arg.name.is_ident("callee_balance")
+            {
+                let (name, value) = if arg.name.is_ident("balance") {
+                    ("balance", &mut balance)
+                } else {
+                    ("callee_balance", &mut callee_balance)
+                };
+                if let Some((_, ast)) = value.take() {
+                    return Err(duplicate_config_err(ast, arg, name, "test"))
+                }
+                if let ast::PathOrLit::Lit(syn::Lit::Int(lit_int)) = &arg.value {
+                    lit_int.base10_parse::<u128>()?;
+                    *value = Some((lit_int.clone(), arg))
+                } else {
+                    return Err(format_err_spanned!(
+                        arg,
+                        "expected an integer literal for `{}` ink! test configuration argument",
+                        name,
+                    ))
+                }
+            } else {
+                return Err(format_err_spanned!(
+                    arg,
+                    "encountered unknown or unsupported ink! test configuration argument",
+                ))
+            }
+        }
+        Ok(InkTestConfig {
+            caller: caller.map(|(value, _)| value),
+            balance: balance.map(|(value, _)| value),
+            callee_balance: callee_balance.map(|(value, _)| value),
+        })
+    }
+}
+
+impl InkTestConfig {
+    /// Returns the name of the default account calling the contract if specified.
+    pub fn caller(&self) -> Option<&syn::Ident> {
+        self.caller.as_ref()
+    }
+
+    /// Returns the balance of the caller if specified.
+    pub fn balance(&self) -> Option<&syn::LitInt> {
+        self.balance.as_ref()
+    }
+
+    /// Returns the balance of the contract under test if specified.
+    pub fn callee_balance(&self) -> Option<&syn::LitInt> {
+        self.callee_balance.as_ref()
+    }
 }
 
 impl TryFrom<syn::ItemFn> for InkTest {
@@ -26,20 +128,94 @@ impl TryFrom<syn::ItemFn> for InkTest {
 
     fn try_from(item_fn: syn::ItemFn) -> Result<Self, Self::Error> {
         idents_lint::ensure_no_ink_identifiers(&item_fn)?;
-        Ok(Self { item_fn })
+        Ok(Self {
+            item_fn,
+            config: Default::default(),
+        })
     }
 }
 
 impl InkTest {
     /// Returns `Ok` if the test matches all requirements for an ink! test definition.
     pub fn new(attr: TokenStream2, input: TokenStream2) -> Result<Self, syn::Error> {
-        if !attr.is_empty() {
-            return Err(format_err_spanned!(
-                attr,
-                "unexpected attribute input for ink! test definition"
-            ))
-        }
+        let parsed_config = syn::parse2::<ast::AttributeArgs>(attr)?;
+        let config = InkTestConfig::try_from(parsed_config)?;
         let item_fn = syn::parse2::<syn::ItemFn>(input)?;
-        InkTest::try_from(item_fn)
+        let mut ink_test = InkTest::try_from(item_fn)?;
+        ink_test.config = config;
+        Ok(ink_test)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Asserts that the given input configuration attribute argument are converted
+    /// into the expected ink! test configuration or yields the expected error message.
+    fn assert_try_from(
+        input: ast::AttributeArgs,
+        expected: Result<InkTestConfig, &'static str>,
+    ) {
+        assert_eq!(
+            <InkTestConfig as TryFrom<ast::AttributeArgs>>::try_from(input)
+                .map_err(|err| err.to_string()),
+            expected.map_err(ToString::to_string),
+        );
+    }
+
+    #[test]
+    fn empty_config_works() {
+        assert_try_from(syn::parse_quote! {}, Ok(InkTestConfig::default()))
+    }
+
+    #[test]
+    fn accounts_and_balances_work() {
+        assert_try_from(
+            syn::parse_quote! {
+                caller = "bob", balance = 1_000, callee_balance = 500
+            },
+            Ok(InkTestConfig {
+                caller: Some(syn::parse_quote! { bob }),
+                balance: Some(syn::parse_quote! { 1_000 }),
+                callee_balance: Some(syn::parse_quote! { 500 }),
+            }),
+        )
+    }
+
+    #[test]
+    fn unknown_caller_fails() {
+        assert_try_from(
+            syn::parse_quote! { caller = "ferdie" },
+            Err("expected one of \"alice\", \"bob\", \"charlie\", \"django\", \"eve\", \"frank\" for `caller` ink! test configuration argument"),
+        )
+    }
+
+    #[test]
+    fn invalid_balance_fails() {
+        assert_try_from(
+            syn::parse_quote! { balance = "1000" },
+            Err("expected an integer literal for `balance` ink! test configuration argument"),
+        );
+        assert_try_from(
+            syn::parse_quote! { callee_balance = true },
+            Err("expected an integer literal for `callee_balance` ink! test configuration argument"),
+        );
+    }
+
+    #[test]
+    fn duplicate_args_fail() {
+        assert_try_from(
+            syn::parse_quote! { balance = 1, balance = 2 },
+            Err("encountered duplicate ink! test `balance` configuration argument"),
+        );
+    }
+
+    #[test]
+    fn unknown_arg_fails() {
+        assert_try_from(
+            syn::parse_quote! { callee = "alice" },
+            Err("encountered unknown or unsupported ink! test configuration argument"),
+        );
     }
 }
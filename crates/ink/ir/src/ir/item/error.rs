@@ -0,0 +1,323 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    ir,
+    ir::utils,
+};
+use proc_macro2::Ident;
+use syn::spanned::Spanned as _;
+
+/// The name of the field holding the error code in the metadata of error variants.
+const ERROR_CODE_FIELD: &str = "error_code";
+
+/// An ink! error enum definition.
+///
+/// Every variant of an ink! error is assigned a stable 4-byte error code, the first
+/// four bytes of the BLAKE2b-256 hash of its path `Enum::Variant`. The error code
+/// is independent of the order of the variants and shared by equally named errors
+/// across all contracts.
+///
+/// # Example
+///
+/// ```
+/// # let error = <ink_ir::Error as TryFrom<syn::ItemEnum>>::try_from(syn::parse_quote! {
+/// #[ink(error)]
+/// pub enum Error {
+///     InsufficientBalance { required: Balance },
+///     NotOwner,
+/// }
+/// # }).unwrap();
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+pub struct Error {
+    item: syn::ItemEnum,
+}
+
+impl quote::ToTokens for Error {
+    /// We mainly implement this trait for this ink! type to have a derived
+    /// [`Spanned`](`syn::spanned::Spanned`) implementation for it.
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        self.item.to_tokens(tokens)
+    }
+}
+
+impl Error {
+    /// Returns `true` if the first ink! annotation on the given enum is
+    /// `#[ink(error)]`.
+    ///
+    /// # Errors
+    ///
+    /// If the first found ink! attribute is malformed.
+    pub(super) fn is_ink_error(item_enum: &syn::ItemEnum) -> Result<bool, syn::Error> {
+        if !ir::contains_ink_attributes(&item_enum.attrs) {
+            return Ok(false)
+        }
+        let attr = ir::first_ink_attribute(&item_enum.attrs)?
+            .expect("missing expected ink! attribute for enum");
+        Ok(matches!(attr.first().kind(), ir::AttributeArg::Error))
+    }
+}
+
+impl TryFrom<syn::ItemEnum> for Error {
+    type Error = syn::Error;
+
+    fn try_from(item_enum: syn::ItemEnum) -> Result<Self, Self::Error> {
+        let enum_span = item_enum.span();
+        let (_ink_attrs, other_attrs) = ir::sanitize_attributes(
+            enum_span,
+            item_enum.attrs,
+            &ir::AttributeArgKind::Error,
+            |arg| {
+                match arg.kind() {
+                    ir::AttributeArg::Error => Ok(()),
+                    _ => Err(None),
+                }
+            },
+        )?;
+        if !item_enum.generics.params.is_empty() {
+            return Err(format_err_spanned!(
+                item_enum.generics.params,
+                "generic ink! error enums are not supported",
+            ))
+        }
+        utils::ensure_pub_visibility("error enums", enum_span, &item_enum.vis)?;
+        if item_enum.variants.is_empty() {
+            return Err(format_err!(
+                enum_span,
+                "ink! error enums must have at least one variant",
+            ))
+        }
+        if item_enum.variants.len() > usize::from(u8::MAX) + 1 {
+            return Err(format_err!(
+                enum_span,
+                "ink! error enums must not have more than 256 variants",
+            ))
+        }
+        let mut codes = std::collections::HashMap::new();
+        for variant in item_enum.variants.iter() {
+            if ir::contains_ink_attributes(&variant.attrs) {
+                return Err(format_err_spanned!(
+                    variant,
+                    "ink! error variants do not support ink! attributes",
+                ))
+            }
+            if let Some((_, discriminant)) = &variant.discriminant {
+                return Err(format_err_spanned!(
+                    discriminant,
+                    "ink! error variants must not have explicit discriminants, \
+                    they are identified by their error code",
+                ))
+            }
+            if let Some(field) = variant.fields.iter().find(
+                |field| matches!(&field.ident, Some(ident) if ident == ERROR_CODE_FIELD),
+            ) {
+                return Err(format_err_spanned!(
+                    field,
+                    "the `{}` field name is reserved for the error code of ink! error variants",
+                    ERROR_CODE_FIELD,
+                ))
+            }
+            let code = error_code(&item_enum.ident, &variant.ident);
+            if let Some(other) = codes.insert(code, &variant.ident) {
+                return Err(format_err_spanned!(
+                    variant,
+                    "the error code of this variant collides with the one of `{}`",
+                    other,
+                ))
+            }
+        }
+        Ok(Self {
+            item: syn::ItemEnum {
+                attrs: other_attrs,
+                ..item_enum
+            },
+        })
+    }
+}
+
+impl Error {
+    /// Returns the identifier of the error enum.
+    pub fn ident(&self) -> &Ident {
+        &self.item.ident
+    }
+
+    /// Returns all non-ink! attributes.
+    pub fn attrs(&self) -> &[syn::Attribute] {
+        &self.item.attrs
+    }
+
+    /// Returns the underlying enum definition.
+    pub fn item(&self) -> &syn::ItemEnum {
+        &self.item
+    }
+
+    /// Returns the name of the field holding the error code in the metadata of the
+    /// error variants.
+    pub fn error_code_field() -> &'static str {
+        ERROR_CODE_FIELD
+    }
+
+    /// Returns an iterator yielding the variants of the error enum together with
+    /// their error codes.
+    pub fn variants(&self) -> impl Iterator<Item = (&syn::Variant, [u8; 4])> {
+        self.item
+            .variants
+            .iter()
+            .map(|variant| (variant, error_code(&self.item.ident, &variant.ident)))
+    }
+}
+
+/// Returns the error code of the variant `variant` of the ink! error `error`.
+fn error_code(error: &Ident, variant: &Ident) -> [u8; 4] {
+    let mut output = [0; 32];
+    ir::blake2b_256(format!("{}::{}", error, variant).as_bytes(), &mut output);
+    [output[0], output[1], output[2], output[3]]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_try_from_fails(item_enum: syn::ItemEnum, expected: &str) {
+        assert_eq!(
+            <Error as TryFrom<_>>::try_from(item_enum).map_err(|err| err.to_string()),
+            Err(expected.to_string())
+        )
+    }
+
+    #[test]
+    fn simple_try_from_works() {
+        let item_enum: syn::ItemEnum = syn::parse_quote! {
+            #[ink(error)]
+            #[derive(Debug)]
+            pub enum MyError {
+                NotOwner,
+                InsufficientBalance { required: u128 },
+                Other(u32),
+            }
+        };
+        let error = <Error as TryFrom<_>>::try_from(item_enum).unwrap();
+        assert_eq!(error.ident(), "MyError");
+        assert_eq!(error.attrs().len(), 1);
+        let variants = error
+            .variants()
+            .map(|(variant, code)| (variant.ident.to_string(), code))
+            .collect::<Vec<_>>();
+        assert_eq!(variants.len(), 3);
+        assert_eq!(variants[0].0, "NotOwner");
+        assert_eq!(
+            variants[0].1,
+            error_code(
+                &syn::parse_quote! { MyError },
+                &syn::parse_quote! { NotOwner }
+            )
+        );
+        assert_ne!(variants[0].1, variants[1].1);
+    }
+
+    #[test]
+    fn error_codes_depend_on_the_variant_path() {
+        let error_codes = |item_enum: syn::ItemEnum| {
+            <Error as TryFrom<syn::ItemEnum>>::try_from(item_enum)
+                .unwrap()
+                .variants()
+                .map(|(variant, code)| (variant.ident.to_string(), code))
+                .collect::<std::collections::HashMap<_, _>>()
+        };
+        let fst = error_codes(syn::parse_quote! {
+            #[ink(error)]
+            pub enum Error { NotOwner, Paused }
+        });
+        // Neither the order nor the fields of the variants matter.
+        let snd = error_codes(syn::parse_quote! {
+            #[ink(error)]
+            pub enum Error { Paused, NotOwner(u8) }
+        });
+        assert_eq!(fst, snd);
+        // The name of the enum does.
+        let trd = error_codes(syn::parse_quote! {
+            #[ink(error)]
+            pub enum OtherError { NotOwner, Paused }
+        });
+        assert_ne!(fst["NotOwner"], trd["NotOwner"]);
+    }
+
+    #[test]
+    fn generic_error_fails() {
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(error)]
+                pub enum MyError<T> { Other(T) }
+            },
+            "generic ink! error enums are not supported",
+        )
+    }
+
+    #[test]
+    fn non_pub_error_fails() {
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(error)]
+                enum MyError { NotOwner }
+            },
+            "non `pub` ink! error enums are not supported",
+        )
+    }
+
+    #[test]
+    fn empty_error_fails() {
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(error)]
+                pub enum MyError {}
+            },
+            "ink! error enums must have at least one variant",
+        )
+    }
+
+    #[test]
+    fn explicit_discriminant_fails() {
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(error)]
+                pub enum MyError { NotOwner = 1 }
+            },
+            "ink! error variants must not have explicit discriminants, \
+            they are identified by their error code",
+        )
+    }
+
+    #[test]
+    fn reserved_field_name_fails() {
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(error)]
+                pub enum MyError { Other { error_code: u32 } }
+            },
+            "the `error_code` field name is reserved for the error code of ink! error variants",
+        )
+    }
+
+    #[test]
+    fn conflicting_attributes_fail() {
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(error, anonymous)]
+                pub enum MyError { NotOwner }
+            },
+            "encountered conflicting ink! attribute argument",
+        )
+    }
+}
@@ -43,6 +43,8 @@ use syn::spanned::Spanned as _;
 pub struct Event {
     item: syn::ItemStruct,
     pub anonymous: bool,
+    /// The Solidity style event signature of `#[ink(signature_topic = "S")]`.
+    signature: Option<String>,
 }
 
 impl quote::ToTokens for Event {
@@ -86,7 +88,9 @@ impl TryFrom<syn::ItemStruct> for Event {
             &ir::AttributeArgKind::Event,
             |arg| {
                 match arg.kind() {
-                    ir::AttributeArg::Event | ir::AttributeArg::Anonymous => Ok(()),
+                    ir::AttributeArg::Event
+                    | ir::AttributeArg::Anonymous(_)
+                    | ir::AttributeArg::SignatureTopic(_) => Ok(()),
                     _ => Err(None),
                 }
             },
@@ -98,6 +102,14 @@ impl TryFrom<syn::ItemStruct> for Event {
             ))
         }
         utils::ensure_pub_visibility("event structs", struct_span, &item_struct.vis)?;
+        let anonymous = ink_attrs.is_anonymous();
+        let signature = ink_attrs.signature_topic();
+        if anonymous && signature.is_some() {
+            return Err(format_err!(
+                struct_span,
+                "anonymous ink! events cannot have a signature topic",
+            ))
+        }
         'repeat: for field in item_struct.fields.iter() {
             let field_span = field.span();
             let (ink_attrs, _) = ir::partition_attributes(field.attrs.clone())?;
@@ -108,14 +120,24 @@ impl TryFrom<syn::ItemStruct> for Event {
                 ir::InkAttribute::from_expanded(ink_attrs).map_err(|err| {
                     err.into_combine(format_err!(field_span, "at this invocation",))
                 })?;
-            if !matches!(normalized.first().kind(), ir::AttributeArg::Topic) {
+            if !matches!(
+                normalized.first().kind(),
+                ir::AttributeArg::Topic
+                    | ir::AttributeArg::HashedTopic
+                    | ir::AttributeArg::Decimals(_)
+            ) {
                 return Err(format_err!(
                     field_span,
-                    "first optional ink! attribute of an event field must be #[ink(topic)]",
+                    "first optional ink! attribute of an event field must be #[ink(topic)] or #[ink(decimals = N)]",
                 ))
             }
             for arg in normalized.args() {
-                if !matches!(arg.kind(), ir::AttributeArg::Topic) {
+                if !matches!(
+                    arg.kind(),
+                    ir::AttributeArg::Topic
+                        | ir::AttributeArg::HashedTopic
+                        | ir::AttributeArg::Decimals(_)
+                ) {
                     return Err(format_err!(
                         arg.span(),
                         "encountered conflicting ink! attribute for event field",
@@ -128,7 +150,8 @@ impl TryFrom<syn::ItemStruct> for Event {
                 attrs: other_attrs,
                 ..item_struct
             },
-            anonymous: ink_attrs.is_anonymous(),
+            anonymous,
+            signature,
         })
     }
 }
@@ -149,6 +172,23 @@ impl Event {
     pub fn attrs(&self) -> &[syn::Attribute] {
         &self.item.attrs
     }
+
+    /// Returns the Solidity style event signature if any.
+    ///
+    /// This is set via `#[ink(signature_topic = "S")]`.
+    pub fn signature(&self) -> Option<&str> {
+        self.signature.as_deref()
+    }
+
+    /// Returns the signature topic of the event if any.
+    ///
+    /// This is the Keccak-256 hash of the Solidity style event signature, just like
+    /// the first topic of non-anonymous events of the EVM.
+    pub fn signature_topic(&self) -> Option<[u8; 32]> {
+        use sha3::Digest as _;
+        self.signature()
+            .map(|signature| sha3::Keccak256::digest(signature.as_bytes()).into())
+    }
 }
 
 /// An event field with a flag indicating if this field is an event topic.
@@ -156,6 +196,16 @@ impl Event {
 pub struct EventField<'a> {
     /// The associated `field` is an event topic if this is `true`.
     pub is_topic: bool,
+    /// The associated `field` is an event topic whose value is hashed
+    /// if this is `true`.
+    ///
+    /// This is the case for fields annotated with `#[ink(topic(hash))]`.
+    pub is_hashed_topic: bool,
+    /// The number of decimal places of the associated `field` if it is an amount
+    /// of a currency.
+    ///
+    /// This is set for fields annotated with `#[ink(decimals = N)]`.
+    pub decimals: Option<u8>,
     /// The event field.
     field: &'a syn::Field,
 }
@@ -212,11 +262,31 @@ impl<'a> Iterator for EventFieldsIter<'a> {
         match self.iter.next() {
             None => None,
             Some(field) => {
-                let is_topic = ir::first_ink_attribute(&field.attrs)
-                    .unwrap_or_default()
-                    .map(|attr| matches!(attr.first().kind(), ir::AttributeArg::Topic))
-                    .unwrap_or_default();
-                Some(EventField { is_topic, field })
+                let (ink_attrs, _) =
+                    ir::partition_attributes(field.attrs.clone()).unwrap_or_default();
+                let args = || {
+                    ink_attrs
+                        .iter()
+                        .flat_map(ir::InkAttribute::args)
+                        .map(ir::AttributeFrag::kind)
+                };
+                let is_topic = args().any(|arg| {
+                    matches!(arg, ir::AttributeArg::Topic | ir::AttributeArg::HashedTopic)
+                });
+                let is_hashed_topic =
+                    args().any(|arg| matches!(arg, ir::AttributeArg::HashedTopic));
+                let decimals = args().find_map(|arg| {
+                    match arg {
+                        ir::AttributeArg::Decimals(decimals) => Some(*decimals),
+                        _ => None,
+                    }
+                });
+                Some(EventField {
+                    is_topic,
+                    is_hashed_topic,
+                    decimals,
+                    field,
+                })
             }
         }
     }
@@ -365,7 +435,7 @@ mod tests {
                     field_2: bool,
                 }
             },
-            "first optional ink! attribute of an event field must be #[ink(topic)]",
+            "first optional ink! attribute of an event field must be #[ink(topic)] or #[ink(decimals = N)]",
         )
     }
 
@@ -450,6 +520,66 @@ mod tests {
         }
     }
 
+    #[test]
+    fn event_fields_iter_hashed_topics_works() {
+        let input = <Event as TryFrom<syn::ItemStruct>>::try_from(syn::parse_quote! {
+            #[ink(event)]
+            pub struct MyEvent {
+                #[ink(topic)]
+                field_1: i32,
+                #[ink(topic(hash))]
+                field_2: Vec<u8>,
+                field_3: u64,
+            }
+        })
+        .unwrap();
+        let flags = input
+            .fields()
+            .map(|field| (field.is_topic, field.is_hashed_topic))
+            .collect::<Vec<_>>();
+        assert_eq!(flags, vec![(true, false), (true, true), (false, false)]);
+    }
+
+    #[test]
+    fn event_fields_iter_decimals_works() {
+        let input = <Event as TryFrom<syn::ItemStruct>>::try_from(syn::parse_quote! {
+            #[ink(event)]
+            pub struct MyEvent {
+                #[ink(decimals = 12)]
+                #[ink(topic)]
+                field_1: u128,
+                #[ink(decimals = 6)]
+                field_2: u128,
+                field_3: u64,
+            }
+        })
+        .unwrap();
+        let flags = input
+            .fields()
+            .map(|field| (field.is_topic, field.decimals))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            flags,
+            vec![(true, Some(12)), (false, Some(6)), (false, None)]
+        );
+    }
+
+    #[test]
+    fn conflicting_hashed_topic_attributes_fails() {
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(event)]
+                pub struct MyEvent {
+                    #[ink(topic(hash))]
+                    #[ink(topic)]
+                    field_1: Vec<u8>,
+                    field_2: bool,
+                }
+            },
+            "encountered ink! attribute arguments with equal kinds",
+        )
+    }
+
     #[test]
     fn anonymous_event_works() {
         fn assert_anonymous_event(event: syn::ItemStruct) {
@@ -477,5 +607,50 @@ mod tests {
                 field_2: bool,
             }
         });
+        assert_anonymous_event(syn::parse_quote! {
+            #[ink(event, anonymous = true)]
+            pub struct MyEvent {
+                #[ink(topic)]
+                field_1: i32,
+                field_2: bool,
+            }
+        });
+    }
+
+    #[test]
+    fn signature_topic_works() {
+        let event = <Event as TryFrom<syn::ItemStruct>>::try_from(syn::parse_quote! {
+            #[ink(event, anonymous = false, signature_topic = "Transfer(address,address,uint256)")]
+            pub struct Transfer {
+                #[ink(topic)]
+                from: AccountId,
+                #[ink(topic)]
+                to: AccountId,
+                value: Balance,
+            }
+        })
+        .unwrap();
+        assert!(!event.anonymous);
+        assert_eq!(event.signature(), Some("Transfer(address,address,uint256)"));
+        // The well known topic of the ERC-20 `Transfer` event.
+        assert_eq!(
+            event.signature_topic(),
+            Some([
+                0xDD, 0xF2, 0x52, 0xAD, 0x1B, 0xE2, 0xC8, 0x9B, 0x69, 0xC2, 0xB0, 0x68,
+                0xFC, 0x37, 0x8D, 0xAA, 0x95, 0x2B, 0xA7, 0xF1, 0x63, 0xC4, 0xA1, 0x16,
+                0x28, 0xF5, 0x5A, 0x4D, 0xF5, 0x23, 0xB3, 0xEF,
+            ])
+        );
+    }
+
+    #[test]
+    fn anonymous_event_with_signature_topic_fails() {
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(event, anonymous, signature_topic = "Event()")]
+                pub struct MyEvent {}
+            },
+            "anonymous ink! events cannot have a signature topic",
+        )
     }
 }
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod error;
 mod event;
 mod storage;
 
@@ -19,6 +20,7 @@ mod storage;
 mod tests;
 
 pub use self::{
+    error::Error,
     event::Event,
     storage::Storage,
 };
@@ -85,6 +87,28 @@ impl TryFrom<syn::Item> for Item {
                     }
                 }
             }
+            syn::Item::Enum(item_enum)
+                if ir::contains_ink_attributes(&item_enum.attrs) =>
+            {
+                // At this point we know that there must be at least one ink!
+                // attribute. This can be either an ink! error or an invalid
+                // ink! attribute.
+                let attr = ir::first_ink_attribute(&item_enum.attrs)?
+                    .expect("missing expected ink! attribute for enum");
+                match attr.first().kind() {
+                    ir::AttributeArg::Error => {
+                        <ir::Error as TryFrom<_>>::try_from(item_enum)
+                            .map(Into::into)
+                            .map(Self::Ink)
+                    }
+                    _invalid => {
+                        Err(format_err!(
+                            attr.span(),
+                            "encountered unsupported ink! attribute argument on enum",
+                        ))
+                    }
+                }
+            }
             syn::Item::Impl(item_impl) => {
                 if !ir::ItemImpl::is_ink_impl_block(&item_impl)? {
                     return Ok(Self::Rust(item_impl.into()))
@@ -155,6 +179,8 @@ pub enum InkItem {
     Storage(ir::Storage),
     /// An ink! event definition.
     Event(ir::Event),
+    /// An ink! error definition.
+    Error(ir::Error),
     /// An ink! implementation block.
     ImplBlock(ir::ItemImpl),
 }
@@ -166,6 +192,7 @@ impl quote::ToTokens for InkItem {
         match self {
             Self::Storage(storage) => storage.to_tokens(tokens),
             Self::Event(event) => event.to_tokens(tokens),
+            Self::Error(error) => error.to_tokens(tokens),
             Self::ImplBlock(impl_block) => impl_block.to_tokens(tokens),
         }
     }
@@ -186,6 +213,7 @@ impl InkItem {
                     return Ok(true)
                 }
             }
+            syn::Item::Enum(item_enum) => return ir::Error::is_ink_error(item_enum),
             syn::Item::Impl(item_impl) => {
                 return ir::ItemImpl::is_ink_impl_block(item_impl)
             }
@@ -207,6 +235,12 @@ impl From<ir::Event> for InkItem {
     }
 }
 
+impl From<ir::Error> for InkItem {
+    fn from(error: ir::Error) -> Self {
+        Self::Error(error)
+    }
+}
+
 impl From<ir::ItemImpl> for InkItem {
     fn from(impl_block: ir::ItemImpl) -> Self {
         Self::ImplBlock(impl_block)
@@ -244,6 +278,21 @@ impl InkItem {
         self.filter_map_event_item().is_some()
     }
 
+    /// Returns `Some` if `self` is an ink! error enum definition.
+    ///
+    /// Otherwise, returns `None`.
+    pub fn filter_map_error_item(&self) -> Option<&ir::Error> {
+        match self {
+            InkItem::Error(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the ink! specific item is an error enum definition.
+    pub fn is_error_item(&self) -> bool {
+        self.filter_map_error_item().is_some()
+    }
+
     /// Returns `Some` if `self` is an ink! implementation block.
     ///
     /// Otherwise, returns `None`.
@@ -43,6 +43,8 @@ use syn::spanned::Spanned as _;
 pub struct Storage {
     /// The underlying `struct` Rust item.
     ast: syn::ItemStruct,
+    /// The storage layout version if set via `#[ink(storage_version = N)]`.
+    storage_version: Option<u32>,
 }
 
 impl quote::ToTokens for Storage {
@@ -80,33 +82,116 @@ impl TryFrom<syn::ItemStruct> for Storage {
 
     fn try_from(item_struct: syn::ItemStruct) -> Result<Self, Self::Error> {
         let struct_span = item_struct.span();
-        let (_ink_attrs, other_attrs) = ir::sanitize_attributes(
+        let (ink_attrs, other_attrs) = ir::sanitize_attributes(
             struct_span,
             item_struct.attrs,
             &ir::AttributeArgKind::Storage,
             |arg| {
                 match arg.kind() {
-                    ir::AttributeArg::Storage => Ok(()),
+                    ir::AttributeArg::Storage | ir::AttributeArg::StorageVersion(_) => {
+                        Ok(())
+                    }
                     _ => Err(None),
                 }
             },
         )?;
         utils::ensure_pub_visibility("storage structs", struct_span, &item_struct.vis)?;
+        Self::ensure_valid_generics(&item_struct.generics)?;
         Ok(Self {
             ast: syn::ItemStruct {
                 attrs: other_attrs,
                 ..item_struct
             },
+            storage_version: ink_attrs.storage_version(),
         })
     }
 }
 
 impl Storage {
+    /// Ensures that the storage struct has at most one type parameter, which is
+    /// chosen via the `config` argument of `#[ink::contract]`.
+    fn ensure_valid_generics(generics: &syn::Generics) -> Result<(), syn::Error> {
+        let mut type_params = 0;
+        for param in &generics.params {
+            match param {
+                syn::GenericParam::Type(type_param) => {
+                    if let Some(default) = &type_param.default {
+                        return Err(format_err_spanned!(
+                            default,
+                            "the type parameter of ink! storage structs must not have a default, \
+                            use `#[ink::contract(config = MyConfig)]` instead",
+                        ))
+                    }
+                    type_params += 1;
+                    if type_params > 1 {
+                        return Err(format_err_spanned!(
+                            type_param,
+                            "ink! storage structs must not have more than one type parameter",
+                        ))
+                    }
+                }
+                _ => {
+                    return Err(format_err_spanned!(
+                        param,
+                        "ink! storage structs must not have lifetime or const parameters",
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Chooses the `config_type` for the type parameter of the storage struct.
+    ///
+    /// The type becomes the default of the type parameter, so that the storage
+    /// struct can be referred to without generic arguments, e.g. in ink!
+    /// implementation blocks and the generated code.
+    ///
+    /// # Errors
+    ///
+    /// - If the storage struct has a type parameter but no `config_type` is given.
+    /// - If a `config_type` is given but the storage struct has no type parameter.
+    pub(crate) fn set_config_type(
+        &mut self,
+        config_type: Option<&syn::Path>,
+    ) -> Result<(), syn::Error> {
+        match (self.ast.generics.type_params_mut().next(), config_type) {
+            (None, None) => Ok(()),
+            (Some(type_param), Some(config_type)) => {
+                type_param.eq_token = Some(Default::default());
+                type_param.default = Some(syn::Type::Path(syn::TypePath {
+                    qself: None,
+                    path: config_type.clone(),
+                }));
+                Ok(())
+            }
+            (Some(type_param), None) => {
+                Err(format_err_spanned!(
+                    type_param,
+                    "the type parameter of ink! storage structs must be chosen via \
+                    `#[ink::contract(config = MyConfig)]`",
+                ))
+            }
+            (None, Some(config_type)) => {
+                Err(format_err_spanned!(
+                    config_type,
+                    "the `config` ink! configuration argument requires an ink! storage \
+                    struct with a type parameter",
+                ))
+            }
+        }
+    }
+
     /// Returns the non-ink! attributes of the ink! storage struct.
     pub fn attrs(&self) -> &[syn::Attribute] {
         &self.ast.attrs
     }
 
+    /// Returns the storage layout version if set via `#[ink(storage_version = N)]`.
+    pub fn storage_version(&self) -> Option<u32> {
+        self.storage_version
+    }
+
     /// Returns the identifier of the storage struct.
     pub fn ident(&self) -> &Ident {
         &self.ast.ident
@@ -139,6 +224,27 @@ mod tests {
         assert!(Storage::try_from(item_struct).is_ok())
     }
 
+    #[test]
+    fn storage_version_works() {
+        let storage = |item_struct: syn::ItemStruct| {
+            Storage::try_from(item_struct).unwrap().storage_version()
+        };
+        assert_eq!(
+            storage(syn::parse_quote! {
+                #[ink(storage)]
+                pub struct MyStorage {}
+            }),
+            None
+        );
+        assert_eq!(
+            storage(syn::parse_quote! {
+                #[ink(storage, storage_version = 3)]
+                pub struct MyStorage {}
+            }),
+            Some(3)
+        );
+    }
+
     fn assert_try_from_fails(item_struct: syn::ItemStruct, expected: &str) {
         assert_eq!(
             Storage::try_from(item_struct).map_err(|err| err.to_string()),
@@ -204,6 +310,86 @@ mod tests {
         )
     }
 
+    #[test]
+    fn set_config_type_works() {
+        let mut storage =
+            <Storage as TryFrom<syn::ItemStruct>>::try_from(syn::parse_quote! {
+                #[ink(storage)]
+                pub struct MyStorage<T: Config> {
+                    field_1: T::Balance,
+                }
+            })
+            .unwrap();
+        storage
+            .set_config_type(Some(&syn::parse_quote! { super::MyConfig }))
+            .unwrap();
+        let expected: syn::Generics = syn::parse_quote! { <T: Config = super::MyConfig> };
+        assert_eq!(storage.generics(), &expected);
+    }
+
+    #[test]
+    fn set_config_type_fails_for_mismatches() {
+        let generic = |config_type: Option<syn::Path>| {
+            <Storage as TryFrom<syn::ItemStruct>>::try_from(syn::parse_quote! {
+                #[ink(storage)]
+                pub struct MyStorage<T: Config> {}
+            })
+            .unwrap()
+            .set_config_type(config_type.as_ref())
+            .map_err(|err| err.to_string())
+        };
+        assert_eq!(
+            generic(None),
+            Err(
+                "the type parameter of ink! storage structs must be chosen via \
+                `#[ink::contract(config = MyConfig)]`"
+                    .to_string()
+            )
+        );
+        let mut non_generic =
+            <Storage as TryFrom<syn::ItemStruct>>::try_from(syn::parse_quote! {
+                #[ink(storage)]
+                pub struct MyStorage {}
+            })
+            .unwrap();
+        assert_eq!(
+            non_generic
+                .set_config_type(Some(&syn::parse_quote! { MyConfig }))
+                .map_err(|err| err.to_string()),
+            Err(
+                "the `config` ink! configuration argument requires an ink! storage \
+                struct with a type parameter"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn invalid_generics_fail() {
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(storage)]
+                pub struct MyStorage<T, U> {}
+            },
+            "ink! storage structs must not have more than one type parameter",
+        );
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(storage)]
+                pub struct MyStorage<'a> {}
+            },
+            "ink! storage structs must not have lifetime or const parameters",
+        );
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(storage)]
+                pub struct MyStorage<T = MyConfig> {}
+            },
+            "the type parameter of ink! storage structs must not have a default, \
+            use `#[ink::contract(config = MyConfig)]` instead",
+        );
+    }
+
     #[test]
     fn non_pub_storage_struct() {
         assert_try_from_fails(
@@ -45,6 +45,21 @@ fn simple_event_works() {
     ))
 }
 
+#[test]
+fn simple_error_works() {
+    let error_enum: syn::Item = syn::parse_quote! {
+        #[ink(error)]
+        pub enum MyError {
+            NotOwner,
+            InsufficientBalance { required: u128 },
+        }
+    };
+    assert!(matches!(
+        <ir::Item as TryFrom<_>>::try_from(error_enum).map_err(|err| err.to_string()),
+        Ok(ir::Item::Ink(ir::InkItem::Error(_)))
+    ))
+}
+
 #[test]
 fn simple_rust_item_works() {
     let rust_items: Vec<syn::Item> = vec![
@@ -66,6 +66,12 @@ pub struct Constructor {
     pub(super) item: syn::ImplItemMethod,
     /// If the ink! constructor can receive funds.
     is_payable: bool,
+    /// The minimum endowment if the ink! constructor refunds excess value.
+    ///
+    /// # Note
+    ///
+    /// This is set via `#[ink(payable(refund_excess = N))]`.
+    refund_excess: Option<u128>,
     /// An optional user provided selector.
     ///
     /// # Note
@@ -123,6 +129,27 @@ impl Constructor {
         Ok(())
     }
 
+    /// Ensures that the ink! constructor takes no borrowed inputs.
+    ///
+    /// # Errors
+    ///
+    /// If the ink! constructor takes a reference as input.
+    fn ensure_no_borrowed_inputs(
+        method_item: &syn::ImplItemMethod,
+    ) -> Result<(), syn::Error> {
+        for fn_arg in method_item.sig.inputs.iter() {
+            if let syn::FnArg::Typed(pat_type) = fn_arg {
+                if let syn::Type::Reference(reference) = &*pat_type.ty {
+                    return Err(format_err_spanned!(
+                        reference,
+                        "ink! constructors do not support borrowed inputs",
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Sanitizes the attributes for the ink! constructor.
     ///
     /// Returns a tuple of ink! attributes and non-ink! attributes.
@@ -137,6 +164,7 @@ impl Constructor {
                 match arg.kind() {
                     ir::AttributeArg::Constructor
                     | ir::AttributeArg::Payable
+                    | ir::AttributeArg::PayableRefundExcess(_)
                     | ir::AttributeArg::Selector(_) => Ok(()),
                     _ => Err(None),
                 }
@@ -152,12 +180,15 @@ impl TryFrom<syn::ImplItemMethod> for Constructor {
         ensure_callable_invariants(&method_item, CallableKind::Constructor)?;
         Self::ensure_return(&method_item)?;
         Self::ensure_no_self_receiver(&method_item)?;
+        Self::ensure_no_borrowed_inputs(&method_item)?;
         let (ink_attrs, other_attrs) = Self::sanitize_attributes(&method_item)?;
         let is_payable = ink_attrs.is_payable();
+        let refund_excess = ink_attrs.refund_excess();
         let selector = ink_attrs.selector();
         Ok(Constructor {
             selector,
             is_payable,
+            refund_excess,
             item: syn::ImplItemMethod {
                 attrs: other_attrs,
                 ..method_item
@@ -220,6 +251,12 @@ impl Constructor {
         &self.item.attrs
     }
 
+    /// Returns the minimum endowment of the ink! constructor if it refunds
+    /// any transferred value in excess of it.
+    pub fn refund_excess(&self) -> Option<u128> {
+        self.refund_excess
+    }
+
     /// Returns the return type of the ink! constructor if any.
     pub fn output(&self) -> Option<&syn::Type> {
         match &self.item.sig.output {
@@ -329,6 +366,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn refund_excess_works() {
+        let test_inputs: Vec<(Option<u128>, bool, syn::ImplItemMethod)> = vec![
+            // Not payable.
+            (
+                None,
+                false,
+                syn::parse_quote! {
+                    #[ink(constructor)]
+                    fn my_constructor() -> Self {}
+                },
+            ),
+            // Payable without refund.
+            (
+                None,
+                true,
+                syn::parse_quote! {
+                    #[ink(constructor, payable)]
+                    fn my_constructor() -> Self {}
+                },
+            ),
+            // Payable with refund of excess value.
+            (
+                Some(1_000),
+                true,
+                syn::parse_quote! {
+                    #[ink(constructor, payable(refund_excess = 1_000))]
+                    pub fn my_constructor() -> Self {}
+                },
+            ),
+        ];
+        for (expect_refund, expect_payable, item_method) in test_inputs {
+            let constructor =
+                <ir::Constructor as TryFrom<_>>::try_from(item_method).unwrap();
+            assert_eq!(constructor.refund_excess(), expect_refund);
+            assert_eq!(constructor.is_payable(), expect_payable);
+        }
+    }
+
     #[test]
     fn visibility_works() {
         let test_inputs: Vec<(bool, syn::ImplItemMethod)> = vec![
@@ -545,6 +621,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn try_from_borrowed_input_fails() {
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(constructor)]
+                fn my_constructor(data: &[u8]) -> Self {}
+            },
+            "ink! constructors do not support borrowed inputs",
+        )
+    }
+
     #[test]
     fn try_from_visibility_fails() {
         let item_methods: Vec<syn::ImplItemMethod> = vec![
@@ -584,6 +671,16 @@ mod tests {
                 #[ink(event)]
                 fn my_constructor() -> Self {}
             },
+            // non_reentrant
+            syn::parse_quote! {
+                #[ink(constructor, non_reentrant)]
+                fn my_constructor() -> Self {}
+            },
+            // minimum transferred values are only supported for messages
+            syn::parse_quote! {
+                #[ink(constructor, payable(min = 1))]
+                fn my_constructor() -> Self {}
+            },
         ];
         for item_method in item_methods {
             assert_try_from_fails(
@@ -98,6 +98,52 @@ pub struct Message {
     pub(super) item: syn::ImplItemMethod,
     /// If the ink! message can receive funds.
     is_payable: bool,
+    /// The minimum value that must be transferred to the ink! message.
+    ///
+    /// # Note
+    ///
+    /// This is set via `#[ink(payable(min = N))]`.
+    min_transferred_value: Option<u128>,
+    /// If the ink! message is guarded against reentrancy.
+    is_non_reentrant: bool,
+    /// The code hashes of the contracts allowed to call the ink! message if it is
+    /// only callable by other contracts.
+    ///
+    /// # Note
+    ///
+    /// This is set via `#[ink(internal)]`, where no code hashes allow any contract.
+    internal_code_hashes: Option<Vec<[u8; 32]>>,
+    /// If the ink! message returns its items in pages.
+    is_paginated: bool,
+    /// If the dispatch defers decoding the inputs of the ink! message until its
+    /// checks have passed.
+    has_deferred_inputs: bool,
+    /// If the ink! message is executed for calls with empty input data and a
+    /// nonzero transferred value.
+    ///
+    /// # Note
+    ///
+    /// This is set via `#[ink(receive)]` and implies `#[ink(payable)]`.
+    is_receive: bool,
+    /// The frozen version of the ABI of the ink! message if any.
+    ///
+    /// # Note
+    ///
+    /// This is set via `#[ink(abi_version = N)]`.
+    abi_version: Option<u32>,
+    /// The delay in milliseconds if the calls to the ink! message are queued in the
+    /// timelock of the contract.
+    ///
+    /// # Note
+    ///
+    /// This is set via `#[ink(timelocked(delay = "2 days"))]`.
+    timelock_delay: Option<u64>,
+    /// The names of the roles allowed to call the ink! message.
+    ///
+    /// # Note
+    ///
+    /// This is set via `#[ink(only(roles = ["admin", "operator"]))]`.
+    required_roles: Option<Vec<String>>,
     /// An optional user provided selector.
     ///
     /// # Note
@@ -171,6 +217,168 @@ impl Message {
         Ok(())
     }
 
+    /// Ensures that a `paginated` ink! message returns a value.
+    ///
+    /// # Note
+    ///
+    /// That the returned value is a page of `(Vec<T>, Option<Cursor>)` is checked
+    /// by the generated code since it can't be resolved from the syntax alone.
+    fn ensure_paginated_returns_page(
+        method_item: &syn::ImplItemMethod,
+        is_paginated: bool,
+    ) -> Result<(), syn::Error> {
+        if is_paginated && matches!(method_item.sig.output, syn::ReturnType::Default) {
+            return Err(format_err_spanned!(
+                method_item.sig,
+                "`paginated` ink! messages must return a page of `(Vec<T>, Option<Cursor>)`"
+            ))
+        }
+        Ok(())
+    }
+
+    /// Ensures that a `receive` ink! message can be executed for plain transfers.
+    ///
+    /// # Errors
+    ///
+    /// - If the message takes inputs, since plain transfers come without input data.
+    /// - If the message is timelocked, since timelocked messages must not be payable.
+    fn ensure_valid_receive(
+        method_item: &syn::ImplItemMethod,
+        ink_attrs: &ir::InkAttribute,
+    ) -> Result<(), syn::Error> {
+        if !ink_attrs.is_receive() {
+            return Ok(())
+        }
+        if let Some(input) = method_item
+            .sig
+            .inputs
+            .iter()
+            .find(|input| matches!(input, syn::FnArg::Typed(_)))
+        {
+            return Err(format_err_spanned!(
+                input,
+                "`receive` ink! messages must not take inputs"
+            ))
+        }
+        if ink_attrs.timelock_delay().is_some() {
+            return Err(format_err_spanned!(
+                method_item.sig,
+                "`receive` ink! messages must not be timelocked"
+            ))
+        }
+        Ok(())
+    }
+
+    /// Ensures that a `timelocked` ink! message can be queued.
+    ///
+    /// The call is queued in the storage of the contract and answered right away,
+    /// so the message must take `&mut self` and must not return a value. Values
+    /// transferred to it and its borrowed inputs would not outlive the call either.
+    fn ensure_timelocked_can_be_queued(
+        method_item: &syn::ImplItemMethod,
+        ink_attrs: &ir::InkAttribute,
+    ) -> Result<(), syn::Error> {
+        if ink_attrs.timelock_delay().is_none() {
+            return Ok(())
+        }
+        if !matches!(
+            method_item.sig.receiver(),
+            Some(syn::FnArg::Receiver(receiver)) if receiver.mutability.is_some()
+        ) {
+            return Err(format_err_spanned!(
+                method_item.sig,
+                "`timelocked` ink! messages must take `&mut self`"
+            ))
+        }
+        if !matches!(method_item.sig.output, syn::ReturnType::Default) {
+            return Err(format_err_spanned!(
+                method_item.sig.output,
+                "`timelocked` ink! messages must not return a value"
+            ))
+        }
+        if ink_attrs.is_payable() {
+            return Err(format_err_spanned!(
+                method_item.sig,
+                "`timelocked` ink! messages must not be payable"
+            ))
+        }
+        if let Some(input) = method_item.sig.inputs.iter().find(|input| {
+            matches!(input, syn::FnArg::Typed(pat_type) if matches!(&*pat_type.ty, syn::Type::Reference(_)))
+        }) {
+            return Err(format_err_spanned!(
+                input,
+                "`timelocked` ink! messages must not take borrowed inputs"
+            ))
+        }
+        Ok(())
+    }
+
+    /// Ensures that `RawInput` is only taken as the sole input of a wildcard message.
+    ///
+    /// # Errors
+    ///
+    /// - If a message without a wildcard selector takes a `RawInput`.
+    /// - If a wildcard message takes a `RawInput` next to other inputs.
+    fn ensure_raw_input_is_sole_wildcard_input(
+        method_item: &syn::ImplItemMethod,
+        selector: Option<&SelectorOrWildcard>,
+    ) -> Result<(), syn::Error> {
+        let inputs = method_item
+            .sig
+            .inputs
+            .iter()
+            .filter_map(|fn_arg| {
+                match fn_arg {
+                    syn::FnArg::Typed(pat_type) => Some(pat_type),
+                    syn::FnArg::Receiver(_) => None,
+                }
+            })
+            .collect::<Vec<_>>();
+        let is_wildcard = matches!(selector, Some(SelectorOrWildcard::Wildcard));
+        for input in &inputs {
+            if !is_raw_input(&input.ty) {
+                continue
+            }
+            if !is_wildcard {
+                return Err(format_err!(
+                    input.ty,
+                    "`RawInput` can only be taken by an ink! message with a wildcard selector",
+                ))
+            }
+            if inputs.len() != 1 {
+                return Err(format_err!(
+                    input.ty,
+                    "`RawInput` must be the only input of a wildcard ink! message",
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    /// Ensures that the ink! message only takes `&[u8]` and `&str` as borrowed inputs.
+    ///
+    /// # Errors
+    ///
+    /// If the message takes any other reference, a mutable reference or a
+    /// reference with an explicit lifetime.
+    fn ensure_valid_borrowed_inputs(
+        method_item: &syn::ImplItemMethod,
+    ) -> Result<(), syn::Error> {
+        for fn_arg in method_item.sig.inputs.iter() {
+            if let syn::FnArg::Typed(pat_type) = fn_arg {
+                if matches!(&*pat_type.ty, syn::Type::Reference(_))
+                    && !is_borrowed_input(&pat_type.ty)
+                {
+                    return Err(format_err_spanned!(
+                        pat_type.ty,
+                        "ink! messages only support `&[u8]` and `&str` as borrowed inputs",
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Sanitizes the attributes for the ink! message.
     ///
     /// Returns a tuple of ink! attributes and non-ink! attributes.
@@ -185,6 +393,15 @@ impl Message {
                 match arg.kind() {
                     ir::AttributeArg::Message
                     | ir::AttributeArg::Payable
+                    | ir::AttributeArg::PayableMin(_)
+                    | ir::AttributeArg::NonReentrant
+                    | ir::AttributeArg::Internal(_)
+                    | ir::AttributeArg::Paginated
+                    | ir::AttributeArg::DeferredInputs
+                    | ir::AttributeArg::Receive
+                    | ir::AttributeArg::AbiVersion(_)
+                    | ir::AttributeArg::Timelocked(_)
+                    | ir::AttributeArg::Only(_)
                     | ir::AttributeArg::Selector(_) => Ok(()),
                     _ => Err(None),
                 }
@@ -201,10 +418,33 @@ impl TryFrom<syn::ImplItemMethod> for Message {
         Self::ensure_receiver_is_self_ref(&method_item)?;
         Self::ensure_not_return_self(&method_item)?;
         let (ink_attrs, other_attrs) = Self::sanitize_attributes(&method_item)?;
-        let is_payable = ink_attrs.is_payable();
+        let is_receive = ink_attrs.is_receive();
+        let is_payable = ink_attrs.is_payable() || is_receive;
+        let min_transferred_value = ink_attrs.payable_min();
+        let is_non_reentrant = ink_attrs.is_non_reentrant();
+        let internal_code_hashes = ink_attrs.internal_code_hashes();
+        let is_paginated = ink_attrs.is_paginated();
+        let has_deferred_inputs = ink_attrs.has_deferred_inputs();
+        let abi_version = ink_attrs.abi_version();
+        let timelock_delay = ink_attrs.timelock_delay();
+        let required_roles = ink_attrs.required_roles();
         let selector = ink_attrs.selector();
+        Self::ensure_raw_input_is_sole_wildcard_input(&method_item, selector.as_ref())?;
+        Self::ensure_valid_borrowed_inputs(&method_item)?;
+        Self::ensure_paginated_returns_page(&method_item, is_paginated)?;
+        Self::ensure_valid_receive(&method_item, &ink_attrs)?;
+        Self::ensure_timelocked_can_be_queued(&method_item, &ink_attrs)?;
         Ok(Self {
             is_payable,
+            min_transferred_value,
+            is_non_reentrant,
+            internal_code_hashes,
+            is_paginated,
+            has_deferred_inputs,
+            is_receive,
+            abi_version,
+            timelock_delay,
+            required_roles,
             selector,
             item: syn::ImplItemMethod {
                 attrs: other_attrs,
@@ -283,6 +523,73 @@ impl Message {
         }
     }
 
+    /// Returns the minimum value that must be transferred to the ink! message if any.
+    pub fn min_transferred_value(&self) -> Option<u128> {
+        self.min_transferred_value
+    }
+
+    /// Returns `true` if the ink! message is guarded against reentrancy.
+    pub fn is_non_reentrant(&self) -> bool {
+        self.is_non_reentrant
+    }
+
+    /// Returns the code hashes of the contracts allowed to call the ink! message if
+    /// it is only callable by other contracts.
+    ///
+    /// The code hashes are empty if any contract is allowed to call.
+    pub fn internal_code_hashes(&self) -> Option<&[[u8; 32]]> {
+        self.internal_code_hashes.as_deref()
+    }
+
+    /// Returns `true` if the ink! message returns its items in pages.
+    pub fn is_paginated(&self) -> bool {
+        self.is_paginated
+    }
+
+    /// Returns `true` if the dispatch defers decoding the inputs of the ink! message
+    /// until its checks have passed.
+    pub fn has_deferred_inputs(&self) -> bool {
+        self.has_deferred_inputs
+    }
+
+    /// Returns `true` if the ink! message is executed for calls with empty input data
+    /// and a nonzero transferred value.
+    pub fn is_receive(&self) -> bool {
+        self.is_receive
+    }
+
+    /// Returns the frozen version of the ABI of the ink! message if any.
+    pub fn abi_version(&self) -> Option<u32> {
+        self.abi_version
+    }
+
+    /// Returns the delay in milliseconds if the calls to the ink! message are queued
+    /// in the timelock of the contract.
+    pub fn timelock_delay(&self) -> Option<u64> {
+        self.timelock_delay
+    }
+
+    /// Returns the names of the roles allowed to call the ink! message if its
+    /// callers are restricted to accounts having any of them.
+    pub fn required_roles(&self) -> Option<&[String]> {
+        self.required_roles.as_deref()
+    }
+
+    /// Returns `true` if the ink! message has no body and instead uses the default
+    /// implementation of its ink! trait definition.
+    ///
+    /// # Note
+    ///
+    /// This is only allowed for ink! messages in trait implementation blocks.
+    pub fn uses_trait_default(&self) -> bool {
+        match self.item.block.stmts.as_slice() {
+            [syn::Stmt::Item(syn::Item::Verbatim(verbatim))] => {
+                verbatim.to_string() == ";"
+            }
+            _ => false,
+        }
+    }
+
     /// Returns the return type of the ink! message if any.
     pub fn output(&self) -> Option<&syn::Type> {
         match &self.item.sig.output {
@@ -295,11 +602,18 @@ impl Message {
     ///
     /// This is used to to allow callers to handle certain types of errors which are not exposed
     /// by messages.
+    ///
+    /// Calls to `timelocked` messages are queued instead of executed, so they return the
+    /// `ink::contrib::OperationId` of the queued call instead.
     pub fn wrapped_output(&self) -> syn::Type {
-        let return_type = self
-            .output()
-            .map(quote::ToTokens::to_token_stream)
-            .unwrap_or_else(|| quote::quote! { () });
+        let return_type = match self.timelock_delay {
+            Some(_) => quote::quote! { ::ink::contrib::OperationId },
+            None => {
+                self.output()
+                    .map(quote::ToTokens::to_token_stream)
+                    .unwrap_or_else(|| quote::quote! { () })
+            }
+        };
 
         syn::parse_quote! {
             ::ink::MessageResult<#return_type>
@@ -321,6 +635,254 @@ impl Message {
     pub fn checked_ident(&self) -> Ident {
         quote::format_ident!("{}_checked", self.ident())
     }
+
+    /// Returns the identifier of the message with an additional `try_` prefix attached.
+    pub fn try_ident(&self) -> Ident {
+        quote::format_ident!("try_{}", self.ident())
+    }
+
+    /// Returns `true` if the return type of the message is syntactically a `Result`.
+    ///
+    /// # Note
+    ///
+    /// This only checks that the last path segment of the return type is named `Result`
+    /// so that commonly used type aliases such as `type Result<T> = Result<T, Error>`
+    /// are supported as well. The actual `Ok` and `Err` types are resolved via the
+    /// type system by the generated code.
+    pub fn returns_result(&self) -> bool {
+        match self.output() {
+            Some(syn::Type::Path(type_path)) => {
+                type_path.qself.is_none()
+                    && type_path
+                        .path
+                        .segments
+                        .last()
+                        .map(|segment| segment.ident == "Result")
+                        .unwrap_or(false)
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the message is a wildcard message taking the raw call input.
+    ///
+    /// # Note
+    ///
+    /// This only checks that the last path segment of the only input type is named
+    /// `RawInput`. The generated code requires it to be `ink::RawInput`.
+    pub fn takes_raw_input(&self) -> bool {
+        let mut inputs = self.inputs();
+        match (inputs.next(), inputs.next()) {
+            (Some(input), None) => {
+                self.has_wildcard_selector() && is_raw_input(&input.ty)
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the message takes any borrowed input, i.e. `&[u8]` or `&str`.
+    ///
+    /// # Note
+    ///
+    /// Borrowed inputs point into the input of the call, so they are only decoded
+    /// when executing the message.
+    pub fn has_borrowed_inputs(&self) -> bool {
+        self.inputs()
+            .any(|input| matches!(&*input.ty, syn::Type::Reference(_)))
+    }
+
+    /// Returns the Solidity style signature of the ink! message, e.g. `balanceOf(address)`.
+    ///
+    /// The name is the identifier of the message in lower camel case, followed by
+    /// the Solidity equivalents of its input types.
+    ///
+    /// # Errors
+    ///
+    /// If any input type has no known Solidity equivalent.
+    pub fn sol_signature(&self) -> Result<String, syn::Error> {
+        let params = self
+            .inputs()
+            .map(|input| {
+                sol_type(&input.ty).ok_or_else(|| {
+                    format_err_spanned!(
+                        input.ty,
+                        "encountered input type without a known Solidity equivalent, \
+                        use #[ink(selector = S:u32)] to provide the selector of the message",
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(format!(
+            "{}({})",
+            lower_camel_case(&self.ident().to_string()),
+            params.join(",")
+        ))
+    }
+
+    /// Prepares the ink! message for contracts using `#[ink::contract(abi = "solidity")]`.
+    ///
+    /// Unless provided by the user the selector becomes the first four bytes of the
+    /// Keccak-256 hash of the Solidity style signature of the message.
+    pub(crate) fn set_sol_selector(&mut self) -> Result<(), syn::Error> {
+        if self.has_wildcard_selector() {
+            return Err(format_err_spanned!(
+                self.item.sig,
+                "wildcard selectors are not supported by contracts with `abi = \"solidity\"`",
+            ))
+        }
+        if self.has_borrowed_inputs() {
+            return Err(format_err_spanned!(
+                self.item.sig.inputs,
+                "borrowed inputs are not supported by contracts with `abi = \"solidity\"`",
+            ))
+        }
+        if self.is_paginated {
+            return Err(format_err_spanned!(
+                self.item.sig,
+                "`paginated` ink! messages are not supported by contracts with `abi = \"solidity\"`",
+            ))
+        }
+        if self.timelock_delay.is_some() {
+            return Err(format_err_spanned!(
+                self.item.sig,
+                "`timelocked` ink! messages are not supported by contracts with `abi = \"solidity\"`",
+            ))
+        }
+        if self.user_provided_selector().is_none() {
+            use sha3::Digest as _;
+            let signature = self.sol_signature()?;
+            let hash: [u8; 32] = sha3::Keccak256::digest(signature.as_bytes()).into();
+            let selector = ir::Selector::from([hash[0], hash[1], hash[2], hash[3]]);
+            self.selector = Some(SelectorOrWildcard::UserProvided(selector));
+        }
+        Ok(())
+    }
+}
+
+/// Returns the Solidity equivalent of the Rust type `ty` if any.
+///
+/// # Note
+///
+/// This only inspects the syntax of the type. The environmental type aliases are
+/// mapped to the Solidity equivalents of the types of the default environment.
+fn sol_type(ty: &syn::Type) -> Option<String> {
+    match ty {
+        syn::Type::Paren(paren) => sol_type(&paren.elem),
+        syn::Type::Tuple(tuple) => {
+            let elems = tuple
+                .elems
+                .iter()
+                .map(sol_type)
+                .collect::<Option<Vec<_>>>()?;
+            Some(format!("({})", elems.join(",")))
+        }
+        syn::Type::Array(array) => {
+            match (&*array.elem, &array.len) {
+                (
+                    syn::Type::Path(elem),
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Int(len),
+                        ..
+                    }),
+                ) if elem.path.is_ident("u8") && len.base10_digits() == "32" => {
+                    Some(String::from("bytes32"))
+                }
+                _ => None,
+            }
+        }
+        syn::Type::Path(type_path) if type_path.qself.is_none() => {
+            let segment = type_path.path.segments.last()?;
+            match &segment.arguments {
+                syn::PathArguments::None => {
+                    let name = match segment.ident.to_string().as_str() {
+                        "bool" => "bool",
+                        "u8" => "uint8",
+                        "u16" => "uint16",
+                        "u32" | "BlockNumber" => "uint32",
+                        "u64" | "Timestamp" => "uint64",
+                        "u128" | "Balance" => "uint128",
+                        "i8" => "int8",
+                        "i16" => "int16",
+                        "i32" => "int32",
+                        "i64" => "int64",
+                        "i128" => "int128",
+                        "AccountId" | "Hash" => "bytes32",
+                        "Address" => "address",
+                        "Bytes" => "bytes",
+                        "String" => "string",
+                        _ => return None,
+                    };
+                    Some(String::from(name))
+                }
+                syn::PathArguments::AngleBracketed(args) if segment.ident == "Vec" => {
+                    match args.args.iter().collect::<Vec<_>>().as_slice() {
+                        [syn::GenericArgument::Type(elem)] => {
+                            Some(format!("{}[]", sol_type(elem)?))
+                        }
+                        _ => None,
+                    }
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Converts the snake case identifier `ident` into lower camel case.
+fn lower_camel_case(ident: &str) -> String {
+    let mut name = String::new();
+    let mut uppercase_next = false;
+    for c in ident.chars() {
+        if c == '_' {
+            uppercase_next = !name.is_empty();
+        } else if uppercase_next {
+            name.extend(c.to_uppercase());
+            uppercase_next = false;
+        } else {
+            name.push(c);
+        }
+    }
+    name
+}
+
+/// Returns `true` if `ty` is `&[u8]` or `&str` without an explicit lifetime.
+fn is_borrowed_input(ty: &syn::Type) -> bool {
+    /// Returns `true` if `ty` is the plain path `name`.
+    fn is_path(ty: &syn::Type, name: &str) -> bool {
+        matches!(ty, syn::Type::Path(type_path) if type_path.qself.is_none() && type_path.path.is_ident(name))
+    }
+    match ty {
+        syn::Type::Reference(reference) => {
+            let elided = reference
+                .lifetime
+                .as_ref()
+                .map(|lifetime| lifetime.ident == "_")
+                .unwrap_or(true);
+            let elem = match &*reference.elem {
+                syn::Type::Slice(slice) => is_path(&slice.elem, "u8"),
+                elem => is_path(elem, "str"),
+            };
+            reference.mutability.is_none() && elided && elem
+        }
+        _ => false,
+    }
+}
+
+/// Returns `true` if the last path segment of `ty` is named `RawInput`.
+fn is_raw_input(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(type_path) => {
+            type_path.qself.is_none()
+                && type_path
+                    .path
+                    .segments
+                    .last()
+                    .map(|segment| segment.ident == "RawInput")
+                    .unwrap_or(false)
+        }
+        _ => false,
+    }
 }
 
 #[cfg(test)]
@@ -364,6 +926,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn returns_result_works() {
+        let test_inputs: Vec<(bool, syn::ImplItemMethod)> = vec![
+            (
+                false,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn my_message(&self) {}
+                },
+            ),
+            (
+                false,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn my_message(&self) -> Option<i32> {}
+                },
+            ),
+            (
+                true,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn my_message(&self) -> Result<i32, Error> {}
+                },
+            ),
+            (
+                true,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn my_message(&self) -> core::result::Result<(), Error> {}
+                },
+            ),
+            (
+                // Type alias:
+                true,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn my_message(&self) -> Result<()> {}
+                },
+            ),
+        ];
+        for (expected, item_method) in test_inputs {
+            let actual = <ir::Message as TryFrom<_>>::try_from(item_method)
+                .unwrap()
+                .returns_result();
+            assert_eq!(actual, expected);
+        }
+    }
+
     #[test]
     fn inputs_works() {
         macro_rules! expected_inputs {
@@ -461,83 +1071,407 @@ mod tests {
     }
 
     #[test]
-    fn receiver_works() {
-        let test_inputs: Vec<(Receiver, syn::ImplItemMethod)> = vec![
+    fn min_transferred_value_works() {
+        let test_inputs: Vec<(Option<u128>, bool, syn::ImplItemMethod)> = vec![
             (
-                Receiver::Ref,
+                None,
+                false,
                 syn::parse_quote! {
                     #[ink(message)]
-                    fn my_message(&self) {}
+                    pub fn my_message(&mut self) {}
                 },
             ),
             (
-                Receiver::RefMut,
+                None,
+                true,
                 syn::parse_quote! {
                     #[ink(message, payable)]
-                    fn my_message(&mut self) {}
+                    pub fn my_message(&mut self) {}
+                },
+            ),
+            (
+                Some(1_000),
+                true,
+                syn::parse_quote! {
+                    #[ink(message, payable(min = 1_000))]
+                    pub fn my_message(&mut self) {}
                 },
             ),
         ];
-        for (expected_receiver, item_method) in test_inputs {
-            let actual_receiver = <ir::Message as TryFrom<_>>::try_from(item_method)
-                .unwrap()
-                .receiver();
-            assert_eq!(actual_receiver, expected_receiver);
+        for (expect_min, expect_payable, item_method) in test_inputs {
+            let message = <ir::Message as TryFrom<_>>::try_from(item_method).unwrap();
+            assert_eq!(message.min_transferred_value(), expect_min);
+            assert_eq!(message.is_payable(), expect_payable);
         }
     }
 
     #[test]
-    fn visibility_works() {
+    fn is_paginated_works() {
         let test_inputs: Vec<(bool, syn::ImplItemMethod)> = vec![
-            // &self
             (
                 false,
                 syn::parse_quote! {
                     #[ink(message)]
-                    fn my_message(&self) {}
+                    pub fn my_message(&self) -> (Vec<u8>, Option<Cursor>) {}
                 },
             ),
-            // &self + pub
             (
                 true,
                 syn::parse_quote! {
-                    #[ink(message)]
-                    pub fn my_message(&self) {}
+                    #[ink(message, paginated)]
+                    pub fn my_message(&self, cursor: Option<Cursor>) -> (Vec<u8>, Option<Cursor>) {}
                 },
             ),
-            // &mut self
+        ];
+        for (expect_paginated, item_method) in test_inputs {
+            let is_paginated = <ir::Message as TryFrom<_>>::try_from(item_method)
+                .unwrap()
+                .is_paginated();
+            assert_eq!(is_paginated, expect_paginated);
+        }
+    }
+
+    #[test]
+    fn has_deferred_inputs_works() {
+        let test_inputs: Vec<(bool, syn::ImplItemMethod)> = vec![
             (
                 false,
                 syn::parse_quote! {
                     #[ink(message)]
-                    fn my_message(&mut self) {}
+                    pub fn my_message(&self, data: Vec<u8>) {}
                 },
             ),
-            // &mut self + pub
             (
                 true,
                 syn::parse_quote! {
-                    #[ink(message)]
-                    pub fn my_message(&mut self) {}
+                    #[ink(message, deferred_inputs)]
+                    pub fn my_message(&self, data: Vec<u8>) {}
                 },
             ),
         ];
-        for (is_pub, item_method) in test_inputs {
-            let visibility = <ir::Message as TryFrom<_>>::try_from(item_method)
+        for (expect_deferred_inputs, item_method) in test_inputs {
+            let has_deferred_inputs = <ir::Message as TryFrom<_>>::try_from(item_method)
                 .unwrap()
-                .visibility();
-            assert_eq!(visibility.is_pub(), is_pub);
-            assert_eq!(visibility.is_inherited(), !is_pub);
+                .has_deferred_inputs();
+            assert_eq!(has_deferred_inputs, expect_deferred_inputs);
         }
     }
 
     #[test]
-    fn try_from_works() {
-        let item_methods: Vec<syn::ImplItemMethod> = vec![
-            // &self
-            syn::parse_quote! {
-                #[ink(message)]
-                fn my_message(&self) {}
+    fn required_roles_works() {
+        let test_inputs: Vec<(Option<Vec<&str>>, syn::ImplItemMethod)> = vec![
+            (
+                None,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    pub fn my_message(&mut self) {}
+                },
+            ),
+            (
+                Some(vec!["admin", "operator"]),
+                syn::parse_quote! {
+                    #[ink(message, only(roles = ["admin", "operator"]))]
+                    pub fn my_message(&mut self) {}
+                },
+            ),
+        ];
+        for (expect_roles, item_method) in test_inputs {
+            let message = <ir::Message as TryFrom<_>>::try_from(item_method).unwrap();
+            let roles = message
+                .required_roles()
+                .map(|roles| roles.iter().map(String::as_str).collect::<Vec<_>>());
+            assert_eq!(roles, expect_roles);
+        }
+    }
+
+    #[test]
+    fn timelock_delay_works() {
+        let test_inputs: Vec<(Option<u64>, syn::ImplItemMethod)> = vec![
+            (
+                None,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    pub fn my_message(&mut self, value: u32) {}
+                },
+            ),
+            (
+                Some(3_600_000),
+                syn::parse_quote! {
+                    #[ink(message, timelocked(delay = "1 hour"))]
+                    pub fn my_message(&mut self, value: u32) {}
+                },
+            ),
+        ];
+        for (expect_delay, item_method) in test_inputs {
+            let delay = <ir::Message as TryFrom<_>>::try_from(item_method)
+                .unwrap()
+                .timelock_delay();
+            assert_eq!(delay, expect_delay);
+        }
+    }
+
+    #[test]
+    fn try_from_timelocked_which_cannot_be_queued_fails() {
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(message, timelocked(delay = "1 hour"))]
+                pub fn my_message(&mut self) -> u32 {}
+            },
+            "`timelocked` ink! messages must not return a value",
+        );
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(message, timelocked(delay = "1 hour"))]
+                pub fn my_message(&self) {}
+            },
+            "`timelocked` ink! messages must take `&mut self`",
+        );
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(message, payable, timelocked(delay = "1 hour"))]
+                pub fn my_message(&mut self) {}
+            },
+            "`timelocked` ink! messages must not be payable",
+        );
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(message, timelocked(delay = "1 hour"))]
+                pub fn my_message(&mut self, data: &[u8]) {}
+            },
+            "`timelocked` ink! messages must not take borrowed inputs",
+        );
+    }
+
+    #[test]
+    fn is_receive_works() {
+        let test_inputs: Vec<(bool, syn::ImplItemMethod)> = vec![
+            (
+                false,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    pub fn my_message(&mut self) {}
+                },
+            ),
+            (
+                true,
+                syn::parse_quote! {
+                    #[ink(message, receive)]
+                    pub fn my_message(&mut self) {}
+                },
+            ),
+        ];
+        for (expect_receive, item_method) in test_inputs {
+            let message = <ir::Message as TryFrom<_>>::try_from(item_method).unwrap();
+            assert_eq!(message.is_receive(), expect_receive);
+            assert_eq!(message.is_payable(), expect_receive);
+        }
+    }
+
+    #[test]
+    fn abi_version_works() {
+        let test_inputs: Vec<(Option<u32>, syn::ImplItemMethod)> = vec![
+            (
+                None,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    pub fn my_message(&self, value: u32) {}
+                },
+            ),
+            (
+                Some(2),
+                syn::parse_quote! {
+                    #[ink(message, abi_version = 2)]
+                    pub fn my_message(&self, value: u32) {}
+                },
+            ),
+        ];
+        for (expected_version, item_method) in test_inputs {
+            let message = <ir::Message as TryFrom<_>>::try_from(item_method).unwrap();
+            assert_eq!(message.abi_version(), expected_version);
+        }
+    }
+
+    #[test]
+    fn try_from_invalid_receive_fails() {
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(message, receive)]
+                pub fn my_message(&mut self, from: AccountId) {}
+            },
+            "`receive` ink! messages must not take inputs",
+        );
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(message, receive, timelocked(delay = "1 hour"))]
+                pub fn my_message(&mut self) {}
+            },
+            "`receive` ink! messages must not be timelocked",
+        );
+    }
+
+    #[test]
+    fn try_from_paginated_without_output_fails() {
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(message, paginated)]
+                pub fn my_message(&self) {}
+            },
+            "`paginated` ink! messages must return a page of `(Vec<T>, Option<Cursor>)`",
+        )
+    }
+
+    #[test]
+    fn is_non_reentrant_works() {
+        let test_inputs: Vec<(bool, syn::ImplItemMethod)> = vec![
+            // Not guarded.
+            (
+                false,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn my_message(&mut self) {}
+                },
+            ),
+            // Normalized ink! attribute.
+            (
+                true,
+                syn::parse_quote! {
+                    #[ink(message, non_reentrant)]
+                    pub fn my_message(&mut self) {}
+                },
+            ),
+            // Different ink! attributes.
+            (
+                true,
+                syn::parse_quote! {
+                    #[ink(message, payable)]
+                    #[ink(non_reentrant)]
+                    pub fn my_message(&mut self) {}
+                },
+            ),
+        ];
+        for (expect_non_reentrant, item_method) in test_inputs {
+            let is_non_reentrant = <ir::Message as TryFrom<_>>::try_from(item_method)
+                .unwrap()
+                .is_non_reentrant();
+            assert_eq!(is_non_reentrant, expect_non_reentrant);
+        }
+    }
+
+    #[test]
+    fn internal_code_hashes_works() {
+        let test_inputs: Vec<(Option<Vec<[u8; 32]>>, syn::ImplItemMethod)> = vec![
+            (
+                None,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    pub fn my_message(&mut self) {}
+                },
+            ),
+            (
+                Some(Vec::new()),
+                syn::parse_quote! {
+                    #[ink(message, internal)]
+                    pub fn my_message(&mut self) {}
+                },
+            ),
+            (
+                Some(vec![[0x01; 32]]),
+                syn::parse_quote! {
+                    #[ink(message)]
+                    #[ink(internal(
+                        code_hash = "0x0101010101010101010101010101010101010101010101010101010101010101"
+                    ))]
+                    pub fn my_message(&mut self) {}
+                },
+            ),
+        ];
+        for (expect_code_hashes, item_method) in test_inputs {
+            let message = <ir::Message as TryFrom<_>>::try_from(item_method).unwrap();
+            assert_eq!(
+                message.internal_code_hashes(),
+                expect_code_hashes.as_deref()
+            );
+        }
+    }
+
+    #[test]
+    fn receiver_works() {
+        let test_inputs: Vec<(Receiver, syn::ImplItemMethod)> = vec![
+            (
+                Receiver::Ref,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn my_message(&self) {}
+                },
+            ),
+            (
+                Receiver::RefMut,
+                syn::parse_quote! {
+                    #[ink(message, payable)]
+                    fn my_message(&mut self) {}
+                },
+            ),
+        ];
+        for (expected_receiver, item_method) in test_inputs {
+            let actual_receiver = <ir::Message as TryFrom<_>>::try_from(item_method)
+                .unwrap()
+                .receiver();
+            assert_eq!(actual_receiver, expected_receiver);
+        }
+    }
+
+    #[test]
+    fn visibility_works() {
+        let test_inputs: Vec<(bool, syn::ImplItemMethod)> = vec![
+            // &self
+            (
+                false,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn my_message(&self) {}
+                },
+            ),
+            // &self + pub
+            (
+                true,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    pub fn my_message(&self) {}
+                },
+            ),
+            // &mut self
+            (
+                false,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn my_message(&mut self) {}
+                },
+            ),
+            // &mut self + pub
+            (
+                true,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    pub fn my_message(&mut self) {}
+                },
+            ),
+        ];
+        for (is_pub, item_method) in test_inputs {
+            let visibility = <ir::Message as TryFrom<_>>::try_from(item_method)
+                .unwrap()
+                .visibility();
+            assert_eq!(visibility.is_pub(), is_pub);
+            assert_eq!(visibility.is_inherited(), !is_pub);
+        }
+    }
+
+    #[test]
+    fn try_from_works() {
+        let item_methods: Vec<syn::ImplItemMethod> = vec![
+            // &self
+            syn::parse_quote! {
+                #[ink(message)]
+                fn my_message(&self) {}
             },
             // &self + pub
             syn::parse_quote! {
@@ -772,6 +1706,128 @@ mod tests {
         }
     }
 
+    #[test]
+    fn takes_raw_input_works() {
+        let test_inputs: Vec<(bool, syn::ImplItemMethod)> = vec![
+            (
+                true,
+                syn::parse_quote! {
+                    #[ink(message, selector = _)]
+                    fn my_message(&self, input: ink::RawInput) {}
+                },
+            ),
+            (
+                true,
+                syn::parse_quote! {
+                    #[ink(message, payable, selector = _)]
+                    fn my_message(&mut self, input: RawInput) -> u32 {}
+                },
+            ),
+            (
+                false,
+                syn::parse_quote! {
+                    #[ink(message, selector = _)]
+                    fn my_message(&self, input: Vec<u8>) {}
+                },
+            ),
+            (
+                false,
+                syn::parse_quote! {
+                    #[ink(message, selector = _)]
+                    fn my_message(&self) {}
+                },
+            ),
+        ];
+        for (expected, item_method) in test_inputs {
+            let message = <ir::Message as TryFrom<_>>::try_from(item_method).unwrap();
+            assert_eq!(message.takes_raw_input(), expected);
+        }
+    }
+
+    #[test]
+    fn try_from_raw_input_fails() {
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(message)]
+                fn my_message(&self, input: ink::RawInput) {}
+            },
+            "`RawInput` can only be taken by an ink! message with a wildcard selector",
+        );
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(message, selector = 0xCAFEBABE)]
+                fn my_message(&self, input: RawInput) {}
+            },
+            "`RawInput` can only be taken by an ink! message with a wildcard selector",
+        );
+        assert_try_from_fails(
+            syn::parse_quote! {
+                #[ink(message, selector = _)]
+                fn my_message(&self, input: RawInput, other: i32) {}
+            },
+            "`RawInput` must be the only input of a wildcard ink! message",
+        );
+    }
+
+    #[test]
+    fn has_borrowed_inputs_works() {
+        let test_inputs: Vec<(bool, syn::ImplItemMethod)> = vec![
+            (
+                true,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn my_message(&self, data: &[u8]) {}
+                },
+            ),
+            (
+                true,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn my_message(&mut self, a: i32, name: &'_ str) {}
+                },
+            ),
+            (
+                false,
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn my_message(&self, data: Vec<u8>) {}
+                },
+            ),
+        ];
+        for (expected, item_method) in test_inputs {
+            let message = <ir::Message as TryFrom<_>>::try_from(item_method).unwrap();
+            assert_eq!(message.has_borrowed_inputs(), expected);
+        }
+    }
+
+    #[test]
+    fn try_from_invalid_borrowed_input_fails() {
+        let item_methods: Vec<syn::ImplItemMethod> = vec![
+            syn::parse_quote! {
+                #[ink(message)]
+                fn my_message(&self, value: &i32) {}
+            },
+            syn::parse_quote! {
+                #[ink(message)]
+                fn my_message(&self, data: &mut [u8]) {}
+            },
+            syn::parse_quote! {
+                #[ink(message)]
+                fn my_message(&self, name: &'a str) {}
+            },
+            syn::parse_quote! {
+                #[ink(message)]
+                fn my_message(&self, data: &Vec<u8>) {}
+            },
+        ];
+        for item_method in item_methods {
+            assert_try_from_fails(
+                item_method,
+                "ink! messages only support `&[u8]` and `&str` as borrowed inputs",
+            )
+        }
+    }
+
     #[test]
     fn conflicting_attributes_fails() {
         let item_methods: Vec<syn::ImplItemMethod> = vec![
@@ -791,6 +1847,11 @@ mod tests {
                 #[ink(event)]
                 fn my_message(&self) {}
             },
+            // refunds are only supported for constructors
+            syn::parse_quote! {
+                #[ink(message, payable(refund_excess = 1))]
+                fn my_message(&self) {}
+            },
         ];
         for item_method in item_methods {
             assert_try_from_fails(
@@ -799,4 +1860,104 @@ mod tests {
             )
         }
     }
+
+    #[test]
+    fn sol_signature_works() {
+        let test_inputs: Vec<(&str, syn::ImplItemMethod)> = vec![
+            (
+                "totalSupply()",
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn total_supply(&self) -> Balance {}
+                },
+            ),
+            (
+                "transferFrom(address,address,uint128)",
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn transfer_from(&mut self, from: Address, to: Address, value: u128) {}
+                },
+            ),
+            (
+                "submit(bytes32,(uint8,bool),string[],uint8[],bytes)",
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn submit(
+                        &mut self,
+                        id: [u8; 32],
+                        pair: (u8, bool),
+                        names: Vec<String>,
+                        raw: Vec<u8>,
+                        data: ink::sol::Bytes,
+                    ) {}
+                },
+            ),
+        ];
+        for (expected, item_method) in test_inputs {
+            let message = <ir::Message as TryFrom<_>>::try_from(item_method).unwrap();
+            assert_eq!(message.sol_signature().unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn set_sol_selector_works() {
+        let mut message =
+            <ir::Message as TryFrom<syn::ImplItemMethod>>::try_from(syn::parse_quote! {
+                #[ink(message)]
+                fn balance_of(&self, owner: Address) -> u128 {}
+            })
+            .unwrap();
+        message.set_sol_selector().unwrap();
+        assert_eq!(
+            message.user_provided_selector(),
+            Some(&ir::Selector::from([0x70, 0xA0, 0x82, 0x31]))
+        );
+
+        let mut message =
+            <ir::Message as TryFrom<syn::ImplItemMethod>>::try_from(syn::parse_quote! {
+                #[ink(message, selector = 0xCAFEBABE)]
+                fn balance_of(&self, owner: Option<Address>) -> u128 {}
+            })
+            .unwrap();
+        message.set_sol_selector().unwrap();
+        assert_eq!(
+            message.user_provided_selector(),
+            Some(&ir::Selector::from([0xCA, 0xFE, 0xBA, 0xBE]))
+        );
+    }
+
+    #[test]
+    fn set_sol_selector_fails() {
+        let test_inputs: Vec<(&str, syn::ImplItemMethod)> = vec![
+            (
+                "encountered input type without a known Solidity equivalent, \
+                 use #[ink(selector = S:u32)] to provide the selector of the message",
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn balance_of(&self, owner: Option<Address>) -> u128 {}
+                },
+            ),
+            (
+                "wildcard selectors are not supported by contracts with `abi = \"solidity\"`",
+                syn::parse_quote! {
+                    #[ink(message, selector = _)]
+                    fn fallback(&self) {}
+                },
+            ),
+            (
+                "borrowed inputs are not supported by contracts with `abi = \"solidity\"`",
+                syn::parse_quote! {
+                    #[ink(message)]
+                    fn name_len(&self, name: &str) -> u32 {}
+                },
+            ),
+        ];
+        for (expected, item_method) in test_inputs {
+            let mut message = <ir::Message as TryFrom<_>>::try_from(item_method).unwrap();
+            assert_eq!(
+                message.set_sol_selector().map_err(|err| err.to_string()),
+                Err(expected.to_string())
+            );
+        }
+    }
 }
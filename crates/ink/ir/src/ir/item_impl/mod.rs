@@ -20,6 +20,8 @@ use crate::{
 use proc_macro2::{
     Ident,
     Span,
+    TokenStream as TokenStream2,
+    TokenTree,
 };
 
 mod callable;
@@ -51,7 +53,10 @@ pub use self::{
         Receiver,
     },
 };
-use quote::TokenStreamExt as _;
+use quote::{
+    ToTokens as _,
+    TokenStreamExt as _,
+};
 use syn::spanned::Spanned;
 
 /// An ink! implementation block.
@@ -207,6 +212,44 @@ impl ItemImpl {
     }
 }
 
+/// Ensures that the signature of an ink! message or constructor does not use the
+/// `type_params` of its generic implementation block.
+///
+/// # Note
+///
+/// The type parameters are only chosen for the dispatch of the contract, whose
+/// generated code refers to the signatures outside of the implementation block.
+fn ensure_no_type_params(
+    type_params: &[&Ident],
+    sig: &syn::Signature,
+) -> Result<(), syn::Error> {
+    fn find_type_param(type_params: &[&Ident], tokens: TokenStream2) -> Option<Ident> {
+        tokens.into_iter().find_map(|token| {
+            match token {
+                TokenTree::Ident(ident) if type_params.contains(&&ident) => Some(ident),
+                TokenTree::Group(group) => find_type_param(type_params, group.stream()),
+                _ => None,
+            }
+        })
+    }
+    let inputs = sig.inputs.iter().filter_map(|input| {
+        match input {
+            syn::FnArg::Typed(pat_type) => Some(pat_type.ty.to_token_stream()),
+            syn::FnArg::Receiver(_) => None,
+        }
+    });
+    for tokens in inputs.chain(core::iter::once(sig.output.to_token_stream())) {
+        if let Some(type_param) = find_type_param(type_params, tokens) {
+            return Err(format_err!(
+                type_param.span(),
+                "the type parameters of generic ink! implementation blocks must not be \
+                used in the signatures of ink! messages or constructors",
+            ))
+        }
+    }
+    Ok(())
+}
+
 impl TryFrom<syn::ItemImpl> for ItemImpl {
     type Error = syn::Error;
 
@@ -230,11 +273,19 @@ impl TryFrom<syn::ItemImpl> for ItemImpl {
                 "unsafe ink! implementation blocks are not supported",
             ))
         }
-        if !item_impl.generics.params.is_empty() {
-            return Err(format_err_spanned!(
-                item_impl.generics.params,
-                "generic ink! implementation blocks are not supported",
-            ))
+        let mut type_params = Vec::new();
+        for param in &item_impl.generics.params {
+            match param {
+                syn::GenericParam::Type(type_param) => {
+                    type_params.push(&type_param.ident)
+                }
+                _ => {
+                    return Err(format_err_spanned!(
+                        param,
+                        "generic ink! implementation blocks only support type parameters",
+                    ))
+                }
+            }
         }
         let impl_items = item_impl
             .items
@@ -276,6 +327,19 @@ impl TryFrom<syn::ItemImpl> for ItemImpl {
                         "message",
                         is_trait_impl,
                     )?;
+                    if message.has_borrowed_inputs() && is_trait_impl {
+                        return Err(format_err!(
+                            message.item.span(),
+                            "ink! messages in trait impl blocks do not support borrowed inputs",
+                        ))
+                    }
+                    if message.uses_trait_default() && !is_trait_impl {
+                        return Err(format_err!(
+                            message.item.span(),
+                            "ink! messages without a body are only allowed in trait impl blocks",
+                        ))
+                    }
+                    ensure_no_type_params(&type_params, &message.item.sig)?;
                 }
                 ir::ImplItem::Constructor(constructor) => {
                     ensure_valid_visibility(
@@ -284,6 +348,7 @@ impl TryFrom<syn::ItemImpl> for ItemImpl {
                         "constructor",
                         is_trait_impl,
                     )?;
+                    ensure_no_type_params(&type_params, &constructor.item.sig)?;
                 }
                 _ => (),
             }
@@ -337,6 +402,16 @@ impl ItemImpl {
         self.self_ty.as_ref()
     }
 
+    /// Returns the generics of the implementation block.
+    ///
+    /// # Note
+    ///
+    /// Generic implementation blocks are only allowed for generic ink! storage
+    /// structs, see the `config` argument of `#[ink::contract]`.
+    pub fn generics(&self) -> &syn::Generics {
+        &self.generics
+    }
+
     /// Returns the trait type path if this is a trait implementation block.
     ///
     /// Returns `None` if this is an inherent implementation block.
@@ -363,6 +438,17 @@ impl ItemImpl {
         IterMessages::new(self)
     }
 
+    /// Returns an iterator yielding mutable references to the ink! messages of the
+    /// implementation block.
+    pub(crate) fn messages_mut(&mut self) -> impl Iterator<Item = &mut ir::Message> {
+        self.items.iter_mut().filter_map(|item| {
+            match item {
+                ImplItem::Message(message) => Some(message),
+                _ => None,
+            }
+        })
+    }
+
     /// Returns an iterator yielding the ink! messages of the implementation block.
     pub fn iter_constructors(&self) -> IterConstructors {
         IterConstructors::new(self)
@@ -206,6 +206,65 @@ fn visibility_fails() {
     );
 }
 
+#[test]
+fn message_without_body_fails() {
+    assert_try_from_item_impl_fails(
+        syn::parse_quote! {
+            impl MyStorage {
+                #[ink(message)]
+                pub fn my_message(&self);
+            }
+        },
+        "ink! messages without a body are only allowed in trait impl blocks",
+    );
+}
+
+#[test]
+fn trait_message_with_borrowed_input_fails() {
+    assert_try_from_item_impl_fails(
+        syn::parse_quote! {
+            impl MyTrait for MyStorage {
+                #[ink(message)]
+                fn my_message(&self, data: &[u8]) {}
+            }
+        },
+        "ink! messages in trait impl blocks do not support borrowed inputs",
+    );
+}
+
+#[test]
+fn generic_impl_block_fails() {
+    assert_try_from_item_impl_fails(
+        syn::parse_quote! {
+            impl<'a> MyStorage<'a> {
+                #[ink(message)]
+                pub fn my_message(&self) {}
+            }
+        },
+        "generic ink! implementation blocks only support type parameters",
+    );
+    assert_try_from_item_impl_fails(
+        syn::parse_quote! {
+            impl<T: Config> MyStorage<T> {
+                #[ink(message)]
+                pub fn my_message(&self, amount: T::Amount) {}
+            }
+        },
+        "the type parameters of generic ink! implementation blocks must not be \
+        used in the signatures of ink! messages or constructors",
+    );
+    assert_try_from_item_impl_fails(
+        syn::parse_quote! {
+            impl<T: Config> MyStorage<T> {
+                #[ink(constructor)]
+                pub fn my_constructor() -> Result<Self, Error<T>> {}
+            }
+        },
+        "the type parameters of generic ink! implementation blocks must not be \
+        used in the signatures of ink! messages or constructors",
+    );
+}
+
 #[test]
 fn try_from_works() {
     let item_impls: Vec<syn::ItemImpl> = vec![
@@ -229,6 +288,23 @@ fn try_from_works() {
                 fn my_message(&self) {}
             }
         },
+        syn::parse_quote! {
+            impl MyTrait for MyStorage {
+                #[ink(message)]
+                fn my_default_message(&self);
+            }
+        },
+        syn::parse_quote! {
+            impl<T: Config> MyStorage<T> {
+                #[ink(constructor)]
+                pub fn my_constructor() -> Self {}
+                #[ink(message)]
+                pub fn my_message(&self) -> u8 {
+                    T::DECIMALS
+                }
+                fn my_helper(&self, amount: T::Amount) {}
+            }
+        },
     ];
     for item_impl in item_impls {
         assert!(<ir::ItemImpl as TryFrom<syn::ItemImpl>>::try_from(item_impl).is_ok())
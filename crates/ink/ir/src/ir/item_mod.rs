@@ -290,6 +290,268 @@ impl ItemMod {
         }
         Ok(())
     }
+
+    /// Ensures that at most one ink! message is flagged with `#[ink(receive)]`.
+    fn ensure_only_one_receive(items: &[ir::Item]) -> Result<(), syn::Error> {
+        let mut receive: Option<&ir::Message> = None;
+        for message in items
+            .iter()
+            .filter_map(ir::Item::map_ink_item)
+            .filter_map(ir::InkItem::filter_map_impl_block)
+            .flat_map(|item_impl| item_impl.iter_messages())
+        {
+            if !message.is_receive() {
+                continue
+            }
+            match receive {
+                None => receive = Some(message.callable()),
+                Some(overlap) => {
+                    use crate::error::ExtError as _;
+                    return Err(format_err!(
+                        message.callable().span(),
+                        "encountered multiple `receive` ink! messages",
+                    )
+                    .into_combine(format_err!(
+                        overlap.span(),
+                        "first `receive` ink! message here",
+                    )))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds the `grant_role` and `revoke_role` messages managing the
+    /// `ink::contrib::AccessControl` of the contract if any ink! message is
+    /// flagged with `#[ink(only(roles = [..]))]`.
+    ///
+    /// # Note
+    ///
+    /// The existence of exactly one storage struct must be ensured beforehand.
+    fn add_access_control_messages(items: &mut Vec<ir::Item>) -> Result<(), syn::Error> {
+        let uses_roles = items
+            .iter()
+            .filter_map(|item| item.map_ink_item())
+            .filter_map(ir::InkItem::filter_map_impl_block)
+            .flat_map(ir::ItemImpl::iter_messages)
+            .any(|message| message.callable().required_roles().is_some());
+        if !uses_roles {
+            return Ok(())
+        }
+        let storage_ident = items
+            .iter()
+            .find_map(|item| {
+                match item {
+                    ir::Item::Ink(ir::InkItem::Storage(storage)) => Some(storage.ident()),
+                    _ => None,
+                }
+            })
+            .expect("encountered ink! module without a storage struct");
+        let item_impl: syn::ItemImpl = syn::parse_quote! {
+            impl #storage_ident {
+                /// Grants `role` to `account`.
+                ///
+                /// Can only be called by accounts having the admin role.
+                #[ink(message)]
+                pub fn grant_role(
+                    &mut self,
+                    role: ::ink::contrib::Role,
+                    account: <<#storage_ident as ::ink::reflect::ContractEnv>::Env as ::ink::env::Environment>::AccountId,
+                ) -> ::core::result::Result<(), ::ink::contrib::AccessControlError> {
+                    <Self as ::ink::contrib::AccessControlled<
+                        <Self as ::ink::reflect::ContractEnv>::Env>>::access_control(self)
+                        .grant_role(role, account)
+                }
+
+                /// Revokes `role` from `account`.
+                ///
+                /// Can only be called by accounts having the admin role.
+                #[ink(message)]
+                pub fn revoke_role(
+                    &mut self,
+                    role: ::ink::contrib::Role,
+                    account: <<#storage_ident as ::ink::reflect::ContractEnv>::Env as ::ink::env::Environment>::AccountId,
+                ) -> ::core::result::Result<(), ::ink::contrib::AccessControlError> {
+                    <Self as ::ink::contrib::AccessControlled<
+                        <Self as ::ink::reflect::ContractEnv>::Env>>::access_control(self)
+                        .revoke_role(role, account)
+                }
+            }
+        };
+        let item_impl = <ir::ItemImpl as TryFrom<_>>::try_from(item_impl)?;
+        items.push(ir::Item::Ink(ir::InkItem::ImplBlock(item_impl)));
+        Ok(())
+    }
+
+    /// Returns `true` if `path` names the `ink::contrib::Ownable2Step` derive.
+    ///
+    /// # Note
+    ///
+    /// The path can't be resolved by a procedural macro, hence only the exported
+    /// path and the identifier imported from `ink::contrib` are recognized.
+    fn is_ownable_derive(path: &syn::Path) -> bool {
+        let segments = path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect::<Vec<_>>();
+        match segments.as_slice() {
+            [ownable] => path.leading_colon.is_none() && ownable == "Ownable2Step",
+            [ink, contrib, ownable] => {
+                ink == "ink" && contrib == "contrib" && ownable == "Ownable2Step"
+            }
+            _ => false,
+        }
+    }
+
+    /// Adds the ink! messages and ink! events of the two step ownership transfer
+    /// if the ink! storage struct derives `ink::contrib::Ownable2Step`.
+    ///
+    /// # Note
+    ///
+    /// The existence of exactly one storage struct must be ensured beforehand.
+    fn add_ownable_items(items: &mut Vec<ir::Item>) -> Result<(), syn::Error> {
+        let storage = items
+            .iter()
+            .find_map(|item| {
+                match item {
+                    ir::Item::Ink(ir::InkItem::Storage(storage)) => Some(storage),
+                    _ => None,
+                }
+            })
+            .expect("encountered ink! module without a storage struct");
+        let mut derives_ownable = false;
+        for attr in storage.attrs() {
+            if !attr.path.is_ident("derive") {
+                continue
+            }
+            let derives = attr.parse_args_with(
+                syn::punctuated::Punctuated::<syn::Path, syn::Token![,]>::parse_terminated,
+            )?;
+            for path in derives {
+                if Self::is_ownable_derive(&path) {
+                    derives_ownable = true
+                } else if matches!(
+                    path.segments.last(),
+                    Some(segment) if segment.ident == "Ownable2Step"
+                ) {
+                    return Err(format_err_spanned!(
+                        path,
+                        "ink! only recognizes the `Ownable2Step` derive as `Ownable2Step` \
+                        or `ink::contrib::Ownable2Step`",
+                    ))
+                }
+            }
+        }
+        if !derives_ownable {
+            return Ok(())
+        }
+        let storage_ident = storage.ident().clone();
+        let account_id: syn::Type = syn::parse_quote! {
+            <<#storage_ident as ::ink::reflect::ContractEnv>::Env as ::ink::env::Environment>::AccountId
+        };
+        let owned: syn::Path = syn::parse_quote! {
+            ::ink::contrib::Owned<<#storage_ident as ::ink::reflect::ContractEnv>::Env>
+        };
+        let events: [syn::ItemStruct; 2] = [
+            syn::parse_quote! {
+                /// Emitted when the owner starts transferring the ownership.
+                #[ink(event)]
+                pub struct OwnershipTransferStarted {
+                    #[ink(topic)]
+                    previous_owner: ::core::option::Option<#account_id>,
+                    #[ink(topic)]
+                    new_owner: ::core::option::Option<#account_id>,
+                }
+            },
+            syn::parse_quote! {
+                /// Emitted when the ownership is accepted or renounced.
+                #[ink(event)]
+                pub struct OwnershipTransferred {
+                    #[ink(topic)]
+                    previous_owner: ::core::option::Option<#account_id>,
+                    #[ink(topic)]
+                    new_owner: ::core::option::Option<#account_id>,
+                }
+            },
+        ];
+        for event in events {
+            let event = <ir::Event as TryFrom<_>>::try_from(event)?;
+            items.push(ir::Item::Ink(ir::InkItem::Event(event)));
+        }
+        let item_impl: syn::ItemImpl = syn::parse_quote! {
+            impl #storage_ident {
+                /// Returns the owner of the contract, if any.
+                #[ink(message)]
+                pub fn owner(&self) -> ::core::option::Option<#account_id> {
+                    <Self as #owned>::ownable(self).owner()
+                }
+
+                /// Returns the account the ownership is being transferred to, if any.
+                #[ink(message)]
+                pub fn pending_owner(
+                    &self,
+                ) -> ::core::option::Option<#account_id> {
+                    <Self as #owned>::ownable(self).pending_owner()
+                }
+
+                /// Starts transferring the ownership to `new_owner`, who then has to
+                /// accept it.
+                ///
+                /// Can only be called by the owner.
+                #[ink(message)]
+                pub fn transfer_ownership(
+                    &mut self,
+                    new_owner: #account_id,
+                ) -> ::core::result::Result<(), ::ink::contrib::OwnableError> {
+                    let ownable = <Self as #owned>::ownable_mut(self);
+                    ownable.transfer_ownership(::core::clone::Clone::clone(&new_owner))?;
+                    let previous_owner = ownable.owner();
+                    self.env().emit_event(OwnershipTransferStarted {
+                        previous_owner,
+                        new_owner: ::core::option::Option::Some(new_owner),
+                    });
+                    ::core::result::Result::Ok(())
+                }
+
+                /// Accepts the ownership proposed to the caller.
+                ///
+                /// Can only be called by the pending owner.
+                #[ink(message)]
+                pub fn accept_ownership(
+                    &mut self,
+                ) -> ::core::result::Result<(), ::ink::contrib::OwnableError> {
+                    let ownable = <Self as #owned>::ownable_mut(self);
+                    let previous_owner = ownable.accept_ownership()?;
+                    let new_owner = ownable.owner();
+                    self.env().emit_event(OwnershipTransferred {
+                        previous_owner,
+                        new_owner,
+                    });
+                    ::core::result::Result::Ok(())
+                }
+
+                /// Gives up the ownership, leaving the contract without an owner.
+                ///
+                /// Can only be called by the owner.
+                #[ink(message)]
+                pub fn renounce_ownership(
+                    &mut self,
+                ) -> ::core::result::Result<(), ::ink::contrib::OwnableError> {
+                    let ownable = <Self as #owned>::ownable_mut(self);
+                    let previous_owner = ownable.renounce_ownership()?;
+                    self.env().emit_event(OwnershipTransferred {
+                        previous_owner,
+                        new_owner: ::core::option::Option::None,
+                    });
+                    ::core::result::Result::Ok(())
+                }
+            }
+        };
+        let item_impl = <ir::ItemImpl as TryFrom<_>>::try_from(item_impl)?;
+        items.push(ir::Item::Ink(ir::InkItem::ImplBlock(item_impl)));
+        Ok(())
+    }
 }
 
 impl TryFrom<syn::ItemMod> for ItemMod {
@@ -321,15 +583,18 @@ impl TryFrom<syn::ItemMod> for ItemMod {
             }
             return Err(error)
         }
-        let items = items
+        let mut items = items
             .into_iter()
             .map(<ir::Item as TryFrom<syn::Item>>::try_from)
             .collect::<Result<Vec<_>, syn::Error>>()?;
         Self::ensure_storage_struct_quantity(module_span, &items)?;
+        Self::add_access_control_messages(&mut items)?;
+        Self::add_ownable_items(&mut items)?;
         Self::ensure_contains_message(module_span, &items)?;
         Self::ensure_contains_constructor(module_span, &items)?;
         Self::ensure_no_overlapping_selectors(&items)?;
         Self::ensure_only_one_wildcard_selector(&items)?;
+        Self::ensure_only_one_receive(&items)?;
         Ok(Self {
             attrs: other_attrs,
             vis: module.vis,
@@ -397,6 +662,51 @@ impl ItemMod {
         storage
     }
 
+    /// Returns a mutable reference to the storage struct definition.
+    ///
+    /// # Note
+    ///
+    /// The existence of exactly one storage struct is ensured when parsing the
+    /// ink! module.
+    pub(crate) fn storage_mut(&mut self) -> &mut ir::Storage {
+        self.items
+            .iter_mut()
+            .find_map(|item| {
+                match item {
+                    ir::Item::Ink(ir::InkItem::Storage(storage)) => Some(storage),
+                    _ => None,
+                }
+            })
+            .expect("encountered ink! module without a storage struct")
+    }
+
+    /// Prepares the ink! messages for the given `abi`.
+    ///
+    /// With [`ir::Abi::Solidity`] the messages must not be part of ink! trait
+    /// implementations and get selectors derived from their Solidity style signatures.
+    pub(crate) fn set_abi(&mut self, abi: ir::Abi) -> Result<(), syn::Error> {
+        if abi == ir::Abi::Scale {
+            return Ok(())
+        }
+        for item_impl in self.items.iter_mut().filter_map(|item| {
+            match item {
+                ir::Item::Ink(ir::InkItem::ImplBlock(item_impl)) => Some(item_impl),
+                _ => None,
+            }
+        }) {
+            if let Some(trait_path) = item_impl.trait_path() {
+                return Err(format_err_spanned!(
+                    trait_path,
+                    "ink! trait implementations are not supported by contracts with `abi = \"solidity\"`",
+                ))
+            }
+            for message in item_impl.messages_mut() {
+                message.set_sol_selector()?;
+            }
+        }
+        Self::ensure_no_overlapping_selectors(&self.items)
+    }
+
     /// Returns all (ink! and non-ink! specific) item definitions of the ink! inline module.
     pub fn items(&self) -> &[ir::Item] {
         self.items.as_slice()
@@ -476,6 +786,11 @@ impl ItemMod {
         IterEvents::new(self)
     }
 
+    /// Returns all ink! error enum definitions of the ink! module.
+    pub fn errors(&self) -> IterErrors {
+        IterErrors::new(self)
+    }
+
     /// Returns all non-ink! attributes of the ink! module.
     pub fn attrs(&self) -> &[syn::Attribute] {
         &self.attrs
@@ -552,6 +867,39 @@ impl<'a> Iterator for IterEvents<'a> {
     }
 }
 
+/// Iterator yielding all ink! error enum definitions within the ink!
+/// [`ItemMod`](`crate::ir::ItemMod`).
+pub struct IterErrors<'a> {
+    items_iter: IterInkItems<'a>,
+}
+
+impl<'a> IterErrors<'a> {
+    /// Creates a new ink! errors iterator.
+    fn new(ink_module: &'a ItemMod) -> Self {
+        Self {
+            items_iter: IterInkItems::new(ink_module),
+        }
+    }
+}
+
+impl<'a> Iterator for IterErrors<'a> {
+    type Item = &'a ir::Error;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        'repeat: loop {
+            match self.items_iter.next() {
+                None => return None,
+                Some(ink_item) => {
+                    if let Some(error) = ink_item.filter_map_error_item() {
+                        return Some(error)
+                    }
+                    continue 'repeat
+                }
+            }
+        }
+    }
+}
+
 /// Iterator yielding all ink! implementation block definitions within the ink!
 /// [`ItemMod`](`crate::ir::ItemMod`).
 pub struct IterItemImpls<'a> {
@@ -879,6 +1227,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn multiple_receive_messages_fails() {
+        assert_fail(
+            syn::parse_quote! {
+                mod my_module {
+                    #[ink(storage)]
+                    pub struct MyStorage {}
+
+                    impl MyStorage {
+                        #[ink(constructor)]
+                        pub fn my_constructor() -> Self {}
+
+                        #[ink(message, receive)]
+                        pub fn my_message1(&mut self) {}
+
+                        #[ink(message, receive)]
+                        pub fn my_message2(&mut self) {}
+                    }
+                }
+            },
+            "encountered multiple `receive` ink! messages",
+        );
+    }
+
     #[test]
     fn wildcard_selector_on_constructor_works() {
         assert!(
@@ -920,4 +1292,144 @@ mod tests {
             "encountered ink! attribute arguments with equal kinds",
         );
     }
+
+    #[test]
+    fn only_adds_access_control_messages() {
+        let messages = |item_mod: syn::ItemMod| {
+            <ir::ItemMod as TryFrom<syn::ItemMod>>::try_from(item_mod)
+                .unwrap()
+                .impls()
+                .flat_map(ir::ItemImpl::iter_messages)
+                .map(|message| ir::Callable::ident(message.callable()).to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            messages(syn::parse_quote! {
+                mod my_module {
+                    #[ink(storage)]
+                    pub struct MyStorage {}
+
+                    impl MyStorage {
+                        #[ink(constructor)]
+                        pub fn my_constructor() -> Self {}
+
+                        #[ink(message, only(roles = ["operator"]))]
+                        pub fn my_message(&mut self) {}
+                    }
+                }
+            }),
+            vec!["my_message", "grant_role", "revoke_role"],
+        );
+        assert_eq!(
+            messages(syn::parse_quote! {
+                mod my_module {
+                    #[ink(storage)]
+                    pub struct MyStorage {}
+
+                    impl MyStorage {
+                        #[ink(constructor)]
+                        pub fn my_constructor() -> Self {}
+
+                        #[ink(message)]
+                        pub fn my_message(&mut self) {}
+                    }
+                }
+            }),
+            vec!["my_message"],
+        );
+    }
+
+    #[test]
+    fn only_adds_ownable_items() {
+        let item_mod = |item_mod: syn::ItemMod| {
+            <ir::ItemMod as TryFrom<syn::ItemMod>>::try_from(item_mod).unwrap()
+        };
+        let messages = |item_mod: &ir::ItemMod| {
+            item_mod
+                .impls()
+                .flat_map(ir::ItemImpl::iter_messages)
+                .map(|message| ir::Callable::ident(message.callable()).to_string())
+                .collect::<Vec<_>>()
+        };
+        let events = |item_mod: &ir::ItemMod| {
+            item_mod
+                .events()
+                .map(|event| event.ident().to_string())
+                .collect::<Vec<_>>()
+        };
+        let owned = item_mod(syn::parse_quote! {
+            mod my_module {
+                #[ink(storage)]
+                #[derive(::ink::contrib::Ownable2Step)]
+                pub struct MyStorage {
+                    ownable: Ownable2Step,
+                }
+
+                impl MyStorage {
+                    #[ink(constructor)]
+                    pub fn my_constructor() -> Self {}
+
+                    #[ink(message)]
+                    pub fn my_message(&mut self) {}
+                }
+            }
+        });
+        assert_eq!(
+            messages(&owned),
+            vec![
+                "my_message",
+                "owner",
+                "pending_owner",
+                "transfer_ownership",
+                "accept_ownership",
+                "renounce_ownership",
+            ],
+        );
+        assert_eq!(
+            events(&owned),
+            vec!["OwnershipTransferStarted", "OwnershipTransferred"],
+        );
+        let not_owned = item_mod(syn::parse_quote! {
+            mod my_module {
+                #[ink(storage)]
+                #[derive(Debug)]
+                pub struct MyStorage {}
+
+                impl MyStorage {
+                    #[ink(constructor)]
+                    pub fn my_constructor() -> Self {}
+
+                    #[ink(message)]
+                    pub fn my_message(&mut self) {}
+                }
+            }
+        });
+        assert_eq!(messages(&not_owned), vec!["my_message"]);
+        assert!(events(&not_owned).is_empty());
+    }
+
+    #[test]
+    fn foreign_ownable_derive_fails() {
+        assert_fail(
+            syn::parse_quote! {
+                mod my_module {
+                    #[ink(storage)]
+                    #[derive(my_crate::Ownable2Step)]
+                    pub struct MyStorage {
+                        ownable: my_crate::Ownable2Step,
+                    }
+
+                    impl MyStorage {
+                        #[ink(constructor)]
+                        pub fn my_constructor() -> Self {}
+
+                        #[ink(message)]
+                        pub fn my_message(&mut self) {}
+                    }
+                }
+            },
+            "ink! only recognizes the `Ownable2Step` derive as `Ownable2Step` \
+            or `ink::contrib::Ownable2Step`",
+        )
+    }
 }
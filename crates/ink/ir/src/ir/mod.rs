@@ -65,10 +65,17 @@ pub use self::{
         ChainExtensionMethod,
         ExtensionId,
     },
-    config::Config,
+    config::{
+        Abi,
+        Config,
+    },
     contract::Contract,
-    ink_test::InkTest,
+    ink_test::{
+        InkTest,
+        InkTestConfig,
+    },
     item::{
+        Error,
         Event,
         InkItem,
         Item,
@@ -90,6 +97,7 @@ pub use self::{
     },
     item_mod::{
         ItemMod,
+        IterErrors,
         IterEvents,
         IterItemImpls,
     },
@@ -98,7 +106,10 @@ pub use self::{
         SelectorMacro,
         TraitPrefix,
     },
-    storage_item::StorageItem,
+    storage_item::{
+        PackedCollection,
+        StorageItem,
+    },
     trait_def::{
         InkItemTrait,
         InkTraitDefinition,
@@ -25,6 +25,15 @@ pub struct StorageItemConfig {
     /// If set to `false`, implementing all storage traits is disabled. In some cases
     /// this can be helpful to override the default implementation of the trait.
     derive: bool,
+    /// The namespace under which the storage keys of the fields are computed.
+    ///
+    /// If set, the keys are computed from the namespace instead of the name of the
+    /// type, so that reusable components from different crates don't collide.
+    namespace: Option<String>,
+    /// If set to `true`, the integer fields are stored with the compact encoding.
+    ///
+    /// This is set via `encoding = compact`, the default is `encoding = scale`.
+    compact_encoding: bool,
 }
 
 impl TryFrom<ast::AttributeArgs> for StorageItemConfig {
@@ -32,6 +41,8 @@ impl TryFrom<ast::AttributeArgs> for StorageItemConfig {
 
     fn try_from(args: ast::AttributeArgs) -> Result<Self, Self::Error> {
         let mut derive: Option<syn::LitBool> = None;
+        let mut namespace: Option<syn::LitStr> = None;
+        let mut encoding: Option<syn::Ident> = None;
         for arg in args.into_iter() {
             if arg.name.is_ident("derive") {
                 if let Some(lit_bool) = derive {
@@ -50,6 +61,50 @@ impl TryFrom<ast::AttributeArgs> for StorageItemConfig {
                         "expected a bool literal for `derive` ink! storage item configuration argument",
                     ));
                 }
+            } else if arg.name.is_ident("namespace") {
+                if let Some(lit_str) = namespace {
+                    return Err(duplicate_config_err(
+                        lit_str,
+                        arg,
+                        "namespace",
+                        "storage item",
+                    ))
+                }
+                match &arg.value {
+                    ast::PathOrLit::Lit(syn::Lit::Str(lit_str))
+                        if !lit_str.value().is_empty() =>
+                    {
+                        namespace = Some(lit_str.clone())
+                    }
+                    _ => {
+                        return Err(format_err_spanned!(
+                            arg,
+                            "expected a non-empty string literal for `namespace` ink! storage item configuration argument",
+                        ))
+                    }
+                }
+            } else if arg.name.is_ident("encoding") {
+                if let Some(ident) = encoding {
+                    return Err(duplicate_config_err(
+                        ident,
+                        arg,
+                        "encoding",
+                        "storage item",
+                    ))
+                }
+                match &arg.value {
+                    ast::PathOrLit::Path(path)
+                        if path.is_ident("scale") || path.is_ident("compact") =>
+                    {
+                        encoding = path.get_ident().cloned()
+                    }
+                    _ => {
+                        return Err(format_err_spanned!(
+                            arg,
+                            "expected `scale` or `compact` for `encoding` ink! storage item configuration argument",
+                        ))
+                    }
+                }
             } else {
                 return Err(format_err_spanned!(
                     arg,
@@ -57,8 +112,19 @@ impl TryFrom<ast::AttributeArgs> for StorageItemConfig {
                 ));
             }
         }
+        let compact_encoding = matches!(&encoding, Some(ident) if ident == "compact");
+        if let (Some(lit_bool), true) = (&derive, compact_encoding) {
+            if !lit_bool.value {
+                return Err(format_err_spanned!(
+                    lit_bool,
+                    "`encoding = compact` requires the storage traits to be derived",
+                ))
+            }
+        }
         Ok(StorageItemConfig {
             derive: derive.map(|lit_bool| lit_bool.value).unwrap_or(true),
+            namespace: namespace.map(|lit_str| lit_str.value()),
+            compact_encoding,
         })
     }
 }
@@ -68,4 +134,14 @@ impl StorageItemConfig {
     pub fn derive(&self) -> bool {
         self.derive
     }
+
+    /// Returns the namespace of the storage keys if specified.
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
+    /// Returns `true` if the integer fields are stored with the compact encoding.
+    pub fn compact_encoding(&self) -> bool {
+        self.compact_encoding
+    }
 }
@@ -14,25 +14,67 @@
 
 mod config;
 
-use crate::utils::find_storage_key_salt;
+use crate::{
+    error::ExtError as _,
+    ir,
+    utils::find_storage_key_salt,
+};
 use config::StorageItemConfig;
-use proc_macro2::TokenStream as TokenStream2;
+use proc_macro2::{
+    Span,
+    TokenStream as TokenStream2,
+};
 use quote::{
     quote,
     ToTokens,
 };
-use std::collections::HashSet;
+use std::collections::{
+    HashMap,
+    HashSet,
+};
+use syn::spanned::Spanned as _;
+
+/// The collections whose encoded size is not bounded by their type.
+const UNBOUNDED_COLLECTIONS: &[&str] = &[
+    "Vec",
+    "VecDeque",
+    "LinkedList",
+    "BinaryHeap",
+    "BTreeMap",
+    "BTreeSet",
+    "HashMap",
+    "HashSet",
+];
+
+/// The storage types which store their contents in storage cells of their own.
+const NON_PACKED_TYPES: &[&str] = &["Mapping", "Lazy"];
+
+/// A field of a storage item which packs an unbounded collection into the storage
+/// cell of the storage item.
+pub struct PackedCollection<'a> {
+    /// The field packing the collection.
+    pub field: &'a syn::Field,
+    /// The name of the field, or its index if the field is unnamed.
+    pub field_name: String,
+    /// The type identifier of the packed collection, e.g. `Vec`.
+    pub collection: &'a syn::Ident,
+}
 
 /// A checked ink! storage item with its configuration.
 pub struct StorageItem {
     ast: syn::DeriveInput,
     config: StorageItemConfig,
+    /// The storage keys set via `#[ink(storage_key = N)]` by the variant and
+    /// index of their field.
+    storage_keys: HashMap<(Option<syn::Ident>, usize), u32>,
+    /// The variant and index of the fields flagged via `#[ink(bounded)]`.
+    bounded_fields: HashSet<(Option<syn::Ident>, usize)>,
 }
 
 impl StorageItem {
     /// Returns `Ok` if the input matches all requirements for an ink! storage item.
     pub fn new(config: TokenStream2, item: TokenStream2) -> Result<Self, syn::Error> {
-        let ast = syn::parse2::<syn::DeriveInput>(item)?;
+        let mut ast = syn::parse2::<syn::DeriveInput>(item)?;
         let parsed_config = syn::parse2::<crate::ast::AttributeArgs>(config)?;
         let config = StorageItemConfig::try_from(parsed_config)?;
 
@@ -50,7 +92,14 @@ impl StorageItem {
             }
         }
 
-        Ok(Self { ast, config })
+        let (storage_keys, bounded_fields) = extract_field_attributes(&mut ast.data)?;
+
+        Ok(Self {
+            ast,
+            config,
+            storage_keys,
+            bounded_fields,
+        })
     }
 
     /// Returns AST.
@@ -92,6 +141,53 @@ impl StorageItem {
             .collect()
     }
 
+    /// Returns the fields which pack an unbounded collection, such as a `Vec` or a
+    /// `BTreeMap`, into the storage cell of the storage item.
+    ///
+    /// # Note
+    ///
+    /// Collections stored in a `Mapping` or `Lazy` are not reported since they are
+    /// stored in storage cells of their own, neither are the fields flagged via
+    /// `#[ink(bounded)]`.
+    pub fn packed_collections(&self) -> Vec<PackedCollection<'_>> {
+        let fields: Vec<(Option<&syn::Ident>, Vec<&syn::Field>)> = match self.data() {
+            syn::Data::Struct(st) => vec![(None, st.fields.iter().collect())],
+            syn::Data::Enum(en) => {
+                en.variants
+                    .iter()
+                    .map(|variant| {
+                        (Some(&variant.ident), variant.fields.iter().collect())
+                    })
+                    .collect()
+            }
+            syn::Data::Union(un) => vec![(None, un.fields.named.iter().collect())],
+        };
+        fields
+            .into_iter()
+            .flat_map(|(variant, fields)| {
+                fields
+                    .into_iter()
+                    .enumerate()
+                    .filter_map(move |(index, field)| {
+                        if self.bounded_fields.contains(&(variant.cloned(), index)) {
+                            return None
+                        }
+                        let collection = find_unbounded_collection(&field.ty)?;
+                        let field_name = field
+                            .ident
+                            .as_ref()
+                            .map(ToString::to_string)
+                            .unwrap_or_else(|| index.to_string());
+                        Some(PackedCollection {
+                            field,
+                            field_name,
+                            collection,
+                        })
+                    })
+            })
+            .collect()
+    }
+
     /// Returns the config of the storage.
     pub fn config(&self) -> &StorageItemConfig {
         &self.config
@@ -122,6 +218,12 @@ impl StorageItem {
         &self.ast.data
     }
 
+    /// Returns the storage key set via `#[ink(storage_key = N)]` for the field at
+    /// `index` of the given enum `variant`, or of the struct or union if `None`.
+    pub fn storage_key(&self, variant: Option<&syn::Ident>, index: usize) -> Option<u32> {
+        self.storage_keys.get(&(variant.cloned(), index)).copied()
+    }
+
     /// Returns salt for storage key.
     pub fn salt(&self) -> TokenStream2 {
         if let Some(param) = find_storage_key_salt(&self.ast) {
@@ -131,3 +233,116 @@ impl StorageItem {
         }
     }
 }
+
+/// Returns the identifier of the first unbounded collection packed by `ty`.
+fn find_unbounded_collection(ty: &syn::Type) -> Option<&syn::Ident> {
+    match ty {
+        syn::Type::Path(path) => {
+            let segment = path.path.segments.last()?;
+            let ident = segment.ident.to_string();
+            if NON_PACKED_TYPES.contains(&ident.as_str()) {
+                return None
+            }
+            if UNBOUNDED_COLLECTIONS.contains(&ident.as_str()) {
+                return Some(&segment.ident)
+            }
+            match &segment.arguments {
+                syn::PathArguments::AngleBracketed(args) => {
+                    args.args.iter().find_map(|arg| {
+                        match arg {
+                            syn::GenericArgument::Type(ty) => {
+                                find_unbounded_collection(ty)
+                            }
+                            _ => None,
+                        }
+                    })
+                }
+                _ => None,
+            }
+        }
+        syn::Type::Array(array) => find_unbounded_collection(&array.elem),
+        syn::Type::Tuple(tuple) => tuple.elems.iter().find_map(find_unbounded_collection),
+        syn::Type::Paren(paren) => find_unbounded_collection(&paren.elem),
+        syn::Type::Group(group) => find_unbounded_collection(&group.elem),
+        _ => None,
+    }
+}
+
+/// Removes the `#[ink(storage_key = N)]` and `#[ink(bounded)]` attributes from the
+/// fields of `data` and returns the storage keys and the bounded fields by the
+/// variant and index of their field.
+///
+/// # Errors
+///
+/// - If a field has ink! attributes other than `#[ink(storage_key = N)]` and
+///   `#[ink(bounded)]`.
+/// - If several fields use the same storage key.
+#[allow(clippy::type_complexity)]
+fn extract_field_attributes(
+    data: &mut syn::Data,
+) -> Result<
+    (
+        HashMap<(Option<syn::Ident>, usize), u32>,
+        HashSet<(Option<syn::Ident>, usize)>,
+    ),
+    syn::Error,
+> {
+    let fields: Vec<(Option<syn::Ident>, Vec<&mut syn::Field>)> = match data {
+        syn::Data::Struct(st) => vec![(None, st.fields.iter_mut().collect())],
+        syn::Data::Enum(en) => {
+            en.variants
+                .iter_mut()
+                .map(|variant| {
+                    (
+                        Some(variant.ident.clone()),
+                        variant.fields.iter_mut().collect(),
+                    )
+                })
+                .collect()
+        }
+        syn::Data::Union(un) => vec![(None, un.fields.named.iter_mut().collect())],
+    };
+    let mut storage_keys = HashMap::new();
+    let mut bounded_fields = HashSet::new();
+    let mut seen_keys = HashMap::<u32, Span>::new();
+    for (variant, fields) in fields {
+        for (index, field) in fields.into_iter().enumerate() {
+            let (ink_attrs, other_attrs) = ir::sanitize_optional_attributes(
+                field.span(),
+                field.attrs.drain(..),
+                |arg| {
+                    match arg.kind() {
+                        ir::AttributeArg::StorageKey(_) | ir::AttributeArg::Bounded => {
+                            Ok(())
+                        }
+                        _ => Err(None),
+                    }
+                },
+            )?;
+            field.attrs = other_attrs;
+            if matches!(&ink_attrs, Some(attr) if attr.is_bounded()) {
+                bounded_fields.insert((variant.clone(), index));
+            }
+            let (key, span) = match ink_attrs
+                .and_then(|attr| Some((attr.storage_key()?, attr.span())))
+            {
+                Some(key_and_span) => key_and_span,
+                None => continue,
+            };
+            if let Some(previous) = seen_keys.get(&key) {
+                return Err(format_err!(
+                    span,
+                    "encountered duplicate storage key 0x{:08X}",
+                    key,
+                )
+                .into_combine(format_err!(
+                    *previous,
+                    "previous duplicate storage key here"
+                )))
+            }
+            seen_keys.insert(key, span);
+            storage_keys.insert((variant.clone(), index), key);
+        }
+    }
+    Ok((storage_keys, bounded_fields))
+}
@@ -156,7 +156,6 @@ impl InkItemTrait {
     ///     - associated types (`type`)
     ///     - macros definitions or usages
     ///     - unknown token sequences (verbatim)
-    ///     - methods with default implementations
     /// - If the trait contains methods which do not respect the ink! trait definition requirements:
     ///     - All trait methods need to be declared as either `#[ink(message)]` or `#[ink(constructor)]`
     ///       and need to respect their respective rules.
@@ -215,12 +214,6 @@ impl InkItemTrait {
     /// - If the method does not respect the properties of either an
     ///   ink! message or ink! constructor.
     fn analyse_trait_method(method: &syn::TraitItemMethod) -> Result<()> {
-        if let Some(default_impl) = &method.default {
-            return Err(format_err_spanned!(
-                default_impl,
-                "ink! trait methods with default implementations are not supported"
-            ))
-        }
         if let Some(constness) = &method.sig.constness {
             return Err(format_err_spanned!(
                 constness,
@@ -126,21 +126,14 @@ fn trait_def_containing_non_flagged_method_is_denied() {
 }
 
 #[test]
-fn trait_def_containing_default_implemented_methods_is_denied() {
+fn trait_def_containing_default_implemented_constructor_is_denied() {
     assert_ink_trait_eq_err!(
-        error: "ink! trait methods with default implementations are not supported",
+        error: "ink! trait definitions must not have constructors",
         pub trait MyTrait {
             #[ink(constructor)]
             fn default_implemented() -> Self {}
         }
     );
-    assert_ink_trait_eq_err!(
-        error: "ink! trait methods with default implementations are not supported",
-        pub trait MyTrait {
-            #[ink(message)]
-            fn default_implemented(&self) {}
-        }
-    );
 }
 
 #[test]
@@ -337,6 +330,28 @@ fn trait_def_is_ok() {
     )
 }
 
+#[test]
+fn trait_def_with_default_implemented_messages_is_ok() {
+    let item_trait =
+        <InkItemTrait as TryFrom<syn::ItemTrait>>::try_from(syn::parse_quote! {
+            pub trait MyTrait {
+                #[ink(message)]
+                fn my_message(&self) -> bool;
+                #[ink(message)]
+                fn default_implemented(&self) -> bool {
+                    !self.my_message()
+                }
+            }
+        })
+        .unwrap();
+    let has_default_impl = item_trait
+        .iter_items()
+        .flat_map(|(item, _)| item.filter_map_message())
+        .map(|message| message.default_impl().is_some())
+        .collect::<Vec<_>>();
+    assert_eq!(has_default_impl, vec![false, true]);
+}
+
 #[test]
 fn trait_def_with_namespace_is_ok() {
     assert!(
@@ -44,6 +44,7 @@ pub use self::{
         blake2b_256,
         marker,
         utils,
+        Abi,
         Blake2x256Macro,
         Callable,
         CallableKind,
@@ -53,12 +54,14 @@ pub use self::{
         Config,
         Constructor,
         Contract,
+        Error,
         Event,
         ExtensionId,
         ImplItem,
         InkItem,
         InkItemTrait,
         InkTest,
+        InkTestConfig,
         InkTraitDefinition,
         InkTraitItem,
         InkTraitMessage,
@@ -68,12 +71,14 @@ pub use self::{
         ItemImpl,
         ItemMod,
         IterConstructors,
+        IterErrors,
         IterEvents,
         IterInkTraitItems,
         IterItemImpls,
         IterMessages,
         Message,
         Namespace,
+        PackedCollection,
         Receiver,
         Selector,
         SelectorMacro,
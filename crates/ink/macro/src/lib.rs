@@ -23,6 +23,8 @@ mod blake2b;
 mod chain_extension;
 mod contract;
 mod ink_test;
+mod ownable;
+mod runtime_call;
 mod selector;
 mod storage;
 mod storage_item;
@@ -206,6 +208,179 @@ pub fn selector_bytes(input: TokenStream) -> TokenStream {
 ///
 ///     **Default value:** `DefaultEnvironment` defined in `ink_env` crate.
 ///
+/// - `entry: String`
+///
+///     Tells the ink! code generator to only export the contract's entry points and
+///     metadata if the crate feature with the given name is enabled. This allows to
+///     define multiple ink! smart contracts within a single crate and to select the one
+///     to compile, e.g. via `cargo contract build --features adder`.
+///
+///     **Usage Example:**
+///     ```
+///     #[ink::contract(entry = "adder")]
+///     mod adder {
+///         # #[ink(storage)]
+///         # pub struct Adder;
+///         # impl Adder {
+///         #     #[ink(constructor)]
+///         #     pub fn construct() -> Self { Adder {} }
+///         #     #[ink(message)]
+///         #     pub fn message(&self) {}
+///         # }
+///         // ...
+///     }
+///
+///     #[ink::contract(entry = "subber")]
+///     mod subber {
+///         # #[ink(storage)]
+///         # pub struct Subber;
+///         # impl Subber {
+///         #     #[ink(constructor)]
+///         #     pub fn construct() -> Self { Subber {} }
+///         #     #[ink(message)]
+///         #     pub fn message(&self) {}
+///         # }
+///         // ...
+///     }
+///     ```
+///
+///     **Default value:** The entry points are always exported.
+///
+/// - `config: Path`
+///
+///     Tells the ink! code generator which type to use for the single type parameter of
+///     a generic `#[ink(storage)]` struct. This allows to share the logic of a contract
+///     between multiple instantiations that only differ in some associated types or
+///     constants, e.g. the number of decimals of a token.
+///
+///     The ink! implementation blocks may be generic over the type parameter as well.
+///     The deployed contract uses the chosen type, while off-chain tests may
+///     instantiate the storage struct with any type. Since the dispatch of the
+///     contract refers to the signatures of its messages and constructors, they must
+///     not use the type parameter.
+///
+///     **Usage Example:**
+///     ```
+///     #[ink::contract(config = SixDecimals)]
+///     mod token {
+///         pub trait Config {
+///             const DECIMALS: u8;
+///         }
+///
+///         pub struct SixDecimals;
+///
+///         impl Config for SixDecimals {
+///             const DECIMALS: u8 = 6;
+///         }
+///
+///         #[ink(storage)]
+///         pub struct Token<T: Config> {
+///             marker: core::marker::PhantomData<T>,
+///         }
+///
+///         impl<T: Config> Token<T> {
+///             #[ink(constructor)]
+///             pub fn new() -> Self {
+///                 Self { marker: Default::default() }
+///             }
+///
+///             #[ink(message)]
+///             pub fn decimals(&self) -> u8 {
+///                 T::DECIMALS
+///             }
+///         }
+///     }
+///     ```
+///
+///     **Default value:** The `#[ink(storage)]` struct must not be generic.
+///
+/// - `abi: String`
+///
+///     Tells the ink! code generator how the messages of the contract encode their
+///     inputs and outputs. With `"solidity"` they are Solidity ABI encoded and their
+///     selectors are derived from their Solidity style signatures, e.g.
+///     `balanceOf(address)`, so that EVM tooling is able to call them. Constructors
+///     stay SCALE encoded and ink! trait implementations are not supported.
+///
+///     **Usage Example:**
+///     ```
+///     #[ink::contract(abi = "solidity")]
+///     mod token {
+///         #[ink(storage)]
+///         pub struct Token {}
+///
+///         impl Token {
+///             #[ink(constructor)]
+///             pub fn new() -> Self {
+///                 Self {}
+///             }
+///
+///             #[ink(message)]
+///             pub fn balance_of(&self, owner: ink::sol::Address) -> Balance {
+///                 0
+///             }
+///         }
+///     }
+///     ```
+///
+///     **Default value:** `"scale"`, i.e. all inputs and outputs are SCALE encoded.
+///
+/// - `max_message_size: u32`
+///
+///     Tells the ink! code generator to reject calls and instantiations whose input
+///     exceeds the given number of bytes. The entry points check the size of the
+///     input before decoding it and revert with `LangError::InputTooLarge`, which
+///     protects the contract from paying for decoding huge inputs.
+///
+///     The input is read into the 16 kB static buffer of the environment, from which
+///     it is then decoded, and inputs exceeding the buffer trap before the check.
+///     Therefore the value must be smaller than 16384 bytes.
+///
+///     **Usage Example:**
+///     ```
+///     #[ink::contract(max_message_size = 8_192)]
+///     mod contract {
+///         # #[ink(storage)]
+///         # pub struct Contract;
+///         # impl Contract {
+///         #     #[ink(constructor)]
+///         #     pub fn construct() -> Self { Contract {} }
+///         #     #[ink(message)]
+///         #     pub fn message(&self) {}
+///         # }
+///         // ...
+///     }
+///     ```
+///
+///     **Default value:** The size of the input is only limited by the environment.
+///
+/// - `embed_metadata_hash: bool`
+///
+///     Tells the ink! code generator to embed the hash of the contract metadata into
+///     the `ink_metadata_hash` custom section of the Wasm blob. The hash is read from
+///     the `INK_METADATA_HASH` environment variable at build time, see
+///     `ink::metadata_hash!` for how to build a contract with it. Clients then verify
+///     that metadata belongs to deployed code with
+///     `ink::metadata::verify::verify_metadata_hash`, without trusting a registry.
+///
+///     **Usage Example:**
+///     ```
+///     #[ink::contract(embed_metadata_hash = true)]
+///     mod contract {
+///         # #[ink(storage)]
+///         # pub struct Contract;
+///         # impl Contract {
+///         #     #[ink(constructor)]
+///         #     pub fn construct() -> Self { Contract {} }
+///         #     #[ink(message)]
+///         #     pub fn message(&self) {}
+///         # }
+///         // ...
+///     }
+///     ```
+///
+///     **Default value:** `false`.
+///
 /// ## Analysis
 ///
 /// The `#[ink::contract]` macro fully analyses its input smart contract
@@ -239,6 +414,14 @@ pub fn selector_bytes(input: TokenStream) -> TokenStream {
 ///     }
 ///     ```
 ///
+///     The layout of the storage struct can be versioned via
+///     `#[ink(storage, storage_version = N)]`. Upgraded contracts then migrate their
+///     storage on the first message call after `set_code_hash`.
+///     For more information visit the `ink::storage::migrate` module documentation.
+///
+///     The storage key of a non-packed field can be pinned via
+///     `#[ink(storage_key = N)]`, see [`macro@storage_item`].
+///
 /// - There must be at least one `#[ink(constructor)]` defined method.
 ///
 ///     Methods flagged with `#[ink(constructor)]` are special in that they are dispatchable
@@ -350,6 +533,366 @@ pub fn selector_bytes(input: TokenStream) -> TokenStream {
 ///     # }
 ///     ```
 ///
+///     **Refunding Payable Constructors:**
+///
+///     A payable ink! constructor can declare a minimum endowment using
+///     `payable(refund_excess = N)`. Any value transferred in excess of `N` is refunded
+///     to the caller before the constructor executes, while transferring less than `N`
+///     rejects the instantiation. The contract always keeps at least the existential
+///     deposit, even if `N` is below it.
+///
+///     ```
+///     # #[ink::contract]
+///     # mod flipper {
+///         # #[ink(storage)]
+///         # pub struct Flipper {
+///         #     value: bool,
+///         # }
+///     impl Flipper {
+///         #[ink(constructor, payable(refund_excess = 1_000))]
+///         pub fn new(initial_value: bool) -> Self {
+///             Flipper { value: initial_value }
+///         }
+///         # #[ink(message)]
+///         # pub fn get(&self) -> bool {
+///         #     self.value
+///         # }
+///     }
+///     # }
+///     ```
+///
+///     **Payable Messages with a Minimum Value:**
+///
+///     A payable ink! message can require a minimum transferred value using
+///     `payable(min = N)`. Calls transferring less than `N` are reverted with
+///     `LangError::InsufficientTransferredValue` before the message executes.
+///
+///     ```
+///     # #[ink::contract]
+///     # mod flipper {
+///         # #[ink(storage)]
+///         # pub struct Flipper {
+///         #     value: bool,
+///         # }
+///     impl Flipper {
+///         # #[ink(constructor)]
+///         # pub fn new(initial_value: bool) -> Self {
+///         #     Flipper { value: initial_value }
+///         # }
+///         #[ink(message, payable(min = 1_000))]
+///         pub fn deposit(&mut self) {
+///             self.value = true;
+///         }
+///     }
+///     # }
+///     ```
+///
+///     **Paginated Messages:**
+///
+///     An ink! message returning a large collection can be flagged as `paginated`.
+///     It must return a page of `(Vec<T>, Option<Cursor>)`, see the
+///     `ink::pagination` module for the helpers to build such pages. The message is
+///     marked as `paginated` in the contract metadata, so that tools like explorers
+///     know to read the whole collection page by page.
+///
+///     **Messages with Deferred Inputs:**
+///
+///     The inputs of an ink! message flagged as `deferred_inputs` are only decoded once
+///     the checks of the message, e.g. denying payment, `payable(min = N)` or
+///     `internal`, have passed. Calls failing them then do not pay for decoding
+///     large inputs. Inputs failing to decode revert the call with
+///     `LangError::CouldNotReadInput`.
+///
+///     Note that this only postpones decoding, it does not stream the inputs. The
+///     whole input of the call is still read up front and all inputs are decoded at
+///     once before executing the message.
+///
+///     ```
+///     # #[ink::contract]
+///     # mod flipper {
+///         # #[ink(storage)]
+///         # pub struct Flipper {
+///         #     value: bool,
+///         # }
+///     impl Flipper {
+///         # #[ink(constructor)]
+///         # pub fn new(initial_value: bool) -> Self {
+///         #     Flipper { value: initial_value }
+///         # }
+///         #[ink(message, payable(min = 1_000), deferred_inputs)]
+///         pub fn upload(&mut self, blob: ink::prelude::vec::Vec<u8>) {
+///             self.value = !blob.is_empty();
+///         }
+///     }
+///     # }
+///     ```
+///
+///     **Receiving Plain Transfers:**
+///
+///     Calls with empty input data and a nonzero transferred value are dispatched to
+///     the ink! message flagged as `receive`, so that the contract can account them.
+///     Without such a message these calls are rejected since they carry no selector.
+///     A `receive` message takes no inputs and is implicitly `payable`. At most one
+///     message of a contract can be flagged as `receive`. It can still be called via
+///     its selector as well.
+///
+///     Note that transfers made via `pallet-balances` do not call the contract, so
+///     they are not seen by the `receive` message.
+///
+///     ```
+///     # #[ink::contract]
+///     # mod vault {
+///         # #[ink(storage)]
+///         # pub struct Vault {
+///         #     deposits: ink::storage::Mapping<AccountId, Balance>,
+///         # }
+///     impl Vault {
+///         # #[ink(constructor)]
+///         # pub fn new() -> Self {
+///         #     Vault { deposits: Default::default() }
+///         # }
+///         /// Credits plain transfers to the deposit of the caller.
+///         #[ink(message, receive)]
+///         pub fn deposit(&mut self) {
+///             let caller = self.env().caller();
+///             let deposit = self.deposits.get(caller).unwrap_or_default();
+///             self.deposits
+///                 .insert(caller, &(deposit + self.env().transferred_value()));
+///         }
+///     }
+///     # }
+///     ```
+///
+///     **Frozen ABI:**
+///
+///     An ink! message flagged as `abi_version = N` freezes the encoding of its
+///     arguments as version `N` of its ABI, so that long-lived integrations can rely
+///     on it while the contract evolves internally. The version is part of the
+///     metadata of the message, and the stability snapshot generated by
+///     `ink::codegen::stability_test!()` lists the encodings of the arguments in
+///     order, i.e. their types including the fields and variants of structs and enums.
+///     The snapshot test then fails if they change without bumping `N`, even when
+///     updating the snapshot via `INK_UPDATE_STABILITY`. Renaming an argument keeps
+///     the ABI, while reordering or renaming the fields of a struct argument breaks
+///     it.
+///
+///     ```
+///     # #[ink::contract]
+///     # mod token {
+///         # #[ink(storage)]
+///         # pub struct Token {
+///         #     balances: ink::storage::Mapping<AccountId, Balance>,
+///         # }
+///     impl Token {
+///         # #[ink(constructor)]
+///         # pub fn new() -> Self {
+///         #     Token { balances: Default::default() }
+///         # }
+///         #[ink(message, abi_version = 1)]
+///         pub fn transfer(&mut self, to: AccountId, value: Balance) {
+///             let from = self.env().caller();
+///             let balance = self.balances.get(from).unwrap_or_default();
+///             self.balances.insert(from, &balance.saturating_sub(value));
+///             let balance = self.balances.get(to).unwrap_or_default();
+///             self.balances.insert(to, &(balance + value));
+///         }
+///     }
+///     # }
+///     ```
+///
+///     **Timelocked Messages:**
+///
+///     Calls dispatched to an ink! message flagged as `timelocked(delay = "2 days")`
+///     are queued in the `ink::contrib::Timelock` of the contract instead of being
+///     executed, and return the `ink::contrib::OperationId` of the queued call. The
+///     delay is given in `milliseconds`, `seconds`, `minutes`, `hours`, `days` or
+///     `weeks`. Once it has passed, the queued call is executed via the generated
+///     `execute_timelocked(id)` method, which applies the `internal`, `only(roles)`
+///     and `non_reentrant` guards of the message to the executing caller. The account
+///     which queued the call can cancel it via the generated `cancel_timelocked(id)`
+///     method. Timelocked messages must take `&mut self`, must not return a value and
+///     must not be payable. The contract
+///     gives access to its timelock by implementing `ink::contrib::Timelocked`, see
+///     the `ink::contrib::timelock` module for a complete example.
+///
+///     ```
+///     # #[ink::contract]
+///     # mod flipper {
+///         # use ink::contrib::{Timelock, Timelocked};
+///         # #[ink(storage)]
+///         # pub struct Flipper {
+///         #     value: bool,
+///         #     timelock: Timelock,
+///         # }
+///         # impl Timelocked for Flipper {
+///         #     fn timelock(&mut self) -> &mut Timelock {
+///         #         &mut self.timelock
+///         #     }
+///         # }
+///     impl Flipper {
+///         # #[ink(constructor)]
+///         # pub fn new(initial_value: bool) -> Self {
+///         #     Flipper { value: initial_value, timelock: Timelock::new() }
+///         # }
+///         /// Flips the current value one hour after the call.
+///         #[ink(message, timelocked(delay = "1 hour"))]
+///         pub fn flip(&mut self) {
+///             self.value = !self.value;
+///         }
+///
+///         /// Executes the queued flip with the given `id`.
+///         #[ink(message)]
+///         pub fn execute(
+///             &mut self,
+///             id: ink::contrib::OperationId,
+///         ) -> Result<(), ink::contrib::TimelockError> {
+///             self.execute_timelocked(id)
+///         }
+///
+///         /// Cancels the queued flip with the given `id`.
+///         #[ink(message)]
+///         pub fn cancel(
+///             &mut self,
+///             id: ink::contrib::OperationId,
+///         ) -> Result<(), ink::contrib::TimelockError> {
+///             self.cancel_timelocked(id)
+///         }
+///     }
+///     # }
+///     ```
+///
+///     **Role Restricted Messages:**
+///
+///     Calls dispatched to an ink! message flagged as `only(roles = ["admin", "operator"])`
+///     are reverted with `LangError::CallerNotAllowed` unless the caller has any of the
+///     given roles in the `ink::contrib::AccessControl` of the contract. The contract
+///     declares the access control as a field of its storage struct, it is not added
+///     by ink!, and gives access to it by implementing `ink::contrib::AccessControlled`.
+///     It then gets the generated `grant_role` and `revoke_role` messages, which can only
+///     be called by accounts having the `"admin"` role. See the
+///     `ink::contrib::access_control` module for more details.
+///
+///     ```
+///     # #[ink::contract]
+///     # mod flipper {
+///         # use ink::contrib::{AccessControl, AccessControlled};
+///         # #[ink(storage)]
+///         # pub struct Flipper {
+///         #     value: bool,
+///         #     access_control: AccessControl,
+///         # }
+///         # impl AccessControlled for Flipper {
+///         #     fn access_control(&mut self) -> &mut AccessControl {
+///         #         &mut self.access_control
+///         #     }
+///         # }
+///     impl Flipper {
+///         #[ink(constructor)]
+///         pub fn new(initial_value: bool) -> Self {
+///             Flipper {
+///                 value: initial_value,
+///                 access_control: AccessControl::with_admin(Self::env().caller()),
+///             }
+///         }
+///
+///         /// Flips the current value, callable by admins and operators only.
+///         #[ink(message, only(roles = ["admin", "operator"]))]
+///         pub fn flip(&mut self) {
+///             self.value = !self.value;
+///         }
+///     }
+///     # }
+///     ```
+///
+///     **Non-Reentrant Messages:**
+///
+///     An ink! message can be guarded against reentrancy by adding the `non_reentrant`
+///     flag to it. A lock is taken in the contract storage for the duration of the
+///     message, so that any call reentering a `non_reentrant` message of the same
+///     contract fails, including calls reentering through other contracts. The lock
+///     is released when the message returns and rolled back if it traps.
+///
+///     ```
+///     # #[ink::contract]
+///     # mod flipper {
+///         # #[ink(storage)]
+///         # pub struct Flipper {
+///         #     value: bool,
+///         # }
+///     impl Flipper {
+///         # #[ink(constructor)]
+///         # pub fn new(initial_value: bool) -> Self {
+///         #     Flipper { value: initial_value }
+///         # }
+///         /// Flips the current value.
+///         #[ink(message, non_reentrant)]
+///         pub fn flip(&mut self) {
+///             self.value = !self.value;
+///         }
+///     }
+///     # }
+///     ```
+///
+///     **Internal Messages:**
+///
+///     An ink! message flagged as `internal` can only be called by other contracts.
+///     Calls by the origin of the call stack, e.g. users signing an extrinsic, are
+///     reverted with `LangError::CallerNotAllowed` before the message executes.
+///     With `internal(code_hash = "0x…", …)` the calling contract must also run one
+///     of the given code hashes.
+///
+///     ```
+///     # #[ink::contract]
+///     # mod adder {
+///         # #[ink(storage)]
+///         # pub struct Adder {
+///         #     value: i32,
+///         # }
+///     impl Adder {
+///         # #[ink(constructor)]
+///         # pub fn new() -> Self {
+///         #     Adder { value: 0 }
+///         # }
+///         /// Adds `by` to the value, callable only by the delegator contract.
+///         #[ink(message, internal(
+///             code_hash = "0x9bd6b7dd8d3a7a3ed1f2c9f09b7b7bb7c8c2ba2a0c08d5a3e0b9c5f5c3b8f3a1"
+///         ))]
+///         pub fn inc(&mut self, by: i32) {
+///             self.value += by;
+///         }
+///     }
+///     # }
+///     ```
+///
+///     **Borrowed Message Inputs:**
+///
+///     Besides owned types, ink! messages can take `&[u8]` and `&str` as inputs.
+///     They are encoded like `Vec<u8>` and `String` but point into the input of the
+///     call instead of being copied out of it, which saves allocations for large
+///     payloads. Constructors and messages of trait implementations only take owned
+///     inputs.
+///
+///     ```
+///     # #[ink::contract]
+///     # mod hasher {
+///         # #[ink(storage)]
+///         # pub struct Hasher {}
+///     impl Hasher {
+///         # #[ink(constructor)]
+///         # pub fn new() -> Self {
+///         #     Hasher {}
+///         # }
+///         /// Returns the BLAKE2b-256 hash of `data`.
+///         #[ink(message)]
+///         pub fn hash(&self, data: &[u8]) -> [u8; 32] {
+///             let mut output = [0; 32];
+///             ink::env::hash_bytes::<ink::env::hash::Blake2x256>(data, &mut output);
+///             output
+///         }
+///     }
+///     # }
+///     ```
+///
 ///     **Controlling the messages selector:**
 ///
 ///     Every ink! message and ink! constructor has a unique selector with which the
@@ -478,6 +1021,151 @@ pub fn selector_bytes(input: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// Event fields annotated with `#[ink(topic)]` are emitted as topics of the event.
+/// Large fields such as `Vec<u8>` or `String` can instead be annotated with
+/// `#[ink(topic(hash))]` in order to emit the `Blake2x256` hash of their SCALE
+/// encoded value as a fixed-size topic. The hash is emitted without any prefix, so
+/// indexers compute the topic of a value by hashing its SCALE encoding.
+///
+/// ```
+/// #[ink::contract]
+/// mod registry {
+///     use ink::prelude::string::String;
+///
+///     /// Emitted every time a new name is registered.
+///     #[ink(event)]
+///     pub struct Registered {
+///         #[ink(topic)]
+///         owner: AccountId,
+///         #[ink(topic(hash))]
+///         name: String,
+///     }
+///
+///     #[ink(storage)]
+///     pub struct Registry {}
+///
+///     impl Registry {
+///         #[ink(constructor)]
+///         pub fn new() -> Self {
+///             Self {}
+///         }
+///
+///         #[ink(message)]
+///         pub fn register(&mut self, name: String) {
+///             let owner = self.env().caller();
+///             self.env().emit_event(Registered { owner, name });
+///         }
+///     }
+/// }
+/// ```
+///
+/// Event fields holding amounts of a currency, like balances, can be annotated with
+/// `#[ink(decimals = N)]`. The event metadata then declares their `N` decimal places,
+/// so that tools can render e.g. `1.5 UNIT` instead of the integer amount. In debug
+/// messages the contract itself renders them with `ink::env::format_balance`.
+///
+/// ```
+/// #[ink::contract]
+/// mod token {
+///     #[ink(event)]
+///     pub struct Transferred {
+///         #[ink(topic)]
+///         to: AccountId,
+///         #[ink(decimals = 12)]
+///         value: Balance,
+///     }
+///
+///     #[ink(storage)]
+///     pub struct Token {}
+///
+///     impl Token {
+///         #[ink(constructor)]
+///         pub fn new() -> Self {
+///             Self {}
+///         }
+///
+///         #[ink(message)]
+///         pub fn transfer(&mut self, to: AccountId, value: Balance) {
+///             ink::env::debug_println!(
+///                 "transfer of {} UNIT",
+///                 ink::env::format_balance(value, 12)
+///             );
+///             self.env().emit_event(Transferred { to, value });
+///         }
+///     }
+/// }
+/// ```
+///
+/// Indexers built for EVM events identify events by the Keccak-256 hash of their
+/// Solidity style signature as first topic. Events annotated with
+/// `#[ink(signature_topic = "S")]` emit this hash of the signature `S` instead of
+/// the ink! event signature, which is also provided by the event metadata.
+///
+/// ```
+/// #[ink::contract]
+/// mod token {
+///     #[ink(event, signature_topic = "Transfer(address,address,uint128)")]
+///     pub struct Transfer {
+///         #[ink(topic)]
+///         from: AccountId,
+///         #[ink(topic)]
+///         to: AccountId,
+///         value: Balance,
+///     }
+/// #
+/// #   #[ink(storage)]
+/// #   pub struct Token {}
+/// #
+/// #   impl Token {
+/// #       #[ink(constructor)]
+/// #       pub fn new() -> Self {
+/// #           Self {}
+/// #       }
+/// #
+/// #       #[ink(message)]
+/// #       pub fn message(&self) {}
+/// #   }
+/// }
+/// ```
+///
+/// ## Errors
+///
+/// Enums annotated with `#[ink(error)]` assign every variant a stable 4-byte error
+/// code, derived from its path `Enum::Variant`, and encode it in place of the variant
+/// index. Wallets and indexers can thus decode the failure reasons of reverted
+/// messages uniformly across contracts. The `scale::Encode`, `scale::Decode` and
+/// `scale_info::TypeInfo` implementations of ink! errors are generated and must not
+/// be derived. See `ink::reflect::ContractError` for the encoding.
+///
+/// ```
+/// #[ink::contract]
+/// mod vault {
+///     #[ink(error)]
+///     #[derive(Debug, PartialEq, Eq)]
+///     pub enum Error {
+///         /// The caller is not the owner of the vault.
+///         NotOwner,
+///         /// The vault holds less than the requested amount.
+///         InsufficientBalance { required: Balance },
+///     }
+///
+///     #[ink(storage)]
+///     pub struct Vault {}
+///
+///     impl Vault {
+///         #[ink(constructor)]
+///         pub fn new() -> Self {
+///             Self {}
+///         }
+///
+///         #[ink(message)]
+///         pub fn withdraw(&mut self, amount: Balance) -> Result<(), Error> {
+///             Err(Error::InsufficientBalance { required: amount })
+///         }
+///     }
+/// }
+/// ```
+///
 /// ## Example: Flipper
 ///
 /// The below code shows the complete implementation of the so-called Flipper
@@ -598,6 +1286,66 @@ pub fn contract(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// # Default Implementations
+///
+/// ink! trait messages may provide a default implementation. It can only use the
+/// other messages of the trait and the environment via `Self::Env`, since the
+/// storage of the implementing contract is unknown to the trait definition.
+///
+/// Implementers use the default implementation by declaring the message without a
+/// body, into which the code generator inlines the body of the default
+/// implementation. Messages with a body override it. Since the body is inlined,
+/// it is type checked for every implementer and paths within it are resolved at the
+/// implementer, so they should be fully qualified.
+///
+/// ```
+/// #[ink::contract]
+/// mod counter {
+///     #[ink::trait_definition]
+///     pub trait Counter {
+///         #[ink(message)]
+///         fn get(&self) -> u32;
+///
+///         #[ink(message)]
+///         fn set(&mut self, value: u32);
+///
+///         /// Increments the counter by `by`.
+///         #[ink(message)]
+///         fn inc(&mut self, by: u32) {
+///             let value = self.get() + by;
+///             self.set(value)
+///         }
+///     }
+///
+///     #[ink(storage)]
+///     pub struct Contract {
+///         value: u32,
+///     }
+///
+///     impl Contract {
+///         #[ink(constructor)]
+///         pub fn new() -> Self {
+///             Self { value: 0 }
+///         }
+///     }
+///
+///     impl Counter for Contract {
+///         #[ink(message)]
+///         fn get(&self) -> u32 {
+///             self.value
+///         }
+///
+///         #[ink(message)]
+///         fn set(&mut self, value: u32) {
+///             self.value = value;
+///         }
+///
+///         #[ink(message)]
+///         fn inc(&mut self, by: u32);
+///     }
+/// }
+/// ```
+///
 /// ## Header Arguments
 ///
 /// The `#[ink::trait_definition]` macro can be provided with some additional comma-separated
@@ -765,8 +1513,8 @@ pub fn trait_definition(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///
 /// ## Header Arguments
 ///
-/// The `#[ink::storage_item]` macro can be provided with an additional comma-separated
-/// header argument:
+/// The `#[ink::storage_item]` macro can be provided with additional comma-separated
+/// header arguments:
 ///
 /// - `derive: bool`
 ///
@@ -791,6 +1539,131 @@ pub fn trait_definition(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///     ```
 ///
 ///     **Default value:** true.
+///
+/// - `namespace: String`
+///
+///     The `namespace` configuration parameter computes the storage keys of the
+///     fields from the given namespace instead of the name of the type. Reusable
+///     components should set a namespace unique to them, so that their keys don't
+///     collide with those of other components, e.g. when two crates both define a
+///     `Data` type. Renaming the type keeps the keys of its fields.
+///
+///     **Usage Example:**
+///     ```
+///     use ink::storage::Lazy;
+///
+///     #[ink::storage_item(namespace = "ownable")]
+///     struct Data {
+///         // Stored under the key computed from `ownable::owner`.
+///         owner: Lazy<[u8; 32]>,
+///     }
+///     ```
+///
+///     **Default value:** The name of the type.
+///
+/// - `encoding: scale | compact`
+///
+///     With `encoding = compact` the item is packed and its fields of type `u32`,
+///     `u64` or `u128` are stored with the SCALE compact encoding. Values much
+///     smaller than the maximum of their type then take up to 4 bytes instead of 8
+///     or 16, which shrinks values of large mappings, such as the orders of an order
+///     book, and the storage deposits paid for them. In exchange values close to the
+///     maximum take one byte more than their default encoding, and encoding and
+///     decoding takes longer, see the `compact_encoding` benchmark of the `ink` crate.
+///
+///     The macro does not resolve type aliases, so fields of an alias such as
+///     `Balance` or `type Price = u128` are only stored compactly if flagged with
+///     `#[codec(compact)]`. Fields flagged with any `#[codec(..)]` attribute keep the
+///     encoding configured by it.
+///
+///     The compact encoding is part of the storage layout: changing it requires a
+///     migration of the stored values. Since the item is packed, it must not contain
+///     non-packed fields such as a `Mapping`.
+///
+///     **Usage Example:**
+///     ```
+///     use ink::storage::Mapping;
+///
+///     type Price = u128;
+///
+///     #[ink::storage_item(encoding = compact)]
+///     struct Order {
+///         maker: [u8; 32],
+///         // Takes 2 instead of 16 bytes for a price of 1_000.
+///         #[codec(compact)]
+///         price: Price,
+///         amount: u128,
+///     }
+///
+///     #[ink::storage_item]
+///     struct OrderBook {
+///         orders: Mapping<u32, Order>,
+///     }
+///     ```
+///
+///     **Default value:** `scale`.
+///
+/// ## Field Arguments
+///
+/// - `#[ink(storage_key = N: u32)]`
+///
+///     By default the storage key of a non-packed field is computed from the names
+///     of the type (or its `namespace`), the variant and the field, so renaming them relocates the stored
+///     values. Annotating the field with `#[ink(storage_key = N)]` pins its storage
+///     key to `N` instead. Upgradeable contracts can use this to keep the storage of
+///     existing fields while refactoring and appending new fields.
+///
+///     The annotation has no effect on packed fields, which are stored in the storage
+///     cell of their parent. Every storage key may only be used once per type.
+///
+///     **Usage Example:**
+///     ```
+///     use ink::storage::{
+///         Lazy,
+///         Mapping,
+///     };
+///
+///     #[ink::storage_item]
+///     struct Registry {
+///         #[ink(storage_key = 0x1234_5678)]
+///         owners: Mapping<u32, [u8; 32]>,
+///         #[ink(storage_key = 0x1234_5679)]
+///         total: Lazy<u32>,
+///     }
+///     ```
+///
+/// ## Unbounded Collections
+///
+/// Packed fields are loaded into the static buffer of the contract together with
+/// the storage cell of their parent. A field packing a collection without a bound
+/// on its size, such as a `Vec` or a `BTreeMap`, fails to load once the encoded
+/// cell exceeds the buffer, so a warning suggests to store its elements in a
+/// `Mapping` or the collection in a `Lazy` instead.
+///
+/// Fields whose collection is bounded by the contract logic opt out of the warning
+/// via `#[ink(bounded)]`. The warning is raised via the `deprecated` lint, so
+/// `#![deny(deprecated)]` turns it into an error.
+///
+/// ```
+/// use ink::{
+///     prelude::vec::Vec,
+///     storage::Mapping,
+/// };
+///
+/// #[ink::storage_item]
+/// struct Members {
+///     // Warns because the `Vec` grows with every member.
+///     // list: Vec<[u8; 32]>,
+///     list: Mapping<u32, [u8; 32]>,
+///     len: u32,
+///     // At most ten admins are allowed.
+///     #[ink(bounded)]
+///     admins: Vec<[u8; 32]>,
+/// }
+/// ```
+///
+/// The storage layout metadata of a contract can be checked for unbounded fields
+/// with `ink::metadata::layout::report`.
 #[proc_macro_attribute]
 pub fn storage_item(attr: TokenStream, item: TokenStream) -> TokenStream {
     storage_item::generate(attr.into(), item.into()).into()
@@ -834,6 +1707,28 @@ pub fn storage_item(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///     }
 /// }
 /// ```
+///
+/// # Configuration
+///
+/// The off-chain environment of a test can be set up with the following
+/// configuration arguments instead of calling the `ink::env::test::set_*`
+/// functions at the start of the test:
+///
+/// - `caller: String`: the default account calling the contract, one of
+///   `"alice"`, `"bob"`, `"charlie"`, `"django"`, `"eve"` or `"frank"`.
+/// - `balance: Balance`: the balance of the caller.
+/// - `callee_balance: Balance`: the balance of the contract under test.
+///
+/// ```
+/// #[ink::test(caller = "alice", balance = 1_000, callee_balance = 500)]
+/// fn test3() {
+///     type E = ink_env::DefaultEnvironment;
+///     let alice = ink_env::test::default_accounts::<E>().alice;
+///     assert_eq!(ink_env::caller::<E>(), alice);
+///     assert_eq!(ink_env::test::get_account_balance::<E>(alice), Ok(1_000));
+///     assert_eq!(ink_env::balance::<E>(), 500);
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn test(attr: TokenStream, item: TokenStream) -> TokenStream {
     ink_test::generate(attr.into(), item.into()).into()
@@ -1376,6 +2271,86 @@ synstructure::decl_derive!(
     /// ```
     storage::storage_layout_derive
 );
+synstructure::decl_derive!(
+    [Ownable2Step] =>
+    /// Derives `ink::contrib::Owned` for an ink! storage struct with an
+    /// `ink::contrib::Ownable2Step` field.
+    ///
+    /// Additionally the ink! storage struct of an ink! contract deriving
+    /// `Ownable2Step` gets the ink! messages
+    ///
+    /// - `owner` and `pending_owner`, returning the respective account if any,
+    /// - `transfer_ownership(new_owner)`, proposing `new_owner` as the new owner,
+    /// - `accept_ownership`, making the pending owner the owner,
+    /// - `renounce_ownership`, leaving the contract without an owner,
+    ///
+    /// and the ink! events `OwnershipTransferStarted` and `OwnershipTransferred`
+    /// emitted by them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// #[ink::contract]
+    /// mod owned {
+    ///     use ink::contrib::Ownable2Step;
+    ///
+    ///     #[ink(storage)]
+    ///     #[derive(Ownable2Step)]
+    ///     pub struct Owned {
+    ///         ownable: Ownable2Step,
+    ///     }
+    ///
+    ///     impl Owned {
+    ///         #[ink(constructor)]
+    ///         pub fn new() -> Self {
+    ///             Self {
+    ///                 ownable: Ownable2Step::new(Self::env().caller()),
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ownable::ownable_derive
+);
+synstructure::decl_derive!(
+    [RuntimeCall, attributes(runtime_call)] =>
+    /// Derives `ink::runtime_call::RuntimeCall` for an enum mirroring the calls of a
+    /// pallet, so that they can be dispatched via `self.env().call_runtime(&call)`.
+    ///
+    /// The indices of the pallet in the runtime and of every call in the pallet must be
+    /// given via `#[runtime_call(index = N)]`, as found in the metadata of the runtime.
+    /// The fields of a call are encoded in order, fields which the pallet takes
+    /// compact encoded must be marked with `#[runtime_call(compact)]`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ink::runtime_call::RuntimeCall;
+    /// # type AccountId = [u8; 32];
+    ///
+    /// /// The calls of the `Balances` pallet at index 4 of the runtime.
+    /// #[derive(RuntimeCall)]
+    /// #[runtime_call(index = 4)]
+    /// enum BalancesCall {
+    ///     #[runtime_call(index = 3)]
+    ///     TransferKeepAlive {
+    ///         dest: (u8, AccountId),
+    ///         #[runtime_call(compact)]
+    ///         value: u128,
+    ///     },
+    /// }
+    ///
+    /// let call = BalancesCall::TransferKeepAlive {
+    ///     dest: (0, [0x01; 32]),
+    ///     value: 100,
+    /// };
+    /// assert_eq!(
+    ///     scale::Encode::encode(&call),
+    ///     scale::Encode::encode(&(4u8, 3u8, (0u8, [0x01u8; 32]), scale::Compact(100u128))),
+    /// );
+    /// ```
+    runtime_call::runtime_call_derive
+);
 
 #[cfg(test)]
 pub use contract::generate_or_err;
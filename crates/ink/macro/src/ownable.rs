@@ -0,0 +1,102 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{
+    quote,
+    ToTokens,
+};
+use syn::spanned::Spanned;
+
+/// Returns `true` if `ty` is the `Ownable2Step` type.
+///
+/// Also matches the `<Ownable2Step as AutoStorableHint<..>>::Type` the types of the
+/// storage fields are wrapped into by `#[ink::storage_item]`.
+fn is_ownable(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(syn::TypePath {
+            qself: Some(qself), ..
+        }) => is_ownable(&qself.ty),
+        syn::Type::Path(syn::TypePath { qself: None, path }) => {
+            matches!(
+                path.segments.last(),
+                Some(segment) if segment.ident == "Ownable2Step"
+            )
+        }
+        syn::Type::Group(group) => is_ownable(&group.elem),
+        syn::Type::Paren(paren) => is_ownable(&paren.elem),
+        _ => false,
+    }
+}
+
+/// Derives `ink::contrib::Owned` for the struct having an `Ownable2Step` field.
+pub fn ownable_derive(s: synstructure::Structure) -> TokenStream2 {
+    let fields = match &s.ast().data {
+        syn::Data::Struct(data) => &data.fields,
+        _ => {
+            return syn::Error::new(
+                s.ast().span(),
+                "`Ownable2Step` can only be derived for structs",
+            )
+            .to_compile_error()
+        }
+    };
+    let mut ownable_fields = fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| is_ownable(&field.ty))
+        .map(|(n, field)| {
+            match &field.ident {
+                Some(ident) => ident.to_token_stream(),
+                None => syn::Index::from(n).to_token_stream(),
+            }
+        });
+    let field = match (ownable_fields.next(), ownable_fields.next()) {
+        (Some(field), None) => field,
+        (None, _) => {
+            return syn::Error::new(
+                s.ast().ident.span(),
+                "deriving `Ownable2Step` requires exactly one `Ownable2Step` field",
+            )
+            .to_compile_error()
+        }
+        (Some(_), Some(second)) => {
+            return syn::Error::new(
+                second.span(),
+                "deriving `Ownable2Step` requires exactly one `Ownable2Step` field",
+            )
+            .to_compile_error()
+        }
+    };
+    s.gen_impl(quote! {
+        gen impl ::ink::contrib::Owned<<Self as ::ink::reflect::ContractEnv>::Env>
+            for @Self
+        {
+            fn ownable(
+                &self,
+            ) -> &::ink::contrib::Ownable2Step<<Self as ::ink::reflect::ContractEnv>::Env>
+            {
+                &self.#field
+            }
+
+            fn ownable_mut(
+                &mut self,
+            ) -> &mut ::ink::contrib::Ownable2Step<
+                <Self as ::ink::reflect::ContractEnv>::Env,
+            > {
+                &mut self.#field
+            }
+        }
+    })
+}
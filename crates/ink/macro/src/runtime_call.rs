@@ -0,0 +1,176 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use proc_macro2::{
+    Span,
+    TokenStream as TokenStream2,
+};
+use quote::{
+    quote,
+    quote_spanned,
+};
+use std::collections::HashMap;
+use syn::spanned::Spanned;
+
+/// The arguments of the `#[runtime_call(..)]` attributes of an item.
+#[derive(Default)]
+struct RuntimeCallArgs {
+    /// The `index = N` argument, if any.
+    index: Option<(u8, Span)>,
+    /// If the `compact` argument is given.
+    compact: bool,
+}
+
+impl RuntimeCallArgs {
+    /// Parses the arguments of all `#[runtime_call(..)]` attributes in `attrs`.
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut args = Self::default();
+        for attr in attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("runtime_call"))
+        {
+            let list = match attr.parse_meta()? {
+                syn::Meta::List(list) => list,
+                meta => {
+                    return Err(syn::Error::new(
+                        meta.span(),
+                        "expected `#[runtime_call(..)]`",
+                    ))
+                }
+            };
+            for nested in list.nested {
+                match nested {
+                    syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                        path,
+                        lit: syn::Lit::Int(lit),
+                        ..
+                    })) if path.is_ident("index") && args.index.is_none() => {
+                        args.index = Some((lit.base10_parse::<u8>()?, lit.span()));
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::Path(path))
+                        if path.is_ident("compact") && !args.compact =>
+                    {
+                        args.compact = true;
+                    }
+                    nested => {
+                        return Err(syn::Error::new(
+                            nested.span(),
+                            "encountered unknown or duplicate `runtime_call` argument, \
+                             expected `index = N` or `compact`",
+                        ))
+                    }
+                }
+            }
+        }
+        Ok(args)
+    }
+
+    /// Returns the index of the item or an error if it has none.
+    fn expect_index(&self, item: Span, what: &str) -> syn::Result<u8> {
+        self.index.map(|(index, _)| index).ok_or_else(|| {
+            syn::Error::new(
+                item,
+                format!(
+                    "missing `#[runtime_call(index = N)]` with the index of the {} \
+                     in the runtime metadata",
+                    what
+                ),
+            )
+        })
+    }
+}
+
+/// Derives `ink_env::runtime_call::RuntimeCall` for an enum of the calls of a pallet.
+pub fn runtime_call_derive(s: synstructure::Structure) -> TokenStream2 {
+    match runtime_call_derive_or_err(s) {
+        Ok(output) => output,
+        Err(err) => err.to_compile_error(),
+    }
+}
+
+fn runtime_call_derive_or_err(
+    mut s: synstructure::Structure,
+) -> syn::Result<TokenStream2> {
+    if !matches!(s.ast().data, syn::Data::Enum(_)) {
+        return Err(syn::Error::new(
+            s.ast().ident.span(),
+            "`RuntimeCall` can only be derived for enums of the calls of a pallet",
+        ))
+    }
+    if s.variants().is_empty() {
+        return Err(syn::Error::new(
+            s.ast().ident.span(),
+            "`RuntimeCall` can not be derived for enums without calls",
+        ))
+    }
+    let pallet_index = RuntimeCallArgs::parse(&s.ast().attrs)?
+        .expect_index(s.ast().ident.span(), "pallet")?;
+    let mut call_indices = HashMap::new();
+    let mut arms = Vec::new();
+    for variant in s.variants() {
+        let args = RuntimeCallArgs::parse(variant.ast().attrs)?;
+        let call_index = args.expect_index(variant.ast().ident.span(), "call")?;
+        if let Some(previous) = call_indices.insert(call_index, variant.ast().ident) {
+            return Err(syn::Error::new(
+                args.index
+                    .map(|(_, span)| span)
+                    .unwrap_or_else(Span::call_site),
+                format!(
+                    "encountered duplicate call index {} of `{}` and `{}`",
+                    call_index,
+                    previous,
+                    variant.ast().ident
+                ),
+            ))
+        }
+        let mut fields = Vec::new();
+        for binding in variant.bindings() {
+            let ty = &binding.ast().ty;
+            let span = ty.span();
+            if RuntimeCallArgs::parse(&binding.ast().attrs)?.compact {
+                fields.push(quote_spanned!(span=>
+                    ::scale::Encode::encode_to(
+                        &<<#ty as ::scale::HasCompact>::Type as ::scale::EncodeAsRef<'_, #ty>>::RefType::from(#binding),
+                        __dest,
+                    );
+                ));
+            } else {
+                fields.push(quote_spanned!(span=>
+                    ::scale::Encode::encode_to(#binding, __dest);
+                ));
+            }
+        }
+        let pat = variant.pat();
+        arms.push(quote! {
+            #pat => {
+                ::scale::Output::push_byte(__dest, #call_index);
+                #( #fields )*
+            }
+        });
+    }
+    s.add_bounds(synstructure::AddBounds::None);
+    Ok(s.gen_impl(quote! {
+        gen impl ::scale::Encode for @Self {
+            #[allow(non_camel_case_types)]
+            fn encode_to<__ink_O: ::scale::Output + ?::core::marker::Sized>(&self, __dest: &mut __ink_O) {
+                ::scale::Output::push_byte(__dest, #pallet_index);
+                match self {
+                    #( #arms )*
+                }
+            }
+        }
+
+        gen impl ::ink::env::runtime_call::RuntimeCall for @Self {}
+    }))
+}
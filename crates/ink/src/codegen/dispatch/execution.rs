@@ -14,6 +14,7 @@
 
 use crate::reflect::DispatchError;
 use ink_env::Environment;
+use ink_primitives::LangError;
 
 /// Returns `Ok` if the caller did not transfer additional value to the callee.
 ///
@@ -31,3 +32,307 @@ where
     }
     Ok(())
 }
+
+/// Refunds the transferred value in excess of `minimum` back to the caller.
+///
+/// Used by ink! constructors flagged with `#[ink(payable(refund_excess = N))]`.
+///
+/// The contract always keeps at least the existential deposit, even if `minimum`
+/// is below it, since the refund would otherwise reap the contract account.
+///
+/// # Errors
+///
+/// - If the caller transferred less than `minimum` to the callee.
+/// - If refunding the excess value to the caller failed.
+#[inline]
+pub fn refund_excess_endowment<E>(
+    minimum: <E as Environment>::Balance,
+) -> Result<(), DispatchError>
+where
+    E: Environment,
+{
+    let transferred = ink_env::transferred_value::<E>();
+    if transferred < minimum {
+        return Err(DispatchError::InsufficientEndowment)
+    }
+    let kept = core::cmp::max(minimum, ink_env::minimum_balance::<E>());
+    if transferred > kept {
+        ink_env::transfer::<E>(ink_env::caller::<E>(), transferred - kept)
+            .map_err(|_| DispatchError::CouldNotRefundExcess)?;
+    }
+    Ok(())
+}
+
+/// Ensures that at least `minimum` was transferred to the callee.
+///
+/// Used by ink! messages flagged with `#[ink(payable(min = N))]`.
+///
+/// # Errors
+///
+/// If the caller transferred less than `minimum` to the callee.
+#[inline]
+pub fn ensure_min_transferred_value<E>(
+    minimum: <E as Environment>::Balance,
+) -> Result<(), LangError>
+where
+    E: Environment,
+{
+    if ink_env::transferred_value::<E>() < minimum {
+        return Err(LangError::InsufficientTransferredValue)
+    }
+    Ok(())
+}
+
+/// Returns `true` if the call is a plain transfer, i.e. it comes with empty input
+/// data and a nonzero transferred value.
+///
+/// Used to dispatch to the ink! message flagged with `#[ink(receive)]`. Inputs
+/// which don't know their remaining length are never plain transfers.
+#[inline]
+pub fn is_plain_transfer<E, I>(input: &mut I) -> bool
+where
+    E: Environment,
+    I: scale::Input,
+{
+    matches!(input.remaining_len(), Ok(Some(0)))
+        && ink_env::transferred_value::<E>() != <E as Environment>::Balance::from(0_u32)
+}
+
+/// Ensures that the caller is a contract, and that its code hash is one of the
+/// `allowed_code_hashes` unless they are empty.
+///
+/// Used by ink! messages flagged with `#[ink(internal)]`.
+///
+/// # Errors
+///
+/// - If the caller is the origin of the call stack, i.e. not a contract.
+/// - If the code hash of the calling contract is not allowed.
+#[inline]
+pub fn ensure_internal_caller<E>(
+    allowed_code_hashes: &[[u8; 32]],
+) -> Result<(), LangError>
+where
+    E: Environment,
+{
+    if ink_env::caller_is_origin::<E>() {
+        return Err(LangError::CallerNotAllowed)
+    }
+    if allowed_code_hashes.is_empty() {
+        return Ok(())
+    }
+    let code_hash = ink_env::code_hash::<E>(&ink_env::caller::<E>())
+        .map_err(|_| LangError::CallerNotAllowed)?;
+    if !allowed_code_hashes
+        .iter()
+        .any(|allowed| code_hash.as_ref() == &allowed[..])
+    {
+        return Err(LangError::CallerNotAllowed)
+    }
+    Ok(())
+}
+
+/// Marks the execution of an ink! constructor for as long as it is alive.
+///
+/// The off-chain environment attributes the events emitted meanwhile to the
+/// constructor, see `ink_env::test::assert_constructor_event`. This is a no-op
+/// on-chain.
+#[must_use]
+pub struct ConstructorScope {
+    _private: (),
+}
+
+impl ConstructorScope {
+    /// Enters the scope of an ink! constructor.
+    #[inline]
+    pub fn enter() -> Self {
+        #[cfg(feature = "std")]
+        ink_env::test::enter_constructor();
+        Self { _private: () }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Drop for ConstructorScope {
+    fn drop(&mut self) {
+        ink_env::test::leave_constructor();
+    }
+}
+
+/// Returns `Ok` if the caller has any of the given `roles` in `access_control`.
+///
+/// Used by ink! messages flagged with `#[ink(only(roles = [..]))]`.
+///
+/// # Errors
+///
+/// If the caller has none of the `roles`.
+pub fn ensure_any_role<E>(
+    access_control: &crate::contrib::AccessControl<E>,
+    roles: &[crate::contrib::Role],
+) -> Result<(), LangError>
+where
+    E: Environment,
+    E::AccountId: scale::EncodeLike,
+{
+    if !access_control.has_any_role(roles, ink_env::caller::<E>()) {
+        return Err(LangError::CallerNotAllowed)
+    }
+    Ok(())
+}
+
+/// The storage key of the lock taken by `#[ink(non_reentrant)]` messages.
+const REENTRANCY_GUARD_KEY: ink_primitives::Key =
+    ink_primitives::KeyComposer::from_str("ink::reentrancy_guard");
+
+/// Takes the reentrancy lock of the executed contract.
+///
+/// Used by ink! messages flagged with `#[ink(non_reentrant)]`.
+///
+/// # Note
+///
+/// The lock is stored in the contract storage so that it is also observed by
+/// calls reentering the contract through cross-contract calls. In case the
+/// message traps or reverts all storage changes, including the lock, are
+/// rolled back by the contracts pallet.
+///
+/// # Errors
+///
+/// If the lock has already been taken by another `non_reentrant` message
+/// further up the call stack.
+#[inline]
+pub fn acquire_reentrancy_lock() -> Result<(), DispatchError> {
+    if ink_env::contains_contract_storage(&REENTRANCY_GUARD_KEY).is_some() {
+        return Err(DispatchError::ReentrantCall)
+    }
+    ink_env::set_contract_storage(&REENTRANCY_GUARD_KEY, &());
+    Ok(())
+}
+
+/// Releases the reentrancy lock taken by [`acquire_reentrancy_lock`].
+#[inline]
+pub fn release_reentrancy_lock() {
+    ink_env::clear_contract_storage(&REENTRANCY_GUARD_KEY);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reentrancy_lock_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            assert_eq!(acquire_reentrancy_lock(), Ok(()));
+            assert_eq!(acquire_reentrancy_lock(), Err(DispatchError::ReentrantCall));
+            release_reentrancy_lock();
+            assert_eq!(acquire_reentrancy_lock(), Ok(()));
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn plain_transfer_needs_empty_input_and_value() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let is_plain_transfer = |mut input: &[u8]| {
+                is_plain_transfer::<ink_env::DefaultEnvironment, _>(&mut input)
+            };
+            assert!(!is_plain_transfer(&[]));
+            ink_env::test::set_value_transferred::<ink_env::DefaultEnvironment>(1);
+            assert!(is_plain_transfer(&[]));
+            assert!(!is_plain_transfer(&[0xCA, 0xFE, 0xBA, 0xBE]));
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn internal_caller_must_not_be_origin() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            assert_eq!(
+                ensure_internal_caller::<ink_env::DefaultEnvironment>(&[]),
+                Err(LangError::CallerNotAllowed)
+            );
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn refund_excess_endowment_keeps_existential_deposit() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|accounts| {
+            // given
+            let existential_deposit =
+                ink_env::minimum_balance::<ink_env::DefaultEnvironment>();
+            let transferred = existential_deposit + 100;
+            let contract = ink_env::test::callee::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            ink_env::test::set_account_balance::<ink_env::DefaultEnvironment>(
+                contract,
+                transferred,
+            );
+            ink_env::test::set_value_transferred::<ink_env::DefaultEnvironment>(
+                transferred,
+            );
+            let bob_balance = ink_env::test::get_account_balance::<
+                ink_env::DefaultEnvironment,
+            >(accounts.bob)?;
+
+            // when
+            let refunded = refund_excess_endowment::<ink_env::DefaultEnvironment>(10);
+
+            // then
+            assert_eq!(refunded, Ok(()));
+            assert_eq!(
+                ink_env::test::get_account_balance::<ink_env::DefaultEnvironment>(
+                    contract
+                )?,
+                existential_deposit
+            );
+            assert_eq!(
+                ink_env::test::get_account_balance::<ink_env::DefaultEnvironment>(
+                    accounts.bob
+                )?,
+                bob_balance + 100
+            );
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn refund_excess_endowment_refunds_above_minimum() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|accounts| {
+            // given
+            let existential_deposit =
+                ink_env::minimum_balance::<ink_env::DefaultEnvironment>();
+            let minimum = existential_deposit * 2;
+            let contract = ink_env::test::callee::<ink_env::DefaultEnvironment>();
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob);
+            ink_env::test::set_account_balance::<ink_env::DefaultEnvironment>(
+                contract,
+                minimum + 100,
+            );
+            ink_env::test::set_value_transferred::<ink_env::DefaultEnvironment>(
+                minimum + 100,
+            );
+
+            // when
+            let refunded =
+                refund_excess_endowment::<ink_env::DefaultEnvironment>(minimum);
+
+            // then
+            assert_eq!(refunded, Ok(()));
+            assert_eq!(
+                ink_env::test::get_account_balance::<ink_env::DefaultEnvironment>(
+                    contract
+                )?,
+                minimum
+            );
+            assert_eq!(
+                refund_excess_endowment::<ink_env::DefaultEnvironment>(minimum + 101),
+                Err(DispatchError::InsufficientEndowment)
+            );
+            Ok(())
+        })
+        .unwrap()
+    }
+}
@@ -0,0 +1,166 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// Input which yields an already decoded selector before the rest of the input.
+///
+/// Used to decode the [`RawInput`](crate::RawInput) of wildcard messages, whose
+/// selector has already been consumed by the dispatch.
+pub struct SelectorPrefixedInput<'a, I> {
+    selector: [u8; 4],
+    offset: usize,
+    input: &'a mut I,
+}
+
+impl<'a, I> SelectorPrefixedInput<'a, I> {
+    /// Creates an input yielding `selector` followed by `input`.
+    pub fn new(selector: [u8; 4], input: &'a mut I) -> Self {
+        Self {
+            selector,
+            offset: 0,
+            input,
+        }
+    }
+}
+
+impl<'a, I> scale::Input for SelectorPrefixedInput<'a, I>
+where
+    I: scale::Input,
+{
+    fn remaining_len(&mut self) -> Result<Option<usize>, scale::Error> {
+        let prefix_len = self.selector.len() - self.offset;
+        Ok(self.input.remaining_len()?.map(|len| len + prefix_len))
+    }
+
+    fn read(&mut self, into: &mut [u8]) -> Result<(), scale::Error> {
+        let prefix = &self.selector[self.offset..];
+        let from_prefix = into.len().min(prefix.len());
+        into[..from_prefix].copy_from_slice(&prefix[..from_prefix]);
+        self.offset += from_prefix;
+        self.input.read(&mut into[from_prefix..])
+    }
+}
+
+/// The inputs of an ink! message whose decoding is deferred to its execution.
+///
+/// The dispatch defers decoding the inputs of messages flagged as `deferred_inputs`
+/// until their checks, e.g. denying payment or ensuring the caller, have passed, so
+/// that calls failing them do not pay for decoding large inputs. The inputs of
+/// messages taking borrowed inputs are deferred as well, since they can only point
+/// into the input of the call while it is accessed.
+///
+/// # Note
+///
+/// Only the position of the inputs is recorded, they are decoded from the input of
+/// the call when executing the message, see [`ink_env::with_input_bytes`]. The input
+/// of the call has been read as a whole at this point, and the inputs of the
+/// message are decoded all at once.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct DeferredInput {
+    offset: usize,
+}
+
+impl DeferredInput {
+    /// Creates the inputs starting at `offset` in the input of the call.
+    pub fn new(offset: usize) -> Self {
+        Self { offset }
+    }
+
+    /// Calls `f` with the encoded inputs, pointing into the input of the call.
+    pub fn with_input<F, R>(self, f: F) -> R
+    where
+        F: FnOnce(&mut &[u8]) -> R,
+    {
+        ink_env::with_input_bytes(|input| {
+            f(&mut input.get(self.offset..).unwrap_or_default())
+        })
+    }
+}
+
+/// Decodes the borrowed inputs of ink! messages, i.e. `&[u8]` and `&str`.
+///
+/// Borrowed inputs are SCALE encoded like their owned counterparts `Vec<u8>`
+/// and `String`.
+pub trait DecodeBorrowed<'a>: Sized {
+    /// Decodes the input pointing into the given `input` without copying it.
+    fn decode_borrowed(input: &mut &'a [u8]) -> Result<Self, scale::Error>;
+}
+
+/// Splits the SCALE encoded bytes of a `Vec<u8>` off the start of `input`.
+fn take_bytes<'a>(input: &mut &'a [u8]) -> Result<&'a [u8], scale::Error> {
+    let len = <scale::Compact<u32> as scale::Decode>::decode(input)?.0 as usize;
+    if input.len() < len {
+        return Err("not enough bytes to borrow the input".into())
+    }
+    let (bytes, rest) = input.split_at(len);
+    *input = rest;
+    Ok(bytes)
+}
+
+impl<'a> DecodeBorrowed<'a> for &'a [u8] {
+    fn decode_borrowed(input: &mut &'a [u8]) -> Result<Self, scale::Error> {
+        take_bytes(input)
+    }
+}
+
+impl<'a> DecodeBorrowed<'a> for &'a str {
+    fn decode_borrowed(input: &mut &'a [u8]) -> Result<Self, scale::Error> {
+        core::str::from_utf8(take_bytes(input)?)
+            .map_err(|_| "invalid UTF-8 in borrowed input".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scale::Input as _;
+
+    #[test]
+    fn selector_prefixed_input_works() {
+        let mut rest = &[5u8, 6, 7][..];
+        let mut input = SelectorPrefixedInput::new([1, 2, 3, 4], &mut rest);
+        assert_eq!(input.remaining_len(), Ok(Some(7)));
+        let mut buffer = [0u8; 2];
+        input.read(&mut buffer).unwrap();
+        assert_eq!(buffer, [1, 2]);
+        assert_eq!(input.remaining_len(), Ok(Some(5)));
+        let mut buffer = [0u8; 4];
+        input.read(&mut buffer).unwrap();
+        assert_eq!(buffer, [3, 4, 5, 6]);
+        assert_eq!(input.read_byte(), Ok(7));
+        assert!(input.read_byte().is_err());
+    }
+
+    #[test]
+    fn decode_borrowed_works() {
+        let encoded = scale::Encode::encode(&(vec![1u8, 2, 3], "ink!", 7u8));
+        let mut input = &encoded[..];
+        assert_eq!(
+            <&[u8] as DecodeBorrowed>::decode_borrowed(&mut input),
+            Ok(&[1u8, 2, 3][..])
+        );
+        assert_eq!(
+            <&str as DecodeBorrowed>::decode_borrowed(&mut input),
+            Ok("ink!")
+        );
+        assert_eq!(input, &[7]);
+    }
+
+    #[test]
+    fn decode_borrowed_fails_for_invalid_input() {
+        let mut input: &[u8] = &[12, 1, 2];
+        assert!(<&[u8] as DecodeBorrowed>::decode_borrowed(&mut input).is_err());
+        let mut input: &[u8] = &[4, 0xFF];
+        assert!(<&str as DecodeBorrowed>::decode_borrowed(&mut input).is_err());
+    }
+}
@@ -14,13 +14,30 @@
 
 mod execution;
 mod info;
+mod input;
 mod type_check;
 
 pub use self::{
-    execution::deny_payment,
+    execution::{
+        acquire_reentrancy_lock,
+        deny_payment,
+        ensure_any_role,
+        ensure_internal_caller,
+        ensure_min_transferred_value,
+        is_plain_transfer,
+        refund_excess_endowment,
+        release_reentrancy_lock,
+        ConstructorScope,
+    },
     info::ContractCallBuilder,
+    input::{
+        DecodeBorrowed,
+        DeferredInput,
+        SelectorPrefixedInput,
+    },
     type_check::{
         DispatchInput,
         DispatchOutput,
+        PaginatedOutput,
     },
 };
@@ -67,3 +67,32 @@ where
 pub struct DispatchOutput<T>(T)
 where
     T: scale::Encode + 'static;
+
+/// Used to check if `T` is allowed as output of a `paginated` ink! message.
+///
+/// # Note
+///
+/// The output of a `paginated` ink! message must be a
+/// [`Page`](crate::pagination::Page).
+///
+/// # Example
+///
+/// This compiles since `(Vec<i32>, Option<Cursor>)` is a page.
+///
+/// ```
+/// # use ink::codegen::PaginatedOutput;
+/// # use ink::pagination::Cursor;
+/// const _: () = ink::codegen::utils::consume_type::<
+///     PaginatedOutput<(Vec<i32>, Option<Cursor>)>,
+/// >();
+/// ```
+///
+/// This fails to compile since `Vec<i32>` is missing the cursor.
+///
+/// ```compile_fail
+/// # use ink::codegen::PaginatedOutput;
+/// const _: () = ink::codegen::utils::consume_type::<PaginatedOutput<Vec<i32>>>();
+/// ```
+pub struct PaginatedOutput<T>(T)
+where
+    T: crate::pagination::Paginated;
@@ -0,0 +1,157 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// Parses the hex encoded metadata hash given by the `INK_METADATA_HASH`
+/// environment variable at build time.
+///
+/// Returns `None` if the environment variable is not set.
+///
+/// # Panics
+///
+/// If the value is not a hex encoded 32 bytes hash, optionally prefixed by `0x`.
+/// Since the function is evaluated at compile time this fails the build.
+pub const fn parse_metadata_hash(hex: Option<&str>) -> Option<[u8; 32]> {
+    let hex = match hex {
+        Some(hex) => hex.as_bytes(),
+        None => return None,
+    };
+    let offset = match hex {
+        [b'0', b'x', ..] => 2,
+        _ => 0,
+    };
+    if hex.len() != offset + 64 {
+        panic!("`INK_METADATA_HASH` must be a hex encoded 32 bytes hash")
+    }
+    let mut hash = [0u8; 32];
+    let mut n = 0;
+    while n < 32 {
+        hash[n] =
+            hex_digit(hex[offset + 2 * n]) << 4 | hex_digit(hex[offset + 2 * n + 1]);
+        n += 1;
+    }
+    Some(hash)
+}
+
+/// Returns the length of the `ink_metadata_hash` custom section holding `hash`.
+pub const fn metadata_hash_section_len(hash: Option<[u8; 32]>) -> usize {
+    match hash {
+        Some(_) => 32,
+        None => 0,
+    }
+}
+
+/// Returns the contents of the `ink_metadata_hash` custom section holding `hash`.
+///
+/// `N` is the [`metadata_hash_section_len`] of `hash`, so that the section is empty
+/// if the metadata hash is not known at build time.
+pub const fn metadata_hash_section<const N: usize>(hash: Option<[u8; 32]>) -> [u8; N] {
+    let mut section = [0u8; N];
+    if let Some(hash) = hash {
+        let mut n = 0;
+        while n < N {
+            section[n] = hash[n];
+            n += 1;
+        }
+    }
+    section
+}
+
+const fn hex_digit(digit: u8) -> u8 {
+    match digit {
+        b'0'..=b'9' => digit - b'0',
+        b'a'..=b'f' => digit - b'a' + 10,
+        b'A'..=b'F' => digit - b'A' + 10,
+        _ => panic!("`INK_METADATA_HASH` must be a hex encoded 32 bytes hash"),
+    }
+}
+
+/// Returns the hash of the contract metadata the contract was built with as
+/// `Option<[u8; 32]>`.
+///
+/// The hash is given by the `INK_METADATA_HASH` environment variable at build time,
+/// which is `None` if the variable is not set. Contracts configured with
+/// `#[ink::contract(embed_metadata_hash = true)]` additionally embed the hash into
+/// the `ink_metadata_hash` custom section of their Wasm blob, where clients read it
+/// from without calling the contract.
+///
+/// The hash is computed by `ink::metadata::verify::metadata_hash` from the
+/// metadata generated for the contract, so building a contract with its metadata
+/// hash takes two steps:
+///
+/// 1. Build the contract to generate its metadata.
+/// 2. Build the contract again with `INK_METADATA_HASH` set to the hex encoded
+///    hash of the metadata. The metadata does not change, since it does not depend
+///    on the Wasm blob.
+///
+/// # Example
+///
+/// ```
+/// #[ink::contract(embed_metadata_hash = true)]
+/// mod registered {
+///     #[ink(storage)]
+///     pub struct Registered {}
+///
+///     impl Registered {
+///         #[ink(constructor)]
+///         pub fn new() -> Self {
+///             Self {}
+///         }
+///
+///         /// Returns the hash of the metadata of the contract.
+///         #[ink(message)]
+///         pub fn metadata_hash(&self) -> Option<[u8; 32]> {
+///             ink::metadata_hash!()
+///         }
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! metadata_hash {
+    () => {{
+        // Evaluated at compile time, so that an invalid hash fails the build.
+        const METADATA_HASH: ::core::option::Option<[::core::primitive::u8; 32]> =
+            $crate::codegen::parse_metadata_hash(::core::option_env!(
+                "INK_METADATA_HASH"
+            ));
+        METADATA_HASH
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_metadata_hash_works() {
+        let hex = "01".repeat(32);
+        assert_eq!(parse_metadata_hash(None), None);
+        assert_eq!(parse_metadata_hash(Some(&hex)), Some([0x01; 32]));
+        assert_eq!(
+            parse_metadata_hash(Some(&format!("0x{}", "aB".repeat(32)))),
+            Some([0xAB; 32])
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "`INK_METADATA_HASH` must be a hex encoded 32 bytes hash")]
+    fn parse_metadata_hash_rejects_short_hash() {
+        parse_metadata_hash(Some("0x0101"));
+    }
+
+    #[test]
+    #[should_panic(expected = "`INK_METADATA_HASH` must be a hex encoded 32 bytes hash")]
+    fn parse_metadata_hash_rejects_invalid_digits() {
+        parse_metadata_hash(Some(&"zz".repeat(32)));
+    }
+}
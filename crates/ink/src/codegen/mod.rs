@@ -18,15 +18,30 @@ mod dispatch;
 mod env;
 mod event;
 mod implies_return;
+mod metadata_hash;
+#[cfg(feature = "std")]
+mod stability;
 mod trait_def;
 pub mod utils;
 
 pub use self::{
     dispatch::{
+        acquire_reentrancy_lock,
         deny_payment,
+        ensure_any_role,
+        ensure_internal_caller,
+        ensure_min_transferred_value,
+        is_plain_transfer,
+        refund_excess_endowment,
+        release_reentrancy_lock,
+        ConstructorScope,
         ContractCallBuilder,
+        DecodeBorrowed,
+        DeferredInput,
         DispatchInput,
         DispatchOutput,
+        PaginatedOutput,
+        SelectorPrefixedInput,
     },
     env::{
         Env,
@@ -40,6 +55,11 @@ pub use self::{
         RespectTopicLimit,
     },
     implies_return::ImpliesReturn,
+    metadata_hash::{
+        metadata_hash_section,
+        metadata_hash_section_len,
+        parse_metadata_hash,
+    },
     trait_def::{
         TraitCallBuilder,
         TraitCallForwarder,
@@ -48,3 +68,11 @@ pub use self::{
         TraitMessageSelector,
     },
 };
+
+#[cfg(feature = "std")]
+pub use self::stability::{
+    check_stability,
+    UPDATE_STABILITY_VAR,
+};
+#[cfg(feature = "std")]
+pub use crate::stability_test;
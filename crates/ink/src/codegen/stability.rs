@@ -0,0 +1,206 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ink_metadata::{
+    stability::StabilitySnapshot,
+    InkProject,
+};
+
+/// The environment variable which makes [`check_stability`] update the stored
+/// snapshot instead of checking it.
+pub const UPDATE_STABILITY_VAR: &str = "INK_UPDATE_STABILITY";
+
+/// Checks that the selectors, event signatures and storage keys of the contract
+/// described by `project` match the snapshot stored at `path`.
+///
+/// The snapshot is written to `path` if there is none yet or if the environment
+/// variable [`UPDATE_STABILITY_VAR`] is set.
+///
+/// # Panics
+///
+/// - If the snapshot of the contract differs from the stored snapshot.
+/// - If the arguments of a message with a frozen ABI changed without bumping its
+///   `abi_version`, even if [`UPDATE_STABILITY_VAR`] is set.
+pub fn check_stability(project: &InkProject, path: &str) {
+    check_snapshot(&StabilitySnapshot::new(project), path)
+}
+
+fn check_snapshot(snapshot: &StabilitySnapshot, path: &str) {
+    let stored = std::fs::read_to_string(path)
+        .ok()
+        .map(|stored| StabilitySnapshot::parse(&stored));
+    if let Some(stored) = &stored {
+        check_frozen_abis(snapshot, stored, path)
+    }
+    let stored = match stored {
+        Some(stored) if std::env::var_os(UPDATE_STABILITY_VAR).is_none() => stored,
+        _ => {
+            std::fs::write(path, snapshot.to_string()).unwrap_or_else(|error| {
+                panic!(
+                    "writing the stability snapshot to {} failed: {}",
+                    path, error
+                )
+            });
+            return
+        }
+    };
+    let (removed, added) = snapshot.changes(&stored);
+    if removed.is_empty() && added.is_empty() {
+        return
+    }
+    let mut changes = String::new();
+    for entry in removed {
+        changes.push_str(&format!("\n- {}", entry));
+    }
+    for entry in added {
+        changes.push_str(&format!("\n+ {}", entry));
+    }
+    panic!(
+        "the selectors, event signatures or storage keys of the contract changed \
+         compared to {}:{}\n\nrerun the test with `{}=1` if the changes are intended",
+        path, changes, UPDATE_STABILITY_VAR,
+    )
+}
+
+fn check_frozen_abis(
+    snapshot: &StabilitySnapshot,
+    stored: &StabilitySnapshot,
+    path: &str,
+) {
+    let changes = snapshot.frozen_abi_changes(stored);
+    if changes.is_empty() {
+        return
+    }
+    let mut entries = String::new();
+    for (previous, current) in changes {
+        entries.push_str(&format!("\n- {}\n+ {}", previous, current));
+    }
+    panic!(
+        "the arguments of messages with a frozen ABI changed compared to {}:{}\n\n\
+         bump the `abi_version` of the messages to change their arguments",
+        path, entries,
+    )
+}
+
+/// Generates a unit test which fails if the selectors, event signatures or storage
+/// keys of the contract change.
+///
+/// On its first run the test stores a snapshot of them in the file at the given
+/// path relative to the manifest of the crate, `ink-stability.txt` by default,
+/// which should be committed. Afterwards it fails whenever the snapshot of the
+/// contract differs from the stored one, e.g. since a message or a storage field
+/// was renamed by a refactoring. Intended changes are stored by running the test
+/// with the `INK_UPDATE_STABILITY` environment variable set, except for changed
+/// arguments of messages with a frozen `abi_version`, which always fail the test.
+///
+/// The test requires the metadata of the contract, so the crate must not be
+/// compiled with the `ink-as-dependency` feature. For contracts with an `entry`
+/// feature it only compiles with the feature enabled.
+///
+/// # Example
+///
+/// ```ignore
+/// #[ink::contract]
+/// mod flipper {
+///     // ...
+/// }
+///
+/// ink::codegen::stability_test!();
+/// ```
+#[macro_export]
+macro_rules! stability_test {
+    () => {
+        $crate::codegen::stability_test!("ink-stability.txt");
+    };
+    ( $path:literal ) => {
+        #[cfg(test)]
+        #[test]
+        fn ink_stability_test() {
+            extern "Rust" {
+                fn __ink_generate_metadata() -> $crate::metadata::InkProject;
+            }
+            // SAFETY: `#[ink::contract]` defines the function with this signature.
+            let project = unsafe { __ink_generate_metadata() };
+            $crate::codegen::check_stability(
+                &project,
+                ::core::concat!(::core::env!("CARGO_MANIFEST_DIR"), "/", $path),
+            );
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot_path(name: &str) -> String {
+        let path = std::env::temp_dir().join(format!(
+            "ink-stability-{}-{}.txt",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn check_snapshot_stores_and_accepts_unchanged_snapshot() {
+        let path = snapshot_path("unchanged");
+        let snapshot = StabilitySnapshot::parse(
+            "constructor new 0x9bae9d5e\nmessage flip 0x633aa551\n",
+        );
+
+        check_snapshot(&snapshot, &path);
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "constructor new 0x9bae9d5e\nmessage flip 0x633aa551\n"
+        );
+        check_snapshot(&snapshot, &path);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn check_snapshot_rejects_changed_snapshot() {
+        let path = snapshot_path("changed");
+        check_snapshot(&StabilitySnapshot::parse("message flip 0x633aa551"), &path);
+
+        let result = std::panic::catch_unwind(|| {
+            check_snapshot(&StabilitySnapshot::parse("message flop 0x1e2a8d2b"), &path)
+        });
+        let _ = std::fs::remove_file(&path);
+        let error = result.unwrap_err();
+        let message = error.downcast_ref::<String>().unwrap();
+        assert!(message.contains("\n- message flip 0x633aa551"));
+        assert!(message.contains("\n+ message flop 0x1e2a8d2b"));
+    }
+
+    #[test]
+    fn check_snapshot_rejects_changed_frozen_abi() {
+        let path = snapshot_path("frozen");
+        check_snapshot(
+            &StabilitySnapshot::parse("abi transfer v1(u128, bool)"),
+            &path,
+        );
+
+        let result = std::panic::catch_unwind(|| {
+            check_snapshot(&StabilitySnapshot::parse("abi transfer v1(bool)"), &path)
+        });
+        let _ = std::fs::remove_file(&path);
+        let error = result.unwrap_err();
+        let message = error.downcast_ref::<String>().unwrap();
+        assert!(
+            message.contains("\n- abi transfer v1(u128, bool)\n+ abi transfer v1(bool)")
+        );
+    }
+}
@@ -16,8 +16,10 @@
 
 mod identity_type;
 mod same_type;
+mod unique_selectors;
 
 pub use self::{
     identity_type::consume_type,
     same_type::IsSameType,
+    unique_selectors::assert_unique_selectors,
 };
@@ -0,0 +1,84 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// Asserts that all `selectors` are unique.
+///
+/// The `labels` name the message of the selector at the same index.
+///
+/// # Note
+///
+/// This is evaluated in a constant context by the ink! codegen, so that overlapping
+/// selectors of messages, including the ones from ink! trait definitions, fail to
+/// compile with an error naming both messages.
+///
+/// # Usage: Compiles
+///
+/// ```
+/// # use ink::codegen::utils::assert_unique_selectors;
+/// const _: () = assert_unique_selectors(&[0x01, 0x02], &["first", "second"]);
+/// ```
+///
+/// # Usage: Compile Error
+///
+/// ```compile_fail
+/// # use ink::codegen::utils::assert_unique_selectors;
+/// const _: () = assert_unique_selectors(&[0x01, 0x01], &["first", "second"]);
+/// ```
+pub const fn assert_unique_selectors<const N: usize>(
+    selectors: &[u32; N],
+    labels: &[&str; N],
+) {
+    let mut second = 0;
+    while second < N {
+        let mut first = 0;
+        while first < second {
+            if selectors[first] == selectors[second] {
+                overlapping_selectors(labels[first], labels[second])
+            }
+            first += 1;
+        }
+        second += 1;
+    }
+}
+
+/// Panics with an error naming the messages with overlapping selectors.
+///
+/// The error is assembled in a buffer since formatting is not available in a
+/// constant context. Overlong labels are truncated.
+const fn overlapping_selectors(first: &str, second: &str) -> ! {
+    let parts: [&[u8]; 5] = [
+        b"encountered ink! messages with overlapping selectors: `",
+        first.as_bytes(),
+        b"` and `",
+        second.as_bytes(),
+        b"`",
+    ];
+    let mut buffer = [0x00; 512];
+    let mut len = 0;
+    let mut part = 0;
+    while part < parts.len() {
+        let mut index = 0;
+        while index < parts[part].len() && len < buffer.len() {
+            buffer[len] = parts[part][index];
+            len += 1;
+            index += 1;
+        }
+        part += 1;
+    }
+    let (message, _) = buffer.split_at(len);
+    match core::str::from_utf8(message) {
+        Ok(message) => panic!("{}", message),
+        Err(_) => panic!("encountered ink! messages with overlapping selectors"),
+    }
+}
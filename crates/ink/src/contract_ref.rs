@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::reflect::ContractEnv;
 use ink_env::Environment;
 
 /// Implemented by contracts that are compiled as dependencies.
@@ -24,3 +25,208 @@ where
     /// Returns the underlying account identifier of the instantiated contract.
     fn to_account_id(&self) -> <T as Environment>::AccountId;
 }
+
+/// The error returned by the `try_*` methods of contract references.
+///
+/// Distinguishes the error returned by the called ink! message itself from the
+/// errors that might occur while performing the cross-contract call.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CalleeError<E> {
+    /// The cross-contract call failed in the environment, e.g. the callee trapped.
+    Env(ink_env::Error),
+    /// The callee failed to dispatch the message, e.g. due to an unknown selector.
+    Lang(ink_primitives::LangError),
+    /// The called message returned its own error.
+    Callee(E),
+}
+
+/// The error returned by [`WeakContractRef`] if the referenced contract no longer
+/// exists, e.g. because it terminated itself.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CalleeGone;
+
+/// The error returned by the generated `at_code_hash` constructors of contract
+/// references if the contract does not run the expected code.
+///
+/// # Example
+///
+/// ```
+/// #[ink::contract]
+/// mod adder {
+///     #[ink(storage)]
+///     pub struct Adder {
+///         value: i32,
+///     }
+///
+///     impl Adder {
+///         #[ink(constructor)]
+///         pub fn new() -> Self {
+///             Self { value: 0 }
+///         }
+///
+///         #[ink(message)]
+///         pub fn inc(&mut self, by: i32) -> i32 {
+///             self.value += by;
+///             self.value
+///         }
+///     }
+/// }
+///
+/// use adder::{
+///     Adder,
+///     AdderRef,
+/// };
+/// use ink::{
+///     env::DefaultEnvironment,
+///     RebindError,
+/// };
+///
+/// ink::env::test::run_test::<DefaultEnvironment, _>(|accounts| {
+///     ink::off_chain::register_contract(accounts.bob, Adder::new);
+///     ink::off_chain::register_contract(accounts.charlie, Adder::new);
+///     let code_hash = ink::env::code_hash::<DefaultEnvironment>(&accounts.bob)?;
+///
+///     let mut adder = AdderRef::at_code_hash(accounts.bob, code_hash).unwrap();
+///     assert_eq!(adder.inc(2), 2);
+///     // Points the reference to the upgraded adder.
+///     adder = AdderRef::at_code_hash(accounts.charlie, code_hash).unwrap();
+///     assert_eq!(adder.inc(2), 2);
+///
+///     assert_eq!(
+///         AdderRef::at_code_hash(accounts.django, code_hash),
+///         Err(RebindError::CalleeGone)
+///     );
+///     assert_eq!(
+///         AdderRef::at_code_hash(accounts.bob, [0x00; 32].into()),
+///         Err(RebindError::CodeHashMismatch)
+///     );
+///     Ok(())
+/// })
+/// .unwrap();
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum RebindError {
+    /// No contract exists at the given account.
+    CalleeGone,
+    /// The contract at the given account runs another code than expected.
+    CodeHashMismatch,
+}
+
+/// A reference to another contract which might no longer exist.
+///
+/// Contract references, such as the generated `AccumulatorRef`, assume that the
+/// referenced contract exists forever and calls to it trap otherwise. This wrapper
+/// checks the liveness of the contract via its code hash before handing out the
+/// reference, so that a terminated contract results in [`CalleeGone`] instead.
+///
+/// # Example
+///
+/// ```
+/// #[ink::contract]
+/// mod accumulator {
+///     #[ink(storage)]
+///     pub struct Accumulator {
+///         value: i32,
+///     }
+///
+///     impl Accumulator {
+///         #[ink(constructor)]
+///         pub fn new() -> Self {
+///             Self { value: 0 }
+///         }
+///
+///         #[ink(message)]
+///         pub fn inc(&mut self, by: i32) -> i32 {
+///             self.value += by;
+///             self.value
+///         }
+///
+///         #[ink(message)]
+///         pub fn terminate(&mut self) {
+///             self.env().terminate_contract(self.env().caller())
+///         }
+///     }
+/// }
+///
+/// use accumulator::{
+///     Accumulator,
+///     AccumulatorRef,
+/// };
+/// use ink::{
+///     env::{
+///         call::FromAccountId,
+///         DefaultEnvironment,
+///     },
+///     CalleeGone,
+///     WeakContractRef,
+/// };
+///
+/// ink::env::test::run_test::<DefaultEnvironment, _>(|accounts| {
+///     ink::off_chain::register_contract(accounts.bob, Accumulator::new);
+///     let mut accumulator = WeakContractRef::new(
+///         <AccumulatorRef as FromAccountId<DefaultEnvironment>>::from_account_id(
+///             accounts.bob,
+///         ),
+///     );
+///     assert_eq!(accumulator.get_mut().map(|acc| acc.inc(2)), Ok(2));
+///     // A terminating contract returns no output, hence the output is not decoded.
+///     let _ = accumulator.get().unwrap().call_builder().terminate().fire();
+///     assert!(!accumulator.exists());
+///     assert_eq!(accumulator.get_mut().map(|acc| acc.inc(2)), Err(CalleeGone));
+///     Ok(())
+/// })
+/// .unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, crate::storage::traits::StorageLayout)
+)]
+pub struct WeakContractRef<C> {
+    contract: C,
+}
+
+impl<C> WeakContractRef<C> {
+    /// Creates a weak reference from the reference to a contract.
+    pub fn new(contract: C) -> Self {
+        Self { contract }
+    }
+
+    /// Returns the underlying contract reference without checking its liveness.
+    pub fn into_inner(self) -> C {
+        self.contract
+    }
+}
+
+impl<C> WeakContractRef<C>
+where
+    C: ContractEnv + ToAccountId<<C as ContractEnv>::Env>,
+{
+    /// Returns `true` if the referenced contract still exists.
+    pub fn exists(&self) -> bool {
+        ink_env::code_hash::<C::Env>(&self.contract.to_account_id()).is_ok()
+    }
+
+    /// Returns the contract reference if the referenced contract still exists.
+    pub fn get(&self) -> Result<&C, CalleeGone> {
+        if !self.exists() {
+            return Err(CalleeGone)
+        }
+        Ok(&self.contract)
+    }
+
+    /// Returns the mutable contract reference if the referenced contract still exists.
+    pub fn get_mut(&mut self) -> Result<&mut C, CalleeGone> {
+        if !self.exists() {
+            return Err(CalleeGone)
+        }
+        Ok(&mut self.contract)
+    }
+}
+
+impl<C> From<C> for WeakContractRef<C> {
+    fn from(contract: C) -> Self {
+        Self::new(contract)
+    }
+}
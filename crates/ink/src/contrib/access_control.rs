@@ -0,0 +1,284 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Role based access control for ink! messages.
+//!
+//! Checking the caller by hand in every privileged message is easy to get wrong.
+//! Instead, calls to ink! messages flagged as `only(roles = ["admin", "operator"])`
+//! are reverted with [`LangError::CallerNotAllowed`](ink_primitives::LangError)
+//! unless the caller has any of the given roles. Roles are identified by the
+//! BLAKE2x256 hash of their name, see [`role`].
+//!
+//! The roles granted to the accounts are stored in an [`AccessControl`]. ink! does
+//! not add it to the storage of the contract: the contract declares it as a field
+//! of its storage struct and gives access to it by implementing
+//! [`AccessControlled`]. Contracts using `only` then get the generated
+//! `grant_role` and `revoke_role` messages, which can only be called by accounts
+//! having the [`ADMIN_ROLE`], i.e. the role named `"admin"`.
+//!
+//! # Note
+//!
+//! - The accounts are those of the environment of the contract. Contracts using a
+//!   custom environment declare an `AccessControl<Environment>` and implement
+//!   `AccessControlled<Environment>`.
+//! - The roles of an account are only checked for calls dispatched to the
+//!   contract. Calling the method of an `only` message from Rust code, e.g. from
+//!   other messages, does not check them.
+//!
+//! # Example
+//!
+//! ```
+//! #[ink::contract]
+//! mod guarded {
+//!     use ink::contrib::{
+//!         AccessControl,
+//!         AccessControlled,
+//!     };
+//!
+//!     #[ink(storage)]
+//!     pub struct Guarded {
+//!         access_control: AccessControl,
+//!         paused: bool,
+//!     }
+//!
+//!     impl AccessControlled for Guarded {
+//!         fn access_control(&mut self) -> &mut AccessControl {
+//!             &mut self.access_control
+//!         }
+//!     }
+//!
+//!     impl Guarded {
+//!         #[ink(constructor)]
+//!         pub fn new() -> Self {
+//!             Self {
+//!                 access_control: AccessControl::with_admin(Self::env().caller()),
+//!                 paused: false,
+//!             }
+//!         }
+//!
+//!         /// Pauses the contract, callable by admins and operators only.
+//!         #[ink(message, only(roles = ["admin", "operator"]))]
+//!         pub fn pause(&mut self) {
+//!             self.paused = true;
+//!         }
+//!
+//!         #[ink(message)]
+//!         pub fn paused(&self) -> bool {
+//!             self.paused
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::env::{
+    DefaultEnvironment,
+    Environment,
+};
+use ink_storage::Mapping;
+
+/// The identifier of a role, the BLAKE2x256 hash of its name.
+pub type Role = [u8; 32];
+
+/// The role allowed to grant and revoke roles, i.e. `role("admin")`.
+pub const ADMIN_ROLE: Role = [
+    0x1A, 0xA6, 0x6B, 0x39, 0x3B, 0x8A, 0xB4, 0x7D, 0x24, 0x89, 0xDE, 0x43, 0x01, 0x6F,
+    0x5F, 0x39, 0xCA, 0x8C, 0x24, 0x12, 0x73, 0x7C, 0x6C, 0xDC, 0x5F, 0x7B, 0xC3, 0x3D,
+    0xE2, 0xF6, 0xDB, 0xED,
+];
+
+/// Returns the identifier of the role with the given `name`.
+///
+/// This is the identifier used for the role names of `only(roles = [..])`.
+pub fn role(name: &str) -> Role {
+    let mut output =
+        <ink_env::hash::Blake2x256 as ink_env::hash::HashOutput>::Type::default();
+    ink_env::hash_bytes::<ink_env::hash::Blake2x256>(name.as_bytes(), &mut output);
+    output
+}
+
+/// The errors of the [`AccessControl`].
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum AccessControlError {
+    /// Returned if the caller does not have the role required for the operation.
+    MissingRole,
+}
+
+/// The storage of the roles granted to the accounts calling a contract.
+///
+/// The accounts are those of the environment `E` of the contract.
+///
+/// See the [module documentation](crate::contrib::access_control) for more details.
+#[crate::storage_item]
+#[derive(Debug)]
+pub struct AccessControl<E: Environment = DefaultEnvironment> {
+    roles: Mapping<(Role, E::AccountId), ()>,
+}
+
+impl<E: Environment> Default for AccessControl<E> {
+    fn default() -> Self {
+        Self {
+            roles: Default::default(),
+        }
+    }
+}
+
+impl<E> AccessControl<E>
+where
+    E: Environment,
+    E::AccountId: scale::EncodeLike,
+{
+    /// Creates an access control without any granted roles.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates an access control granting the [`ADMIN_ROLE`] to `admin`.
+    pub fn with_admin(admin: E::AccountId) -> Self {
+        let mut access_control = Self::new();
+        access_control.set_role(ADMIN_ROLE, admin, true);
+        access_control
+    }
+
+    /// Returns `true` if `account` has `role`.
+    pub fn has_role(&self, role: Role, account: E::AccountId) -> bool {
+        self.roles.contains((role, account))
+    }
+
+    /// Returns `true` if `account` has any of the given `roles`.
+    pub fn has_any_role(&self, roles: &[Role], account: E::AccountId) -> bool {
+        roles
+            .iter()
+            .any(|role| self.has_role(*role, account.clone()))
+    }
+
+    /// Ensures that the caller of the contract has `role`.
+    ///
+    /// # Errors
+    ///
+    /// If the caller does not have `role`.
+    pub fn ensure_caller_has_role(&self, role: Role) -> Result<(), AccessControlError> {
+        if !self.has_role(role, ink_env::caller::<E>()) {
+            return Err(AccessControlError::MissingRole)
+        }
+        Ok(())
+    }
+
+    /// Grants `role` to `account` on behalf of the caller of the contract.
+    ///
+    /// # Errors
+    ///
+    /// If the caller does not have the [`ADMIN_ROLE`].
+    pub fn grant_role(
+        &mut self,
+        role: Role,
+        account: E::AccountId,
+    ) -> Result<(), AccessControlError> {
+        self.ensure_caller_has_role(ADMIN_ROLE)?;
+        self.set_role(role, account, true);
+        Ok(())
+    }
+
+    /// Revokes `role` from `account` on behalf of the caller of the contract.
+    ///
+    /// # Errors
+    ///
+    /// If the caller does not have the [`ADMIN_ROLE`].
+    pub fn revoke_role(
+        &mut self,
+        role: Role,
+        account: E::AccountId,
+    ) -> Result<(), AccessControlError> {
+        self.ensure_caller_has_role(ADMIN_ROLE)?;
+        self.set_role(role, account, false);
+        Ok(())
+    }
+
+    /// Grants or revokes `role` of `account` without checking the caller.
+    ///
+    /// # Note
+    ///
+    /// This is meant for constructors and for contracts implementing their own
+    /// rules of who may grant roles.
+    pub fn set_role(&mut self, role: Role, account: E::AccountId, granted: bool) {
+        if granted {
+            self.roles.insert((role, account), &());
+        } else {
+            self.roles.remove((role, account));
+        }
+    }
+}
+
+/// Implemented by contracts with `only` ink! messages to give access to their
+/// [`AccessControl`].
+///
+/// `E` is the environment of the contract.
+pub trait AccessControlled<E: Environment = DefaultEnvironment> {
+    /// Returns the access control of the contract.
+    fn access_control(&mut self) -> &mut AccessControl<E>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_test(
+        f: impl FnOnce(AccessControl, ink_env::test::DefaultAccounts<DefaultEnvironment>),
+    ) {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|accounts| {
+            ink_env::test::set_caller::<DefaultEnvironment>(accounts.alice);
+            f(AccessControl::with_admin(accounts.alice), accounts);
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn admin_role_works() {
+        assert_eq!(role("admin"), ADMIN_ROLE);
+        assert_ne!(role("operator"), ADMIN_ROLE);
+    }
+
+    #[test]
+    fn grant_and_revoke_role_works() {
+        run_test(|mut access_control, accounts| {
+            let operator = role("operator");
+            assert!(access_control.has_role(ADMIN_ROLE, accounts.alice));
+            assert!(!access_control.has_role(operator, accounts.bob));
+            assert_eq!(access_control.grant_role(operator, accounts.bob), Ok(()));
+            assert!(access_control.has_role(operator, accounts.bob));
+            assert!(access_control.has_any_role(&[ADMIN_ROLE, operator], accounts.bob));
+            assert!(!access_control.has_role(ADMIN_ROLE, accounts.bob));
+            assert_eq!(access_control.revoke_role(operator, accounts.bob), Ok(()));
+            assert!(!access_control.has_role(operator, accounts.bob));
+        })
+    }
+
+    #[test]
+    fn only_admin_grants_roles() {
+        run_test(|mut access_control, accounts| {
+            let operator = role("operator");
+            ink_env::test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                access_control.grant_role(operator, accounts.bob),
+                Err(AccessControlError::MissingRole)
+            );
+            assert_eq!(
+                access_control.revoke_role(ADMIN_ROLE, accounts.alice),
+                Err(AccessControlError::MissingRole)
+            );
+            assert!(access_control.has_role(ADMIN_ROLE, accounts.alice));
+        })
+    }
+}
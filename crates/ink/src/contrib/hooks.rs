@@ -0,0 +1,345 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A registry of contracts notified about the events of another contract.
+//!
+//! Observing contracts register a [`Hook`], i.e. a contract account and the
+//! selector of the message to call, in the [`Hooks`] of the observed contract.
+//! The observed contract then calls all registered hooks via [`Hooks::notify_all`].
+//!
+//! Every call is made with the gas limit of the registry and without allowing the
+//! callee to call back into the observed contract. A failing hook does not revert
+//! the notification: the remaining hooks are still called and the failed hooks are
+//! returned to the contract, which decides whether to e.g. unregister them. The
+//! number of hooks is bounded, so that the gas of a notification is bounded too.
+//!
+//! # Example
+//!
+//! ```
+//! #[ink::contract]
+//! mod token {
+//!     use ink::{
+//!         contrib::{
+//!             Hook,
+//!             Hooks,
+//!             HooksError,
+//!         },
+//!         prelude::vec::Vec,
+//!     };
+//!
+//!     #[ink(storage)]
+//!     pub struct Token {
+//!         hooks: Hooks,
+//!     }
+//!
+//!     impl Token {
+//!         #[ink(constructor)]
+//!         pub fn new() -> Self {
+//!             let hooks = Hooks::new(8, 100_000_000_000)
+//!                 .unwrap_or_else(|error| panic!("invalid hooks: {:?}", error));
+//!             Self { hooks }
+//!         }
+//!
+//!         /// Calls `on_transfer(from, to, value)` of the caller on every transfer.
+//!         #[ink(message)]
+//!         pub fn subscribe(&mut self) -> Result<(), HooksError> {
+//!             self.hooks.register(Hook::new(
+//!                 self.env().caller(),
+//!                 ink::selector_bytes!("on_transfer"),
+//!             ))
+//!         }
+//!
+//!         #[ink(message)]
+//!         pub fn unsubscribe(&mut self) -> Result<(), HooksError> {
+//!             self.hooks.unregister(&Hook::new(
+//!                 self.env().caller(),
+//!                 ink::selector_bytes!("on_transfer"),
+//!             ))
+//!         }
+//!
+//!         #[ink(message)]
+//!         pub fn transfer(&mut self, to: AccountId, value: Balance) -> Vec<Hook> {
+//!             // ...
+//!             self.hooks.notify_all(&(self.env().caller(), to, value))
+//!         }
+//!     }
+//! }
+//! ```
+
+use super::AccountId;
+use crate::env::{
+    call::{
+        build_call,
+        Call,
+        ExecutionInput,
+        Selector,
+    },
+    CallFlags,
+    DefaultEnvironment,
+    Gas,
+};
+use ink_prelude::vec::Vec;
+use ink_storage::traits::{
+    AutoKey,
+    StorageKey,
+};
+
+/// The errors of the [`Hooks`].
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum HooksError {
+    /// Returned if the maximum number of hooks or the gas limit is zero.
+    InvalidLimit,
+    /// Returned if the hook is already registered.
+    AlreadyRegistered,
+    /// Returned if the hook is not registered.
+    NotRegistered,
+    /// Returned if the maximum number of hooks is registered already.
+    TooManyHooks,
+}
+
+/// A message of a contract called by [`Hooks::notify_all`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, crate::storage::traits::StorageLayout)
+)]
+pub struct Hook {
+    /// The account of the called contract.
+    callee: AccountId,
+    /// The selector of the called message.
+    selector: [u8; 4],
+}
+
+impl Hook {
+    /// Creates a hook calling the message with `selector` of `callee`.
+    pub fn new(callee: AccountId, selector: [u8; 4]) -> Self {
+        Self { callee, selector }
+    }
+
+    /// Returns the account of the called contract.
+    pub fn callee(&self) -> AccountId {
+        self.callee
+    }
+
+    /// Returns the selector of the called message.
+    pub fn selector(&self) -> [u8; 4] {
+        self.selector
+    }
+}
+
+/// The storage of a registry of hooks with the logic to notify them.
+///
+/// See the [module documentation](crate::contrib::hooks) for more details.
+#[crate::storage_item]
+#[derive(Debug)]
+pub struct Hooks<KEY: StorageKey = AutoKey> {
+    // All hooks are read by every notification and bounded by `max_hooks`.
+    #[ink(bounded)]
+    hooks: Vec<Hook>,
+    max_hooks: u32,
+    gas_limit: Gas,
+}
+
+impl<KEY: StorageKey> Hooks<KEY> {
+    /// Creates a registry of at most `max_hooks` hooks, which are each called with
+    /// `gas_limit`.
+    ///
+    /// # Errors
+    ///
+    /// If `max_hooks` or `gas_limit` is zero.
+    pub fn new(max_hooks: u32, gas_limit: Gas) -> Result<Self, HooksError> {
+        if max_hooks == 0 || gas_limit == 0 {
+            return Err(HooksError::InvalidLimit)
+        }
+        Ok(Self {
+            hooks: Vec::new(),
+            max_hooks,
+            gas_limit,
+        })
+    }
+
+    /// Returns the registered hooks in the order they are notified.
+    pub fn hooks(&self) -> &[Hook] {
+        &self.hooks
+    }
+
+    /// Returns the maximum number of registered hooks.
+    pub fn max_hooks(&self) -> u32 {
+        self.max_hooks
+    }
+
+    /// Returns the gas limit of the call of every hook.
+    pub fn gas_limit(&self) -> Gas {
+        self.gas_limit
+    }
+
+    /// Returns `true` if `hook` is registered.
+    pub fn is_registered(&self, hook: &Hook) -> bool {
+        self.hooks.contains(hook)
+    }
+
+    /// Registers `hook`, which is notified after all already registered hooks.
+    ///
+    /// # Errors
+    ///
+    /// - If `hook` is already registered.
+    /// - If the maximum number of hooks is registered already.
+    pub fn register(&mut self, hook: Hook) -> Result<(), HooksError> {
+        if self.is_registered(&hook) {
+            return Err(HooksError::AlreadyRegistered)
+        }
+        if self.hooks.len() >= self.max_hooks as usize {
+            return Err(HooksError::TooManyHooks)
+        }
+        self.hooks.push(hook);
+        Ok(())
+    }
+
+    /// Unregisters `hook`.
+    ///
+    /// # Errors
+    ///
+    /// If `hook` is not registered.
+    pub fn unregister(&mut self, hook: &Hook) -> Result<(), HooksError> {
+        let index = self
+            .hooks
+            .iter()
+            .position(|registered| registered == hook)
+            .ok_or(HooksError::NotRegistered)?;
+        self.hooks.remove(index);
+        Ok(())
+    }
+
+    /// Calls every registered hook with `args` as the arguments of its message.
+    ///
+    /// Returns the hooks whose call failed or was reverted by the callee.
+    ///
+    /// # Note
+    ///
+    /// The return values of the called messages are ignored, so that a hook can't
+    /// make the notification fail by returning undecodable data.
+    pub fn notify_all<Args>(&self, args: &Args) -> Vec<Hook>
+    where
+        Args: scale::Encode,
+    {
+        self.hooks
+            .iter()
+            .filter(|hook| self.notify(hook, args).is_err())
+            .copied()
+            .collect()
+    }
+
+    fn notify<Args>(&self, hook: &Hook, args: &Args) -> Result<(), crate::env::Error>
+    where
+        Args: scale::Encode,
+    {
+        build_call::<DefaultEnvironment>()
+            .call_type(Call::new().callee(hook.callee).gas_limit(self.gas_limit))
+            .call_flags(CallFlags::default().set_allow_reentry(false))
+            .exec_input(ExecutionInput::new(Selector::new(hook.selector)).push_arg(args))
+            .returns::<()>()
+            .fire()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::RefCell;
+
+    std::thread_local! {
+        /// The inputs of the calls to the observers.
+        static CALLS: RefCell<Vec<Vec<u8>>> = const { RefCell::new(Vec::new()) };
+    }
+
+    /// An observer recording its calls.
+    fn observer(input: &[u8]) {
+        CALLS.with(|calls| calls.borrow_mut().push(input.to_vec()));
+    }
+
+    /// An observer which always traps.
+    fn trapping_observer(_input: &[u8]) {
+        panic!("observer trapped")
+    }
+
+    const OBSERVER: [u8; 32] = [0xFE; 32];
+    const TRAPPING_OBSERVER: [u8; 32] = [0xFF; 32];
+    const SELECTOR: [u8; 4] = [0xCA, 0xFE, 0xBA, 0xBE];
+
+    fn run_test(f: impl FnOnce(Hooks)) {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|_| {
+            ink_env::test::register_contract::<DefaultEnvironment>(
+                OBSERVER.into(),
+                observer,
+            );
+            ink_env::test::register_contract::<DefaultEnvironment>(
+                TRAPPING_OBSERVER.into(),
+                trapping_observer,
+            );
+            CALLS.with(|calls| calls.borrow_mut().clear());
+            f(Hooks::new(2, 1_000_000).unwrap());
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn new_fails_for_invalid_limits() {
+        assert_eq!(
+            Hooks::<AutoKey>::new(0, 1_000_000).err(),
+            Some(HooksError::InvalidLimit)
+        );
+        assert_eq!(
+            Hooks::<AutoKey>::new(2, 0).err(),
+            Some(HooksError::InvalidLimit)
+        );
+    }
+
+    #[test]
+    fn registration_works() {
+        run_test(|mut hooks| {
+            let hook = Hook::new(OBSERVER.into(), SELECTOR);
+            let other = Hook::new(OBSERVER.into(), [0x00; 4]);
+            assert_eq!(hooks.register(hook), Ok(()));
+            assert_eq!(hooks.register(hook), Err(HooksError::AlreadyRegistered));
+            assert_eq!(hooks.register(other), Ok(()));
+            assert_eq!(
+                hooks.register(Hook::new(TRAPPING_OBSERVER.into(), SELECTOR)),
+                Err(HooksError::TooManyHooks)
+            );
+            assert_eq!(hooks.hooks(), &[hook, other]);
+            assert_eq!(hooks.unregister(&hook), Ok(()));
+            assert_eq!(hooks.unregister(&hook), Err(HooksError::NotRegistered));
+            assert!(!hooks.is_registered(&hook));
+            assert_eq!(hooks.hooks(), &[other]);
+        })
+    }
+
+    #[test]
+    fn notify_all_collects_failures() {
+        run_test(|mut hooks| {
+            let failing = Hook::new(TRAPPING_OBSERVER.into(), SELECTOR);
+            hooks.register(failing).unwrap();
+            hooks
+                .register(Hook::new(OBSERVER.into(), SELECTOR))
+                .unwrap();
+
+            assert_eq!(hooks.notify_all(&(7u8, true)), vec![failing]);
+            CALLS.with(|calls| {
+                assert_eq!(*calls.borrow(), vec![vec![0xCA, 0xFE, 0xBA, 0xBE, 7, 1]])
+            });
+        })
+    }
+}
@@ -0,0 +1,75 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Ready-made building blocks for common smart contract patterns.
+//!
+//! Every building block is a storage item implementing the logic of the pattern.
+//! Contracts embed it into their storage and expose its functionality through
+//! their own messages, which leaves emitting events and access control beyond the
+//! pattern to the contract.
+//!
+//! # Note
+//!
+//! The building blocks use the `AccountId`, `Balance` and `Timestamp` types of the
+//! [`DefaultEnvironment`](ink_env::DefaultEnvironment).
+
+pub mod access_control;
+pub mod hooks;
+pub mod multisig;
+pub mod ownable;
+pub mod timelock;
+
+pub use self::{
+    access_control::{
+        role,
+        AccessControl,
+        AccessControlError,
+        AccessControlled,
+        Role,
+        ADMIN_ROLE,
+    },
+    hooks::{
+        Hook,
+        Hooks,
+        HooksError,
+    },
+    multisig::{
+        CallRequest,
+        Multisig,
+        MultisigError,
+        RequestId,
+    },
+    ownable::{
+        Ownable2Step,
+        OwnableError,
+        Owned,
+    },
+    timelock::{
+        Operation,
+        OperationId,
+        Timelock,
+        TimelockError,
+        Timelocked,
+    },
+};
+/// Derives [`Owned`] for the storage struct of a contract, see
+/// [`ownable`](crate::contrib::ownable).
+pub use ink_macro::Ownable2Step;
+
+/// The account identifier type used by the building blocks.
+pub type AccountId = <ink_env::DefaultEnvironment as ink_env::Environment>::AccountId;
+/// The balance type used by the building blocks.
+pub type Balance = <ink_env::DefaultEnvironment as ink_env::Environment>::Balance;
+/// The timestamp type used by the building blocks.
+pub type Timestamp = <ink_env::DefaultEnvironment as ink_env::Environment>::Timestamp;
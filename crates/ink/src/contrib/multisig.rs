@@ -0,0 +1,582 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A multisig wallet executing contract calls confirmed by a threshold of owners.
+//!
+//! Any owner submits a [`CallRequest`] to the [`Multisig`], which is then confirmed
+//! by the other owners. Once the number of confirmations reaches the threshold
+//! anyone can execute the call. The call is made by the contract embedding the
+//! multisig, transferring the requested value from its balance.
+//!
+//! # Example
+//!
+//! ```
+//! #[ink::contract]
+//! mod wallet {
+//!     use ink::{
+//!         contrib::{
+//!             CallRequest,
+//!             Multisig,
+//!             MultisigError,
+//!             RequestId,
+//!         },
+//!         prelude::vec::Vec,
+//!     };
+//!
+//!     #[ink(storage)]
+//!     pub struct Wallet {
+//!         multisig: Multisig,
+//!     }
+//!
+//!     impl Wallet {
+//!         #[ink(constructor)]
+//!         pub fn new(owners: Vec<AccountId>, threshold: u32) -> Self {
+//!             let multisig = Multisig::new(owners, threshold)
+//!                 .unwrap_or_else(|error| panic!("invalid owners: {:?}", error));
+//!             Self { multisig }
+//!         }
+//!
+//!         #[ink(message)]
+//!         pub fn submit(&mut self, request: CallRequest) -> Result<RequestId, MultisigError> {
+//!             self.multisig.submit(self.env().caller(), request)
+//!         }
+//!
+//!         #[ink(message)]
+//!         pub fn confirm(&mut self, id: RequestId) -> Result<u32, MultisigError> {
+//!             self.multisig.confirm(self.env().caller(), id)
+//!         }
+//!
+//!         #[ink(message, payable)]
+//!         pub fn execute(&mut self, id: RequestId) -> Result<(), MultisigError> {
+//!             self.multisig.execute(id)
+//!         }
+//!     }
+//! }
+//! ```
+
+use super::{
+    AccountId,
+    Balance,
+};
+use crate::env::{
+    call::{
+        build_call,
+        Call,
+        ExecutionInput,
+        Selector,
+    },
+    CallFlags,
+    DefaultEnvironment,
+    Gas,
+};
+use ink_prelude::vec::Vec;
+use ink_storage::{
+    traits::{
+        AutoKey,
+        StorageKey,
+    },
+    Mapping,
+};
+
+/// The identifier of a [`CallRequest`] submitted to a [`Multisig`].
+pub type RequestId = u32;
+
+/// The errors of the [`Multisig`].
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum MultisigError {
+    /// Returned if the caller is not an owner of the multisig.
+    NotOwner,
+    /// Returned if an account is listed more than once as owner.
+    DuplicateOwner,
+    /// Returned if the threshold is zero or exceeds the number of owners.
+    InvalidThreshold,
+    /// Returned if no call request with the given identifier is pending.
+    UnknownRequest,
+    /// Returned if the owner has already confirmed the call request.
+    AlreadyConfirmed,
+    /// Returned if the owner has not confirmed the call request.
+    NotConfirmed,
+    /// Returned if the call request has less confirmations than the threshold.
+    NotEnoughConfirmations,
+    /// Returned if the executed call failed or was reverted by the callee.
+    CallFailed,
+}
+
+/// A contract call awaiting the confirmation of the owners of a [`Multisig`].
+///
+/// The call is built from a typed [`ExecutionInput`], so that its arguments are
+/// checked against the types of the called message when it is submitted.
+///
+/// # Example
+///
+/// ```
+/// use ink::{
+///     contrib::CallRequest,
+///     env::call::{
+///         ExecutionInput,
+///         Selector,
+///     },
+/// };
+///
+/// let request = CallRequest::new(
+///     [0x01; 32].into(),
+///     ExecutionInput::new(Selector::new(ink::selector_bytes!("transfer")))
+///         .push_arg([0x02; 32])
+///         .push_arg(100u128),
+/// )
+/// .transferred_value(10);
+/// assert_eq!(request.selector(), ink::selector_bytes!("transfer"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, crate::storage::traits::StorageLayout)
+)]
+pub struct CallRequest {
+    /// The account of the called contract.
+    callee: AccountId,
+    /// The selector of the called message.
+    selector: [u8; 4],
+    /// The SCALE encoded arguments of the called message.
+    input: Vec<u8>,
+    /// The value transferred to the callee.
+    transferred_value: Balance,
+    /// The gas limit of the call, `0` for all the remaining gas.
+    gas_limit: Gas,
+    /// Whether the callee may call back into the multisig contract.
+    allow_reentry: bool,
+}
+
+impl CallRequest {
+    /// Creates a request to call the message of `callee` given by `input`.
+    ///
+    /// The request transfers no value, uses all the remaining gas and forbids the
+    /// callee to call back into the multisig contract.
+    pub fn new<Args>(callee: AccountId, input: ExecutionInput<Args>) -> Self
+    where
+        Args: scale::Encode,
+    {
+        let mut input = scale::Encode::encode(&input);
+        let args = input.split_off(4);
+        let selector = [input[0], input[1], input[2], input[3]];
+        Self {
+            callee,
+            selector,
+            input: args,
+            transferred_value: 0,
+            gas_limit: 0,
+            allow_reentry: false,
+        }
+    }
+
+    /// Sets the value transferred to the callee.
+    pub fn transferred_value(self, transferred_value: Balance) -> Self {
+        Self {
+            transferred_value,
+            ..self
+        }
+    }
+
+    /// Sets the gas limit of the call.
+    pub fn gas_limit(self, gas_limit: Gas) -> Self {
+        Self { gas_limit, ..self }
+    }
+
+    /// Allows the callee to call back into the multisig contract.
+    ///
+    /// This is required for requests calling the multisig contract itself.
+    pub fn allow_reentry(self, allow_reentry: bool) -> Self {
+        Self {
+            allow_reentry,
+            ..self
+        }
+    }
+
+    /// Returns the account of the called contract.
+    pub fn callee(&self) -> AccountId {
+        self.callee
+    }
+
+    /// Returns the selector of the called message.
+    pub fn selector(&self) -> [u8; 4] {
+        self.selector
+    }
+
+    /// Returns the SCALE encoded arguments of the called message.
+    pub fn input(&self) -> &[u8] {
+        &self.input
+    }
+}
+
+/// The already SCALE encoded arguments of a [`CallRequest`].
+struct EncodedArgs<'a>(&'a [u8]);
+
+impl scale::Encode for EncodedArgs<'_> {
+    fn size_hint(&self) -> usize {
+        self.0.len()
+    }
+
+    fn encode_to<O: scale::Output + ?Sized>(&self, output: &mut O) {
+        output.write(self.0);
+    }
+}
+
+/// The storage of a multisig wallet with the logic to confirm and execute calls.
+///
+/// See the [module documentation](crate::contrib::multisig) for more details.
+#[crate::storage_item]
+#[derive(Debug)]
+pub struct Multisig<KEY: StorageKey = AutoKey> {
+    // The owners are only set on creation and read by every operation, so they
+    // are packed on purpose.
+    #[ink(bounded)]
+    owners: Vec<AccountId>,
+    threshold: u32,
+    next_request_id: RequestId,
+    requests: Mapping<RequestId, CallRequest>,
+    confirmations: Mapping<(RequestId, AccountId), ()>,
+    confirmation_counts: Mapping<RequestId, u32>,
+}
+
+impl<KEY: StorageKey> Multisig<KEY> {
+    /// Creates a multisig executing calls confirmed by `threshold` of the `owners`.
+    ///
+    /// # Errors
+    ///
+    /// - If an account is listed more than once in `owners`.
+    /// - If `threshold` is zero or exceeds the number of `owners`.
+    pub fn new(owners: Vec<AccountId>, threshold: u32) -> Result<Self, MultisigError> {
+        if owners
+            .iter()
+            .enumerate()
+            .any(|(n, owner)| owners[..n].contains(owner))
+        {
+            return Err(MultisigError::DuplicateOwner)
+        }
+        if threshold == 0 || threshold as usize > owners.len() {
+            return Err(MultisigError::InvalidThreshold)
+        }
+        Ok(Self {
+            owners,
+            threshold,
+            next_request_id: 0,
+            requests: Default::default(),
+            confirmations: Default::default(),
+            confirmation_counts: Default::default(),
+        })
+    }
+
+    /// Returns the owners of the multisig.
+    pub fn owners(&self) -> &[AccountId] {
+        &self.owners
+    }
+
+    /// Returns the number of confirmations required to execute a call request.
+    pub fn threshold(&self) -> u32 {
+        self.threshold
+    }
+
+    /// Returns `true` if `account` is an owner of the multisig.
+    pub fn is_owner(&self, account: &AccountId) -> bool {
+        self.owners.contains(account)
+    }
+
+    /// Returns the pending call request with the given `id`.
+    pub fn request(&self, id: RequestId) -> Option<CallRequest> {
+        self.requests.get(id)
+    }
+
+    /// Returns the number of confirmations of the call request with the given `id`.
+    pub fn confirmations(&self, id: RequestId) -> u32 {
+        self.confirmation_counts.get(id).unwrap_or_default()
+    }
+
+    /// Returns `true` if `owner` has confirmed the call request with the given `id`.
+    pub fn is_confirmed_by(&self, id: RequestId, owner: AccountId) -> bool {
+        self.confirmations.contains((id, owner))
+    }
+
+    /// Submits the `request` on behalf of `owner`, who confirms it right away.
+    ///
+    /// Returns the identifier of the submitted call request.
+    ///
+    /// # Errors
+    ///
+    /// If `owner` is not an owner of the multisig.
+    pub fn submit(
+        &mut self,
+        owner: AccountId,
+        request: CallRequest,
+    ) -> Result<RequestId, MultisigError> {
+        self.ensure_owner(&owner)?;
+        let id = self.next_request_id;
+        self.next_request_id = id.wrapping_add(1);
+        self.requests.insert(id, &request);
+        self.confirm(owner, id)?;
+        Ok(id)
+    }
+
+    /// Confirms the call request with the given `id` on behalf of `owner`.
+    ///
+    /// Returns the number of confirmations of the call request.
+    ///
+    /// # Errors
+    ///
+    /// - If `owner` is not an owner of the multisig.
+    /// - If no call request with the given `id` is pending.
+    /// - If `owner` has already confirmed the call request.
+    pub fn confirm(
+        &mut self,
+        owner: AccountId,
+        id: RequestId,
+    ) -> Result<u32, MultisigError> {
+        self.ensure_owner(&owner)?;
+        self.ensure_pending(id)?;
+        if self.is_confirmed_by(id, owner) {
+            return Err(MultisigError::AlreadyConfirmed)
+        }
+        self.confirmations.insert((id, owner), &());
+        let confirmations = self.confirmations(id) + 1;
+        self.confirmation_counts.insert(id, &confirmations);
+        Ok(confirmations)
+    }
+
+    /// Revokes the confirmation of the call request with the given `id` by `owner`.
+    ///
+    /// Returns the number of remaining confirmations of the call request.
+    ///
+    /// # Errors
+    ///
+    /// - If `owner` is not an owner of the multisig.
+    /// - If no call request with the given `id` is pending.
+    /// - If `owner` has not confirmed the call request.
+    pub fn revoke(
+        &mut self,
+        owner: AccountId,
+        id: RequestId,
+    ) -> Result<u32, MultisigError> {
+        self.ensure_owner(&owner)?;
+        self.ensure_pending(id)?;
+        if !self.is_confirmed_by(id, owner) {
+            return Err(MultisigError::NotConfirmed)
+        }
+        self.confirmations.remove((id, owner));
+        let confirmations = self.confirmations(id) - 1;
+        self.confirmation_counts.insert(id, &confirmations);
+        Ok(confirmations)
+    }
+
+    /// Cancels the call request with the given `id` on behalf of `owner`.
+    ///
+    /// # Errors
+    ///
+    /// - If `owner` is not an owner of the multisig.
+    /// - If no call request with the given `id` is pending.
+    pub fn cancel(
+        &mut self,
+        owner: AccountId,
+        id: RequestId,
+    ) -> Result<(), MultisigError> {
+        self.ensure_owner(&owner)?;
+        self.take(id).map(|_| ())
+    }
+
+    /// Executes the call request with the given `id`.
+    ///
+    /// The request is removed before the call is made, so that it can not be
+    /// executed again by a reentrant call.
+    ///
+    /// # Note
+    ///
+    /// The message executing the request must return the error, so that the
+    /// contract execution is reverted and the request is kept if the call fails.
+    ///
+    /// # Errors
+    ///
+    /// - If no call request with the given `id` is pending.
+    /// - If the call request has less confirmations than the threshold.
+    /// - If the call fails or is reverted by the callee.
+    pub fn execute(&mut self, id: RequestId) -> Result<(), MultisigError> {
+        self.ensure_pending(id)?;
+        if self.confirmations(id) < self.threshold {
+            return Err(MultisigError::NotEnoughConfirmations)
+        }
+        let request = self.take(id)?;
+        build_call::<DefaultEnvironment>()
+            .call_type(
+                Call::new()
+                    .callee(request.callee)
+                    .gas_limit(request.gas_limit)
+                    .transferred_value(request.transferred_value),
+            )
+            .call_flags(CallFlags::default().set_allow_reentry(request.allow_reentry))
+            .exec_input(
+                ExecutionInput::new(Selector::new(request.selector))
+                    .push_arg(EncodedArgs(&request.input)),
+            )
+            .returns::<()>()
+            .fire()
+            .map_err(|_| MultisigError::CallFailed)
+    }
+
+    /// Removes the call request with the given `id` and all its confirmations.
+    fn take(&mut self, id: RequestId) -> Result<CallRequest, MultisigError> {
+        let request = self
+            .requests
+            .take(id)
+            .ok_or(MultisigError::UnknownRequest)?;
+        for owner in &self.owners {
+            self.confirmations.remove((id, owner));
+        }
+        self.confirmation_counts.remove(id);
+        Ok(request)
+    }
+
+    fn ensure_owner(&self, account: &AccountId) -> Result<(), MultisigError> {
+        if !self.is_owner(account) {
+            return Err(MultisigError::NotOwner)
+        }
+        Ok(())
+    }
+
+    fn ensure_pending(&self, id: RequestId) -> Result<(), MultisigError> {
+        if !self.requests.contains(id) {
+            return Err(MultisigError::UnknownRequest)
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::RefCell;
+
+    std::thread_local! {
+        /// The inputs and transferred values of the calls to the callee.
+        static CALLS: RefCell<Vec<(Vec<u8>, Balance)>> = const { RefCell::new(Vec::new()) };
+    }
+
+    /// A callee recording its calls, which traps if the first argument is `0`.
+    fn callee(input: &[u8]) {
+        let value = ink_env::transferred_value::<DefaultEnvironment>();
+        CALLS.with(|calls| calls.borrow_mut().push((input.to_vec(), value)));
+        if input.get(4) == Some(&0) {
+            panic!("callee trapped")
+        }
+    }
+
+    const CALLEE: [u8; 32] = [0xFF; 32];
+
+    fn request(arg: u8) -> CallRequest {
+        CallRequest::new(
+            CALLEE.into(),
+            ExecutionInput::new(Selector::new([0xCA, 0xFE, 0xBA, 0xBE])).push_arg(arg),
+        )
+    }
+
+    fn run_test(f: impl FnOnce(Multisig, [AccountId; 4])) {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|accounts| {
+            ink_env::test::register_contract::<DefaultEnvironment>(CALLEE.into(), callee);
+            CALLS.with(|calls| calls.borrow_mut().clear());
+            let owners = [accounts.alice, accounts.bob, accounts.charlie];
+            let multisig = Multisig::new(owners.to_vec(), 2).unwrap();
+            f(
+                multisig,
+                [accounts.alice, accounts.bob, accounts.charlie, accounts.eve],
+            );
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn new_fails_for_invalid_owners() {
+        let (alice, bob) = (AccountId::from([0x01; 32]), AccountId::from([0x02; 32]));
+        assert_eq!(
+            Multisig::<AutoKey>::new(vec![alice, bob, alice], 2).err(),
+            Some(MultisigError::DuplicateOwner)
+        );
+        assert_eq!(
+            Multisig::<AutoKey>::new(vec![alice, bob], 0).err(),
+            Some(MultisigError::InvalidThreshold)
+        );
+        assert_eq!(
+            Multisig::<AutoKey>::new(vec![alice, bob], 3).err(),
+            Some(MultisigError::InvalidThreshold)
+        );
+    }
+
+    #[test]
+    fn confirmations_work() {
+        run_test(|mut multisig, [alice, bob, _charlie, eve]| {
+            assert_eq!(
+                multisig.submit(eve, request(1)),
+                Err(MultisigError::NotOwner)
+            );
+            let id = multisig.submit(alice, request(1)).unwrap();
+            assert_eq!(multisig.request(id), Some(request(1)));
+            assert_eq!(multisig.confirmations(id), 1);
+            assert_eq!(
+                multisig.confirm(alice, id),
+                Err(MultisigError::AlreadyConfirmed)
+            );
+            assert_eq!(multisig.confirm(eve, id), Err(MultisigError::NotOwner));
+            assert_eq!(multisig.revoke(bob, id), Err(MultisigError::NotConfirmed));
+            assert_eq!(multisig.confirm(bob, id), Ok(2));
+            assert_eq!(multisig.revoke(bob, id), Ok(1));
+            assert!(!multisig.is_confirmed_by(id, bob));
+            assert_eq!(
+                multisig.confirm(bob, id + 1),
+                Err(MultisigError::UnknownRequest)
+            );
+            assert_eq!(multisig.cancel(bob, id), Ok(()));
+            assert_eq!(multisig.request(id), None);
+            assert_eq!(multisig.confirmations(id), 0);
+            assert!(!multisig.is_confirmed_by(id, alice));
+        })
+    }
+
+    #[test]
+    fn execute_requires_threshold() {
+        run_test(|mut multisig, [alice, bob, charlie, _eve]| {
+            ink_env::test::set_account_balance::<DefaultEnvironment>(
+                ink_env::account_id::<DefaultEnvironment>(),
+                1_000,
+            );
+            let id = multisig
+                .submit(alice, request(7).transferred_value(100))
+                .unwrap();
+            assert_eq!(
+                multisig.execute(id),
+                Err(MultisigError::NotEnoughConfirmations)
+            );
+            assert_eq!(multisig.confirm(bob, id), Ok(2));
+            assert_eq!(multisig.execute(id), Ok(()));
+            CALLS.with(|calls| {
+                assert_eq!(
+                    *calls.borrow(),
+                    vec![(vec![0xCA, 0xFE, 0xBA, 0xBE, 7], 100)]
+                )
+            });
+            assert_eq!(multisig.execute(id), Err(MultisigError::UnknownRequest));
+            assert!(!multisig.is_confirmed_by(id, alice));
+
+            let id = multisig.submit(charlie, request(0)).unwrap();
+            multisig.confirm(alice, id).unwrap();
+            assert_eq!(multisig.execute(id), Err(MultisigError::CallFailed));
+        })
+    }
+}
@@ -0,0 +1,259 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Ownership of a contract transferred in two steps.
+//!
+//! The owner of an [`Ownable2Step`] proposes a new owner, who then has to accept the
+//! ownership. Until then the previous owner keeps it, so that a typo in the account
+//! of the new owner can't lock everyone out of the contract.
+//!
+//! Contracts deriving [`Ownable2Step`](macro@Ownable2Step) on their storage struct
+//! implement [`Owned`] for the `Ownable2Step` field and get the generated messages
+//!
+//! - `owner()` and `pending_owner()`,
+//! - `transfer_ownership(new_owner)`, which emits `OwnershipTransferStarted`,
+//! - `accept_ownership()` and `renounce_ownership()`, which emit
+//!   `OwnershipTransferred`,
+//!
+//! as well as the generated `OwnershipTransferStarted` and `OwnershipTransferred`
+//! events. Messages of the contract restricted to the owner call
+//! [`Ownable2Step::ensure_caller_is_owner`].
+//!
+//! The owners are accounts of the environment of the contract, so contracts with a
+//! custom `#[ink::contract(env = ..)]` declare the field as
+//! `Ownable2Step<Environment>`. The derive is only recognized by ink! as
+//! `Ownable2Step` or `ink::contrib::Ownable2Step`.
+//!
+//! # Example
+//!
+//! ```
+//! #[ink::contract]
+//! mod owned {
+//!     use ink::contrib::{
+//!         Ownable2Step,
+//!         OwnableError,
+//!     };
+//!
+//!     #[ink(storage)]
+//!     #[derive(Ownable2Step)]
+//!     pub struct Owned {
+//!         ownable: Ownable2Step,
+//!         fee: Balance,
+//!     }
+//!
+//!     impl Owned {
+//!         #[ink(constructor)]
+//!         pub fn new() -> Self {
+//!             Self {
+//!                 ownable: Ownable2Step::new(Self::env().caller()),
+//!                 fee: 0,
+//!             }
+//!         }
+//!
+//!         /// Sets the fee, callable by the owner only.
+//!         #[ink(message)]
+//!         pub fn set_fee(&mut self, fee: Balance) -> Result<(), OwnableError> {
+//!             self.ownable.ensure_caller_is_owner()?;
+//!             self.fee = fee;
+//!             Ok(())
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::env::{
+    DefaultEnvironment,
+    Environment,
+};
+
+/// The errors of the [`Ownable2Step`].
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum OwnableError {
+    /// Returned if the caller is not the owner of the contract.
+    NotOwner,
+    /// Returned if the caller is not the pending owner of the contract.
+    NotPendingOwner,
+}
+
+/// The storage of the owner of a contract with the logic to transfer it.
+///
+/// The owners are accounts of the environment `E` of the contract.
+///
+/// See the [module documentation](crate::contrib::ownable) for more details.
+#[crate::storage_item]
+#[derive(Debug)]
+pub struct Ownable2Step<E: Environment = DefaultEnvironment> {
+    owner: Option<E::AccountId>,
+    pending_owner: Option<E::AccountId>,
+}
+
+impl<E: Environment> Ownable2Step<E> {
+    /// Creates an ownable owned by `owner`.
+    pub fn new(owner: E::AccountId) -> Self {
+        Self {
+            owner: Some(owner),
+            pending_owner: None,
+        }
+    }
+
+    /// Returns the owner, or `None` if the ownership was renounced.
+    pub fn owner(&self) -> Option<E::AccountId> {
+        self.owner.clone()
+    }
+
+    /// Returns the account the ownership is being transferred to, if any.
+    pub fn pending_owner(&self) -> Option<E::AccountId> {
+        self.pending_owner.clone()
+    }
+
+    /// Ensures that the caller of the contract is the owner.
+    ///
+    /// # Errors
+    ///
+    /// If the caller is not the owner.
+    pub fn ensure_caller_is_owner(&self) -> Result<(), OwnableError> {
+        if self.owner.as_ref() != Some(&ink_env::caller::<E>()) {
+            return Err(OwnableError::NotOwner)
+        }
+        Ok(())
+    }
+
+    /// Starts transferring the ownership to `new_owner` on behalf of the caller.
+    ///
+    /// The ownership is only transferred once `new_owner` accepts it. Until then the
+    /// transfer is replaced by starting another one.
+    ///
+    /// # Errors
+    ///
+    /// If the caller is not the owner.
+    pub fn transfer_ownership(
+        &mut self,
+        new_owner: E::AccountId,
+    ) -> Result<(), OwnableError> {
+        self.ensure_caller_is_owner()?;
+        self.pending_owner = Some(new_owner);
+        Ok(())
+    }
+
+    /// Accepts the ownership on behalf of the caller.
+    ///
+    /// Returns the previous owner.
+    ///
+    /// # Errors
+    ///
+    /// If the caller is not the pending owner.
+    pub fn accept_ownership(&mut self) -> Result<Option<E::AccountId>, OwnableError> {
+        let caller = ink_env::caller::<E>();
+        if self.pending_owner.as_ref() != Some(&caller) {
+            return Err(OwnableError::NotPendingOwner)
+        }
+        self.pending_owner = None;
+        Ok(self.owner.replace(caller))
+    }
+
+    /// Gives up the ownership on behalf of the caller, leaving the contract without
+    /// an owner for good.
+    ///
+    /// Returns the previous owner.
+    ///
+    /// # Errors
+    ///
+    /// If the caller is not the owner.
+    pub fn renounce_ownership(&mut self) -> Result<Option<E::AccountId>, OwnableError> {
+        self.ensure_caller_is_owner()?;
+        self.pending_owner = None;
+        Ok(self.owner.take())
+    }
+}
+
+/// Implemented by contracts deriving [`Ownable2Step`](macro@Ownable2Step) to give
+/// access to their [`Ownable2Step`].
+///
+/// `E` is the environment of the contract.
+pub trait Owned<E: Environment = DefaultEnvironment> {
+    /// Returns the ownable of the contract.
+    fn ownable(&self) -> &Ownable2Step<E>;
+
+    /// Returns the ownable of the contract mutably.
+    fn ownable_mut(&mut self) -> &mut Ownable2Step<E>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_test(
+        f: impl FnOnce(Ownable2Step, ink_env::test::DefaultAccounts<DefaultEnvironment>),
+    ) {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|accounts| {
+            ink_env::test::set_caller::<DefaultEnvironment>(accounts.alice);
+            f(Ownable2Step::new(accounts.alice), accounts);
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn transfer_ownership_works() {
+        run_test(|mut ownable, accounts| {
+            assert_eq!(ownable.transfer_ownership(accounts.bob), Ok(()));
+            assert_eq!(ownable.owner(), Some(accounts.alice));
+            assert_eq!(ownable.pending_owner(), Some(accounts.bob));
+
+            assert_eq!(
+                ownable.accept_ownership(),
+                Err(OwnableError::NotPendingOwner)
+            );
+            ink_env::test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(ownable.accept_ownership(), Ok(Some(accounts.alice)));
+            assert_eq!(ownable.owner(), Some(accounts.bob));
+            assert_eq!(ownable.pending_owner(), None);
+            assert_eq!(ownable.ensure_caller_is_owner(), Ok(()));
+        })
+    }
+
+    #[test]
+    fn only_owner_transfers_ownership() {
+        run_test(|mut ownable, accounts| {
+            ink_env::test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                ownable.transfer_ownership(accounts.bob),
+                Err(OwnableError::NotOwner)
+            );
+            assert_eq!(ownable.renounce_ownership(), Err(OwnableError::NotOwner));
+            assert_eq!(ownable.pending_owner(), None);
+        })
+    }
+
+    #[test]
+    fn renounce_ownership_works() {
+        run_test(|mut ownable, accounts| {
+            ownable.transfer_ownership(accounts.bob).unwrap();
+            assert_eq!(ownable.renounce_ownership(), Ok(Some(accounts.alice)));
+            assert_eq!(ownable.owner(), None);
+            assert_eq!(ownable.pending_owner(), None);
+            assert_eq!(
+                ownable.ensure_caller_is_owner(),
+                Err(OwnableError::NotOwner)
+            );
+
+            ink_env::test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                ownable.accept_ownership(),
+                Err(OwnableError::NotPendingOwner)
+            );
+        })
+    }
+}
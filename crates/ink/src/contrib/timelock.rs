@@ -0,0 +1,366 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A timelock queueing calls which can only be executed after a delay.
+//!
+//! Governance actions such as changing a fee are usually announced ahead of time,
+//! so that users can react before they take effect. Calls dispatched to ink!
+//! messages flagged as `timelocked(delay = "2 days")` are therefore not executed.
+//! Instead they are queued as an [`Operation`] in the [`Timelock`] of the contract
+//! and return its [`OperationId`]. The operation becomes ready once the delay has
+//! passed.
+//!
+//! The contract gives access to its timelock by implementing [`Timelocked`] and
+//! gets two generated methods, which it usually exposes via its own messages:
+//!
+//! - `execute_timelocked(id)` executes a ready operation. The `internal`,
+//!   `only(roles)` and `non_reentrant` guards of the queued message are applied
+//!   when the call is queued, and once more to the executing caller.
+//! - `cancel_timelocked(id)` removes a queued operation. Only the account which
+//!   queued the call, see [`Operation::scheduled_by`], may cancel it.
+//!
+//! # Note
+//!
+//! - The operation is executed with the timelocked message's input as encoded
+//!   when it was queued. Upgrading the code of the contract in between may turn
+//!   it into [`TimelockError::InvalidInput`].
+//! - Calling the method of a `timelocked` message from Rust code, e.g. from other
+//!   messages or in unit tests, executes it right away.
+//!
+//! # Example
+//!
+//! ```
+//! #[ink::contract]
+//! mod governed {
+//!     use ink::contrib::{
+//!         OperationId,
+//!         Timelock,
+//!         TimelockError,
+//!         Timelocked,
+//!     };
+//!
+//!     #[ink(storage)]
+//!     pub struct Governed {
+//!         timelock: Timelock,
+//!         fee: Balance,
+//!     }
+//!
+//!     impl Timelocked for Governed {
+//!         fn timelock(&mut self) -> &mut Timelock {
+//!             &mut self.timelock
+//!         }
+//!     }
+//!
+//!     impl Governed {
+//!         #[ink(constructor)]
+//!         pub fn new() -> Self {
+//!             Self {
+//!                 timelock: Timelock::new(),
+//!                 fee: 0,
+//!             }
+//!         }
+//!
+//!         /// Sets the fee two days after the call.
+//!         #[ink(message, timelocked(delay = "2 days"))]
+//!         pub fn set_fee(&mut self, fee: Balance) {
+//!             self.fee = fee;
+//!         }
+//!
+//!         #[ink(message)]
+//!         pub fn execute(&mut self, id: OperationId) -> Result<(), TimelockError> {
+//!             self.execute_timelocked(id)
+//!         }
+//!
+//!         #[ink(message)]
+//!         pub fn cancel(&mut self, id: OperationId) -> Result<(), TimelockError> {
+//!             self.cancel_timelocked(id)
+//!         }
+//!
+//!         #[ink(message)]
+//!         pub fn fee(&self) -> Balance {
+//!             self.fee
+//!         }
+//!     }
+//! }
+//! ```
+
+use super::{
+    AccountId,
+    Timestamp,
+};
+use crate::env::DefaultEnvironment;
+use ink_prelude::vec::Vec;
+use ink_storage::Mapping;
+
+/// The identifier of an [`Operation`] queued in a [`Timelock`].
+pub type OperationId = u32;
+
+/// The errors of the [`Timelock`].
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum TimelockError {
+    /// Returned if no operation with the given identifier is queued.
+    UnknownOperation,
+    /// Returned if the delay of the operation has not passed yet.
+    NotReady,
+    /// Returned if the input of the operation does not match its message anymore,
+    /// e.g. after the code of the contract has been upgraded.
+    InvalidInput,
+    /// Returned if the caller is not allowed to execute or cancel the operation.
+    CallerNotAllowed,
+    /// Returned if the message of the operation is `non_reentrant` and its lock has
+    /// already been taken.
+    ReentrantCall,
+}
+
+/// A call to a `timelocked` ink! message queued in a [`Timelock`].
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, crate::storage::traits::StorageLayout)
+)]
+pub struct Operation {
+    /// The selector of the called message.
+    selector: [u8; 4],
+    /// The SCALE encoded inputs of the called message.
+    input: Vec<u8>,
+    /// The account which queued the call.
+    scheduled_by: AccountId,
+    /// The timestamp from which on the operation can be executed.
+    ready_at: Timestamp,
+}
+
+impl Operation {
+    /// Returns the selector of the called message.
+    pub fn selector(&self) -> [u8; 4] {
+        self.selector
+    }
+
+    /// Returns the SCALE encoded inputs of the called message.
+    pub fn input(&self) -> &[u8] {
+        &self.input
+    }
+
+    /// Returns the account which queued the call.
+    pub fn scheduled_by(&self) -> AccountId {
+        self.scheduled_by
+    }
+
+    /// Returns the timestamp from which on the operation can be executed.
+    pub fn ready_at(&self) -> Timestamp {
+        self.ready_at
+    }
+}
+
+/// The storage of the queued calls to the `timelocked` ink! messages of a contract.
+///
+/// See the [module documentation](crate::contrib::timelock) for more details.
+#[crate::storage_item]
+#[derive(Debug, Default)]
+pub struct Timelock {
+    next_id: OperationId,
+    operations: Mapping<OperationId, Operation>,
+}
+
+impl Timelock {
+    /// Creates an empty timelock.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Returns the queued operation with the given `id`.
+    pub fn operation(&self, id: OperationId) -> Option<Operation> {
+        self.operations.get(id)
+    }
+
+    /// Returns the identifier of the next queued operation.
+    ///
+    /// Operations are numbered in the order they are queued.
+    pub fn next_id(&self) -> OperationId {
+        self.next_id
+    }
+
+    /// Queues a call of the message with the given `selector` and SCALE encoded
+    /// `input` which becomes ready after `delay` milliseconds.
+    ///
+    /// The call is queued on behalf of the caller of the contract.
+    ///
+    /// # Note
+    ///
+    /// This is called by the dispatch of `timelocked` ink! messages.
+    pub fn schedule(
+        &mut self,
+        selector: [u8; 4],
+        input: Vec<u8>,
+        delay: Timestamp,
+    ) -> OperationId {
+        let id = self.next_id;
+        self.next_id = id.wrapping_add(1);
+        let now = ink_env::block_timestamp::<DefaultEnvironment>();
+        let operation = Operation {
+            selector,
+            input,
+            scheduled_by: ink_env::caller::<DefaultEnvironment>(),
+            ready_at: now.saturating_add(delay),
+        };
+        self.operations.insert(id, &operation);
+        id
+    }
+
+    /// Cancels the queued operation with the given `id`.
+    ///
+    /// # Errors
+    ///
+    /// If no operation with the given `id` is queued.
+    pub fn cancel(&mut self, id: OperationId) -> Result<Operation, TimelockError> {
+        self.operations
+            .take(id)
+            .ok_or(TimelockError::UnknownOperation)
+    }
+
+    /// Cancels the queued operation with the given `id` on behalf of the caller.
+    ///
+    /// # Note
+    ///
+    /// This is called by the generated `cancel_timelocked` method.
+    ///
+    /// # Errors
+    ///
+    /// - If no operation with the given `id` is queued.
+    /// - If the caller is not the account which queued the operation.
+    pub fn cancel_scheduled(
+        &mut self,
+        id: OperationId,
+    ) -> Result<Operation, TimelockError> {
+        let operation = self.operation(id).ok_or(TimelockError::UnknownOperation)?;
+        if ink_env::caller::<DefaultEnvironment>() != operation.scheduled_by {
+            return Err(TimelockError::CallerNotAllowed)
+        }
+        self.cancel(id)
+    }
+
+    /// Returns the queued operation with the given `id` if its delay has passed.
+    ///
+    /// # Errors
+    ///
+    /// - If no operation with the given `id` is queued.
+    /// - If the delay of the operation has not passed yet.
+    pub fn ready(&self, id: OperationId) -> Result<Operation, TimelockError> {
+        let operation = self.operation(id).ok_or(TimelockError::UnknownOperation)?;
+        if ink_env::block_timestamp::<DefaultEnvironment>() < operation.ready_at {
+            return Err(TimelockError::NotReady)
+        }
+        Ok(operation)
+    }
+
+    /// Removes the queued operation with the given `id` in order to execute it.
+    ///
+    /// # Note
+    ///
+    /// This is called by the generated `execute_timelocked` method. The operation
+    /// is removed before it is executed, so that it can not be executed twice.
+    ///
+    /// # Errors
+    ///
+    /// - If no operation with the given `id` is queued.
+    /// - If the delay of the operation has not passed yet.
+    pub fn take_ready(&mut self, id: OperationId) -> Result<Operation, TimelockError> {
+        let operation = self.ready(id)?;
+        self.operations.remove(id);
+        Ok(operation)
+    }
+}
+
+/// Implemented by contracts with `timelocked` ink! messages to give access to
+/// their [`Timelock`].
+pub trait Timelocked {
+    /// Returns the timelock of the contract.
+    fn timelock(&mut self) -> &mut Timelock;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SELECTOR: [u8; 4] = [0xCA, 0xFE, 0xBA, 0xBE];
+
+    fn run_test(f: impl FnOnce(Timelock, AccountId)) {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|accounts| {
+            ink_env::test::set_caller::<DefaultEnvironment>(accounts.bob);
+            ink_env::test::advance_blocks::<DefaultEnvironment>(1, 1_000);
+            f(Timelock::new(), accounts.bob);
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn schedule_works() {
+        run_test(|mut timelock, bob| {
+            let id = timelock.schedule(SELECTOR, vec![1, 2], 500);
+            assert_eq!(id, 0);
+            assert_eq!(timelock.next_id(), 1);
+            let operation = timelock.operation(id).unwrap();
+            assert_eq!(operation.selector(), SELECTOR);
+            assert_eq!(operation.input(), &[1, 2]);
+            assert_eq!(operation.scheduled_by(), bob);
+            assert_eq!(
+                operation.ready_at(),
+                ink_env::block_timestamp::<DefaultEnvironment>() + 500
+            );
+            assert_eq!(timelock.schedule(SELECTOR, vec![3], 500), 1);
+        })
+    }
+
+    #[test]
+    fn take_ready_respects_delay() {
+        run_test(|mut timelock, _bob| {
+            let id = timelock.schedule(SELECTOR, vec![1], 500);
+            ink_env::test::advance_blocks::<DefaultEnvironment>(1, 499);
+            assert_eq!(timelock.take_ready(id), Err(TimelockError::NotReady));
+            ink_env::test::advance_blocks::<DefaultEnvironment>(1, 1);
+            assert_eq!(timelock.take_ready(id).map(|op| op.input), Ok(vec![1]));
+            assert_eq!(
+                timelock.take_ready(id),
+                Err(TimelockError::UnknownOperation)
+            );
+        })
+    }
+
+    #[test]
+    fn cancel_works() {
+        run_test(|mut timelock, _bob| {
+            let id = timelock.schedule(SELECTOR, vec![1], 500);
+            assert!(timelock.cancel(id).is_ok());
+            assert_eq!(timelock.operation(id), None);
+            assert_eq!(timelock.cancel(id), Err(TimelockError::UnknownOperation));
+        })
+    }
+
+    #[test]
+    fn cancel_scheduled_requires_scheduler() {
+        run_test(|mut timelock, _bob| {
+            let id = timelock.schedule(SELECTOR, vec![1], 500);
+            let accounts = ink_env::test::default_accounts::<DefaultEnvironment>();
+            ink_env::test::set_caller::<DefaultEnvironment>(accounts.charlie);
+            assert_eq!(
+                timelock.cancel_scheduled(id),
+                Err(TimelockError::CallerNotAllowed)
+            );
+            ink_env::test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert!(timelock.cancel_scheduled(id).is_ok());
+            assert_eq!(timelock.operation(id), None);
+        })
+    }
+}
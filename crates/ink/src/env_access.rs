@@ -219,6 +219,102 @@ where
         ink_env::gas_left::<E>()
     }
 
+    /// Returns the weight left for the contract execution, including the storage
+    /// proof size.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[ink::contract]
+    /// # pub mod my_contract {
+    /// #     use ink::prelude::vec::Vec;
+    /// #
+    /// #     #[ink(storage)]
+    /// #     pub struct MyContract {
+    /// #         cursor: u32,
+    /// #     }
+    /// #
+    /// #     impl MyContract {
+    /// #         #[ink(constructor)]
+    /// #         pub fn new() -> Self {
+    /// #             Self { cursor: 0 }
+    /// #         }
+    /// #
+    /// /// Pays out to the `recipients` starting at the stored cursor and stops
+    /// /// before running out of weight, so that the next call can continue.
+    /// #[ink(message)]
+    /// pub fn airdrop(&mut self, recipients: Vec<AccountId>, amount: Balance) {
+    ///     const WEIGHT_PER_TRANSFER: u64 = 1_000_000_000;
+    ///     const PROOF_SIZE_PER_TRANSFER: u64 = 1_000;
+    ///     while let Some(recipient) = recipients.get(self.cursor as usize) {
+    ///         let weight_left = self.env().weight_left();
+    ///         if weight_left.ref_time < WEIGHT_PER_TRANSFER
+    ///             || weight_left.proof_size < PROOF_SIZE_PER_TRANSFER
+    ///         {
+    ///             return
+    ///         }
+    ///         self.env()
+    ///             .transfer(*recipient, amount)
+    ///             .unwrap_or_else(|error| panic!("transfer failed: {:?}", error));
+    ///         self.cursor += 1;
+    ///     }
+    /// }
+    /// #
+    /// #     }
+    /// # }
+    /// ```
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_env::weight_left`]
+    pub fn weight_left(self) -> ink_env::Weight {
+        ink_env::weight_left::<E>()
+    }
+
+    /// Returns the storage added and removed by the current call so far.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[ink::contract]
+    /// # pub mod my_contract {
+    /// #     use ink::storage::Mapping;
+    /// #
+    /// #     #[ink(storage)]
+    /// #     pub struct MyContract {
+    /// #         names: Mapping<AccountId, ink::prelude::string::String>,
+    /// #     }
+    /// #
+    /// #     impl MyContract {
+    /// #         #[ink(constructor)]
+    /// #         pub fn new() -> Self {
+    /// #             Self { names: Default::default() }
+    /// #         }
+    /// #
+    /// /// Registers the `name` of the caller, who pays for the storage it takes.
+    /// #[ink(message, payable)]
+    /// pub fn register(&mut self, name: ink::prelude::string::String) {
+    ///     const DEPOSIT_PER_BYTE: Balance = 1_000;
+    ///     const DEPOSIT_PER_ITEM: Balance = 10_000;
+    ///     self.names.insert(self.env().caller(), &name);
+    ///     let charge = self
+    ///         .env()
+    ///         .storage_deposit_info()
+    ///         .charge(DEPOSIT_PER_BYTE, DEPOSIT_PER_ITEM);
+    ///     assert!(self.env().transferred_value() >= charge, "deposit too low");
+    /// }
+    /// #
+    /// #     }
+    /// # }
+    /// ```
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_env::storage_deposit_info`]
+    pub fn storage_deposit_info(self) -> ink_env::StorageDepositInfo {
+        ink_env::storage_deposit_info()
+    }
+
     /// Returns the timestamp of the current block.
     ///
     /// # Example
@@ -409,6 +505,81 @@ where
         ink_env::minimum_balance::<E>()
     }
 
+    /// Returns the account ID of the author of the current block.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[ink::contract]
+    /// # pub mod my_contract {
+    /// #     #[ink(storage)]
+    /// #     pub struct MyContract { }
+    /// #
+    /// #     impl MyContract {
+    /// #         #[ink(constructor)]
+    /// #         pub fn new() -> Self {
+    /// #             Self {}
+    /// #         }
+    /// #
+    /// #[ink(message)]
+    /// pub fn is_block_author(&self) -> bool {
+    ///     self.env().block_author() == Ok(self.env().caller())
+    /// }
+    /// #
+    /// #     }
+    /// # }
+    /// ```
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_env::block_author`]
+    pub fn block_author(
+        self,
+    ) -> core::result::Result<E::AccountId, ink_env::runtime_function::NotSupported> {
+        ink_env::block_author::<E>()
+    }
+
+    /// Returns the hash of the parent of the current block.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_env::parent_hash`]
+    pub fn parent_hash(
+        self,
+    ) -> core::result::Result<E::Hash, ink_env::runtime_function::NotSupported> {
+        ink_env::parent_hash::<E>()
+    }
+
+    /// Returns the identifier of the chain.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_env::chain_id`]
+    pub fn chain_id(
+        self,
+    ) -> core::result::Result<u64, ink_env::runtime_function::NotSupported> {
+        ink_env::chain_id::<E>()
+    }
+
+    /// Returns randomness for the given `subject` which block producers can predict
+    /// and influence.
+    ///
+    /// # Note
+    ///
+    /// Use a [`SecureRandomness`](ink_env::random::SecureRandomness) source if
+    /// anything of value depends on the outcome.
+    ///
+    /// For more details visit: [`ink_env::random_insecure`]
+    pub fn random_insecure(
+        self,
+        subject: &[u8],
+    ) -> core::result::Result<
+        ink_env::random::InsecureRandomness<E::Hash, E::BlockNumber>,
+        ink_env::RandomnessError,
+    > {
+        ink_env::random_insecure::<E>(subject)
+    }
+
     /// Instantiates another contract.
     ///
     /// # Example
@@ -480,6 +651,27 @@ where
         ink_env::instantiate_contract::<E, Args, Salt, C>(params)
     }
 
+    /// Instantiates another contract with a fallible constructor.
+    ///
+    /// The error of a constructor of type `Result<Self, ContractError>` is returned
+    /// back to the caller, see [`Self::instantiate_contract`] for an example of
+    /// setting up the `params`.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_env::try_instantiate_contract`]
+    pub fn try_instantiate_contract<Args, Salt, C, ContractError>(
+        self,
+        params: &CreateParams<E, Args, Salt, C>,
+    ) -> Result<core::result::Result<E::AccountId, ContractError>>
+    where
+        Args: scale::Encode,
+        Salt: AsRef<[u8]>,
+        ContractError: scale::Decode,
+    {
+        ink_env::try_instantiate_contract::<E, Args, Salt, C, ContractError>(params)
+    }
+
     /// Invokes a contract message and returns its result.
     ///
     /// # Example
@@ -666,6 +858,88 @@ where
         ink_env::transfer::<E>(destination, value)
     }
 
+    /// Transfers value from the contract to the destination account ID while keeping
+    /// the contract account alive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[ink::contract]
+    /// # pub mod my_contract {
+    /// #     #[ink(storage)]
+    /// #     pub struct MyContract { }
+    /// #
+    /// #     impl MyContract {
+    /// #         #[ink(constructor)]
+    /// #         pub fn new() -> Self {
+    /// #             Self {}
+    /// #         }
+    /// #
+    /// /// Transfers `value` to the caller unless it would remove the contract account.
+    /// #[ink(message)]
+    /// pub fn withdraw(&mut self, value: Balance) -> bool {
+    ///     match self.env().transfer_keep_alive(self.env().caller(), value) {
+    ///         Ok(()) => true,
+    ///         Err(ink::env::Error::BelowMinimumBalance) => false,
+    ///         Err(err) => panic!("transfer failed: {:?}", err),
+    ///     }
+    /// }
+    /// #
+    /// #     }
+    /// # }
+    /// ```
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_env::transfer_keep_alive`]
+    pub fn transfer_keep_alive(
+        self,
+        destination: E::AccountId,
+        value: E::Balance,
+    ) -> Result<()> {
+        ink_env::transfer_keep_alive::<E>(destination, value)
+    }
+
+    /// Transfers the whole balance of the contract to the destination account ID and
+    /// returns the transferred value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[ink::contract]
+    /// # pub mod my_contract {
+    /// #     #[ink(storage)]
+    /// #     pub struct MyContract { }
+    /// #
+    /// #     impl MyContract {
+    /// #         #[ink(constructor)]
+    /// #         pub fn new() -> Self {
+    /// #             Self {}
+    /// #         }
+    /// #
+    /// /// Transfers everything but the minimum balance to the caller.
+    /// #[ink(message)]
+    /// pub fn sweep(&mut self) -> Balance {
+    ///     self.env()
+    ///         .transfer_all(self.env().caller(), true)
+    ///         .unwrap_or_else(|err| panic!("transfer failed: {:?}", err))
+    /// }
+    /// #
+    /// #     }
+    /// # }
+    /// ```
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_env::transfer_all`]
+    pub fn transfer_all(
+        self,
+        destination: E::AccountId,
+        keep_alive: bool,
+    ) -> Result<E::Balance> {
+        ink_env::transfer_all::<E>(destination, keep_alive)
+    }
+
     /// Computes the hash of the given bytes using the cryptographic hash `H`.
     ///
     /// # Example
@@ -890,6 +1164,38 @@ where
         ink_env::caller_is_origin::<E>()
     }
 
+    /// Returns the account which signed the extrinsic executing the call stack.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[ink::contract]
+    /// # pub mod my_contract {
+    /// #     #[ink(storage)]
+    /// #     pub struct MyContract { }
+    /// #
+    /// #     impl MyContract {
+    /// #         #[ink(constructor)]
+    /// #         pub fn new() -> Self {
+    /// #             Self {}
+    /// #         }
+    /// #
+    /// #[ink(message)]
+    /// pub fn origin(&self) -> AccountId {
+    ///     self.env().origin()
+    /// }
+    /// #    }
+    /// # }
+    /// ```
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_env::origin`]
+    #[cfg(feature = "unstable-interface")]
+    pub fn origin(self) -> E::AccountId {
+        ink_env::origin::<E>()
+    }
+
     /// Returns the code hash of the contract at the given `account` id.
     ///
     /// # Example
@@ -906,9 +1212,12 @@ where
     /// #             Self {}
     /// #         }
     /// #
+    /// /// Returns `true` if the contract at `account_id` runs the same code as
+    /// /// this contract.
     /// #[ink(message)]
-    /// pub fn code_hash(&mut self, account_id: AccountId) -> Option<Hash> {
-    ///     self.env().code_hash(&account_id).ok()
+    /// pub fn is_clone(&self, account_id: AccountId) -> bool {
+    ///     let own_code_hash = self.env().own_code_hash();
+    ///     own_code_hash.is_ok() && self.env().code_hash(&account_id) == own_code_hash
     /// }
     /// #    }
     /// # }
@@ -921,7 +1230,7 @@ where
         ink_env::code_hash::<E>(account_id)
     }
 
-    /// Returns the code hash of the contract at the given `account` id.
+    /// Returns the code hash of the currently executing contract.
     ///
     /// # Example
     ///
@@ -951,4 +1260,57 @@ where
     pub fn own_code_hash(self) -> Result<E::Hash> {
         ink_env::own_code_hash::<E>()
     }
+
+    /// Dispatches the runtime `call` with the contract as origin.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[ink::contract]
+    /// # pub mod my_contract {
+    /// #     use ink::runtime_call::RuntimeCall;
+    /// #
+    /// #     #[ink(storage)]
+    /// #     pub struct MyContract { }
+    /// #
+    /// /// The calls of the `Balances` pallet at index 4 of the runtime.
+    /// #[derive(RuntimeCall)]
+    /// #[runtime_call(index = 4)]
+    /// enum BalancesCall {
+    ///     #[runtime_call(index = 3)]
+    ///     TransferKeepAlive {
+    ///         dest: (u8, AccountId),
+    ///         #[runtime_call(compact)]
+    ///         value: Balance,
+    ///     },
+    /// }
+    /// #
+    /// #     impl MyContract {
+    /// #         #[ink(constructor)]
+    /// #         pub fn new() -> Self {
+    /// #             Self {}
+    /// #         }
+    /// #
+    /// #[ink(message)]
+    /// pub fn transfer_through_runtime(&mut self, dest: AccountId, value: Balance) -> bool {
+    ///     let call = BalancesCall::TransferKeepAlive {
+    ///         dest: (0, dest),
+    ///         value,
+    ///     };
+    ///     self.env().call_runtime(&call).is_ok()
+    /// }
+    /// #    }
+    /// # }
+    /// ```
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_env::call_runtime`]
+    #[cfg(feature = "call-runtime")]
+    pub fn call_runtime<Call>(self, call: &Call) -> Result<()>
+    where
+        Call: ink_env::runtime_call::RuntimeCall,
+    {
+        ink_env::call_runtime::<E, _>(call)
+    }
 }
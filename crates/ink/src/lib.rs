@@ -18,6 +18,9 @@
 )]
 #![cfg_attr(not(feature = "std"), no_std)]
 
+// Allows the ink! macros to refer to `::ink` from within this crate.
+extern crate self as ink;
+
 #[macro_use]
 #[doc(hidden)]
 pub mod result_info;
@@ -25,18 +28,33 @@ pub mod result_info;
 #[cfg_attr(not(feature = "show-codegen-docs"), doc(hidden))]
 pub mod codegen;
 
+pub mod contrib;
+pub mod math;
+#[cfg(feature = "std")]
+pub mod off_chain;
+pub mod pagination;
+pub mod proxy;
 pub mod reflect;
+pub mod sol;
+pub mod standards;
 
 mod chain_extension;
 mod contract_ref;
 mod env_access;
+mod raw_input;
 
 pub use ink_env as env;
+pub use ink_env::log;
 #[cfg(feature = "std")]
 pub use ink_metadata as metadata;
 pub use ink_prelude as prelude;
 pub use ink_primitives as primitives;
 
+pub mod runtime_call {
+    pub use ink_env::runtime_call::*;
+    pub use ink_macro::RuntimeCall;
+}
+
 pub mod storage {
     pub mod traits {
         pub use ink_macro::{
@@ -48,8 +66,12 @@ pub mod storage {
         pub use ink_storage::traits::*;
     }
     pub use ink_storage::{
+        migrate,
+        prune,
+        IterableMapping,
         Lazy,
         Mapping,
+        StorageVec,
     };
 }
 
@@ -58,8 +80,15 @@ pub use self::{
         ChainExtensionInstance,
         IsResultType,
     },
-    contract_ref::ToAccountId,
+    contract_ref::{
+        CalleeError,
+        CalleeGone,
+        RebindError,
+        ToAccountId,
+        WeakContractRef,
+    },
     env_access::EnvAccess,
+    raw_input::RawInput,
 };
 pub use ink_macro::{
     blake2x256,
@@ -0,0 +1,539 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Checked arithmetic which reverts with a descriptive error.
+//!
+//! The arithmetic operators of [`Checked`] revert the execution of the contract
+//! with an encoded [`ArithmeticError`] instead of trapping with a bare panic if
+//! the operation overflows or divides by zero. This allows callers and clients
+//! to find out which operation failed with which operands.
+//!
+//! In addition [`Checked::mul_div`] and [`Checked::mul_div_ceil`] compute
+//! `a * b / c` using a 256-bit intermediate product, which is what price
+//! calculations of automated market makers usually need. They are based on
+//! [`mul_div`](ink_prelude::fixed::mul_div) of `ink_prelude`.
+//!
+//! # Example
+//!
+//! ```
+//! use ink::math::{
+//!     ArithmeticError,
+//!     ArithmeticOp,
+//!     Balance,
+//! };
+//!
+//! let reserve = Balance::new(1_000);
+//! let amount = Balance::new(250);
+//!
+//! assert_eq!(reserve + amount, Balance::new(1_250));
+//! assert_eq!(reserve.mul_div(amount, reserve + amount), Balance::new(200));
+//! assert_eq!(
+//!     amount.try_sub(reserve),
+//!     Err(ArithmeticError {
+//!         op: ArithmeticOp::Sub,
+//!         lhs: 250,
+//!         rhs: 1_000,
+//!     })
+//! );
+//! ```
+//!
+//! # Note
+//!
+//! Since the off-chain environment does not support reverting outside of a
+//! contract call, the operators panic with the description of the error when
+//! compiled with the `std` feature instead.
+
+use core::{
+    fmt,
+    ops::{
+        Add,
+        AddAssign,
+        Div,
+        DivAssign,
+        Mul,
+        MulAssign,
+        Rem,
+        RemAssign,
+        Sub,
+        SubAssign,
+    },
+};
+use ink_prelude::fixed::{
+    mul_div,
+    Rounding,
+};
+#[cfg(not(feature = "std"))]
+use ink_primitives::MessageResult;
+
+/// The balance type of the [`DefaultEnvironment`](ink_env::DefaultEnvironment)
+/// with checked arithmetic.
+pub type Balance =
+    Checked<<ink_env::DefaultEnvironment as ink_env::Environment>::Balance>;
+
+/// An arithmetic operation of [`Checked`] values.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum ArithmeticOp {
+    /// The addition `lhs + rhs`.
+    Add,
+    /// The subtraction `lhs - rhs`.
+    Sub,
+    /// The multiplication `lhs * rhs`.
+    Mul,
+    /// The division `lhs / rhs`.
+    Div,
+    /// The remainder `lhs % rhs`.
+    Rem,
+}
+
+impl ArithmeticOp {
+    /// Returns the symbol of the operator.
+    fn symbol(&self) -> &'static str {
+        match self {
+            Self::Add => "+",
+            Self::Sub => "-",
+            Self::Mul => "*",
+            Self::Div => "/",
+            Self::Rem => "%",
+        }
+    }
+}
+
+/// The error a [`Checked`] arithmetic operation reverts with.
+///
+/// # Note
+///
+/// If [`Checked::mul_div`] fails `op` is [`ArithmeticOp::Mul`] with the factors
+/// as operands if the quotient overflows, and [`ArithmeticOp::Div`] with the
+/// left factor and the zero divisor as operands if it divides by zero.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ArithmeticError<T> {
+    /// The operation that failed.
+    pub op: ArithmeticOp,
+    /// The left hand side operand.
+    pub lhs: T,
+    /// The right hand side operand.
+    pub rhs: T,
+}
+
+impl<T> fmt::Display for ArithmeticError<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let reason = match self.op {
+            ArithmeticOp::Div | ArithmeticOp::Rem => "overflow or division by zero",
+            _ => "overflow",
+        };
+        write!(
+            f,
+            "arithmetic {}: {} {} {}",
+            reason,
+            self.lhs,
+            self.op.symbol(),
+            self.rhs
+        )
+    }
+}
+
+/// Reverts the execution of the contract with the given error.
+///
+/// The error is returned like the error of an ink! message returning
+/// `Result<_, ArithmeticError<T>>`, so that callers decode it like the output of
+/// any other reverted ink! message.
+#[cold]
+fn revert<T>(error: ArithmeticError<T>) -> !
+where
+    T: scale::Encode + fmt::Display,
+{
+    #[cfg(feature = "std")]
+    {
+        panic!("{}", error)
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        ink_env::return_value::<MessageResult<Result<(), ArithmeticError<T>>>>(
+            ink_env::ReturnFlags::new_with_reverted(true),
+            &Ok(Err(error)),
+        )
+    }
+}
+
+/// Integer types supported by [`Checked`].
+pub trait CheckedInt:
+    Copy + Ord + scale::Encode + scale::Decode + fmt::Debug + fmt::Display
+{
+    /// Checked addition, returns `None` on overflow.
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    /// Checked subtraction, returns `None` on overflow.
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    /// Checked multiplication, returns `None` on overflow.
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    /// Checked division, returns `None` on overflow or division by zero.
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+    /// Checked remainder, returns `None` on overflow or division by zero.
+    fn checked_rem(self, rhs: Self) -> Option<Self>;
+    /// Saturating addition.
+    fn saturating_add(self, rhs: Self) -> Self;
+    /// Saturating subtraction.
+    fn saturating_sub(self, rhs: Self) -> Self;
+    /// Saturating multiplication.
+    fn saturating_mul(self, rhs: Self) -> Self;
+}
+
+macro_rules! impl_checked_int {
+    ( $( $ty:ty ),* $(,)? ) => {
+        $(
+            impl CheckedInt for $ty {
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$ty>::checked_add(self, rhs)
+                }
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$ty>::checked_sub(self, rhs)
+                }
+                fn checked_mul(self, rhs: Self) -> Option<Self> {
+                    <$ty>::checked_mul(self, rhs)
+                }
+                fn checked_div(self, rhs: Self) -> Option<Self> {
+                    <$ty>::checked_div(self, rhs)
+                }
+                fn checked_rem(self, rhs: Self) -> Option<Self> {
+                    <$ty>::checked_rem(self, rhs)
+                }
+                fn saturating_add(self, rhs: Self) -> Self {
+                    <$ty>::saturating_add(self, rhs)
+                }
+                fn saturating_sub(self, rhs: Self) -> Self {
+                    <$ty>::saturating_sub(self, rhs)
+                }
+                fn saturating_mul(self, rhs: Self) -> Self {
+                    <$ty>::saturating_mul(self, rhs)
+                }
+            }
+        )*
+    };
+}
+impl_checked_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+/// An integer whose arithmetic operators revert with an [`ArithmeticError`]
+/// on overflow or division by zero.
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    scale::Encode,
+    scale::Decode,
+)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, crate::storage::traits::StorageLayout)
+)]
+pub struct Checked<T>(T);
+
+impl<T> Checked<T>
+where
+    T: CheckedInt,
+{
+    /// Creates a new checked integer.
+    pub const fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Returns the underlying integer.
+    pub fn get(self) -> T {
+        self.0
+    }
+
+    /// Applies the checked operation or returns the error describing it.
+    fn apply(
+        self,
+        op: ArithmeticOp,
+        rhs: Self,
+        f: fn(T, T) -> Option<T>,
+    ) -> Result<Self, ArithmeticError<T>> {
+        f(self.0, rhs.0).map(Self).ok_or(ArithmeticError {
+            op,
+            lhs: self.0,
+            rhs: rhs.0,
+        })
+    }
+
+    /// Adds `rhs` or returns an error on overflow.
+    pub fn try_add(self, rhs: Self) -> Result<Self, ArithmeticError<T>> {
+        self.apply(ArithmeticOp::Add, rhs, T::checked_add)
+    }
+
+    /// Subtracts `rhs` or returns an error on overflow.
+    pub fn try_sub(self, rhs: Self) -> Result<Self, ArithmeticError<T>> {
+        self.apply(ArithmeticOp::Sub, rhs, T::checked_sub)
+    }
+
+    /// Multiplies by `rhs` or returns an error on overflow.
+    pub fn try_mul(self, rhs: Self) -> Result<Self, ArithmeticError<T>> {
+        self.apply(ArithmeticOp::Mul, rhs, T::checked_mul)
+    }
+
+    /// Divides by `rhs` or returns an error on overflow or division by zero.
+    pub fn try_div(self, rhs: Self) -> Result<Self, ArithmeticError<T>> {
+        self.apply(ArithmeticOp::Div, rhs, T::checked_div)
+    }
+
+    /// Computes the remainder of the division by `rhs` or returns an error on
+    /// overflow or division by zero.
+    pub fn try_rem(self, rhs: Self) -> Result<Self, ArithmeticError<T>> {
+        self.apply(ArithmeticOp::Rem, rhs, T::checked_rem)
+    }
+
+    /// Adds `rhs`, saturating at the numeric bounds.
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+
+    /// Subtracts `rhs`, saturating at the numeric bounds.
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+
+    /// Multiplies by `rhs`, saturating at the numeric bounds.
+    pub fn saturating_mul(self, rhs: Self) -> Self {
+        Self(self.0.saturating_mul(rhs.0))
+    }
+}
+
+impl Checked<u128> {
+    /// Computes `self * mul / div` rounding down, or returns an error if the
+    /// quotient overflows or `div` is zero.
+    ///
+    /// The product is computed with 256 bits, so it never overflows by itself.
+    pub fn try_mul_div(
+        self,
+        mul: Self,
+        div: Self,
+    ) -> Result<Self, ArithmeticError<u128>> {
+        mul_div(self.0, mul.0, div.0, Rounding::Down)
+            .map(Self)
+            .ok_or_else(|| mul_div_error(self.0, mul.0, div.0))
+    }
+
+    /// Computes `self * mul / div` rounding up, or returns an error if the
+    /// quotient overflows or `div` is zero.
+    pub fn try_mul_div_ceil(
+        self,
+        mul: Self,
+        div: Self,
+    ) -> Result<Self, ArithmeticError<u128>> {
+        mul_div(self.0, mul.0, div.0, Rounding::Up)
+            .map(Self)
+            .ok_or_else(|| mul_div_error(self.0, mul.0, div.0))
+    }
+
+    /// Computes `self * mul / div` rounding down.
+    ///
+    /// # Panics
+    ///
+    /// Reverts with an [`ArithmeticError`] if the quotient overflows or `div`
+    /// is zero.
+    pub fn mul_div(self, mul: Self, div: Self) -> Self {
+        self.try_mul_div(mul, div)
+            .unwrap_or_else(|error| revert(error))
+    }
+
+    /// Computes `self * mul / div` rounding up.
+    ///
+    /// # Panics
+    ///
+    /// Reverts with an [`ArithmeticError`] if the quotient overflows or `div`
+    /// is zero.
+    pub fn mul_div_ceil(self, mul: Self, div: Self) -> Self {
+        self.try_mul_div_ceil(mul, div)
+            .unwrap_or_else(|error| revert(error))
+    }
+}
+
+/// Returns the error of a failed `lhs * mul / div`.
+fn mul_div_error(lhs: u128, mul: u128, div: u128) -> ArithmeticError<u128> {
+    if div == 0 {
+        ArithmeticError {
+            op: ArithmeticOp::Div,
+            lhs,
+            rhs: div,
+        }
+    } else {
+        ArithmeticError {
+            op: ArithmeticOp::Mul,
+            lhs,
+            rhs: mul,
+        }
+    }
+}
+
+impl<T> From<T> for Checked<T>
+where
+    T: CheckedInt,
+{
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> fmt::Display for Checked<T>
+where
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+macro_rules! impl_checked_op {
+    ( $( $op:ident, $op_fn:ident, $assign:ident, $assign_fn:ident, $try_fn:ident; )* ) => {
+        $(
+            impl<T> $op for Checked<T>
+            where
+                T: CheckedInt,
+            {
+                type Output = Self;
+
+                fn $op_fn(self, rhs: Self) -> Self {
+                    self.$try_fn(rhs).unwrap_or_else(|error| revert(error))
+                }
+            }
+
+            impl<T> $op<T> for Checked<T>
+            where
+                T: CheckedInt,
+            {
+                type Output = Self;
+
+                fn $op_fn(self, rhs: T) -> Self {
+                    <Self as $op>::$op_fn(self, Self(rhs))
+                }
+            }
+
+            impl<T> $assign for Checked<T>
+            where
+                T: CheckedInt,
+            {
+                fn $assign_fn(&mut self, rhs: Self) {
+                    *self = <Self as $op>::$op_fn(*self, rhs);
+                }
+            }
+
+            impl<T> $assign<T> for Checked<T>
+            where
+                T: CheckedInt,
+            {
+                fn $assign_fn(&mut self, rhs: T) {
+                    *self = <Self as $op>::$op_fn(*self, Self(rhs));
+                }
+            }
+        )*
+    };
+}
+impl_checked_op! {
+    Add, add, AddAssign, add_assign, try_add;
+    Sub, sub, SubAssign, sub_assign, try_sub;
+    Mul, mul, MulAssign, mul_assign, try_mul;
+    Div, div, DivAssign, div_assign, try_div;
+    Rem, rem, RemAssign, rem_assign, try_rem;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mul_div_works() {
+        let max = Balance::new(u128::MAX);
+        assert_eq!(
+            max.mul_div(Balance::new(3), Balance::new(4)),
+            Balance::new(u128::MAX / 4 * 3 + 2)
+        );
+        assert_eq!(
+            max.mul_div_ceil(Balance::new(3), Balance::new(4)),
+            Balance::new(u128::MAX / 4 * 3 + 3)
+        );
+    }
+
+    #[test]
+    fn checked_operators_work() {
+        let mut value = Balance::new(10);
+        value += 5;
+        value -= Balance::new(3);
+        value *= 4;
+        value /= 3;
+        assert_eq!(value, Balance::new(16));
+        assert_eq!(value % 5, Balance::new(1));
+        assert_eq!(Checked::new(-3_i32) - 4, Checked::new(-7));
+        assert_eq!(
+            Checked::new(u8::MAX).saturating_add(Checked::new(1)).get(),
+            255
+        );
+    }
+
+    #[test]
+    fn failed_operations_describe_the_error() {
+        assert_eq!(
+            Checked::new(1_u32).try_div(Checked::new(0)),
+            Err(ArithmeticError {
+                op: ArithmeticOp::Div,
+                lhs: 1,
+                rhs: 0,
+            })
+        );
+        assert_eq!(
+            Balance::new(u128::MAX).try_mul_div(Balance::new(2), Balance::new(1)),
+            Err(ArithmeticError {
+                op: ArithmeticOp::Mul,
+                lhs: u128::MAX,
+                rhs: 2,
+            })
+        );
+        assert_eq!(
+            Balance::new(1).try_mul_div_ceil(Balance::new(2), Balance::new(0)),
+            Err(ArithmeticError {
+                op: ArithmeticOp::Div,
+                lhs: 1,
+                rhs: 0,
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "arithmetic overflow: 0 - 1")]
+    fn underflow_reverts() {
+        let _ = Balance::new(0) - Balance::new(1);
+    }
+
+    #[test]
+    fn encoded_error_decodes() {
+        let error = ArithmeticError {
+            op: ArithmeticOp::Add,
+            lhs: u128::MAX,
+            rhs: 1_u128,
+        };
+        let encoded = scale::Encode::encode(&error);
+        assert_eq!(
+            <ArithmeticError<u128> as scale::Decode>::decode(&mut &encoded[..]),
+            Ok(error)
+        );
+    }
+}
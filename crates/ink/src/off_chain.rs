@@ -0,0 +1,131 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cross-contract calls in the off-chain environment.
+//!
+//! Unit tests can register ink! smart contracts under an account ID, so that calls
+//! to the account, e.g. via a contract reference, execute the message dispatch of
+//! the contract just like on-chain.
+//!
+//! # Example
+//!
+//! ```
+//! #[ink::contract]
+//! mod counter {
+//!     #[ink(storage)]
+//!     pub struct Counter {
+//!         value: u32,
+//!     }
+//!
+//!     impl Counter {
+//!         #[ink(constructor)]
+//!         pub fn new() -> Self {
+//!             Self { value: 0 }
+//!         }
+//!
+//!         #[ink(message)]
+//!         pub fn inc(&mut self, by: u32) -> u32 {
+//!             self.value += by;
+//!             self.value
+//!         }
+//!     }
+//! }
+//!
+//! use counter::{
+//!     Counter,
+//!     CounterRef,
+//! };
+//! use ink::env::{
+//!     call::FromAccountId,
+//!     DefaultEnvironment,
+//! };
+//!
+//! ink::env::test::run_test::<DefaultEnvironment, _>(|accounts| {
+//!     ink::off_chain::register_contract(accounts.bob, Counter::new);
+//!     let mut counter = <CounterRef as FromAccountId<DefaultEnvironment>>::from_account_id(
+//!         accounts.bob,
+//!     );
+//!     assert_eq!(counter.inc(2), 2);
+//!     assert_eq!(counter.inc(3), 5);
+//!     Ok(())
+//! })
+//! .unwrap();
+//! ```
+
+use crate::reflect::{
+    ContractEnv,
+    ContractMessageDecoder,
+    ExecuteDispatchable,
+};
+use ink_env::{
+    Environment,
+    ReturnFlags,
+};
+use ink_primitives::{
+    LangError,
+    MessageResult,
+};
+use ink_storage::traits::{
+    Storable,
+    StorageKey,
+};
+
+/// The account ID type of the environment of the contract `C`.
+type AccountIdOf<C> = <<C as ContractEnv>::Env as Environment>::AccountId;
+
+/// Registers the contract returned by `constructor` under `account_id`.
+///
+/// The `constructor` is executed with `account_id` as callee, so that it writes
+/// to the storage of the account. Calls to the account are then dispatched to the
+/// messages of the contract, which load and store the contract from the storage
+/// of the account.
+///
+/// # Note
+///
+/// Instantiating contracts via their code hash fails with
+/// [`Error::CodeNotFound`](ink_env::Error::CodeNotFound) in the off-chain
+/// environment, so contracts calling each other must be registered one by one.
+pub fn register_contract<C, F>(account_id: AccountIdOf<C>, constructor: F)
+where
+    C: ContractEnv + ContractMessageDecoder + Storable + StorageKey,
+    F: FnOnce() -> C,
+{
+    let callee = ink_env::test::callee::<C::Env>();
+    ink_env::test::set_callee::<C::Env>(account_id.clone());
+    let contract = constructor();
+    ink_env::set_contract_storage(&<C as StorageKey>::KEY, &contract);
+    ink_env::test::set_callee::<C::Env>(callee);
+    ink_env::test::register_contract::<C::Env>(account_id, call::<C>);
+}
+
+/// The `call` entry point of the contract `C`.
+fn call<C>(mut input: &[u8])
+where
+    C: ContractMessageDecoder,
+{
+    let dispatchable =
+        match <<C as ContractMessageDecoder>::Type as scale::Decode>::decode(&mut input) {
+            Ok(dispatchable) => dispatchable,
+            Err(_decoding_error) => {
+                ink_env::return_value::<MessageResult<()>>(
+                    ReturnFlags::new_with_reverted(true),
+                    &Err(LangError::CouldNotReadInput),
+                )
+            }
+        };
+    <<C as ContractMessageDecoder>::Type as ExecuteDispatchable>::execute_dispatchable(
+        dispatchable,
+    )
+    .unwrap_or_else(|error| panic!("dispatching ink! message failed: {}", error))
+}
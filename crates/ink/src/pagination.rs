@@ -0,0 +1,180 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Paginated getters for large collections.
+//!
+//! The output of an ink! message is copied into a static buffer of limited size,
+//! so returning a large collection at once can fail. A `paginated` ink! message
+//! instead returns a [`Page`] of the collection together with the [`Cursor`] to
+//! the next page, if any. Callers read the whole collection by passing the
+//! returned cursor to the next call until it returns `None`.
+//!
+//! # Example
+//!
+//! ```
+//! #[ink::contract]
+//! mod registry {
+//!     use ink::{
+//!         pagination::{
+//!             paginate,
+//!             Cursor,
+//!             Page,
+//!         },
+//!         prelude::vec::Vec,
+//!     };
+//!
+//!     #[ink(storage)]
+//!     pub struct Registry {
+//!         entries: Vec<AccountId>,
+//!     }
+//!
+//!     impl Registry {
+//!         #[ink(constructor)]
+//!         pub fn new() -> Self {
+//!             Self {
+//!                 entries: Vec::new(),
+//!             }
+//!         }
+//!
+//!         /// Returns at most `limit` entries starting at `cursor`.
+//!         #[ink(message, paginated)]
+//!         pub fn entries(&self, cursor: Option<Cursor>, limit: u32) -> Page<AccountId> {
+//!             paginate(self.entries.iter().copied(), cursor, limit)
+//!         }
+//!     }
+//! }
+//! ```
+
+use ink_prelude::vec::Vec;
+
+/// The maximum encoded size of the items of a page returned by [`paginate`].
+///
+/// This is half the size of the static buffer the output of an ink! message is
+/// copied to, which leaves room for the remaining output of the message.
+pub const MAX_PAGE_SIZE: usize = 1 << 13;
+
+/// The position of the next page of a paginated collection.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct Cursor {
+    offset: u32,
+}
+
+impl Cursor {
+    /// Creates a cursor pointing to the item at `offset`.
+    pub fn new(offset: u32) -> Self {
+        Self { offset }
+    }
+
+    /// Returns the offset of the item the cursor points to.
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+}
+
+/// A page of items followed by the cursor to the next page, if any.
+pub type Page<T> = (Vec<T>, Option<Cursor>);
+
+/// Implemented by the outputs of `#[ink(message, paginated)]` messages.
+pub trait Paginated: private::Sealed {}
+
+impl<T> Paginated for Page<T> {}
+
+mod private {
+    /// Seals the implementations of [`Paginated`](super::Paginated).
+    pub trait Sealed {}
+
+    impl<T> Sealed for super::Page<T> {}
+}
+
+/// Returns the page of at most `limit` items of `items` starting at `cursor`.
+///
+/// Starts at the first item if `cursor` is `None`. The page is cut short if the
+/// encoded items would exceed [`MAX_PAGE_SIZE`] bytes, so that it can always be
+/// returned from an ink! message.
+///
+/// # Panics
+///
+/// - If `limit` is zero, since the returned cursor would never advance and callers
+///   reading the whole collection would loop forever.
+/// - If a single item exceeds [`MAX_PAGE_SIZE`] bytes, since the collection could
+///   not be paginated past it.
+pub fn paginate<T, I>(items: I, cursor: Option<Cursor>, limit: u32) -> Page<T>
+where
+    T: scale::Encode,
+    I: IntoIterator<Item = T>,
+{
+    assert!(limit > 0, "encountered a page limit of zero");
+    let offset = cursor.map(|cursor| cursor.offset()).unwrap_or_default();
+    let mut items = items.into_iter().skip(offset as usize).peekable();
+    let mut page = Vec::new();
+    let mut size = 0;
+    while page.len() < limit as usize {
+        let item_size = match items.peek() {
+            Some(item) => item.encoded_size(),
+            None => return (page, None),
+        };
+        if size + item_size > MAX_PAGE_SIZE {
+            assert!(
+                !page.is_empty(),
+                "encountered item exceeding the maximum page size"
+            );
+            break
+        }
+        size += item_size;
+        page.extend(items.next());
+    }
+    let next = items
+        .peek()
+        .map(|_| Cursor::new(offset + page.len() as u32));
+    (page, next)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paginate_works() {
+        let items = 0..10u32;
+        let (page, cursor) = paginate(items.clone(), None, 4);
+        assert_eq!(page, vec![0, 1, 2, 3]);
+        assert_eq!(cursor, Some(Cursor::new(4)));
+        let (page, cursor) = paginate(items.clone(), cursor, 4);
+        assert_eq!(page, vec![4, 5, 6, 7]);
+        let (page, cursor) = paginate(items, cursor, 4);
+        assert_eq!(page, vec![8, 9]);
+        assert_eq!(cursor, None);
+    }
+
+    #[test]
+    fn paginate_respects_max_page_size() {
+        let items = vec![[0u8; 1024]; 20];
+        let (page, cursor) = paginate(items.iter().copied(), None, 100);
+        assert_eq!(page.len(), MAX_PAGE_SIZE / 1024);
+        assert_eq!(cursor, Some(Cursor::new(page.len() as u32)));
+    }
+
+    #[test]
+    #[should_panic(expected = "encountered a page limit of zero")]
+    fn paginate_fails_for_zero_limit() {
+        let _ = paginate(0..10u32, None, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "encountered item exceeding the maximum page size")]
+    fn paginate_fails_for_oversized_item() {
+        let _ = paginate(vec![vec![0u8; MAX_PAGE_SIZE]], None, 1);
+    }
+}
@@ -0,0 +1,241 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A ready-made upgradeable proxy based on `delegate_call`.
+//!
+//! A proxy contract embeds [`Proxy`] into its storage and forwards every call that
+//! does not match one of its own selectors to the code of an implementation
+//! contract via `delegate_call`. The implementation code hence operates on the
+//! storage of the proxy contract, which allows upgrading the logic of a contract
+//! by pointing the proxy to a new code hash while keeping its state.
+//!
+//! # Note
+//!
+//! The code hash of the implementation and the admin account are stored in
+//! dedicated storage cells instead of the storage struct of the proxy contract.
+//! All messages of [`Proxy`] take `&self`, so proxy messages don't need to write
+//! the root storage cell which is shared with the implementation contract.
+//!
+//! # Example
+//!
+//! ```
+//! #[ink::contract]
+//! mod my_proxy {
+//!     use ink::proxy::{
+//!         Proxy,
+//!         ProxyError,
+//!     };
+//!
+//!     #[ink(storage)]
+//!     pub struct MyProxy {
+//!         proxy: Proxy,
+//!     }
+//!
+//!     impl MyProxy {
+//!         #[ink(constructor)]
+//!         pub fn new(implementation: Hash) -> Self {
+//!             Self {
+//!                 proxy: Proxy::new(implementation, Self::env().caller()),
+//!             }
+//!         }
+//!
+//!         /// Points the proxy to a new implementation, only callable by the admin.
+//!         #[ink(message)]
+//!         pub fn upgrade(&self, new_hash: Hash) -> Result<(), ProxyError> {
+//!             self.proxy.upgrade(new_hash)
+//!         }
+//!
+//!         /// Forwards all other calls to the implementation.
+//!         #[ink(message, payable, selector = _)]
+//!         pub fn forward(&self) {
+//!             self.proxy.forward()
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::env::{
+    call::build_delegate_call,
+    CallFlags,
+    DefaultEnvironment,
+    Environment,
+};
+use ink_primitives::{
+    Key,
+    KeyComposer,
+};
+
+/// The hash type used by the proxy.
+pub type Hash = <DefaultEnvironment as Environment>::Hash;
+/// The account identifier type used by the proxy.
+pub type AccountId = <DefaultEnvironment as Environment>::AccountId;
+
+/// The storage key of the code hash of the implementation.
+const IMPLEMENTATION_KEY: Key = KeyComposer::from_str("ink::proxy::implementation");
+/// The storage key of the admin account.
+const ADMIN_KEY: Key = KeyComposer::from_str("ink::proxy::admin");
+
+/// The errors of the [`Proxy`].
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum ProxyError {
+    /// Returned if the caller is not the admin of the proxy.
+    NotAdmin,
+}
+
+/// An upgradeable proxy forwarding calls to an implementation via `delegate_call`.
+///
+/// See the [module documentation](crate::proxy) for more details.
+#[derive(Debug, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, crate::storage::traits::StorageLayout)
+)]
+pub struct Proxy {}
+
+impl Proxy {
+    /// Stores the code hash of the `implementation` and the `admin` account.
+    ///
+    /// Must be called from the constructor of the proxy contract.
+    pub fn new(implementation: Hash, admin: AccountId) -> Self {
+        ink_env::set_contract_storage(&IMPLEMENTATION_KEY, &implementation);
+        ink_env::set_contract_storage(&ADMIN_KEY, &admin);
+        Self {}
+    }
+
+    /// Returns the code hash of the implementation.
+    pub fn implementation(&self) -> Hash {
+        Self::get(&IMPLEMENTATION_KEY)
+    }
+
+    /// Returns the admin account.
+    pub fn admin(&self) -> AccountId {
+        Self::get(&ADMIN_KEY)
+    }
+
+    /// Points the proxy to the implementation at `new_hash`.
+    ///
+    /// # Errors
+    ///
+    /// If the caller is not the admin.
+    pub fn upgrade(&self, new_hash: Hash) -> Result<(), ProxyError> {
+        self.ensure_admin()?;
+        ink_env::set_contract_storage(&IMPLEMENTATION_KEY, &new_hash);
+        Ok(())
+    }
+
+    /// Transfers the admin rights to `new_admin`.
+    ///
+    /// # Errors
+    ///
+    /// If the caller is not the admin.
+    pub fn change_admin(&self, new_admin: AccountId) -> Result<(), ProxyError> {
+        self.ensure_admin()?;
+        ink_env::set_contract_storage(&ADMIN_KEY, &new_admin);
+        Ok(())
+    }
+
+    /// Forwards the input of the current call to the implementation.
+    ///
+    /// The call is a tail call, so the output of the implementation is returned to
+    /// the caller and this function never returns.
+    ///
+    /// # Panics
+    ///
+    /// If the delegate call fails.
+    pub fn forward(&self) -> ! {
+        let implementation = self.implementation();
+        build_delegate_call::<DefaultEnvironment>(implementation)
+            .call_flags(
+                CallFlags::default()
+                    .set_forward_input(true)
+                    .set_tail_call(true),
+            )
+            .fire()
+            .unwrap_or_else(|err| {
+                panic!(
+                    "delegate call to {:?} failed due to {:?}",
+                    implementation, err
+                )
+            });
+        unreachable!("the forwarded call will never return since `tail_call` was set")
+    }
+
+    fn ensure_admin(&self) -> Result<(), ProxyError> {
+        if ink_env::caller::<DefaultEnvironment>() != self.admin() {
+            return Err(ProxyError::NotAdmin)
+        }
+        Ok(())
+    }
+
+    fn get<T: scale::Decode + scale::Encode>(key: &Key) -> T {
+        match ink_env::get_contract_storage(key) {
+            Ok(Some(value)) => value,
+            Ok(None) => panic!("proxy was not initialized"),
+            Err(_) => panic!("could not properly decode proxy storage entry"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_test(f: impl FnOnce(Proxy, AccountId)) {
+        ink_env::test::run_test::<DefaultEnvironment, _>(|accounts| {
+            ink_env::test::set_caller::<DefaultEnvironment>(accounts.alice);
+            f(
+                Proxy::new(Hash::from([0x01; 32]), accounts.alice),
+                accounts.bob,
+            );
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn upgrade_works() {
+        run_test(|proxy, _| {
+            assert_eq!(proxy.implementation(), Hash::from([0x01; 32]));
+            assert_eq!(proxy.upgrade(Hash::from([0x02; 32])), Ok(()));
+            assert_eq!(proxy.implementation(), Hash::from([0x02; 32]));
+        })
+    }
+
+    #[test]
+    fn upgrade_requires_admin() {
+        run_test(|proxy, bob| {
+            ink_env::test::set_caller::<DefaultEnvironment>(bob);
+            assert_eq!(
+                proxy.upgrade(Hash::from([0x02; 32])),
+                Err(ProxyError::NotAdmin)
+            );
+            assert_eq!(proxy.implementation(), Hash::from([0x01; 32]));
+        })
+    }
+
+    #[test]
+    fn change_admin_works() {
+        run_test(|proxy, bob| {
+            assert_eq!(proxy.change_admin(bob), Ok(()));
+            assert_eq!(proxy.admin(), bob);
+            assert_eq!(
+                proxy.upgrade(Hash::from([0x02; 32])),
+                Err(ProxyError::NotAdmin)
+            );
+            ink_env::test::set_caller::<DefaultEnvironment>(bob);
+            assert_eq!(proxy.upgrade(Hash::from([0x02; 32])), Ok(()));
+        })
+    }
+}
@@ -0,0 +1,122 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ink_prelude::vec::Vec;
+
+/// The raw input of a call dispatched to a wildcard message.
+///
+/// A message with a wildcard selector, i.e. `#[ink(message, selector = _)]`, may take
+/// a `RawInput` as its only input in order to receive the selector and the undecoded
+/// input bytes of the call it handles.
+///
+/// # Example
+///
+/// ```
+/// #[ink::contract]
+/// mod relayer {
+///     #[ink(storage)]
+///     pub struct Relayer {}
+///
+///     impl Relayer {
+///         #[ink(constructor)]
+///         pub fn new() -> Self {
+///             Self {}
+///         }
+///
+///         #[ink(message, selector = _)]
+///         pub fn fallback(&self, input: ink::RawInput) -> [u8; 4] {
+///             let _payload: &[u8] = input.data();
+///             input.selector()
+///         }
+///     }
+/// }
+/// ```
+///
+/// # Note
+///
+/// The SCALE encoding of a `RawInput` is its selector followed by its data without
+/// any length prefix. Decoding a `RawInput` consumes all remaining input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct RawInput {
+    selector: [u8; 4],
+    data: Vec<u8>,
+}
+
+impl RawInput {
+    /// Creates a raw input from the `selector` and the input `data` following it.
+    pub fn new(selector: [u8; 4], data: Vec<u8>) -> Self {
+        Self { selector, data }
+    }
+
+    /// Returns the selector of the call.
+    pub fn selector(&self) -> [u8; 4] {
+        self.selector
+    }
+
+    /// Returns the input bytes following the selector.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns the input bytes following the selector.
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+impl scale::Encode for RawInput {
+    fn size_hint(&self) -> usize {
+        self.selector.len() + self.data.len()
+    }
+
+    fn encode_to<T: scale::Output + ?Sized>(&self, dest: &mut T) {
+        dest.write(&self.selector);
+        dest.write(&self.data);
+    }
+}
+
+impl scale::Decode for RawInput {
+    fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+        let selector = <[u8; 4] as scale::Decode>::decode(input)?;
+        let len = input
+            .remaining_len()?
+            .ok_or("the length of the raw input is unknown")?;
+        let mut data = ink_prelude::vec![0; len];
+        input.read(&mut data)?;
+        Ok(Self { selector, data })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scale::{
+        Decode,
+        Encode,
+    };
+
+    #[test]
+    fn codec_works() {
+        let raw = RawInput::new([0xDE, 0xAD, 0xBE, 0xEF], vec![1, 2, 3]);
+        let encoded = raw.encode();
+        assert_eq!(encoded, vec![0xDE, 0xAD, 0xBE, 0xEF, 1, 2, 3]);
+        assert_eq!(RawInput::decode(&mut &encoded[..]), Ok(raw));
+    }
+
+    #[test]
+    fn decode_without_selector_fails() {
+        assert!(RawInput::decode(&mut &[0x01, 0x02][..]).is_err());
+    }
+}
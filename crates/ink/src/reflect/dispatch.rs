@@ -607,6 +607,12 @@ pub enum DispatchError {
     CouldNotReadInput,
     /// Invalidly paid an unpayable dispatchable.
     PaidUnpayableMessage,
+    /// Paid less than the minimum endowment of a refunding dispatchable.
+    InsufficientEndowment,
+    /// Failed to refund the excess value of a refunding dispatchable.
+    CouldNotRefundExcess,
+    /// Reentered a non-reentrant dispatchable.
+    ReentrantCall,
 }
 
 impl Display for DispatchError {
@@ -625,6 +631,9 @@ impl DispatchError {
             Self::InvalidParameters => "unable to decode input",
             Self::CouldNotReadInput => "could not read input",
             Self::PaidUnpayableMessage => "paid an unpayable message",
+            Self::InsufficientEndowment => "paid less than the minimum endowment",
+            Self::CouldNotRefundExcess => "could not refund excess endowment",
+            Self::ReentrantCall => "encountered reentrant call",
         }
     }
 }
@@ -0,0 +1,103 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+/// Implemented by the `#[ink(error)]` enums of a contract.
+///
+/// Every variant of an ink! error is assigned a stable 4-byte error code, the first
+/// four bytes of the BLAKE2b-256 hash of its path `Enum::Variant`, e.g.
+/// `Error::NotOwner`. The error code does not change when variants are reordered,
+/// added or removed, and equally named errors share it across all contracts, which
+/// allows wallets and indexers to decode failure reasons uniformly.
+///
+/// # Encoding
+///
+/// A variant is SCALE encoded as
+///
+/// | Bytes   | Content                                    |
+/// |---------|--------------------------------------------|
+/// | `0..4`  | the error code of the variant              |
+/// | `4..`   | the SCALE encoded fields of the variant    |
+///
+/// The metadata of the enum describes the error code as the leading `[u8; 4]` field
+/// of every variant, named `error_code` for variants with named fields. Unlike for
+/// other enums, the variant index of the metadata is not encoded, decoders identify
+/// the variant by its error code instead.
+///
+/// An ink! message returning `Err` of an ink! error reverts with the SCALE encoded
+/// `MessageResult<Result<T, E>>` as output, i.e. `0x00` for the absence of a
+/// `LangError`, `0x01` for the `Err` variant and the encoded error. Use
+/// [`decode_error_code`] to extract the error code from such an output.
+///
+/// # Usage
+///
+/// ```
+/// #[ink::contract]
+/// pub mod contract {
+///     #[ink(storage)]
+///     pub struct Contract {}
+///
+///     #[ink(error)]
+///     #[derive(Debug, PartialEq, Eq)]
+///     pub enum Error {
+///         NotOwner,
+///         InsufficientBalance { required: Balance },
+///     }
+///
+///     impl Contract {
+///         #[ink(constructor)]
+///         pub fn constructor() -> Self { Self {} }
+///
+///         #[ink(message)]
+///         pub fn message(&self) -> Result<(), Error> {
+///             Err(Error::NotOwner)
+///         }
+///     }
+/// }
+///
+/// use contract::Error;
+/// use ink::reflect::ContractError;
+///
+/// let error = Error::InsufficientBalance { required: 42 };
+/// let revert_data = scale::Encode::encode(&ink::MessageResult::Ok(Err::<(), _>(error)));
+/// assert_eq!(
+///     ink::reflect::decode_error_code(&revert_data),
+///     Some(Error::InsufficientBalance { required: 42 }.error_code())
+/// );
+/// assert_eq!(Error::ERROR_CODES[0].0, "NotOwner");
+/// ```
+pub trait ContractError {
+    /// The names of the variants with their error codes, in declaration order.
+    const ERROR_CODES: &'static [(&'static str, [u8; 4])];
+
+    /// Returns the error code of the variant.
+    fn error_code(&self) -> [u8; 4];
+}
+
+/// Returns the error code of the ink! error in the output of a reverted ink! message.
+///
+/// Returns `None` if the `output` does not hold an ink! error as laid out in the
+/// documentation of [`ContractError`], e.g. because the message failed with a
+/// `LangError`.
+///
+/// # Note
+///
+/// This only inspects the layout of the `output`, so messages returning `Err` of
+/// other types may yield a bogus error code. Check it against the
+/// [`ContractError::ERROR_CODES`] of the called contract where known.
+pub fn decode_error_code(output: &[u8]) -> Option<[u8; 4]> {
+    match output {
+        [0x00, 0x01, a, b, c, d, ..] => Some([*a, *b, *c, *d]),
+        _ => None,
+    }
+}
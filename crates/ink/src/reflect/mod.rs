@@ -25,6 +25,7 @@
 
 mod contract;
 mod dispatch;
+mod error;
 mod event;
 mod trait_def;
 
@@ -48,6 +49,10 @@ pub use self::{
         DispatchableMessageInfo,
         ExecuteDispatchable,
     },
+    error::{
+        decode_error_code,
+        ContractError,
+    },
     event::ContractEventBase,
     trait_def::{
         TraitDefinitionRegistry,
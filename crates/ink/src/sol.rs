@@ -0,0 +1,685 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Solidity ABI encoding for ink! contracts using `#[ink::contract(abi = "solidity")]`.
+//!
+//! The messages of such contracts take their inputs and return their outputs
+//! ABI encoded instead of SCALE encoded, so that they can be called by EVM
+//! tooling. Their selectors are the first four bytes of the Keccak-256 hash of
+//! their Solidity style signature, e.g. `balanceOf(address)`.
+//!
+//! | Rust                                | Solidity  |
+//! |-------------------------------------|-----------|
+//! | `bool`                              | `bool`    |
+//! | `u8` .. `u128`                      | `uint8` .. `uint128` |
+//! | `i8` .. `i128`                      | `int8` .. `int128` |
+//! | `AccountId`, `Hash`, `[u8; 32]`     | `bytes32` |
+//! | [`Address`]                         | `address` |
+//! | [`Bytes`]                           | `bytes`   |
+//! | `String`                            | `string`  |
+//! | `Vec<T>`                            | `T[]`     |
+//! | `(A, B, ..)`                        | `(A,B,..)` |
+//! | `Result<T, E>` as message output    | `T`, or `E` as revert data |
+//!
+//! # Example
+//!
+//! ```
+//! use ink::sol::{
+//!     Address,
+//!     SolEncoded,
+//! };
+//! use scale::{
+//!     Decode as _,
+//!     Encode as _,
+//! };
+//!
+//! // The input of `transfer(address,uint128)` following its selector.
+//! let input = SolEncoded((Address::from([0x01; 20]), 1_000_u128)).encode();
+//! assert_eq!(input.len(), 64);
+//! assert_eq!(&input[12..32], &[0x01; 20]);
+//!
+//! let decoded = SolEncoded::<(Address, u128)>::decode(&mut &input[..]).unwrap();
+//! assert_eq!(decoded.0 .1, 1_000);
+//! ```
+//!
+//! # Note
+//!
+//! Constructors of such contracts still take SCALE encoded inputs.
+
+use ink_prelude::{
+    string::String,
+    vec::Vec,
+};
+use ink_primitives::{
+    AccountId,
+    Hash,
+    LangError,
+};
+
+/// The size of a word of the Solidity ABI encoding.
+const WORD: usize = 32;
+
+/// Types which can be ABI encoded.
+pub trait SolEncode {
+    /// `true` if the type is dynamically sized, e.g. `string` or `T[]`.
+    ///
+    /// Dynamically sized values are referenced by an offset in the head of the
+    /// enclosing tuple and encoded in its tail.
+    const DYNAMIC: bool;
+
+    /// Appends the ABI encoding of the value to `out`.
+    fn encode_sol(&self, out: &mut Vec<u8>);
+}
+
+/// Types which can be ABI decoded.
+pub trait SolDecode: Sized {
+    /// `true` if the type is dynamically sized, e.g. `string` or `T[]`.
+    const DYNAMIC: bool;
+
+    /// The number of bytes the value occupies in the head of an enclosing tuple.
+    const HEAD_SIZE: usize = WORD;
+
+    /// Decodes the value whose ABI encoding starts at the beginning of `data`.
+    fn decode_sol(data: &[u8]) -> Result<Self, scale::Error>;
+}
+
+/// Wraps a tuple of values into their ABI encoding as a sequence of parameters.
+///
+/// The SCALE codec of `SolEncoded` is the ABI encoding of the wrapped tuple
+/// without any length prefix. Decoding a `SolEncoded` consumes all remaining input.
+///
+/// This is how ink! encodes the inputs and outputs of messages of contracts using
+/// `#[ink::contract(abi = "solidity")]`, and it allows to call such contracts via
+/// the call builder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolEncoded<T>(pub T);
+
+impl<T> scale::Encode for SolEncoded<T>
+where
+    T: SolEncode,
+{
+    fn encode_to<O: scale::Output + ?Sized>(&self, dest: &mut O) {
+        let mut out = Vec::new();
+        self.0.encode_sol(&mut out);
+        dest.write(&out);
+    }
+}
+
+impl<T> scale::Decode for SolEncoded<T>
+where
+    T: SolDecode,
+{
+    fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+        let len = input
+            .remaining_len()?
+            .ok_or("the length of the ABI encoded input is unknown")?;
+        let mut data = ink_prelude::vec![0; len];
+        input.read(&mut data)?;
+        T::decode_sol(&data).map(Self)
+    }
+}
+
+impl<T> From<SolEncoded<(T,)>> for Result<T, LangError> {
+    fn from(encoded: SolEncoded<(T,)>) -> Self {
+        Ok(encoded.0 .0)
+    }
+}
+
+/// A 20 byte Ethereum address, encoded as Solidity `address`.
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    scale::Encode,
+    scale::Decode,
+    derive_more::From,
+)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, crate::storage::traits::StorageLayout)
+)]
+pub struct Address([u8; 20]);
+
+impl AsRef<[u8; 20]> for Address {
+    fn as_ref(&self) -> &[u8; 20] {
+        &self.0
+    }
+}
+
+/// A byte sequence, encoded as Solidity `bytes` instead of `uint8[]`.
+#[derive(
+    Debug, Default, Clone, PartialEq, Eq, scale::Encode, scale::Decode, derive_more::From,
+)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, crate::storage::traits::StorageLayout)
+)]
+pub struct Bytes(pub Vec<u8>);
+
+/// Returns the word of `data` at `offset`.
+fn word(data: &[u8], offset: usize) -> Result<&[u8; WORD], scale::Error> {
+    offset
+        .checked_add(WORD)
+        .and_then(|end| data.get(offset..end))
+        .and_then(|word| word.try_into().ok())
+        .ok_or_else(|| "ABI encoded input is too short".into())
+}
+
+/// Appends the given value as a word to `out`.
+fn write_usize(out: &mut Vec<u8>, value: usize) {
+    out.extend_from_slice(&[0; WORD - 8]);
+    out.extend_from_slice(&(value as u64).to_be_bytes());
+}
+
+/// Reads a length or offset from the word of `data` at `offset`.
+fn read_usize(data: &[u8], offset: usize) -> Result<usize, scale::Error> {
+    let word = word(data, offset)?;
+    if word[..WORD - 8].iter().any(|byte| *byte != 0) {
+        return Err("ABI encoded length or offset is too large".into())
+    }
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(&word[WORD - 8..]);
+    usize::try_from(u64::from_be_bytes(bytes))
+        .map_err(|_| "ABI encoded length or offset is too large".into())
+}
+
+/// Appends the head and tail encoding of the given members of a tuple to `out`.
+///
+/// Every member is given as its encoding and whether it is dynamically sized.
+fn encode_members(out: &mut Vec<u8>, members: &[(bool, Vec<u8>)]) {
+    let head_size: usize = members
+        .iter()
+        .map(|(dynamic, encoded)| if *dynamic { WORD } else { encoded.len() })
+        .sum();
+    let mut tail = Vec::new();
+    for (dynamic, encoded) in members {
+        if *dynamic {
+            write_usize(out, head_size + tail.len());
+            tail.extend_from_slice(encoded);
+        } else {
+            out.extend_from_slice(encoded);
+        }
+    }
+    out.extend_from_slice(&tail);
+}
+
+/// Returns the ABI encoding of a single value.
+fn encode_member<T>(value: &T) -> (bool, Vec<u8>)
+where
+    T: SolEncode + ?Sized,
+{
+    let mut encoded = Vec::new();
+    value.encode_sol(&mut encoded);
+    (T::DYNAMIC, encoded)
+}
+
+/// Decodes a tuple member whose head is at `offset` of the tuple encoding `data`.
+fn decode_member<T>(data: &[u8], offset: usize) -> Result<T, scale::Error>
+where
+    T: SolDecode,
+{
+    if T::DYNAMIC {
+        let start = read_usize(data, offset)?;
+        let tail = data
+            .get(start..)
+            .ok_or("ABI encoded offset is out of bounds")?;
+        T::decode_sol(tail)
+    } else {
+        let head = data.get(offset..).ok_or("ABI encoded input is too short")?;
+        T::decode_sol(head)
+    }
+}
+
+impl<T> SolEncode for &T
+where
+    T: SolEncode + ?Sized,
+{
+    const DYNAMIC: bool = T::DYNAMIC;
+
+    fn encode_sol(&self, out: &mut Vec<u8>) {
+        (*self).encode_sol(out)
+    }
+}
+
+impl SolEncode for bool {
+    const DYNAMIC: bool = false;
+
+    fn encode_sol(&self, out: &mut Vec<u8>) {
+        (*self as u8).encode_sol(out)
+    }
+}
+
+impl SolDecode for bool {
+    const DYNAMIC: bool = false;
+
+    fn decode_sol(data: &[u8]) -> Result<Self, scale::Error> {
+        match u8::decode_sol(data)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err("ABI encoded bool is out of range".into()),
+        }
+    }
+}
+
+macro_rules! impl_sol_uint {
+    ( $( $ty:ty ),* ) => {
+        $(
+            impl SolEncode for $ty {
+                const DYNAMIC: bool = false;
+
+                fn encode_sol(&self, out: &mut Vec<u8>) {
+                    let bytes = self.to_be_bytes();
+                    out.extend_from_slice(&[0; WORD][bytes.len()..]);
+                    out.extend_from_slice(&bytes);
+                }
+            }
+
+            impl SolDecode for $ty {
+                const DYNAMIC: bool = false;
+
+                fn decode_sol(data: &[u8]) -> Result<Self, scale::Error> {
+                    let word = word(data, 0)?;
+                    let (padding, bytes) = word.split_at(WORD - ::core::mem::size_of::<$ty>());
+                    if padding.iter().any(|byte| *byte != 0) {
+                        return Err("ABI encoded integer is out of range".into())
+                    }
+                    Ok(<$ty>::from_be_bytes(bytes.try_into().expect("size matches the integer")))
+                }
+            }
+        )*
+    };
+}
+impl_sol_uint!(u8, u16, u32, u64, u128);
+
+macro_rules! impl_sol_int {
+    ( $( $ty:ty ),* ) => {
+        $(
+            impl SolEncode for $ty {
+                const DYNAMIC: bool = false;
+
+                fn encode_sol(&self, out: &mut Vec<u8>) {
+                    let bytes = self.to_be_bytes();
+                    let sign = if *self < 0 { 0xFF } else { 0x00 };
+                    out.extend_from_slice(&[sign; WORD][bytes.len()..]);
+                    out.extend_from_slice(&bytes);
+                }
+            }
+
+            impl SolDecode for $ty {
+                const DYNAMIC: bool = false;
+
+                fn decode_sol(data: &[u8]) -> Result<Self, scale::Error> {
+                    let word = word(data, 0)?;
+                    let (padding, bytes) = word.split_at(WORD - ::core::mem::size_of::<$ty>());
+                    let value = <$ty>::from_be_bytes(bytes.try_into().expect("size matches the integer"));
+                    let sign = if value < 0 { 0xFF } else { 0x00 };
+                    if padding.iter().any(|byte| *byte != sign) {
+                        return Err("ABI encoded integer is out of range".into())
+                    }
+                    Ok(value)
+                }
+            }
+        )*
+    };
+}
+impl_sol_int!(i8, i16, i32, i64, i128);
+
+impl SolEncode for [u8; 32] {
+    const DYNAMIC: bool = false;
+
+    fn encode_sol(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self)
+    }
+}
+
+impl SolDecode for [u8; 32] {
+    const DYNAMIC: bool = false;
+
+    fn decode_sol(data: &[u8]) -> Result<Self, scale::Error> {
+        word(data, 0).copied()
+    }
+}
+
+macro_rules! impl_sol_bytes32 {
+    ( $( $ty:ty ),* ) => {
+        $(
+            impl SolEncode for $ty {
+                const DYNAMIC: bool = false;
+
+                fn encode_sol(&self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(AsRef::<[u8]>::as_ref(self))
+                }
+            }
+
+            impl SolDecode for $ty {
+                const DYNAMIC: bool = false;
+
+                fn decode_sol(data: &[u8]) -> Result<Self, scale::Error> {
+                    <[u8; 32]>::decode_sol(data).map(Into::into)
+                }
+            }
+        )*
+    };
+}
+impl_sol_bytes32!(AccountId, Hash);
+
+impl SolEncode for Address {
+    const DYNAMIC: bool = false;
+
+    fn encode_sol(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&[0; WORD - 20]);
+        out.extend_from_slice(&self.0);
+    }
+}
+
+impl SolDecode for Address {
+    const DYNAMIC: bool = false;
+
+    fn decode_sol(data: &[u8]) -> Result<Self, scale::Error> {
+        let word = word(data, 0)?;
+        let (padding, bytes) = word.split_at(WORD - 20);
+        if padding.iter().any(|byte| *byte != 0) {
+            return Err("ABI encoded address is out of range".into())
+        }
+        Ok(Self(bytes.try_into().expect("address has 20 bytes")))
+    }
+}
+
+/// Appends the given bytes with their length and padded to a whole word to `out`.
+fn encode_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    write_usize(out, bytes.len());
+    out.extend_from_slice(bytes);
+    let padding = (WORD - bytes.len() % WORD) % WORD;
+    out.extend_from_slice(&[0; WORD][..padding]);
+}
+
+/// Decodes bytes prefixed with their length.
+fn decode_bytes(data: &[u8]) -> Result<&[u8], scale::Error> {
+    let len = read_usize(data, 0)?;
+    WORD.checked_add(len)
+        .and_then(|end| data.get(WORD..end))
+        .ok_or_else(|| "ABI encoded bytes are out of bounds".into())
+}
+
+impl SolEncode for Bytes {
+    const DYNAMIC: bool = true;
+
+    fn encode_sol(&self, out: &mut Vec<u8>) {
+        encode_bytes(out, &self.0)
+    }
+}
+
+impl SolDecode for Bytes {
+    const DYNAMIC: bool = true;
+
+    fn decode_sol(data: &[u8]) -> Result<Self, scale::Error> {
+        decode_bytes(data).map(|bytes| Self(bytes.to_vec()))
+    }
+}
+
+impl SolEncode for str {
+    const DYNAMIC: bool = true;
+
+    fn encode_sol(&self, out: &mut Vec<u8>) {
+        encode_bytes(out, self.as_bytes())
+    }
+}
+
+impl SolEncode for String {
+    const DYNAMIC: bool = true;
+
+    fn encode_sol(&self, out: &mut Vec<u8>) {
+        self.as_str().encode_sol(out)
+    }
+}
+
+impl SolDecode for String {
+    const DYNAMIC: bool = true;
+
+    fn decode_sol(data: &[u8]) -> Result<Self, scale::Error> {
+        let bytes = decode_bytes(data)?;
+        String::from_utf8(bytes.to_vec())
+            .map_err(|_| "ABI encoded string is not valid UTF-8".into())
+    }
+}
+
+impl<T> SolEncode for [T]
+where
+    T: SolEncode,
+{
+    const DYNAMIC: bool = true;
+
+    fn encode_sol(&self, out: &mut Vec<u8>) {
+        write_usize(out, self.len());
+        let members = self.iter().map(encode_member).collect::<Vec<_>>();
+        encode_members(out, &members);
+    }
+}
+
+impl<T> SolEncode for Vec<T>
+where
+    T: SolEncode,
+{
+    const DYNAMIC: bool = true;
+
+    fn encode_sol(&self, out: &mut Vec<u8>) {
+        self.as_slice().encode_sol(out)
+    }
+}
+
+impl<T> SolDecode for Vec<T>
+where
+    T: SolDecode,
+{
+    const DYNAMIC: bool = true;
+
+    fn decode_sol(data: &[u8]) -> Result<Self, scale::Error> {
+        let len = read_usize(data, 0)?;
+        let elements = &data[WORD..];
+        // Every element occupies at least its head, which rules out lengths
+        // whose allocation would exceed the input.
+        if len.saturating_mul(T::HEAD_SIZE.max(1)) > elements.len() {
+            return Err("ABI encoded array is out of bounds".into())
+        }
+        (0..len)
+            .map(|index| decode_member(elements, index * T::HEAD_SIZE))
+            .collect()
+    }
+}
+
+/// Encodes the value of `Ok` or the error of `Err` as the single return value of a
+/// message.
+///
+/// Messages returning `Err` revert, so the caller receives the ABI encoded error as
+/// revert data instead of the ABI encoded value.
+///
+/// # Note
+///
+/// Since the encoding depends on the variant it is only supported as the output of
+/// a message, but not nested within other types.
+impl<T, E> SolEncode for Result<T, E>
+where
+    T: SolEncode,
+    E: SolEncode,
+{
+    // The encoding is a complete sequence of return values on its own.
+    const DYNAMIC: bool = false;
+
+    fn encode_sol(&self, out: &mut Vec<u8>) {
+        match self {
+            Ok(value) => (value,).encode_sol(out),
+            Err(error) => (error,).encode_sol(out),
+        }
+    }
+}
+
+/// Decodes the return value of a message returning `Result` as `Ok`.
+///
+/// # Note
+///
+/// The ABI encoding does not contain the variant. The revert data of a message
+/// returning `Err` has to be decoded as `SolEncoded<(E,)>` instead.
+impl<T, E> SolDecode for Result<T, E>
+where
+    T: SolDecode,
+{
+    const DYNAMIC: bool = false;
+
+    fn decode_sol(data: &[u8]) -> Result<Self, scale::Error> {
+        <(T,)>::decode_sol(data).map(|(value,)| Ok(value))
+    }
+}
+
+macro_rules! impl_sol_tuple {
+    ( $( $ty:ident ),* ) => {
+        impl<$( $ty ),*> SolEncode for ( $( $ty, )* )
+        where
+            $( $ty: SolEncode, )*
+        {
+            const DYNAMIC: bool = false $( || $ty::DYNAMIC )*;
+
+            #[allow(non_snake_case, unused_variables)]
+            fn encode_sol(&self, out: &mut Vec<u8>) {
+                let ( $( $ty, )* ) = self;
+                encode_members(out, &[ $( encode_member($ty) ),* ]);
+            }
+        }
+
+        impl<$( $ty ),*> SolDecode for ( $( $ty, )* )
+        where
+            $( $ty: SolDecode, )*
+        {
+            const DYNAMIC: bool = false $( || $ty::DYNAMIC )*;
+            const HEAD_SIZE: usize = if <Self as SolDecode>::DYNAMIC { WORD } else { 0 $( + $ty::HEAD_SIZE )* };
+
+            #[allow(unused_assignments, unused_mut, unused_variables)]
+            fn decode_sol(data: &[u8]) -> Result<Self, scale::Error> {
+                let mut offset = 0;
+                Ok(( $(
+                    {
+                        let member = decode_member::<$ty>(data, offset)?;
+                        offset += $ty::HEAD_SIZE;
+                        member
+                    },
+                )* ))
+            }
+        }
+    };
+}
+impl_sol_tuple!();
+impl_sol_tuple!(A);
+impl_sol_tuple!(A, B);
+impl_sol_tuple!(A, B, C);
+impl_sol_tuple!(A, B, C, D);
+impl_sol_tuple!(A, B, C, D, E);
+impl_sol_tuple!(A, B, C, D, E, F);
+impl_sol_tuple!(A, B, C, D, E, F, G);
+impl_sol_tuple!(A, B, C, D, E, F, G, H);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scale::{
+        Decode,
+        Encode,
+    };
+
+    fn hex(encoded: &[u8]) -> Vec<String> {
+        encoded
+            .chunks(WORD)
+            .map(|word| word.iter().map(|byte| format!("{:02x}", byte)).collect())
+            .collect()
+    }
+
+    fn roundtrip<T>(value: T) -> Vec<u8>
+    where
+        T: SolEncode + SolDecode + PartialEq + core::fmt::Debug + Clone,
+    {
+        let encoded = SolEncoded(value.clone()).encode();
+        assert_eq!(
+            SolEncoded::<T>::decode(&mut &encoded[..]),
+            Ok(SolEncoded(value))
+        );
+        encoded
+    }
+
+    #[test]
+    fn static_values_work() {
+        let encoded = roundtrip((true, -1_i8, 0x0102_u16, Address::from([0xAB; 20])));
+        assert_eq!(
+            hex(&encoded),
+            [
+                format!("{:064x}", 1),
+                "f".repeat(64),
+                format!("{:064x}", 0x0102),
+                format!("{}{}", "0".repeat(24), "ab".repeat(20)),
+            ]
+        );
+        roundtrip((u128::MAX, i128::MIN, AccountId::from([0x01; 32])));
+    }
+
+    #[test]
+    fn dynamic_values_work() {
+        // The encoding of `f(uint256,uint32[],bytes10,bytes)` from the Solidity docs
+        // with `bytes10` left out: `f(0x123, [0x456, 0x789], "Hello, world!")`.
+        let encoded = roundtrip((
+            0x123_u128,
+            vec![0x456_u32, 0x789],
+            Bytes(b"Hello, world!".to_vec()),
+        ));
+        assert_eq!(
+            hex(&encoded),
+            [
+                format!("{:064x}", 0x123),
+                format!("{:064x}", 0x60),
+                format!("{:064x}", 0xc0),
+                format!("{:064x}", 2),
+                format!("{:064x}", 0x456),
+                format!("{:064x}", 0x789),
+                format!("{:064x}", 13),
+                format!("{:0<64}", "48656c6c6f2c20776f726c6421"),
+            ]
+        );
+        roundtrip((vec![String::from("one"), String::from("two")], ()));
+        roundtrip(vec![(1_u8, String::from("nested"))]);
+    }
+
+    #[test]
+    fn result_encodes_its_variant() {
+        let ok: Result<u128, String> = Ok(7);
+        assert_eq!(SolEncoded((ok,)).encode(), SolEncoded((7_u128,)).encode());
+        let err: Result<u128, String> = Err(String::from("error"));
+        assert_eq!(
+            SolEncoded((err,)).encode(),
+            SolEncoded((String::from("error"),)).encode()
+        );
+    }
+
+    #[test]
+    fn invalid_input_fails() {
+        let too_large = SolEncoded((256_u16,)).encode();
+        assert!(SolEncoded::<(u8,)>::decode(&mut &too_large[..]).is_err());
+        let not_bool = SolEncoded((2_u8,)).encode();
+        assert!(SolEncoded::<(bool,)>::decode(&mut &not_bool[..]).is_err());
+        let huge_array = SolEncoded((0x20_u8, u64::MAX)).encode();
+        assert!(SolEncoded::<(Vec<u8>,)>::decode(&mut &huge_array[..]).is_err());
+        assert!(SolEncoded::<(u8,)>::decode(&mut &[0; 31][..]).is_err());
+    }
+}
@@ -0,0 +1,37 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Standard token interfaces as ink! trait definitions.
+//!
+//! The trait definitions follow the Polkadot Standard Proposals (PSPs), so the
+//! selectors of their messages match the ones of other implementations of the
+//! same standard.
+//!
+//! Every standard comes with a storage item implementing its default logic.
+//! Contracts embed it into their storage and forward the messages of their
+//! trait implementation to it.
+//!
+//! # Note
+//!
+//! The standards use the `AccountId` and `Balance` types of the
+//! [`DefaultEnvironment`](ink_env::DefaultEnvironment).
+
+pub mod psp22;
+pub mod psp34;
+pub mod psp37;
+
+/// The account identifier type used by the standards.
+pub type AccountId = <ink_env::DefaultEnvironment as ink_env::Environment>::AccountId;
+/// The balance type used by the standards.
+pub type Balance = <ink_env::DefaultEnvironment as ink_env::Environment>::Balance;
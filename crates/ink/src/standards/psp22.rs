@@ -0,0 +1,296 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The PSP22 fungible token standard.
+//!
+//! See <https://github.com/w3f/PSPs/blob/master/PSPs/psp-22.md>.
+
+use super::{
+    AccountId,
+    Balance,
+};
+use ink_prelude::{
+    string::String,
+    vec::Vec,
+};
+use ink_storage::{
+    traits::{
+        AutoKey,
+        StorageKey,
+    },
+    Mapping,
+};
+
+/// The errors of the PSP22 standard.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PSP22Error {
+    /// Custom error type for cases not covered by the standard.
+    Custom(String),
+    /// Returned if there are not enough tokens to complete the operation.
+    InsufficientBalance,
+    /// Returned if there is not enough allowance to complete the operation.
+    InsufficientAllowance,
+    /// Returned if the recipient is the zero address.
+    ZeroRecipientAddress,
+    /// Returned if the sender is the zero address.
+    ZeroSenderAddress,
+    /// Returned if a safe transfer check failed.
+    SafeTransferCheckFailed(String),
+}
+
+/// The PSP22 fungible token interface.
+#[crate::trait_definition]
+pub trait PSP22 {
+    /// Returns the total token supply.
+    #[ink(message)]
+    fn total_supply(&self) -> Balance;
+
+    /// Returns the account balance of `owner`.
+    #[ink(message)]
+    fn balance_of(&self, owner: AccountId) -> Balance;
+
+    /// Returns the amount which `spender` is still allowed to withdraw from `owner`.
+    #[ink(message)]
+    fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance;
+
+    /// Transfers `value` amount of tokens from the caller's account to account `to`
+    /// with additional `data` in unspecified format.
+    #[ink(message)]
+    fn transfer(
+        &mut self,
+        to: AccountId,
+        value: Balance,
+        data: Vec<u8>,
+    ) -> Result<(), PSP22Error>;
+
+    /// Transfers `value` tokens on the behalf of `from` to the account `to`
+    /// with additional `data` in unspecified format.
+    #[ink(message)]
+    fn transfer_from(
+        &mut self,
+        from: AccountId,
+        to: AccountId,
+        value: Balance,
+        data: Vec<u8>,
+    ) -> Result<(), PSP22Error>;
+
+    /// Allows `spender` to withdraw from the caller's account multiple times,
+    /// up to the `value` amount.
+    #[ink(message)]
+    fn approve(&mut self, spender: AccountId, value: Balance) -> Result<(), PSP22Error>;
+
+    /// Atomically increases the allowance granted to `spender` by the caller.
+    #[ink(message)]
+    fn increase_allowance(
+        &mut self,
+        spender: AccountId,
+        delta_value: Balance,
+    ) -> Result<(), PSP22Error>;
+
+    /// Atomically decreases the allowance granted to `spender` by the caller.
+    #[ink(message)]
+    fn decrease_allowance(
+        &mut self,
+        spender: AccountId,
+        delta_value: Balance,
+    ) -> Result<(), PSP22Error>;
+}
+
+/// The storage of a PSP22 token with the default implementation of its logic.
+///
+/// Contracts implementing [`PSP22`] can embed this into their storage and
+/// forward their messages to it. Emitting the `Transfer` and `Approval` events
+/// is left to the contract.
+#[crate::storage_item]
+#[derive(Default, Debug)]
+pub struct PSP22Data<KEY: StorageKey = AutoKey> {
+    total_supply: Balance,
+    balances: Mapping<AccountId, Balance>,
+    allowances: Mapping<(AccountId, AccountId), Balance>,
+}
+
+impl<KEY: StorageKey> PSP22Data<KEY> {
+    /// Returns the total token supply.
+    pub fn total_supply(&self) -> Balance {
+        self.total_supply
+    }
+
+    /// Returns the account balance of `owner`.
+    pub fn balance_of(&self, owner: AccountId) -> Balance {
+        self.balances.get(owner).unwrap_or_default()
+    }
+
+    /// Returns the amount which `spender` is still allowed to withdraw from `owner`.
+    pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+        self.allowances.get((owner, spender)).unwrap_or_default()
+    }
+
+    /// Transfers `value` tokens from `from` to `to`.
+    pub fn transfer(
+        &mut self,
+        from: AccountId,
+        to: AccountId,
+        value: Balance,
+    ) -> Result<(), PSP22Error> {
+        let from_balance = self.balance_of(from);
+        if from_balance < value {
+            return Err(PSP22Error::InsufficientBalance)
+        }
+        self.balances.insert(from, &(from_balance - value));
+        let to_balance = self.balance_of(to);
+        self.balances.insert(to, &(to_balance + value));
+        Ok(())
+    }
+
+    /// Transfers `value` tokens from `from` to `to` on behalf of `spender`.
+    ///
+    /// Decreases the allowance of `spender` granted by `from` accordingly.
+    pub fn transfer_from(
+        &mut self,
+        spender: AccountId,
+        from: AccountId,
+        to: AccountId,
+        value: Balance,
+    ) -> Result<(), PSP22Error> {
+        let allowance = self.allowance(from, spender);
+        if allowance < value {
+            return Err(PSP22Error::InsufficientAllowance)
+        }
+        self.transfer(from, to, value)?;
+        self.allowances
+            .insert((from, spender), &(allowance - value));
+        Ok(())
+    }
+
+    /// Sets the allowance of `spender` granted by `owner` to `value`.
+    pub fn approve(&mut self, owner: AccountId, spender: AccountId, value: Balance) {
+        self.allowances.insert((owner, spender), &value);
+    }
+
+    /// Increases the allowance of `spender` granted by `owner` by `delta_value`.
+    ///
+    /// Returns the new allowance.
+    pub fn increase_allowance(
+        &mut self,
+        owner: AccountId,
+        spender: AccountId,
+        delta_value: Balance,
+    ) -> Balance {
+        let allowance = self.allowance(owner, spender).saturating_add(delta_value);
+        self.approve(owner, spender, allowance);
+        allowance
+    }
+
+    /// Decreases the allowance of `spender` granted by `owner` by `delta_value`.
+    ///
+    /// Returns the new allowance.
+    pub fn decrease_allowance(
+        &mut self,
+        owner: AccountId,
+        spender: AccountId,
+        delta_value: Balance,
+    ) -> Result<Balance, PSP22Error> {
+        let allowance = self
+            .allowance(owner, spender)
+            .checked_sub(delta_value)
+            .ok_or(PSP22Error::InsufficientAllowance)?;
+        self.approve(owner, spender, allowance);
+        Ok(allowance)
+    }
+
+    /// Creates `value` new tokens and assigns them to `to`.
+    pub fn mint(&mut self, to: AccountId, value: Balance) -> Result<(), PSP22Error> {
+        let total_supply = self
+            .total_supply
+            .checked_add(value)
+            .ok_or_else(|| PSP22Error::Custom(String::from("total supply overflow")))?;
+        self.total_supply = total_supply;
+        let to_balance = self.balance_of(to);
+        self.balances.insert(to, &(to_balance + value));
+        Ok(())
+    }
+
+    /// Destroys `value` tokens owned by `from`.
+    pub fn burn(&mut self, from: AccountId, value: Balance) -> Result<(), PSP22Error> {
+        let from_balance = self.balance_of(from);
+        if from_balance < value {
+            return Err(PSP22Error::InsufficientBalance)
+        }
+        self.balances.insert(from, &(from_balance - value));
+        self.total_supply -= value;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_test(f: impl FnOnce(&mut PSP22Data)) {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            f(&mut PSP22Data::default());
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn mint_and_transfer_works() {
+        run_test(|token| {
+            let (alice, bob) = (AccountId::from([0x01; 32]), AccountId::from([0x02; 32]));
+            token.mint(alice, 100).unwrap();
+            assert_eq!(token.total_supply(), 100);
+
+            assert_eq!(token.transfer(alice, bob, 30), Ok(()));
+            assert_eq!(token.balance_of(alice), 70);
+            assert_eq!(token.balance_of(bob), 30);
+            assert_eq!(
+                token.transfer(bob, alice, 31),
+                Err(PSP22Error::InsufficientBalance)
+            );
+        })
+    }
+
+    #[test]
+    fn transfer_from_works() {
+        run_test(|token| {
+            let (alice, bob) = (AccountId::from([0x01; 32]), AccountId::from([0x02; 32]));
+            token.mint(alice, 100).unwrap();
+            assert_eq!(
+                token.transfer_from(bob, alice, bob, 10),
+                Err(PSP22Error::InsufficientAllowance)
+            );
+
+            token.approve(alice, bob, 10);
+            assert_eq!(token.increase_allowance(alice, bob, 5), 15);
+            assert_eq!(token.decrease_allowance(alice, bob, 3), Ok(12));
+            assert_eq!(token.transfer_from(bob, alice, bob, 10), Ok(()));
+            assert_eq!(token.allowance(alice, bob), 2);
+            assert_eq!(token.balance_of(bob), 10);
+        })
+    }
+
+    #[test]
+    fn burn_works() {
+        run_test(|token| {
+            let alice = AccountId::from([0x01; 32]);
+            token.mint(alice, 100).unwrap();
+            assert_eq!(token.burn(alice, 101), Err(PSP22Error::InsufficientBalance));
+            assert_eq!(token.burn(alice, 40), Ok(()));
+            assert_eq!(token.total_supply(), 60);
+            assert_eq!(token.balance_of(alice), 60);
+        })
+    }
+}
@@ -0,0 +1,305 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The PSP34 non-fungible token standard.
+//!
+//! See <https://github.com/w3f/PSPs/blob/master/PSPs/psp-34.md>.
+
+use super::{
+    AccountId,
+    Balance,
+};
+use ink_prelude::{
+    string::String,
+    vec::Vec,
+};
+use ink_storage::{
+    traits::{
+        AutoKey,
+        StorageKey,
+    },
+    Mapping,
+};
+
+/// The identifier of a PSP34 token.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, crate::storage::traits::StorageLayout)
+)]
+pub enum Id {
+    /// An identifier of type `u8`.
+    U8(u8),
+    /// An identifier of type `u16`.
+    U16(u16),
+    /// An identifier of type `u32`.
+    U32(u32),
+    /// An identifier of type `u64`.
+    U64(u64),
+    /// An identifier of type `u128`.
+    U128(u128),
+    /// An identifier of arbitrary bytes.
+    Bytes(Vec<u8>),
+}
+
+/// The errors of the PSP34 standard.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PSP34Error {
+    /// Custom error type for cases not covered by the standard.
+    Custom(String),
+    /// Returned if the owner approves themselves.
+    SelfApprove,
+    /// Returned if the caller doesn't have the allowance to transfer the token.
+    NotApproved,
+    /// Returned if the token already exists.
+    TokenExists,
+    /// Returned if the token doesn't exist.
+    TokenNotExists,
+    /// Returned if a safe transfer check failed.
+    SafeTransferCheckFailed(String),
+}
+
+/// The PSP34 non-fungible token interface.
+#[crate::trait_definition]
+pub trait PSP34 {
+    /// Returns the collection identifier.
+    #[ink(message)]
+    fn collection_id(&self) -> Id;
+
+    /// Returns the number of tokens owned by `owner`.
+    #[ink(message)]
+    fn balance_of(&self, owner: AccountId) -> u32;
+
+    /// Returns the owner of the token `id` if it exists.
+    #[ink(message)]
+    fn owner_of(&self, id: Id) -> Option<AccountId>;
+
+    /// Returns `true` if `operator` is allowed to transfer the token `id` of `owner`,
+    /// or all tokens of `owner` if `id` is `None`.
+    #[ink(message)]
+    fn allowance(&self, owner: AccountId, operator: AccountId, id: Option<Id>) -> bool;
+
+    /// Approves or disapproves `operator` to transfer the token `id` of the caller,
+    /// or all tokens of the caller if `id` is `None`.
+    #[ink(message)]
+    fn approve(
+        &mut self,
+        operator: AccountId,
+        id: Option<Id>,
+        approved: bool,
+    ) -> Result<(), PSP34Error>;
+
+    /// Transfers the token `id` from the caller to `to`
+    /// with additional `data` in unspecified format.
+    #[ink(message)]
+    fn transfer(
+        &mut self,
+        to: AccountId,
+        id: Id,
+        data: Vec<u8>,
+    ) -> Result<(), PSP34Error>;
+
+    /// Returns the total number of tokens.
+    #[ink(message)]
+    fn total_supply(&self) -> Balance;
+}
+
+/// The owner, the operator and the optional token of an allowance.
+type AllowanceKey = (AccountId, AccountId, Option<Id>);
+
+/// The storage of a PSP34 token with the default implementation of its logic.
+///
+/// Contracts implementing [`PSP34`] can embed this into their storage and
+/// forward their messages to it. Emitting the `Transfer` and `Approval` events
+/// is left to the contract.
+#[crate::storage_item]
+#[derive(Default)]
+pub struct PSP34Data<KEY: StorageKey = AutoKey> {
+    total_supply: Balance,
+    owners: Mapping<Id, AccountId>,
+    balances: Mapping<AccountId, u32>,
+    allowances: Mapping<AllowanceKey, ()>,
+}
+
+impl<KEY: StorageKey> PSP34Data<KEY> {
+    /// Returns the total number of tokens.
+    pub fn total_supply(&self) -> Balance {
+        self.total_supply
+    }
+
+    /// Returns the number of tokens owned by `owner`.
+    pub fn balance_of(&self, owner: AccountId) -> u32 {
+        self.balances.get(owner).unwrap_or_default()
+    }
+
+    /// Returns the owner of the token `id` if it exists.
+    pub fn owner_of(&self, id: Id) -> Option<AccountId> {
+        self.owners.get(id)
+    }
+
+    /// Returns `true` if `operator` is allowed to transfer the token `id` of `owner`,
+    /// or all tokens of `owner` if `id` is `None`.
+    pub fn allowance(
+        &self,
+        owner: AccountId,
+        operator: AccountId,
+        id: Option<Id>,
+    ) -> bool {
+        self.allowances.contains((owner, operator, None::<Id>))
+            || id.is_some() && self.allowances.contains((owner, operator, id))
+    }
+
+    /// Approves or disapproves `operator` to transfer the token `id` of `owner`,
+    /// or all tokens of `owner` if `id` is `None`.
+    pub fn approve(
+        &mut self,
+        owner: AccountId,
+        operator: AccountId,
+        id: Option<Id>,
+        approved: bool,
+    ) -> Result<(), PSP34Error> {
+        if owner == operator {
+            return Err(PSP34Error::SelfApprove)
+        }
+        if let Some(id) = &id {
+            if self.owner_of(id.clone()) != Some(owner) {
+                return Err(PSP34Error::NotApproved)
+            }
+        }
+        if approved {
+            self.allowances.insert((owner, operator, id), &());
+        } else {
+            self.allowances.remove((owner, operator, id));
+        }
+        Ok(())
+    }
+
+    /// Transfers the token `id` to `to` on behalf of `caller`.
+    ///
+    /// The `caller` must either own the token or be approved by its owner.
+    pub fn transfer(
+        &mut self,
+        caller: AccountId,
+        to: AccountId,
+        id: Id,
+    ) -> Result<(), PSP34Error> {
+        let owner = self
+            .owner_of(id.clone())
+            .ok_or(PSP34Error::TokenNotExists)?;
+        if owner != caller && !self.allowance(owner, caller, Some(id.clone())) {
+            return Err(PSP34Error::NotApproved)
+        }
+        self.allowances.remove((owner, caller, Some(id.clone())));
+        self.balances.insert(owner, &(self.balance_of(owner) - 1));
+        self.balances.insert(to, &(self.balance_of(to) + 1));
+        self.owners.insert(id, &to);
+        Ok(())
+    }
+
+    /// Creates the token `id` and assigns it to `to`.
+    pub fn mint(&mut self, to: AccountId, id: Id) -> Result<(), PSP34Error> {
+        if self.owners.contains(id.clone()) {
+            return Err(PSP34Error::TokenExists)
+        }
+        self.owners.insert(id, &to);
+        self.balances.insert(to, &(self.balance_of(to) + 1));
+        self.total_supply += 1;
+        Ok(())
+    }
+
+    /// Destroys the token `id` owned by `from`.
+    pub fn burn(&mut self, from: AccountId, id: Id) -> Result<(), PSP34Error> {
+        match self.owner_of(id.clone()) {
+            None => return Err(PSP34Error::TokenNotExists),
+            Some(owner) if owner != from => return Err(PSP34Error::NotApproved),
+            Some(_) => (),
+        }
+        self.owners.remove(id);
+        self.balances.insert(from, &(self.balance_of(from) - 1));
+        self.total_supply -= 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_test(f: impl FnOnce(&mut PSP34Data)) {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            f(&mut PSP34Data::default());
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn mint_and_transfer_works() {
+        run_test(|token| {
+            let (alice, bob) = (AccountId::from([0x01; 32]), AccountId::from([0x02; 32]));
+            assert_eq!(token.mint(alice, Id::U8(1)), Ok(()));
+            assert_eq!(token.mint(bob, Id::U8(1)), Err(PSP34Error::TokenExists));
+            assert_eq!(token.total_supply(), 1);
+
+            assert_eq!(
+                token.transfer(bob, bob, Id::U8(1)),
+                Err(PSP34Error::NotApproved)
+            );
+            assert_eq!(token.transfer(alice, bob, Id::U8(1)), Ok(()));
+            assert_eq!(token.owner_of(Id::U8(1)), Some(bob));
+            assert_eq!(token.balance_of(alice), 0);
+            assert_eq!(token.balance_of(bob), 1);
+        })
+    }
+
+    #[test]
+    fn approve_works() {
+        run_test(|token| {
+            let (alice, bob) = (AccountId::from([0x01; 32]), AccountId::from([0x02; 32]));
+            token.mint(alice, Id::U8(1)).unwrap();
+            token.mint(alice, Id::U8(2)).unwrap();
+            assert_eq!(
+                token.approve(alice, alice, None, true),
+                Err(PSP34Error::SelfApprove)
+            );
+
+            assert_eq!(token.approve(alice, bob, Some(Id::U8(1)), true), Ok(()));
+            assert!(token.allowance(alice, bob, Some(Id::U8(1))));
+            assert!(!token.allowance(alice, bob, Some(Id::U8(2))));
+            assert_eq!(token.transfer(bob, bob, Id::U8(1)), Ok(()));
+            assert!(!token.allowance(alice, bob, Some(Id::U8(1))));
+
+            assert_eq!(token.approve(alice, bob, None, true), Ok(()));
+            assert!(token.allowance(alice, bob, Some(Id::U8(2))));
+            assert_eq!(token.approve(alice, bob, None, false), Ok(()));
+            assert!(!token.allowance(alice, bob, Some(Id::U8(2))));
+        })
+    }
+
+    #[test]
+    fn burn_works() {
+        run_test(|token| {
+            let (alice, bob) = (AccountId::from([0x01; 32]), AccountId::from([0x02; 32]));
+            token.mint(alice, Id::U8(1)).unwrap();
+            assert_eq!(token.burn(bob, Id::U8(1)), Err(PSP34Error::NotApproved));
+            assert_eq!(token.burn(alice, Id::U8(1)), Ok(()));
+            assert_eq!(
+                token.burn(alice, Id::U8(1)),
+                Err(PSP34Error::TokenNotExists)
+            );
+            assert_eq!(token.total_supply(), 0);
+        })
+    }
+}
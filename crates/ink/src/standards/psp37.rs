@@ -0,0 +1,355 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The PSP37 multi-token standard.
+//!
+//! See <https://github.com/w3f/PSPs/blob/master/PSPs/psp-37.md>.
+
+use super::{
+    psp34::Id,
+    AccountId,
+    Balance,
+};
+use ink_prelude::{
+    string::String,
+    vec::Vec,
+};
+use ink_storage::{
+    traits::{
+        AutoKey,
+        StorageKey,
+    },
+    Mapping,
+};
+
+/// The errors of the PSP37 standard.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PSP37Error {
+    /// Custom error type for cases not covered by the standard.
+    Custom(String),
+    /// Returned if there are not enough tokens to complete the operation.
+    InsufficientBalance,
+    /// Returned if the recipient is the zero address.
+    TransferToZeroAddress,
+    /// Returned if the caller is not allowed to perform the operation.
+    NotAllowed,
+    /// Returned if a safe transfer check failed.
+    SafeTransferCheckFailed(String),
+}
+
+/// The PSP37 multi-token interface.
+#[crate::trait_definition]
+pub trait PSP37 {
+    /// Returns the amount of tokens `id` owned by `owner`,
+    /// or the number of different tokens owned by `owner` if `id` is `None`.
+    #[ink(message)]
+    fn balance_of(&self, owner: AccountId, id: Option<Id>) -> Balance;
+
+    /// Returns the total supply of the token `id`,
+    /// or the number of different tokens if `id` is `None`.
+    #[ink(message)]
+    fn total_supply(&self, id: Option<Id>) -> Balance;
+
+    /// Returns the amount of tokens `id` of `owner` that `operator` is allowed to transfer,
+    /// or the allowance for all tokens of `owner` if `id` is `None`.
+    #[ink(message)]
+    fn allowance(&self, owner: AccountId, operator: AccountId, id: Option<Id>)
+        -> Balance;
+
+    /// Allows `operator` to transfer `value` of the caller's tokens `id`,
+    /// or all tokens of the caller if `id` is `None`.
+    #[ink(message)]
+    fn approve(
+        &mut self,
+        operator: AccountId,
+        id: Option<Id>,
+        value: Balance,
+    ) -> Result<(), PSP37Error>;
+
+    /// Transfers `value` of the tokens `id` from the caller to `to`
+    /// with additional `data` in unspecified format.
+    #[ink(message)]
+    fn transfer(
+        &mut self,
+        to: AccountId,
+        id: Id,
+        value: Balance,
+        data: Vec<u8>,
+    ) -> Result<(), PSP37Error>;
+
+    /// Transfers `value` of the tokens `id` on behalf of `from` to `to`
+    /// with additional `data` in unspecified format.
+    #[ink(message)]
+    fn transfer_from(
+        &mut self,
+        from: AccountId,
+        to: AccountId,
+        id: Id,
+        value: Balance,
+        data: Vec<u8>,
+    ) -> Result<(), PSP37Error>;
+}
+
+/// The owner, the operator and the optional token of an allowance.
+type AllowanceKey = (AccountId, AccountId, Option<Id>);
+
+/// The storage of a PSP37 token with the default implementation of its logic.
+///
+/// Contracts implementing [`PSP37`] can embed this into their storage and
+/// forward their messages to it. Emitting the `Transfer` and `Approval` events
+/// is left to the contract.
+#[crate::storage_item]
+#[derive(Default)]
+pub struct PSP37Data<KEY: StorageKey = AutoKey> {
+    token_count: Balance,
+    supplies: Mapping<Id, Balance>,
+    balances: Mapping<(AccountId, Id), Balance>,
+    owned_tokens: Mapping<AccountId, Balance>,
+    allowances: Mapping<AllowanceKey, Balance>,
+}
+
+impl<KEY: StorageKey> PSP37Data<KEY> {
+    /// Returns the amount of tokens `id` owned by `owner`,
+    /// or the number of different tokens owned by `owner` if `id` is `None`.
+    pub fn balance_of(&self, owner: AccountId, id: Option<Id>) -> Balance {
+        match id {
+            Some(id) => self.balances.get((owner, id)).unwrap_or_default(),
+            None => self.owned_tokens.get(owner).unwrap_or_default(),
+        }
+    }
+
+    /// Returns the total supply of the token `id`,
+    /// or the number of different tokens if `id` is `None`.
+    pub fn total_supply(&self, id: Option<Id>) -> Balance {
+        match id {
+            Some(id) => self.supplies.get(id).unwrap_or_default(),
+            None => self.token_count,
+        }
+    }
+
+    /// Returns the amount of tokens `id` of `owner` that `operator` is allowed to transfer.
+    ///
+    /// An allowance for all tokens of `owner` takes precedence.
+    pub fn allowance(
+        &self,
+        owner: AccountId,
+        operator: AccountId,
+        id: Option<Id>,
+    ) -> Balance {
+        match self.allowances.get((owner, operator, None::<Id>)) {
+            Some(allowance) => allowance,
+            None if id.is_some() => {
+                self.allowances
+                    .get((owner, operator, id))
+                    .unwrap_or_default()
+            }
+            None => 0,
+        }
+    }
+
+    /// Allows `operator` to transfer `value` of the tokens `id` of `owner`,
+    /// or all tokens of `owner` if `id` is `None`.
+    pub fn approve(
+        &mut self,
+        owner: AccountId,
+        operator: AccountId,
+        id: Option<Id>,
+        value: Balance,
+    ) -> Result<(), PSP37Error> {
+        if owner == operator {
+            return Err(PSP37Error::NotAllowed)
+        }
+        if value == 0 {
+            self.allowances.remove((owner, operator, id));
+        } else {
+            self.allowances.insert((owner, operator, id), &value);
+        }
+        Ok(())
+    }
+
+    /// Transfers `value` of the tokens `id` from `from` to `to`.
+    pub fn transfer(
+        &mut self,
+        from: AccountId,
+        to: AccountId,
+        id: Id,
+        value: Balance,
+    ) -> Result<(), PSP37Error> {
+        self.decrease_balance(from, id.clone(), value)?;
+        self.increase_balance(to, id, value);
+        Ok(())
+    }
+
+    /// Transfers `value` of the tokens `id` from `from` to `to` on behalf of `operator`.
+    ///
+    /// Decreases the allowance of `operator` for the token `id` accordingly,
+    /// unless `operator` is allowed to transfer all tokens of `from`.
+    pub fn transfer_from(
+        &mut self,
+        operator: AccountId,
+        from: AccountId,
+        to: AccountId,
+        id: Id,
+        value: Balance,
+    ) -> Result<(), PSP37Error> {
+        // Allowances for all tokens of `from` are not decreased.
+        if operator != from && !self.allowances.contains((from, operator, None::<Id>)) {
+            let allowance = self.allowance(from, operator, Some(id.clone()));
+            if allowance < value {
+                return Err(PSP37Error::NotAllowed)
+            }
+            self.approve(from, operator, Some(id.clone()), allowance - value)?;
+        }
+        self.transfer(from, to, id, value)
+    }
+
+    /// Creates `value` new tokens `id` and assigns them to `to`.
+    pub fn mint(
+        &mut self,
+        to: AccountId,
+        id: Id,
+        value: Balance,
+    ) -> Result<(), PSP37Error> {
+        let supply = self.total_supply(Some(id.clone()));
+        let new_supply = supply
+            .checked_add(value)
+            .ok_or_else(|| PSP37Error::Custom(String::from("total supply overflow")))?;
+        if supply == 0 && value > 0 {
+            self.token_count += 1;
+        }
+        self.supplies.insert(id.clone(), &new_supply);
+        self.increase_balance(to, id, value);
+        Ok(())
+    }
+
+    /// Destroys `value` of the tokens `id` owned by `from`.
+    pub fn burn(
+        &mut self,
+        from: AccountId,
+        id: Id,
+        value: Balance,
+    ) -> Result<(), PSP37Error> {
+        self.decrease_balance(from, id.clone(), value)?;
+        let new_supply = self.total_supply(Some(id.clone())) - value;
+        if new_supply == 0 {
+            self.supplies.remove(id);
+            self.token_count -= 1;
+        } else {
+            self.supplies.insert(id, &new_supply);
+        }
+        Ok(())
+    }
+
+    fn increase_balance(&mut self, owner: AccountId, id: Id, value: Balance) {
+        if value == 0 {
+            return
+        }
+        let balance = self.balance_of(owner, Some(id.clone()));
+        if balance == 0 {
+            self.owned_tokens
+                .insert(owner, &(self.balance_of(owner, None) + 1));
+        }
+        self.balances.insert((owner, id), &(balance + value));
+    }
+
+    fn decrease_balance(
+        &mut self,
+        owner: AccountId,
+        id: Id,
+        value: Balance,
+    ) -> Result<(), PSP37Error> {
+        let balance = self.balance_of(owner, Some(id.clone()));
+        if balance < value {
+            return Err(PSP37Error::InsufficientBalance)
+        }
+        if value == 0 {
+            return Ok(())
+        }
+        if balance == value {
+            self.balances.remove((owner, id));
+            self.owned_tokens
+                .insert(owner, &(self.balance_of(owner, None) - 1));
+        } else {
+            self.balances.insert((owner, id), &(balance - value));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_test(f: impl FnOnce(&mut PSP37Data)) {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            f(&mut PSP37Data::default());
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn mint_and_transfer_works() {
+        run_test(|token| {
+            let (alice, bob) = (AccountId::from([0x01; 32]), AccountId::from([0x02; 32]));
+            token.mint(alice, Id::U8(1), 10).unwrap();
+            token.mint(alice, Id::U8(2), 5).unwrap();
+            assert_eq!(token.total_supply(None), 2);
+            assert_eq!(token.total_supply(Some(Id::U8(1))), 10);
+            assert_eq!(token.balance_of(alice, None), 2);
+
+            assert_eq!(
+                token.transfer(alice, bob, Id::U8(2), 6),
+                Err(PSP37Error::InsufficientBalance)
+            );
+            assert_eq!(token.transfer(alice, bob, Id::U8(2), 5), Ok(()));
+            assert_eq!(token.balance_of(alice, None), 1);
+            assert_eq!(token.balance_of(bob, Some(Id::U8(2))), 5);
+        })
+    }
+
+    #[test]
+    fn transfer_from_works() {
+        run_test(|token| {
+            let (alice, bob) = (AccountId::from([0x01; 32]), AccountId::from([0x02; 32]));
+            token.mint(alice, Id::U8(1), 10).unwrap();
+            assert_eq!(
+                token.transfer_from(bob, alice, bob, Id::U8(1), 1),
+                Err(PSP37Error::NotAllowed)
+            );
+
+            token.approve(alice, bob, Some(Id::U8(1)), 3).unwrap();
+            assert_eq!(token.transfer_from(bob, alice, bob, Id::U8(1), 2), Ok(()));
+            assert_eq!(token.allowance(alice, bob, Some(Id::U8(1))), 1);
+
+            token.approve(alice, bob, None, Balance::MAX).unwrap();
+            assert_eq!(token.transfer_from(bob, alice, bob, Id::U8(1), 8), Ok(()));
+            assert_eq!(token.balance_of(bob, Some(Id::U8(1))), 10);
+        })
+    }
+
+    #[test]
+    fn burn_works() {
+        run_test(|token| {
+            let alice = AccountId::from([0x01; 32]);
+            token.mint(alice, Id::U8(1), 10).unwrap();
+            assert_eq!(token.burn(alice, Id::U8(1), 4), Ok(()));
+            assert_eq!(token.total_supply(Some(Id::U8(1))), 6);
+            assert_eq!(token.burn(alice, Id::U8(1), 6), Ok(()));
+            assert_eq!(token.total_supply(None), 0);
+            assert_eq!(token.balance_of(alice, None), 0);
+        })
+    }
+}
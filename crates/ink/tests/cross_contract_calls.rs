@@ -0,0 +1,175 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+mod accumulator {
+    #[ink(storage)]
+    pub struct Accumulator {
+        value: i32,
+    }
+
+    impl Accumulator {
+        #[ink(constructor)]
+        pub fn new(init_value: i32) -> Self {
+            Self { value: init_value }
+        }
+
+        /// Adds `by` to the value, reverting if the value would become negative.
+        #[ink(message)]
+        pub fn inc(&mut self, by: i32) -> Result<(), ()> {
+            self.value += by;
+            if self.value < 0 {
+                return Err(())
+            }
+            Ok(())
+        }
+
+        /// Forwards [`Accumulator::inc`] to the accumulator at `via`.
+        #[ink(message)]
+        pub fn inc_via(&mut self, via: AccountId, by: i32) -> Result<(), ()> {
+            let mut accumulator: AccumulatorRef =
+                ::ink::env::call::FromAccountId::from_account_id(via);
+            accumulator.inc(by)
+        }
+
+        #[ink(message)]
+        pub fn get(&self) -> i32 {
+            self.value
+        }
+
+        /// Returns the value, but only to other contracts.
+        #[ink(message, internal)]
+        pub fn get_internal(&self) -> i32 {
+            self.value
+        }
+
+        #[ink(message)]
+        pub fn caller(&self) -> AccountId {
+            self.env().caller()
+        }
+
+        /// Returns the caller of the accumulator at `via`.
+        #[ink(message)]
+        pub fn caller_via(&self, via: AccountId) -> AccountId {
+            let accumulator: AccumulatorRef =
+                ::ink::env::call::FromAccountId::from_account_id(via);
+            accumulator.caller()
+        }
+    }
+}
+
+use accumulator::{
+    Accumulator,
+    AccumulatorRef,
+};
+use ink::env::{
+    call::FromAccountId,
+    test,
+    CallFlags,
+    DefaultEnvironment,
+};
+
+#[test]
+fn nested_cross_contract_calls_work() {
+    test::run_test::<DefaultEnvironment, _>(|accounts| {
+        // given
+        ink::off_chain::register_contract(accounts.bob, || Accumulator::new(10));
+        ink::off_chain::register_contract(accounts.charlie, || Accumulator::new(0));
+        let bob = <AccumulatorRef as FromAccountId<DefaultEnvironment>>::from_account_id(
+            accounts.bob,
+        );
+        let mut charlie =
+            <AccumulatorRef as FromAccountId<DefaultEnvironment>>::from_account_id(
+                accounts.charlie,
+            );
+
+        // when
+        assert_eq!(charlie.inc_via(accounts.bob, 5), Ok(()));
+
+        // then
+        assert_eq!(bob.get(), 15);
+        assert_eq!(charlie.get(), 0);
+        assert_eq!(bob.caller(), accounts.alice);
+        assert_eq!(charlie.caller_via(accounts.bob), accounts.charlie);
+        assert_eq!(test::callee::<DefaultEnvironment>(), accounts.alice);
+        Ok(())
+    })
+    .unwrap()
+}
+
+#[test]
+fn reverted_cross_contract_calls_revert_storage() {
+    test::run_test::<DefaultEnvironment, _>(|accounts| {
+        // given
+        ink::off_chain::register_contract(accounts.bob, || Accumulator::new(10));
+        let mut accumulator =
+            <AccumulatorRef as FromAccountId<DefaultEnvironment>>::from_account_id(
+                accounts.bob,
+            );
+
+        // when
+        let result = accumulator.inc(-11);
+
+        // then
+        assert_eq!(result, Err(()));
+        assert_eq!(accumulator.get(), 10);
+        Ok(())
+    })
+    .unwrap()
+}
+
+#[test]
+fn call_builder_allows_setting_call_flags() {
+    test::run_test::<DefaultEnvironment, _>(|accounts| {
+        // given
+        ink::off_chain::register_contract(accounts.bob, || Accumulator::new(10));
+        let accumulator =
+            <AccumulatorRef as FromAccountId<DefaultEnvironment>>::from_account_id(
+                accounts.bob,
+            );
+
+        // when
+        let result = accumulator
+            .call_builder()
+            .inc(5)
+            .call_flags(CallFlags::default().set_allow_reentry(true))
+            .fire();
+
+        // then
+        assert_eq!(result, Ok(Ok(Ok(()))));
+        assert_eq!(accumulator.get(), 15);
+        Ok(())
+    })
+    .unwrap()
+}
+
+#[test]
+fn internal_messages_are_callable_by_contracts() {
+    test::run_test::<DefaultEnvironment, _>(|accounts| {
+        // given
+        ink::off_chain::register_contract(accounts.bob, || Accumulator::new(10));
+        let accumulator =
+            <AccumulatorRef as FromAccountId<DefaultEnvironment>>::from_account_id(
+                accounts.bob,
+            );
+
+        // then
+        assert!(ink::env::caller_is_origin::<DefaultEnvironment>());
+        assert_eq!(accumulator.get_internal(), 10);
+        Ok(())
+    })
+    .unwrap()
+}
@@ -0,0 +1,19 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract(embed_metadata_hash = true)]
+mod metadata_hash {
+    #[ink(storage)]
+    pub struct MetadataHash {}
+
+    impl MetadataHash {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self {}
+        }
+
+        #[ink(message)]
+        pub fn metadata_hash(&self) -> Option<[u8; 32]> {
+            ink::metadata_hash!()
+        }
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Builds the contract in `tests/metadata-hash` for `wasm32-unknown-unknown` and
+//! reads the embedded metadata hash back from the Wasm blob.
+//!
+//! Requires the `wasm32-unknown-unknown` target to be installed.
+
+use ink_metadata::verify::embedded_metadata_hash;
+use std::{
+    path::Path,
+    process::Command,
+};
+
+/// Builds the contract with `INK_METADATA_HASH` set to `hash` into `target_dir`
+/// and returns its Wasm blob.
+fn build_contract(hash: Option<&str>, target_dir: &Path) -> Vec<u8> {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let mut cargo = Command::new(env!("CARGO"));
+    cargo
+        .args([
+            "build",
+            "--release",
+            "--no-default-features",
+            "--target=wasm32-unknown-unknown",
+        ])
+        .arg("--manifest-path")
+        .arg(manifest_dir.join("tests/metadata-hash/Cargo.toml"))
+        .arg("--target-dir")
+        .arg(target_dir)
+        .env_remove("INK_METADATA_HASH");
+    if let Some(hash) = hash {
+        cargo.env("INK_METADATA_HASH", hash);
+    }
+    let status = cargo.status().expect("failed to run `cargo build`");
+    assert!(status.success(), "building the contract failed");
+
+    std::fs::read(target_dir.join("wasm32-unknown-unknown/release/metadata_hash.wasm"))
+        .expect("the contract was built")
+}
+
+#[test]
+fn wasm_blob_embeds_metadata_hash() {
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("metadata-hash");
+    let hash = [0xAB; 32];
+    let hex = hash
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>();
+
+    let wasm = build_contract(Some(&hex), &target_dir);
+    assert_eq!(embedded_metadata_hash(&wasm), Some(hash));
+
+    let wasm = build_contract(None, &target_dir);
+    assert_eq!(embedded_metadata_hash(&wasm), None);
+}
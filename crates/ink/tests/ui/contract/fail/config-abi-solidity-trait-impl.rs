@@ -0,0 +1,25 @@
+#[ink::trait_definition]
+pub trait Flip {
+    #[ink(message)]
+    fn flip(&mut self);
+}
+
+#[ink::contract(abi = "solidity")]
+mod contract {
+    #[ink(storage)]
+    pub struct Contract {}
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {}
+        }
+    }
+
+    impl super::Flip for Contract {
+        #[ink(message)]
+        fn flip(&mut self) {}
+    }
+}
+
+fn main() {}
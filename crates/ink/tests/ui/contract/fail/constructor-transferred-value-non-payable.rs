@@ -0,0 +1,24 @@
+#[ink::contract]
+mod contract {
+    #[ink(storage)]
+    pub struct Contract {}
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn non_payable() -> Self {
+            Self {}
+        }
+
+        #[ink(message)]
+        pub fn instantiate(&self, code_hash: Hash) {
+            let _: ContractRef = ContractRef::non_payable()
+                .code_hash(code_hash)
+                .transferred_value(100)
+                .salt_bytes([0xDE, 0xAD, 0xBE, 0xEF])
+                .instantiate()
+                .unwrap();
+        }
+    }
+}
+
+fn main() {}
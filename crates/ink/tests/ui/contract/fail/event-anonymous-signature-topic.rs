@@ -0,0 +1,23 @@
+#[ink::contract]
+mod contract {
+    #[ink(storage)]
+    pub struct Contract {}
+
+    #[ink(event, anonymous, signature_topic = "Event(uint8)")]
+    pub struct Event {
+        #[ink(topic)]
+        arg: u8,
+    }
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {}
+        }
+
+        #[ink(message)]
+        pub fn message(&self) {}
+    }
+}
+
+fn main() {}
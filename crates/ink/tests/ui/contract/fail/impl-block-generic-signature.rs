@@ -0,0 +1,33 @@
+pub trait Config {
+    type Amount: ink::storage::traits::Packed
+        + ink::storage::traits::StorageLayout
+        + 'static;
+}
+
+pub struct MyConfig;
+
+impl Config for MyConfig {
+    type Amount = u64;
+}
+
+#[ink::contract(config = super::MyConfig)]
+mod contract {
+    use super::Config;
+
+    #[ink(storage)]
+    pub struct Contract<T: Config> {
+        total: T::Amount,
+    }
+
+    impl<T: Config> Contract<T> {
+        #[ink(constructor)]
+        pub fn new(total: T::Amount) -> Self {
+            Self { total }
+        }
+
+        #[ink(message)]
+        pub fn message(&self) {}
+    }
+}
+
+fn main() {}
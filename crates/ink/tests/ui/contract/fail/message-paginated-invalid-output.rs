@@ -0,0 +1,19 @@
+#[ink::contract]
+mod contract {
+    #[ink(storage)]
+    pub struct Contract {}
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {}
+        }
+
+        #[ink(message, paginated)]
+        pub fn values(&self) -> Vec<u32> {
+            Vec::new()
+        }
+    }
+}
+
+fn main() {}
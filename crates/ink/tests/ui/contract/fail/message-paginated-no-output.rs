@@ -0,0 +1,17 @@
+#[ink::contract]
+mod contract {
+    #[ink(storage)]
+    pub struct Contract {}
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {}
+        }
+
+        #[ink(message, paginated)]
+        pub fn values(&self) {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+#[ink::contract]
+mod contract {
+    #[ink(storage)]
+    pub struct Contract<T> {
+        marker: core::marker::PhantomData<T>,
+    }
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {
+                marker: Default::default(),
+            }
+        }
+
+        #[ink(message)]
+        pub fn message(&self) {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+#[ink::contract]
+mod contract {
+    #[ink(storage, storage_version = 0)]
+    pub struct Contract {}
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {}
+        }
+
+        #[ink(message)]
+        pub fn message(&self) {}
+    }
+}
+
+fn main() {}
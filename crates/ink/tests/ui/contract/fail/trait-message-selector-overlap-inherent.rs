@@ -0,0 +1,32 @@
+mod foo {
+    #[ink::trait_definition]
+    pub trait TraitDefinition {
+        #[ink(message, selector = 0xCAFEBABE)]
+        fn message(&self);
+    }
+}
+
+#[ink::contract]
+pub mod contract {
+    use super::foo::TraitDefinition;
+
+    #[ink(storage)]
+    pub struct Contract {}
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {}
+        }
+
+        #[ink(message, selector = 0xCAFEBABE)]
+        pub fn inherent_message(&self) {}
+    }
+
+    impl TraitDefinition for Contract {
+        #[ink(message)]
+        fn message(&self) {}
+    }
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+#[ink::trait_definition]
+pub trait Counter {
+    #[ink(message)]
+    fn get(&self) -> u32;
+}
+
+#[ink::contract]
+mod contract {
+    use super::Counter;
+
+    #[ink(storage)]
+    pub struct Contract {}
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {}
+        }
+    }
+
+    impl Counter for Contract {
+        #[ink(message)]
+        fn get(&self) -> u32;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,52 @@
+#[ink::contract(abi = "solidity")]
+mod contract {
+    #[ink(storage)]
+    pub struct Contract {}
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {}
+        }
+
+        #[ink(message)]
+        pub fn balance_of(&self, _owner: ink::sol::Address) -> Balance {
+            0
+        }
+
+        #[ink(message, selector = 0xA9059CBB)]
+        pub fn transfer(&mut self, _to: ink::sol::Address, _value: Balance) -> bool {
+            true
+        }
+    }
+}
+
+use contract::Contract;
+
+fn main() {
+    assert_eq!(
+        <Contract as ::ink::reflect::DispatchableMessageInfo<
+            {
+                <Contract as ::ink::reflect::ContractDispatchableMessages<
+                    {
+                        <Contract as ::ink::reflect::ContractAmountDispatchables>::MESSAGES
+                    },
+                >>::IDS[0]
+            },
+        >>::SELECTOR,
+        // `balanceOf(address)`
+        [0x70, 0xA0, 0x82, 0x31],
+    );
+    assert_eq!(
+        <Contract as ::ink::reflect::DispatchableMessageInfo<
+            {
+                <Contract as ::ink::reflect::ContractDispatchableMessages<
+                    {
+                        <Contract as ::ink::reflect::ContractAmountDispatchables>::MESSAGES
+                    },
+                >>::IDS[1]
+            },
+        >>::SELECTOR,
+        [0xA9, 0x05, 0x9C, 0xBB],
+    );
+}
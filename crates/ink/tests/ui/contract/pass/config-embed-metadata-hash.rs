@@ -0,0 +1,19 @@
+#[ink::contract(embed_metadata_hash = true)]
+mod contract {
+    #[ink(storage)]
+    pub struct Contract {}
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {}
+        }
+
+        #[ink(message)]
+        pub fn metadata_hash(&self) -> Option<[u8; 32]> {
+            ink::metadata_hash!()
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,33 @@
+#[ink::contract(entry = "adder")]
+mod adder {
+    #[ink(storage)]
+    pub struct Adder {}
+
+    impl Adder {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {}
+        }
+
+        #[ink(message)]
+        pub fn message(&self) {}
+    }
+}
+
+#[ink::contract(entry = "subber")]
+mod subber {
+    #[ink(storage)]
+    pub struct Subber {}
+
+    impl Subber {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {}
+        }
+
+        #[ink(message)]
+        pub fn message(&self) {}
+    }
+}
+
+fn main() {}
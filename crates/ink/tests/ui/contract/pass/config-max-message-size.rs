@@ -0,0 +1,28 @@
+#[ink::contract(max_message_size = 8_192)]
+mod contract {
+    use ink::prelude::vec::Vec;
+
+    #[ink(storage)]
+    pub struct Contract {
+        data: Vec<u8>,
+    }
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor(data: Vec<u8>) -> Self {
+            Self { data }
+        }
+
+        #[ink(message)]
+        pub fn set(&mut self, data: Vec<u8>) {
+            self.data = data;
+        }
+
+        #[ink(message)]
+        pub fn starts_with(&self, prefix: &[u8]) -> bool {
+            self.data.starts_with(prefix)
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,83 @@
+#[ink::contract]
+mod contract {
+    #[ink(storage)]
+    pub struct Contract {
+        admin: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct Instantiated {
+        #[ink(topic)]
+        pub admin: AccountId,
+    }
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            let admin = Self::env().caller();
+            Self::env().emit_event(Instantiated { admin });
+            Self { admin }
+        }
+
+        #[ink(constructor)]
+        pub fn try_new(fail: bool) -> Result<Self, ()> {
+            if fail {
+                return Err(())
+            }
+            Ok(Self::new())
+        }
+
+        #[ink(message)]
+        pub fn reinstantiate(&self) {
+            self.env().emit_event(Instantiated { admin: self.admin });
+        }
+    }
+}
+
+use contract::{
+    Contract,
+    Instantiated,
+};
+use ink::env::DefaultEnvironment;
+
+fn main() {
+    ink::env::test::run_test::<DefaultEnvironment, _>(|accounts| {
+        ink::env::test::set_caller::<DefaultEnvironment>(accounts.bob);
+        let contract = Contract::new();
+        let instantiated =
+            ink::env::test::assert_constructor_event::<DefaultEnvironment, Instantiated>();
+        assert_eq!(instantiated.admin, accounts.bob);
+
+        // Events of messages are not attributed to the constructor.
+        contract.reinstantiate();
+        assert_eq!(ink::env::test::recorded_events().count(), 2);
+        assert_eq!(ink::env::test::recorded_constructor_events().count(), 1);
+
+        // Constructors calling each other emit their events once.
+        ink::env::test::set_caller::<DefaultEnvironment>(accounts.charlie);
+        assert!(Contract::try_new(true).is_err());
+        assert!(Contract::try_new(false).is_ok());
+        assert_eq!(ink::env::test::recorded_constructor_events().count(), 2);
+        let instantiated =
+            ink::env::test::assert_constructor_event::<DefaultEnvironment, Instantiated>();
+        assert_eq!(instantiated.admin, accounts.charlie);
+
+        // Constructors of registered contracts are captured as well.
+        ink::env::test::set_caller::<DefaultEnvironment>(accounts.django);
+        ink::off_chain::register_contract::<Contract, _>([0x01; 32].into(), Contract::new);
+        let instantiated =
+            ink::env::test::assert_constructor_event::<DefaultEnvironment, Instantiated>();
+        assert_eq!(instantiated.admin, accounts.django);
+        Ok(())
+    })
+    .unwrap();
+
+    let result = std::panic::catch_unwind(|| {
+        ink::env::test::run_test::<DefaultEnvironment, _>(|_| {
+            let _ = Contract::try_new(true);
+            ink::env::test::assert_constructor_event::<DefaultEnvironment, Instantiated>();
+            Ok(())
+        })
+    });
+    assert!(result.is_err());
+}
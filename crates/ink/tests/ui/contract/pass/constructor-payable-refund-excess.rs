@@ -0,0 +1,21 @@
+#[ink::contract]
+mod contract {
+    #[ink(storage)]
+    pub struct Contract {}
+
+    impl Contract {
+        #[ink(constructor, selector = 0, payable(refund_excess = 1_000))]
+        pub fn constructor() -> Self {
+            Self {}
+        }
+
+        #[ink(message)]
+        pub fn message(&self) {}
+    }
+}
+
+use contract::Contract;
+
+fn main() {
+    assert!(<Contract as ::ink::reflect::DispatchableConstructorInfo<0>>::PAYABLE);
+}
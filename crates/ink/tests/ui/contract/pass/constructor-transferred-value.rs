@@ -0,0 +1,41 @@
+#[ink::contract]
+mod contract {
+    #[ink(storage)]
+    pub struct Contract {}
+
+    impl Contract {
+        #[ink(constructor, payable)]
+        pub fn payable() -> Self {
+            Self {}
+        }
+
+        #[ink(constructor)]
+        pub fn non_payable() -> Self {
+            Self {}
+        }
+
+        #[ink(message)]
+        pub fn instantiate(&self, code_hash: Hash) {
+            let _: ContractRef = ContractRef::payable()
+                .code_hash(code_hash)
+                .transferred_value(100)
+                .salt_bytes([0xDE, 0xAD, 0xBE, 0xEF])
+                .instantiate()
+                .unwrap();
+            let _: ContractRef = ContractRef::payable()
+                .code_hash(code_hash)
+                .endowment(100)
+                .salt_bytes([0xDE, 0xAD, 0xBE, 0xEF])
+                .instantiate()
+                .unwrap();
+            let _: ContractRef = ContractRef::non_payable()
+                .code_hash(code_hash)
+                .endowment(0)
+                .salt_bytes([0xDE, 0xAD, 0xBE, 0xEF])
+                .instantiate()
+                .unwrap();
+        }
+    }
+}
+
+fn main() {}
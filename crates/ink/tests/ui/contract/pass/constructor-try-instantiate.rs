@@ -0,0 +1,36 @@
+#[ink::contract]
+mod contract {
+    #[ink(storage)]
+    pub struct Contract {}
+
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(::scale_info::TypeInfo))]
+    pub enum Error {
+        Foo,
+    }
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor(fail: bool) -> Result<Self, Error> {
+            if fail {
+                return Err(Error::Foo)
+            }
+            Ok(Self {})
+        }
+
+        #[ink(message)]
+        pub fn instantiate(&self, code_hash: Hash) -> Option<Error> {
+            let result: Result<ContractRef, Error> = ContractRef::constructor(true)
+                .code_hash(code_hash)
+                .endowment(0)
+                .salt_bytes([0xDE, 0xAD, 0xBE, 0xEF])
+                .try_instantiate()
+                .unwrap_or_else(|error| {
+                    panic!("received an unexpected environmental error: {:?}", error)
+                });
+            result.err()
+        }
+    }
+}
+
+fn main() {}
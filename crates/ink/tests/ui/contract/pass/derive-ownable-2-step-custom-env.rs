@@ -0,0 +1,56 @@
+#[derive(Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum EthEnv {}
+
+impl ink_env::Environment for EthEnv {
+    const MAX_EVENT_TOPICS: usize = 4;
+    type AccountId = [u8; 20];
+    type Balance = u128;
+    type Hash = [u8; 32];
+    type Timestamp = u64;
+    type BlockNumber = u32;
+    type ChainExtension = ();
+}
+
+#[ink::contract(env = super::EthEnv)]
+mod contract {
+    use ink::contrib::{
+        Ownable2Step,
+        OwnableError,
+    };
+
+    #[ink(storage)]
+    #[derive(ink::contrib::Ownable2Step)]
+    pub struct Contract {
+        ownable: Ownable2Step<Environment>,
+        value: bool,
+    }
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self {
+                ownable: Ownable2Step::new(Self::env().caller()),
+                value: false,
+            }
+        }
+
+        #[ink(message)]
+        pub fn set_value(&mut self, value: bool) -> Result<(), OwnableError> {
+            self.ownable.ensure_caller_is_owner()?;
+            self.value = value;
+            Ok(())
+        }
+    }
+}
+
+use contract::ContractRef;
+use ink::contrib::OwnableError;
+
+// The generated messages take the accounts of the contract's environment.
+fn _transfer_ownership(contract: &mut ContractRef, new_owner: [u8; 20]) {
+    let _: Result<(), OwnableError> = contract.transfer_ownership(new_owner);
+    let _: Option<[u8; 20]> = contract.owner();
+}
+
+fn main() {}
@@ -0,0 +1,59 @@
+#[ink::contract]
+mod contract {
+    use ink::contrib::{
+        Ownable2Step,
+        OwnableError,
+    };
+
+    #[ink(storage)]
+    #[derive(Ownable2Step)]
+    pub struct Contract {
+        ownable: Ownable2Step,
+        value: bool,
+    }
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self {
+                ownable: Ownable2Step::new(Self::env().caller()),
+                value: false,
+            }
+        }
+
+        #[ink(message)]
+        pub fn set_value(&mut self, value: bool) -> Result<(), OwnableError> {
+            self.ownable.ensure_caller_is_owner()?;
+            self.value = value;
+            Ok(())
+        }
+    }
+}
+
+fn main() {
+    use contract::Contract;
+    use ink::contrib::OwnableError;
+
+    ink::env::test::run_test::<ink::env::DefaultEnvironment, _>(|accounts| {
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+        let mut contract = Contract::new();
+        assert_eq!(contract.owner(), Some(accounts.alice));
+        assert_eq!(contract.transfer_ownership(accounts.bob), Ok(()));
+        assert_eq!(contract.pending_owner(), Some(accounts.bob));
+        assert_eq!(
+            contract.accept_ownership(),
+            Err(OwnableError::NotPendingOwner)
+        );
+
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert_eq!(contract.accept_ownership(), Ok(()));
+        assert_eq!(contract.owner(), Some(accounts.bob));
+        assert_eq!(contract.set_value(true), Ok(()));
+        assert_eq!(contract.renounce_ownership(), Ok(()));
+        assert_eq!(contract.owner(), None);
+        assert_eq!(contract.set_value(false), Err(OwnableError::NotOwner));
+        assert_eq!(ink::env::test::recorded_events().count(), 3,);
+        Ok(())
+    })
+    .unwrap()
+}
@@ -0,0 +1,83 @@
+#[ink::contract]
+mod contract {
+    #[ink(storage)]
+    pub struct Contract {}
+
+    /// The errors of the contract.
+    #[ink(error)]
+    #[derive(Debug, PartialEq, Eq)]
+    pub enum Error {
+        /// The caller is not the owner.
+        NotOwner,
+        InsufficientBalance { required: Balance, available: Balance },
+        Other(u8, ink::prelude::string::String),
+    }
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {}
+        }
+
+        #[ink(message)]
+        pub fn withdraw(&self, amount: Balance) -> Result<(), Error> {
+            Err(Error::InsufficientBalance {
+                required: amount,
+                available: 0,
+            })
+        }
+    }
+}
+
+use contract::Error;
+use ink::reflect::ContractError as _;
+
+fn main() {
+    for error in [
+        Error::NotOwner,
+        Error::InsufficientBalance {
+            required: 10,
+            available: 5,
+        },
+        Error::Other(1, "reason".into()),
+    ] {
+        let encoded = scale::Encode::encode(&error);
+        assert_eq!(&encoded[..4], &error.error_code()[..]);
+        assert_eq!(<Error as scale::Decode>::decode(&mut &encoded[..]), Ok(error));
+    }
+
+    // The error codes only depend on the paths of the variants.
+    assert_eq!(Error::ERROR_CODES.len(), 3);
+    assert_eq!(Error::ERROR_CODES[0].0, "NotOwner");
+    let mut hash = [0; 32];
+    ink::env::hash_bytes::<ink::env::hash::Blake2x256>(b"Error::NotOwner", &mut hash);
+    assert_eq!(Error::NotOwner.error_code(), [hash[0], hash[1], hash[2], hash[3]]);
+
+    // Decoding fails if the error code does not match any variant.
+    let mut encoded = scale::Encode::encode(&Error::NotOwner);
+    encoded[0] ^= 0xFF;
+    assert!(<Error as scale::Decode>::decode(&mut &encoded[..]).is_err());
+
+    let output = scale::Encode::encode(&ink::MessageResult::Ok(
+        contract::Contract::constructor().withdraw(10),
+    ));
+    assert_eq!(
+        ink::reflect::decode_error_code(&output),
+        Some(
+            Error::InsufficientBalance {
+                required: 0,
+                available: 0
+            }
+            .error_code()
+        )
+    );
+    assert_eq!(ink::reflect::decode_error_code(&[0x01, 0x01]), None);
+
+    let type_info = <Error as scale_info::TypeInfo>::type_info();
+    let scale_info::TypeDef::Variant(variants) = type_info.type_def else {
+        panic!("expected a variant type")
+    };
+    let fields = &variants.variants[1].fields;
+    assert_eq!(fields.len(), 3);
+    assert_eq!(fields[0].name, Some("error_code"));
+}
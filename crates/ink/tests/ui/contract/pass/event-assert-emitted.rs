@@ -0,0 +1,62 @@
+#[ink::contract]
+mod contract {
+    #[ink(storage)]
+    pub struct Contract {}
+
+    #[ink(event)]
+    pub struct Transferred {
+        #[ink(topic)]
+        pub from: Option<AccountId>,
+        pub value: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Approved {
+        pub value: Balance,
+    }
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {}
+        }
+
+        #[ink(message)]
+        pub fn transfer(&self, value: Balance) {
+            self.env().emit_event(Approved { value: 1 });
+            self.env().emit_event(Transferred { from: None, value });
+        }
+    }
+}
+
+use contract::{
+    Approved,
+    Contract,
+    Transferred,
+};
+
+fn main() {
+    ink::env::test::run_test::<ink::env::DefaultEnvironment, _>(|_| {
+        let contract = Contract::constructor();
+        contract.transfer(42);
+
+        let transfers = ink::env::test::recorded_events_decoded::<Transferred>();
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].value, 42);
+        assert_eq!(ink::env::test::recorded_events_decoded::<Approved>().len(), 1);
+
+        ink::env::assert_emitted!(Transferred { value: 42, .. });
+        ink::env::assert_emitted!(Approved { value: 1 });
+        Ok(())
+    })
+    .unwrap();
+
+    let result = std::panic::catch_unwind(|| {
+        ink::env::test::run_test::<ink::env::DefaultEnvironment, _>(|_| {
+            Contract::constructor().transfer(42);
+            ink::env::assert_emitted!(Transferred { value: 43, .. });
+            Ok(())
+        })
+    });
+    assert!(result.is_err());
+}
@@ -0,0 +1,59 @@
+#[ink::contract]
+mod contract {
+    #[ink(storage)]
+    pub struct Contract {}
+
+    #[ink(event)]
+    pub struct Transferred {
+        #[ink(topic)]
+        to: AccountId,
+        #[ink(decimals = 12)]
+        value: Balance,
+        #[ink(topic, decimals = 6)]
+        fee: Balance,
+    }
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {}
+        }
+
+        #[ink(message)]
+        pub fn message(&self) {
+            ink::env::debug_println!(
+                "transferred {} UNIT",
+                ink::env::format_balance(1_500_000_000_000u128, 12)
+            );
+            self.env().emit_event(Transferred {
+                to: self.env().caller(),
+                value: 1_500_000_000_000,
+                fee: 1_000,
+            });
+        }
+    }
+}
+
+use ink::metadata::InkProject;
+
+fn generate_metadata() -> InkProject {
+    extern "Rust" {
+        fn __ink_generate_metadata() -> InkProject;
+    }
+
+    unsafe { __ink_generate_metadata() }
+}
+
+fn main() {
+    let metadata = generate_metadata();
+    let event = metadata.spec().events().iter().next().unwrap();
+    let decimals = event
+        .args()
+        .iter()
+        .map(|arg| (arg.indexed(), arg.decimals()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        decimals,
+        vec![(true, None), (false, Some(12)), (true, Some(6))]
+    );
+}
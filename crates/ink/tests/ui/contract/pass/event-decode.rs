@@ -0,0 +1,96 @@
+#[ink::contract]
+mod contract {
+    #[ink(storage)]
+    pub struct Contract {}
+
+    #[ink(event)]
+    pub struct Transferred {
+        #[ink(topic)]
+        pub from: AccountId,
+        #[ink(topic)]
+        pub value: Balance,
+        pub memo: Vec<u8>,
+    }
+
+    #[ink(event)]
+    pub struct Approved {
+        pub value: Balance,
+    }
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {}
+        }
+
+        #[ink(message)]
+        pub fn transfer(&self, value: Balance) {
+            self.env().emit_event(Transferred {
+                from: self.env().caller(),
+                value,
+                memo: vec![0x01, 0x02],
+            });
+        }
+    }
+}
+
+use contract::{
+    Approved,
+    Contract,
+    Transferred,
+};
+use ink::env::{
+    hash::{
+        Blake2x256,
+        CryptoHash,
+    },
+    test::{
+        DecodeEvent,
+        EmittedEvent,
+    },
+    DefaultEnvironment,
+};
+
+/// Returns the topic of the SCALE encoded `value`, which is padded with zeros to
+/// 32 bytes or hashed if it is longer.
+fn topic(value: &impl scale::Encode) -> Vec<u8> {
+    let encoded = scale::Encode::encode(value);
+    let mut output = [0u8; 32];
+    if encoded.len() <= 32 {
+        output[..encoded.len()].copy_from_slice(&encoded);
+    } else {
+        <Blake2x256 as CryptoHash>::hash(&encoded, &mut output);
+    }
+    output.to_vec()
+}
+
+fn main() {
+    ink::env::test::run_test::<DefaultEnvironment, _>(|accounts| {
+        Contract::constructor().transfer(42);
+
+        let event = ink::env::test::recorded_events().next().unwrap();
+        assert_eq!(
+            event.topics,
+            vec![
+                topic(&(&b""[..], *b"Contract::Transferred")),
+                topic(&(&b"Contract::Transferred::from"[..], accounts.alice)),
+                topic(&(&b"Contract::Transferred::value"[..], 42u128)),
+            ]
+        );
+
+        let transferred = Transferred::decode_event(&event).unwrap();
+        assert_eq!(transferred.from, accounts.alice);
+        assert_eq!(transferred.value, 42);
+        assert_eq!(transferred.memo, vec![0x01, 0x02]);
+        assert!(Approved::decode_event(&event).is_none());
+
+        // E2E tests only know the data of the emitted events.
+        let data_only = EmittedEvent {
+            topics: Vec::new(),
+            data: event.data.clone(),
+        };
+        assert_eq!(Transferred::decode_event(&data_only).unwrap().value, 42);
+        Ok(())
+    })
+    .unwrap()
+}
@@ -0,0 +1,75 @@
+#[ink::contract]
+mod contract {
+    use ink::prelude::{
+        string::String,
+        vec::Vec,
+    };
+
+    #[ink(storage)]
+    pub struct Contract {}
+
+    #[ink(event)]
+    pub struct Event {
+        #[ink(topic)]
+        arg_1: i8,
+        #[ink(topic(hash))]
+        arg_2: Vec<u8>,
+        #[ink(topic(hash))]
+        arg_3: String,
+        arg_4: i32,
+    }
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self::env().emit_event(Event {
+                arg_1: 1,
+                arg_2: Vec::from([0x42; 128]),
+                arg_3: String::from("a rather long topic value"),
+                arg_4: 4,
+            });
+            Self {}
+        }
+
+        #[ink(message)]
+        pub fn message(&self) {
+            self.env().emit_event(Event {
+                arg_1: 1,
+                arg_2: Vec::new(),
+                arg_3: String::new(),
+                arg_4: 4,
+            });
+        }
+    }
+}
+
+use contract::Contract;
+use ink::env::{
+    hash::{
+        Blake2x256,
+        CryptoHash,
+    },
+    DefaultEnvironment,
+};
+
+fn blake2x256(value: &impl scale::Encode) -> Vec<u8> {
+    let mut output = [0u8; 32];
+    <Blake2x256 as CryptoHash>::hash(&scale::Encode::encode(value), &mut output);
+    output.to_vec()
+}
+
+fn main() {
+    ink::env::test::run_test::<DefaultEnvironment, _>(|_| {
+        let _ = Contract::constructor();
+        let event = ink::env::test::recorded_events().next().unwrap();
+        // The signature topic and `arg_1` precede the hashed topics.
+        assert_eq!(event.topics.len(), 4);
+        assert_eq!(event.topics[2], blake2x256(&vec![0x42u8; 128]));
+        assert_eq!(
+            event.topics[3],
+            blake2x256(&String::from("a rather long topic value"))
+        );
+        Ok(())
+    })
+    .unwrap()
+}
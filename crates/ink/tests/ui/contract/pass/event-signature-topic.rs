@@ -0,0 +1,52 @@
+#[ink::contract]
+mod contract {
+    #[ink(storage)]
+    pub struct Contract {}
+
+    #[ink(event, anonymous = false, signature_topic = "Transfer(address,address,uint128)")]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        value: Balance,
+    }
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {}
+        }
+
+        #[ink(message)]
+        pub fn message(&self) {
+            self.env().emit_event(Transfer {
+                from: self.env().caller(),
+                to: self.env().account_id(),
+                value: 42,
+            });
+        }
+    }
+}
+
+use contract::Contract;
+use ink::env::{
+    hash::{
+        CryptoHash,
+        Keccak256,
+    },
+    test,
+    DefaultEnvironment,
+};
+
+fn main() {
+    test::run_test::<DefaultEnvironment, _>(|_| {
+        Contract::constructor().message();
+        let events = test::recorded_events().collect::<Vec<_>>();
+        let mut expected = [0x00; 32];
+        Keccak256::hash(b"Transfer(address,address,uint128)", &mut expected);
+        assert_eq!(events[0].topics[0], expected);
+        Ok(())
+    })
+    .unwrap()
+}
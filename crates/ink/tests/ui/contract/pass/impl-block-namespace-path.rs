@@ -0,0 +1,64 @@
+#[ink::trait_definition]
+pub trait Flip {
+    #[ink(message)]
+    fn flip(&mut self);
+}
+
+#[ink::contract]
+mod contract {
+    #[ink(storage)]
+    pub struct Contract {
+        value: bool,
+    }
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self { value: false }
+        }
+    }
+
+    #[ink(namespace = "my_std::v2")]
+    impl Contract {
+        #[ink(message)]
+        pub fn flip(&mut self) {
+            self.value = !self.value;
+        }
+    }
+
+    impl super::Flip for Contract {
+        #[ink(message)]
+        fn flip(&mut self) {
+            self.value = !self.value;
+        }
+    }
+}
+
+use ink::metadata::InkProject;
+
+fn generate_metadata() -> InkProject {
+    extern "Rust" {
+        fn __ink_generate_metadata() -> InkProject;
+    }
+
+    unsafe { __ink_generate_metadata() }
+}
+
+fn main() {
+    let metadata = generate_metadata();
+    let messages = metadata.spec().messages();
+    assert_eq!(messages.len(), 2);
+
+    let inherent = &messages[0];
+    assert_eq!(inherent.label(), "flip");
+    assert_eq!(inherent.namespace().map(String::as_str), Some("my_std::v2"));
+    assert_eq!(
+        inherent.selector().to_bytes(),
+        ink::selector_bytes!("my_std::v2::flip")
+    );
+
+    let trait_message = &messages[1];
+    assert_eq!(trait_message.label(), "Flip::flip");
+    assert_eq!(trait_message.namespace(), None);
+    assert_ne!(inherent.selector(), trait_message.selector());
+}
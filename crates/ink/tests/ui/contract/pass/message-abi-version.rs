@@ -0,0 +1,54 @@
+#[ink::contract]
+mod contract {
+    #[ink(storage)]
+    pub struct Contract {
+        value: Balance,
+    }
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self { value: 0 }
+        }
+
+        #[ink(message, abi_version = 2)]
+        pub fn set(&mut self, to: AccountId, value: Balance) {
+            let _ = to;
+            self.value = value;
+        }
+
+        #[ink(message)]
+        pub fn get(&self) -> Balance {
+            self.value
+        }
+    }
+}
+
+use ink::metadata::{
+    stability::StabilitySnapshot,
+    InkProject,
+};
+
+fn generate_metadata() -> InkProject {
+    extern "Rust" {
+        fn __ink_generate_metadata() -> InkProject;
+    }
+
+    unsafe { __ink_generate_metadata() }
+}
+
+fn main() {
+    let metadata = generate_metadata();
+    let versions = metadata
+        .spec()
+        .messages()
+        .iter()
+        .map(|message| message.abi_version())
+        .collect::<Vec<_>>();
+    assert_eq!(versions, vec![Some(2), None]);
+    let snapshot = StabilitySnapshot::new(&metadata);
+    assert!(snapshot
+        .entries()
+        .iter()
+        .any(|entry| entry == "abi set v2(AccountId([u8; 32]), u128)"));
+}
@@ -0,0 +1,77 @@
+use ink::{
+    codegen::TraitCallBuilder,
+    env::{
+        call::{
+            build_call,
+            Call,
+            ExecutionInput,
+            Selector,
+        },
+        DefaultEnvironment,
+    },
+    selector_bytes,
+    LangError,
+    MessageResult,
+};
+
+#[ink::contract]
+pub mod contract {
+    #[ink(storage)]
+    pub struct Contract {}
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {}
+        }
+
+        #[ink(message)]
+        pub fn checksum(&self, data: &[u8], name: &str, seed: u32) -> u32 {
+            data.iter()
+                .chain(name.as_bytes())
+                .fold(seed, |acc, byte| acc.wrapping_add(*byte as u32))
+        }
+
+        #[ink(message)]
+        pub fn len(&self, data: &[u8]) -> u32 {
+            data.len() as u32
+        }
+    }
+}
+
+use contract::{
+    Contract,
+    ContractRef,
+};
+
+fn _call_builder_borrows_inputs(contract: &ContractRef, data: &[u8]) {
+    let _call = contract.call().checksum(data, "name", 0);
+}
+
+fn main() {
+    ink::env::test::run_test::<DefaultEnvironment, _>(|_| {
+        let account_id = [0x01; 32].into();
+        ink::off_chain::register_contract::<Contract, _>(
+            account_id,
+            Contract::constructor,
+        );
+        let contract = ContractRef::from_account_id(account_id);
+        assert_eq!(contract.checksum(&[1, 2, 3], "ink", 42), 370);
+        assert_eq!(contract.len(&[0; 100]), 100);
+
+        // Invalid UTF-8 for a borrowed `&str` input.
+        let result = build_call::<DefaultEnvironment>()
+            .call_type(Call::new().callee(account_id))
+            .exec_input(
+                ExecutionInput::new(Selector::new(selector_bytes!("checksum")))
+                    .push_arg(vec![1u8, 2, 3])
+                    .push_arg(vec![0xFFu8])
+                    .push_arg(42u32),
+            )
+            .returns::<MessageResult<u32>>()
+            .fire();
+        assert_eq!(result, Ok(Err(LangError::CouldNotReadInput)));
+        Ok(())
+    })
+    .unwrap();
+}
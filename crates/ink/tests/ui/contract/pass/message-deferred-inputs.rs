@@ -0,0 +1,104 @@
+use ink::{
+    env::{
+        call::{
+            build_call,
+            Call,
+            ExecutionInput,
+            Selector,
+        },
+        DefaultEnvironment,
+    },
+    reflect::{
+        ContractMessageDecoder,
+        DecodeDispatch,
+        DispatchError,
+    },
+    selector_bytes,
+    LangError,
+    MessageResult,
+};
+
+#[ink::contract]
+pub mod contract {
+    use ink::prelude::vec::Vec;
+
+    #[ink(storage)]
+    pub struct Contract {}
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {}
+        }
+
+        #[ink(message, deferred_inputs)]
+        pub fn upload(&self, data: Vec<u8>, seed: u32) -> u32 {
+            data.iter()
+                .fold(seed, |acc, byte| acc.wrapping_add(*byte as u32))
+        }
+
+        #[ink(message, deferred_inputs)]
+        pub fn checksum(&self, data: &[u8], name: &str) -> u32 {
+            (data.len() + name.len()) as u32
+        }
+
+        #[ink(message)]
+        pub fn len(&self, data: Vec<u8>) -> u32 {
+            data.len() as u32
+        }
+    }
+}
+
+use contract::{
+    Contract,
+    ContractRef,
+};
+
+fn main() {
+    // Decoding deferred inputs is postponed until the checks of the message have
+    // passed, so invalid inputs are not detected when decoding the dispatchable.
+    let mut input_bytes = Vec::new();
+    input_bytes.extend(selector_bytes!("upload"));
+    input_bytes.extend([0xFF; 3]);
+    assert!(
+        <<Contract as ContractMessageDecoder>::Type as DecodeDispatch>::decode_dispatch(
+            &mut &input_bytes[..]
+        )
+        .is_ok()
+    );
+    // Messages without deferred inputs are decoded along with their selector.
+    let mut input_bytes = Vec::new();
+    input_bytes.extend(selector_bytes!("len"));
+    input_bytes.extend([0xFF; 3]);
+    assert_eq!(
+        <<Contract as ContractMessageDecoder>::Type as DecodeDispatch>::decode_dispatch(
+            &mut &input_bytes[..]
+        )
+        .map(|_| ())
+        .unwrap_err(),
+        DispatchError::InvalidParameters,
+    );
+
+    ink::env::test::run_test::<DefaultEnvironment, _>(|_| {
+        let account_id = [0x01; 32].into();
+        ink::off_chain::register_contract::<Contract, _>(
+            account_id,
+            Contract::constructor,
+        );
+        let contract = ContractRef::from_account_id(account_id);
+        assert_eq!(contract.upload(vec![1, 2, 3], 42), 48);
+        assert_eq!(contract.checksum(&[0; 10], "ink"), 13);
+
+        let result = build_call::<DefaultEnvironment>()
+            .call_type(Call::new().callee(account_id))
+            .exec_input(
+                ExecutionInput::new(Selector::new(selector_bytes!("upload")))
+                    .push_arg(0xFFu8),
+            )
+            .returns::<MessageResult<u32>>()
+            .fire();
+        assert_eq!(result, Ok(Err(LangError::CouldNotReadInput)));
+        Ok(())
+    })
+    .unwrap();
+}
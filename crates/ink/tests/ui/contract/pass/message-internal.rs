@@ -0,0 +1,35 @@
+#[ink::contract]
+mod contract {
+    #[ink(storage)]
+    pub struct Contract {
+        value: i32,
+    }
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self { value: 0 }
+        }
+
+        #[ink(message, internal)]
+        pub fn inc(&mut self, by: i32) {
+            self.value += by;
+        }
+
+        #[ink(message, payable)]
+        #[ink(internal(
+            code_hash = "0x0101010101010101010101010101010101010101010101010101010101010101",
+            code_hash = "0x0202020202020202020202020202020202020202020202020202020202020202",
+        ))]
+        pub fn dec(&mut self, by: i32) {
+            self.value -= by;
+        }
+
+        #[ink(message)]
+        pub fn get(&self) -> i32 {
+            self.value
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,31 @@
+#[ink::contract]
+mod contract {
+    #[ink(storage)]
+    pub struct Contract {
+        value: Balance,
+    }
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self { value: 0 }
+        }
+
+        #[ink(message, non_reentrant)]
+        pub fn withdraw(&mut self) -> Balance {
+            core::mem::take(&mut self.value)
+        }
+
+        #[ink(message, payable, non_reentrant)]
+        pub fn deposit(&mut self) {
+            self.value += self.env().transferred_value();
+        }
+
+        #[ink(message)]
+        pub fn value(&self) -> Balance {
+            self.value
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,61 @@
+#[derive(Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum EthEnv {}
+
+impl ink_env::Environment for EthEnv {
+    const MAX_EVENT_TOPICS: usize = 4;
+    type AccountId = [u8; 20];
+    type Balance = u128;
+    type Hash = [u8; 32];
+    type Timestamp = u64;
+    type BlockNumber = u32;
+    type ChainExtension = ();
+}
+
+#[ink::contract(env = super::EthEnv)]
+mod contract {
+    use ink::contrib::{
+        AccessControl,
+        AccessControlled,
+    };
+
+    #[ink(storage)]
+    pub struct Contract {
+        access_control: AccessControl<Environment>,
+        value: i32,
+    }
+
+    impl AccessControlled<Environment> for Contract {
+        fn access_control(&mut self) -> &mut AccessControl<Environment> {
+            &mut self.access_control
+        }
+    }
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {
+                access_control: AccessControl::with_admin(Self::env().caller()),
+                value: 0,
+            }
+        }
+
+        #[ink(message, only(roles = ["admin"]))]
+        pub fn set(&mut self, value: i32) {
+            self.value = value;
+        }
+    }
+}
+
+use contract::ContractRef;
+use ink::contrib::{
+    role,
+    AccessControlError,
+};
+
+// The generated messages take the accounts of the contract's environment.
+fn _grant_role(contract: &mut ContractRef, account: [u8; 20]) {
+    let _: Result<(), AccessControlError> = contract.grant_role(role("operator"), account);
+}
+
+fn main() {}
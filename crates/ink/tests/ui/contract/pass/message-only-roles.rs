@@ -0,0 +1,91 @@
+#[ink::contract]
+mod contract {
+    use ink::contrib::{
+        AccessControl,
+        AccessControlled,
+    };
+
+    #[ink(storage)]
+    pub struct Contract {
+        access_control: AccessControl,
+        value: i32,
+    }
+
+    impl AccessControlled for Contract {
+        fn access_control(&mut self) -> &mut AccessControl {
+            &mut self.access_control
+        }
+    }
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {
+                access_control: AccessControl::with_admin(Self::env().caller()),
+                value: 0,
+            }
+        }
+
+        #[ink(message, only(roles = ["admin", "operator"]))]
+        pub fn set(&mut self, value: i32) {
+            self.value = value;
+        }
+
+        #[ink(message)]
+        pub fn get(&self) -> i32 {
+            self.value
+        }
+    }
+}
+
+use contract::{
+    Contract,
+    ContractRef,
+};
+use ink::{
+    contrib::{
+        role,
+        AccessControlError,
+    },
+    env::{
+        call::FromAccountId,
+        DefaultEnvironment,
+    },
+    LangError,
+};
+
+fn main() {
+    ink::env::test::run_test::<DefaultEnvironment, _>(|accounts| {
+        let account_id = [0x01; 32].into();
+        ink::env::test::set_caller::<DefaultEnvironment>(accounts.alice);
+        ink::off_chain::register_contract::<Contract, _>(
+            account_id,
+            Contract::constructor,
+        );
+        let mut contract = ContractRef::from_account_id(account_id);
+
+        // Calls are made on behalf of the callee of the test.
+        ink::env::test::set_callee::<DefaultEnvironment>(accounts.bob);
+        assert_eq!(contract.set_checked(1), Err(LangError::CallerNotAllowed));
+        assert_eq!(
+            contract.grant_role(role("operator"), accounts.bob),
+            Err(AccessControlError::MissingRole)
+        );
+
+        ink::env::test::set_callee::<DefaultEnvironment>(accounts.alice);
+        assert_eq!(contract.set_checked(2), Ok(()));
+        assert_eq!(contract.grant_role(role("operator"), accounts.bob), Ok(()));
+
+        ink::env::test::set_callee::<DefaultEnvironment>(accounts.bob);
+        assert_eq!(contract.set_checked(3), Ok(()));
+        assert_eq!(contract.get(), 3);
+
+        ink::env::test::set_callee::<DefaultEnvironment>(accounts.alice);
+        assert_eq!(contract.revoke_role(role("operator"), accounts.bob), Ok(()));
+        ink::env::test::set_callee::<DefaultEnvironment>(accounts.bob);
+        assert_eq!(contract.set_checked(4), Err(LangError::CallerNotAllowed));
+        assert_eq!(contract.get(), 3);
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,66 @@
+#[ink::contract]
+mod contract {
+    use ink::pagination::{
+        paginate,
+        Cursor,
+        Page,
+    };
+
+    #[ink(storage)]
+    pub struct Contract {
+        values: ink::prelude::vec::Vec<u32>,
+    }
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {
+                values: (0..10).collect(),
+            }
+        }
+
+        #[ink(message, paginated)]
+        pub fn values(&self, cursor: Option<Cursor>, limit: u32) -> Page<u32> {
+            paginate(self.values.iter().copied(), cursor, limit)
+        }
+
+        #[ink(message, paginated)]
+        pub fn keys(&self, cursor: Option<Cursor>) -> (Vec<u8>, Option<Cursor>) {
+            paginate(0..=u8::MAX, cursor, 32)
+        }
+
+        #[ink(message)]
+        pub fn len(&self) -> u32 {
+            self.values.len() as u32
+        }
+    }
+}
+
+use contract::Contract;
+use ink::metadata::InkProject;
+
+fn generate_metadata() -> InkProject {
+    extern "Rust" {
+        fn __ink_generate_metadata() -> InkProject;
+    }
+
+    unsafe { __ink_generate_metadata() }
+}
+
+fn main() {
+    let contract = Contract::constructor();
+    let (page, cursor) = contract.values(None, 4);
+    assert_eq!(page, vec![0, 1, 2, 3]);
+    let (page, cursor) = contract.values(cursor, 8);
+    assert_eq!(page, vec![4, 5, 6, 7, 8, 9]);
+    assert_eq!(cursor, None);
+
+    let metadata = generate_metadata();
+    let paginated = metadata
+        .spec()
+        .messages()
+        .iter()
+        .map(|message| message.paginated())
+        .collect::<Vec<_>>();
+    assert_eq!(paginated, vec![true, true, false]);
+}
@@ -0,0 +1,25 @@
+#[ink::contract]
+mod contract {
+    #[ink(storage)]
+    pub struct Contract {}
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {}
+        }
+
+        #[ink(message, selector = 1, payable(min = 1_000))]
+        pub fn deposit(&self) {}
+
+        #[ink(message, selector = 2)]
+        pub fn message(&self) {}
+    }
+}
+
+use contract::Contract;
+
+fn main() {
+    assert!(<Contract as ::ink::reflect::DispatchableMessageInfo<1>>::PAYABLE);
+    assert!(!<Contract as ::ink::reflect::DispatchableMessageInfo<2>>::PAYABLE);
+}
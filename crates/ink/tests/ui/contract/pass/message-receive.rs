@@ -0,0 +1,63 @@
+use ink::{
+    env::DefaultEnvironment,
+    reflect::{
+        ContractMessageDecoder,
+        DecodeDispatch,
+        DispatchError,
+    },
+    selector_bytes,
+};
+
+#[ink::contract]
+pub mod contract {
+    #[ink(storage)]
+    pub struct Contract {
+        received: Balance,
+    }
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self { received: 0 }
+        }
+
+        #[ink(message, receive)]
+        pub fn receive(&mut self) {
+            self.received += self.env().transferred_value();
+        }
+
+        #[ink(message)]
+        pub fn received(&self) -> Balance {
+            self.received
+        }
+    }
+}
+
+use contract::Contract;
+
+fn decode_dispatch(mut input: &[u8]) -> Result<(), DispatchError> {
+    <<Contract as ContractMessageDecoder>::Type as DecodeDispatch>::decode_dispatch(
+        &mut input,
+    )
+    .map(|_| ())
+}
+
+fn main() {
+    ink::env::test::run_test::<DefaultEnvironment, _>(|_| {
+        // Calls without input data and value have no selector.
+        assert_eq!(decode_dispatch(&[]), Err(DispatchError::InvalidSelector));
+
+        // Plain transfers dispatch to `receive`.
+        ink::env::test::set_value_transferred::<DefaultEnvironment>(100);
+        assert_eq!(decode_dispatch(&[]), Ok(()));
+
+        // Calls with a selector are dispatched as usual.
+        assert_eq!(decode_dispatch(&selector_bytes!("received")), Ok(()));
+        assert_eq!(
+            decode_dispatch(&selector_bytes!("unknown_selector")),
+            Err(DispatchError::UnknownSelector)
+        );
+        Ok(())
+    })
+    .unwrap()
+}
@@ -0,0 +1,63 @@
+use ink::{
+    env::{
+        call::{
+            build_call,
+            Call,
+            ExecutionInput,
+            Selector,
+        },
+        DefaultEnvironment,
+    },
+    sol::SolEncoded,
+};
+
+#[ink::contract(abi = "solidity")]
+mod contract {
+    use ink::prelude::string::String;
+
+    #[ink(storage)]
+    pub struct Contract {}
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {}
+        }
+
+        #[ink(message, selector = 0xCAFEBABE)]
+        pub fn checked_div(&self, a: u128, b: u128) -> Result<u128, String> {
+            a.checked_div(b)
+                .ok_or_else(|| String::from("division by zero"))
+        }
+    }
+}
+
+use contract::Contract;
+
+fn main() {
+    ink::env::test::run_test::<DefaultEnvironment, _>(|_| {
+        let account_id = [0x01; 32].into();
+        ink::off_chain::register_contract::<Contract, _>(
+            account_id,
+            Contract::constructor,
+        );
+        let call = |a: u128, b: u128| {
+            build_call::<DefaultEnvironment>()
+                .call_type(Call::new().callee(account_id))
+                .exec_input(
+                    ExecutionInput::new(Selector::new([0xCA, 0xFE, 0xBA, 0xBE]))
+                        .push_arg(SolEncoded((a, b))),
+                )
+        };
+
+        // `Ok` returns the ABI encoded value.
+        let result = call(6, 3).returns::<SolEncoded<(u128,)>>().fire();
+        assert_eq!(result, Ok(SolEncoded((2,))));
+
+        // `Err` reverts with the ABI encoded error.
+        let result = call(6, 0).returns::<SolEncoded<(String,)>>().fire();
+        assert_eq!(result, Ok(SolEncoded((String::from("division by zero"),))));
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,123 @@
+#[ink::contract]
+mod contract {
+    use ink::contrib::{
+        AccessControl,
+        AccessControlled,
+        OperationId,
+        Timelock,
+        TimelockError,
+        Timelocked,
+    };
+
+    #[ink(storage)]
+    pub struct Contract {
+        access_control: AccessControl,
+        timelock: Timelock,
+        value: i32,
+    }
+
+    impl AccessControlled for Contract {
+        fn access_control(&mut self) -> &mut AccessControl {
+            &mut self.access_control
+        }
+    }
+
+    impl Timelocked for Contract {
+        fn timelock(&mut self) -> &mut Timelock {
+            &mut self.timelock
+        }
+    }
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {
+                access_control: AccessControl::with_admin(Self::env().caller()),
+                timelock: Timelock::new(),
+                value: 0,
+            }
+        }
+
+        #[ink(message, timelocked(delay = "1 minute"))]
+        pub fn set(&mut self, value: i32) {
+            self.value = value;
+        }
+
+        #[ink(message, only(roles = ["admin"]), timelocked(delay = "1 minute"))]
+        pub fn reset(&mut self) {
+            self.value = 0;
+        }
+
+        #[ink(message)]
+        pub fn execute(&mut self, id: OperationId) -> Result<(), TimelockError> {
+            self.execute_timelocked(id)
+        }
+
+        #[ink(message)]
+        pub fn cancel(&mut self, id: OperationId) -> Result<(), TimelockError> {
+            self.cancel_timelocked(id)
+        }
+
+        #[ink(message)]
+        pub fn get(&self) -> i32 {
+            self.value
+        }
+    }
+}
+
+use contract::{
+    Contract,
+    ContractRef,
+};
+use ink::{
+    contrib::TimelockError,
+    env::{
+        call::FromAccountId,
+        DefaultEnvironment,
+    },
+};
+
+fn main() {
+    ink::env::test::run_test::<DefaultEnvironment, _>(|accounts| {
+        let account_id = [0x01; 32].into();
+        ink::env::test::set_caller::<DefaultEnvironment>(accounts.alice);
+        ink::off_chain::register_contract::<Contract, _>(
+            account_id,
+            Contract::constructor,
+        );
+        let mut contract = ContractRef::from_account_id(account_id);
+
+        // Calls are made on behalf of the callee of the test. They are queued
+        // instead of executed and return the identifier of the queued call.
+        ink::env::test::set_callee::<DefaultEnvironment>(accounts.alice);
+        assert_eq!(contract.set(42), 0);
+        assert_eq!(contract.get(), 0);
+        assert_eq!(contract.execute(0), Err(TimelockError::NotReady));
+        assert_eq!(contract.execute(1), Err(TimelockError::UnknownOperation));
+
+        ink::env::test::advance_blocks::<DefaultEnvironment>(10, 6_000);
+        assert_eq!(contract.execute(0), Ok(()));
+        assert_eq!(contract.get(), 42);
+        assert_eq!(contract.execute(0), Err(TimelockError::UnknownOperation));
+
+        // Only the account which queued a call can cancel it.
+        assert_eq!(contract.set(7), 1);
+        ink::env::test::set_callee::<DefaultEnvironment>(accounts.bob);
+        assert_eq!(contract.cancel(1), Err(TimelockError::CallerNotAllowed));
+        ink::env::test::set_callee::<DefaultEnvironment>(accounts.alice);
+        assert_eq!(contract.cancel(1), Ok(()));
+        ink::env::test::advance_blocks::<DefaultEnvironment>(10, 6_000);
+        assert_eq!(contract.execute(1), Err(TimelockError::UnknownOperation));
+
+        // The guards of the message apply to the executing caller as well.
+        assert_eq!(contract.reset(), 2);
+        ink::env::test::advance_blocks::<DefaultEnvironment>(10, 6_000);
+        ink::env::test::set_callee::<DefaultEnvironment>(accounts.bob);
+        assert_eq!(contract.execute(2), Err(TimelockError::CallerNotAllowed));
+        ink::env::test::set_callee::<DefaultEnvironment>(accounts.alice);
+        assert_eq!(contract.execute(2), Ok(()));
+        assert_eq!(contract.get(), 0);
+        Ok(())
+    })
+    .unwrap();
+}
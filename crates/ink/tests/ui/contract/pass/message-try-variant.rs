@@ -0,0 +1,46 @@
+#[ink::contract]
+mod contract {
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, scale_info::TypeInfo)]
+    pub enum Error {
+        Failed,
+    }
+
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    #[ink(storage)]
+    #[derive(Default)]
+    pub struct Contract {}
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self::default()
+        }
+
+        #[ink(message)]
+        pub fn message(&self) {}
+
+        #[ink(message)]
+        pub fn message_result(&self, value: u8) -> core::result::Result<u8, Error> {
+            Ok(value)
+        }
+
+        #[ink(message)]
+        pub fn message_result_alias(&mut self) -> Result<()> {
+            Err(Error::Failed)
+        }
+    }
+}
+
+use contract::{
+    ContractRef,
+    Error,
+};
+
+#[allow(dead_code)]
+fn call_try_variants(contract: &mut ContractRef) {
+    let _: Result<u8, ink::CalleeError<Error>> = contract.try_message_result(42);
+    let _: Result<(), ink::CalleeError<Error>> = contract.try_message_result_alias();
+}
+
+fn main() {}
@@ -0,0 +1,71 @@
+use ink::{
+    reflect::{
+        ContractMessageDecoder,
+        DecodeDispatch,
+        DispatchError,
+    },
+    selector_bytes,
+};
+use scale::Encode;
+
+#[ink::contract]
+pub mod contract {
+    #[ink(storage)]
+    pub struct Contract {}
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self {}
+        }
+
+        #[ink(message)]
+        pub fn message(&self, _input: i32) {}
+
+        #[ink(message, selector = _)]
+        pub fn fallback(&self, input: ink::RawInput) -> [u8; 4] {
+            let _data: &[u8] = input.data();
+            input.selector()
+        }
+    }
+}
+
+use contract::Contract;
+
+fn main() {
+    // Call with an unknown selector and arbitrary data dispatches to `fallback`.
+    {
+        let mut input_bytes = Vec::new();
+        input_bytes.extend(selector_bytes!("unknown_selector"));
+        input_bytes.extend([0x01, 0x02, 0x03]);
+        assert!(
+            <<Contract as ContractMessageDecoder>::Type as DecodeDispatch>::decode_dispatch(
+                &mut &input_bytes[..]).is_ok()
+        );
+    }
+    // Call with an unknown selector and without data dispatches to `fallback`.
+    {
+        let input_bytes = selector_bytes!("unknown_selector");
+        assert!(
+            <<Contract as ContractMessageDecoder>::Type as DecodeDispatch>::decode_dispatch(
+                &mut &input_bytes[..]).is_ok()
+        );
+    }
+    // Call to `message` still decodes its parameters.
+    {
+        let mut input_bytes = Vec::new();
+        input_bytes.extend(selector_bytes!("message"));
+        assert_eq!(
+            <<Contract as ContractMessageDecoder>::Type
+                as DecodeDispatch>::decode_dispatch(&mut &input_bytes[..])
+                .map(|_| ())
+                .unwrap_err(),
+            DispatchError::InvalidParameters,
+        );
+        input_bytes.extend(42i32.encode());
+        assert!(
+            <<Contract as ContractMessageDecoder>::Type as DecodeDispatch>::decode_dispatch(
+                &mut &input_bytes[..]).is_ok()
+        );
+    }
+}
@@ -0,0 +1,115 @@
+#[ink::contract]
+mod token {
+    use ink::{
+        prelude::vec::Vec,
+        standards::psp22::{
+            PSP22Data,
+            PSP22Error,
+            PSP22,
+        },
+    };
+
+    #[ink(storage)]
+    #[derive(Default)]
+    pub struct Token {
+        psp22: PSP22Data,
+    }
+
+    impl Token {
+        #[ink(constructor)]
+        pub fn new(total_supply: Balance) -> Self {
+            let mut instance = Self::default();
+            instance
+                .psp22
+                .mint(Self::env().caller(), total_supply)
+                .expect("minting the initial supply must succeed");
+            instance
+        }
+    }
+
+    impl PSP22 for Token {
+        #[ink(message)]
+        fn total_supply(&self) -> Balance {
+            self.psp22.total_supply()
+        }
+
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> Balance {
+            self.psp22.balance_of(owner)
+        }
+
+        #[ink(message)]
+        fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+            self.psp22.allowance(owner, spender)
+        }
+
+        #[ink(message)]
+        fn transfer(
+            &mut self,
+            to: AccountId,
+            value: Balance,
+            _data: Vec<u8>,
+        ) -> Result<(), PSP22Error> {
+            self.psp22.transfer(self.env().caller(), to, value)
+        }
+
+        #[ink(message)]
+        fn transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+            _data: Vec<u8>,
+        ) -> Result<(), PSP22Error> {
+            self.psp22.transfer_from(self.env().caller(), from, to, value)
+        }
+
+        #[ink(message)]
+        fn approve(&mut self, spender: AccountId, value: Balance) -> Result<(), PSP22Error> {
+            self.psp22.approve(self.env().caller(), spender, value);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn increase_allowance(
+            &mut self,
+            spender: AccountId,
+            delta_value: Balance,
+        ) -> Result<(), PSP22Error> {
+            self.psp22
+                .increase_allowance(self.env().caller(), spender, delta_value);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn decrease_allowance(
+            &mut self,
+            spender: AccountId,
+            delta_value: Balance,
+        ) -> Result<(), PSP22Error> {
+            self.psp22
+                .decrease_allowance(self.env().caller(), spender, delta_value)
+                .map(|_| ())
+        }
+    }
+}
+
+use ink::standards::psp22::PSP22 as _;
+
+fn main() {
+    ink::env::test::run_test::<ink::env::DefaultEnvironment, _>(|accounts| {
+        let mut token = token::Token::new(100);
+        assert_eq!(token.total_supply(), 100);
+        assert_eq!(token.transfer(accounts.bob, 10, Vec::new()), Ok(()));
+        assert_eq!(token.balance_of(accounts.bob), 10);
+        assert_eq!(token.balance_of(accounts.alice), 90);
+        Ok(())
+    })
+    .unwrap();
+
+    // The selectors follow the PSP22 standard.
+    assert_eq!(
+        <token::Token as ink::reflect::DispatchableMessageInfo<0x162df8c2>>::SELECTOR,
+        [0x16, 0x2d, 0xf8, 0xc2],
+    );
+}
@@ -0,0 +1,81 @@
+pub trait Config {
+    type Amount: ink::storage::traits::Packed
+        + ink::storage::traits::StorageLayout
+        + Default
+        + core::ops::AddAssign
+        + From<u32>
+        + Into<u128>
+        + Copy
+        + 'static;
+    const DECIMALS: u8;
+}
+
+pub struct SixDecimals;
+
+impl Config for SixDecimals {
+    type Amount = u64;
+    const DECIMALS: u8 = 6;
+}
+
+pub struct EighteenDecimals;
+
+impl Config for EighteenDecimals {
+    type Amount = u128;
+    const DECIMALS: u8 = 18;
+}
+
+#[ink::contract(config = super::SixDecimals)]
+mod contract {
+    use super::Config;
+
+    #[ink(storage)]
+    pub struct Contract<T: Config> {
+        total: T::Amount,
+    }
+
+    impl<T: Config> Contract<T> {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self {
+                total: Default::default(),
+            }
+        }
+
+        #[ink(message)]
+        pub fn inc(&mut self, by: u32) {
+            self.total += T::Amount::from(by);
+        }
+
+        #[ink(message)]
+        pub fn total(&self) -> u128 {
+            self.total.into()
+        }
+
+        #[ink(message)]
+        pub fn decimals(&self) -> u8 {
+            T::DECIMALS
+        }
+    }
+}
+
+use contract::Contract;
+
+fn main() {
+    ink::env::test::run_test::<ink::env::DefaultEnvironment, _>(|_| {
+        let mut six = Contract::<SixDecimals>::new();
+        six.inc(5);
+        assert_eq!(six.total(), 5);
+        assert_eq!(six.decimals(), 6);
+
+        let mut eighteen = Contract::<EighteenDecimals>::new();
+        eighteen.inc(7);
+        assert_eq!(eighteen.total(), 7);
+        assert_eq!(eighteen.decimals(), 18);
+
+        // The contract is deployed with the type chosen via `config`.
+        let deployed: Contract = six;
+        assert_eq!(deployed.decimals(), 6);
+        Ok(())
+    })
+    .unwrap()
+}
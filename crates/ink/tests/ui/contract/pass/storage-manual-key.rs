@@ -0,0 +1,45 @@
+#[ink::contract]
+mod contract {
+    use ink::storage::{
+        traits::StorageKey,
+        Lazy,
+        Mapping,
+    };
+
+    #[ink(storage)]
+    #[derive(Default)]
+    pub struct Contract {
+        #[ink(storage_key = 0x1234_5678)]
+        balances: Mapping<AccountId, Balance>,
+        #[ink(storage_key = 0x1234_5679)]
+        owner: Lazy<AccountId>,
+        total_supply: Balance,
+    }
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self::default()
+        }
+
+        #[ink(message)]
+        pub fn total_supply(&self) -> Balance {
+            self.total_supply
+        }
+
+        #[ink(message)]
+        pub fn keys(&self) -> (u32, u32) {
+            (self.balances.key(), self.owner.key())
+        }
+    }
+}
+
+fn main() {
+    use contract::Contract;
+    ink::env::test::run_test::<ink::env::DefaultEnvironment, _>(|_| {
+        let contract = Contract::constructor();
+        assert_eq!(contract.keys(), (0x1234_5678, 0x1234_5679));
+        Ok(())
+    })
+    .unwrap()
+}
@@ -0,0 +1,49 @@
+#[ink::contract]
+mod contract {
+    use ink::storage::{
+        migrate::{
+            migrate_value,
+            Migrate,
+        },
+        traits::StorageKey,
+    };
+
+    #[derive(scale::Decode, scale::Encode)]
+    pub struct V1Layout {
+        value: u32,
+    }
+
+    #[ink(storage, storage_version = 2)]
+    pub struct Contract {
+        value: u64,
+    }
+
+    fn migrate_v1_to_v2(old: V1Layout) -> Contract {
+        Contract {
+            value: old.value as u64,
+        }
+    }
+
+    impl Migrate for Contract {
+        fn migrate(from_version: u32) {
+            match from_version {
+                1 => migrate_value(&<Self as StorageKey>::KEY, migrate_v1_to_v2),
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn constructor() -> Self {
+            Self { value: 0 }
+        }
+
+        #[ink(message)]
+        pub fn value(&self) -> u64 {
+            self.value
+        }
+    }
+}
+
+fn main() {}
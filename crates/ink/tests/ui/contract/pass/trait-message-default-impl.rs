@@ -0,0 +1,95 @@
+#[ink::trait_definition]
+pub trait Counter {
+    #[ink(message)]
+    fn get(&self) -> u32;
+
+    #[ink(message)]
+    fn set(&mut self, value: u32);
+
+    #[ink(message)]
+    fn inc(&mut self, by: u32) -> u32 {
+        let value = self.get() + by;
+        self.set(value);
+        value
+    }
+
+    #[ink(message)]
+    fn reset(&mut self) {
+        self.set(0)
+    }
+
+    #[ink(message)]
+    fn dec_saturating(&mut self, by: u32) -> u32 {
+        let value = self.get().saturating_sub(by);
+        self.set(value);
+        value
+    }
+
+    #[ink(message)]
+    fn caller_is_self(&self) -> bool {
+        ink::env::caller::<Self::Env>() == ink::env::account_id::<Self::Env>()
+    }
+}
+
+#[ink::contract]
+mod contract {
+    use super::Counter;
+
+    #[ink(storage)]
+    pub struct Contract {
+        value: u32,
+    }
+
+    impl Contract {
+        #[ink(constructor)]
+        pub fn new(value: u32) -> Self {
+            Self { value }
+        }
+    }
+
+    impl Counter for Contract {
+        #[ink(message)]
+        fn get(&self) -> u32 {
+            self.value
+        }
+
+        #[ink(message)]
+        fn set(&mut self, value: u32) {
+            self.value = value;
+        }
+
+        #[ink(message)]
+        fn inc(&mut self, by: u32) -> u32;
+
+        #[ink(message)]
+        fn reset(&mut self) {
+            self.value = 1;
+        }
+
+        #[ink(message)]
+        fn dec_saturating(&mut self, by: u32) -> u32;
+
+        #[ink(message)]
+        fn caller_is_self(&self) -> bool;
+    }
+}
+
+use contract::Contract;
+
+fn main() {
+    ink::env::test::run_test::<ink::env::DefaultEnvironment, _>(|accounts| {
+        let mut contract = Contract::new(1);
+        assert_eq!(<Contract as Counter>::inc(&mut contract, 2), 3);
+        assert_eq!(<Contract as Counter>::get(&contract), 3);
+        <Contract as Counter>::reset(&mut contract);
+        assert_eq!(<Contract as Counter>::get(&contract), 1);
+        assert_eq!(<Contract as Counter>::dec_saturating(&mut contract, 2), 0);
+        ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.charlie);
+        assert!(!<Contract as Counter>::caller_is_self(&contract));
+        ink::env::test::set_callee::<ink::env::DefaultEnvironment>(accounts.bob);
+        assert!(<Contract as Counter>::caller_is_self(&contract));
+        Ok(())
+    })
+    .unwrap()
+}
@@ -0,0 +1,11 @@
+use ink::runtime_call::RuntimeCall;
+
+#[derive(RuntimeCall)]
+#[runtime_call(index = 0)]
+enum SystemCall {
+    #[runtime_call(index = 0)]
+    Remark { remark: Vec<u8> },
+    RemarkWithEvent { remark: Vec<u8> },
+}
+
+fn main() {}
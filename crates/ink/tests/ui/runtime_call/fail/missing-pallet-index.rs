@@ -0,0 +1,9 @@
+use ink::runtime_call::RuntimeCall;
+
+#[derive(RuntimeCall)]
+enum SystemCall {
+    #[runtime_call(index = 0)]
+    Remark { remark: Vec<u8> },
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use ink::runtime_call::RuntimeCall;
+
+#[derive(RuntimeCall)]
+#[runtime_call(index = 0)]
+struct Remark {
+    remark: Vec<u8>,
+}
+
+fn main() {}
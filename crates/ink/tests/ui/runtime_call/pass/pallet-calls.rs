@@ -0,0 +1,57 @@
+use ink::{
+    primitives::AccountId,
+    runtime_call::{
+        PalletCall,
+        RuntimeCall,
+    },
+};
+use scale::Encode as _;
+
+#[derive(RuntimeCall)]
+#[runtime_call(index = 4)]
+enum BalancesCall {
+    #[runtime_call(index = 3)]
+    TransferKeepAlive {
+        dest: (u8, AccountId),
+        #[runtime_call(compact)]
+        value: u128,
+    },
+    #[runtime_call(index = 7)]
+    ForceUnreserve(AccountId, u128),
+}
+
+#[derive(RuntimeCall)]
+#[runtime_call(index = 0)]
+enum SystemCall {
+    #[runtime_call(index = 0)]
+    Remark { remark: Vec<u8> },
+    #[runtime_call(index = 9)]
+    Noop,
+}
+
+fn assert_runtime_call<T: ink::env::runtime_call::RuntimeCall>(_: &T) {}
+
+fn main() {
+    let dest = AccountId::from([0x01; 32]);
+    let call = BalancesCall::TransferKeepAlive {
+        dest: (0, dest),
+        value: 100,
+    };
+    assert_runtime_call(&call);
+    assert_eq!(
+        call.encode(),
+        PalletCall::new(4, 3, ((0u8, dest), scale::Compact(100u128))).encode()
+    );
+    assert_eq!(
+        BalancesCall::ForceUnreserve(dest, 100).encode(),
+        PalletCall::new(4, 7, (dest, 100u128)).encode()
+    );
+    assert_eq!(
+        SystemCall::Remark {
+            remark: vec![0x01, 0x02]
+        }
+        .encode(),
+        PalletCall::new(0, 0, vec![0x01u8, 0x02]).encode()
+    );
+    assert_eq!(SystemCall::Noop.encode(), vec![0, 9]);
+}
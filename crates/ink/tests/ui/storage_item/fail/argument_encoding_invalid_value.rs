@@ -0,0 +1,6 @@
+#[ink::storage_item(encoding = "compact")]
+struct Order {
+    price: u128,
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+#[ink::storage_item(namespace = ownable)]
+#[derive(Default)]
+struct Ownable {
+    owner: u128,
+}
+
+fn main() {}
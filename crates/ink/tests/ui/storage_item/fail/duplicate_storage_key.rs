@@ -0,0 +1,9 @@
+#[ink::storage_item]
+struct Contract {
+    #[ink(storage_key = 0x1234_5678)]
+    a: ink_storage::Mapping<u128, u128>,
+    #[ink(storage_key = 0x1234_5678)]
+    b: ink_storage::Lazy<u128>,
+}
+
+fn main() {}
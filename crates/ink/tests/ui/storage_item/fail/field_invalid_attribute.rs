@@ -0,0 +1,7 @@
+#[ink::storage_item]
+struct Contract {
+    #[ink(message)]
+    a: ink_storage::Lazy<u128>,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+#![deny(deprecated)]
+
+use ink_prelude::vec::Vec;
+
+#[ink::storage_item]
+struct Contract {
+    a: u32,
+    b: Vec<u128>,
+}
+
+fn main() {}
@@ -0,0 +1,109 @@
+use ink::storage::{
+    traits::{
+        Packed,
+        Storable,
+    },
+    Mapping,
+};
+
+type Balance = u128;
+type BlockNumber = u32;
+
+#[ink::storage_item(encoding = compact)]
+#[derive(Debug, PartialEq, Eq)]
+struct Order {
+    maker: [u8; 32],
+    // Aliases are only compacted if flagged explicitly.
+    #[codec(compact)]
+    price: Balance,
+    amount: u128,
+    expiry: BlockNumber,
+    side: u8,
+}
+
+mod fixed_point {
+    /// A balance type of a custom environment, which is no integer.
+    #[derive(Debug, PartialEq, Eq, scale::Decode, scale::Encode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Balance {
+        pub mantissa: u64,
+        pub decimals: u8,
+    }
+
+    #[ink::storage_item(encoding = compact)]
+    #[derive(Debug, PartialEq, Eq)]
+    pub struct Account {
+        pub balance: Balance,
+        // Primitive integers flagged with `#[codec(..)]` keep their encoding.
+        #[codec(skip)]
+        pub cached: u64,
+        pub nonce: u64,
+    }
+}
+
+#[ink::storage_item(encoding = compact)]
+#[derive(Debug, PartialEq, Eq)]
+enum Fill {
+    Partial { filled: u64 },
+    Complete,
+}
+
+#[ink::storage_item]
+struct OrderBook {
+    orders: Mapping<u32, Order>,
+    fills: Mapping<u32, Fill>,
+}
+
+fn assert_packed<P: Packed>() {}
+
+fn encoded_len<T: Storable>(value: &T) -> usize {
+    let mut encoded = Vec::new();
+    Storable::encode(value, &mut encoded);
+    encoded.len()
+}
+
+fn main() {
+    assert_packed::<Order>();
+    assert_packed::<Fill>();
+
+    let order = Order {
+        maker: [0x01; 32],
+        price: 1_000,
+        amount: 50_000_000,
+        expiry: 1_000_000,
+        side: 0,
+    };
+    // 32 + 2 + 4 + 4 + 1 bytes instead of 32 + 16 + 16 + 4 + 1 bytes, the block
+    // number alias not being compacted.
+    assert_eq!(encoded_len(&order), 43);
+    let mut encoded = Vec::new();
+    Storable::encode(&order, &mut encoded);
+    assert_eq!(<Order as Storable>::decode(&mut &encoded[..]), Ok(order));
+
+    // Values close to the maximum take one more byte than their default encoding.
+    let order = Order {
+        maker: [0x01; 32],
+        price: Balance::MAX,
+        amount: u128::MAX,
+        expiry: BlockNumber::MAX,
+        side: 0,
+    };
+    assert_eq!(encoded_len(&order), 32 + 17 + 17 + 4 + 1);
+
+    let account = fixed_point::Account {
+        balance: fixed_point::Balance {
+            mantissa: 1_000,
+            decimals: 2,
+        },
+        cached: 0,
+        nonce: 1,
+    };
+    assert_packed::<fixed_point::Account>();
+    assert_eq!(encoded_len(&account), 8 + 1 + 1);
+
+    assert_eq!(encoded_len(&Fill::Partial { filled: 1 }), 2);
+    assert_eq!(encoded_len(&Fill::Complete), 1);
+}
@@ -0,0 +1,47 @@
+use ink_primitives::KeyComposer;
+use ink_storage::{
+    traits::StorageKey,
+    Lazy,
+    Mapping,
+};
+
+#[ink::storage_item]
+#[derive(Default)]
+struct Contract {
+    #[ink(storage_key = 0x1234_5678)]
+    a: Mapping<u128, u128>,
+    b: Lazy<u128>,
+    #[ink(storage_key = 42)]
+    c: Lazy<u128>,
+}
+
+#[ink::storage_item]
+enum State {
+    Active {
+        #[ink(storage_key = 0xABCD)]
+        balances: Mapping<u128, u128>,
+    },
+    Paused(Lazy<u128>),
+}
+
+fn main() {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let contract = Contract::default();
+        assert_eq!(contract.a.key(), 0x1234_5678);
+        assert_eq!(contract.b.key(), KeyComposer::from_str("Contract::b"));
+        assert_eq!(contract.c.key(), 42);
+
+        let state = State::Active {
+            balances: Default::default(),
+        };
+        if let State::Active { balances } = state {
+            assert_eq!(balances.key(), 0xABCD);
+        }
+        let state = State::Paused(Default::default());
+        if let State::Paused(paused) = state {
+            assert_eq!(paused.key(), KeyComposer::from_str("State::Paused::0"));
+        }
+        Ok(())
+    })
+    .unwrap()
+}
@@ -0,0 +1,57 @@
+use ink_primitives::KeyComposer;
+use ink_storage::{
+    traits::StorageKey,
+    Lazy,
+    Mapping,
+};
+
+mod ownable {
+    #[ink::storage_item(namespace = "ownable")]
+    #[derive(Default)]
+    pub struct Data {
+        pub owner: ink_storage::Lazy<u128>,
+    }
+}
+
+mod pausable {
+    #[ink::storage_item(namespace = "pausable")]
+    #[derive(Default)]
+    pub struct Data {
+        pub owner: ink_storage::Lazy<u128>,
+    }
+}
+
+#[ink::storage_item(namespace = "registry")]
+#[derive(Default)]
+struct Registry {
+    entries: Mapping<u128, u128>,
+    #[ink(storage_key = 42)]
+    total: Lazy<u128>,
+}
+
+#[ink::storage_item(namespace = "state")]
+enum State {
+    Paused(Lazy<u128>),
+}
+
+fn main() {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let ownable = ownable::Data::default();
+        let pausable = pausable::Data::default();
+        assert_eq!(ownable.owner.key(), KeyComposer::from_str("ownable::owner"));
+        assert_eq!(pausable.owner.key(), KeyComposer::from_str("pausable::owner"));
+
+        let registry = Registry::default();
+        assert_eq!(
+            registry.entries.key(),
+            KeyComposer::from_str("registry::entries")
+        );
+        assert_eq!(registry.total.key(), 42);
+
+        let state = State::Paused(Default::default());
+        let State::Paused(paused) = state;
+        assert_eq!(paused.key(), KeyComposer::from_str("state::Paused::0"));
+        Ok(())
+    })
+    .unwrap()
+}
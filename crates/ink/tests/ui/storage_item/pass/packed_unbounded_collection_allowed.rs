@@ -0,0 +1,20 @@
+#![deny(deprecated)]
+
+use ink_prelude::{
+    collections::BTreeMap,
+    vec::Vec,
+};
+use ink_storage::{
+    Lazy,
+    Mapping,
+};
+
+#[ink::storage_item]
+struct Contract {
+    a: Mapping<u128, Vec<u8>>,
+    b: Lazy<BTreeMap<u128, u128>>,
+    #[ink(bounded)]
+    c: Vec<u128>,
+}
+
+fn main() {}
@@ -1,7 +0,0 @@
-#[ink::trait_definition]
-pub trait TraitDefinition {
-    #[ink(message)]
-    fn message(&self) {}
-}
-
-fn main() {}
@@ -0,0 +1,17 @@
+#[ink::trait_definition]
+pub trait TraitDefinition {
+    #[ink(message)]
+    fn message(&self) -> bool;
+
+    #[ink(message)]
+    fn default_message(&self) -> bool {
+        !self.message()
+    }
+
+    #[ink(message)]
+    fn default_message_using_env(&mut self) -> bool {
+        ink::env::caller::<Self::Env>() == ink::env::account_id::<Self::Env>()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,315 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::layout::{
+    Layout,
+    StructLayout,
+};
+use core::fmt::Display;
+use ink_prelude::collections::btree_map::BTreeMap;
+use ink_primitives::Key;
+use scale_info::{
+    form::MetaForm,
+    MetaType,
+};
+
+/// The compatibility of a storage layout with the layout it replaces.
+///
+/// Ordered from the least to the most severe.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Compatibility {
+    /// The new code can read all storage written by the old code.
+    Compatible,
+    /// Fields keep their storage key but are stored with a different type, so
+    /// the stored values must be migrated before the new code can read them.
+    NeedsMigration,
+    /// A storage key is reused by other fields with a different type, so the new
+    /// code would interpret values of the old fields as its own.
+    Breaking,
+}
+
+/// A change of the storage cell at a single storage key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LayoutChange {
+    /// The storage key of the changed cell.
+    pub key: Key,
+    /// The paths of the fields stored in the cell by the old layout.
+    pub old: Vec<String>,
+    /// The paths of the fields stored in the cell by the new layout.
+    pub new: Vec<String>,
+    /// The compatibility of the change.
+    pub compatibility: Compatibility,
+}
+
+/// The changes between two storage layouts, see [`diff`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LayoutDiff {
+    changes: Vec<LayoutChange>,
+}
+
+impl LayoutDiff {
+    /// Returns the changed storage cells ordered by their storage key.
+    pub fn changes(&self) -> &[LayoutChange] {
+        &self.changes
+    }
+
+    /// Returns `true` if the storage layouts are the same.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns the compatibility of the most severe change.
+    pub fn compatibility(&self) -> Compatibility {
+        self.changes
+            .iter()
+            .map(|change| change.compatibility)
+            .max()
+            .unwrap_or(Compatibility::Compatible)
+    }
+}
+
+impl Display for LayoutDiff {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        for change in &self.changes {
+            writeln!(
+                f,
+                "{:?} at key 0x{:08X}: [{}] -> [{}]",
+                change.compatibility,
+                change.key,
+                change.old.join(", "),
+                change.new.join(", "),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares the `old` storage layout of a contract with the `new` one.
+///
+/// The layouts are compared per storage cell. Every field stored in a cell is
+/// identified by its path of field names, e.g. `balances.value`, and its type:
+///
+/// - Cells which are only used by one of the layouts and renamed fields are
+///   [`Compatibility::Compatible`].
+/// - Cells which keep at least one of their fields but store different types are
+///   [`Compatibility::NeedsMigration`], for example after adding a field to a struct.
+/// - Cells whose fields are all replaced by fields of different types are
+///   [`Compatibility::Breaking`].
+///
+/// # Note
+///
+/// The comparison is conservative: appending a variant to an enum is reported as
+/// [`Compatibility::NeedsMigration`] although existing values could still be decoded.
+pub fn diff(old: &Layout, new: &Layout) -> LayoutDiff {
+    let old_cells = Cells::collect(old);
+    let new_cells = Cells::collect(new);
+    let mut keys = old_cells.keys().chain(new_cells.keys()).collect::<Vec<_>>();
+    keys.sort_unstable();
+    keys.dedup();
+    let changes = keys
+        .into_iter()
+        .filter_map(|key| {
+            let old = old_cells.get(key).map(Vec::as_slice).unwrap_or_default();
+            let new = new_cells.get(key).map(Vec::as_slice).unwrap_or_default();
+            let compatibility = compare_cells(old, new)?;
+            Some(LayoutChange {
+                key: *key,
+                old: old.iter().map(|field| field.path.clone()).collect(),
+                new: new.iter().map(|field| field.path.clone()).collect(),
+                compatibility,
+            })
+        })
+        .collect();
+    LayoutDiff { changes }
+}
+
+/// Returns the compatibility of the change of a cell, or `None` if it is unchanged.
+fn compare_cells(old: &[Field], new: &[Field]) -> Option<Compatibility> {
+    if old == new {
+        return None
+    }
+    if old.is_empty() || new.is_empty() {
+        return Some(Compatibility::Compatible)
+    }
+    let same_types =
+        old.len() == new.len() && old.iter().zip(new).all(|(old, new)| old.ty == new.ty);
+    if same_types {
+        return Some(Compatibility::Compatible)
+    }
+    let keeps_field = old
+        .iter()
+        .any(|old| new.iter().any(|new| new.path == old.path));
+    if keeps_field {
+        Some(Compatibility::NeedsMigration)
+    } else {
+        Some(Compatibility::Breaking)
+    }
+}
+
+/// A field stored in a storage cell.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct Field {
+    /// The path of field names leading to the field.
+    pub(super) path: String,
+    /// The type of the field.
+    pub(super) ty: MetaType,
+}
+
+/// Collects the fields of a layout by the storage key of their cell.
+#[derive(Default)]
+pub(super) struct Cells {
+    cells: BTreeMap<Key, Vec<Field>>,
+    path: Vec<String>,
+}
+
+impl Cells {
+    pub(super) fn collect(layout: &Layout) -> BTreeMap<Key, Vec<Field>> {
+        let mut cells = Self::default();
+        cells.visit(layout);
+        cells.cells
+    }
+
+    fn visit(&mut self, layout: &Layout<MetaForm>) {
+        match layout {
+            Layout::Leaf(leaf) => {
+                self.cells
+                    .entry(*leaf.key().key())
+                    .or_default()
+                    .push(Field {
+                        path: self.path.join("."),
+                        ty: *leaf.ty(),
+                    })
+            }
+            Layout::Root(root) => self.visit(root.layout()),
+            Layout::Hash(hash) => self.visit(hash.layout()),
+            Layout::Array(array) => {
+                self.path.push(format!("[{}]", array.len()));
+                self.visit(array.layout());
+                self.path.pop();
+            }
+            Layout::Struct(st) => self.visit_struct(st),
+            Layout::Enum(en) => {
+                for variant in en.variants().values() {
+                    self.path.push(variant.name().to_string());
+                    self.visit_struct(variant);
+                    self.path.pop();
+                }
+            }
+        }
+    }
+
+    fn visit_struct(&mut self, st: &StructLayout) {
+        for field in st.fields() {
+            self.path.push(field.name().to_string());
+            self.visit(field.layout());
+            self.path.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::{
+        FieldLayout,
+        LeafLayout,
+        RootLayout,
+    };
+
+    fn contract(fields: Vec<FieldLayout>) -> Layout {
+        RootLayout::new(0.into(), StructLayout::new("Contract", fields)).into()
+    }
+
+    fn leaf<T: scale_info::TypeInfo + 'static>(
+        name: &'static str,
+        key: Key,
+    ) -> FieldLayout {
+        FieldLayout::new(name, LeafLayout::from_key::<T>(key.into()))
+    }
+
+    fn lazy<T: scale_info::TypeInfo + 'static>(
+        name: &'static str,
+        key: Key,
+    ) -> FieldLayout {
+        FieldLayout::new(
+            name,
+            RootLayout::new(key.into(), LeafLayout::from_key::<T>(key.into())),
+        )
+    }
+
+    #[test]
+    fn same_layout_is_empty() {
+        let layout = contract(vec![leaf::<u32>("a", 0), lazy::<u64>("b", 1)]);
+        let diff = diff(&layout, &layout);
+        assert!(diff.is_empty());
+        assert_eq!(diff.compatibility(), Compatibility::Compatible);
+    }
+
+    #[test]
+    fn added_removed_and_renamed_fields_are_compatible() {
+        let old = contract(vec![leaf::<u32>("a", 0), lazy::<u64>("b", 1)]);
+        let new = contract(vec![leaf::<u32>("renamed", 0), lazy::<bool>("c", 2)]);
+        let diff = diff(&old, &new);
+        assert_eq!(diff.compatibility(), Compatibility::Compatible);
+        assert_eq!(
+            diff.changes(),
+            &[
+                LayoutChange {
+                    key: 0,
+                    old: vec!["a".to_string()],
+                    new: vec!["renamed".to_string()],
+                    compatibility: Compatibility::Compatible,
+                },
+                LayoutChange {
+                    key: 1,
+                    old: vec!["b".to_string()],
+                    new: vec![],
+                    compatibility: Compatibility::Compatible,
+                },
+                LayoutChange {
+                    key: 2,
+                    old: vec![],
+                    new: vec!["c".to_string()],
+                    compatibility: Compatibility::Compatible,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn changed_types_need_migration() {
+        let old = contract(vec![leaf::<u32>("a", 0)]);
+        let new = contract(vec![leaf::<u32>("a", 0), leaf::<u8>("b", 0)]);
+        assert_eq!(
+            diff(&old, &new).compatibility(),
+            Compatibility::NeedsMigration
+        );
+
+        let old = contract(vec![leaf::<u32>("a", 0), lazy::<u32>("b", 1)]);
+        let new = contract(vec![leaf::<u32>("a", 0), lazy::<u128>("b", 1)]);
+        assert_eq!(
+            diff(&old, &new).compatibility(),
+            Compatibility::NeedsMigration
+        );
+    }
+
+    #[test]
+    fn reused_key_with_other_type_is_breaking() {
+        let old = contract(vec![leaf::<u32>("a", 0), lazy::<u64>("b", 1)]);
+        let new = contract(vec![leaf::<u32>("a", 0), lazy::<bool>("c", 1)]);
+        let diff = diff(&old, &new);
+        assert_eq!(diff.compatibility(), Compatibility::Breaking);
+        assert_eq!(diff.to_string(), "Breaking at key 0x00000001: [b] -> [c]\n");
+    }
+}
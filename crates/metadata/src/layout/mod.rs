@@ -12,11 +12,25 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod diff;
+mod report;
 #[cfg(test)]
 mod tests;
 mod validate;
 
 use core::fmt::Display;
+pub use diff::{
+    diff,
+    Compatibility,
+    LayoutChange,
+    LayoutDiff,
+};
+pub use report::{
+    report,
+    CellReport,
+    FieldReport,
+    LayoutReport,
+};
 pub use validate::ValidateLayout;
 
 use crate::{
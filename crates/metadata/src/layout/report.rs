@@ -0,0 +1,269 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::diff::Cells;
+use crate::layout::{
+    Layout,
+    LayoutKey,
+};
+use scale_info::{
+    MetaType,
+    TypeDef,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+/// A field stored in a storage cell, see [`report`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldReport {
+    /// The path of field names leading to the field, e.g. `balances.value`.
+    pub path: String,
+    /// The name of the type of the field, e.g. `Vec<u8>`.
+    pub ty: String,
+    /// Whether the encoded size of the field is unbounded, because its type contains
+    /// a sequence such as a `Vec` or a `BTreeMap`.
+    pub unbounded: bool,
+}
+
+/// A storage cell with the fields packed into it, see [`report`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CellReport {
+    /// The storage key of the cell.
+    pub key: LayoutKey,
+    /// The fields packed into the cell.
+    pub fields: Vec<FieldReport>,
+}
+
+/// The storage cells of a storage layout and the fields packed into them.
+///
+/// The report is serializable, so that tools can check the storage layout of a
+/// contract for unbounded fields.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayoutReport {
+    cells: Vec<CellReport>,
+}
+
+impl LayoutReport {
+    /// Returns the storage cells ordered by their storage key.
+    pub fn cells(&self) -> &[CellReport] {
+        &self.cells
+    }
+
+    /// Returns the fields whose encoded size is unbounded with their storage cell.
+    ///
+    /// Every cell is loaded into the static buffer of the contract as a whole, so
+    /// these fields should be stored in a `Mapping` or `Lazy` instead.
+    pub fn unbounded_fields(&self) -> impl Iterator<Item = (&CellReport, &FieldReport)> {
+        self.cells.iter().flat_map(|cell| {
+            cell.fields
+                .iter()
+                .filter(|field| field.unbounded)
+                .map(move |field| (cell, field))
+        })
+    }
+}
+
+/// Reports the storage cells of the `layout` with the fields packed into them.
+///
+/// Fields are identified by their path of field names as for [`diff`](super::diff).
+pub fn report(layout: &Layout) -> LayoutReport {
+    let cells = Cells::collect(layout)
+        .into_iter()
+        .map(|(key, fields)| {
+            CellReport {
+                key: LayoutKey::from(&key),
+                fields: fields
+                    .into_iter()
+                    .map(|field| {
+                        FieldReport {
+                            path: field.path,
+                            ty: type_name(&field.ty),
+                            unbounded: is_unbounded(&field.ty, &mut Vec::new()),
+                        }
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+    LayoutReport { cells }
+}
+
+/// Returns the name of the type `ty` including its type parameters.
+fn type_name(ty: &MetaType) -> String {
+    let info = ty.type_info();
+    let name_of = |ty: &MetaType| type_name(ty);
+    if let Some(ident) = info.path().ident() {
+        let params = info
+            .type_params()
+            .iter()
+            .filter_map(|param| param.ty().map(name_of))
+            .collect::<Vec<_>>();
+        if params.is_empty() {
+            return ident.to_string()
+        }
+        return format!("{}<{}>", ident, params.join(", "))
+    }
+    match info.type_def() {
+        TypeDef::Sequence(sequence) => format!("Vec<{}>", name_of(sequence.type_param())),
+        TypeDef::Array(array) => {
+            format!("[{}; {}]", name_of(array.type_param()), array.len())
+        }
+        TypeDef::Tuple(tuple) => {
+            let fields = tuple.fields().iter().map(name_of).collect::<Vec<_>>();
+            format!("({})", fields.join(", "))
+        }
+        TypeDef::Primitive(primitive) => format!("{:?}", primitive).to_lowercase(),
+        TypeDef::Compact(compact) => {
+            format!("Compact<{}>", name_of(compact.type_param()))
+        }
+        TypeDef::BitSequence(_) => "BitVec".to_string(),
+        TypeDef::Composite(_) | TypeDef::Variant(_) => "_".to_string(),
+    }
+}
+
+/// Returns `true` if the type `ty` contains a sequence.
+///
+/// The types in `visited` are skipped, which terminates the search for recursive
+/// types.
+fn is_unbounded(ty: &MetaType, visited: &mut Vec<MetaType>) -> bool {
+    if visited.contains(ty) {
+        return false
+    }
+    visited.push(*ty);
+    let info = ty.type_info();
+    let mut is_unbounded = |ty: &MetaType| is_unbounded(ty, visited);
+    match info.type_def() {
+        TypeDef::Sequence(_) | TypeDef::BitSequence(_) => true,
+        TypeDef::Composite(composite) => {
+            composite
+                .fields()
+                .iter()
+                .any(|field| is_unbounded(field.ty()))
+        }
+        TypeDef::Variant(variant) => {
+            variant
+                .variants()
+                .iter()
+                .flat_map(|variant| variant.fields())
+                .any(|field| is_unbounded(field.ty()))
+        }
+        TypeDef::Array(array) => is_unbounded(array.type_param()),
+        TypeDef::Tuple(tuple) => tuple.fields().iter().any(is_unbounded),
+        TypeDef::Compact(_) | TypeDef::Primitive(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::{
+        FieldLayout,
+        LeafLayout,
+        RootLayout,
+        StructLayout,
+    };
+    use ink_primitives::Key;
+    use std::collections::BTreeMap;
+
+    fn leaf<T: scale_info::TypeInfo + 'static>(
+        name: &'static str,
+        key: Key,
+    ) -> FieldLayout {
+        FieldLayout::new(name, LeafLayout::from_key::<T>(key.into()))
+    }
+
+    fn layout() -> Layout {
+        RootLayout::new(
+            0.into(),
+            StructLayout::new(
+                "Contract",
+                vec![
+                    leaf::<u32>("a", 0),
+                    leaf::<Option<Vec<u8>>>("b", 0),
+                    FieldLayout::new(
+                        "c",
+                        RootLayout::new(
+                            1.into(),
+                            LeafLayout::from_key::<BTreeMap<u8, bool>>(1.into()),
+                        ),
+                    ),
+                    leaf::<([u8; 4], bool)>("d", 0),
+                ],
+            ),
+        )
+        .into()
+    }
+
+    #[test]
+    fn reports_fields_by_cell() {
+        let report = report(&layout());
+        let cells = report
+            .cells()
+            .iter()
+            .map(|cell| {
+                let fields = cell
+                    .fields
+                    .iter()
+                    .map(|field| (field.path.as_str(), field.ty.as_str()))
+                    .collect::<Vec<_>>();
+                (*cell.key.key(), fields)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            cells,
+            vec![
+                (
+                    0,
+                    vec![
+                        ("a", "u32"),
+                        ("b", "Option<Vec<u8>>"),
+                        ("d", "([u8; 4], bool)")
+                    ]
+                ),
+                (1, vec![("c", "BTreeMap<u8, bool>")]),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_unbounded_fields() {
+        let report = report(&layout());
+        let unbounded = report
+            .unbounded_fields()
+            .map(|(cell, field)| (*cell.key.key(), field.path.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(unbounded, vec![(0, "b"), (1, "c")]);
+    }
+
+    #[test]
+    fn report_serializes_to_json() {
+        let report = report(&layout());
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(
+            json["cells"][1],
+            serde_json::json!({
+                "key": "0x00000001",
+                "fields": [{
+                    "path": "c",
+                    "ty": "BTreeMap<u8, bool>",
+                    "unbounded": true,
+                }],
+            })
+        );
+        let decoded: LayoutReport = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, report);
+    }
+}
@@ -26,7 +26,12 @@ mod tests;
 
 pub mod layout;
 mod specs;
+#[cfg(feature = "std")]
+pub mod stability;
+#[cfg(feature = "std")]
+pub mod typescript;
 mod utils;
+pub mod verify;
 
 pub use ink_primitives::LangError;
 
@@ -46,11 +51,18 @@ pub use self::specs::{
     MessageSpecBuilder,
     ReturnTypeSpec,
     Selector,
+    SignatureTopic,
     TypeSpec,
 };
 
 use impl_serde::serialize as serde_hex;
 
+#[cfg(not(feature = "std"))]
+use alloc::{
+    format,
+    string::String,
+};
+
 #[cfg(feature = "derive")]
 use scale_info::{
     form::PortableForm,
@@ -95,6 +107,9 @@ pub struct InkProject {
     /// The layout of the storage data structure
     layout: layout::Layout<PortableForm>,
     spec: ContractSpec<PortableForm>,
+    /// Information about how the contract was built.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    build: Option<BuildInfo>,
 }
 
 impl InkProject {
@@ -111,6 +126,7 @@ impl InkProject {
             layout: layout.into().into_portable(&mut registry),
             spec: spec.into().into_portable(&mut registry),
             registry: registry.into(),
+            build: None,
         }
     }
 
@@ -127,9 +143,22 @@ impl InkProject {
             layout,
             spec,
             registry,
+            build: None,
         }
     }
 
+    /// Attaches the given build information to the ink! project.
+    ///
+    /// # Note
+    ///
+    /// The build information describes the Wasm blob, which is built separately
+    /// from the metadata generator. It is thus attached by the build tool after
+    /// `__ink_generate_metadata` once the Wasm blob has been built.
+    pub fn with_build_info(mut self, build: BuildInfo) -> Self {
+        self.build = Some(build);
+        self
+    }
+
     /// Returns the metadata version used by the contract.
     pub fn version(&self) -> &MetadataVersion {
         &self.version
@@ -149,4 +178,106 @@ impl InkProject {
     pub fn spec(&self) -> &ContractSpec<PortableForm> {
         &self.spec
     }
+
+    /// Returns the build information of the contract if any.
+    pub fn build_info(&self) -> Option<&BuildInfo> {
+        self.build.as_ref()
+    }
+}
+
+/// Information about the language and compiler settings a contract was built with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildInfo {
+    /// The source language and its version, e.g. `ink! 4.0.0`.
+    language: String,
+    /// The version of the Rust compiler, if provided by the build tool.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    compiler: Option<String>,
+    /// Whether the Wasm blob was built with debug assertions enabled.
+    debug_assertions: bool,
+    /// The code hash of the Wasm blob, if provided by the build tool.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    code_hash: Option<CodeHash>,
+}
+
+impl BuildInfo {
+    /// Creates new build information for the current ink! version.
+    ///
+    /// `debug_assertions` is the setting of the profile the Wasm blob was built
+    /// with, as passed to the compiler by the build tool.
+    pub fn new(debug_assertions: bool) -> Self {
+        Self {
+            language: format!("ink! {}", env!("CARGO_PKG_VERSION")),
+            compiler: None,
+            debug_assertions,
+            code_hash: None,
+        }
+    }
+
+    /// Sets the version of the Rust compiler used to build the contract.
+    pub fn with_compiler<C>(mut self, compiler: C) -> Self
+    where
+        C: Into<String>,
+    {
+        self.compiler = Some(compiler.into());
+        self
+    }
+
+    /// Sets the code hash of the Wasm blob the contract was compiled to.
+    pub fn with_code_hash(mut self, code_hash: [u8; 32]) -> Self {
+        self.code_hash = Some(code_hash.into());
+        self
+    }
+
+    /// Returns the source language and its version.
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Returns the version of the Rust compiler if known.
+    pub fn compiler(&self) -> Option<&str> {
+        self.compiler.as_deref()
+    }
+
+    /// Returns `true` if the Wasm blob was built with debug assertions enabled.
+    pub fn debug_assertions(&self) -> bool {
+        self.debug_assertions
+    }
+
+    /// Returns the code hash of the Wasm blob if known.
+    pub fn code_hash(&self) -> Option<&CodeHash> {
+        self.code_hash.as_ref()
+    }
+}
+
+/// The 32 byte code hash of a contract Wasm blob.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::From)]
+pub struct CodeHash([u8; 32]);
+
+impl CodeHash {
+    /// Returns the underlying bytes of the code hash.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl Serialize for CodeHash {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serde_hex::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CodeHash {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let mut arr = [0; 32];
+        serde_hex::deserialize_check_len(d, serde_hex::ExpectedLen::Exact(&mut arr[..]))?;
+        Ok(arr.into())
+    }
 }
@@ -16,7 +16,10 @@
 
 use crate::{
     serde_hex,
-    utils::trim_extra_whitespace,
+    utils::{
+        extract_param_docs,
+        trim_extra_whitespace,
+    },
 };
 #[cfg(not(feature = "std"))]
 use alloc::{
@@ -287,7 +290,7 @@ impl IntoPortable for ConstructorSpec {
             args: self
                 .args
                 .into_iter()
-                .map(|arg| arg.into_portable(registry))
+                .map(|arg| arg.with_docs_from(&self.docs).into_portable(registry))
                 .collect::<Vec<_>>(),
             return_type: self.return_type.into_portable(registry),
             docs: self.docs.into_iter().map(|s| s.into()).collect(),
@@ -496,6 +499,27 @@ pub struct MessageSpec<F: Form = MetaForm> {
     return_type: ReturnTypeSpec<F>,
     /// The message documentation.
     docs: Vec<F::String>,
+    /// The namespace of the implementation block defining the message, if any.
+    ///
+    /// The selector of the message is computed from the namespace and the
+    /// label. Only serialized if set in order to stay compatible with
+    /// existing metadata consumers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    namespace: Option<F::String>,
+    /// The frozen version of the ABI of the message, if any.
+    ///
+    /// The types and the order of the arguments of a message with a frozen ABI
+    /// only change together with its version.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    abi_version: Option<u32>,
+    /// If the message returns the items of a collection in pages of
+    /// `(Vec<T>, Option<Cursor>)`, so that tools like explorers can read the whole
+    /// collection by passing the returned cursor to the next call.
+    ///
+    /// Only serialized if `true` in order to stay compatible with existing
+    /// metadata consumers.
+    #[serde(default, skip_serializing_if = "core::ops::Not::not")]
+    paginated: bool,
 }
 
 /// Type state for builders to tell that some mandatory state has not yet been set
@@ -539,6 +563,9 @@ where
                 args: Vec::new(),
                 return_type: ReturnTypeSpec::new(None),
                 docs: Vec::new(),
+                namespace: None,
+                abi_version: None,
+                paginated: false,
             },
             marker: PhantomData,
         }
@@ -586,6 +613,21 @@ where
     pub fn docs(&self) -> &[F::String] {
         &self.docs
     }
+
+    /// Returns the namespace of the implementation block defining the message, if any.
+    pub fn namespace(&self) -> Option<&F::String> {
+        self.namespace.as_ref()
+    }
+
+    /// Returns the frozen version of the ABI of the message, if any.
+    pub fn abi_version(&self) -> Option<u32> {
+        self.abi_version
+    }
+
+    /// Returns true if the message returns the items of a collection in pages.
+    pub fn paginated(&self) -> bool {
+        self.paginated
+    }
 }
 
 /// A builder for messages.
@@ -706,6 +748,27 @@ where
         this.spec.docs = docs.into_iter().collect::<Vec<_>>();
         this
     }
+
+    /// Sets the namespace of the implementation block defining the message.
+    pub fn namespace(self, namespace: <F as Form>::String) -> Self {
+        let mut this = self;
+        this.spec.namespace = Some(namespace);
+        this
+    }
+
+    /// Freezes the ABI of the message as `version`.
+    pub fn abi_version(self, version: u32) -> Self {
+        let mut this = self;
+        this.spec.abi_version = Some(version);
+        this
+    }
+
+    /// Sets if the message returns the items of a collection in pages.
+    pub fn paginated(self, is_paginated: bool) -> Self {
+        let mut this = self;
+        this.spec.paginated = is_paginated;
+        this
+    }
 }
 
 impl<F>
@@ -737,10 +800,13 @@ impl IntoPortable for MessageSpec {
             args: self
                 .args
                 .into_iter()
-                .map(|arg| arg.into_portable(registry))
+                .map(|arg| arg.with_docs_from(&self.docs).into_portable(registry))
                 .collect::<Vec<_>>(),
             return_type: self.return_type.into_portable(registry),
             docs: self.docs.into_iter().map(|s| s.into()).collect(),
+            namespace: self.namespace.map(|namespace| namespace.to_string()),
+            abi_version: self.abi_version,
+            paginated: self.paginated,
         }
     }
 }
@@ -758,6 +824,16 @@ pub struct EventSpec<F: Form = MetaForm> {
     args: Vec<EventParamSpec<F>>,
     /// The event documentation.
     docs: Vec<F::String>,
+    /// The user provided signature topic of the event.
+    ///
+    /// Only serialized if set in order to stay compatible with existing
+    /// metadata consumers.
+    #[serde(
+        rename = "signatureTopic",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    signature_topic: Option<SignatureTopic>,
 }
 
 /// An event specification builder.
@@ -795,6 +871,16 @@ where
         this
     }
 
+    /// Sets the signature topic of the event specification.
+    pub fn signature_topic<T>(self, signature_topic: T) -> Self
+    where
+        T: Into<[u8; 32]>,
+    {
+        let mut this = self;
+        this.spec.signature_topic = Some(SignatureTopic::new(signature_topic));
+        this
+    }
+
     /// Finalizes building the event specification.
     pub fn done(self) -> EventSpec<F> {
         self.spec
@@ -813,6 +899,7 @@ impl IntoPortable for EventSpec {
                 .map(|arg| arg.into_portable(registry))
                 .collect::<Vec<_>>(),
             docs: self.docs.into_iter().map(|s| s.into()).collect(),
+            signature_topic: self.signature_topic,
         }
     }
 }
@@ -828,6 +915,7 @@ where
                 label,
                 args: Vec::new(),
                 docs: Vec::new(),
+                signature_topic: None,
             },
         }
     }
@@ -851,6 +939,50 @@ where
     pub fn docs(&self) -> &[F::String] {
         &self.docs
     }
+
+    /// The user provided signature topic of the event if any.
+    pub fn signature_topic(&self) -> Option<&SignatureTopic> {
+        self.signature_topic.as_ref()
+    }
+}
+
+/// The 32 byte signature topic identifying an event.
+#[derive(Debug, PartialEq, Eq, derive_more::From)]
+pub struct SignatureTopic([u8; 32]);
+
+impl serde::Serialize for SignatureTopic {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serde_hex::serialize(&self.0, serializer)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for SignatureTopic {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let mut arr = [0; 32];
+        serde_hex::deserialize_check_len(d, serde_hex::ExpectedLen::Exact(&mut arr[..]))?;
+        Ok(arr.into())
+    }
+}
+
+impl SignatureTopic {
+    /// Creates a new signature topic.
+    pub fn new<T>(bytes: T) -> Self
+    where
+        T: Into<[u8; 32]>,
+    {
+        Self(bytes.into())
+    }
+
+    /// Returns the underlying signature topic bytes.
+    pub fn to_bytes(&self) -> &[u8] {
+        &self.0
+    }
 }
 
 /// The 4 byte selector to identify constructors and messages
@@ -1060,6 +1192,20 @@ pub struct EventParamSpec<F: Form = MetaForm> {
     label: F::String,
     /// If the event parameter is indexed.
     indexed: bool,
+    /// If the event parameter is indexed by the hash of its value.
+    ///
+    /// Only serialized if `true` in order to stay compatible with existing
+    /// metadata consumers.
+    #[serde(default, skip_serializing_if = "core::ops::Not::not")]
+    hashed: bool,
+    /// The number of decimal places of the parameter if it is an amount of a
+    /// currency, e.g. a balance, so that tools can render `1.5 UNIT` instead of
+    /// the integer amount.
+    ///
+    /// Only serialized if set in order to stay compatible with existing metadata
+    /// consumers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    decimals: Option<u8>,
     /// The type of the parameter.
     #[serde(rename = "type")]
     ty: TypeSpec<F>,
@@ -1074,6 +1220,8 @@ impl IntoPortable for EventParamSpec {
         EventParamSpec {
             label: self.label.to_string(),
             indexed: self.indexed,
+            hashed: self.hashed,
+            decimals: self.decimals,
             ty: self.ty.into_portable(registry),
             docs: self.docs.into_iter().map(|s| s.into()).collect(),
         }
@@ -1092,6 +1240,10 @@ where
                 label,
                 // By default event parameters are not indexed.
                 indexed: false,
+                // By default indexed event parameters are not hashed.
+                hashed: false,
+                // By default event parameters are no amounts of a currency.
+                decimals: None,
                 // We initialize every parameter type as `()`.
                 ty: Default::default(),
                 // We start with empty docs.
@@ -1109,6 +1261,17 @@ where
         self.indexed
     }
 
+    /// Returns true if the event parameter is indexed by the hash of its value.
+    pub fn hashed(&self) -> bool {
+        self.hashed
+    }
+
+    /// Returns the number of decimal places of the event parameter if it is an
+    /// amount of a currency.
+    pub fn decimals(&self) -> Option<u8> {
+        self.decimals
+    }
+
     /// Returns the type of the parameter.
     pub fn ty(&self) -> &TypeSpec<F> {
         &self.ty
@@ -1148,6 +1311,21 @@ where
         this
     }
 
+    /// If the event parameter is indexed by the hash of its value.
+    pub fn hashed(self, is_hashed: bool) -> Self {
+        let mut this = self;
+        this.spec.hashed = is_hashed;
+        this
+    }
+
+    /// Sets the number of decimal places of the event parameter, which is an
+    /// amount of a currency.
+    pub fn decimals(self, decimals: u8) -> Self {
+        let mut this = self;
+        this.spec.decimals = Some(decimals);
+        this
+    }
+
     /// Sets the documentation of the event parameter.
     pub fn docs<D>(self, docs: D) -> Self
     where
@@ -1232,6 +1410,12 @@ pub struct MessageParamSpec<F: Form = MetaForm> {
     /// The type of the parameter.
     #[serde(rename = "type")]
     ty: TypeSpec<F>,
+    /// The documentation of the parameter.
+    ///
+    /// Only serialized if not empty in order to stay compatible with existing
+    /// metadata consumers.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    docs: Vec<F::String>,
 }
 
 impl IntoPortable for MessageParamSpec {
@@ -1241,10 +1425,25 @@ impl IntoPortable for MessageParamSpec {
         MessageParamSpec {
             label: self.label.to_string(),
             ty: self.ty.into_portable(registry),
+            docs: self.docs.into_iter().map(|s| s.into()).collect(),
         }
     }
 }
 
+impl MessageParamSpec {
+    /// Takes the documentation of the parameter from the `docs` of its message or
+    /// constructor unless it has been set explicitly.
+    ///
+    /// See [`extract_param_docs`] for the supported styles of parameter
+    /// documentation.
+    fn with_docs_from(mut self, docs: &[&'static str]) -> Self {
+        if self.docs.is_empty() {
+            self.docs = extract_param_docs(docs, self.label);
+        }
+        self
+    }
+}
+
 impl<F> MessageParamSpec<F>
 where
     F: Form,
@@ -1257,6 +1456,7 @@ where
                 label,
                 // Uses `()` type by default.
                 ty: TypeSpec::default(),
+                docs: Vec::new(),
             },
         }
     }
@@ -1270,6 +1470,11 @@ where
     pub fn ty(&self) -> &TypeSpec<F> {
         &self.ty
     }
+
+    /// Returns the documentation of the parameter.
+    pub fn docs(&self) -> &[F::String] {
+        &self.docs
+    }
 }
 
 /// Used to construct a message parameter specification.
@@ -1290,6 +1495,20 @@ where
         this
     }
 
+    /// Sets the documentation of the message parameter.
+    ///
+    /// Otherwise the documentation is extracted from the documentation of the
+    /// message or constructor.
+    pub fn docs<D>(self, docs: D) -> Self
+    where
+        D: IntoIterator<Item = <F as Form>::String>,
+    {
+        let mut this = self;
+        debug_assert!(this.spec.docs.is_empty());
+        this.spec.docs = docs.into_iter().collect::<Vec<_>>();
+        this
+    }
+
     /// Finishes construction of the message parameter.
     pub fn done(self) -> MessageParamSpec<F> {
         self.spec
@@ -0,0 +1,395 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Snapshots of the parts of a contract its callers and its storage rely on.
+//!
+//! A [`StabilitySnapshot`] lists the selectors of the constructors and messages,
+//! the signatures of the events and the root storage keys of a contract. Comparing
+//! the snapshot with one taken before a refactoring reveals unintended changes,
+//! which would break existing callers, indexers or the stored data.
+//!
+//! Messages annotated with `#[ink(message, abi_version = N)]` additionally list the
+//! encodings of their arguments in order, i.e. their types including the fields and
+//! variants of structs and enums. Their arguments must not change as long as the
+//! version stays the same, see [`StabilitySnapshot::frozen_abi_changes`].
+
+use crate::{
+    layout::Layout,
+    InkProject,
+};
+use impl_serde::serialize::to_hex;
+use scale_info::{
+    form::PortableForm,
+    Field,
+    PortableRegistry,
+    TypeDef,
+};
+use std::fmt::{
+    Display,
+    Formatter,
+    Result as FmtResult,
+};
+
+/// The selectors, event signatures and storage keys of a contract.
+///
+/// The snapshot is displayed with one entry per line, e.g.
+///
+/// ```text
+/// constructor new 0x9bae9d5e
+/// message flip 0x633aa551
+/// abi set v1(bool)
+/// event Flipped(topic value: bool)
+/// storage Flipper 0x00000000
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StabilitySnapshot {
+    entries: Vec<String>,
+}
+
+impl StabilitySnapshot {
+    /// Takes the snapshot of the contract described by `project`.
+    pub fn new(project: &InkProject) -> Self {
+        let spec = project.spec();
+        let registry = project.registry();
+        let mut entries = Vec::new();
+        for constructor in spec.constructors() {
+            entries.push(format!(
+                "constructor {} {}",
+                constructor.label(),
+                to_hex(constructor.selector().to_bytes(), false)
+            ));
+        }
+        for message in spec.messages() {
+            entries.push(format!(
+                "message {} {}",
+                message.label(),
+                to_hex(message.selector().to_bytes(), false)
+            ));
+        }
+        for message in spec.messages() {
+            if let Some(version) = message.abi_version() {
+                let args = message
+                    .args()
+                    .iter()
+                    .map(|arg| {
+                        type_encoding(registry, arg.ty().ty().id(), &mut Vec::new())
+                    })
+                    .collect::<Vec<_>>();
+                entries.push(format!(
+                    "abi {} v{}({})",
+                    message.label(),
+                    version,
+                    args.join(", ")
+                ));
+            }
+        }
+        for event in spec.events() {
+            let args = event
+                .args()
+                .iter()
+                .map(|arg| {
+                    format!(
+                        "{}{}: {}",
+                        if arg.indexed() { "topic " } else { "" },
+                        arg.label(),
+                        type_name(registry, arg.ty().ty().id())
+                    )
+                })
+                .collect::<Vec<_>>();
+            let mut entry = format!("event {}({})", event.label(), args.join(", "));
+            if let Some(signature_topic) = event.signature_topic() {
+                entry
+                    .push_str(&format!(" {}", to_hex(signature_topic.to_bytes(), false)));
+            }
+            entries.push(entry);
+        }
+        collect_root_keys(project.layout(), String::new(), &mut entries);
+        Self { entries }
+    }
+
+    /// Parses a snapshot from its display representation.
+    ///
+    /// Empty lines are ignored.
+    pub fn parse(snapshot: &str) -> Self {
+        let entries = snapshot
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(ToString::to_string)
+            .collect();
+        Self { entries }
+    }
+
+    /// Returns the entries of the snapshot.
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Returns the entries of the `previous` snapshot missing in this snapshot and
+    /// the entries of this snapshot missing in the `previous` snapshot.
+    pub fn changes<'a>(
+        &'a self,
+        previous: &'a StabilitySnapshot,
+    ) -> (Vec<&'a str>, Vec<&'a str>) {
+        let removed = previous
+            .entries
+            .iter()
+            .filter(|entry| !self.entries.contains(entry))
+            .map(String::as_str)
+            .collect();
+        let added = self
+            .entries
+            .iter()
+            .filter(|entry| !previous.entries.contains(entry))
+            .map(String::as_str)
+            .collect();
+        (removed, added)
+    }
+
+    /// Returns the pairs of entries of the `previous` snapshot and this snapshot for
+    /// the messages whose arguments changed without bumping their frozen ABI version.
+    ///
+    /// Unlike the other [`changes`](Self::changes), these always break the callers
+    /// relying on the frozen ABI.
+    pub fn frozen_abi_changes<'a>(
+        &'a self,
+        previous: &'a StabilitySnapshot,
+    ) -> Vec<(&'a str, &'a str)> {
+        self.entries
+            .iter()
+            .filter_map(|entry| {
+                let version = frozen_abi_version(entry)?;
+                previous
+                    .entries
+                    .iter()
+                    .find(|stored| frozen_abi_version(stored) == Some(version))
+                    .filter(|stored| *stored != entry)
+                    .map(|stored| (stored.as_str(), entry.as_str()))
+            })
+            .collect()
+    }
+}
+
+/// Returns the label and the version of the message of a frozen ABI `entry`, i.e.
+/// the entry without the arguments.
+fn frozen_abi_version(entry: &str) -> Option<&str> {
+    if !entry.starts_with("abi ") {
+        return None
+    }
+    entry.find('(').map(|args| &entry[..args])
+}
+
+impl Display for StabilitySnapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        for entry in &self.entries {
+            writeln!(f, "{}", entry)?;
+        }
+        Ok(())
+    }
+}
+
+/// Collects the root keys of the `layout` at the field `path`.
+///
+/// The root key of the storage struct of the contract is named after the struct,
+/// all other root keys after the path of field names leading to them.
+fn collect_root_keys(
+    layout: &Layout<PortableForm>,
+    path: String,
+    entries: &mut Vec<String>,
+) {
+    match layout {
+        Layout::Root(root) => {
+            let name = match (path.is_empty(), root.layout()) {
+                (true, Layout::Struct(struct_layout)) => struct_layout.name().clone(),
+                (true, Layout::Enum(enum_layout)) => enum_layout.name().clone(),
+                _ => path.clone(),
+            };
+            entries.push(format!(
+                "storage {} {}",
+                name,
+                to_hex(&root.root_key().key().to_be_bytes(), false)
+            ));
+            collect_root_keys(root.layout(), path, entries)
+        }
+        Layout::Hash(hash) => collect_root_keys(hash.layout(), path, entries),
+        Layout::Array(array) => collect_root_keys(array.layout(), path, entries),
+        Layout::Struct(struct_layout) => {
+            for field in struct_layout.fields() {
+                collect_root_keys(field.layout(), join(&path, field.name()), entries)
+            }
+        }
+        Layout::Enum(enum_layout) => {
+            for variant in enum_layout.variants().values() {
+                let variant_path = join(&path, variant.name());
+                for field in variant.fields() {
+                    collect_root_keys(
+                        field.layout(),
+                        join(&variant_path, field.name()),
+                        entries,
+                    )
+                }
+            }
+        }
+        Layout::Leaf(_) => (),
+    }
+}
+
+/// Appends `name` to the field `path`.
+fn join(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+/// Returns the name of the type `id` including its type parameters.
+fn type_name(registry: &PortableRegistry, id: u32) -> String {
+    let ty = match registry.resolve(id) {
+        Some(ty) => ty,
+        None => return format!("#{}", id),
+    };
+    let name_of = |id: u32| type_name(registry, id);
+    if let Some(ident) = ty.path().ident() {
+        let params = ty
+            .type_params()
+            .iter()
+            .filter_map(|param| param.ty().map(|ty| name_of(ty.id())))
+            .collect::<Vec<_>>();
+        if params.is_empty() {
+            return ident
+        }
+        return format!("{}<{}>", ident, params.join(", "))
+    }
+    match ty.type_def() {
+        TypeDef::Sequence(sequence) => {
+            format!("Vec<{}>", name_of(sequence.type_param().id()))
+        }
+        TypeDef::Array(array) => {
+            format!("[{}; {}]", name_of(array.type_param().id()), array.len())
+        }
+        TypeDef::Tuple(tuple) => {
+            let fields = tuple
+                .fields()
+                .iter()
+                .map(|field| name_of(field.id()))
+                .collect::<Vec<_>>();
+            format!("({})", fields.join(", "))
+        }
+        TypeDef::Primitive(primitive) => format!("{:?}", primitive).to_lowercase(),
+        TypeDef::Compact(compact) => {
+            format!("Compact<{}>", name_of(compact.type_param().id()))
+        }
+        TypeDef::BitSequence(_) => "BitVec".to_string(),
+        TypeDef::Composite(_) | TypeDef::Variant(_) => "_".to_string(),
+    }
+}
+
+/// Returns the encoding of the type `id`, i.e. its name followed by its fields or
+/// variants, recursively.
+///
+/// Unlike the [`type_name`], the encoding changes whenever the SCALE encoding of the
+/// type does, e.g. if the fields of a struct argument are reordered. Types nested
+/// into themselves are only expanded at their `outer` occurrence.
+fn type_encoding(registry: &PortableRegistry, id: u32, outer: &mut Vec<u32>) -> String {
+    let ty = match registry.resolve(id) {
+        Some(ty) if !outer.contains(&id) => ty,
+        _ => return type_name(registry, id),
+    };
+    outer.push(id);
+    let encoding = match ty.type_def() {
+        TypeDef::Composite(composite) => {
+            format!(
+                "{}{}",
+                type_name(registry, id),
+                fields_encoding(registry, composite.fields(), outer)
+            )
+        }
+        TypeDef::Variant(variant) => {
+            let variants = variant
+                .variants()
+                .iter()
+                .map(|variant| {
+                    format!(
+                        "{}{} = {}",
+                        variant.name(),
+                        fields_encoding(registry, variant.fields(), outer),
+                        variant.index()
+                    )
+                })
+                .collect::<Vec<_>>();
+            format!("{} {{ {} }}", type_name(registry, id), variants.join(", "))
+        }
+        TypeDef::Sequence(sequence) => {
+            format!(
+                "Vec<{}>",
+                type_encoding(registry, sequence.type_param().id(), outer)
+            )
+        }
+        TypeDef::Array(array) => {
+            format!(
+                "[{}; {}]",
+                type_encoding(registry, array.type_param().id(), outer),
+                array.len()
+            )
+        }
+        TypeDef::Tuple(tuple) => {
+            let fields = tuple
+                .fields()
+                .iter()
+                .map(|field| type_encoding(registry, field.id(), outer))
+                .collect::<Vec<_>>();
+            format!("({})", fields.join(", "))
+        }
+        TypeDef::Compact(compact) => {
+            format!(
+                "Compact<{}>",
+                type_encoding(registry, compact.type_param().id(), outer)
+            )
+        }
+        TypeDef::Primitive(_) | TypeDef::BitSequence(_) => type_name(registry, id),
+    };
+    outer.pop();
+    encoding
+}
+
+/// Returns the encoding of the `fields` of a struct or an enum variant.
+///
+/// Named fields are listed with their names, so that swapping two fields of the
+/// same type changes the encoding as well.
+fn fields_encoding(
+    registry: &PortableRegistry,
+    fields: &[Field<PortableForm>],
+    outer: &mut Vec<u32>,
+) -> String {
+    if fields.is_empty() {
+        return String::new()
+    }
+    let named = fields.iter().all(|field| field.name().is_some());
+    let fields = fields
+        .iter()
+        .map(|field| {
+            let encoding = type_encoding(registry, field.ty().id(), outer);
+            match field.name() {
+                Some(name) if named => format!("{}: {}", name, encoding),
+                _ => encoding,
+            }
+        })
+        .collect::<Vec<_>>();
+    if named {
+        format!(" {{ {} }}", fields.join(", "))
+    } else {
+        format!("({})", fields.join(", "))
+    }
+}
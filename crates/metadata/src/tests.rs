@@ -443,3 +443,488 @@ fn construct_runtime_contract_spec() {
     );
     assert_eq!(event_spec, expected_event_spec);
 }
+
+#[test]
+fn hashed_event_param_spec_json() {
+    let path: Path<PortableForm> = Path::from_segments_unchecked(["Vec".to_string()]);
+    let spec = EventParamSpec::new("payload".to_string())
+        .of_type(TypeSpec::new(42.into(), path))
+        .indexed(true)
+        .hashed(true)
+        .docs(vec![])
+        .done();
+    assert!(spec.hashed());
+
+    let json = serde_json::to_value(&spec).unwrap();
+    assert_eq!(
+        json,
+        json!({
+            "label": "payload",
+            "indexed": true,
+            "hashed": true,
+            "type": {
+                "type": 42,
+                "displayName": [
+                    "Vec"
+                ]
+            },
+            "docs": []
+        })
+    );
+    let deserialized: EventParamSpec<PortableForm> =
+        serde_json::from_value(json).unwrap();
+    assert_eq!(deserialized, spec);
+}
+
+#[test]
+fn event_param_spec_with_decimals_json() {
+    let path: Path<PortableForm> = Path::from_segments_unchecked(["Balance".to_string()]);
+    let spec = EventParamSpec::new("value".to_string())
+        .of_type(TypeSpec::new(42.into(), path))
+        .decimals(12)
+        .docs(vec![])
+        .done();
+    assert_eq!(spec.decimals(), Some(12));
+
+    let json = serde_json::to_value(&spec).unwrap();
+    assert_eq!(
+        json,
+        json!({
+            "label": "value",
+            "indexed": false,
+            "decimals": 12,
+            "type": {
+                "type": 42,
+                "displayName": [
+                    "Balance"
+                ]
+            },
+            "docs": []
+        })
+    );
+    let deserialized: EventParamSpec<PortableForm> =
+        serde_json::from_value(json).unwrap();
+    assert_eq!(deserialized, spec);
+}
+
+#[test]
+fn event_spec_with_signature_topic_json() {
+    let spec: EventSpec<PortableForm> = EventSpec::new("Transfer".to_string())
+        .args(vec![])
+        .docs(vec![])
+        .signature_topic([0x42; 32])
+        .done();
+    assert_eq!(
+        spec.signature_topic(),
+        Some(&SignatureTopic::new([0x42; 32]))
+    );
+
+    let json = serde_json::to_value(&spec).unwrap();
+    assert_eq!(
+        json,
+        json!({
+            "label": "Transfer",
+            "args": [],
+            "docs": [],
+            "signatureTopic": format!("0x{}", "42".repeat(32)),
+        })
+    );
+    let deserialized: EventSpec<PortableForm> = serde_json::from_value(json).unwrap();
+    assert_eq!(deserialized, spec);
+}
+
+#[test]
+fn message_spec_with_namespace_json() {
+    let spec: MessageSpec<PortableForm> = MessageSpec::from_label("get".to_string())
+        .selector([0x01, 0x02, 0x03, 0x04])
+        .mutates(false)
+        .payable(false)
+        .args(vec![])
+        .returns(ReturnTypeSpec::new(None))
+        .docs(vec![])
+        .namespace("my_std::v2".to_string())
+        .done();
+    assert_eq!(spec.namespace().map(String::as_str), Some("my_std::v2"));
+
+    let json = serde_json::to_value(&spec).unwrap();
+    assert_eq!(json["namespace"], json!("my_std::v2"));
+    let deserialized: MessageSpec<PortableForm> = serde_json::from_value(json).unwrap();
+    assert_eq!(deserialized, spec);
+
+    let spec = runtime_message_spec();
+    assert_eq!(spec.namespace(), None);
+    let json = serde_json::to_value(&spec).unwrap();
+    assert!(json.get("namespace").is_none());
+}
+
+#[test]
+fn message_spec_paginated_json() {
+    let spec: MessageSpec<PortableForm> = MessageSpec::from_label("entries".to_string())
+        .selector([0x01, 0x02, 0x03, 0x04])
+        .mutates(false)
+        .payable(false)
+        .args(vec![])
+        .returns(ReturnTypeSpec::new(None))
+        .docs(vec![])
+        .paginated(true)
+        .done();
+    assert!(spec.paginated());
+
+    let json = serde_json::to_value(&spec).unwrap();
+    assert_eq!(json["paginated"], json!(true));
+    let deserialized: MessageSpec<PortableForm> = serde_json::from_value(json).unwrap();
+    assert_eq!(deserialized, spec);
+
+    let spec = runtime_message_spec();
+    assert!(!spec.paginated());
+    let json = serde_json::to_value(&spec).unwrap();
+    assert!(json.get("paginated").is_none());
+}
+
+#[test]
+fn extract_param_docs_works() {
+    let docs = [
+        " Transfers `value` to `to`.",
+        "",
+        " # Parameters",
+        "",
+        " - `to`: The account to transfer to.",
+        " - value - The amount to transfer,",
+        "   in the smallest unit.",
+        " - total: Not a parameter.",
+        "",
+        " # Errors",
+        "",
+        " - to: Not in the parameters section.",
+    ];
+    assert_eq!(
+        crate::utils::extract_param_docs(&docs, "to"),
+        vec!["The account to transfer to."]
+    );
+    assert_eq!(
+        crate::utils::extract_param_docs(&docs, "value"),
+        vec!["The amount to transfer,", "in the smallest unit."]
+    );
+    assert!(crate::utils::extract_param_docs(&docs, "from").is_empty());
+
+    let docs = [
+        " Approves `spender`.",
+        " @param spender The account allowed to spend.",
+        " @param amount: The allowance.",
+        " @return Nothing.",
+    ];
+    assert_eq!(
+        crate::utils::extract_param_docs(&docs, "spender"),
+        vec!["The account allowed to spend."]
+    );
+    assert_eq!(
+        crate::utils::extract_param_docs(&docs, "amount"),
+        vec!["The allowance."]
+    );
+}
+
+#[test]
+fn message_param_docs_are_extracted_from_message_docs() {
+    let spec = MessageSpec::from_label("transfer")
+        .selector([0x01, 0x02, 0x03, 0x04])
+        .mutates(true)
+        .payable(false)
+        .args(vec![
+            MessageParamSpec::new("to")
+                .of_type(TypeSpec::with_name_segs::<[u8; 32], _>(
+                    vec!["AccountId"].into_iter().map(AsRef::as_ref),
+                ))
+                .done(),
+            MessageParamSpec::new("value")
+                .of_type(TypeSpec::with_name_segs::<u128, _>(
+                    vec!["Balance"].into_iter().map(AsRef::as_ref),
+                ))
+                .docs(vec!["Set explicitly."])
+                .done(),
+            MessageParamSpec::new("memo")
+                .of_type(TypeSpec::with_name_segs::<u8, _>(
+                    vec!["u8"].into_iter().map(AsRef::as_ref),
+                ))
+                .done(),
+        ])
+        .returns(ReturnTypeSpec::new(None))
+        .docs(vec![
+            " Transfers `value` to `to`.",
+            " @param to The account to transfer to.",
+            " @param value The amount to transfer.",
+        ])
+        .done();
+    let mut registry = Registry::new();
+    let spec = spec.into_portable(&mut registry);
+    assert_eq!(spec.args()[0].docs(), ["The account to transfer to."]);
+    assert_eq!(spec.args()[1].docs(), ["Set explicitly."]);
+    assert!(spec.args()[2].docs().is_empty());
+
+    let json = serde_json::to_value(&spec).unwrap();
+    assert_eq!(
+        json["args"][0]["docs"],
+        json!(["The account to transfer to."])
+    );
+    assert!(json["args"][2].get("docs").is_none());
+    let deserialized: MessageSpec<PortableForm> = serde_json::from_value(json).unwrap();
+    assert_eq!(deserialized, spec);
+}
+
+#[test]
+fn typescript_bindings_work() {
+    #[derive(scale_info::TypeInfo)]
+    #[allow(dead_code)]
+    enum Error {
+        InsufficientBalance,
+        Other(u32),
+    }
+
+    let spec: ContractSpec = ContractSpec::new()
+        .constructors(vec![ConstructorSpec::from_label("new")
+            .selector([94u8, 189u8, 136u8, 214u8])
+            .payable(false)
+            .args(vec![MessageParamSpec::new("init_value")
+                .of_type(TypeSpec::of_type::<u64>())
+                .done()])
+            .returns(ReturnTypeSpec::new(None))
+            .docs(vec!["Creates a new contract."])
+            .done()])
+        .messages(vec![
+            MessageSpec::from_label("get_value")
+                .selector([1u8, 2u8, 3u8, 4u8])
+                .mutates(false)
+                .payable(false)
+                .args(Vec::new())
+                .returns(ReturnTypeSpec::new(TypeSpec::of_type::<Option<bool>>()))
+                .docs(Vec::new())
+                .done(),
+            MessageSpec::from_label("transfer")
+                .selector([5u8, 6u8, 7u8, 8u8])
+                .mutates(true)
+                .payable(false)
+                .args(vec![MessageParamSpec::new("to_account")
+                    .of_type(TypeSpec::of_type::<[u8; 32]>())
+                    .done()])
+                .returns(ReturnTypeSpec::new(TypeSpec::of_type::<Result<(), Error>>()))
+                .docs(Vec::new())
+                .done(),
+        ])
+        .events(vec![EventSpec::new("Transferred")
+            .args(vec![EventParamSpec::new("amount")
+                .of_type(TypeSpec::of_type::<u128>())
+                .indexed(false)
+                .docs(Vec::new())
+                .done()])
+            .docs(Vec::new())
+            .done()])
+        .docs(Vec::new())
+        .done();
+    let layout = layout::Layout::Leaf(layout::LeafLayout::from_key::<u64>(
+        layout::LayoutKey::new(0u32),
+    ));
+    let project = InkProject::new(layout, spec);
+
+    let bindings = typescript::generate_typescript(&project, "my_token");
+
+    assert!(bindings.contains(
+        "export type Error = { insufficientBalance: null } | { other: number };"
+    ));
+    assert!(bindings.contains(
+        "export interface TransferredEvent {\n    amount: number | string;\n}"
+    ));
+    assert!(bindings.contains("export class MyToken {"));
+    assert!(bindings.contains(
+        "        getValue: (caller: string, options: ContractOptions) =>\n            \
+         this.contract.query[this.contract.abi.messages[0].method](caller, options),"
+    ));
+    assert!(bindings.contains("        /** @returns `boolean | null` */"));
+    assert!(bindings.contains("        /** @returns `{ ok: null } | { err: Error }` */"));
+    assert!(bindings.contains(
+        "        transfer: (options: ContractOptions, toAccount: string) =>\n            \
+         this.contract.tx[this.contract.abi.messages[1].method](options, toAccount),"
+    ));
+    assert!(!bindings.contains("getValue: (options"));
+    assert!(bindings.contains("export class MyTokenCode {"));
+    assert!(bindings.contains(
+        "        /**\n         * Creates a new contract.\n         */\n        \
+         new: (options: BlueprintOptions, initValue: number | string) =>"
+    ));
+}
+
+#[test]
+fn stability_snapshot_works() {
+    let spec: ContractSpec = ContractSpec::new()
+        .constructors(vec![ConstructorSpec::from_label("new")
+            .selector([94u8, 189u8, 136u8, 214u8])
+            .payable(false)
+            .returns(ReturnTypeSpec::new(None))
+            .done()])
+        .messages(vec![MessageSpec::from_label("transfer")
+            .selector([5u8, 6u8, 7u8, 8u8])
+            .mutates(true)
+            .payable(false)
+            .args(vec![
+                MessageParamSpec::new("to")
+                    .of_type(TypeSpec::of_type::<[u8; 32]>())
+                    .done(),
+                MessageParamSpec::new("value")
+                    .of_type(TypeSpec::of_type::<u128>())
+                    .done(),
+            ])
+            .returns(ReturnTypeSpec::new(None))
+            .abi_version(1)
+            .done()])
+        .events(vec![EventSpec::new("Transferred")
+            .args(vec![
+                EventParamSpec::new("to")
+                    .of_type(TypeSpec::of_type::<Option<[u8; 32]>>())
+                    .indexed(true)
+                    .done(),
+                EventParamSpec::new("amounts")
+                    .of_type(TypeSpec::of_type::<Vec<(u128, bool)>>())
+                    .indexed(false)
+                    .done(),
+            ])
+            .done()])
+        .done();
+    let layout = layout::Layout::Root(layout::RootLayout::new(
+        layout::LayoutKey::new(0u32),
+        layout::StructLayout::new(
+            "Token",
+            vec![
+                layout::FieldLayout::new(
+                    "total_supply",
+                    layout::LeafLayout::from_key::<u128>(layout::LayoutKey::new(0u32)),
+                ),
+                layout::FieldLayout::new(
+                    "balances",
+                    layout::RootLayout::new(
+                        layout::LayoutKey::new(0x1234_5678u32),
+                        layout::LeafLayout::from_key::<u128>(layout::LayoutKey::new(
+                            0x1234_5678u32,
+                        )),
+                    ),
+                ),
+            ],
+        ),
+    ));
+    let project = InkProject::new(layout, spec);
+
+    let snapshot = stability::StabilitySnapshot::new(&project);
+
+    assert_eq!(
+        snapshot.to_string(),
+        "constructor new 0x5ebd88d6\n\
+         message transfer 0x05060708\n\
+         abi transfer v1([u8; 32], u128)\n\
+         event Transferred(topic to: Option<[u8; 32]>, amounts: Vec<(u128, bool)>)\n\
+         storage Token 0x00000000\n\
+         storage balances 0x12345678\n"
+    );
+    assert_eq!(
+        stability::StabilitySnapshot::parse(&snapshot.to_string()),
+        snapshot
+    );
+
+    let previous = stability::StabilitySnapshot::parse(
+        "constructor new 0x5ebd88d6\nmessage send 0x05060708\n",
+    );
+    let (removed, added) = snapshot.changes(&previous);
+    assert_eq!(removed, vec!["message send 0x05060708"]);
+    assert_eq!(
+        added,
+        vec![
+            "message transfer 0x05060708",
+            "abi transfer v1([u8; 32], u128)",
+            "event Transferred(topic to: Option<[u8; 32]>, amounts: Vec<(u128, bool)>)",
+            "storage Token 0x00000000",
+            "storage balances 0x12345678",
+        ]
+    );
+    assert!(snapshot.frozen_abi_changes(&previous).is_empty());
+
+    let previous = stability::StabilitySnapshot::parse(
+        "abi transfer v1(u128, [u8; 32])\nabi transfer v2(u128)\nabi approve v1(u128)",
+    );
+    assert_eq!(
+        snapshot.frozen_abi_changes(&previous),
+        vec![(
+            "abi transfer v1(u128, [u8; 32])",
+            "abi transfer v1([u8; 32], u128)"
+        )]
+    );
+}
+
+#[test]
+fn stability_snapshot_detects_reordered_struct_fields() {
+    mod before {
+        #[derive(scale_info::TypeInfo)]
+        pub struct Order {
+            pub price: u128,
+            pub amount: u32,
+            pub side: Option<Side>,
+        }
+
+        #[derive(scale_info::TypeInfo)]
+        pub enum Side {
+            Buy,
+            Sell(u8),
+        }
+    }
+
+    mod after {
+        #[derive(scale_info::TypeInfo)]
+        pub struct Order {
+            pub amount: u32,
+            pub price: u128,
+            pub side: Option<Side>,
+        }
+
+        #[derive(scale_info::TypeInfo)]
+        pub enum Side {
+            Buy,
+            Sell(u8),
+        }
+    }
+
+    fn snapshot<T: scale_info::TypeInfo + 'static>() -> stability::StabilitySnapshot {
+        let spec: ContractSpec = ContractSpec::new()
+            .constructors(vec![ConstructorSpec::from_label("new")
+                .selector([94u8, 189u8, 136u8, 214u8])
+                .payable(false)
+                .returns(ReturnTypeSpec::new(None))
+                .done()])
+            .messages(vec![MessageSpec::from_label("place")
+                .selector([5u8, 6u8, 7u8, 8u8])
+                .mutates(true)
+                .payable(false)
+                .args(vec![MessageParamSpec::new("order")
+                    .of_type(TypeSpec::of_type::<T>())
+                    .done()])
+                .returns(ReturnTypeSpec::new(None))
+                .abi_version(1)
+                .done()])
+            .done();
+        let layout = layout::Layout::Leaf(layout::LeafLayout::from_key::<u8>(
+            layout::LayoutKey::new(0u32),
+        ));
+        stability::StabilitySnapshot::new(&InkProject::new(layout, spec))
+    }
+
+    let previous = snapshot::<before::Order>();
+    let current = snapshot::<after::Order>();
+
+    assert_eq!(
+        previous.entries()[2],
+        "abi place v1(Order { price: u128, amount: u32, side: Option<Side> \
+         { None = 0, Some(Side { Buy = 0, Sell(u8) = 1 }) = 1 } })"
+    );
+    assert_eq!(
+        current.frozen_abi_changes(&previous),
+        vec![(
+            previous.entries()[2].as_str(),
+            "abi place v1(Order { amount: u32, price: u128, side: Option<Side> \
+             { None = 0, Some(Side { Buy = 0, Sell(u8) = 1 }) = 1 } })"
+        )]
+    );
+}
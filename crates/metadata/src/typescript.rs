@@ -0,0 +1,497 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generation of TypeScript bindings from the metadata of an ink! smart contract.
+//!
+//! The generated bindings consist of
+//!
+//! - type definitions for all named types of the contract,
+//! - an interface for every event of the contract,
+//! - wrappers around the `@polkadot/api-contract` API for all constructors and messages.
+//!
+//! The type definitions follow the JSON representation of the types used by
+//! `@polkadot/api`, e.g. `Result<T, E>` becomes `{ ok: T } | { err: E }`.
+
+use crate::{
+    InkProject,
+    TypeSpec,
+};
+use scale_info::{
+    form::PortableForm,
+    Field,
+    PortableRegistry,
+    Type,
+    TypeDef,
+    TypeDefPrimitive,
+};
+use std::{
+    collections::{
+        BTreeMap,
+        BTreeSet,
+    },
+    fmt::Write as _,
+};
+
+/// Generates TypeScript bindings for the contract described by `project`.
+///
+/// The `contract_name` is used to name the generated wrapper classes, e.g.
+/// `flipper` yields the classes `Flipper` and `FlipperCode`.
+pub fn generate_typescript(project: &InkProject, contract_name: &str) -> String {
+    TypeScriptGenerator::new(project.registry()).generate(project, contract_name)
+}
+
+/// Generates the TypeScript bindings for the types of a single registry.
+struct TypeScriptGenerator<'a> {
+    registry: &'a PortableRegistry,
+    /// The names of the types exported as named type definitions.
+    names: BTreeMap<u32, String>,
+}
+
+impl<'a> TypeScriptGenerator<'a> {
+    /// Creates a new generator and assigns unique names to all named types.
+    fn new(registry: &'a PortableRegistry) -> Self {
+        let mut names = BTreeMap::new();
+        let mut used = BTreeSet::new();
+        for ty in registry.types() {
+            if !is_named_type(ty.ty()) {
+                continue
+            }
+            let ident = pascal_case(
+                ty.ty()
+                    .path()
+                    .segments()
+                    .last()
+                    .expect("named types must have an identifier"),
+            );
+            let name = if used.contains(&ident) {
+                format!("{}{}", ident, ty.id())
+            } else {
+                ident
+            };
+            used.insert(name.clone());
+            names.insert(ty.id(), name);
+        }
+        Self { registry, names }
+    }
+
+    /// Generates the bindings for the whole contract.
+    fn generate(&self, project: &InkProject, contract_name: &str) -> String {
+        let spec = project.spec();
+        let name = pascal_case(contract_name);
+        let mut out = String::new();
+        writeln!(
+            out,
+            "// This file was generated from the metadata of the `{}` ink! smart contract.",
+            contract_name
+        )
+        .unwrap();
+        out.push_str("// Do not edit it manually.\n\n");
+        out.push_str("import type { ApiPromise } from '@polkadot/api';\n");
+        out.push_str(
+            "import { CodePromise, ContractPromise } from '@polkadot/api-contract';\n",
+        );
+        out.push_str(
+            "import type { BlueprintOptions, ContractOptions } from '@polkadot/api-contract/types';\n\n",
+        );
+        out.push_str("type Abi = ConstructorParameters<typeof ContractPromise>[1];\n");
+
+        for ty in self.registry.types() {
+            if let Some(name) = self.names.get(&ty.id()) {
+                out.push('\n');
+                write_docs(&mut out, "", ty.ty().docs());
+                writeln!(
+                    out,
+                    "export type {} = {};",
+                    name,
+                    self.type_definition(ty.ty())
+                )
+                .unwrap();
+            }
+        }
+
+        for event in spec.events() {
+            out.push('\n');
+            write_docs(&mut out, "", event.docs());
+            writeln!(
+                out,
+                "export interface {}Event {{",
+                pascal_case(event.label())
+            )
+            .unwrap();
+            for arg in event.args() {
+                write_docs(&mut out, "    ", arg.docs());
+                writeln!(
+                    out,
+                    "    {}: {};",
+                    camel_case(arg.label()),
+                    self.type_spec(arg.ty())
+                )
+                .unwrap();
+            }
+            out.push_str("}\n");
+        }
+        if !spec.events().is_empty() {
+            let events = spec
+                .events()
+                .iter()
+                .map(|event| {
+                    format!(
+                        "{{ name: '{}'; args: {}Event }}",
+                        event.label(),
+                        pascal_case(event.label())
+                    )
+                })
+                .collect::<Vec<_>>();
+            writeln!(out, "\nexport type {}Event =", name).unwrap();
+            for event in events {
+                writeln!(out, "    | {}", event).unwrap();
+            }
+            out.push_str(";\n");
+        }
+
+        out.push('\n');
+        write_docs(&mut out, "", spec.docs());
+        writeln!(out, "export class {} {{", name).unwrap();
+        out.push_str("    constructor(readonly contract: ContractPromise) {}\n\n");
+        writeln!(
+            out,
+            "    static at(api: ApiPromise, abi: Abi, address: string): {} {{",
+            name
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "        return new {}(new ContractPromise(api, abi, address));",
+            name
+        )
+        .unwrap();
+        out.push_str("    }\n\n");
+        out.push_str("    /** Dry runs messages without submitting a transaction. */\n");
+        out.push_str("    readonly query = {\n");
+        for (index, message) in spec.messages().iter().enumerate() {
+            let (params, args) = self.params(message.args());
+            write_docs(&mut out, "        ", message.docs());
+            if let Some(ty) = message.return_type().opt_type() {
+                writeln!(out, "        /** @returns `{}` */", self.type_spec(ty))
+                    .unwrap();
+            }
+            writeln!(
+                out,
+                "        {}: (caller: string, options: ContractOptions{}) =>",
+                camel_case(message.label()),
+                params
+            )
+            .unwrap();
+            writeln!(
+                out,
+                "            this.contract.query[this.contract.abi.messages[{}].method](caller, options{}),",
+                index, args
+            )
+            .unwrap();
+        }
+        out.push_str("    };\n\n");
+        out.push_str("    /** Submits transactions calling mutating messages. */\n");
+        out.push_str("    readonly tx = {\n");
+        for (index, message) in spec.messages().iter().enumerate() {
+            if !message.mutates() {
+                continue
+            }
+            let (params, args) = self.params(message.args());
+            write_docs(&mut out, "        ", message.docs());
+            writeln!(
+                out,
+                "        {}: (options: ContractOptions{}) =>",
+                camel_case(message.label()),
+                params
+            )
+            .unwrap();
+            writeln!(
+                out,
+                "            this.contract.tx[this.contract.abi.messages[{}].method](options{}),",
+                index, args
+            )
+            .unwrap();
+        }
+        out.push_str("    };\n");
+        out.push_str("}\n\n");
+
+        writeln!(out, "/** Instantiates the `{}` contract. */", contract_name).unwrap();
+        writeln!(out, "export class {}Code {{", name).unwrap();
+        out.push_str("    constructor(readonly code: CodePromise) {}\n\n");
+        writeln!(
+            out,
+            "    static from(api: ApiPromise, abi: Abi, wasm: Uint8Array | string): {}Code {{",
+            name
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "        return new {}Code(new CodePromise(api, abi, wasm));",
+            name
+        )
+        .unwrap();
+        out.push_str("    }\n\n");
+        out.push_str("    readonly tx = {\n");
+        for (index, constructor) in spec.constructors().iter().enumerate() {
+            let (params, args) = self.params(constructor.args());
+            write_docs(&mut out, "        ", constructor.docs());
+            writeln!(
+                out,
+                "        {}: (options: BlueprintOptions{}) =>",
+                camel_case(constructor.label()),
+                params
+            )
+            .unwrap();
+            writeln!(
+                out,
+                "            this.code.tx[this.code.abi.constructors[{}].method](options{}),",
+                index, args
+            )
+            .unwrap();
+        }
+        out.push_str("    };\n");
+        out.push_str("}\n");
+        out
+    }
+
+    /// Returns the typed parameter list and the argument list for the given arguments.
+    ///
+    /// Both are prefixed with `, ` unless there are no arguments.
+    fn params(&self, args: &[crate::MessageParamSpec<PortableForm>]) -> (String, String) {
+        let mut params = String::new();
+        let mut names = String::new();
+        for arg in args {
+            let label = camel_case(arg.label());
+            write!(params, ", {}: {}", label, self.type_spec(arg.ty())).unwrap();
+            write!(names, ", {}", label).unwrap();
+        }
+        (params, names)
+    }
+
+    /// Returns the TypeScript type of the given type specification.
+    fn type_spec(&self, spec: &TypeSpec<PortableForm>) -> String {
+        self.type_expr(spec.ty().id())
+    }
+
+    /// Returns the TypeScript type referring to the type with the given `id`.
+    fn type_expr(&self, id: u32) -> String {
+        if let Some(name) = self.names.get(&id) {
+            return name.clone()
+        }
+        let ty = self.resolve(id);
+        match ty.type_def() {
+            TypeDef::Variant(variant) if is_prelude_type(ty, "Option") => {
+                let some = variant
+                    .variants()
+                    .iter()
+                    .find(|variant| variant.name() == "Some")
+                    .and_then(|variant| variant.fields().first())
+                    .expect("`Option` must have a `Some` variant with a field");
+                format!("{} | null", self.type_expr(some.ty().id()))
+            }
+            TypeDef::Variant(variant) if is_prelude_type(ty, "Result") => {
+                let field = |name: &str| {
+                    variant
+                        .variants()
+                        .iter()
+                        .find(|variant| variant.name() == name)
+                        .and_then(|variant| variant.fields().first())
+                        .map(|field| self.type_expr(field.ty().id()))
+                        .unwrap_or_else(|| "null".to_string())
+                };
+                format!("{{ ok: {} }} | {{ err: {} }}", field("Ok"), field("Err"))
+            }
+            TypeDef::Sequence(sequence) => self.array_expr(sequence.type_param().id()),
+            TypeDef::Array(array) => self.array_expr(array.type_param().id()),
+            TypeDef::Compact(compact) => self.type_expr(compact.type_param().id()),
+            _ => self.type_definition(ty),
+        }
+    }
+
+    /// Returns the TypeScript type for a sequence of elements of the type with the given `id`.
+    ///
+    /// Byte sequences are represented by their hex encoding.
+    fn array_expr(&self, id: u32) -> String {
+        match self.resolve(id).type_def() {
+            TypeDef::Primitive(TypeDefPrimitive::U8) => "string".to_string(),
+            _ => format!("Array<{}>", self.type_expr(id)),
+        }
+    }
+
+    /// Returns the TypeScript type describing the structure of the given type.
+    fn type_definition(&self, ty: &Type<PortableForm>) -> String {
+        match ty.type_def() {
+            TypeDef::Composite(composite) => self.fields_expr(composite.fields()),
+            TypeDef::Variant(variant) => {
+                let variants = variant.variants();
+                if variants.is_empty() {
+                    return "never".to_string()
+                }
+                if variants.iter().all(|variant| variant.fields().is_empty()) {
+                    return variants
+                        .iter()
+                        .map(|variant| format!("'{}'", variant.name()))
+                        .collect::<Vec<_>>()
+                        .join(" | ")
+                }
+                variants
+                    .iter()
+                    .map(|variant| {
+                        format!(
+                            "{{ {}: {} }}",
+                            camel_case(variant.name()),
+                            self.fields_expr(variant.fields())
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" | ")
+            }
+            TypeDef::Tuple(tuple) => {
+                if tuple.fields().is_empty() {
+                    return "null".to_string()
+                }
+                let fields = tuple
+                    .fields()
+                    .iter()
+                    .map(|field| self.type_expr(field.id()))
+                    .collect::<Vec<_>>();
+                format!("[{}]", fields.join(", "))
+            }
+            TypeDef::Primitive(primitive) => primitive_expr(primitive).to_string(),
+            TypeDef::BitSequence(_) => "string".to_string(),
+            TypeDef::Sequence(_) | TypeDef::Array(_) | TypeDef::Compact(_) => {
+                unreachable!("sequences, arrays and compacts are never named types")
+            }
+        }
+    }
+
+    /// Returns the TypeScript type for the fields of a struct or an enum variant.
+    fn fields_expr(&self, fields: &[Field<PortableForm>]) -> String {
+        match fields {
+            [] => "null".to_string(),
+            [field] if field.name().is_none() => self.type_expr(field.ty().id()),
+            fields if fields.iter().all(|field| field.name().is_some()) => {
+                let fields = fields
+                    .iter()
+                    .map(|field| {
+                        format!(
+                            "{}: {}",
+                            camel_case(field.name().expect("checked above")),
+                            self.type_expr(field.ty().id())
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                format!("{{ {} }}", fields.join("; "))
+            }
+            fields => {
+                let fields = fields
+                    .iter()
+                    .map(|field| self.type_expr(field.ty().id()))
+                    .collect::<Vec<_>>();
+                format!("[{}]", fields.join(", "))
+            }
+        }
+    }
+
+    /// Returns the type with the given `id` from the registry.
+    fn resolve(&self, id: u32) -> &Type<PortableForm> {
+        self.registry
+            .resolve(id)
+            .unwrap_or_else(|| panic!("encountered unknown type with id {}", id))
+    }
+}
+
+/// Returns `true` if the type is exported as a named type definition.
+///
+/// These are all structs and enums except for `Option` and `Result` which are
+/// represented inline.
+fn is_named_type(ty: &Type<PortableForm>) -> bool {
+    matches!(ty.type_def(), TypeDef::Composite(_) | TypeDef::Variant(_))
+        && !ty.path().segments().is_empty()
+        && !is_prelude_type(ty, "Option")
+        && !is_prelude_type(ty, "Result")
+}
+
+/// Returns `true` if the type is the prelude type with the given `ident`.
+fn is_prelude_type(ty: &Type<PortableForm>, ident: &str) -> bool {
+    matches!(ty.path().segments(), [segment] if segment == ident)
+}
+
+/// Returns the TypeScript type of a primitive type.
+///
+/// Integers that might not fit into a JavaScript `number` are represented
+/// as either `number` or `string`.
+fn primitive_expr(primitive: &TypeDefPrimitive) -> &'static str {
+    match primitive {
+        TypeDefPrimitive::Bool => "boolean",
+        TypeDefPrimitive::Char | TypeDefPrimitive::Str => "string",
+        TypeDefPrimitive::U8
+        | TypeDefPrimitive::U16
+        | TypeDefPrimitive::U32
+        | TypeDefPrimitive::I8
+        | TypeDefPrimitive::I16
+        | TypeDefPrimitive::I32 => "number",
+        TypeDefPrimitive::U64
+        | TypeDefPrimitive::U128
+        | TypeDefPrimitive::U256
+        | TypeDefPrimitive::I64
+        | TypeDefPrimitive::I128
+        | TypeDefPrimitive::I256 => "number | string",
+    }
+}
+
+/// Writes the given documentation lines as a JSDoc comment with the given indentation.
+fn write_docs(out: &mut String, indent: &str, docs: &[String]) {
+    if docs.is_empty() {
+        return
+    }
+    writeln!(out, "{}/**", indent).unwrap();
+    for line in docs {
+        let line = line.trim_end();
+        if line.is_empty() {
+            writeln!(out, "{} *", indent).unwrap();
+        } else {
+            writeln!(out, "{} * {}", indent, line.trim_start()).unwrap();
+        }
+    }
+    writeln!(out, "{} */", indent).unwrap();
+}
+
+/// Converts the given identifier into `camelCase`.
+///
+/// Every non-alphanumeric character is treated as a word boundary, so that
+/// e.g. `BaseErc20::total_supply` becomes `baseErc20TotalSupply`.
+fn camel_case(ident: &str) -> String {
+    let pascal = pascal_case(ident);
+    let mut chars = pascal.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Converts the given identifier into `PascalCase`.
+///
+/// Every non-alphanumeric character is treated as a word boundary.
+fn pascal_case(ident: &str) -> String {
+    ident
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            let first = chars.next().expect("words are never empty");
+            first.to_uppercase().chain(chars).collect::<String>()
+        })
+        .collect()
+}
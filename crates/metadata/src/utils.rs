@@ -66,3 +66,72 @@ pub fn trim_extra_whitespace(item: &str) -> &str {
         item.trim_end()
     }
 }
+
+/// Extracts the documentation of the parameter `label` from the `docs` of a message
+/// or constructor.
+///
+/// Two styles of parameter documentation are supported:
+///
+/// - List items of a `# Parameters` or `# Arguments` section, e.g.
+///   ``- `amount`: The amount to transfer.`` or `* amount - The amount to transfer.`
+/// - `@param` tags, e.g. `@param amount The amount to transfer.`
+///
+/// Lines following the first line of the documentation of a parameter belong to it
+/// until the next list item, tag, heading or empty line.
+pub fn extract_param_docs<'a>(docs: &[&'a str], label: &str) -> Vec<&'a str> {
+    let mut param_docs = Vec::new();
+    let mut in_params_section = false;
+    let mut in_param = false;
+    for line in docs.iter().map(|line| line.trim()) {
+        if let Some(heading) = line.strip_prefix('#') {
+            let heading = heading.trim_start_matches('#').trim();
+            in_params_section = heading.eq_ignore_ascii_case("parameters")
+                || heading.eq_ignore_ascii_case("arguments");
+            in_param = false;
+            continue
+        }
+        if let Some(tag) = line.strip_prefix("@param") {
+            in_param = false;
+            if let Some(description) = strip_param_label(tag.trim_start(), label) {
+                param_docs.push(description);
+                in_param = true;
+            }
+            continue
+        }
+        let list_item = line
+            .strip_prefix('-')
+            .or_else(|| line.strip_prefix('*'))
+            .filter(|_| in_params_section);
+        if let Some(item) = list_item {
+            in_param = false;
+            if let Some(description) = strip_param_label(item.trim_start(), label) {
+                param_docs.push(description);
+                in_param = true;
+            }
+            continue
+        }
+        if line.is_empty() || line.starts_with('@') {
+            in_param = false;
+        } else if in_param {
+            param_docs.push(line);
+        }
+    }
+    param_docs
+}
+
+/// Returns the description following the parameter `label` at the start of `item`.
+///
+/// The label may be enclosed in backticks and be followed by `:` or `-`.
+fn strip_param_label<'a>(item: &'a str, label: &str) -> Option<&'a str> {
+    let rest = match item.strip_prefix('`') {
+        Some(quoted) => quoted.strip_prefix(label)?.strip_prefix('`')?,
+        None => item.strip_prefix(label)?,
+    };
+    let description = match rest.trim_start().strip_prefix([':', '-']) {
+        Some(description) => description,
+        // Reject labels which are a prefix of the parameter name, e.g. `to` of `total`.
+        None if rest.starts_with(char::is_whitespace) || rest.is_empty() => rest,
+        None => return None,
+    };
+    Some(description.trim())
+}
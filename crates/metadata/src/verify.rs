@@ -0,0 +1,355 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Verification of contract metadata against a Wasm blob.
+//!
+//! Block explorers and clients can use this to make sure that the metadata
+//! they are about to decode events and messages with actually belongs to the
+//! deployed code.
+//!
+//! Contracts built with `#[ink::contract(embed_metadata_hash = true)]` carry the
+//! [`metadata_hash`] in their Wasm blob, which links the deployed code to its
+//! metadata without relying on the code hash recorded by the build tool.
+
+use crate::{
+    layout::Layout,
+    ContractSpec,
+    InkProject,
+    MetadataVersion,
+};
+use blake2::{
+    digest::consts::U32,
+    Blake2b,
+    Digest as _,
+};
+use scale_info::{
+    form::PortableForm,
+    PortableRegistry,
+};
+use serde::Serialize;
+
+/// The name of the Wasm custom section holding the [`metadata_hash`] of a contract.
+pub const METADATA_HASH_SECTION: &str = "ink_metadata_hash";
+
+/// Errors that can occur when verifying metadata against a Wasm blob.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerifyError {
+    /// The metadata does not contain the code hash of a Wasm blob.
+    MissingCodeHash,
+    /// The code hash of the Wasm blob differs from the one in the metadata.
+    CodeHashMismatch {
+        /// The code hash recorded in the metadata.
+        expected: [u8; 32],
+        /// The code hash of the given Wasm blob.
+        found: [u8; 32],
+    },
+    /// The Wasm blob does not embed a metadata hash.
+    MissingMetadataHash,
+    /// The metadata hash embedded in the Wasm blob differs from the metadata.
+    MetadataHashMismatch {
+        /// The metadata hash embedded in the Wasm blob.
+        expected: [u8; 32],
+        /// The hash of the given metadata.
+        found: [u8; 32],
+    },
+}
+
+impl core::fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::MissingCodeHash => write!(f, "metadata does not contain a code hash"),
+            Self::CodeHashMismatch { .. } => {
+                write!(f, "code hash of the Wasm blob does not match the metadata")
+            }
+            Self::MissingMetadataHash => {
+                write!(f, "Wasm blob does not embed a metadata hash")
+            }
+            Self::MetadataHashMismatch { .. } => {
+                write!(f, "metadata hash embedded in the Wasm blob does not match")
+            }
+        }
+    }
+}
+
+/// Returns the code hash of the given Wasm blob.
+///
+/// This is the BLAKE2 256-bit hash `pallet-contracts` identifies uploaded code by.
+pub fn code_hash(wasm: &[u8]) -> [u8; 32] {
+    let mut hasher = <Blake2b<U32>>::new();
+    hasher.update(wasm);
+    hasher.finalize().into()
+}
+
+/// Verifies that the metadata was built alongside the given Wasm blob.
+///
+/// # Errors
+///
+/// - If the metadata does not record a code hash.
+/// - If the code hash of `wasm` differs from the recorded one.
+pub fn verify_wasm(metadata: &InkProject, wasm: &[u8]) -> Result<(), VerifyError> {
+    let expected = *metadata
+        .build_info()
+        .and_then(|build| build.code_hash())
+        .ok_or(VerifyError::MissingCodeHash)?
+        .as_bytes();
+    let found = code_hash(wasm);
+    if expected != found {
+        return Err(VerifyError::CodeHashMismatch { expected, found })
+    }
+    Ok(())
+}
+
+/// Returns `true` if the metadata was built alongside the given Wasm blob.
+///
+/// Metadata without a recorded code hash never matches. Use [`verify_wasm`]
+/// to find out why verification failed.
+pub fn matches_wasm(metadata: &InkProject, wasm: &[u8]) -> bool {
+    verify_wasm(metadata, wasm).is_ok()
+}
+
+/// The parts of the metadata covered by the [`metadata_hash`].
+#[derive(Serialize)]
+struct HashedMetadata<'a> {
+    version: &'a MetadataVersion,
+    #[serde(flatten)]
+    registry: &'a PortableRegistry,
+    #[serde(rename = "storage")]
+    layout: &'a Layout<PortableForm>,
+    spec: &'a ContractSpec<PortableForm>,
+}
+
+/// Returns the hash of the metadata embedded by contracts built with
+/// `#[ink::contract(embed_metadata_hash = true)]`.
+///
+/// This is the BLAKE2 256-bit hash of the JSON serialized metadata. The build
+/// information is left out, since it records the code hash of the Wasm blob the
+/// metadata hash is embedded into.
+pub fn metadata_hash(metadata: &InkProject) -> [u8; 32] {
+    let json = serde_json::to_vec(&HashedMetadata {
+        version: &metadata.version,
+        registry: &metadata.registry,
+        layout: &metadata.layout,
+        spec: &metadata.spec,
+    })
+    .expect("the metadata is always serializable to JSON");
+    code_hash(&json)
+}
+
+/// Returns the metadata hash embedded in the [`METADATA_HASH_SECTION`] custom
+/// section of the given Wasm blob.
+///
+/// Returns `None` if the Wasm blob is malformed or does not embed a metadata hash.
+pub fn embedded_metadata_hash(wasm: &[u8]) -> Option<[u8; 32]> {
+    let mut sections = wasm.strip_prefix(b"\0asm\x01\0\0\0")?;
+    while let Some((&id, rest)) = sections.split_first() {
+        let (len, rest) = read_leb128(rest)?;
+        let (section, rest) = split_at_checked(rest, len)?;
+        sections = rest;
+        if id != 0 {
+            continue
+        }
+        let (name_len, section) = read_leb128(section)?;
+        let (name, payload) = split_at_checked(section, name_len)?;
+        if name == METADATA_HASH_SECTION.as_bytes() {
+            return payload.try_into().ok()
+        }
+    }
+    None
+}
+
+/// Verifies that the given Wasm blob embeds the hash of the metadata.
+///
+/// # Errors
+///
+/// - If the Wasm blob does not embed a metadata hash.
+/// - If the embedded metadata hash differs from the hash of `metadata`.
+pub fn verify_metadata_hash(
+    metadata: &InkProject,
+    wasm: &[u8],
+) -> Result<(), VerifyError> {
+    let expected =
+        embedded_metadata_hash(wasm).ok_or(VerifyError::MissingMetadataHash)?;
+    let found = metadata_hash(metadata);
+    if expected != found {
+        return Err(VerifyError::MetadataHashMismatch { expected, found })
+    }
+    Ok(())
+}
+
+/// Reads an unsigned LEB128 encoded `u32` from the start of `bytes`.
+fn read_leb128(bytes: &[u8]) -> Option<(usize, &[u8])> {
+    let mut value = 0u32;
+    for (n, byte) in bytes.iter().enumerate().take(5) {
+        value |= u32::from(byte & 0x7F).checked_shl(7 * n as u32)?;
+        if byte & 0x80 == 0 {
+            return Some((value as usize, &bytes[n + 1..]))
+        }
+    }
+    None
+}
+
+/// Splits `bytes` at `mid` if it has at least `mid` bytes.
+fn split_at_checked(bytes: &[u8], mid: usize) -> Option<(&[u8], &[u8])> {
+    (mid <= bytes.len()).then(|| bytes.split_at(mid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        layout::{
+            Layout,
+            LayoutKey,
+            LeafLayout,
+        },
+        BuildInfo,
+        ConstructorSpec,
+        ContractSpec,
+        MessageSpec,
+        ReturnTypeSpec,
+    };
+
+    fn project() -> InkProject {
+        let layout = Layout::Leaf(LeafLayout::from_key::<u8>(LayoutKey::new(0_u32)));
+        let spec: ContractSpec = ContractSpec::new()
+            .constructors([ConstructorSpec::from_label("new")
+                .selector([0x9B, 0xAE, 0x9D, 0x5E])
+                .payable(false)
+                .returns(ReturnTypeSpec::new(None))
+                .done()])
+            .messages([MessageSpec::from_label("get")
+                .selector([0x2F, 0x86, 0x5B, 0xD9])
+                .mutates(false)
+                .payable(false)
+                .returns(ReturnTypeSpec::new(None))
+                .done()])
+            .done();
+        InkProject::new(layout, spec)
+    }
+
+    #[test]
+    fn matches_recorded_code_hash() {
+        let wasm = b"\0asm\x01\0\0\0";
+        let metadata = project()
+            .with_build_info(BuildInfo::new(false).with_code_hash(code_hash(wasm)));
+
+        assert!(matches_wasm(&metadata, wasm));
+        assert!(!matches_wasm(&metadata, b"\0asm\x01\0\0\x01"));
+        assert_eq!(
+            verify_wasm(&metadata, b""),
+            Err(VerifyError::CodeHashMismatch {
+                expected: code_hash(wasm),
+                found: code_hash(b""),
+            })
+        );
+    }
+
+    #[test]
+    fn missing_code_hash_never_matches() {
+        let metadata = project().with_build_info(BuildInfo::new(true));
+
+        assert!(!matches_wasm(&metadata, b""));
+        assert_eq!(
+            verify_wasm(&metadata, b""),
+            Err(VerifyError::MissingCodeHash)
+        );
+    }
+
+    /// Returns a Wasm blob with a type section and the given custom section.
+    fn wasm_with_custom_section(name: &str, payload: &[u8]) -> Vec<u8> {
+        let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+        wasm.extend([0x01, 0x04, 0x01, 0x60, 0x00, 0x00]);
+        wasm.push(0x00);
+        wasm.push((1 + name.len() + payload.len()) as u8);
+        wasm.push(name.len() as u8);
+        wasm.extend(name.as_bytes());
+        wasm.extend(payload);
+        wasm
+    }
+
+    #[test]
+    fn embedded_metadata_hash_works() {
+        let metadata = project();
+        let hash = metadata_hash(&metadata);
+        let wasm = wasm_with_custom_section(METADATA_HASH_SECTION, &hash);
+
+        assert_eq!(embedded_metadata_hash(&wasm), Some(hash));
+        assert_eq!(verify_metadata_hash(&metadata, &wasm), Ok(()));
+        assert_eq!(
+            verify_metadata_hash(&metadata, b"\0asm\x01\0\0\0"),
+            Err(VerifyError::MissingMetadataHash)
+        );
+        let other = wasm_with_custom_section(METADATA_HASH_SECTION, &[0x00; 32]);
+        assert_eq!(
+            verify_metadata_hash(&metadata, &other),
+            Err(VerifyError::MetadataHashMismatch {
+                expected: [0x00; 32],
+                found: hash,
+            })
+        );
+    }
+
+    #[test]
+    fn embedded_metadata_hash_rejects_malformed_sections() {
+        let hash = [0x01; 32];
+        assert_eq!(
+            embedded_metadata_hash(&wasm_with_custom_section("name", &hash)),
+            None
+        );
+        assert_eq!(
+            embedded_metadata_hash(&wasm_with_custom_section(
+                METADATA_HASH_SECTION,
+                &hash[..31]
+            )),
+            None
+        );
+        let mut truncated = wasm_with_custom_section(METADATA_HASH_SECTION, &hash);
+        truncated.pop();
+        assert_eq!(embedded_metadata_hash(&truncated), None);
+    }
+
+    #[test]
+    fn metadata_hash_ignores_build_info() {
+        let metadata = project();
+        let hash = metadata_hash(&metadata);
+        let metadata =
+            metadata.with_build_info(BuildInfo::new(true).with_code_hash([0x01; 32]));
+        assert_eq!(metadata_hash(&metadata), hash);
+
+        let json = serde_json::to_value(&metadata).unwrap();
+        let decoded: InkProject = serde_json::from_value(json).unwrap();
+        assert_eq!(metadata_hash(&decoded), hash);
+    }
+
+    #[test]
+    fn build_info_roundtrips_through_json() {
+        let wasm = b"\0asm\x01\0\0\0";
+        let build = BuildInfo::new(false)
+            .with_compiler("rustc 1.66.0")
+            .with_code_hash(code_hash(wasm));
+        let metadata = project().with_build_info(build.clone());
+
+        let json = serde_json::to_value(&metadata).unwrap();
+        assert_eq!(
+            json["build"]["language"],
+            format!("ink! {}", env!("CARGO_PKG_VERSION"))
+        );
+        assert_eq!(json["build"]["compiler"], "rustc 1.66.0");
+        assert_eq!(json["build"]["debugAssertions"], false);
+
+        let decoded: InkProject = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded.build_info(), Some(&build));
+        assert!(matches_wasm(&decoded, wasm));
+    }
+}
@@ -0,0 +1,477 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Unsigned fixed-point decimal numbers.
+//!
+//! [`UFixed128<DECIMALS>`] represents a number `x` by the integer `x * 10^DECIMALS`,
+//! e.g. `1.5` is `1_500_000` for `UFixed128<6>`. Multiplications and divisions
+//! take a [`Rounding`] mode since their exact result is not representable in
+//! general. Their intermediate results are computed with 256 bits, so they only
+//! fail if the final result does not fit.
+//!
+//! # Example
+//!
+//! ```
+//! use ink_prelude::fixed::{
+//!     Rounding,
+//!     UFixed128,
+//! };
+//!
+//! type Rate = UFixed128<9>;
+//!
+//! // A fee of 0.3%.
+//! let fee = Rate::from_rational(3, 1_000, Rounding::Down).unwrap();
+//! // The fee charged for transferring 1_001 tokens, rounded in favour of the contract.
+//! assert_eq!(fee.checked_mul_int(1_001, Rounding::Up), Some(4));
+//! assert_eq!(fee.checked_mul_int(1_001, Rounding::Down), Some(3));
+//! ```
+//!
+//! Integer amounts of the smallest unit of a currency, like balances, are rendered
+//! with their decimal places by [`format_decimal`].
+
+use core::{
+    fmt,
+    ops::{
+        Add,
+        Sub,
+    },
+};
+
+/// The rounding mode of an operation whose exact result is not representable.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Rounding {
+    /// Rounds towards zero.
+    Down,
+    /// Rounds away from zero.
+    Up,
+    /// Rounds to the nearest representable number, and up if both are equally near.
+    HalfUp,
+    /// Rounds to the nearest representable number, and to the even one if both
+    /// are equally near.
+    HalfEven,
+}
+
+/// An unsigned fixed-point decimal number with `DECIMALS` decimal places.
+///
+/// It is encoded like the `u128` representing it, see [`UFixed128::into_inner`].
+///
+/// # Note
+///
+/// The sum and the difference of two numbers are exact and thus implement
+/// [`Add`] and [`Sub`], which panic on overflow like the integer operations.
+/// Products and quotients require a [`Rounding`] mode, e.g. [`UFixed128::checked_mul`].
+#[derive(
+    Debug,
+    Default,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    scale::Encode,
+    scale::Decode,
+)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct UFixed128<const DECIMALS: u32>(u128);
+
+impl<const DECIMALS: u32> UFixed128<DECIMALS> {
+    /// The factor between a number and the integer representing it, i.e. `10^DECIMALS`.
+    ///
+    /// Using a `DECIMALS` greater than `38` fails to compile.
+    pub const SCALE: u128 = 10u128.pow(DECIMALS);
+
+    /// The number `0`.
+    pub const ZERO: Self = Self(0);
+
+    /// The number `1`.
+    pub const ONE: Self = Self(Self::SCALE);
+
+    /// The greatest representable number.
+    pub const MAX: Self = Self(u128::MAX);
+
+    /// Creates the number represented by `inner`, i.e. `inner / 10^DECIMALS`.
+    pub const fn from_inner(inner: u128) -> Self {
+        Self(inner)
+    }
+
+    /// Returns the integer representing the number, i.e. `self * 10^DECIMALS`.
+    pub const fn into_inner(self) -> u128 {
+        self.0
+    }
+
+    /// Returns the integer `value` as a fixed-point number.
+    ///
+    /// Returns `None` if it is not representable.
+    pub fn from_integer(value: u128) -> Option<Self> {
+        value.checked_mul(Self::SCALE).map(Self)
+    }
+
+    /// Returns `numerator / denominator` as a fixed-point number.
+    ///
+    /// Returns `None` if `denominator` is zero or the result is not representable.
+    pub fn from_rational(
+        numerator: u128,
+        denominator: u128,
+        rounding: Rounding,
+    ) -> Option<Self> {
+        mul_div(numerator, Self::SCALE, denominator, rounding).map(Self)
+    }
+
+    /// Returns the number rounded to an integer.
+    pub fn to_integer(self, rounding: Rounding) -> u128 {
+        mul_div(self.0, 1, Self::SCALE, rounding)
+            .expect("dividing by the non-zero scale never overflows")
+    }
+
+    /// Returns `self + rhs`, or `None` on overflow.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+
+    /// Returns `self - rhs`, or `None` on underflow.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Self)
+    }
+
+    /// Returns `self + rhs`, saturating at [`UFixed128::MAX`].
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+
+    /// Returns `self - rhs`, saturating at [`UFixed128::ZERO`].
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+
+    /// Returns `self * rhs` rounded according to `rounding`, or `None` on overflow.
+    pub fn checked_mul(self, rhs: Self, rounding: Rounding) -> Option<Self> {
+        mul_div(self.0, rhs.0, Self::SCALE, rounding).map(Self)
+    }
+
+    /// Returns `self / rhs` rounded according to `rounding`.
+    ///
+    /// Returns `None` if `rhs` is zero or on overflow.
+    pub fn checked_div(self, rhs: Self, rounding: Rounding) -> Option<Self> {
+        mul_div(self.0, Self::SCALE, rhs.0, rounding).map(Self)
+    }
+
+    /// Returns `self * rhs` for the integer `rhs`, rounded to an integer according
+    /// to `rounding`, or `None` on overflow.
+    ///
+    /// This applies a rate to an amount, e.g. computes the interest of a balance.
+    pub fn checked_mul_int(self, rhs: u128, rounding: Rounding) -> Option<u128> {
+        mul_div(self.0, rhs, Self::SCALE, rounding)
+    }
+}
+
+impl<const DECIMALS: u32> Add for UFixed128<DECIMALS> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl<const DECIMALS: u32> Sub for UFixed128<DECIMALS> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl<const DECIMALS: u32> fmt::Display for UFixed128<DECIMALS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let integer = self.0 / Self::SCALE;
+        if DECIMALS == 0 {
+            return write!(f, "{}", integer)
+        }
+        write!(
+            f,
+            "{}.{:0width$}",
+            integer,
+            self.0 % Self::SCALE,
+            width = DECIMALS as usize
+        )
+    }
+}
+
+/// Returns the decimal representation of the integer `value` with `decimals`
+/// decimal places, e.g. `1.5` for `1_500_000` with `6` decimals.
+///
+/// Trailing zeros of the fraction are omitted, so that whole numbers are rendered
+/// without a fraction. Formatting the returned value does not allocate.
+///
+/// # Example
+///
+/// ```
+/// use ink_prelude::fixed::format_decimal;
+///
+/// assert_eq!(format!("{} UNIT", format_decimal(1_500_000, 6)), "1.5 UNIT");
+/// assert_eq!(format!("{}", format_decimal(42, 6)), "0.000042");
+/// assert_eq!(format!("{}", format_decimal(7_000_000, 6)), "7");
+/// ```
+pub fn format_decimal(value: u128, decimals: u8) -> DisplayDecimal {
+    DisplayDecimal { value, decimals }
+}
+
+/// The decimal representation of an integer, see [`format_decimal`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct DisplayDecimal {
+    value: u128,
+    decimals: u8,
+}
+
+impl fmt::Display for DisplayDecimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut width = u32::from(self.decimals);
+        // Every `u128` is less than `10^39`, which does not fit into a `u128` itself.
+        let (integer, mut fraction) = match 10u128.checked_pow(width) {
+            Some(scale) => (self.value / scale, self.value % scale),
+            None => (0, self.value),
+        };
+        write!(f, "{}", integer)?;
+        if fraction == 0 {
+            return Ok(())
+        }
+        while fraction % 10 == 0 {
+            fraction /= 10;
+            width -= 1;
+        }
+        write!(f, ".{:0width$}", fraction, width = width as usize)
+    }
+}
+
+/// Returns `a * b / c` rounded according to `rounding`.
+///
+/// The product is computed with 256 bits, so only the quotient can overflow.
+/// Returns `None` if `c` is zero or the quotient does not fit into a `u128`.
+///
+/// # Example
+///
+/// ```
+/// use ink_prelude::fixed::{
+///     mul_div,
+///     Rounding,
+/// };
+///
+/// assert_eq!(mul_div(u128::MAX, u128::MAX, u128::MAX, Rounding::Down), Some(u128::MAX));
+/// assert_eq!(mul_div(7, 10, 4, Rounding::Down), Some(17));
+/// assert_eq!(mul_div(7, 10, 4, Rounding::Up), Some(18));
+/// assert_eq!(mul_div(u128::MAX, 2, 1, Rounding::Down), None);
+/// assert_eq!(mul_div(1, 1, 0, Rounding::Down), None);
+/// ```
+pub fn mul_div(a: u128, b: u128, c: u128, rounding: Rounding) -> Option<u128> {
+    if c == 0 {
+        return None
+    }
+    let (high, low) = mul_wide(a, b);
+    let (quotient, remainder) = if high == 0 {
+        (low / c, low % c)
+    } else {
+        div_wide(high, low, c)?
+    };
+    let round_up = match rounding {
+        Rounding::Down => false,
+        Rounding::Up => remainder > 0,
+        Rounding::HalfUp => remainder >= c - remainder,
+        Rounding::HalfEven => {
+            remainder > c - remainder || (remainder == c - remainder && quotient % 2 == 1)
+        }
+    };
+    if round_up {
+        quotient.checked_add(1)
+    } else {
+        Some(quotient)
+    }
+}
+
+/// Returns the high and low 128 bits of the 256 bit product `a * b`.
+fn mul_wide(a: u128, b: u128) -> (u128, u128) {
+    const MASK: u128 = u64::MAX as u128;
+    let (a_high, a_low) = (a >> 64, a & MASK);
+    let (b_high, b_low) = (b >> 64, b & MASK);
+    let low_low = a_low * b_low;
+    let high_low = a_high * b_low;
+    let low_high = a_low * b_high;
+    let high_high = a_high * b_high;
+    let middle = (low_low >> 64) + (high_low & MASK) + (low_high & MASK);
+    let low = (middle << 64) | (low_low & MASK);
+    let high = high_high + (high_low >> 64) + (low_high >> 64) + (middle >> 64);
+    (high, low)
+}
+
+/// Returns the quotient and remainder of the 256 bit number `high * 2^128 + low`
+/// divided by `divisor`.
+///
+/// Returns `None` if the quotient does not fit into a `u128`.
+fn div_wide(high: u128, mut low: u128, divisor: u128) -> Option<(u128, u128)> {
+    if high >= divisor {
+        return None
+    }
+    let mut remainder = high;
+    let mut quotient = 0;
+    for _ in 0..128 {
+        let carry = remainder >> 127;
+        remainder = (remainder << 1) | (low >> 127);
+        low <<= 1;
+        quotient <<= 1;
+        if carry == 1 || remainder >= divisor {
+            remainder = remainder.wrapping_sub(divisor);
+            quotient |= 1;
+        }
+    }
+    Some((quotient, remainder))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Fixed = UFixed128<6>;
+
+    fn fixed(inner: u128) -> Fixed {
+        Fixed::from_inner(inner)
+    }
+
+    #[test]
+    fn conversions_work() {
+        assert_eq!(Fixed::ONE, fixed(1_000_000));
+        assert_eq!(Fixed::from_integer(3), Some(fixed(3_000_000)));
+        assert_eq!(Fixed::from_integer(u128::MAX), None);
+        assert_eq!(
+            Fixed::from_rational(1, 3, Rounding::Down),
+            Some(fixed(333_333))
+        );
+        assert_eq!(
+            Fixed::from_rational(2, 3, Rounding::Up),
+            Some(fixed(666_667))
+        );
+        assert_eq!(Fixed::from_rational(1, 0, Rounding::Down), None);
+        assert_eq!(fixed(2_500_000).to_integer(Rounding::Down), 2);
+        assert_eq!(fixed(2_500_000).to_integer(Rounding::HalfUp), 3);
+        assert_eq!(fixed(2_500_000).to_integer(Rounding::HalfEven), 2);
+        assert_eq!(fixed(3_500_000).to_integer(Rounding::HalfEven), 4);
+        assert_eq!(fixed(2_000_001).to_integer(Rounding::Up), 3);
+    }
+
+    #[test]
+    fn mul_and_div_round() {
+        let third = Fixed::from_rational(1, 3, Rounding::Down).unwrap();
+        assert_eq!(
+            third.checked_mul(third, Rounding::Down),
+            Some(fixed(111_110))
+        );
+        assert_eq!(third.checked_mul(third, Rounding::Up), Some(fixed(111_111)));
+        assert_eq!(
+            Fixed::ONE.checked_div(fixed(3_000_000), Rounding::HalfUp),
+            Some(fixed(333_333))
+        );
+        assert_eq!(
+            fixed(2_000_000).checked_div(fixed(3_000_000), Rounding::HalfUp),
+            Some(fixed(666_667))
+        );
+        assert_eq!(Fixed::ONE.checked_div(Fixed::ZERO, Rounding::Down), None);
+        assert_eq!(
+            Fixed::MAX.checked_mul(fixed(2_000_000), Rounding::Down),
+            None
+        );
+    }
+
+    #[test]
+    fn mul_div_works() {
+        assert_eq!(
+            mul_div(u128::MAX, 3, 4, Rounding::Down),
+            Some(u128::MAX / 4 * 3 + 2)
+        );
+        assert_eq!(
+            mul_div(u128::MAX, u128::MAX - 1, u128::MAX, Rounding::Down),
+            Some(u128::MAX - 1)
+        );
+        assert_eq!(
+            mul_div(1 << 100, 1 << 100, 1 << 90, Rounding::Down),
+            Some(1 << 110)
+        );
+        assert_eq!(
+            mul_div(u128::MAX, u128::MAX, u128::MAX - 1, Rounding::Down),
+            None
+        );
+        assert_eq!(
+            mul_div(u128::MAX, 3, 4, Rounding::Up),
+            Some(u128::MAX / 4 * 3 + 3)
+        );
+        assert_eq!(mul_div(u128::MAX, 1, 1, Rounding::Up), Some(u128::MAX));
+        assert_eq!(mul_div(u128::MAX, 2, 2, Rounding::Up), Some(u128::MAX));
+    }
+
+    #[test]
+    fn wide_intermediate_results_work() {
+        let large = Fixed::from_integer(u128::MAX / Fixed::SCALE / 2).unwrap();
+        assert_eq!(
+            large.checked_mul(fixed(2_000_000), Rounding::Down),
+            Some(fixed(large.into_inner() * 2))
+        );
+        assert_eq!(
+            large
+                .checked_mul(Fixed::ONE, Rounding::Down)
+                .and_then(|x| x.checked_div(Fixed::ONE, Rounding::Down)),
+            Some(large)
+        );
+        assert_eq!(
+            Fixed::MAX.checked_mul_int(1_000_000, Rounding::Down),
+            Some(u128::MAX)
+        );
+        assert_eq!(mul_wide(0, u128::MAX), (0, 0));
+        assert_eq!(mul_wide(u128::MAX, 2), (1, u128::MAX - 1));
+        assert_eq!(mul_wide(u128::MAX, u128::MAX), (u128::MAX - 1, 1));
+        assert_eq!(mul_wide(1 << 64, 1 << 64), (1, 0));
+        assert_eq!(div_wide(1, 0, 2), Some((1 << 127, 0)));
+        assert_eq!(div_wide(u128::MAX - 1, 1, u128::MAX), Some((u128::MAX, 0)));
+    }
+
+    #[test]
+    fn display_and_encoding_work() {
+        assert_eq!(format!("{}", fixed(1_500_000)), "1.500000");
+        assert_eq!(format!("{}", fixed(42)), "0.000042");
+        assert_eq!(format!("{}", UFixed128::<0>::from_inner(7)), "7");
+        let encoded = scale::Encode::encode(&fixed(1_500_000));
+        assert_eq!(encoded, scale::Encode::encode(&1_500_000u128));
+        assert_eq!(
+            <Fixed as scale::Decode>::decode(&mut &encoded[..]),
+            Ok(fixed(1_500_000))
+        );
+    }
+
+    #[test]
+    fn format_decimal_works() {
+        assert_eq!(format_decimal(1_500_000, 6).to_string(), "1.5");
+        assert_eq!(format_decimal(1_000_001, 6).to_string(), "1.000001");
+        assert_eq!(format_decimal(0, 6).to_string(), "0");
+        assert_eq!(format_decimal(123, 0).to_string(), "123");
+        assert_eq!(
+            format_decimal(u128::MAX, 38).to_string(),
+            "3.40282366920938463463374607431768211455"
+        );
+        assert_eq!(
+            format_decimal(1, 40).to_string(),
+            "0.0000000000000000000000000000000000000001"
+        );
+        assert_eq!(
+            format_decimal(u128::MAX, 255).to_string().len(),
+            "0.".len() + 255
+        );
+    }
+}
@@ -31,6 +31,8 @@ extern crate alloc;
 
 use cfg_if::cfg_if;
 
+pub mod fixed;
+
 cfg_if! {
     if #[cfg(feature = "std")] {
         pub use std::{
@@ -28,6 +28,8 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 mod key;
+#[cfg(feature = "ss58")]
+mod ss58;
 mod types;
 
 pub use self::{
@@ -38,10 +40,17 @@ pub use self::{
     types::{
         AccountId,
         Clear,
+        FromHexError,
         Hash,
     },
 };
 
+#[cfg(feature = "ss58")]
+pub use self::ss58::{
+    Ss58Error,
+    SUBSTRATE_SS58_PREFIX,
+};
+
 /// An error emitted by the smart contracting language.
 ///
 /// This is different than errors from:
@@ -54,6 +63,13 @@ pub use self::{
 pub enum LangError {
     /// Failed to read execution input for the dispatchable.
     CouldNotReadInput = 1u32,
+    /// Transferred less than the minimum value required by the dispatchable.
+    InsufficientTransferredValue = 2u32,
+    /// The caller is not allowed to call the dispatchable.
+    CallerNotAllowed = 3u32,
+    /// The input of the dispatchable exceeds the maximum size configured for the
+    /// contract.
+    InputTooLarge = 4u32,
 }
 
 /// The `Result` type for ink! messages.
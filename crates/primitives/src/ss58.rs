@@ -0,0 +1,256 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The SS58 address format of Substrate based chains.
+//!
+//! An address is the base58 encoding of the network prefix of the chain, the
+//! account ID and the first two bytes of the Blake2b-512 hash of both as checksum.
+//!
+//! # Note
+//!
+//! This module is only available with the `ss58` crate feature, decoding addresses
+//! additionally requires the `std` feature.
+
+use crate::AccountId;
+use blake2::digest::{
+    consts::U64,
+    Digest as _,
+};
+use ink_prelude::{
+    string::String,
+    vec::Vec,
+};
+
+/// The network prefix of the generic Substrate address format.
+pub const SUBSTRATE_SS58_PREFIX: u16 = 42;
+
+/// The base58 alphabet used by SS58 addresses.
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// The bytes prepended to the checksummed data.
+const CHECKSUM_PREAMBLE: &[u8] = b"SS58PRE";
+
+/// The length of the checksum of an address.
+const CHECKSUM_LEN: usize = 2;
+
+/// The errors of decoding an SS58 address.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Ss58Error {
+    /// The address contains characters which are not part of the base58 alphabet.
+    BadBase58,
+    /// The address does not encode a 32 bytes account ID.
+    BadLength,
+    /// The address uses a network prefix reserved for other formats.
+    InvalidPrefix,
+    /// The checksum of the address does not match its content.
+    InvalidChecksum,
+}
+
+impl core::fmt::Display for Ss58Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::BadBase58 => write!(f, "address is not valid base58"),
+            Self::BadLength => write!(f, "address has an invalid length"),
+            Self::InvalidPrefix => write!(f, "address has an invalid network prefix"),
+            Self::InvalidChecksum => write!(f, "address has an invalid checksum"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Ss58Error {}
+
+impl AccountId {
+    /// Decodes the account ID from an SS58 `address` of any network.
+    ///
+    /// # Errors
+    ///
+    /// If the `address` is not a valid SS58 address of a 32 bytes account ID.
+    #[cfg(feature = "std")]
+    pub fn from_ss58check(address: &str) -> Result<Self, Ss58Error> {
+        Self::from_ss58check_with_prefix(address).map(|(account_id, _)| account_id)
+    }
+
+    /// Decodes the account ID and network prefix from an SS58 `address`.
+    ///
+    /// # Errors
+    ///
+    /// If the `address` is not a valid SS58 address of a 32 bytes account ID.
+    #[cfg(feature = "std")]
+    pub fn from_ss58check_with_prefix(address: &str) -> Result<(Self, u16), Ss58Error> {
+        let data = decode_base58(address)?;
+        let (prefix, prefix_len) = match data.first() {
+            Some(&first) if first < 64 => (u16::from(first), 1),
+            Some(&first) if first < 128 => {
+                let second = *data.get(1).ok_or(Ss58Error::BadLength)?;
+                let lower = (first << 2) | (second >> 6);
+                let upper = second & 0b0011_1111;
+                (u16::from(lower) | (u16::from(upper) << 8), 2)
+            }
+            Some(_) => return Err(Ss58Error::InvalidPrefix),
+            None => return Err(Ss58Error::BadLength),
+        };
+        if data.len() != prefix_len + 32 + CHECKSUM_LEN {
+            return Err(Ss58Error::BadLength)
+        }
+        let (body, checksum) = data.split_at(prefix_len + 32);
+        if checksum != &ss58hash(body)[..CHECKSUM_LEN] {
+            return Err(Ss58Error::InvalidChecksum)
+        }
+        let account_id = <[u8; 32]>::try_from(&body[prefix_len..])
+            .expect("the length of the account ID has been checked above");
+        Ok((Self::from(account_id), prefix))
+    }
+
+    /// Encodes the account ID as SS58 address of the network with the given `prefix`.
+    ///
+    /// # Panics
+    ///
+    /// If the `prefix` exceeds `16_383`, the largest network prefix of the format.
+    pub fn to_ss58check(&self, prefix: u16) -> String {
+        let mut data = match prefix {
+            0..=63 => ink_prelude::vec![prefix as u8],
+            64..=16_383 => {
+                let first = ((prefix & 0b0000_0000_1111_1100) as u8) >> 2;
+                let second = ((prefix >> 8) as u8) | (((prefix & 0b11) as u8) << 6);
+                ink_prelude::vec![first | 0b0100_0000, second]
+            }
+            _ => panic!("encountered invalid SS58 network prefix: {}", prefix),
+        };
+        data.extend_from_slice(<Self as AsRef<[u8]>>::as_ref(self));
+        let checksum = ss58hash(&data);
+        data.extend_from_slice(&checksum[..CHECKSUM_LEN]);
+        encode_base58(&data)
+    }
+}
+
+/// Returns the Blake2b-512 hash of the `data` prefixed with the checksum preamble.
+fn ss58hash(data: &[u8]) -> [u8; 64] {
+    let mut hasher = blake2::Blake2b::<U64>::new();
+    hasher.update(CHECKSUM_PREAMBLE);
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+/// Encodes the `data` in base58.
+fn encode_base58(data: &[u8]) -> String {
+    // Little endian base58 digits of the big endian `data`.
+    let mut digits = Vec::<u8>::new();
+    for &byte in data {
+        let mut carry = u32::from(byte);
+        for digit in &mut digits {
+            carry += u32::from(*digit) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    let zeros = data.iter().take_while(|&&byte| byte == 0).count();
+    let mut encoded = ink_prelude::vec![ALPHABET[0]; zeros];
+    encoded.extend(digits.iter().rev().map(|&digit| ALPHABET[digit as usize]));
+    String::from_utf8(encoded).expect("the base58 alphabet is ASCII")
+}
+
+/// Decodes the base58 `encoded` string.
+#[cfg(feature = "std")]
+fn decode_base58(encoded: &str) -> Result<Vec<u8>, Ss58Error> {
+    // Little endian bytes of the big endian base58 digits.
+    let mut bytes = Vec::<u8>::new();
+    for c in encoded.bytes() {
+        let mut carry = ALPHABET
+            .iter()
+            .position(|&symbol| symbol == c)
+            .ok_or(Ss58Error::BadBase58)? as u32;
+        for byte in &mut bytes {
+            carry += u32::from(*byte) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+    let zeros = encoded.bytes().take_while(|&c| c == ALPHABET[0]).count();
+    let mut decoded = ink_prelude::vec![0x00; zeros];
+    decoded.extend(bytes.into_iter().rev());
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The account ID of Alice in the development chains.
+    const ALICE: [u8; 32] = [
+        0xd4, 0x35, 0x93, 0xc7, 0x15, 0xfd, 0xd3, 0x1c, 0x61, 0x14, 0x1a, 0xbd, 0x04,
+        0xa9, 0x9f, 0xd6, 0x82, 0x2c, 0x85, 0x58, 0x85, 0x4c, 0xcd, 0xe3, 0x9a, 0x56,
+        0x84, 0xe7, 0xa5, 0x6d, 0xa2, 0x7d,
+    ];
+
+    const ALICE_SS58: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+    #[test]
+    fn to_ss58check_works() {
+        let alice = AccountId::from(ALICE);
+        assert_eq!(alice.to_ss58check(SUBSTRATE_SS58_PREFIX), ALICE_SS58);
+    }
+
+    #[test]
+    fn from_ss58check_works() {
+        assert_eq!(
+            AccountId::from_ss58check_with_prefix(ALICE_SS58),
+            Ok((AccountId::from(ALICE), SUBSTRATE_SS58_PREFIX))
+        );
+    }
+
+    #[test]
+    fn ss58check_roundtrips_for_two_byte_prefixes() {
+        let alice = AccountId::from(ALICE);
+        for prefix in [64, 255, 1284, 16_383] {
+            let address = alice.to_ss58check(prefix);
+            assert_eq!(
+                AccountId::from_ss58check_with_prefix(&address),
+                Ok((alice, prefix))
+            );
+        }
+    }
+
+    #[test]
+    fn from_ss58check_rejects_invalid_addresses() {
+        let mut corrupted = String::from(ALICE_SS58);
+        corrupted.replace_range(10..11, "Y");
+        assert_eq!(
+            AccountId::from_ss58check(&corrupted),
+            Err(Ss58Error::InvalidChecksum)
+        );
+        assert_eq!(
+            AccountId::from_ss58check("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKut0Y"),
+            Err(Ss58Error::BadBase58)
+        );
+        assert_eq!(
+            AccountId::from_ss58check(&ALICE_SS58[..40]),
+            Err(Ss58Error::BadLength)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "encountered invalid SS58 network prefix: 16384")]
+    fn to_ss58check_fails_for_invalid_prefix() {
+        let _ = AccountId::from(ALICE).to_ss58check(16_384);
+    }
+}
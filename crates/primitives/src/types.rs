@@ -14,6 +14,7 @@
 
 use core::array::TryFromSliceError;
 use derive_more::From;
+use ink_prelude::string::String;
 use scale::{
     Decode,
     Encode,
@@ -119,6 +120,68 @@ impl AsRef<[u8]> for Hash {
     }
 }
 
+impl Hash {
+    /// Parses the hash from its hex representation, optionally prefixed with `0x`.
+    ///
+    /// # Errors
+    ///
+    /// If `hex` contains non-hex characters or does not encode 32 bytes.
+    pub fn from_hex(hex: &str) -> Result<Self, FromHexError> {
+        let hex = hex.strip_prefix("0x").unwrap_or(hex).as_bytes();
+        if hex.len() != 64 {
+            return Err(FromHexError::InvalidLength)
+        }
+        let mut hash = [0x00; 32];
+        for (byte, digits) in hash.iter_mut().zip(hex.chunks_exact(2)) {
+            *byte = (hex_digit(digits[0])? << 4) | hex_digit(digits[1])?;
+        }
+        Ok(Self(hash))
+    }
+
+    /// Returns the hex representation of the hash prefixed with `0x`.
+    pub fn to_hex(&self) -> String {
+        const DIGITS: &[u8; 16] = b"0123456789abcdef";
+        let mut hex = String::with_capacity(2 + 64);
+        hex.push_str("0x");
+        for byte in self.0 {
+            hex.push(char::from(DIGITS[usize::from(byte >> 4)]));
+            hex.push(char::from(DIGITS[usize::from(byte & 0x0F)]));
+        }
+        hex
+    }
+}
+
+/// Returns the value of the ASCII hex `digit`.
+fn hex_digit(digit: u8) -> Result<u8, FromHexError> {
+    match digit {
+        b'0'..=b'9' => Ok(digit - b'0'),
+        b'a'..=b'f' => Ok(digit - b'a' + 10),
+        b'A'..=b'F' => Ok(digit - b'A' + 10),
+        _ => Err(FromHexError::InvalidCharacter),
+    }
+}
+
+/// The errors of parsing a [`Hash`] from its hex representation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FromHexError {
+    /// The hex representation contains a character which is not a hex digit.
+    InvalidCharacter,
+    /// The hex representation does not encode 32 bytes.
+    InvalidLength,
+}
+
+impl core::fmt::Display for FromHexError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self {
+            Self::InvalidCharacter => write!(f, "invalid hex character"),
+            Self::InvalidLength => write!(f, "invalid hex length, expected 32 bytes"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FromHexError {}
+
 impl AsMut<[u8]> for Hash {
     fn as_mut(&mut self) -> &mut [u8] {
         &mut self.0[..]
@@ -155,3 +218,26 @@ impl Clear for Hash {
         Self(<[u8; 32] as Clear>::clear())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_hex_roundtrip_works() {
+        let hash = Hash::from([0xAB; 32]);
+        let hex = hash.to_hex();
+        assert_eq!(hex, format!("0x{}", "ab".repeat(32)));
+        assert_eq!(Hash::from_hex(&hex), Ok(hash));
+        assert_eq!(Hash::from_hex(&"AB".repeat(32)), Ok(hash));
+    }
+
+    #[test]
+    fn hash_from_invalid_hex_fails() {
+        assert_eq!(Hash::from_hex("0xab"), Err(FromHexError::InvalidLength));
+        assert_eq!(
+            Hash::from_hex(&"xy".repeat(32)),
+            Err(FromHexError::InvalidCharacter)
+        );
+    }
+}
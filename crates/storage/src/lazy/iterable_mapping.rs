@@ -0,0 +1,564 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A mapping to contract storage that keeps track of its keys.
+//!
+//! # Note
+//!
+//! Just like [`Mapping`](crate::Mapping) this mapping doesn't actually "own" any data.
+//! Besides the values it additionally stores an index of all keys in the contract
+//! storage so that the entries of the mapping can be enumerated.
+
+use crate::traits::{
+    AutoKey,
+    Packed,
+    StorableHint,
+    StorageKey,
+};
+use core::marker::PhantomData;
+use ink_primitives::Key;
+use ink_storage_traits::Storable;
+use scale::{
+    Error,
+    Input,
+    Output,
+};
+
+/// Tag of the storage cell holding the number of entries in the mapping.
+const LEN: u8 = 0;
+/// Tag of the storage cells holding the key at a given position of the key index.
+const KEY_AT: u8 = 1;
+/// Tag of the storage cells holding the value at a given key.
+const VALUE: u8 = 2;
+/// Tag of the storage cells holding the position of a given key in the key index.
+const INDEX_OF: u8 = 3;
+
+/// A mapping of key-value pairs directly into contract storage which can be iterated.
+///
+/// In contrast to [`Mapping`](crate::Mapping) this mapping maintains an index of all of its
+/// keys in the contract storage. This allows to query the amount of entries with
+/// [`len`](IterableMapping::len) and to enumerate the entries with
+/// [`keys`](IterableMapping::keys) or [`iter`](IterableMapping::iter).
+///
+/// # Important
+///
+/// Maintaining the key index requires additional storage reads and writes for every
+/// insertion of a new key and every removal of an existing key. Prefer
+/// [`Mapping`](crate::Mapping) if the entries never need to be enumerated.
+///
+/// Keys are removed from the index by swapping them with the last key, so the
+/// iteration order is not stable across removals.
+///
+/// The mapping requires its own pre-defined storage key where to store its entries. By default,
+/// the is automatically calculated using [`AutoKey`](crate::traits::AutoKey) during compilation.
+/// However, anyone can specify a storage key using [`ManualKey`](crate::traits::ManualKey).
+///
+/// This is an example of how you can do this:
+/// ```rust
+/// # use ink::env::{
+/// #     Environment,
+/// #     DefaultEnvironment,
+/// # };
+/// # type AccountId = <DefaultEnvironment as Environment>::AccountId;
+///
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::IterableMapping;
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct MyContract {
+///     balances: IterableMapping<AccountId, Balance>,
+/// }
+///
+/// impl MyContract {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         let mut instance = Self::default();
+///         let caller = Self::env().caller();
+///         let value: Balance = Default::default();
+///         instance.balances.insert(caller, &value);
+///         instance
+///     }
+///
+///     #[ink(message)]
+///     pub fn total_supply(&self) -> Balance {
+///         self.balances.iter().map(|(_, balance)| balance).sum()
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct IterableMapping<K, V: Packed, KeyType: StorageKey = AutoKey> {
+    #[allow(clippy::type_complexity)]
+    _marker: PhantomData<fn() -> (K, V, KeyType)>,
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<K, V, KeyType> Default for IterableMapping<K, V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<K, V, KeyType> IterableMapping<K, V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    /// Creates a new empty `IterableMapping`.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<K, V, KeyType> ::core::fmt::Debug for IterableMapping<K, V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("IterableMapping")
+            .field("key", &KeyType::KEY)
+            .finish()
+    }
+}
+
+impl<K, V, KeyType> IterableMapping<K, V, KeyType>
+where
+    K: Packed,
+    V: Packed,
+    KeyType: StorageKey,
+{
+    /// Returns the number of entries in the mapping.
+    #[inline]
+    pub fn len(&self) -> u32 {
+        ink_env::get_contract_storage::<_, u32>(&(&KeyType::KEY, LEN))
+            .unwrap_or_else(|error| {
+                panic!("Failed to get length of IterableMapping: {:?}", error)
+            })
+            .unwrap_or(0)
+    }
+
+    /// Returns `true` if the mapping contains no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Insert the given `value` at `key` to the contract storage.
+    ///
+    /// Returns the size in bytes of the pre-existing value at the specified key if any.
+    ///
+    /// # Note
+    ///
+    /// Inserting a new `key` appends it to the key index.
+    #[inline]
+    pub fn insert<R>(&mut self, key: K, value: &R) -> Option<u32>
+    where
+        R: Storable + scale::EncodeLike<V>,
+    {
+        let size = ink_env::set_contract_storage(&(&KeyType::KEY, VALUE, &key), value);
+        if size.is_none() {
+            let index = self.len();
+            ink_env::set_contract_storage(&(&KeyType::KEY, KEY_AT, index), &key);
+            ink_env::set_contract_storage(&(&KeyType::KEY, INDEX_OF, &key), &index);
+            self.set_len(index + 1);
+        }
+        size
+    }
+
+    /// Get the `value` at `key` from the contract storage.
+    ///
+    /// Returns `None` if no `value` exists at the given `key`.
+    #[inline]
+    pub fn get(&self, key: &K) -> Option<V> {
+        ink_env::get_contract_storage(&(&KeyType::KEY, VALUE, key)).unwrap_or_else(
+            |error| panic!("Failed to get value in IterableMapping: {:?}", error),
+        )
+    }
+
+    /// Checks if a value is stored at the given `key` in the contract storage.
+    #[inline]
+    pub fn contains(&self, key: &K) -> bool {
+        ink_env::contains_contract_storage(&(&KeyType::KEY, VALUE, key)).is_some()
+    }
+
+    /// Removes the `value` at `key`, returning the previous `value` at `key` from storage.
+    ///
+    /// Returns `None` if no `value` exists at the given `key`.
+    ///
+    /// # Note
+    ///
+    /// The last key of the key index takes the position of the removed `key`.
+    #[inline]
+    pub fn take(&mut self, key: &K) -> Option<V> {
+        let value = ink_env::take_contract_storage(&(&KeyType::KEY, VALUE, key))
+            .unwrap_or_else(|error| {
+                panic!("Failed to take value in IterableMapping: {:?}", error)
+            })?;
+        self.remove_from_index(key);
+        Some(value)
+    }
+
+    /// Clears the value at `key` from storage.
+    ///
+    /// # Note
+    ///
+    /// The last key of the key index takes the position of the removed `key`.
+    #[inline]
+    pub fn remove(&mut self, key: &K) {
+        if ink_env::contains_contract_storage(&(&KeyType::KEY, VALUE, key)).is_some() {
+            ink_env::clear_contract_storage(&(&KeyType::KEY, VALUE, key));
+            self.remove_from_index(key);
+        }
+    }
+
+    /// Returns the key at position `index` of the key index.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    #[inline]
+    pub fn key_at(&self, index: u32) -> Option<K> {
+        if index >= self.len() {
+            return None
+        }
+        ink_env::get_contract_storage(&(&KeyType::KEY, KEY_AT, index)).unwrap_or_else(
+            |error| panic!("Failed to get key in IterableMapping: {:?}", error),
+        )
+    }
+
+    /// Returns an iterator over the keys of the mapping.
+    ///
+    /// Keys are loaded lazily from the contract storage.
+    pub fn keys(&self) -> Keys<'_, K, V, KeyType> {
+        Keys {
+            mapping: self,
+            next: 0,
+            len: self.len(),
+        }
+    }
+
+    /// Returns an iterator over the entries of the mapping.
+    ///
+    /// Entries are loaded lazily from the contract storage.
+    pub fn iter(&self) -> Iter<'_, K, V, KeyType> {
+        Iter { keys: self.keys() }
+    }
+
+    /// Retains only the entries for which `f` returns `true`.
+    ///
+    /// All other entries are removed from the contract storage.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        let mut index = 0;
+        while index < self.len() {
+            let key = self
+                .key_at(index)
+                .expect("index is within bounds of the key index");
+            let value = self
+                .get(&key)
+                .expect("keys in the key index always have a value");
+            if f(&key, &value) {
+                index += 1;
+            } else {
+                // The last key is swapped into `index`, so it must be visited again.
+                self.remove(&key);
+            }
+        }
+    }
+
+    /// Removes `key` from the key index by swapping it with the last key.
+    fn remove_from_index(&mut self, key: &K) {
+        let index: u32 = ink_env::take_contract_storage(&(&KeyType::KEY, INDEX_OF, key))
+            .unwrap_or_else(|error| {
+                panic!("Failed to take index in IterableMapping: {:?}", error)
+            })
+            .expect("keys with a value always have an index");
+        let last = self.len() - 1;
+        if index != last {
+            let last_key: K =
+                ink_env::get_contract_storage(&(&KeyType::KEY, KEY_AT, last))
+                    .unwrap_or_else(|error| {
+                        panic!("Failed to get key in IterableMapping: {:?}", error)
+                    })
+                    .expect("the key index has no gaps");
+            ink_env::set_contract_storage(&(&KeyType::KEY, KEY_AT, index), &last_key);
+            ink_env::set_contract_storage(&(&KeyType::KEY, INDEX_OF, &last_key), &index);
+        }
+        ink_env::clear_contract_storage(&(&KeyType::KEY, KEY_AT, last));
+        self.set_len(last);
+    }
+
+    /// Sets the number of entries in the mapping.
+    fn set_len(&mut self, len: u32) {
+        ink_env::set_contract_storage(&(&KeyType::KEY, LEN), &len);
+    }
+}
+
+/// An iterator over the keys of an [`IterableMapping`].
+pub struct Keys<'a, K, V: Packed, KeyType: StorageKey> {
+    mapping: &'a IterableMapping<K, V, KeyType>,
+    next: u32,
+    len: u32,
+}
+
+impl<'a, K, V, KeyType> Iterator for Keys<'a, K, V, KeyType>
+where
+    K: Packed,
+    V: Packed,
+    KeyType: StorageKey,
+{
+    type Item = K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.len {
+            return None
+        }
+        let key = self.mapping.key_at(self.next);
+        self.next += 1;
+        key
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.len - self.next) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, K, V, KeyType> ExactSizeIterator for Keys<'a, K, V, KeyType>
+where
+    K: Packed,
+    V: Packed,
+    KeyType: StorageKey,
+{
+}
+
+/// An iterator over the entries of an [`IterableMapping`].
+pub struct Iter<'a, K, V: Packed, KeyType: StorageKey> {
+    keys: Keys<'a, K, V, KeyType>,
+}
+
+impl<'a, K, V, KeyType> Iterator for Iter<'a, K, V, KeyType>
+where
+    K: Packed,
+    V: Packed,
+    KeyType: StorageKey,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let key = self.keys.next()?;
+        let value = self
+            .keys
+            .mapping
+            .get(&key)
+            .expect("keys in the key index always have a value");
+        Some((key, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.keys.size_hint()
+    }
+}
+
+impl<'a, K, V, KeyType> ExactSizeIterator for Iter<'a, K, V, KeyType>
+where
+    K: Packed,
+    V: Packed,
+    KeyType: StorageKey,
+{
+}
+
+impl<K, V, KeyType> Storable for IterableMapping<K, V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<T: Output + ?Sized>(&self, _dest: &mut T) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<K, V, Key, InnerKey> StorableHint<Key> for IterableMapping<K, V, InnerKey>
+where
+    V: Packed,
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = IterableMapping<K, V, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<K, V, KeyType> StorageKey for IterableMapping<K, V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        RootLayout,
+    };
+
+    impl<K, V, KeyType> StorageLayout for IterableMapping<K, V, KeyType>
+    where
+        K: scale_info::TypeInfo + 'static,
+        V: Packed + StorageLayout + scale_info::TypeInfo + 'static,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <V as StorageLayout>::layout(&KeyType::KEY),
+            ))
+        }
+    }
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ManualKey;
+
+    #[test]
+    fn insert_and_get_work() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut mapping: IterableMapping<u8, u8> = IterableMapping::new();
+            assert!(mapping.is_empty());
+            assert_eq!(mapping.insert(1, &2), None);
+            assert_eq!(mapping.get(&1), Some(2));
+            assert!(mapping.contains(&1));
+            assert_eq!(mapping.len(), 1);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn overwriting_does_not_grow_the_key_index() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut mapping: IterableMapping<u8, u8> = IterableMapping::new();
+            mapping.insert(1, &2);
+            assert!(mapping.insert(1, &3).is_some());
+            assert_eq!(mapping.get(&1), Some(3));
+            assert_eq!(mapping.len(), 1);
+            assert_eq!(mapping.keys().collect::<Vec<_>>(), vec![1]);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn iter_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut mapping: IterableMapping<u8, u8> = IterableMapping::new();
+            mapping.insert(1, &10);
+            mapping.insert(2, &20);
+            mapping.insert(3, &30);
+            assert_eq!(mapping.keys().len(), 3);
+            assert_eq!(
+                mapping.iter().collect::<Vec<_>>(),
+                vec![(1, 10), (2, 20), (3, 30)]
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn take_and_remove_work() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut mapping: IterableMapping<u8, u8> = IterableMapping::new();
+            mapping.insert(1, &10);
+            mapping.insert(2, &20);
+            mapping.insert(3, &30);
+
+            assert_eq!(mapping.take(&1), Some(10));
+            assert_eq!(mapping.take(&1), None);
+            assert_eq!(mapping.keys().collect::<Vec<_>>(), vec![3, 2]);
+
+            mapping.remove(&2);
+            mapping.remove(&2);
+            assert_eq!(mapping.iter().collect::<Vec<_>>(), vec![(3, 30)]);
+            assert_eq!(mapping.key_at(1), None);
+            assert_eq!(mapping.len(), 1);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn retain_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut mapping: IterableMapping<u8, u8> = IterableMapping::new();
+            for key in 0..10 {
+                mapping.insert(key, &(key * 10));
+            }
+            mapping.retain(|key, _| key % 3 == 0);
+
+            let mut keys = mapping.keys().collect::<Vec<_>>();
+            keys.sort_unstable();
+            assert_eq!(keys, vec![0, 3, 6, 9]);
+            assert_eq!(mapping.get(&1), None);
+            assert_eq!(mapping.get(&9), Some(90));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn mappings_with_different_keys_are_independent() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut mapping: IterableMapping<u8, u8, ManualKey<123>> =
+                IterableMapping::new();
+            mapping.insert(1, &2);
+
+            let mapping2: IterableMapping<u8, u8, ManualKey<124>> =
+                IterableMapping::new();
+            assert!(mapping2.is_empty());
+
+            let mapping3: IterableMapping<u8, u8, ManualKey<123>> =
+                IterableMapping::new();
+            assert_eq!(mapping3.keys().collect::<Vec<_>>(), vec![1]);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+}
@@ -26,6 +26,7 @@ use crate::traits::{
     StorageKey,
 };
 use core::marker::PhantomData;
+use ink_prelude::vec::Vec;
 use ink_primitives::Key;
 use ink_storage_traits::Storable;
 use scale::{
@@ -154,9 +155,34 @@ where
             .unwrap_or_else(|error| panic!("Failed to get value in Mapping: {:?}", error))
     }
 
+    /// Get the `value`s at all of the given `keys` from the contract storage.
+    ///
+    /// Returns the `value`s in the order of the `keys`, with `None` for every `key`
+    /// at which no `value` exists.
+    ///
+    /// # Note
+    ///
+    /// The environment has no host function to read multiple storage cells at once,
+    /// so every `value` is still read with its own host function call. The calls
+    /// share the static buffer of the environment and the returned `Vec` is allocated
+    /// once, which makes this a convenient fit for messages aggregating many entries.
+    pub fn get_many<I, Q>(&self, keys: I) -> Vec<Option<V>>
+    where
+        I: IntoIterator<Item = Q>,
+        Q: scale::EncodeLike<K>,
+    {
+        let keys = keys.into_iter();
+        let mut values = Vec::with_capacity(keys.size_hint().0);
+        values.extend(keys.map(|key| self.get(key)));
+        values
+    }
+
     /// Removes the `value` at `key`, returning the previous `value` at `key` from storage.
     ///
     /// Returns `None` if no `value` exists at the given `key`.
+    ///
+    /// This reads and clears the `value` with a single host function call,
+    /// which is cheaper than a [`Mapping::get`] followed by a [`Mapping::remove`].
     #[inline]
     pub fn take<Q>(&self, key: Q) -> Option<V>
     where
@@ -180,7 +206,10 @@ where
 
     /// Checks if a value is stored at the given `key` in the contract storage.
     ///
-    /// Returns `None` if no `value` exists at the given `key`.
+    /// Returns `false` if no `value` exists at the given `key`.
+    ///
+    /// The check neither loads nor decodes the `value`, which makes it cheaper
+    /// than checking the result of [`Mapping::get`].
     #[inline]
     pub fn contains<Q>(&self, key: Q) -> bool
     where
@@ -286,6 +315,24 @@ mod tests {
         .unwrap()
     }
 
+    #[test]
+    fn get_many_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut mapping: Mapping<u8, u8> = Mapping::new();
+            mapping.insert(1, &10);
+            mapping.insert(3, &30);
+
+            assert_eq!(
+                mapping.get_many([3, 2, 1, 3]),
+                vec![Some(30), None, Some(10), Some(30)]
+            );
+            assert_eq!(mapping.get_many(Vec::<u8>::new()), vec![]);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
     #[test]
     fn gets_default_if_no_key_set() {
         ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
@@ -321,6 +368,25 @@ mod tests {
         .unwrap()
     }
 
+    #[test]
+    fn contains_and_size_work() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut mapping: Mapping<u8, u32> = Mapping::new();
+            assert!(!mapping.contains(1));
+            assert_eq!(mapping.size(1), None);
+
+            mapping.insert(1, &2);
+            assert!(mapping.contains(1));
+            assert_eq!(mapping.size(1), Some(4));
+
+            mapping.take(1);
+            assert!(!mapping.contains(1));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
     #[test]
     fn can_clear_entries() {
         ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
@@ -18,17 +18,27 @@
 //! These low-level collections are not aware of the elements they manage thus
 //! extra care has to be taken when operating directly on them.
 
+mod iterable_mapping;
 mod mapping;
+mod storage_vec;
 
 #[doc(inline)]
-pub use self::mapping::Mapping;
+pub use self::{
+    iterable_mapping::IterableMapping,
+    mapping::Mapping,
+    storage_vec::StorageVec,
+};
 
 use crate::traits::{
     AutoKey,
     StorableHint,
     StorageKey,
 };
-use core::marker::PhantomData;
+use core::{
+    cell::RefCell,
+    marker::PhantomData,
+};
+use ink_prelude::vec::Vec;
 use ink_primitives::Key;
 use ink_storage_traits::Storable;
 use scale::{
@@ -50,10 +60,18 @@ use scale::{
 /// upgradeable contracts or you want to be resistant to future changes of storage
 /// key calculation strategy.
 ///
+/// # Caching
+///
+/// The value is read from the contract storage at most once per instance and cached
+/// afterwards. Since the `Lazy` fields of the contract storage are instantiated for
+/// every constructor or message execution, the value is read at most once per
+/// execution. Values written with [`set`](crate::Lazy::set) are written to the
+/// contract storage immediately and cached as well.
+///
 /// # Note
 ///
 /// If the contract has two or more `Lazy` with the same storage key, modifying the value of one
-/// of them will modify others.
+/// of them will modify others, unless they have cached the value already.
 ///
 /// This is an example of how you can do this:
 /// ```rust
@@ -91,6 +109,8 @@ use scale::{
 /// ```
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
 pub struct Lazy<V, KeyType: StorageKey = AutoKey> {
+    #[cfg_attr(feature = "std", codec(skip))]
+    cache: RefCell<Option<Option<Vec<u8>>>>,
     _marker: PhantomData<fn() -> (V, KeyType)>,
 }
 
@@ -101,6 +121,7 @@ where
 {
     fn default() -> Self {
         Self {
+            cache: Default::default(),
             _marker: Default::default(),
         }
     }
@@ -113,6 +134,7 @@ where
     /// Creates a new empty `Lazy`.
     pub fn new() -> Self {
         Self {
+            cache: Default::default(),
             _marker: Default::default(),
         }
     }
@@ -133,16 +155,33 @@ where
     KeyType: StorageKey,
 {
     /// Reads the `value` from the contract storage, if it exists.
+    ///
+    /// The contract storage is only read upon the first access.
     pub fn get(&self) -> Option<V> {
-        match ink_env::get_contract_storage::<Key, V>(&KeyType::KEY) {
+        if let Some(cached) = &*self.cache.borrow() {
+            return cached
+                .as_ref()
+                .and_then(|encoded| V::decode(&mut &encoded[..]).ok())
+        }
+        let value = match ink_env::get_contract_storage::<Key, V>(&KeyType::KEY) {
             Ok(Some(value)) => Some(value),
             _ => None,
-        }
+        };
+        *self.cache.borrow_mut() = Some(value.as_ref().map(Self::encode_value));
+        value
     }
 
     /// Writes the given `value` to the contract storage.
     pub fn set(&mut self, value: &V) {
         ink_env::set_contract_storage::<Key, V>(&KeyType::KEY, value);
+        *self.cache.get_mut() = Some(Some(Self::encode_value(value)));
+    }
+
+    /// Returns the SCALE encoding of the given `value`.
+    fn encode_value(value: &V) -> Vec<u8> {
+        let mut encoded = Vec::new();
+        value.encode(&mut encoded);
+        encoded
     }
 }
 
@@ -155,10 +194,7 @@ where
     ///
     /// Returns the default value for the storage type if no `value` exists.
     pub fn get_or_default(&self) -> V {
-        match ink_env::get_contract_storage::<Key, V>(&KeyType::KEY) {
-            Ok(Some(value)) => value,
-            _ => Default::default(),
-        }
+        self.get().unwrap_or_default()
     }
 }
 
@@ -267,4 +303,62 @@ mod tests {
         })
         .unwrap()
     }
+
+    #[test]
+    fn set_writes_through() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut storage: Lazy<u8, ManualKey<42>> = Lazy::new();
+            storage.set(&2);
+            assert_eq!(ink_env::get_contract_storage::<Key, u8>(&42), Ok(Some(2)));
+            assert_eq!(storage.get(), Some(2));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn set_is_counted_in_storage_deposit_info() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut storage: Lazy<u32, ManualKey<42>> = Lazy::new();
+            storage.set(&2);
+            assert_eq!(
+                ink_env::storage_deposit_info(),
+                ink_env::StorageDepositInfo {
+                    bytes_added: 4,
+                    items_added: 1,
+                    ..Default::default()
+                }
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn get_reads_contract_storage_once() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            ink_env::set_contract_storage::<Key, u8>(&42, &7);
+            let contract = ink_env::test::callee::<ink_env::DefaultEnvironment>();
+            let (reads, _) = ink_env::test::get_contract_storage_rw::<
+                ink_env::DefaultEnvironment,
+            >(&contract);
+
+            let storage: Lazy<u8, ManualKey<42>> = Lazy::new();
+            assert_eq!(storage.get(), Some(7));
+            assert_eq!(storage.get(), Some(7));
+            assert_eq!(storage.get_or_default(), 7);
+
+            assert_eq!(
+                ink_env::test::get_contract_storage_rw::<ink_env::DefaultEnvironment>(
+                    &contract
+                ),
+                (reads + 1, 1)
+            );
+
+            Ok(())
+        })
+        .unwrap()
+    }
 }
@@ -0,0 +1,470 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A vector in contract storage which stores each element in its own storage cell.
+//!
+//! # Note
+//!
+//! Just like [`Mapping`](crate::Mapping) this vector doesn't actually "own" any data.
+//! Elements are only loaded from the contract storage when they are accessed.
+
+use crate::traits::{
+    AutoKey,
+    Packed,
+    StorableHint,
+    StorageKey,
+};
+use core::marker::PhantomData;
+use ink_primitives::Key;
+use ink_storage_traits::Storable;
+use scale::{
+    Error,
+    Input,
+    Output,
+};
+
+/// Tag of the storage cell holding the number of elements in the vector.
+const LEN: u8 = 0;
+/// Tag of the storage cells holding the element at a given index.
+const ELEMENT: u8 = 1;
+
+/// A vector of values stored directly in contract storage.
+///
+/// In contrast to a `Vec<V>` in packed storage, which is loaded and stored as a whole,
+/// the length of a `StorageVec` and each of its elements live in their own storage cells.
+/// This makes [`push`](StorageVec::push), [`pop`](StorageVec::pop),
+/// [`get`](StorageVec::get) and [`set`](StorageVec::set) operate in constant time
+/// regardless of the number of elements.
+///
+/// # Important
+///
+/// The vector requires its own pre-defined storage key where to store its elements. By default,
+/// the is automatically calculated using [`AutoKey`](crate::traits::AutoKey) during compilation.
+/// However, anyone can specify a storage key using [`ManualKey`](crate::traits::ManualKey).
+///
+/// This is an example of how you can do this:
+/// ```rust
+/// # use ink::env::{
+/// #     Environment,
+/// #     DefaultEnvironment,
+/// # };
+/// # type AccountId = <DefaultEnvironment as Environment>::AccountId;
+///
+/// # #[ink::contract]
+/// # mod my_module {
+/// use ink::storage::StorageVec;
+///
+/// #[ink(storage)]
+/// #[derive(Default)]
+/// pub struct MyContract {
+///     members: StorageVec<AccountId>,
+/// }
+///
+/// impl MyContract {
+///     #[ink(constructor)]
+///     pub fn new() -> Self {
+///         let mut instance = Self::default();
+///         let caller = Self::env().caller();
+///         instance.members.push(&caller);
+///         instance
+///     }
+///
+///     #[ink(message)]
+///     pub fn is_member(&self, account: AccountId) -> bool {
+///         self.members.iter().any(|member| member == account)
+///     }
+/// }
+/// # }
+/// ```
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct StorageVec<V: Packed, KeyType: StorageKey = AutoKey> {
+    _marker: PhantomData<fn() -> (V, KeyType)>,
+}
+
+/// We implement this manually because the derived implementation adds trait bounds.
+impl<V, KeyType> Default for StorageVec<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn default() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<V, KeyType> StorageVec<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    /// Creates a new empty `StorageVec`.
+    pub fn new() -> Self {
+        Self {
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<V, KeyType> ::core::fmt::Debug for StorageVec<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("StorageVec")
+            .field("key", &KeyType::KEY)
+            .finish()
+    }
+}
+
+impl<V, KeyType> StorageVec<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    /// Returns the number of elements in the vector.
+    #[inline]
+    pub fn len(&self) -> u32 {
+        ink_env::get_contract_storage::<_, u32>(&(&KeyType::KEY, LEN))
+            .unwrap_or_else(|error| {
+                panic!("Failed to get length of StorageVec: {:?}", error)
+            })
+            .unwrap_or(0)
+    }
+
+    /// Returns `true` if the vector contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends the given `value` to the back of the vector.
+    ///
+    /// # Panics
+    ///
+    /// If the vector already contains `u32::MAX` elements.
+    #[inline]
+    pub fn push<R>(&mut self, value: &R)
+    where
+        R: Storable + scale::EncodeLike<V>,
+    {
+        let len = self.len();
+        let new_len = len.checked_add(1).expect("StorageVec is full");
+        ink_env::set_contract_storage(&(&KeyType::KEY, ELEMENT, len), value);
+        self.set_len(new_len);
+    }
+
+    /// Removes the last element from the vector and returns it.
+    ///
+    /// Returns `None` if the vector is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<V> {
+        let last = self.len().checked_sub(1)?;
+        let value = ink_env::take_contract_storage(&(&KeyType::KEY, ELEMENT, last))
+            .unwrap_or_else(|error| {
+                panic!("Failed to take element in StorageVec: {:?}", error)
+            });
+        self.set_len(last);
+        value
+    }
+
+    /// Returns the element at `index` from the contract storage.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    #[inline]
+    pub fn get(&self, index: u32) -> Option<V> {
+        if index >= self.len() {
+            return None
+        }
+        self.get_unchecked(index)
+    }
+
+    /// Replaces the element at `index` with the given `value`.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    #[inline]
+    pub fn set<R>(&mut self, index: u32, value: &R)
+    where
+        R: Storable + scale::EncodeLike<V>,
+    {
+        assert!(index < self.len(), "StorageVec index out of bounds");
+        ink_env::set_contract_storage(&(&KeyType::KEY, ELEMENT, index), value);
+    }
+
+    /// Removes the element at `index` and returns it.
+    ///
+    /// Returns `None` if `index` is out of bounds.
+    ///
+    /// # Note
+    ///
+    /// The last element takes the position of the removed element, so this does
+    /// not preserve the order of the elements but operates in constant time.
+    #[inline]
+    pub fn swap_remove(&mut self, index: u32) -> Option<V> {
+        let last = self.len().checked_sub(1)?;
+        if index > last {
+            return None
+        }
+        let last_value: V =
+            ink_env::take_contract_storage(&(&KeyType::KEY, ELEMENT, last))
+                .unwrap_or_else(|error| {
+                    panic!("Failed to take element in StorageVec: {:?}", error)
+                })
+                .expect("StorageVec has no gaps");
+        self.set_len(last);
+        if index == last {
+            return Some(last_value)
+        }
+        let removed = self.get_unchecked(index);
+        ink_env::set_contract_storage(&(&KeyType::KEY, ELEMENT, index), &last_value);
+        removed
+    }
+
+    /// Returns an iterator over the elements of the vector.
+    ///
+    /// Elements are loaded lazily from the contract storage.
+    pub fn iter(&self) -> Iter<'_, V, KeyType> {
+        Iter {
+            vec: self,
+            next: 0,
+            len: self.len(),
+        }
+    }
+
+    /// Returns the element at `index` without checking the bounds.
+    fn get_unchecked(&self, index: u32) -> Option<V> {
+        ink_env::get_contract_storage(&(&KeyType::KEY, ELEMENT, index)).unwrap_or_else(
+            |error| panic!("Failed to get element in StorageVec: {:?}", error),
+        )
+    }
+
+    /// Sets the number of elements in the vector.
+    fn set_len(&mut self, len: u32) {
+        ink_env::set_contract_storage(&(&KeyType::KEY, LEN), &len);
+    }
+}
+
+/// An iterator over the elements of a [`StorageVec`].
+pub struct Iter<'a, V: Packed, KeyType: StorageKey> {
+    vec: &'a StorageVec<V, KeyType>,
+    next: u32,
+    len: u32,
+}
+
+impl<'a, V, KeyType> Iterator for Iter<'a, V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    type Item = V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.len {
+            return None
+        }
+        let value = self.vec.get_unchecked(self.next);
+        self.next += 1;
+        value
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.len - self.next) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, V, KeyType> ExactSizeIterator for Iter<'a, V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+}
+
+impl<V, KeyType> Storable for StorageVec<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    #[inline]
+    fn encode<T: Output + ?Sized>(&self, _dest: &mut T) {}
+
+    #[inline]
+    fn decode<I: Input>(_input: &mut I) -> Result<Self, Error> {
+        Ok(Default::default())
+    }
+}
+
+impl<V, Key, InnerKey> StorableHint<Key> for StorageVec<V, InnerKey>
+where
+    V: Packed,
+    Key: StorageKey,
+    InnerKey: StorageKey,
+{
+    type Type = StorageVec<V, Key>;
+    type PreferredKey = InnerKey;
+}
+
+impl<V, KeyType> StorageKey for StorageVec<V, KeyType>
+where
+    V: Packed,
+    KeyType: StorageKey,
+{
+    const KEY: Key = KeyType::KEY;
+}
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::StorageLayout;
+    use ink_metadata::layout::{
+        Layout,
+        LayoutKey,
+        RootLayout,
+    };
+
+    impl<V, KeyType> StorageLayout for StorageVec<V, KeyType>
+    where
+        V: Packed + StorageLayout + scale_info::TypeInfo + 'static,
+        KeyType: StorageKey + scale_info::TypeInfo + 'static,
+    {
+        fn layout(_: &Key) -> Layout {
+            Layout::Root(RootLayout::new(
+                LayoutKey::from(&KeyType::KEY),
+                <V as StorageLayout>::layout(&KeyType::KEY),
+            ))
+        }
+    }
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::ManualKey;
+
+    #[test]
+    fn push_and_get_work() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut vec: StorageVec<u8> = StorageVec::new();
+            assert!(vec.is_empty());
+            vec.push(&1);
+            vec.push(&2);
+            assert_eq!(vec.len(), 2);
+            assert_eq!(vec.get(0), Some(1));
+            assert_eq!(vec.get(1), Some(2));
+            assert_eq!(vec.get(2), None);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn pop_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut vec: StorageVec<u8> = StorageVec::new();
+            assert_eq!(vec.pop(), None);
+            vec.push(&1);
+            vec.push(&2);
+            assert_eq!(vec.pop(), Some(2));
+            assert_eq!(vec.pop(), Some(1));
+            assert_eq!(vec.pop(), None);
+            assert!(vec.is_empty());
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn set_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut vec: StorageVec<u8> = StorageVec::new();
+            vec.push(&1);
+            vec.set(0, &3);
+            assert_eq!(vec.get(0), Some(3));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    #[should_panic(expected = "StorageVec index out of bounds")]
+    fn set_out_of_bounds_fails() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut vec: StorageVec<u8> = StorageVec::new();
+            vec.set(0, &3);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn swap_remove_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut vec: StorageVec<u8> = StorageVec::new();
+            for value in 0..4 {
+                vec.push(&value);
+            }
+            assert_eq!(vec.swap_remove(4), None);
+            assert_eq!(vec.swap_remove(1), Some(1));
+            assert_eq!(vec.iter().collect::<Vec<_>>(), vec![0, 3, 2]);
+            assert_eq!(vec.swap_remove(2), Some(2));
+            assert_eq!(vec.iter().collect::<Vec<_>>(), vec![0, 3]);
+            assert_eq!(vec.len(), 2);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn iter_works() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut vec: StorageVec<u8> = StorageVec::new();
+            for value in 0..5 {
+                vec.push(&(value * 10));
+            }
+            let mut iter = vec.iter();
+            assert_eq!(iter.len(), 5);
+            assert_eq!(iter.next(), Some(0));
+            assert_eq!(iter.len(), 4);
+            assert_eq!(iter.collect::<Vec<_>>(), vec![10, 20, 30, 40]);
+
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn vecs_with_different_keys_are_independent() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut vec: StorageVec<u8, ManualKey<123>> = StorageVec::new();
+            vec.push(&1);
+
+            let vec2: StorageVec<u8, ManualKey<124>> = StorageVec::new();
+            assert!(vec2.is_empty());
+
+            let vec3: StorageVec<u8, ManualKey<123>> = StorageVec::new();
+            assert_eq!(vec3.get(0), Some(1));
+
+            Ok(())
+        })
+        .unwrap()
+    }
+}
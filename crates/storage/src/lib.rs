@@ -52,8 +52,13 @@ pub use ink_storage_traits as traits;
 #[allow(dead_code)]
 pub(crate) mod lazy;
 
+pub mod migrate;
+pub mod prune;
+
 #[doc(inline)]
 pub use self::lazy::{
+    IterableMapping,
     Lazy,
     Mapping,
+    StorageVec,
 };
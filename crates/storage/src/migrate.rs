@@ -0,0 +1,257 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Versioned storage layouts and migrations between them.
+//!
+//! A contract opts into versioning by annotating its storage struct with
+//! `#[ink(storage, storage_version = N)]`. Constructors of such a contract record `N`
+//! as the stored version. Every message first compares the stored version against `N`
+//! and, if the stored version is older, runs the [`Migrate`] implementation of the
+//! storage struct once per version step before the storage is loaded.
+//!
+//! This makes migrations lazy: after upgrading the code of a contract via
+//! `set_code_hash` the storage is migrated by the first message that is called.
+//!
+//! # Note
+//!
+//! Contracts which were deployed before they used `storage_version` have no stored
+//! version. Their storage is considered to be at version `1`.
+//!
+//! # Example
+//!
+//! ```
+//! # #[ink::contract]
+//! # mod my_module {
+//! use ink::storage::{
+//!     migrate::{
+//!         migrate_value,
+//!         Migrate,
+//!     },
+//!     traits::StorageKey,
+//! };
+//!
+//! /// The storage layout of version `1` of this contract.
+//! #[derive(scale::Decode, scale::Encode)]
+//! pub struct V1Layout {
+//!     value: u32,
+//! }
+//!
+//! #[ink(storage, storage_version = 2)]
+//! pub struct MyContract {
+//!     value: u64,
+//!     owner: AccountId,
+//! }
+//!
+//! fn migrate_v1_to_v2(old: V1Layout) -> MyContract {
+//!     MyContract {
+//!         value: old.value as u64,
+//!         owner: AccountId::from([0x00; 32]),
+//!     }
+//! }
+//!
+//! impl Migrate for MyContract {
+//!     fn migrate(from_version: u32) {
+//!         match from_version {
+//!             1 => migrate_value(&<Self as StorageKey>::KEY, migrate_v1_to_v2),
+//!             _ => unreachable!("no migration from version {}", from_version),
+//!         }
+//!     }
+//! }
+//!
+//! impl MyContract {
+//!     #[ink(constructor)]
+//!     pub fn new() -> Self {
+//!         Self { value: 0, owner: Self::env().caller() }
+//!     }
+//!
+//!     #[ink(message)]
+//!     pub fn get(&self) -> u64 {
+//!         self.value
+//!     }
+//! }
+//! # }
+//! ```
+
+use ink_primitives::{
+    Key,
+    KeyComposer,
+};
+use ink_storage_traits::Storable;
+
+/// The storage key under which the storage version of the contract is stored.
+pub const STORAGE_VERSION_KEY: Key = KeyComposer::from_str("ink::storage_version");
+
+/// The version of a storage layout without a stored version.
+pub const DEFAULT_STORAGE_VERSION: u32 = 1;
+
+/// The version of the storage layout of a contract.
+///
+/// # Note
+///
+/// Implemented by the `#[ink::contract]` macro for storage structs annotated with
+/// `#[ink(storage_version = N)]`.
+pub trait StorageVersion {
+    /// The storage layout version the contract code expects.
+    const VERSION: u32;
+}
+
+/// Migrates the storage of a contract from one layout version to the next.
+///
+/// Must be implemented for every storage struct annotated with
+/// `#[ink(storage_version = N)]`.
+pub trait Migrate: StorageVersion {
+    /// Migrates the storage from `from_version` to `from_version + 1`.
+    ///
+    /// Called once per version step, in ascending order, until the storage
+    /// is at [`StorageVersion::VERSION`].
+    fn migrate(from_version: u32);
+}
+
+/// Returns the storage version stored in the contract storage.
+///
+/// Returns [`DEFAULT_STORAGE_VERSION`] if no version has been stored yet.
+pub fn stored_version() -> u32 {
+    match ink_env::get_contract_storage::<Key, u32>(&STORAGE_VERSION_KEY) {
+        Ok(Some(version)) => version,
+        Ok(None) => DEFAULT_STORAGE_VERSION,
+        Err(_) => panic!("could not properly decode the storage version"),
+    }
+}
+
+/// Stores `version` as the storage version of the contract.
+pub fn set_stored_version(version: u32) {
+    ink_env::set_contract_storage::<Key, u32>(&STORAGE_VERSION_KEY, &version);
+}
+
+/// Runs all migrations of `T` from the stored version up to [`StorageVersion::VERSION`].
+///
+/// Does nothing, apart from reading the stored version, if the storage is already
+/// up to date.
+///
+/// # Panics
+///
+/// If the stored version is newer than [`StorageVersion::VERSION`], which happens
+/// if the code of a contract is downgraded.
+pub fn migrate<T>()
+where
+    T: Migrate,
+{
+    let stored = stored_version();
+    if stored == T::VERSION {
+        return
+    }
+    assert!(
+        stored < T::VERSION,
+        "stored storage version {} is newer than the contract storage version {}",
+        stored,
+        T::VERSION,
+    );
+    for from_version in stored..T::VERSION {
+        T::migrate(from_version);
+    }
+    set_stored_version(T::VERSION);
+}
+
+/// Reads the value stored under `key` as `Old`, converts it and stores it back as `New`.
+///
+/// # Panics
+///
+/// If there is no value stored under `key` or it can not be decoded as `Old`.
+pub fn migrate_value<Old, New, F>(key: &Key, f: F)
+where
+    Old: Storable,
+    New: Storable,
+    F: FnOnce(Old) -> New,
+{
+    let old = match ink_env::get_contract_storage::<Key, Old>(key) {
+        Ok(Some(old)) => old,
+        Ok(None) => panic!("storage entry to migrate was empty"),
+        Err(_) => panic!("could not properly decode storage entry to migrate"),
+    };
+    ink_env::set_contract_storage::<Key, New>(key, &f(old));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROOT_KEY: Key = 42;
+
+    struct Contract;
+
+    impl StorageVersion for Contract {
+        const VERSION: u32 = 3;
+    }
+
+    impl Migrate for Contract {
+        fn migrate(from_version: u32) {
+            match from_version {
+                1 => migrate_value(&ROOT_KEY, |old: u8| old as u16 + 1),
+                2 => migrate_value(&ROOT_KEY, |old: u16| old as u32 * 10),
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    #[test]
+    fn stored_version_defaults_to_one() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            assert_eq!(stored_version(), DEFAULT_STORAGE_VERSION);
+            set_stored_version(5);
+            assert_eq!(stored_version(), 5);
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn migrate_runs_all_steps() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            ink_env::set_contract_storage(&ROOT_KEY, &1u8);
+            migrate::<Contract>();
+            assert_eq!(stored_version(), 3);
+            assert_eq!(ink_env::get_contract_storage(&ROOT_KEY), Ok(Some(20u32)));
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn migrate_resumes_from_stored_version() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            ink_env::set_contract_storage(&ROOT_KEY, &7u16);
+            set_stored_version(2);
+            migrate::<Contract>();
+            assert_eq!(ink_env::get_contract_storage(&ROOT_KEY), Ok(Some(70u32)));
+            // Already up to date, so this must not migrate again.
+            migrate::<Contract>();
+            assert_eq!(ink_env::get_contract_storage(&ROOT_KEY), Ok(Some(70u32)));
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "stored storage version 4 is newer than the contract storage version 3"
+    )]
+    fn migrate_from_newer_version_fails() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            set_stored_version(4);
+            migrate::<Contract>();
+            Ok(())
+        })
+        .unwrap()
+    }
+}
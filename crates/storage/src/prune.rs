@@ -0,0 +1,178 @@
+// Copyright 2018-2022 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Batched clearing of the entries of a [`Mapping`].
+//!
+//! Contracts shrinking their state, e.g. to refund storage deposits, often need to
+//! clear more entries than fit into the gas limit of a single call. [`clear_keys`]
+//! clears a bounded number of entries per call and returns a cursor to resume from
+//! in the next call.
+//!
+//! The contracts pallet stores the entries of a contract in a child trie under the
+//! hashes of their keys, so neither the contract nor the runtime can enumerate the
+//! entries of a mapping by a key prefix. The keys to clear must hence be known to
+//! the contract, e.g. because it keeps them in a [`StorageVec`](crate::StorageVec)
+//! or derives them from other state.
+//!
+//! # Example
+//!
+//! ```
+//! use ink::storage::{
+//!     prune::clear_keys,
+//!     Mapping,
+//! };
+//!
+//! # ink::env::test::run_test::<ink::env::DefaultEnvironment, _>(|_| {
+//! let mut balances = Mapping::<u32, u128>::new();
+//! let holders = [1, 2, 3, 4, 5];
+//! for holder in holders {
+//!     balances.insert(holder, &100);
+//! }
+//!
+//! let mut cursor = Some(0);
+//! while let Some(start) = cursor {
+//!     // In a contract, every call clears the next batch and stores the cursor.
+//!     let cleared = clear_keys(&balances, &holders, start, 2);
+//!     cursor = cleared.cursor();
+//! }
+//! assert!(holders.iter().all(|holder| !balances.contains(holder)));
+//! # Ok(())
+//! # }).unwrap();
+//! ```
+
+use crate::Mapping;
+use ink_storage_traits::{
+    Packed,
+    StorageKey,
+};
+
+/// The outcome of a call to [`clear_keys`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ClearedKeys {
+    removed: u32,
+    cursor: Option<u32>,
+}
+
+impl ClearedKeys {
+    /// Returns the number of entries removed by the call.
+    ///
+    /// Keys without an entry are skipped, so this can be less than the number of
+    /// keys processed by the call.
+    pub fn removed(&self) -> u32 {
+        self.removed
+    }
+
+    /// Returns the position of the first key not processed yet, from which the
+    /// next call resumes, or `None` if all keys have been processed.
+    pub fn cursor(&self) -> Option<u32> {
+        self.cursor
+    }
+
+    /// Returns `true` if all keys have been processed.
+    pub fn is_complete(&self) -> bool {
+        self.cursor.is_none()
+    }
+}
+
+/// Clears the entries of `mapping` at the `keys` from the position `cursor` on,
+/// processing at most `limit` keys.
+///
+/// Pass the cursor of the previous call as `cursor` to resume clearing where it
+/// stopped, or `0` to start from the first key.
+///
+/// # Panics
+///
+/// If `limit` is zero, as the clearing would never progress.
+pub fn clear_keys<K, V, KeyType, I>(
+    mapping: &Mapping<K, V, KeyType>,
+    keys: I,
+    cursor: u32,
+    limit: u32,
+) -> ClearedKeys
+where
+    K: scale::Encode,
+    V: Packed,
+    KeyType: StorageKey,
+    I: IntoIterator,
+    I::Item: scale::EncodeLike<K>,
+{
+    assert!(limit > 0, "encountered a limit of zero");
+    let mut keys = keys.into_iter().skip(cursor as usize).peekable();
+    let mut removed = 0;
+    let mut processed = 0;
+    for key in keys.by_ref().take(limit as usize) {
+        // Unlike `contains` and `remove`, this clears the entry with a single host
+        // function call.
+        if mapping.take(key).is_some() {
+            removed += 1;
+        }
+        processed += 1;
+    }
+    ClearedKeys {
+        removed,
+        cursor: keys.peek().is_some().then_some(cursor + processed),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ink_env::{
+        test,
+        DefaultEnvironment,
+    };
+
+    #[test]
+    fn clear_keys_resumes_from_cursor() {
+        test::run_test::<DefaultEnvironment, _>(|_| {
+            let mut mapping = Mapping::<u8, u32>::new();
+            for key in [1, 2, 4, 5, 7] {
+                mapping.insert(key, &u32::from(key));
+            }
+            let keys = [1u8, 2, 3, 4, 5, 6, 7];
+
+            let first = clear_keys(&mapping, keys, 0, 3);
+            assert_eq!((first.removed(), first.cursor()), (2, Some(3)));
+            let second = clear_keys(&mapping, keys, 3, 3);
+            assert_eq!((second.removed(), second.cursor()), (2, Some(6)));
+            let third = clear_keys(&mapping, keys, 6, 3);
+            assert_eq!((third.removed(), third.cursor()), (1, None));
+            assert!(third.is_complete());
+            assert!(keys.iter().all(|key| !mapping.contains(key)));
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn clear_keys_completes_at_last_key() {
+        test::run_test::<DefaultEnvironment, _>(|_| {
+            let mut mapping = Mapping::<u8, u32>::new();
+            mapping.insert(1, &1);
+            mapping.insert(2, &2);
+            let cleared = clear_keys(&mapping, [1u8, 2], 0, 2);
+            assert_eq!((cleared.removed(), cleared.cursor()), (2, None));
+            assert_eq!(mapping.get(3), None);
+            Ok(())
+        })
+        .unwrap()
+    }
+
+    #[test]
+    #[should_panic(expected = "encountered a limit of zero")]
+    fn clear_keys_fails_for_zero_limit() {
+        clear_keys(&Mapping::<u8, u32>::new(), [1u8], 0, 0);
+    }
+}
@@ -16,6 +16,7 @@ use crate::{
     Packed,
     StorageLayout,
 };
+use core::marker::PhantomData;
 use ink_metadata::layout::{
     ArrayLayout,
     Discriminant,
@@ -172,6 +173,13 @@ where
     }
 }
 
+impl<T> StorageLayout for PhantomData<T> {
+    fn layout(_key: &Key) -> Layout {
+        // Takes no space in the storage, e.g. markers of generic storage structs.
+        Layout::Struct(StructLayout::new("PhantomData", Vec::new()))
+    }
+}
+
 impl<T> StorageLayout for Option<T>
 where
     T: StorageLayout,
@@ -28,17 +28,11 @@ pub mod just_terminates {
     mod tests {
         use super::*;
 
-        #[ink::test]
+        #[ink::test(caller = "alice", callee_balance = 100)]
         fn terminating_works() {
             // given
             let accounts =
                 ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
-            let contract_id = ink::env::test::callee::<ink::env::DefaultEnvironment>();
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
-            ink::env::test::set_account_balance::<ink::env::DefaultEnvironment>(
-                contract_id,
-                100,
-            );
             let mut contract = JustTerminate::new();
 
             // when
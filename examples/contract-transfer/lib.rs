@@ -233,17 +233,13 @@ pub mod give_me {
                 .await
                 .expect("instantiate failed")
                 .account_id;
-            let balance_before: Balance = client
-                .balance(contract_acc_id.clone())
-                .await
-                .expect("getting balance failed");
 
             // when
             let transfer = contract_transfer::messages::give_me(120);
-            let call_res = client
-                .call(
+            let (call_res, accounting) = client
+                .call_with_accounting(
                     &mut ink_e2e::eve(),
-                    contract_acc_id.clone(),
+                    contract_acc_id,
                     transfer,
                     0,
                     None,
@@ -256,12 +252,10 @@ pub mod give_me {
                 String::from_utf8_lossy(&call_res.dry_run.debug_message)
                     .contains("requested value: 120\n");
             assert!(contains_debug_println);
-
-            let balance_after: Balance = client
-                .balance(contract_acc_id)
-                .await
-                .expect("getting balance failed");
-            assert_eq!(balance_before - balance_after, 120);
+            assert_eq!(
+                accounting.contract_balance,
+                ink_e2e::BalanceDelta::Decrease(120)
+            );
 
             Ok(())
         }
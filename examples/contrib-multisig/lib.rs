@@ -0,0 +1,408 @@
+//! A multisig wallet built on the [`ink::contrib::Multisig`] building block.
+//!
+//! Any owner can submit a request to call an arbitrary message of another
+//! contract. Once enough owners have confirmed the request anyone can execute
+//! it, which makes the call on behalf of the wallet and forwards the requested
+//! value from the wallet's balance.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract]
+pub mod contrib_multisig {
+    use ink::{
+        contrib::{
+            CallRequest,
+            Multisig,
+            MultisigError,
+            RequestId,
+        },
+        prelude::vec::Vec,
+    };
+
+    /// Emitted when an owner submits a call request.
+    #[ink(event)]
+    pub struct Submitted {
+        #[ink(topic)]
+        id: RequestId,
+        #[ink(topic)]
+        owner: AccountId,
+    }
+
+    /// Emitted when an owner confirms a call request.
+    #[ink(event)]
+    pub struct Confirmed {
+        #[ink(topic)]
+        id: RequestId,
+        #[ink(topic)]
+        owner: AccountId,
+        confirmations: u32,
+    }
+
+    /// Emitted when a call request has been executed.
+    #[ink(event)]
+    pub struct Executed {
+        #[ink(topic)]
+        id: RequestId,
+    }
+
+    #[ink(storage)]
+    pub struct ContribMultisig {
+        multisig: Multisig,
+    }
+
+    impl ContribMultisig {
+        /// Creates a new wallet with the given `owners`, which executes a call
+        /// request once `threshold` of them have confirmed it.
+        ///
+        /// # Panics
+        ///
+        /// If the owners contain duplicates or the threshold is not between one
+        /// and the number of owners.
+        #[ink(constructor)]
+        pub fn new(owners: Vec<AccountId>, threshold: u32) -> Self {
+            let multisig = Multisig::new(owners, threshold)
+                .unwrap_or_else(|error| panic!("invalid multisig: {:?}", error));
+            Self { multisig }
+        }
+
+        /// Submits a call request, which is confirmed by the caller right away.
+        #[ink(message)]
+        pub fn submit(
+            &mut self,
+            request: CallRequest,
+        ) -> Result<RequestId, MultisigError> {
+            let owner = self.env().caller();
+            let id = self.multisig.submit(owner, request)?;
+            self.env().emit_event(Submitted { id, owner });
+            self.env().emit_event(Confirmed {
+                id,
+                owner,
+                confirmations: 1,
+            });
+            Ok(id)
+        }
+
+        /// Confirms the call request with the given `id` on behalf of the caller.
+        #[ink(message)]
+        pub fn confirm(&mut self, id: RequestId) -> Result<u32, MultisigError> {
+            let owner = self.env().caller();
+            let confirmations = self.multisig.confirm(owner, id)?;
+            self.env().emit_event(Confirmed {
+                id,
+                owner,
+                confirmations,
+            });
+            Ok(confirmations)
+        }
+
+        /// Revokes the caller's confirmation of the call request with the given `id`.
+        #[ink(message)]
+        pub fn revoke(&mut self, id: RequestId) -> Result<u32, MultisigError> {
+            self.multisig.revoke(self.env().caller(), id)
+        }
+
+        /// Cancels the call request with the given `id`.
+        #[ink(message)]
+        pub fn cancel(&mut self, id: RequestId) -> Result<(), MultisigError> {
+            self.multisig.cancel(self.env().caller(), id)
+        }
+
+        /// Executes the call request with the given `id`.
+        ///
+        /// # Note
+        ///
+        /// The message is payable, so that the value forwarded by the request
+        /// can be paid as part of the execution.
+        #[ink(message, payable)]
+        pub fn execute(&mut self, id: RequestId) -> Result<(), MultisigError> {
+            self.multisig.execute(id)?;
+            self.env().emit_event(Executed { id });
+            Ok(())
+        }
+
+        /// Returns the owners of the wallet.
+        #[ink(message)]
+        pub fn owners(&self) -> Vec<AccountId> {
+            self.multisig.owners().to_vec()
+        }
+
+        /// Returns the number of confirmations required to execute a call request.
+        #[ink(message)]
+        pub fn threshold(&self) -> u32 {
+            self.multisig.threshold()
+        }
+
+        /// Returns the pending call request with the given `id`, if any.
+        #[ink(message)]
+        pub fn request(&self, id: RequestId) -> Option<CallRequest> {
+            self.multisig.request(id)
+        }
+
+        /// Returns the number of confirmations of the call request with the given `id`.
+        #[ink(message)]
+        pub fn confirmations(&self, id: RequestId) -> u32 {
+            self.multisig.confirmations(id)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::{
+            call::{
+                ExecutionInput,
+                Selector,
+            },
+            test::{
+                default_accounts,
+                set_caller,
+            },
+            DefaultEnvironment,
+        };
+
+        fn flip_request() -> CallRequest {
+            let callee = AccountId::from([0xFF; 32]);
+            CallRequest::new(
+                callee,
+                ExecutionInput::new(Selector::new(ink::selector_bytes!("flip"))),
+            )
+        }
+
+        #[ink::test]
+        fn execution_requires_threshold_confirmations() {
+            let accounts = default_accounts::<DefaultEnvironment>();
+            let mut wallet = ContribMultisig::new(
+                vec![accounts.alice, accounts.bob, accounts.charlie],
+                2,
+            );
+
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            let id = wallet.submit(flip_request()).expect("alice is an owner");
+            assert_eq!(wallet.confirmations(id), 1);
+            assert_eq!(
+                wallet.execute(id),
+                Err(MultisigError::NotEnoughConfirmations)
+            );
+
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(wallet.confirm(id), Ok(2));
+            assert_eq!(wallet.revoke(id), Ok(1));
+            assert_eq!(
+                wallet.execute(id),
+                Err(MultisigError::NotEnoughConfirmations)
+            );
+            assert_eq!(ink::env::test::recorded_events().count(), 3);
+        }
+
+        #[ink::test]
+        fn only_owners_can_submit() {
+            let accounts = default_accounts::<DefaultEnvironment>();
+            let mut wallet = ContribMultisig::new(vec![accounts.alice, accounts.bob], 1);
+
+            set_caller::<DefaultEnvironment>(accounts.eve);
+            assert_eq!(wallet.submit(flip_request()), Err(MultisigError::NotOwner));
+            assert_eq!(wallet.request(0), None);
+        }
+    }
+
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use contrib_multisig::contract_types::ink::contrib::multisig::{
+            CallRequest as E2ECallRequest,
+            MultisigError as E2EMultisigError,
+        };
+
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+        fn e2e_account_id<A: AsRef<[u8]>>(account: &A) -> ink::primitives::AccountId {
+            ink::primitives::AccountId::try_from(account.as_ref()).unwrap()
+        }
+
+        #[ink_e2e::test(
+            additional_contracts = "../lang-err-integration-tests/integration-flipper/Cargo.toml"
+        )]
+        async fn e2e_execution_requires_threshold_confirmations(
+            mut client: ink_e2e::Client<C, E>,
+        ) -> E2EResult<()> {
+            // given
+            let flipper_constructor = integration_flipper::constructors::default();
+            let flipper_acc_id = client
+                .instantiate(&mut ink_e2e::alice(), flipper_constructor, 0, None)
+                .await
+                .expect("instantiate `flipper` failed")
+                .account_id;
+
+            let owners = vec![
+                e2e_account_id(ink_e2e::alice().account_id()),
+                e2e_account_id(ink_e2e::bob().account_id()),
+                e2e_account_id(ink_e2e::charlie().account_id()),
+            ];
+            let constructor = contrib_multisig::constructors::new(owners, 2);
+            let contract_acc_id = client
+                .instantiate(&mut ink_e2e::alice(), constructor, 0, None)
+                .await
+                .expect("instantiate failed")
+                .account_id;
+
+            let request = E2ECallRequest {
+                callee: e2e_account_id(&flipper_acc_id),
+                selector: ink::selector_bytes!("flip"),
+                input: Vec::new(),
+                transferred_value: 0,
+                gas_limit: 0,
+                allow_reentry: false,
+            };
+            let id = client
+                .call(
+                    &mut ink_e2e::alice(),
+                    contract_acc_id.clone(),
+                    contrib_multisig::messages::submit(request),
+                    0,
+                    None,
+                )
+                .await
+                .expect("calling `submit` failed")
+                .value
+                .expect("input is valid, call must not fail")
+                .expect("alice is an owner");
+
+            // when
+            let execute_result = client
+                .call(
+                    &mut ink_e2e::charlie(),
+                    contract_acc_id.clone(),
+                    contrib_multisig::messages::execute(id),
+                    0,
+                    None,
+                )
+                .await
+                .expect("calling `execute` failed")
+                .value
+                .expect("input is valid, call must not fail");
+
+            // then
+            assert!(matches!(
+                execute_result,
+                Err(E2EMultisigError::NotEnoughConfirmations)
+            ));
+
+            // when
+            let confirmations = client
+                .call(
+                    &mut ink_e2e::bob(),
+                    contract_acc_id.clone(),
+                    contrib_multisig::messages::confirm(id),
+                    0,
+                    None,
+                )
+                .await
+                .expect("calling `confirm` failed")
+                .value
+                .expect("input is valid, call must not fail");
+            let execute_result = client
+                .call(
+                    &mut ink_e2e::charlie(),
+                    contract_acc_id.clone(),
+                    contrib_multisig::messages::execute(id),
+                    0,
+                    None,
+                )
+                .await
+                .expect("calling `execute` failed")
+                .value
+                .expect("input is valid, call must not fail");
+
+            // then
+            assert!(matches!(confirmations, Ok(2)));
+            assert!(matches!(execute_result, Ok(())));
+
+            let flipped_value = client
+                .call(
+                    &mut ink_e2e::alice(),
+                    flipper_acc_id.clone(),
+                    integration_flipper::messages::get(),
+                    0,
+                    None,
+                )
+                .await
+                .expect("calling `flipper::get` failed")
+                .value
+                .expect("input is valid, call must not fail");
+            assert!(flipped_value);
+
+            Ok(())
+        }
+
+        #[ink_e2e::test(additional_contracts = "../contract-transfer/Cargo.toml")]
+        async fn e2e_execution_forwards_value(
+            mut client: ink_e2e::Client<C, E>,
+        ) -> E2EResult<()> {
+            // given
+            let transfer_constructor = contract_transfer::constructors::new();
+            let transfer_acc_id = client
+                .instantiate(&mut ink_e2e::dave(), transfer_constructor, 0, None)
+                .await
+                .expect("instantiate `contract_transfer` failed")
+                .account_id;
+
+            let owners = vec![e2e_account_id(ink_e2e::dave().account_id())];
+            let constructor = contrib_multisig::constructors::new(owners, 1);
+            let contract_acc_id = client
+                .instantiate(&mut ink_e2e::dave(), constructor, 0, None)
+                .await
+                .expect("instantiate failed")
+                .account_id;
+
+            let request = E2ECallRequest {
+                callee: e2e_account_id(&transfer_acc_id),
+                selector: [0xCA, 0xFE, 0xBA, 0xBE],
+                input: Vec::new(),
+                transferred_value: 10,
+                gas_limit: 0,
+                allow_reentry: false,
+            };
+            let id = client
+                .call(
+                    &mut ink_e2e::dave(),
+                    contract_acc_id.clone(),
+                    contrib_multisig::messages::submit(request),
+                    0,
+                    None,
+                )
+                .await
+                .expect("calling `submit` failed")
+                .value
+                .expect("input is valid, call must not fail")
+                .expect("dave is an owner");
+            let balance_before = client
+                .balance(transfer_acc_id.clone())
+                .await
+                .expect("getting balance failed");
+
+            // when
+            let execute_result = client
+                .call(
+                    &mut ink_e2e::eve(),
+                    contract_acc_id.clone(),
+                    contrib_multisig::messages::execute(id),
+                    10,
+                    None,
+                )
+                .await
+                .expect("calling `execute` failed")
+                .value
+                .expect("input is valid, call must not fail");
+
+            // then
+            assert!(matches!(execute_result, Ok(())));
+            let balance_after = client
+                .balance(transfer_acc_id)
+                .await
+                .expect("getting balance failed");
+            assert_eq!(balance_after, balance_before + 10);
+
+            Ok(())
+        }
+    }
+}
@@ -125,6 +125,11 @@ mod delegator {
 
     #[cfg(all(test, feature = "e2e-tests"))]
     mod e2e_tests {
+        use super::{
+            Delegator,
+            Which,
+        };
+
         type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
         #[ink_e2e::test(
@@ -169,6 +174,10 @@ mod delegator {
                 .await
                 .expect("instantiate failed")
                 .account_id;
+            let delegator = client
+                .read_storage::<Delegator>(delegator_acc_id.clone())
+                .await;
+            assert_eq!(delegator.which, Which::Adder);
 
             // when
             let value = client
@@ -221,6 +230,10 @@ mod delegator {
                 )
                 .await
                 .expect("calling `switch` failed");
+            let delegator = client
+                .read_storage::<Delegator>(delegator_acc_id.clone())
+                .await;
+            assert_eq!(delegator.which, Which::Subber);
             let _ = client
                 .call(
                     &mut ink_e2e::bob(),
@@ -0,0 +1,313 @@
+//! # ERC-20 Wrapper for PSP22 Tokens
+//!
+//! This contract wraps a PSP22 token into an ERC-20 compatible token which can be
+//! used by EVM tooling on chains bridging `pallet-contracts` and Frontier.
+//!
+//! The contract uses `#[ink::contract(abi = "solidity")]`, so its messages take
+//! their inputs and return their outputs ABI encoded, and their selectors are
+//! derived from their Solidity style signatures, e.g. `balanceOf(address)`.
+//!
+//! ERC-20 amounts are `uint256` while ink! balances are `u128`, so the messages
+//! taking amounts use the selectors of the standard ERC-20 signatures explicitly.
+//! Their ABI encoding is the same, amounts exceeding `u128` are rejected.
+//!
+//! EVM addresses are mapped to accounts the same way Frontier's
+//! `HashedAddressMapping` does, see `account_of`.
+//!
+//! ## Usage
+//!
+//! - Holders of the PSP22 token approve the wrapper to spend their tokens and call
+//!   `depositFor` in order to receive the same amount of wrapped tokens.
+//! - Wrapped tokens are transferred just like any other ERC-20 token.
+//! - Holders of wrapped tokens call `withdrawTo` in order to burn them and receive
+//!   the same amount of PSP22 tokens.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[ink::contract(abi = "solidity")]
+mod erc20_psp22_wrapper {
+    use ink::{
+        env::{
+            call::{
+                build_call,
+                Call,
+                ExecutionInput,
+                Selector,
+            },
+            hash::{
+                Blake2x256,
+                HashOutput,
+            },
+        },
+        prelude::vec::Vec,
+        sol::Address,
+        standards::psp22::{
+            PSP22Data,
+            PSP22Error,
+        },
+    };
+
+    /// Returns the account of the EVM `address`.
+    ///
+    /// This is the BLAKE2 256-bit hash of `"evm:"` followed by the address.
+    pub fn account_of(address: Address) -> AccountId {
+        let mut input = b"evm:".to_vec();
+        input.extend_from_slice(address.as_ref());
+        let mut output = <Blake2x256 as HashOutput>::Type::default();
+        ink::env::hash_bytes::<Blake2x256>(&input, &mut output);
+        AccountId::from(output)
+    }
+
+    /// An ERC-20 token backed by the tokens of a PSP22 token.
+    #[ink(storage)]
+    pub struct Erc20Wrapper {
+        /// The wrapped PSP22 token.
+        underlying: AccountId,
+        /// The balances and allowances of the wrapped tokens.
+        data: PSP22Data,
+    }
+
+    impl Erc20Wrapper {
+        /// Creates a wrapper for the PSP22 token at `underlying`.
+        #[ink(constructor)]
+        pub fn new(underlying: AccountId) -> Self {
+            Self {
+                underlying,
+                data: Default::default(),
+            }
+        }
+
+        /// Returns the wrapped PSP22 token.
+        ///
+        /// `underlying()`
+        #[ink(message)]
+        pub fn underlying(&self) -> AccountId {
+            self.underlying
+        }
+
+        /// Returns the total supply of wrapped tokens.
+        ///
+        /// `totalSupply()`
+        #[ink(message)]
+        pub fn total_supply(&self) -> Balance {
+            self.data.total_supply()
+        }
+
+        /// Returns the balance of wrapped tokens of `owner`.
+        ///
+        /// `balanceOf(address)`
+        #[ink(message)]
+        pub fn balance_of(&self, owner: Address) -> Balance {
+            self.data.balance_of(account_of(owner))
+        }
+
+        /// Returns the amount which `spender` is still allowed to withdraw from `owner`.
+        ///
+        /// `allowance(address,address)`
+        #[ink(message)]
+        pub fn allowance(&self, owner: Address, spender: Address) -> Balance {
+            self.data.allowance(account_of(owner), account_of(spender))
+        }
+
+        /// Transfers `value` wrapped tokens from the caller to `to`.
+        ///
+        /// `transfer(address,uint256)`
+        #[ink(message, selector = 0xA9059CBB)]
+        pub fn transfer(&mut self, to: Address, value: Balance) -> bool {
+            let from = self.env().caller();
+            self.data.transfer(from, account_of(to), value).is_ok()
+        }
+
+        /// Allows `spender` to withdraw up to `value` wrapped tokens from the caller.
+        ///
+        /// `approve(address,uint256)`
+        #[ink(message, selector = 0x095EA7B3)]
+        pub fn approve(&mut self, spender: Address, value: Balance) -> bool {
+            let owner = self.env().caller();
+            self.data.approve(owner, account_of(spender), value);
+            true
+        }
+
+        /// Transfers `value` wrapped tokens from `from` to `to` on behalf of the caller.
+        ///
+        /// `transferFrom(address,address,uint256)`
+        #[ink(message, selector = 0x23B872DD)]
+        pub fn transfer_from(
+            &mut self,
+            from: Address,
+            to: Address,
+            value: Balance,
+        ) -> bool {
+            let spender = self.env().caller();
+            self.data
+                .transfer_from(spender, account_of(from), account_of(to), value)
+                .is_ok()
+        }
+
+        /// Deposits `value` PSP22 tokens of the caller and mints the same amount of
+        /// wrapped tokens to `account`.
+        ///
+        /// The caller must have approved the wrapper to spend the PSP22 tokens.
+        ///
+        /// `depositFor(address,uint256)`
+        #[ink(message, selector = 0x2F4F21E2)]
+        pub fn deposit_for(&mut self, account: Address, value: Balance) -> bool {
+            let caller = self.env().caller();
+            let wrapper = self.env().account_id();
+            let deposited = build_call::<Environment>()
+                .call_type(Call::new().callee(self.underlying))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                        "PSP22::transfer_from"
+                    )))
+                    .push_arg(caller)
+                    .push_arg(wrapper)
+                    .push_arg(value)
+                    .push_arg(Vec::<u8>::new()),
+                )
+                .returns::<ink::MessageResult<Result<(), PSP22Error>>>()
+                .fire();
+            matches!(deposited, Ok(Ok(Ok(()))))
+                && self.data.mint(account_of(account), value).is_ok()
+        }
+
+        /// Burns `value` wrapped tokens of the caller and transfers the same amount
+        /// of PSP22 tokens to `account`.
+        ///
+        /// `withdrawTo(address,uint256)`
+        #[ink(message, selector = 0x205C2878)]
+        pub fn withdraw_to(&mut self, account: Address, value: Balance) -> bool {
+            let caller = self.env().caller();
+            if self.data.burn(caller, value).is_err() {
+                return false
+            }
+            let withdrawn = build_call::<Environment>()
+                .call_type(Call::new().callee(self.underlying))
+                .exec_input(
+                    ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                        "PSP22::transfer"
+                    )))
+                    .push_arg(account_of(account))
+                    .push_arg(value)
+                    .push_arg(Vec::<u8>::new()),
+                )
+                .returns::<ink::MessageResult<Result<(), PSP22Error>>>()
+                .fire();
+            if !matches!(withdrawn, Ok(Ok(Ok(())))) {
+                // Reverts burning the wrapped tokens.
+                ink::env::return_value(
+                    ink::env::ReturnFlags::new_with_reverted(true),
+                    &ink::sol::SolEncoded((false,)),
+                )
+            }
+            true
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::sol::SolEncoded;
+        use std::cell::RefCell;
+
+        type DefaultEnvironment = ink::env::DefaultEnvironment;
+
+        const UNDERLYING: [u8; 32] = [0xAA; 32];
+        const WRAPPER: [u8; 32] = [0xBB; 32];
+
+        thread_local! {
+            static PSP22_CALLS: RefCell<Vec<[u8; 4]>> = const { RefCell::new(Vec::new()) };
+        }
+
+        /// A PSP22 token recording the selectors of its calls and accepting all of them.
+        fn psp22(input: &[u8]) {
+            PSP22_CALLS
+                .with(|calls| calls.borrow_mut().push(input[..4].try_into().unwrap()));
+            ink::env::return_value(
+                ink::env::ReturnFlags::default(),
+                &ink::MessageResult::<Result<(), PSP22Error>>::Ok(Ok(())),
+            )
+        }
+
+        fn alice() -> Address {
+            Address::from([0x01; 20])
+        }
+
+        fn bob() -> Address {
+            Address::from([0x02; 20])
+        }
+
+        fn set_caller(address: Address) {
+            ink::env::test::set_caller::<DefaultEnvironment>(account_of(address));
+        }
+
+        #[ink::test]
+        fn wrapping_works() {
+            ink::env::test::register_contract::<DefaultEnvironment>(
+                UNDERLYING.into(),
+                psp22,
+            );
+            let mut wrapper = Erc20Wrapper::new(UNDERLYING.into());
+
+            set_caller(alice());
+            assert!(wrapper.deposit_for(alice(), 100));
+            assert_eq!(wrapper.total_supply(), 100);
+            assert!(wrapper.transfer(bob(), 30));
+            assert!(!wrapper.transfer(bob(), 71));
+            assert!(wrapper.approve(bob(), 20));
+
+            set_caller(bob());
+            assert!(wrapper.transfer_from(alice(), bob(), 20));
+            assert!(!wrapper.transfer_from(alice(), bob(), 1));
+            assert!(wrapper.withdraw_to(bob(), 50));
+
+            assert_eq!(wrapper.balance_of(alice()), 50);
+            assert_eq!(wrapper.balance_of(bob()), 0);
+            assert_eq!(wrapper.allowance(alice(), bob()), 0);
+            assert_eq!(wrapper.total_supply(), 50);
+            assert_eq!(
+                PSP22_CALLS.with(|calls| calls.take()),
+                [
+                    ink::selector_bytes!("PSP22::transfer_from"),
+                    ink::selector_bytes!("PSP22::transfer"),
+                ]
+            );
+        }
+
+        #[ink::test]
+        fn abi_encoded_calls_work() {
+            ink::env::test::register_contract::<DefaultEnvironment>(
+                UNDERLYING.into(),
+                psp22,
+            );
+            ink::off_chain::register_contract::<Erc20Wrapper, _>(WRAPPER.into(), || {
+                Erc20Wrapper::new(UNDERLYING.into())
+            });
+            // The calls below are made by Alice.
+            ink::env::test::set_callee::<DefaultEnvironment>(account_of(alice()));
+            ink::env::test::set_account_balance::<DefaultEnvironment>(
+                account_of(alice()),
+                1_000,
+            );
+
+            // Calls the wrapper like EVM tooling would.
+            let deposited = build_call::<DefaultEnvironment>()
+                .call_type(Call::new().callee(WRAPPER.into()))
+                .exec_input(
+                    ExecutionInput::new(Selector::new([0x2F, 0x4F, 0x21, 0xE2]))
+                        .push_arg(SolEncoded((alice(), 100_u128))),
+                )
+                .returns::<SolEncoded<(bool,)>>()
+                .fire();
+            assert_eq!(deposited, Ok(SolEncoded((true,))));
+
+            // Calls the wrapper via its contract reference.
+            let mut wrapper: Erc20WrapperRef =
+                ink::env::call::FromAccountId::from_account_id(WRAPPER.into());
+            assert!(wrapper.transfer(bob(), 40));
+            assert_eq!(wrapper.balance_of(alice()), 60);
+            assert_eq!(wrapper.balance_of(bob()), 40);
+            assert_eq!(wrapper.underlying(), AccountId::from(UNDERLYING));
+        }
+    }
+}
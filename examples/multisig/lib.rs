@@ -260,7 +260,9 @@ mod multisig {
         /// when an owner is removed.
         transaction_list: Transactions,
         /// The list is a vector because iterating over it is necessary when cleaning
-        /// up the confirmation set.
+        /// up the confirmation set. It is packed since the number of owners is
+        /// bounded by `MAX_OWNERS`.
+        #[ink(bounded)]
         owners: Vec<AccountId>,
         /// Redundant information to speed up the check whether a caller is an owner.
         is_owner: Mapping<AccountId, ()>,